@@ -93,6 +93,12 @@ client_request_definitions! {
         params: ListConversationsParams,
         response: ListConversationsResponse,
     },
+    /// Full-text search over the messages, commands and patched files of
+    /// recorded Codex conversations.
+    SearchConversations {
+        params: SearchConversationsParams,
+        response: SearchConversationsResponse,
+    },
     /// Resume a recorded Codex conversation from a rollout file.
     ResumeConversation {
         params: ResumeConversationParams,
@@ -289,6 +295,64 @@ pub struct ListConversationsResponse {
     pub next_cursor: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchConversationsParams {
+    /// Words to look for. A conversation matches when every word starts a
+    /// word somewhere in it, ignoring ASCII case.
+    pub query: String,
+    /// Optional maximum number of results; defaults to a reasonable server-side value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub enum ConversationSearchSnippetKind {
+    UserMessage,
+    AgentMessage,
+    Command,
+    PatchedFile,
+}
+
+/// Character offsets `[start, end)` into a snippet's `text`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, TS)]
+pub struct TextRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversationSearchSnippet {
+    pub kind: ConversationSearchSnippetKind,
+    pub text: String,
+    /// Parts of `text` that matched the query, in order.
+    pub highlights: Vec<TextRange>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversationSearchResult {
+    pub conversation_id: ConversationId,
+    pub path: PathBuf,
+    /// RFC3339 timestamp string for the session start, if available.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+    /// RFC3339 timestamp string for the last recorded item, if available.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+    pub snippets: Vec<ConversationSearchSnippet>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchConversationsResponse {
+    /// Matching conversations, most recently updated first.
+    pub items: Vec<ConversationSearchResult>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
 #[serde(rename_all = "camelCase")]
 pub struct ResumeConversationParams {
@@ -525,6 +589,8 @@ pub struct SandboxSettings {
     pub writable_roots: Vec<PathBuf>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub network_access: Option<bool>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub network_allowlist: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub exclude_tmpdir_env_var: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -11,6 +11,9 @@ use codex_app_server_protocol::ArchiveConversationParams;
 use codex_app_server_protocol::ArchiveConversationResponse;
 use codex_app_server_protocol::AuthStatusChangeNotification;
 use codex_app_server_protocol::ClientRequest;
+use codex_app_server_protocol::ConversationSearchResult;
+use codex_app_server_protocol::ConversationSearchSnippet;
+use codex_app_server_protocol::ConversationSearchSnippetKind;
 use codex_app_server_protocol::ConversationSummary;
 use codex_app_server_protocol::ExecCommandApprovalParams;
 use codex_app_server_protocol::ExecCommandApprovalResponse;
@@ -38,6 +41,8 @@ use codex_app_server_protocol::RemoveConversationSubscriptionResponse;
 use codex_app_server_protocol::RequestId;
 use codex_app_server_protocol::Result as JsonRpcResult;
 use codex_app_server_protocol::ResumeConversationParams;
+use codex_app_server_protocol::SearchConversationsParams;
+use codex_app_server_protocol::SearchConversationsResponse;
 use codex_app_server_protocol::SendUserMessageParams;
 use codex_app_server_protocol::SendUserMessageResponse;
 use codex_app_server_protocol::SendUserTurnParams;
@@ -47,6 +52,7 @@ use codex_app_server_protocol::ServerRequestPayload;
 use codex_app_server_protocol::SessionConfiguredNotification;
 use codex_app_server_protocol::SetDefaultModelParams;
 use codex_app_server_protocol::SetDefaultModelResponse;
+use codex_app_server_protocol::TextRange;
 use codex_app_server_protocol::UserInfoResponse;
 use codex_app_server_protocol::UserSavedConfig;
 use codex_core::AuthManager;
@@ -56,7 +62,10 @@ use codex_core::Cursor as RolloutCursor;
 use codex_core::INTERACTIVE_SESSION_SOURCES;
 use codex_core::NewConversation;
 use codex_core::RolloutRecorder;
+use codex_core::SearchHitKind;
 use codex_core::SessionMeta;
+use codex_core::SessionSearchHit;
+use codex_core::SessionSearchIndex;
 use codex_core::auth::CLIENT_ID;
 use codex_core::auth::get_auth_file;
 use codex_core::auth::login_with_api_key;
@@ -74,6 +83,7 @@ use codex_core::exec_env::create_env;
 use codex_core::get_platform_sandbox;
 use codex_core::git_info::git_diff_to_remote;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
+use codex_core::protocol::ElicitationAction;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::ExecApprovalRequestEvent;
@@ -130,6 +140,8 @@ pub(crate) struct CodexMessageProcessor {
     // Queue of pending interrupt requests per conversation. We reply when TurnAborted arrives.
     pending_interrupts: Arc<Mutex<HashMap<ConversationId, Vec<RequestId>>>>,
     pending_fuzzy_searches: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
+    // Loaded on the first conversation search and refreshed before each one.
+    search_index: Mutex<Option<SessionSearchIndex>>,
 }
 
 impl CodexMessageProcessor {
@@ -150,6 +162,7 @@ impl CodexMessageProcessor {
             active_login: Arc::new(Mutex::new(None)),
             pending_interrupts: Arc::new(Mutex::new(HashMap::new())),
             pending_fuzzy_searches: Arc::new(Mutex::new(HashMap::new())),
+            search_index: Mutex::new(None),
         }
     }
 
@@ -167,6 +180,9 @@ impl CodexMessageProcessor {
             ClientRequest::ListConversations { request_id, params } => {
                 self.handle_list_conversations(request_id, params).await;
             }
+            ClientRequest::SearchConversations { request_id, params } => {
+                self.handle_search_conversations(request_id, params).await;
+            }
             ClientRequest::ResumeConversation { request_id, params } => {
                 self.handle_resume_conversation(request_id, params).await;
             }
@@ -617,6 +633,7 @@ impl CodexMessageProcessor {
         };
         tracing::debug!("Sandbox type: {sandbox_type:?}");
         let codex_linux_sandbox_exe = self.config.codex_linux_sandbox_exe.clone();
+        let sandbox_limits = self.config.sandbox_limits;
         let outgoing = self.outgoing.clone();
         let req_id = request_id;
         let sandbox_cwd = self.config.cwd.clone();
@@ -626,6 +643,7 @@ impl CodexMessageProcessor {
                 exec_params,
                 sandbox_type,
                 &effective_policy,
+                &sandbox_limits,
                 sandbox_cwd.as_path(),
                 &codex_linux_sandbox_exe,
                 None,
@@ -745,6 +763,43 @@ impl CodexMessageProcessor {
         self.outgoing.send_response(request_id, response).await;
     }
 
+    async fn handle_search_conversations(
+        &self,
+        request_id: RequestId,
+        params: SearchConversationsParams,
+    ) {
+        let mut search_index = self.search_index.lock().await;
+        let codex_home = &self.config.codex_home;
+        let index = match search_index.take() {
+            Some(index) => index.refresh(codex_home).await,
+            None => SessionSearchIndex::load(codex_home).await,
+        };
+        let index = match index {
+            Ok(index) => search_index.insert(index),
+            Err(err) => {
+                let error = JSONRPCErrorError {
+                    code: INTERNAL_ERROR_CODE,
+                    message: format!("failed to search conversations: {err}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        let items = index
+            .search(
+                &params.query,
+                params.limit.unwrap_or(25),
+                INTERACTIVE_SESSION_SOURCES,
+            )
+            .into_iter()
+            .filter_map(conversation_search_result)
+            .collect();
+        let response = SearchConversationsResponse { items };
+        self.outgoing.send_response(request_id, response).await;
+    }
+
     async fn handle_resume_conversation(
         &self,
         request_id: RequestId,
@@ -1301,6 +1356,31 @@ async fn apply_bespoke_event_handling(
                 on_exec_approval_response(event_id, rx, conversation).await;
             });
         }
+        // Clients cannot answer MCP server callbacks yet: refuse them rather
+        // than leave the requesting server waiting.
+        EventMsg::McpSamplingRequest(_) => {
+            if let Err(err) = conversation
+                .submit(Op::McpSamplingApproval {
+                    id: event_id,
+                    decision: ReviewDecision::Denied,
+                })
+                .await
+            {
+                error!("failed to deny MCP sampling request: {err}");
+            }
+        }
+        EventMsg::ElicitationRequest(_) => {
+            if let Err(err) = conversation
+                .submit(Op::ResolveElicitation {
+                    id: event_id,
+                    action: ElicitationAction::Cancel,
+                    content: None,
+                })
+                .await
+            {
+                error!("failed to cancel MCP elicitation request: {err}");
+            }
+        }
         // If this is a TurnAborted, reply to any pending interrupt requests.
         EventMsg::TurnAborted(turn_aborted_event) => {
             let pending = {
@@ -1486,6 +1566,44 @@ fn extract_conversation_summary(
     })
 }
 
+fn conversation_search_result(hit: SessionSearchHit) -> Option<ConversationSearchResult> {
+    let snippets = hit
+        .snippets
+        .into_iter()
+        .map(|snippet| {
+            // Clients index strings by character, not by UTF-8 byte.
+            let char_offset = |byte: usize| snippet.text[..byte].chars().count() as u32;
+            let highlights = snippet
+                .highlights
+                .iter()
+                .map(|range| TextRange {
+                    start: char_offset(range.start),
+                    end: char_offset(range.end),
+                })
+                .collect();
+            let kind = match snippet.kind {
+                SearchHitKind::UserMessage => ConversationSearchSnippetKind::UserMessage,
+                SearchHitKind::AgentMessage => ConversationSearchSnippetKind::AgentMessage,
+                SearchHitKind::Command => ConversationSearchSnippetKind::Command,
+                SearchHitKind::PatchedFile => ConversationSearchSnippetKind::PatchedFile,
+            };
+            ConversationSearchSnippet {
+                kind,
+                text: snippet.text,
+                highlights,
+            }
+        })
+        .collect();
+
+    Some(ConversationSearchResult {
+        conversation_id: hit.conversation_id?,
+        path: hit.path,
+        timestamp: hit.created_at,
+        updated_at: hit.updated_at,
+        snippets,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3,6 +3,7 @@ use std::path::Path;
 
 use app_test_support::McpProcess;
 use app_test_support::to_response;
+use codex_app_server_protocol::ConversationSearchSnippetKind;
 use codex_app_server_protocol::JSONRPCNotification;
 use codex_app_server_protocol::JSONRPCResponse;
 use codex_app_server_protocol::ListConversationsParams;
@@ -11,8 +12,11 @@ use codex_app_server_protocol::NewConversationParams; // reused for overrides sh
 use codex_app_server_protocol::RequestId;
 use codex_app_server_protocol::ResumeConversationParams;
 use codex_app_server_protocol::ResumeConversationResponse;
+use codex_app_server_protocol::SearchConversationsParams;
+use codex_app_server_protocol::SearchConversationsResponse;
 use codex_app_server_protocol::ServerNotification;
 use codex_app_server_protocol::SessionConfiguredNotification;
+use codex_app_server_protocol::TextRange;
 use pretty_assertions::assert_eq;
 use serde_json::json;
 use tempfile::TempDir;
@@ -152,6 +156,93 @@ async fn test_list_and_resume_conversations() {
     assert!(!conversation_id.to_string().is_empty());
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_search_conversations_highlights_matches() {
+    let codex_home = TempDir::new().expect("create temp dir");
+    create_fake_rollout(
+        codex_home.path(),
+        "2025-01-02T12-00-00",
+        "2025-01-02T12:00:00Z",
+        "Fix the flaky résumé parser",
+    );
+    create_fake_rollout(
+        codex_home.path(),
+        "2025-01-01T12-00-00",
+        "2025-01-01T12:00:00Z",
+        "Hello B",
+    );
+
+    let mut mcp = McpProcess::new(codex_home.path())
+        .await
+        .expect("spawn mcp process");
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize())
+        .await
+        .expect("init timeout")
+        .expect("init failed");
+
+    let req_id = mcp
+        .send_search_conversations_request(SearchConversationsParams {
+            query: "PARSE flaky".to_string(),
+            limit: None,
+        })
+        .await
+        .expect("send searchConversations");
+    let resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(req_id)),
+    )
+    .await
+    .expect("searchConversations timeout")
+    .expect("searchConversations resp");
+    let SearchConversationsResponse { items } =
+        to_response::<SearchConversationsResponse>(resp).expect("deserialize response");
+
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].timestamp.as_deref(), Some("2025-01-02T12:00:00Z"));
+    let snippet = &items[0].snippets[0];
+    assert_eq!(snippet.kind, ConversationSearchSnippetKind::UserMessage);
+    assert_eq!(snippet.text, "Fix the flaky résumé parser");
+    // Offsets count characters, so `é` counts once.
+    assert_eq!(
+        snippet.highlights,
+        vec![
+            TextRange { start: 8, end: 13 },
+            TextRange { start: 21, end: 26 },
+        ]
+    );
+
+    // Later searches reuse the loaded index and pick up new rollouts.
+    create_fake_rollout(
+        codex_home.path(),
+        "2025-01-03T12-00-00",
+        "2025-01-03T12:00:00Z",
+        "Another flaky parser",
+    );
+    let req_id = mcp
+        .send_search_conversations_request(SearchConversationsParams {
+            query: "PARSE flaky".to_string(),
+            limit: None,
+        })
+        .await
+        .expect("send searchConversations");
+    let resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(req_id)),
+    )
+    .await
+    .expect("searchConversations timeout")
+    .expect("searchConversations resp");
+    let SearchConversationsResponse { items } =
+        to_response::<SearchConversationsResponse>(resp).expect("deserialize response");
+    assert_eq!(
+        items
+            .iter()
+            .map(|item| item.timestamp.as_deref())
+            .collect::<Vec<_>>(),
+        vec![Some("2025-01-03T12:00:00Z"), Some("2025-01-02T12:00:00Z")]
+    );
+}
+
 fn create_fake_rollout(codex_home: &Path, filename_ts: &str, meta_rfc3339: &str, preview: &str) {
     let uuid = Uuid::new_v4();
     // sessions/YYYY/MM/DD/ derived from filename_ts (YYYY-MM-DDThh-mm-ss)
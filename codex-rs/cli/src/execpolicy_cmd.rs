@@ -0,0 +1,94 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::anyhow;
+use codex_common::CliConfigOverrides;
+use codex_core::approval_rules::project_for_cwd;
+use codex_core::config::Config;
+use codex_core::config::ConfigOverrides;
+use codex_core::exec_policy::ExecPolicy;
+
+/// Work with the execpolicy rules that decide which commands run without
+/// asking, need approval, or are forbidden.
+#[derive(Debug, clap::Parser)]
+pub struct ExecpolicyCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub subcommand: ExecpolicySubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum ExecpolicySubcommand {
+    /// Check that every `should_match` and `should_not_match` example in the
+    /// policy files behaves as declared.
+    Test(TestArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct TestArgs {
+    /// Policy files to check. Defaults to the built-in policy, the files in
+    /// `$CODEX_HOME/policy` and the files in the current project's
+    /// `.codex/policy`.
+    #[arg(value_name = "FILE")]
+    pub files: Vec<PathBuf>,
+}
+
+impl ExecpolicyCli {
+    pub async fn run(self) -> Result<()> {
+        let ExecpolicyCli {
+            config_overrides,
+            subcommand,
+        } = self;
+
+        match subcommand {
+            ExecpolicySubcommand::Test(args) => {
+                run_test(&config_overrides, args).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+async fn run_test(config_overrides: &CliConfigOverrides, args: TestArgs) -> Result<()> {
+    let (policy, load_errors) = if args.files.is_empty() {
+        let overrides = config_overrides.parse_overrides().map_err(|e| anyhow!(e))?;
+        let config = Config::load_with_cli_overrides(overrides, ConfigOverrides::default())
+            .await
+            .context("failed to load configuration")?;
+        // Project policies are checked whether or not the project is trusted:
+        // this only runs their examples, it does not apply them.
+        let project_root = project_for_cwd(&config.cwd);
+        ExecPolicy::load(&ExecPolicy::policy_dirs(
+            &config.codex_home,
+            Some(&project_root),
+        ))
+    } else {
+        (ExecPolicy::load_files(&args.files)?, Vec::new())
+    };
+
+    let mut failed = load_errors.len();
+    for err in &load_errors {
+        println!("error {err}");
+    }
+    for layer in policy.layers() {
+        let failures = layer.check_examples();
+        if failures.is_empty() {
+            println!("ok    {}", layer.source);
+            continue;
+        }
+        failed += 1;
+        println!("FAIL  {}", layer.source);
+        for failure in failures {
+            println!("      {failure}");
+        }
+    }
+
+    if failed > 0 {
+        return Err(anyhow!("{failed} policy file(s) failed"));
+    }
+    Ok(())
+}
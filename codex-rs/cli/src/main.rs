@@ -23,9 +23,15 @@ use owo_colors::OwoColorize;
 use std::path::PathBuf;
 use supports_color::Stream;
 
+mod execpolicy_cmd;
 mod mcp_cmd;
+mod sessions_cmd;
+mod usage_cmd;
 
+use crate::execpolicy_cmd::ExecpolicyCli;
 use crate::mcp_cmd::McpCli;
+use crate::sessions_cmd::SessionsCli;
+use crate::usage_cmd::UsageCli;
 
 /// Codex CLI
 ///
@@ -73,6 +79,15 @@ enum Subcommand {
     /// [experimental] Run the app server.
     AppServer,
 
+    /// Search past sessions.
+    Sessions(SessionsCli),
+
+    /// Summarize token usage and estimated cost across recorded sessions.
+    Usage(UsageCli),
+
+    /// Check execpolicy rules for shell commands.
+    Execpolicy(ExecpolicyCli),
+
     /// Generate shell completion scripts.
     Completion(CompletionCommand),
 
@@ -277,6 +292,27 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             prepend_config_flags(&mut mcp_cli.config_overrides, root_config_overrides.clone());
             mcp_cli.run().await?;
         }
+        Some(Subcommand::Sessions(mut sessions_cli)) => {
+            prepend_config_flags(
+                &mut sessions_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            sessions_cli.run().await?;
+        }
+        Some(Subcommand::Usage(mut usage_cli)) => {
+            prepend_config_flags(
+                &mut usage_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            usage_cli.run().await?;
+        }
+        Some(Subcommand::Execpolicy(mut execpolicy_cli)) => {
+            prepend_config_flags(
+                &mut execpolicy_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            execpolicy_cli.run().await?;
+        }
         Some(Subcommand::AppServer) => {
             codex_app_server::run_main(codex_linux_sandbox_exe, root_config_overrides).await?;
         }
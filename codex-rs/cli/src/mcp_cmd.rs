@@ -236,6 +236,7 @@ async fn run_add(config_overrides: &CliConfigOverrides, add_args: AddArgs) -> Re
         transport,
         startup_timeout_sec: None,
         tool_timeout_sec: None,
+        allow_sampling: false,
     };
 
     servers.insert(name.clone(), new_entry);
@@ -372,6 +373,7 @@ async fn run_list(config_overrides: &CliConfigOverrides, list_args: ListArgs) ->
                     "tool_timeout_sec": cfg
                         .tool_timeout_sec
                         .map(|timeout| timeout.as_secs_f64()),
+                    "allow_sampling": cfg.allow_sampling,
                 })
             })
             .collect();
@@ -533,6 +535,7 @@ async fn run_get(config_overrides: &CliConfigOverrides, get_args: GetArgs) -> Re
             "tool_timeout_sec": server
                 .tool_timeout_sec
                 .map(|timeout| timeout.as_secs_f64()),
+            "allow_sampling": server.allow_sampling,
         }))?;
         println!("{output}");
         return Ok(());
@@ -580,6 +583,9 @@ async fn run_get(config_overrides: &CliConfigOverrides, get_args: GetArgs) -> Re
     if let Some(timeout) = server.tool_timeout_sec {
         println!("  tool_timeout_sec: {}", timeout.as_secs_f64());
     }
+    if server.allow_sampling {
+        println!("  allow_sampling: true");
+    }
     println!("  remove: codex mcp remove {}", get_args.name);
 
     Ok(())
@@ -0,0 +1,267 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::anyhow;
+use codex_common::CliConfigOverrides;
+use codex_core::SearchHitKind;
+use codex_core::SearchSnippet;
+use codex_core::SessionSearchHit;
+use codex_core::SessionSearchIndex;
+use codex_core::config::Config;
+use codex_core::config::ConfigOverrides;
+use codex_core::find_conversation_path_by_id_str;
+use codex_tui::TranscriptFormat;
+use codex_tui::TranscriptOptions;
+use codex_tui::export_transcript;
+use owo_colors::OwoColorize;
+use serde_json::json;
+use supports_color::Stream;
+
+/// Work with recorded sessions in `~/.codex/sessions`.
+#[derive(Debug, clap::Parser)]
+pub struct SessionsCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub subcommand: SessionsSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum SessionsSubcommand {
+    /// Search the messages, commands and patched files of past sessions.
+    Search(SearchArgs),
+
+    /// Export a session as a Markdown, HTML or JSON transcript.
+    Export(ExportArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct SearchArgs {
+    /// Words to look for. A session matches when it contains every word;
+    /// words match as prefixes, ignoring case.
+    #[arg(required = true, num_args = 1..)]
+    pub query: Vec<String>,
+
+    /// Maximum number of sessions to show.
+    #[arg(long, default_value_t = 20)]
+    pub limit: usize,
+
+    /// Output the matches as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ExportArgs {
+    /// Id of the session to export, or the path to its rollout file.
+    #[arg(value_name = "SESSION")]
+    pub session: String,
+
+    /// Transcript format.
+    #[arg(long, value_enum, default_value_t = ExportFormat::Md)]
+    pub format: ExportFormat,
+
+    /// Include the model's reasoning summaries.
+    #[arg(long, default_value_t = false)]
+    pub reasoning: bool,
+
+    /// Write the transcript to this file instead of stdout.
+    #[arg(long, short = 'o', value_name = "FILE")]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    Md,
+    Html,
+    Json,
+}
+
+impl From<ExportFormat> for TranscriptFormat {
+    fn from(format: ExportFormat) -> Self {
+        match format {
+            ExportFormat::Md => TranscriptFormat::Markdown,
+            ExportFormat::Html => TranscriptFormat::Html,
+            ExportFormat::Json => TranscriptFormat::Json,
+        }
+    }
+}
+
+impl SessionsCli {
+    pub async fn run(self) -> Result<()> {
+        let SessionsCli {
+            config_overrides,
+            subcommand,
+        } = self;
+
+        match subcommand {
+            SessionsSubcommand::Search(args) => {
+                run_search(&config_overrides, args).await?;
+            }
+            SessionsSubcommand::Export(args) => {
+                run_export(&config_overrides, args).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+async fn run_search(config_overrides: &CliConfigOverrides, args: SearchArgs) -> Result<()> {
+    let overrides = config_overrides.parse_overrides().map_err(|e| anyhow!(e))?;
+    let config = Config::load_with_cli_overrides(overrides, ConfigOverrides::default())
+        .await
+        .context("failed to load configuration")?;
+
+    let index = SessionSearchIndex::load(&config.codex_home)
+        .await
+        .context("failed to index recorded sessions")?;
+    let hits = index.search(&args.query.join(" "), args.limit, &[]);
+
+    if args.json {
+        let hits_json: Vec<_> = hits.iter().map(hit_json).collect();
+        println!("{}", serde_json::to_string_pretty(&hits_json)?);
+        return Ok(());
+    }
+
+    if hits.is_empty() {
+        println!("No sessions match your search.");
+        return Ok(());
+    }
+
+    let color_enabled = supports_color::on(Stream::Stdout).is_some();
+    for (idx, hit) in hits.iter().enumerate() {
+        if idx > 0 {
+            println!();
+        }
+        let id = hit
+            .conversation_id
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| hit.path.display().to_string());
+        let when = hit
+            .updated_at
+            .as_deref()
+            .or(hit.created_at.as_deref())
+            .map(format_timestamp)
+            .unwrap_or_else(|| "-".to_string());
+        let cwd = hit
+            .cwd
+            .as_ref()
+            .map(|cwd| cwd.display().to_string())
+            .unwrap_or_default();
+        if color_enabled {
+            println!("{}  {}  {}", when.dimmed(), id.cyan(), cwd.dimmed());
+        } else {
+            println!("{when}  {id}  {cwd}");
+        }
+        for snippet in &hit.snippets {
+            println!(
+                "  {:<7}  {}",
+                kind_label(snippet.kind),
+                render_snippet(snippet, color_enabled)
+            );
+        }
+    }
+
+    Ok(())
+}
+
+async fn run_export(config_overrides: &CliConfigOverrides, args: ExportArgs) -> Result<()> {
+    let overrides = config_overrides.parse_overrides().map_err(|e| anyhow!(e))?;
+    let config = Config::load_with_cli_overrides(overrides, ConfigOverrides::default())
+        .await
+        .context("failed to load configuration")?;
+
+    let path = resolve_rollout_path(&config.codex_home, &args.session).await?;
+    let rollout = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let options = TranscriptOptions {
+        include_reasoning: args.reasoning,
+        file_opener_scheme: config.file_opener.get_scheme().map(str::to_string),
+    };
+    let transcript = export_transcript(&rollout, args.format.into(), &options)?;
+
+    match args.output {
+        Some(output) => std::fs::write(&output, transcript)
+            .with_context(|| format!("failed to write {}", output.display()))?,
+        None => print!("{transcript}"),
+    }
+    Ok(())
+}
+
+async fn resolve_rollout_path(codex_home: &Path, session: &str) -> Result<PathBuf> {
+    let candidate = Path::new(session);
+    if candidate.is_file() {
+        return Ok(candidate.to_path_buf());
+    }
+    find_conversation_path_by_id_str(codex_home, session)
+        .await
+        .context("failed to look up the session")?
+        .ok_or_else(|| anyhow!("no recorded session with id `{session}`"))
+}
+
+fn kind_label(kind: SearchHitKind) -> &'static str {
+    match kind {
+        SearchHitKind::UserMessage => "user",
+        SearchHitKind::AgentMessage => "codex",
+        SearchHitKind::Command => "command",
+        SearchHitKind::PatchedFile => "patched",
+    }
+}
+
+/// Shows `2025-05-01T10:00:02.000Z` as `2025-05-01 10:00`.
+fn format_timestamp(timestamp: &str) -> String {
+    timestamp
+        .get(..16)
+        .map(|minutes| minutes.replacen('T', " ", 1))
+        .unwrap_or_else(|| timestamp.to_string())
+}
+
+fn render_snippet(snippet: &SearchSnippet, color_enabled: bool) -> String {
+    if !color_enabled {
+        return snippet.text.clone();
+    }
+    snippet
+        .segments()
+        .into_iter()
+        .map(|(text, highlighted)| {
+            if highlighted {
+                text.bold().yellow().to_string()
+            } else {
+                text.to_string()
+            }
+        })
+        .collect()
+}
+
+fn hit_json(hit: &SessionSearchHit) -> serde_json::Value {
+    let snippets: Vec<_> = hit
+        .snippets
+        .iter()
+        .map(|snippet| {
+            let char_offset = |byte: usize| snippet.text[..byte].chars().count();
+            let highlights: Vec<_> = snippet
+                .highlights
+                .iter()
+                .map(|range| json!({ "start": char_offset(range.start), "end": char_offset(range.end) }))
+                .collect();
+            json!({
+                "kind": snippet.kind,
+                "text": snippet.text,
+                "highlights": highlights,
+            })
+        })
+        .collect();
+    json!({
+        "conversation_id": hit.conversation_id,
+        "path": hit.path,
+        "created_at": hit.created_at,
+        "updated_at": hit.updated_at,
+        "cwd": hit.cwd,
+        "source": hit.source,
+        "snippets": snippets,
+    })
+}
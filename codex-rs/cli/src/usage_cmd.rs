@@ -0,0 +1,278 @@
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::anyhow;
+use anyhow::bail;
+use codex_common::CliConfigOverrides;
+use codex_core::config::Config;
+use codex_core::config::ConfigOverrides;
+use codex_core::usage::UsageGroup;
+use codex_core::usage::UsageRow;
+use codex_core::usage::aggregate_usage;
+use codex_core::usage::scan_usage;
+use codex_core::usage::unpriced_models;
+
+/// Summarize token usage recorded in `~/.codex/sessions`.
+#[derive(Debug, clap::Parser)]
+pub struct UsageCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Columns to group usage by. Repeat the flag or separate values with commas.
+    #[arg(long = "by", value_enum, value_delimiter = ',', default_value = "day")]
+    pub group_by: Vec<UsageGroupArg>,
+
+    /// Only count usage on or after this UTC day (YYYY-MM-DD).
+    #[arg(long, value_name = "DAY", value_parser = parse_day)]
+    pub since: Option<String>,
+
+    /// Estimate the cost of each row from built-in and `model_prices` prices.
+    #[arg(long, default_value_t = false)]
+    pub cost: bool,
+
+    /// Output format.
+    #[arg(long, value_enum, default_value_t = UsageFormat::Table)]
+    pub format: UsageFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum UsageGroupArg {
+    Day,
+    Model,
+    Cwd,
+    Source,
+}
+
+impl From<UsageGroupArg> for UsageGroup {
+    fn from(arg: UsageGroupArg) -> Self {
+        match arg {
+            UsageGroupArg::Day => UsageGroup::Day,
+            UsageGroupArg::Model => UsageGroup::Model,
+            UsageGroupArg::Cwd => UsageGroup::Cwd,
+            UsageGroupArg::Source => UsageGroup::Source,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum UsageFormat {
+    Table,
+    Json,
+    Csv,
+}
+
+impl UsageCli {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(|e| anyhow!(e))?;
+        let config = Config::load_with_cli_overrides(overrides, ConfigOverrides::default())
+            .await
+            .context("failed to load configuration")?;
+
+        let scan = scan_usage(&config.codex_home)
+            .await
+            .context("failed to read recorded sessions")?;
+        if scan.reached_scan_cap {
+            eprintln!("warning: too many sessions to scan; the oldest sessions are not counted");
+        }
+
+        let mut groups: Vec<UsageGroup> = Vec::new();
+        for group in self.group_by.into_iter().map(UsageGroup::from) {
+            if !groups.contains(&group) {
+                groups.push(group);
+            }
+        }
+        let prices = self.cost.then_some(&config.model_prices);
+        if let Some(prices) = prices {
+            let unpriced = unpriced_models(&scan.entries, prices);
+            if !unpriced.is_empty() {
+                let models = unpriced.into_iter().collect::<Vec<_>>().join(", ");
+                eprintln!(
+                    "warning: no price known for {models}; add them to `model_prices` in config.toml to include them in the cost"
+                );
+            }
+        }
+        let rows = aggregate_usage(&scan.entries, &groups, self.since.as_deref(), prices);
+
+        match self.format {
+            UsageFormat::Json => {
+                println!("{}", serde_json::to_string_pretty(&rows)?);
+            }
+            UsageFormat::Csv => print_csv(&groups, &rows, self.cost),
+            UsageFormat::Table => {
+                if rows.is_empty() {
+                    println!("No token usage recorded yet.");
+                } else {
+                    print_table(&groups, &rows, self.cost);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_day(value: &str) -> Result<String> {
+    let bytes = value.as_bytes();
+    let well_formed = bytes.len() == 10
+        && bytes.iter().enumerate().all(|(i, b)| match i {
+            4 | 7 => *b == b'-',
+            _ => b.is_ascii_digit(),
+        });
+    if !well_formed {
+        bail!("expected a day formatted as YYYY-MM-DD, got `{value}`");
+    }
+    Ok(value.to_string())
+}
+
+fn group_header(group: UsageGroup) -> &'static str {
+    match group {
+        UsageGroup::Day => "day",
+        UsageGroup::Model => "model",
+        UsageGroup::Cwd => "cwd",
+        UsageGroup::Source => "source",
+    }
+}
+
+fn group_value(row: &UsageRow, group: UsageGroup) -> String {
+    match group {
+        UsageGroup::Day => row.day.clone(),
+        UsageGroup::Model => row.model.clone(),
+        UsageGroup::Cwd => row.cwd.as_ref().map(|cwd| cwd.display().to_string()),
+        UsageGroup::Source => row.source.as_ref().and_then(|source| {
+            serde_json::to_value(source)
+                .ok()
+                .and_then(|value| value.as_str().map(str::to_string))
+        }),
+    }
+    .unwrap_or_default()
+}
+
+const COUNT_HEADERS: [&str; 5] = ["sessions", "input", "cached", "output", "reasoning"];
+
+fn row_counts(row: &UsageRow) -> [u64; 5] {
+    [
+        row.sessions as u64,
+        row.usage.input_tokens,
+        row.usage.cached_input_tokens,
+        row.usage.output_tokens,
+        row.usage.reasoning_output_tokens,
+    ]
+}
+
+fn print_table(groups: &[UsageGroup], rows: &[UsageRow], cost: bool) {
+    let mut headers: Vec<String> = groups
+        .iter()
+        .map(|group| group_header(*group).to_string())
+        .collect();
+    headers.extend(COUNT_HEADERS.iter().map(ToString::to_string));
+    if cost {
+        headers.push("cost (USD)".to_string());
+    }
+
+    let mut totals = [0u64; 5];
+    let mut total_cost = 0.0;
+    let mut table: Vec<Vec<String>> = Vec::with_capacity(rows.len() + 1);
+    for row in rows {
+        let mut cells: Vec<String> = groups.iter().map(|g| group_value(row, *g)).collect();
+        for (total, count) in totals.iter_mut().zip(row_counts(row)) {
+            *total += count;
+            cells.push(format_count(count));
+        }
+        if let Some(row_cost) = row.cost_usd {
+            total_cost += row_cost;
+            cells.push(format!("{row_cost:.2}"));
+        }
+        table.push(cells);
+    }
+    if rows.len() > 1 {
+        // Sessions can span several rows, so a session total would be misleading.
+        let mut cells = vec![String::new(); groups.len()];
+        if let Some(first) = cells.first_mut() {
+            *first = "total".to_string();
+        }
+        cells.push(String::new());
+        cells.extend(totals[1..].iter().map(|count| format_count(*count)));
+        if cost {
+            cells.push(format!("{total_cost:.2}"));
+        }
+        table.push(cells);
+    }
+
+    let mut widths: Vec<usize> = headers.iter().map(String::len).collect();
+    for cells in &table {
+        for (width, cell) in widths.iter_mut().zip(cells) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let render = |cells: &[String]| {
+        cells
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(i, (cell, width))| {
+                if i < groups.len() {
+                    format!("{cell:<width$}")
+                } else {
+                    format!("{cell:>width$}")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+    println!("{}", render(&headers));
+    for cells in &table {
+        println!("{}", render(cells));
+    }
+}
+
+fn print_csv(groups: &[UsageGroup], rows: &[UsageRow], cost: bool) {
+    let mut headers: Vec<&str> = groups.iter().map(|group| group_header(*group)).collect();
+    headers.extend([
+        "sessions",
+        "input_tokens",
+        "cached_input_tokens",
+        "output_tokens",
+        "reasoning_output_tokens",
+    ]);
+    if cost {
+        headers.push("cost_usd");
+    }
+    println!("{}", headers.join(","));
+
+    for row in rows {
+        let mut cells: Vec<String> = groups
+            .iter()
+            .map(|group| csv_escape(&group_value(row, *group)))
+            .collect();
+        cells.extend(row_counts(row).iter().map(u64::to_string));
+        if let Some(row_cost) = row.cost_usd {
+            cells.push(format!("{row_cost:.4}"));
+        }
+        println!("{}", cells.join(","));
+    }
+}
+
+fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Formats `1234567` as `1,234,567`.
+fn format_count(count: u64) -> String {
+    let digits = count.to_string();
+    let mut formatted = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            formatted.push(',');
+        }
+        formatted.push(digit);
+    }
+    formatted
+}
@@ -0,0 +1,66 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::Result;
+use predicates::str::contains;
+use tempfile::TempDir;
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::cargo_bin("codex")?;
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+#[test]
+fn test_passes_when_examples_behave_as_declared() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let policy_path = codex_home.path().join("git.policy");
+    fs::write(
+        &policy_path,
+        r#"
+define_program(
+    program="git",
+    args=["push"],
+    ask="pushing publishes commits",
+    should_match=[["push"]],
+    should_not_match=[["status"]],
+)
+"#,
+    )?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.args(["execpolicy", "test"])
+        .arg(&policy_path)
+        .assert()
+        .success()
+        .stdout(contains("ok    "));
+
+    Ok(())
+}
+
+#[test]
+fn test_fails_on_mismatched_example() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let policy_dir = codex_home.path().join("policy");
+    fs::create_dir_all(&policy_dir)?;
+    fs::write(
+        policy_dir.join("git.policy"),
+        r#"
+define_program(
+    program="git",
+    args=["status"],
+    should_not_match=[["status"]],
+)
+"#,
+    )?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.current_dir(codex_home.path())
+        .args(["execpolicy", "test"])
+        .assert()
+        .failure()
+        .stdout(contains("ok    #default"))
+        .stdout(contains("should_not_match example `git status` matched"));
+
+    Ok(())
+}
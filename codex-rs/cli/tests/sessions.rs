@@ -0,0 +1,138 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::Result;
+use pretty_assertions::assert_eq;
+use serde_json::Value as JsonValue;
+use serde_json::json;
+use tempfile::TempDir;
+
+const SESSION_ID: &str = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::cargo_bin("codex")?;
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+fn write_rollout(codex_home: &Path) -> Result<()> {
+    let dir = codex_home.join("sessions/2025/05/01");
+    fs::create_dir_all(&dir)?;
+
+    let lines = [
+        json!({
+            "timestamp": "2025-05-01T10:00:00.000Z",
+            "type": "session_meta",
+            "payload": {
+                "id": SESSION_ID,
+                "timestamp": "2025-05-01T10:00:00.000Z",
+                "cwd": "/repo",
+                "originator": "codex_cli_rs",
+                "cli_version": "0.0.0",
+                "instructions": null,
+                "source": "cli",
+            }
+        }),
+        json!({
+            "timestamp": "2025-05-01T10:00:00.500Z",
+            "type": "event_msg",
+            "payload": { "type": "user_message", "message": "list the files", "kind": "plain" }
+        }),
+        json!({
+            "timestamp": "2025-05-01T10:00:01.000Z",
+            "type": "response_item",
+            "payload": {
+                "type": "function_call",
+                "name": "shell",
+                "arguments": json!({ "command": ["bash", "-lc", "ls"] }).to_string(),
+                "call_id": "call-1",
+            }
+        }),
+        json!({
+            "timestamp": "2025-05-01T10:00:02.000Z",
+            "type": "response_item",
+            "payload": {
+                "type": "function_call_output",
+                "call_id": "call-1",
+                "output": json!({
+                    "output": "Cargo.toml\nsrc\n",
+                    "metadata": { "exit_code": 0, "duration_seconds": 0.1 },
+                }).to_string(),
+            }
+        }),
+        json!({
+            "timestamp": "2025-05-01T10:00:03.000Z",
+            "type": "event_msg",
+            "payload": { "type": "agent_message", "message": "There are two entries." }
+        }),
+    ];
+    let contents = lines
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("\n");
+    fs::write(
+        dir.join(format!("rollout-2025-05-01T10-00-00-{SESSION_ID}.jsonl")),
+        contents,
+    )?;
+    Ok(())
+}
+
+#[test]
+fn export_writes_markdown_transcript() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_rollout(codex_home.path())?;
+    let output_path = codex_home.path().join("transcript.md");
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.args(["sessions", "export", SESSION_ID, "-o"])
+        .arg(&output_path)
+        .assert()
+        .success();
+
+    let markdown = fs::read_to_string(&output_path)?;
+    assert!(markdown.contains("**User**\n\nlist the files\n"));
+    assert!(markdown.contains("**Command** (exit code 0)\n\n```bash\nls\n```\n"));
+    assert!(markdown.contains("```text\nCargo.toml\nsrc\n```\n"));
+    assert!(markdown.contains("**Codex**\n\nThere are two entries.\n"));
+
+    Ok(())
+}
+
+#[test]
+fn export_json_lists_entries() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_rollout(codex_home.path())?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd
+        .args(["sessions", "export", SESSION_ID, "--format", "json"])
+        .output()?;
+    assert!(output.status.success());
+    let transcript: JsonValue = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(
+        transcript["entries"][1],
+        json!({
+            "type": "command",
+            "timestamp": "2025-05-01T10:00:01.000Z",
+            "command": "ls",
+            "exit_code": 0,
+            "output": "Cargo.toml\nsrc",
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn export_rejects_unknown_session() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.args(["sessions", "export", SESSION_ID])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("no recorded session"));
+
+    Ok(())
+}
@@ -0,0 +1,154 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::Result;
+use pretty_assertions::assert_eq;
+use serde_json::Value as JsonValue;
+use serde_json::json;
+use tempfile::TempDir;
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::cargo_bin("codex")?;
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+fn token_count(timestamp: &str, input: u64, output: u64) -> JsonValue {
+    let usage = json!({
+        "input_tokens": input,
+        "cached_input_tokens": 0,
+        "output_tokens": output,
+        "reasoning_output_tokens": 0,
+        "total_tokens": input + output,
+    });
+    json!({
+        "timestamp": timestamp,
+        "type": "event_msg",
+        "payload": {
+            "type": "token_count",
+            "info": {
+                "total_token_usage": usage,
+                "last_token_usage": usage,
+                "model_context_window": null,
+            },
+            "rate_limits": null,
+        }
+    })
+}
+
+fn write_rollout(codex_home: &Path, id: &str, model: &str, totals: &[(u64, u64)]) -> Result<()> {
+    let dir = codex_home.join("sessions/2025/05/01");
+    fs::create_dir_all(&dir)?;
+
+    let mut lines = vec![
+        json!({
+            "timestamp": "2025-05-01T10:00:00.000Z",
+            "type": "session_meta",
+            "payload": {
+                "id": id,
+                "timestamp": "2025-05-01T10:00:00.000Z",
+                "cwd": "/repo",
+                "originator": "codex_cli_rs",
+                "cli_version": "0.0.0",
+                "instructions": null,
+                "source": "cli",
+            }
+        }),
+        json!({
+            "timestamp": "2025-05-01T10:00:00.500Z",
+            "type": "event_msg",
+            "payload": { "type": "user_message", "message": "hi", "kind": "plain" }
+        }),
+        json!({
+            "timestamp": "2025-05-01T10:00:01.000Z",
+            "type": "turn_context",
+            "payload": {
+                "cwd": "/repo",
+                "approval_policy": "never",
+                "sandbox_policy": { "mode": "read-only" },
+                "model": model,
+                "summary": "auto",
+            }
+        }),
+    ];
+    lines.extend(
+        totals
+            .iter()
+            .map(|(input, output)| token_count("2025-05-01T10:00:02.000Z", *input, *output)),
+    );
+    let contents = lines
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("\n");
+    fs::write(
+        dir.join(format!("rollout-2025-05-01T10-00-00-{id}.jsonl")),
+        contents,
+    )?;
+    Ok(())
+}
+
+#[test]
+fn usage_shows_empty_state() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd.args(["usage"]).output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("No token usage recorded yet."));
+
+    Ok(())
+}
+
+#[test]
+fn usage_json_groups_sessions_by_model() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_rollout(
+        codex_home.path(),
+        "67e55044-10b1-426f-9247-bb680e5fe0c8",
+        "gpt-5",
+        &[(1_000, 100), (1_500, 300)],
+    )?;
+    write_rollout(
+        codex_home.path(),
+        "0199a213-81c0-7800-8aa1-bbab2a035a53",
+        "gpt-5",
+        &[(500, 50)],
+    )?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd
+        .args(["usage", "--by", "model", "--format", "json"])
+        .output()?;
+    assert!(output.status.success());
+    let rows: JsonValue = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(
+        rows,
+        json!([
+            {
+                "model": "gpt-5",
+                "sessions": 2,
+                "input_tokens": 2_000,
+                "cached_input_tokens": 0,
+                "output_tokens": 350,
+                "reasoning_output_tokens": 0,
+            }
+        ])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn usage_rejects_malformed_since() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.args(["usage", "--since", "May 1"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("YYYY-MM-DD"));
+
+    Ok(())
+}
@@ -3,9 +3,11 @@
 //! This module contains pre-defined agents that are available
 //! by default for specialized tasks.
 
+pub mod refactor;
 pub mod review;
 pub mod security;
 
 // Re-export built-in agents
+pub use refactor::RefactorAgent;
 pub use review::ReviewAgent;
 pub use security::SecurityAgent;
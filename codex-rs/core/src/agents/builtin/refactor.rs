@@ -0,0 +1,559 @@
+//! Refactoring agent for extract-function, dead-code and duplication opportunities.
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::agents::ActivationScore;
+use crate::agents::Agent;
+use crate::agents::AgentId;
+use crate::agents::AgentPermissions;
+use crate::agents::AgentResult;
+use crate::agents::AgentToolkit;
+use crate::agents::Suggestion;
+use crate::agents::Task;
+use crate::agents::TaskContext;
+use crate::agents::permissions::FileAccessPolicy;
+use crate::agents::routing::RoutingHints;
+
+use super::security::CODE_FILE_EXTENSIONS;
+use super::security::is_code_file;
+
+/// Minimum number of consecutive commented-out code lines worth removing.
+const MIN_COMMENTED_CODE_LINES: usize = 2;
+
+/// Minimum number of meaningful lines for a repeated block to count as duplication.
+const MIN_DUPLICATE_LINES: usize = 4;
+
+/// Functions longer than this are candidates for extraction.
+const MAX_FUNCTION_LINES: usize = 50;
+
+/// Keywords in the user's intent that activate the refactor agent.
+const ACTIVATION_KEYWORDS: &[&str] = &[
+    "refactor",
+    "improve",
+    "cleanup",
+    "clean up",
+    "simplify",
+    "restructure",
+    "duplicat",
+    "dead code",
+    "extract",
+];
+
+/// Refactoring agent that looks for mechanical clean-ups and structural
+/// improvements.
+///
+/// The refactor agent identifies:
+/// - Commented-out code (with a patch removing it)
+/// - Unreachable code after `return` (with a patch removing it)
+/// - Duplicated blocks that could be extracted into a shared function
+/// - Long functions that could be split into smaller ones
+///
+/// Whenever a suggestion carries a `code_change`, it is an `apply_patch`
+/// envelope that can be previewed and applied with
+/// [`AgentToolkit::apply_patch`].
+pub struct RefactorAgent {
+    permissions: AgentPermissions,
+}
+
+impl RefactorAgent {
+    /// Creates a new refactor agent with appropriate permissions.
+    pub fn new() -> Self {
+        Self {
+            permissions: AgentPermissions {
+                file_access: FileAccessPolicy::ReadWrite {
+                    allow_patterns: vec!["**".to_string()],
+                    deny_patterns: vec![],
+                },
+                shell_execution: false,
+                network_access: false,
+                allowed_tools: vec![],
+                max_iterations: 10,
+                can_delegate: false,
+            },
+        }
+    }
+
+    /// Reads each file and collects refactoring suggestions.
+    async fn analyze_files(
+        &self,
+        files: &[PathBuf],
+        toolkit: &AgentToolkit,
+    ) -> anyhow::Result<Vec<Suggestion>> {
+        let mut suggestions = Vec::new();
+
+        for file_path in files {
+            if !is_code_file(file_path) {
+                continue;
+            }
+
+            match toolkit.read_file(file_path).await {
+                Ok(content) => {
+                    let display_path = file_path
+                        .strip_prefix(toolkit.workspace_root())
+                        .unwrap_or(file_path);
+                    suggestions.extend(self.analyze_file_content(&content, display_path));
+                }
+                Err(e) => {
+                    tracing::debug!("Skipping {:?} for refactoring: {}", file_path, e);
+                }
+            }
+        }
+
+        Ok(suggestions)
+    }
+
+    /// Analyzes a single file's content for refactoring opportunities.
+    fn analyze_file_content(&self, content: &str, file_path: &Path) -> Vec<Suggestion> {
+        let lines: Vec<&str> = content.lines().collect();
+        let mut suggestions = Vec::new();
+
+        suggestions.extend(find_commented_out_code(&lines, file_path));
+        suggestions.extend(find_unreachable_code(&lines, file_path));
+        suggestions.extend(find_duplicate_blocks(&lines, file_path));
+        suggestions.extend(find_long_functions(&lines, file_path));
+
+        suggestions
+    }
+}
+
+#[async_trait]
+impl Agent for RefactorAgent {
+    fn id(&self) -> AgentId {
+        AgentId::from("refactor")
+    }
+
+    fn name(&self) -> &str {
+        "Refactoring Agent"
+    }
+
+    fn description(&self) -> &str {
+        "Identifies extract-function, dead-code and duplication opportunities and proposes \
+         patches that can be applied directly"
+    }
+
+    fn can_handle(&self, context: &TaskContext) -> ActivationScore {
+        let intent = context.user_intent.to_lowercase();
+        let matches = ACTIVATION_KEYWORDS
+            .iter()
+            .filter(|k| intent.contains(*k))
+            .count();
+
+        ActivationScore::new(matches as f64 * 0.35)
+    }
+
+    fn routing_hints(&self) -> RoutingHints {
+        RoutingHints {
+            keywords: ACTIVATION_KEYWORDS
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            file_extensions: CODE_FILE_EXTENSIONS
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        }
+    }
+
+    async fn execute(&self, task: Task, toolkit: &AgentToolkit) -> anyhow::Result<AgentResult> {
+        let suggestions = self
+            .analyze_files(&task.context.file_paths, toolkit)
+            .await?;
+
+        Ok(AgentResult::Suggestions { items: suggestions })
+    }
+
+    fn permissions(&self) -> &AgentPermissions {
+        &self.permissions
+    }
+
+    fn system_prompt(&self) -> &str {
+        "You are an expert at refactoring code safely. Identify opportunities to extract \
+         functions, remove dead or commented-out code, and eliminate duplication while \
+         preserving behavior. Prefer small, incremental changes. For every suggestion that \
+         changes code, provide the change as an apply_patch envelope starting with \
+         '*** Begin Patch' and ending with '*** End Patch', using paths relative to the \
+         workspace root."
+    }
+}
+
+impl Default for RefactorAgent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Finds runs of commented-out code and proposes removing them.
+fn find_commented_out_code(lines: &[&str], file_path: &Path) -> Vec<Suggestion> {
+    let mut suggestions = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        if !is_commented_out_code(lines[i]) {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        while i < lines.len() && is_commented_out_code(lines[i]) {
+            i += 1;
+        }
+
+        if i - start >= MIN_COMMENTED_CODE_LINES {
+            suggestions.push(Suggestion {
+                title: "Remove commented-out code".to_string(),
+                description: format!(
+                    "{}:{}-{} contains {} lines of commented-out code. Version control already \
+                     keeps the history; removing it makes the file easier to read.",
+                    file_path.display(),
+                    start + 1,
+                    i,
+                    i - start
+                ),
+                code_change: Some(removal_patch(file_path, lines, start, i)),
+            });
+        }
+    }
+
+    suggestions
+}
+
+/// Returns true for `//` comments whose text looks like a statement.
+fn is_commented_out_code(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    let Some(text) = trimmed.strip_prefix("//") else {
+        return false;
+    };
+    if text.starts_with('/') || text.starts_with('!') {
+        // Doc comments.
+        return false;
+    }
+
+    let text = text.trim();
+    // Prose rarely ends in statement or block punctuation.
+    text.ends_with(';') || text.ends_with('{') || text.ends_with('}')
+}
+
+/// Finds statements that follow a `return` in the same block and proposes
+/// removing them.
+fn find_unreachable_code(lines: &[&str], file_path: &Path) -> Vec<Suggestion> {
+    let mut suggestions = Vec::new();
+
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        if !(trimmed.starts_with("return") && trimmed.ends_with(';')) {
+            continue;
+        }
+        if i > 0 && lines[i - 1].trim_start().starts_with("#[") {
+            // Conditionally compiled returns are not dead code.
+            continue;
+        }
+
+        let indent = indentation(line);
+        let mut depth: i64 = 0;
+        let mut end = i + 1;
+        for (j, next) in lines.iter().enumerate().skip(i + 1) {
+            let next_trimmed = next.trim();
+            if next_trimmed.is_empty() {
+                continue;
+            }
+            if indentation(next) < indent
+                || (depth == 0 && next_trimmed.starts_with('}'))
+                || next_trimmed.starts_with("#")
+                || next_trimmed.starts_with("case ")
+                || next_trimmed.starts_with("default")
+            {
+                break;
+            }
+            depth += next_trimmed.matches('{').count() as i64;
+            depth -= next_trimmed.matches('}').count() as i64;
+            end = j + 1;
+            if depth < 0 {
+                break;
+            }
+        }
+
+        if end > i + 1 && depth == 0 {
+            suggestions.push(Suggestion {
+                title: "Remove unreachable code".to_string(),
+                description: format!(
+                    "{}:{}-{} can never run because it follows the return on line {}.",
+                    file_path.display(),
+                    i + 2,
+                    end,
+                    i + 1
+                ),
+                code_change: Some(removal_patch(file_path, lines, i + 1, end)),
+            });
+        }
+    }
+
+    suggestions
+}
+
+/// Finds blocks of lines that appear more than once in a file.
+fn find_duplicate_blocks(lines: &[&str], file_path: &Path) -> Vec<Suggestion> {
+    // Only lines with identifiers carry meaning; braces and blanks are noise.
+    let meaningful: Vec<(usize, &str)> = lines
+        .iter()
+        .enumerate()
+        .map(|(i, l)| (i, l.trim()))
+        .filter(|(_, l)| l.chars().any(char::is_alphanumeric) && !l.starts_with("//"))
+        .collect();
+    if meaningful.len() < MIN_DUPLICATE_LINES * 2 {
+        return Vec::new();
+    }
+
+    let mut first_seen: HashMap<Vec<&str>, usize> = HashMap::new();
+    let mut suggestions = Vec::new();
+    let mut i = 0;
+    while i + MIN_DUPLICATE_LINES <= meaningful.len() {
+        let window: Vec<&str> = meaningful[i..i + MIN_DUPLICATE_LINES]
+            .iter()
+            .map(|(_, l)| *l)
+            .collect();
+        match first_seen.get(&window) {
+            Some(&first) if first + MIN_DUPLICATE_LINES <= i => {
+                suggestions.push(Suggestion {
+                    title: "Extract duplicated code".to_string(),
+                    description: format!(
+                        "{path}:{dup} repeats the block starting at {path}:{orig}. Consider \
+                         extracting it into a shared function.",
+                        path = file_path.display(),
+                        dup = meaningful[i].0 + 1,
+                        orig = meaningful[first].0 + 1,
+                    ),
+                    code_change: None,
+                });
+                i += MIN_DUPLICATE_LINES;
+            }
+            Some(_) => i += 1,
+            None => {
+                first_seen.insert(window, i);
+                i += 1;
+            }
+        }
+    }
+
+    suggestions
+}
+
+/// Finds functions whose body exceeds [`MAX_FUNCTION_LINES`].
+fn find_long_functions(lines: &[&str], file_path: &Path) -> Vec<Suggestion> {
+    let mut suggestions = Vec::new();
+
+    for (i, line) in lines.iter().enumerate() {
+        let Some(name) = function_name(line) else {
+            continue;
+        };
+
+        let mut depth: i64 = 0;
+        let mut opened = false;
+        for (j, body_line) in lines.iter().enumerate().skip(i) {
+            depth += body_line.matches('{').count() as i64;
+            depth -= body_line.matches('}').count() as i64;
+            opened |= body_line.contains('{');
+            if opened && depth <= 0 {
+                let length = j - i + 1;
+                if length > MAX_FUNCTION_LINES {
+                    suggestions.push(Suggestion {
+                        title: format!("Extract smaller functions from `{name}`"),
+                        description: format!(
+                            "{}:{} `{name}` is {length} lines long. Splitting it into \
+                             well-named helpers would make it easier to test and review.",
+                            file_path.display(),
+                            i + 1,
+                        ),
+                        code_change: None,
+                    });
+                }
+                break;
+            }
+        }
+    }
+
+    suggestions
+}
+
+/// Extracts the function name from a definition line.
+fn function_name(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with("//") {
+        return None;
+    }
+    let rest = ["fn ", "function ", "func ", "def "]
+        .iter()
+        .find_map(|keyword| {
+            trimmed
+                .find(keyword)
+                .filter(|&pos| pos == 0 || trimmed.as_bytes()[pos - 1] == b' ')
+                .map(|pos| &trimmed[pos + keyword.len()..])
+        })?;
+    let end = rest.find(['(', '<']).unwrap_or(rest.len());
+    let name = rest[..end].trim();
+    (!name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_')).then_some(name)
+}
+
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// Builds an `apply_patch` envelope that deletes `lines[start..end]`.
+///
+/// One line of context on each side anchors the hunk.
+fn removal_patch(file_path: &Path, lines: &[&str], start: usize, end: usize) -> String {
+    let mut patch = format!(
+        "*** Begin Patch\n*** Update File: {}\n@@\n",
+        file_path.display()
+    );
+    if start > 0 {
+        patch.push_str(&format!(" {}\n", lines[start - 1]));
+    }
+    for line in &lines[start..end] {
+        patch.push_str(&format!("-{line}\n"));
+    }
+    if end < lines.len() {
+        patch.push_str(&format!(" {}\n", lines[end]));
+    }
+    patch.push_str("*** End Patch");
+    patch
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_apply_patch::ApplyPatchFileChange;
+    use codex_apply_patch::MaybeApplyPatchVerified;
+    use codex_apply_patch::maybe_parse_apply_patch_verified;
+    use tempfile::TempDir;
+
+    /// Applies `patch` in memory and returns the new content of `file`.
+    fn patched_content(workspace: &Path, file: &str, patch: &str) -> String {
+        let argv = vec!["apply_patch".to_string(), patch.to_string()];
+        let MaybeApplyPatchVerified::Body(action) =
+            maybe_parse_apply_patch_verified(&argv, workspace)
+        else {
+            panic!("invalid patch: {patch}");
+        };
+        match action.changes().get(&workspace.join(file)) {
+            Some(ApplyPatchFileChange::Update { new_content, .. }) => new_content.clone(),
+            other => panic!("unexpected change: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_refactor_agent_id() {
+        let agent = RefactorAgent::new();
+        assert_eq!(agent.id().as_str(), "refactor");
+        assert!(!agent.system_prompt().is_empty());
+    }
+
+    #[test]
+    fn test_can_handle_refactor_intent() {
+        let agent = RefactorAgent::new();
+        let context = TaskContext {
+            user_intent: "Refactor src/lib.rs and improve readability".to_string(),
+            ..Default::default()
+        };
+        assert!(agent.can_handle(&context).0 >= 0.6);
+
+        let unrelated = TaskContext {
+            user_intent: "Explain this function".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(agent.can_handle(&unrelated).0, 0.0);
+    }
+
+    #[test]
+    fn test_function_name() {
+        assert_eq!(function_name("pub async fn run(&self) {"), Some("run"));
+        assert_eq!(function_name("fn parse<T>(x: T) {"), Some("parse"));
+        assert_eq!(function_name("function render() {"), Some("render"));
+        assert_eq!(function_name("// fn commented() {"), None);
+        assert_eq!(function_name("let offn = 1;"), None);
+    }
+
+    #[test]
+    fn test_long_function_suggestion() {
+        let mut source = vec!["fn long() {".to_string()];
+        source.extend((0..MAX_FUNCTION_LINES).map(|i| format!("    step_{i}();")));
+        source.push("}".to_string());
+        let lines: Vec<&str> = source.iter().map(String::as_str).collect();
+
+        let suggestions = find_long_functions(&lines, Path::new("src/lib.rs"));
+        assert_eq!(suggestions.len(), 1);
+        assert!(suggestions[0].title.contains("`long`"));
+        assert!(suggestions[0].code_change.is_none());
+    }
+
+    #[test]
+    fn test_duplicate_block_suggestion() {
+        let block = "let a = load();\nlet b = parse(a);\nvalidate(&b);\nstore(b);\n";
+        let source = format!("fn one() {{\n{block}}}\n\nfn two() {{\n{block}}}\n");
+        let lines: Vec<&str> = source.lines().collect();
+
+        let suggestions = find_duplicate_blocks(&lines, Path::new("src/lib.rs"));
+        assert_eq!(suggestions.len(), 1);
+        assert!(suggestions[0].description.starts_with("src/lib.rs:9 "));
+        assert!(suggestions[0].description.contains("at src/lib.rs:2."));
+    }
+
+    #[tokio::test]
+    async fn test_execute_returns_applicable_patches() {
+        let workspace = TempDir::new().unwrap();
+        let source = "\
+fn main() {
+    let x = compute();
+    // let y = old_compute();
+    // println!(\"{}\", y);
+    println!(\"{}\", x);
+}
+
+fn compute() -> i32 {
+    return 1;
+    let unused = 2;
+    unused
+}
+";
+        tokio::fs::write(workspace.path().join("lib.rs"), source)
+            .await
+            .unwrap();
+
+        let agent = RefactorAgent::new();
+        let toolkit = AgentToolkit::new(
+            agent.id(),
+            agent.permissions().clone(),
+            workspace.path().to_path_buf(),
+        );
+        let task = Task {
+            context: TaskContext {
+                file_paths: vec![workspace.path().join("lib.rs")],
+                user_intent: "refactor lib.rs".to_string(),
+                ..Default::default()
+            },
+            additional_instructions: None,
+        };
+
+        let AgentResult::Suggestions { items: suggestions } =
+            agent.execute(task, &toolkit).await.unwrap()
+        else {
+            panic!("expected suggestions");
+        };
+        let patches: Vec<&str> = suggestions
+            .iter()
+            .filter_map(|s| s.code_change.as_deref())
+            .collect();
+        assert_eq!(patches.len(), 2);
+        assert!(patches[0].contains("*** Update File: lib.rs"));
+
+        let without_comments = patched_content(workspace.path(), "lib.rs", patches[0]);
+        assert!(!without_comments.contains("old_compute"));
+        assert!(without_comments.contains("let x = compute();"));
+
+        let without_dead_code = patched_content(workspace.path(), "lib.rs", patches[1]);
+        assert!(!without_dead_code.contains("unused"));
+        assert!(without_dead_code.contains("return 1;"));
+    }
+}
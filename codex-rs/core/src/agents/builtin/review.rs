@@ -15,6 +15,20 @@ use crate::agents::Severity;
 use crate::agents::Task;
 use crate::agents::TaskContext;
 use crate::agents::permissions::FileAccessPolicy;
+use crate::agents::routing::RoutingHints;
+
+use super::security::CODE_FILE_EXTENSIONS;
+
+/// Keywords in the user's intent that activate the review agent.
+const ACTIVATION_KEYWORDS: &[&str] = &["review", "check", "analyze", "quality", "lint"];
+
+/// ID of the agent that receives crypto-related files for a deeper look.
+const SECURITY_AGENT_ID: &str = "security";
+
+/// Terms that mark a file as handling cryptography or secrets.
+const CRYPTO_TERMS: &[&str] = &[
+    "md5", "sha1", "encrypt", "decrypt", "cipher", "crypto", "password", "token",
+];
 
 /// Code review agent that analyzes code for quality, maintainability, and best practices.
 ///
@@ -33,12 +47,12 @@ impl ReviewAgent {
     pub fn new() -> Self {
         Self {
             permissions: AgentPermissions {
-                file_access: FileAccessPolicy::ReadOnly,
+                file_access: FileAccessPolicy::read_only(),
                 shell_execution: false,
                 network_access: false,
                 allowed_tools: vec![],
                 max_iterations: 5,
-                can_delegate: false,
+                can_delegate: true,
             },
         }
     }
@@ -46,13 +60,15 @@ impl ReviewAgent {
     /// Analyzes code files and generates review findings.
     ///
     /// This method reads each file and applies heuristic analysis to identify
-    /// common code quality issues.
+    /// common code quality issues. It also returns the files that deal with
+    /// cryptography or secrets so they can be handed to the security agent.
     async fn analyze_code(
         &self,
         files: &[PathBuf],
         toolkit: &AgentToolkit,
-    ) -> anyhow::Result<Vec<CodeReviewFinding>> {
+    ) -> anyhow::Result<(Vec<CodeReviewFinding>, Vec<PathBuf>)> {
         let mut findings = Vec::new();
+        let mut crypto_files = Vec::new();
 
         for file_path in files {
             // Skip non-text files
@@ -64,6 +80,9 @@ impl ReviewAgent {
                 Ok(content) => {
                     let file_findings = self.analyze_file_content(&content, file_path);
                     findings.extend(file_findings);
+                    if mentions_crypto(&content) {
+                        crypto_files.push(file_path.clone());
+                    }
                 }
                 Err(_e) => {
                     // Silently skip files that can't be read (permissions, etc.)
@@ -72,7 +91,30 @@ impl ReviewAgent {
             }
         }
 
-        Ok(findings)
+        Ok((findings, crypto_files))
+    }
+
+    /// Asks the security agent to review `files` and returns its findings.
+    async fn delegate_crypto_review(
+        &self,
+        task: &Task,
+        files: Vec<PathBuf>,
+        toolkit: &AgentToolkit,
+    ) -> anyhow::Result<AgentResult> {
+        let sub_task = Task {
+            context: TaskContext {
+                file_paths: files,
+                ..task.context.clone()
+            },
+            additional_instructions: Some(
+                "These files handle cryptography or secrets; check them for weak algorithms, \
+                 hardcoded credentials and unsafe key handling."
+                    .to_string(),
+            ),
+        };
+        toolkit
+            .delegate(&AgentId::from(SECURITY_AGENT_ID), sub_task)
+            .await
     }
 
     /// Analyzes a single file's content for code quality issues.
@@ -315,18 +357,48 @@ impl Agent for ReviewAgent {
 
     fn can_handle(&self, context: &TaskContext) -> ActivationScore {
         let intent = context.user_intent.to_lowercase();
-        let keywords = ["review", "check", "analyze", "quality", "lint"];
-
-        let matches = keywords.iter().filter(|k| intent.contains(*k)).count();
+        let matches = ACTIVATION_KEYWORDS
+            .iter()
+            .filter(|k| intent.contains(*k))
+            .count();
 
         // Score based on keyword matches (0.0 to 1.0)
         ActivationScore::new(matches as f64 * 0.25)
     }
 
+    fn routing_hints(&self) -> RoutingHints {
+        RoutingHints {
+            keywords: ACTIVATION_KEYWORDS
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            file_extensions: CODE_FILE_EXTENSIONS
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        }
+    }
+
     async fn execute(&self, task: Task, toolkit: &AgentToolkit) -> anyhow::Result<AgentResult> {
-        let findings = self.analyze_code(&task.context.file_paths, toolkit).await?;
+        let (findings, crypto_files) = self.analyze_code(&task.context.file_paths, toolkit).await?;
+        let review = AgentResult::CodeReview { findings };
 
-        Ok(AgentResult::CodeReview { findings })
+        if crypto_files.is_empty() || !toolkit.can_delegate() {
+            return Ok(review);
+        }
+        match self
+            .delegate_crypto_review(&task, crypto_files, toolkit)
+            .await
+        {
+            Ok(security) => Ok(AgentResult::merge(vec![
+                (self.id(), review),
+                (AgentId::from(SECURITY_AGENT_ID), security),
+            ])),
+            Err(e) => {
+                tracing::warn!("review agent could not delegate to security agent: {e:#}");
+                Ok(review)
+            }
+        }
     }
 
     fn permissions(&self) -> &AgentPermissions {
@@ -348,6 +420,12 @@ impl Default for ReviewAgent {
     }
 }
 
+/// Returns true if `content` mentions any of [`CRYPTO_TERMS`].
+fn mentions_crypto(content: &str) -> bool {
+    let content = content.to_lowercase();
+    CRYPTO_TERMS.iter().any(|term| content.contains(term))
+}
+
 /// Checks if a file is likely binary based on extension.
 fn is_binary_file(path: &Path) -> bool {
     if let Some(ext) = path.extension() {
@@ -409,6 +487,7 @@ mod tests {
             git_context: None,
             execution_mode: crate::agents::ExecutionMode::Interactive,
             user_intent: "Please review this code".to_string(),
+            activation_hints: vec![],
         };
 
         let score = agent.can_handle(&context);
@@ -424,6 +503,7 @@ mod tests {
             git_context: None,
             execution_mode: crate::agents::ExecutionMode::Interactive,
             user_intent: "check code quality and analyze for issues".to_string(),
+            activation_hints: vec![],
         };
 
         let score = agent.can_handle(&context);
@@ -439,6 +519,7 @@ mod tests {
             git_context: None,
             execution_mode: crate::agents::ExecutionMode::Interactive,
             user_intent: "write a new feature".to_string(),
+            activation_hints: vec![],
         };
 
         let score = agent.can_handle(&context);
@@ -558,4 +639,40 @@ fn bad_function() {
                 .any(|x| x == "Error Handling")
         );
     }
+
+    #[tokio::test]
+    async fn test_execute_delegates_crypto_files_to_security() {
+        use crate::agents::AgentRouter;
+        use std::sync::Arc;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        tokio::fs::write(
+            temp.path().join("auth.py"),
+            "def hash(password):\n    return hashlib.md5(password.encode()).hexdigest()\n",
+        )
+        .await
+        .unwrap();
+
+        let agent = ReviewAgent::new();
+        let toolkit = AgentToolkit::new(
+            agent.id(),
+            agent.permissions().clone(),
+            temp.path().to_path_buf(),
+        )
+        .with_delegation(Arc::new(AgentRouter::with_builtin_agents()), None);
+        let task = Task {
+            context: TaskContext {
+                file_paths: vec![PathBuf::from("auth.py")],
+                user_intent: "review auth".to_string(),
+                ..Default::default()
+            },
+            additional_instructions: None,
+        };
+
+        let AgentResult::CodeReview { findings } = agent.execute(task, &toolkit).await.unwrap()
+        else {
+            panic!("expected code review");
+        };
+        assert!(findings.iter().any(|f| f.category.contains("MD5")));
+    }
 }
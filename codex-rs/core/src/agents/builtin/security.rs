@@ -18,6 +18,7 @@ use crate::agents::Severity;
 use crate::agents::Task;
 use crate::agents::TaskContext;
 use crate::agents::permissions::FileAccessPolicy;
+use crate::agents::routing::RoutingHints;
 
 /// Vulnerability pattern definition.
 #[derive(Clone)]
@@ -209,7 +210,7 @@ impl SecurityAgent {
     pub fn new() -> Self {
         Self {
             permissions: AgentPermissions {
-                file_access: FileAccessPolicy::ReadOnly,
+                file_access: FileAccessPolicy::read_only(),
                 shell_execution: false,
                 network_access: false,
                 allowed_tools: vec![],
@@ -318,24 +319,30 @@ impl Agent for SecurityAgent {
 
     fn can_handle(&self, context: &TaskContext) -> ActivationScore {
         let intent = context.user_intent.to_lowercase();
-        let keywords = [
-            "security",
-            "vulnerability",
-            "vulnerabilities",
-            "audit",
-            "secure",
-            "exploit",
-            "cve",
-            "sql injection",
-            "xss",
-        ];
-
-        let matches = keywords.iter().filter(|k| intent.contains(*k)).count();
+        let matches = ACTIVATION_KEYWORDS
+            .iter()
+            .filter(|k| intent.contains(*k))
+            .count();
 
         // Higher weight for security keywords
         ActivationScore::new(matches as f64 * 0.3)
     }
 
+    fn routing_hints(&self) -> RoutingHints {
+        RoutingHints {
+            keywords: ACTIVATION_KEYWORDS
+                .iter()
+                .chain(DOMAIN_KEYWORDS)
+                .map(ToString::to_string)
+                .collect(),
+            file_extensions: CODE_FILE_EXTENSIONS
+                .iter()
+                .chain(CONFIG_FILE_EXTENSIONS)
+                .map(ToString::to_string)
+                .collect(),
+        }
+    }
+
     async fn execute(&self, task: Task, toolkit: &AgentToolkit) -> anyhow::Result<AgentResult> {
         let findings = self
             .scan_for_vulnerabilities(&task.context.file_paths, toolkit)
@@ -365,30 +372,36 @@ impl Default for SecurityAgent {
     }
 }
 
+/// Extensions of source files the built-in agents analyze.
+pub(super) const CODE_FILE_EXTENSIONS: &[&str] = &[
+    "rs", "py", "js", "ts", "jsx", "tsx", "java", "go", "php", "rb", "c", "cpp", "cs", "swift",
+    "kt",
+];
+
+/// Configuration formats that commonly hold credentials or security settings.
+const CONFIG_FILE_EXTENSIONS: &[&str] = &["env", "yml", "yaml", "toml", "json", "ini", "tf"];
+
+/// Keywords in the user's intent that activate the security agent.
+const ACTIVATION_KEYWORDS: &[&str] = &[
+    "security",
+    "vulnerability",
+    "vulnerabilities",
+    "audit",
+    "secure",
+    "exploit",
+    "cve",
+    "sql injection",
+    "xss",
+];
+
+/// Additional words that describe the agent's domain for routing.
+const DOMAIN_KEYWORDS: &[&str] = &["secret", "credential", "crypto", "auth", "password"];
+
 /// Checks if a file should be scanned for security issues.
-fn is_code_file(path: &Path) -> bool {
-    if let Some(ext) = path.extension() {
-        let ext_str = ext.to_string_lossy().to_lowercase();
-        matches!(
-            ext_str.as_str(),
-            "rs" | "py"
-                | "js"
-                | "ts"
-                | "jsx"
-                | "tsx"
-                | "java"
-                | "go"
-                | "php"
-                | "rb"
-                | "c"
-                | "cpp"
-                | "cs"
-                | "swift"
-                | "kt"
-        )
-    } else {
-        false
-    }
+pub(super) fn is_code_file(path: &Path) -> bool {
+    path.extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .is_some_and(|ext| CODE_FILE_EXTENSIONS.contains(&ext.as_str()))
 }
 
 #[cfg(test)]
@@ -427,6 +440,7 @@ mod tests {
             git_context: None,
             execution_mode: crate::agents::ExecutionMode::Interactive,
             user_intent: "security audit".to_string(),
+            activation_hints: vec![],
         };
 
         let score = agent.can_handle(&context);
@@ -442,6 +456,7 @@ mod tests {
             git_context: None,
             execution_mode: crate::agents::ExecutionMode::Interactive,
             user_intent: "check for security vulnerabilities and sql injection".to_string(),
+            activation_hints: vec![],
         };
 
         let score = agent.can_handle(&context);
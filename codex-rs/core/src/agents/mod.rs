@@ -7,9 +7,14 @@
 //! # Architecture
 //!
 //! - `router`: Context-based agent selection and routing
+//! - `routing`: Semantic scoring with explainable score breakdowns
+//! - `runtime`: Model-driven execution loop for agents
 //! - `toolkit`: Agent execution toolkit with permission enforcement
 //! - `permissions`: Permission model for agent operations
+//! - `pipeline`: Declarative multi-agent pipelines
 //! - `builtin`: Built-in agents shipped with Codex
+//! - `user`: User-defined agents loaded from Markdown files
+//! - `watcher`: Hot-reload of user-defined agents
 //!
 //! # Core Concepts
 //!
@@ -27,25 +32,69 @@
 //! indicating their suitability for the task. The router selects the
 //! highest-scoring agent above the activation threshold.
 //!
+//! In semantic routing mode the router adds boosts for task files and git
+//! changes that match an agent's `RoutingHints`, for the command's
+//! activation hints, and optionally for the model's intent classification.
+//! Each boost is recorded in a `ScoreBreakdown` with a readable reason.
+//!
+//! ## User-Defined Agents
+//!
+//! Agents can also be declared as Markdown files with YAML frontmatter in
+//! `~/.codex/agents/` or a repository's `.codex/agents/`. The frontmatter
+//! sets the ID, activation keywords and permissions; the body is the system
+//! prompt. `AgentWatcher` reloads them into the router when the files change.
+//!
+//! ## Runtime
+//!
+//! `AgentRuntime` runs an agent as a multi-turn model conversation using the
+//! agent's system prompt, serving tool calls through the toolkit until the
+//! model produces a final answer or `max_iterations` is reached. The agent's
+//! own `execute` remains available as a fast offline fallback.
+//!
 //! ## Toolkit
 //!
 //! Agents execute tasks using a toolkit that provides:
 //! - File system access (with permission validation)
 //! - Shell command execution (through execpolicy)
+//! - Delegation of sub-tasks to other agents
 //! - Workspace context and utilities
+//!
+//! ## Delegation and Pipelines
+//!
+//! Agents whose permissions set `can_delegate` may hand sub-tasks to other
+//! agents through `AgentToolkit::delegate`. Delegation chains are limited to
+//! `MAX_DELEGATION_DEPTH` agents and may not revisit an agent. Commands can
+//! instead name a fixed sequence of agents with a `pipeline` such as
+//! `review -> security -> summarize`; results are merged with
+//! `AgentResult::merge`.
 
 use async_trait::async_trait;
+use codex_protocol::agents::AgentFinding;
+use codex_protocol::agents::AgentFindingSeverity;
+use codex_protocol::agents::AgentRunResult;
+use codex_protocol::agents::AgentRunResultEntry;
+use codex_protocol::agents::AgentSuggestion;
+use serde::Deserialize;
 use std::collections::HashMap;
 use std::path::PathBuf;
 
 pub mod builtin;
 pub mod permissions;
+pub mod pipeline;
 pub mod router;
+pub mod routing;
+pub mod runtime;
 pub mod toolkit;
+pub mod user;
+pub mod watcher;
 
 // Re-export key types
 pub use permissions::AgentPermissions;
+pub use pipeline::AgentPipeline;
 pub use router::AgentRouter;
+pub use routing::RoutingHints;
+pub use routing::ScoreBreakdown;
+pub use runtime::AgentRuntime;
 pub use toolkit::AgentToolkit;
 
 /// Unique identifier for an agent.
@@ -106,6 +155,12 @@ pub trait Agent: Send + Sync {
 
     /// Returns the system prompt defining agent persona.
     fn system_prompt(&self) -> &str;
+
+    /// Returns the signals semantic routing uses in addition to
+    /// `can_handle`. Agents without hints are scored on `can_handle` alone.
+    fn routing_hints(&self) -> RoutingHints {
+        RoutingHints::default()
+    }
 }
 
 /// Context for task analysis and execution.
@@ -121,6 +176,8 @@ pub struct TaskContext {
     pub execution_mode: ExecutionMode,
     /// User's intent/instructions.
     pub user_intent: String,
+    /// Activation hints from the command that created the task.
+    pub activation_hints: Vec<String>,
 }
 
 /// Git context information.
@@ -165,6 +222,147 @@ pub enum AgentResult {
     },
     /// Suggestions for improvements.
     Suggestions { items: Vec<Suggestion> },
+    /// Results from several agents, in the order they ran.
+    Combined {
+        results: Vec<(AgentId, AgentResult)>,
+    },
+}
+
+impl AgentResult {
+    /// Merges results produced by several agents.
+    ///
+    /// Results of the same kind are concatenated (findings with findings,
+    /// suggestions with suggestions); anything else is kept side by side
+    /// in [`AgentResult::Combined`].
+    pub fn merge(results: Vec<(AgentId, AgentResult)>) -> AgentResult {
+        // Flatten nested merges so delegation chains produce a single level.
+        let mut results: Vec<(AgentId, AgentResult)> = results
+            .into_iter()
+            .flat_map(|(id, result)| match result {
+                AgentResult::Combined { results } => results,
+                other => vec![(id, other)],
+            })
+            .collect();
+
+        if results.len() == 1
+            && let Some((_, result)) = results.pop()
+        {
+            return result;
+        }
+        if results
+            .iter()
+            .all(|(_, r)| matches!(r, AgentResult::CodeReview { .. }))
+        {
+            let findings = results
+                .into_iter()
+                .flat_map(|(_, r)| match r {
+                    AgentResult::CodeReview { findings } => findings,
+                    _ => Vec::new(),
+                })
+                .collect();
+            return AgentResult::CodeReview { findings };
+        }
+        if results
+            .iter()
+            .all(|(_, r)| matches!(r, AgentResult::Suggestions { .. }))
+        {
+            let items = results
+                .into_iter()
+                .flat_map(|(_, r)| match r {
+                    AgentResult::Suggestions { items } => items,
+                    _ => Vec::new(),
+                })
+                .collect();
+            return AgentResult::Suggestions { items };
+        }
+
+        AgentResult::Combined { results }
+    }
+
+    /// Returns every code review finding, including those of combined
+    /// results.
+    pub fn findings(&self) -> Vec<&CodeReviewFinding> {
+        match self {
+            AgentResult::CodeReview { findings } => findings.iter().collect(),
+            AgentResult::Combined { results } => {
+                results.iter().flat_map(|(_, r)| r.findings()).collect()
+            }
+            AgentResult::Analysis { .. } | AgentResult::Suggestions { .. } => Vec::new(),
+        }
+    }
+
+    /// Returns true if any finding is at least as severe as `threshold`.
+    pub fn has_findings_at_least(&self, threshold: Severity) -> bool {
+        self.findings()
+            .iter()
+            .any(|finding| finding.severity.is_at_least(threshold))
+    }
+}
+
+impl From<&AgentResult> for AgentRunResult {
+    fn from(result: &AgentResult) -> Self {
+        match result {
+            AgentResult::CodeReview { findings } => AgentRunResult::CodeReview {
+                findings: findings.iter().map(AgentFinding::from).collect(),
+            },
+            AgentResult::Analysis { summary, details } => AgentRunResult::Analysis {
+                summary: summary.clone(),
+                details: details
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect(),
+            },
+            AgentResult::Suggestions { items } => AgentRunResult::Suggestions {
+                items: items
+                    .iter()
+                    .map(|item| AgentSuggestion {
+                        title: item.title.clone(),
+                        description: item.description.clone(),
+                        code_change: item.code_change.clone(),
+                    })
+                    .collect(),
+            },
+            AgentResult::Combined { results } => AgentRunResult::Combined {
+                results: results
+                    .iter()
+                    .map(|(id, result)| AgentRunResultEntry {
+                        agent_id: id.as_str().to_string(),
+                        result: result.into(),
+                    })
+                    .collect(),
+            },
+        }
+    }
+}
+
+impl From<AgentRunResult> for AgentResult {
+    fn from(result: AgentRunResult) -> Self {
+        match result {
+            AgentRunResult::CodeReview { findings } => AgentResult::CodeReview {
+                findings: findings.into_iter().map(CodeReviewFinding::from).collect(),
+            },
+            AgentRunResult::Analysis { summary, details } => AgentResult::Analysis {
+                summary,
+                details: details.into_iter().collect(),
+            },
+            AgentRunResult::Suggestions { items } => AgentResult::Suggestions {
+                items: items
+                    .into_iter()
+                    .map(|item| Suggestion {
+                        title: item.title,
+                        description: item.description,
+                        code_change: item.code_change,
+                    })
+                    .collect(),
+            },
+            AgentRunResult::Combined { results } => AgentResult::Combined {
+                results: results
+                    .into_iter()
+                    .map(|entry| (AgentId::from(&entry.agent_id), entry.result.into()))
+                    .collect(),
+            },
+        }
+    }
 }
 
 /// Code review finding.
@@ -177,14 +375,76 @@ pub struct CodeReviewFinding {
     pub line_number: Option<usize>,
 }
 
+impl From<&CodeReviewFinding> for AgentFinding {
+    fn from(finding: &CodeReviewFinding) -> Self {
+        AgentFinding {
+            severity: finding.severity.into(),
+            category: finding.category.clone(),
+            message: finding.message.clone(),
+            path: finding.location.clone(),
+            line: finding
+                .line_number
+                .and_then(|line| u32::try_from(line).ok()),
+        }
+    }
+}
+
+impl From<AgentFinding> for CodeReviewFinding {
+    fn from(finding: AgentFinding) -> Self {
+        CodeReviewFinding {
+            severity: finding.severity.into(),
+            category: finding.category,
+            message: finding.message,
+            location: finding.path,
+            line_number: finding.line.map(|line| line as usize),
+        }
+    }
+}
+
 /// Severity level for findings.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Severity {
     Error,
     Warning,
     Info,
 }
 
+impl Severity {
+    /// Returns true if `self` is as severe as `threshold` or more.
+    pub fn is_at_least(self, threshold: Severity) -> bool {
+        self.rank() >= threshold.rank()
+    }
+
+    fn rank(self) -> u8 {
+        match self {
+            Severity::Info => 0,
+            Severity::Warning => 1,
+            Severity::Error => 2,
+        }
+    }
+}
+
+impl From<Severity> for AgentFindingSeverity {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Error => AgentFindingSeverity::Error,
+            Severity::Warning => AgentFindingSeverity::Warning,
+            Severity::Info => AgentFindingSeverity::Info,
+        }
+    }
+}
+
+impl From<AgentFindingSeverity> for Severity {
+    fn from(severity: AgentFindingSeverity) -> Self {
+        match severity {
+            AgentFindingSeverity::Error => Severity::Error,
+            AgentFindingSeverity::Warning => Severity::Warning,
+            AgentFindingSeverity::Info => Severity::Info,
+        }
+    }
+}
+
 /// Suggestion for improvement.
 #[derive(Debug, Clone)]
 pub struct Suggestion {
@@ -197,6 +457,128 @@ pub struct Suggestion {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_merge_results() {
+        let finding = |message: &str| CodeReviewFinding {
+            severity: Severity::Warning,
+            category: "Test".to_string(),
+            message: message.to_string(),
+            location: None,
+            line_number: None,
+        };
+
+        let merged = AgentResult::merge(vec![
+            (
+                AgentId::from("review"),
+                AgentResult::CodeReview {
+                    findings: vec![finding("a")],
+                },
+            ),
+            (
+                AgentId::from("security"),
+                AgentResult::CodeReview {
+                    findings: vec![finding("b")],
+                },
+            ),
+        ]);
+        let AgentResult::CodeReview { findings } = merged else {
+            panic!("expected code review");
+        };
+        assert_eq!(findings.len(), 2);
+
+        let merged = AgentResult::merge(vec![
+            (
+                AgentId::from("review"),
+                AgentResult::CodeReview {
+                    findings: vec![finding("a")],
+                },
+            ),
+            (
+                AgentId::from("summarize"),
+                AgentResult::Analysis {
+                    summary: "ok".to_string(),
+                    details: HashMap::new(),
+                },
+            ),
+        ]);
+        let AgentResult::Combined { results } = merged else {
+            panic!("expected combined result");
+        };
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[1].0, AgentId::from("summarize"));
+    }
+
+    #[test]
+    fn test_findings_severity_threshold() {
+        let result = AgentResult::Combined {
+            results: vec![
+                (
+                    AgentId::from("review"),
+                    AgentResult::CodeReview {
+                        findings: vec![CodeReviewFinding {
+                            severity: Severity::Warning,
+                            category: "Test".to_string(),
+                            message: "a".to_string(),
+                            location: None,
+                            line_number: None,
+                        }],
+                    },
+                ),
+                (
+                    AgentId::from("summarize"),
+                    AgentResult::Analysis {
+                        summary: "ok".to_string(),
+                        details: HashMap::new(),
+                    },
+                ),
+            ],
+        };
+
+        assert_eq!(result.findings().len(), 1);
+        assert!(result.has_findings_at_least(Severity::Info));
+        assert!(result.has_findings_at_least(Severity::Warning));
+        assert!(!result.has_findings_at_least(Severity::Error));
+    }
+
+    #[test]
+    fn test_protocol_result_round_trip() {
+        let result = AgentResult::Combined {
+            results: vec![
+                (
+                    AgentId::from("security"),
+                    AgentResult::CodeReview {
+                        findings: vec![CodeReviewFinding {
+                            severity: Severity::Error,
+                            category: "Injection".to_string(),
+                            message: "Unescaped input".to_string(),
+                            location: Some(PathBuf::from("src/db.rs")),
+                            line_number: Some(7),
+                        }],
+                    },
+                ),
+                (
+                    AgentId::from("refactor"),
+                    AgentResult::Suggestions {
+                        items: vec![Suggestion {
+                            title: "Extract helper".to_string(),
+                            description: "Duplicate logic".to_string(),
+                            code_change: None,
+                        }],
+                    },
+                ),
+            ],
+        };
+
+        let protocol = AgentRunResult::from(&result);
+        let findings = protocol.findings();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, AgentFindingSeverity::Error);
+        assert_eq!(findings[0].line, Some(7));
+
+        let back = AgentResult::from(protocol.clone());
+        assert_eq!(AgentRunResult::from(&back), protocol);
+    }
+
     #[test]
     fn test_agent_id() {
         let id = AgentId::from("test-agent");
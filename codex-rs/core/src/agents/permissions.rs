@@ -1,6 +1,14 @@
 //! Agent permission model.
 
 use std::path::Path;
+use std::path::PathBuf;
+
+use globset::GlobBuilder;
+use globset::GlobSet;
+use globset::GlobSetBuilder;
+
+/// Paths no agent may read or write, regardless of its file access policy.
+pub const DEFAULT_DENY_PATTERNS: &[&str] = &[".env", "**/secrets/**", "~/.codex/auth.json"];
 
 /// Agent permissions defining allowed operations.
 #[derive(Debug, Clone)]
@@ -31,9 +39,14 @@ impl AgentPermissions {
     pub fn can_read_file(&self, path: &Path) -> bool {
         match &self.file_access {
             FileAccessPolicy::NoAccess => false,
-            FileAccessPolicy::ReadOnly | FileAccessPolicy::ReadWrite { .. } => {
-                self.matches_patterns(path)
+            FileAccessPolicy::ReadOnly {
+                allow_patterns,
+                deny_patterns,
             }
+            | FileAccessPolicy::ReadWrite {
+                allow_patterns,
+                deny_patterns,
+            } => Self::is_allowed(path, allow_patterns, deny_patterns),
         }
     }
 
@@ -43,67 +56,80 @@ impl AgentPermissions {
             FileAccessPolicy::ReadWrite {
                 allow_patterns,
                 deny_patterns,
-            } => {
-                // Check deny patterns first
-                if deny_patterns.iter().any(|p| Self::matches(path, p)) {
-                    return false;
-                }
-                // Then check allow patterns
-                allow_patterns.iter().any(|p| Self::matches(path, p))
-            }
+            } => Self::is_allowed(path, allow_patterns, deny_patterns),
             _ => false,
         }
     }
 
-    fn matches_patterns(&self, _path: &Path) -> bool {
-        // For now, allow all reads if policy allows reading
-        // TODO: Implement pattern matching
-        true
+    /// Checks whether a path is excluded by the default deny list or by the
+    /// policy's deny patterns.
+    ///
+    /// Unlike [`AgentPermissions::can_read_file`], this ignores allow
+    /// patterns, so it can be applied to absolute paths when the allow
+    /// patterns are written relative to the workspace.
+    pub fn is_denied(&self, path: &Path) -> bool {
+        let deny_patterns = match &self.file_access {
+            FileAccessPolicy::NoAccess => return true,
+            FileAccessPolicy::ReadOnly { deny_patterns, .. }
+            | FileAccessPolicy::ReadWrite { deny_patterns, .. } => deny_patterns,
+        };
+        // Fail closed: a deny list that does not compile denies everything.
+        deny_set(deny_patterns)
+            .map(|deny| deny.is_match(path))
+            .unwrap_or(true)
     }
 
-    fn matches(path: &Path, pattern: &str) -> bool {
-        // Simple glob pattern matching implementation
-        // Support for basic patterns like "**/*.rs" and "**/secrets/**"
-        let path_str = path.to_string_lossy();
-
-        if pattern == "**" || pattern == "*" {
-            return true;
+    fn is_allowed(path: &Path, allow_patterns: &[String], deny_patterns: &[String]) -> bool {
+        let Ok(deny) = deny_set(deny_patterns) else {
+            return false;
+        };
+        if deny.is_match(path) {
+            return false;
         }
 
-        if pattern.contains("**") {
-            // Handle recursive patterns like "**/*.rs" or "**/secrets/**"
-            if let Some((prefix, suffix)) = pattern.split_once("**") {
-                // Check prefix if not empty
-                if !prefix.is_empty() && !path_str.starts_with(prefix) {
-                    return false;
-                }
-
-                // Handle suffix patterns
-                if suffix.starts_with("/*") {
-                    // Pattern like "**/*.rs"
-                    if let Some(extension) = suffix.strip_prefix("/*.") {
-                        return path_str.ends_with(&format!(".{extension}"));
-                    }
-                } else if suffix.starts_with('/') {
-                    // Pattern like "**/secrets/**" or "**/secrets/"
-                    let dir_pattern = suffix.trim_start_matches('/').trim_end_matches("/**");
-                    return path_str.contains(&format!("/{dir_pattern}/"))
-                        || path_str.contains(&format!("\\{dir_pattern}\\"))
-                        || path_str.contains(&format!("/{dir_pattern}"))
-                        || path_str.contains(&format!("\\{dir_pattern}"));
-                }
-            }
-        } else if pattern.starts_with("*.") {
-            // Simple extension pattern like "*.rs"
-            if let Some(extension) = pattern.strip_prefix("*.") {
-                return path_str.ends_with(&format!(".{extension}"));
-            } else {
-                return false;
-            }
-        }
+        build_glob_set(allow_patterns.iter().map(String::as_str))
+            .map(|allow| allow.is_match(path))
+            .unwrap_or(false)
+    }
+}
 
-        // Default: exact match or contains
-        path_str.contains(pattern)
+fn deny_set(deny_patterns: &[String]) -> Result<GlobSet, globset::Error> {
+    build_glob_set(
+        DEFAULT_DENY_PATTERNS
+            .iter()
+            .copied()
+            .chain(deny_patterns.iter().map(String::as_str)),
+    )
+}
+
+/// Compiles gitignore-style patterns into a single matcher.
+///
+/// Patterns without a `/` match at any depth (`*.rs` behaves like
+/// `**/*.rs`), `*` does not cross directory boundaries, and a leading `~/`
+/// expands to the user's home directory.
+fn build_glob_set<'a>(
+    patterns: impl IntoIterator<Item = &'a str>,
+) -> Result<GlobSet, globset::Error> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let pattern = expand_pattern(pattern);
+        builder.add(GlobBuilder::new(&pattern).literal_separator(true).build()?);
+    }
+    builder.build()
+}
+
+fn expand_pattern(pattern: &str) -> String {
+    if let Some(rest) = pattern.strip_prefix("~/") {
+        return match dirs::home_dir() {
+            Some(home) => home.join(rest).to_string_lossy().into_owned(),
+            // Without a home directory the pattern can only match by name.
+            None => format!("**/{rest}"),
+        };
+    }
+    if pattern.contains('/') {
+        pattern.to_string()
+    } else {
+        format!("**/{pattern}")
     }
 }
 
@@ -112,8 +138,11 @@ impl AgentPermissions {
 pub enum FileAccessPolicy {
     /// No file access allowed.
     NoAccess,
-    /// Read-only access to all files.
-    ReadOnly,
+    /// Read-only access with pattern-based filtering.
+    ReadOnly {
+        allow_patterns: Vec<String>,
+        deny_patterns: Vec<String>,
+    },
     /// Read-write access with pattern-based filtering.
     ReadWrite {
         allow_patterns: Vec<String>,
@@ -121,15 +150,74 @@ pub enum FileAccessPolicy {
     },
 }
 
+impl FileAccessPolicy {
+    /// Read-only access to every file not covered by the default deny list.
+    pub fn read_only() -> Self {
+        Self::ReadOnly {
+            allow_patterns: vec!["**".to_string()],
+            deny_patterns: Vec::new(),
+        }
+    }
+}
+
+/// Resolves `path` against `workspace_root` and checks that the result stays
+/// inside the workspace.
+///
+/// Symlinks are resolved through the deepest existing ancestor so that links
+/// pointing outside the workspace are rejected. The remaining components of a
+/// path that does not exist yet must not contain `..`.
+pub fn resolve_in_workspace(workspace_root: &Path, path: &Path) -> std::io::Result<PathBuf> {
+    let root = dunce::canonicalize(workspace_root)?;
+    let joined = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        workspace_root.join(path)
+    };
+
+    let mut existing = joined.as_path();
+    let mut missing = Vec::new();
+    let mut resolved = loop {
+        match dunce::canonicalize(existing) {
+            Ok(resolved) => break resolved,
+            Err(err) => {
+                let (Some(parent), Some(name)) = (existing.parent(), existing.file_name()) else {
+                    return Err(err);
+                };
+                missing.push(name.to_os_string());
+                existing = parent;
+            }
+        }
+    };
+    for name in missing.into_iter().rev() {
+        if name == ".." {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::PermissionDenied,
+                format!("{} escapes the workspace", path.display()),
+            ));
+        }
+        resolved.push(name);
+    }
+
+    if resolved.starts_with(&root) {
+        Ok(resolved)
+    } else {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            format!("{} is outside the workspace", path.display()),
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::path::PathBuf;
+    use tempfile::TempDir;
 
     #[test]
     fn test_read_only_permissions() {
         let perms = AgentPermissions {
-            file_access: FileAccessPolicy::ReadOnly,
+            file_access: FileAccessPolicy::read_only(),
             ..Default::default()
         };
 
@@ -157,5 +245,71 @@ mod tests {
 
         assert!(perms.can_read_file(&PathBuf::from("src/main.rs")));
         assert!(perms.can_write_file(&PathBuf::from("src/main.rs")));
+        assert!(!perms.can_write_file(&PathBuf::from("src/secrets/key.rs")));
+        assert!(!perms.can_write_file(&PathBuf::from("Cargo.toml")));
+    }
+
+    #[test]
+    fn test_read_only_patterns() {
+        let perms = AgentPermissions {
+            file_access: FileAccessPolicy::ReadOnly {
+                allow_patterns: vec!["src/**".into(), "*.md".into()],
+                deny_patterns: vec!["src/generated/**".into()],
+            },
+            ..Default::default()
+        };
+
+        assert!(perms.can_read_file(&PathBuf::from("src/lib.rs")));
+        assert!(perms.can_read_file(&PathBuf::from("docs/guide.md")));
+        assert!(!perms.can_read_file(&PathBuf::from("tests/lib.rs")));
+        assert!(!perms.can_read_file(&PathBuf::from("src/generated/api.rs")));
+        assert!(!perms.can_write_file(&PathBuf::from("src/lib.rs")));
+    }
+
+    #[test]
+    fn test_default_deny_list() {
+        let perms = AgentPermissions {
+            file_access: FileAccessPolicy::read_only(),
+            ..Default::default()
+        };
+
+        assert!(!perms.can_read_file(&PathBuf::from(".env")));
+        assert!(!perms.can_read_file(&PathBuf::from("app/.env")));
+        assert!(!perms.can_read_file(&PathBuf::from("config/secrets/token")));
+        if let Some(home) = dirs::home_dir() {
+            let auth = home.join(".codex").join("auth.json");
+            assert!(!perms.can_read_file(&auth));
+            assert!(perms.is_denied(&auth));
+        }
+        assert!(!perms.is_denied(&PathBuf::from("/workspace/src/main.rs")));
+    }
+
+    #[test]
+    fn test_resolve_in_workspace_rejects_escapes() {
+        let outside = TempDir::new().unwrap();
+        let workspace = TempDir::new().unwrap();
+        std::fs::write(outside.path().join("secret.txt"), "x").unwrap();
+        std::fs::write(workspace.path().join("lib.rs"), "x").unwrap();
+        let root = dunce::canonicalize(workspace.path()).unwrap();
+
+        assert_eq!(
+            resolve_in_workspace(workspace.path(), Path::new("lib.rs")).unwrap(),
+            root.join("lib.rs")
+        );
+        assert_eq!(
+            resolve_in_workspace(workspace.path(), Path::new("new/file.rs")).unwrap(),
+            root.join("new").join("file.rs")
+        );
+        assert!(resolve_in_workspace(workspace.path(), Path::new("../secret.txt")).is_err());
+        assert!(resolve_in_workspace(workspace.path(), Path::new("missing/../../x")).is_err());
+        assert!(
+            resolve_in_workspace(workspace.path(), &outside.path().join("secret.txt")).is_err()
+        );
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(outside.path(), workspace.path().join("link")).unwrap();
+            assert!(resolve_in_workspace(workspace.path(), Path::new("link/secret.txt")).is_err());
+        }
     }
 }
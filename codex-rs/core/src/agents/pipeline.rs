@@ -0,0 +1,248 @@
+//! Declarative multi-agent pipelines.
+//!
+//! A pipeline runs agents one after another on the same task, written as
+//! `review -> security -> summarize`. Each stage sees the results of the
+//! stages before it, and the results of all stages are merged with
+//! [`AgentResult::merge`].
+
+use super::AgentId;
+use super::AgentResult;
+use super::AgentRouter;
+use super::AgentRuntime;
+use super::AgentToolkit;
+use super::Task;
+use super::runtime::run_with_fallback;
+use crate::commands::agents::AgentResultFormatter;
+use crate::commands::agents::OutputFormat;
+
+/// Separator between pipeline stages.
+const STAGE_SEPARATOR: &str = "->";
+
+/// Ordered list of agents to run on a task.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentPipeline {
+    stages: Vec<AgentId>,
+}
+
+impl AgentPipeline {
+    /// Parses a pipeline such as `review -> security -> summarize`.
+    ///
+    /// Stages must be non-empty and each agent may appear only once.
+    pub fn parse(spec: &str) -> anyhow::Result<Self> {
+        let mut stages: Vec<AgentId> = Vec::new();
+        for stage in spec.split(STAGE_SEPARATOR).map(str::trim) {
+            if stage.is_empty() {
+                anyhow::bail!("Invalid pipeline '{spec}': empty stage");
+            }
+            let id = AgentId::from(stage);
+            if stages.contains(&id) {
+                anyhow::bail!("Invalid pipeline '{spec}': agent '{stage}' appears more than once");
+            }
+            stages.push(id);
+        }
+        Ok(Self { stages })
+    }
+
+    /// Returns the agents in the order they run.
+    pub fn stages(&self) -> &[AgentId] {
+        &self.stages
+    }
+
+    /// Runs every stage on `task` and merges their results.
+    ///
+    /// Each stage runs with its own permissions through `toolkit`, and its
+    /// task carries the formatted results of the previous stages as
+    /// additional instructions. All stages must be registered in `router`
+    /// before anything runs.
+    pub async fn run(
+        &self,
+        router: &AgentRouter,
+        runtime: Option<&AgentRuntime>,
+        task: Task,
+        toolkit: &AgentToolkit,
+    ) -> anyhow::Result<AgentResult> {
+        let mut agents = Vec::with_capacity(self.stages.len());
+        for id in &self.stages {
+            let Some(agent) = router.get_agent(id).await else {
+                anyhow::bail!("Pipeline stage '{}' is not a known agent", id.as_str());
+            };
+            agents.push(agent);
+        }
+
+        let mut results: Vec<(AgentId, AgentResult)> = Vec::new();
+        for agent in agents {
+            let stage_task = Task {
+                context: task.context.clone(),
+                additional_instructions: stage_instructions(
+                    task.additional_instructions.as_deref(),
+                    &results,
+                ),
+            };
+            let result = run_with_fallback(
+                runtime,
+                agent.as_ref(),
+                stage_task,
+                &toolkit.for_agent(agent.as_ref()),
+            )
+            .await
+            .map_err(|e| {
+                anyhow::anyhow!("Pipeline stage '{}' failed: {e:#}", agent.id().as_str())
+            })?;
+            results.push((agent.id(), result));
+        }
+
+        Ok(AgentResult::merge(results))
+    }
+}
+
+/// Builds a stage's instructions from the task's own instructions and the
+/// results of the stages that already ran.
+fn stage_instructions(base: Option<&str>, previous: &[(AgentId, AgentResult)]) -> Option<String> {
+    if previous.is_empty() {
+        return base.map(str::to_string);
+    }
+
+    let mut instructions = base.map(|b| format!("{b}\n\n")).unwrap_or_default();
+    instructions.push_str("Results from earlier agents in this pipeline:\n");
+    for (id, result) in previous {
+        instructions.push_str(&format!(
+            "\n### {}\n\n{}",
+            id.as_str(),
+            AgentResultFormatter::format(result, OutputFormat::Markdown)
+        ));
+    }
+    Some(instructions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::ActivationScore;
+    use crate::agents::Agent;
+    use crate::agents::AgentPermissions;
+    use crate::agents::TaskContext;
+    use async_trait::async_trait;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    /// Agent that reports the instructions it received.
+    struct EchoAgent {
+        id: &'static str,
+        permissions: AgentPermissions,
+    }
+
+    #[async_trait]
+    impl Agent for EchoAgent {
+        fn id(&self) -> AgentId {
+            AgentId::from(self.id)
+        }
+
+        fn name(&self) -> &str {
+            self.id
+        }
+
+        fn description(&self) -> &str {
+            "Echoes its instructions"
+        }
+
+        fn can_handle(&self, _context: &TaskContext) -> ActivationScore {
+            ActivationScore::new(0.0)
+        }
+
+        async fn execute(
+            &self,
+            task: Task,
+            _toolkit: &AgentToolkit,
+        ) -> anyhow::Result<AgentResult> {
+            Ok(AgentResult::Analysis {
+                summary: task.additional_instructions.unwrap_or_default(),
+                details: HashMap::new(),
+            })
+        }
+
+        fn permissions(&self) -> &AgentPermissions {
+            &self.permissions
+        }
+
+        fn system_prompt(&self) -> &str {
+            "Echo"
+        }
+    }
+
+    fn router(ids: &[&'static str]) -> AgentRouter {
+        let mut router = AgentRouter::new();
+        for id in ids {
+            router.register_agent(Arc::new(EchoAgent {
+                id,
+                permissions: AgentPermissions::default(),
+            }));
+        }
+        router
+    }
+
+    fn task() -> Task {
+        Task {
+            context: TaskContext::default(),
+            additional_instructions: Some("Focus on auth".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_parse_pipeline() {
+        let pipeline = AgentPipeline::parse("review -> security ->summarize").unwrap();
+        assert_eq!(
+            pipeline.stages(),
+            &[
+                AgentId::from("review"),
+                AgentId::from("security"),
+                AgentId::from("summarize")
+            ]
+        );
+
+        assert!(AgentPipeline::parse("review -> -> security").is_err());
+        assert!(AgentPipeline::parse("").is_err());
+        let err = AgentPipeline::parse("review -> security -> review").unwrap_err();
+        assert!(err.to_string().contains("more than once"));
+    }
+
+    #[tokio::test]
+    async fn test_run_passes_previous_results_forward() {
+        let router = router(&["first", "second"]);
+        let toolkit = AgentToolkit::new(
+            AgentId::from("command"),
+            AgentPermissions::default(),
+            PathBuf::from("/workspace"),
+        );
+        let pipeline = AgentPipeline::parse("first -> second").unwrap();
+
+        let result = pipeline.run(&router, None, task(), &toolkit).await.unwrap();
+
+        let AgentResult::Combined { results } = result else {
+            panic!("expected combined result");
+        };
+        assert_eq!(results.len(), 2);
+        let AgentResult::Analysis { summary, .. } = &results[1].1 else {
+            panic!("expected analysis");
+        };
+        assert!(summary.starts_with("Focus on auth"));
+        assert!(summary.contains("### first"));
+    }
+
+    #[tokio::test]
+    async fn test_run_rejects_unknown_stage_before_running() {
+        let router = router(&["first"]);
+        let toolkit = AgentToolkit::new(
+            AgentId::from("command"),
+            AgentPermissions::default(),
+            PathBuf::from("/workspace"),
+        );
+        let pipeline = AgentPipeline::parse("first -> missing").unwrap();
+
+        let err = pipeline
+            .run(&router, None, task(), &toolkit)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("'missing'"));
+    }
+}
@@ -3,14 +3,36 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use tokio::sync::RwLock;
+
 use super::Agent;
 use super::AgentId;
 use super::TaskContext;
+use super::builtin::RefactorAgent;
+use super::builtin::ReviewAgent;
+use super::builtin::SecurityAgent;
+use super::routing::IntentClassifier;
+use super::routing::ScoreBreakdown;
+use super::routing::semantic_score;
+use crate::config_types::AgentRoutingMode;
 
 /// Agent router that selects the best agent for a given context.
+///
+/// Agents registered in code take precedence over user-defined agents with
+/// the same ID. User-defined agents can be replaced at any time through
+/// [`AgentRouter::set_user_agents`], which is how the agent watcher applies
+/// edits to Markdown agent files.
+///
+/// By default agents are scored with their own `can_handle`. In
+/// [`AgentRoutingMode::Semantic`] the router also weighs file types, git
+/// changes, command activation hints and, when an [`IntentClassifier`] is
+/// set, the model's reading of the request.
 pub struct AgentRouter {
     agents: HashMap<AgentId, Arc<dyn Agent>>,
+    user_agents: RwLock<HashMap<AgentId, Arc<dyn Agent>>>,
     activation_threshold: f64,
+    routing_mode: AgentRoutingMode,
+    intent_classifier: Option<Arc<dyn IntentClassifier>>,
 }
 
 impl AgentRouter {
@@ -18,34 +40,73 @@ impl AgentRouter {
     pub fn new() -> Self {
         Self {
             agents: HashMap::new(),
+            user_agents: RwLock::new(HashMap::new()),
             activation_threshold: 0.6,
+            routing_mode: AgentRoutingMode::default(),
+            intent_classifier: None,
         }
     }
 
+    /// Creates a router with the built-in agents registered.
+    pub fn with_builtin_agents() -> Self {
+        let mut router = Self::new();
+        router.register_agent(Arc::new(RefactorAgent::new()));
+        router.register_agent(Arc::new(ReviewAgent::new()));
+        router.register_agent(Arc::new(SecurityAgent::new()));
+        router
+    }
+
     /// Registers an agent with the router.
     pub fn register_agent(&mut self, agent: Arc<dyn Agent>) {
         self.agents.insert(agent.id(), agent);
     }
 
+    /// Replaces all user-defined agents.
+    ///
+    /// Agents whose ID collides with a registered agent are skipped.
+    pub async fn set_user_agents(&self, agents: Vec<Arc<dyn Agent>>) {
+        let mut user_agents = HashMap::new();
+        for agent in agents {
+            let id = agent.id();
+            if self.agents.contains_key(&id) {
+                tracing::warn!(
+                    "Ignoring user-defined agent '{}': the ID is reserved by a built-in agent",
+                    id.as_str()
+                );
+                continue;
+            }
+            user_agents.insert(id, agent);
+        }
+        *self.user_agents.write().await = user_agents;
+    }
+
+    /// Returns the agent with the given ID, if registered.
+    pub async fn get_agent(&self, id: &AgentId) -> Option<Arc<dyn Agent>> {
+        if let Some(agent) = self.agents.get(id) {
+            return Some(agent.clone());
+        }
+        self.user_agents.read().await.get(id).cloned()
+    }
+
+    /// Returns every available agent, registered agents first.
+    pub async fn agents(&self) -> Vec<Arc<dyn Agent>> {
+        let user_agents = self.user_agents.read().await;
+        self.agents
+            .values()
+            .chain(user_agents.values())
+            .cloned()
+            .collect()
+    }
+
     /// Selects the best agent for the given context.
     ///
     /// Returns the highest-scoring agent if above the activation threshold.
     pub async fn select_agent(&self, context: &TaskContext) -> Option<Arc<dyn Agent>> {
-        let mut scores: Vec<_> = self
-            .agents
-            .values()
-            .map(|agent| {
-                let score = agent.can_handle(context);
-                (agent.clone(), score.0)
-            })
-            .collect();
-
-        // Sort by score descending
-        scores.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+        let scores = self.score_agents(context).await;
 
         // Return top agent if above threshold
-        if let Some((agent, score)) = scores.first()
-            && *score >= self.activation_threshold
+        if let Some((agent, breakdown)) = scores.first()
+            && breakdown.total() >= self.activation_threshold
         {
             return Some(agent.clone());
         }
@@ -55,35 +116,82 @@ impl AgentRouter {
 
     /// Suggests top-k agents for the given context.
     ///
-    /// Returns agents ranked by activation score.
+    /// Returns agents ranked by activation score, each with the breakdown
+    /// that explains its score.
     pub async fn suggest_agents(
         &self,
         context: &TaskContext,
         top_k: usize,
     ) -> Vec<AgentSuggestion> {
-        let mut scores: Vec<_> = self
-            .agents
-            .values()
-            .map(|agent| {
-                let score = agent.can_handle(context);
-                AgentSuggestion {
-                    agent_id: agent.id(),
-                    name: agent.name().to_string(),
-                    description: agent.description().to_string(),
-                    score: score.0,
-                }
+        self.score_agents(context)
+            .await
+            .into_iter()
+            .take(top_k)
+            .map(|(agent, breakdown)| AgentSuggestion {
+                agent_id: agent.id(),
+                name: agent.name().to_string(),
+                description: agent.description().to_string(),
+                score: breakdown.total(),
+                breakdown,
             })
-            .collect();
+            .collect()
+    }
 
-        scores.sort_by(|a, b| {
-            b.score
-                .partial_cmp(&a.score)
+    /// Scores every available agent for `context`, best first.
+    async fn score_agents(&self, context: &TaskContext) -> Vec<(Arc<dyn Agent>, ScoreBreakdown)> {
+        let agents = self.agents().await;
+
+        let mut scores: Vec<_> = match self.routing_mode {
+            AgentRoutingMode::Keyword => agents
+                .into_iter()
+                .map(|agent| {
+                    let breakdown = ScoreBreakdown::keyword_only(agent.can_handle(context).0);
+                    (agent, breakdown)
+                })
+                .collect(),
+            AgentRoutingMode::Semantic => {
+                let intents = match &self.intent_classifier {
+                    Some(classifier) => match classifier.classify(context, &agents).await {
+                        Ok(intents) => Some(intents),
+                        Err(e) => {
+                            tracing::warn!("Intent classification failed: {e:#}");
+                            None
+                        }
+                    },
+                    None => None,
+                };
+                agents
+                    .into_iter()
+                    .map(|agent| {
+                        let confidence = intents
+                            .as_ref()
+                            .map(|intents| intents.get(&agent.id()).copied().unwrap_or(0.0));
+                        let breakdown = semantic_score(agent.as_ref(), context, confidence);
+                        (agent, breakdown)
+                    })
+                    .collect()
+            }
+        };
+
+        // Sort by score descending
+        scores.sort_by(|(_, a), (_, b)| {
+            b.total()
+                .partial_cmp(&a.total())
                 .unwrap_or(std::cmp::Ordering::Equal)
         });
-        scores.truncate(top_k);
         scores
     }
 
+    /// Sets how agents are scored.
+    pub fn set_routing_mode(&mut self, mode: AgentRoutingMode) {
+        self.routing_mode = mode;
+    }
+
+    /// Sets the classifier consulted during semantic routing.
+    pub fn set_intent_classifier(&mut self, classifier: Arc<dyn IntentClassifier>) {
+        self.intent_classifier = Some(classifier);
+    }
+
     /// Sets the activation threshold for agent selection.
     pub fn set_activation_threshold(&mut self, threshold: f64) {
         self.activation_threshold = threshold.clamp(0.0, 1.0);
@@ -103,6 +211,8 @@ pub struct AgentSuggestion {
     pub name: String,
     pub description: String,
     pub score: f64,
+    /// Why the agent received its score.
+    pub breakdown: ScoreBreakdown,
 }
 
 #[cfg(test)]
@@ -199,4 +309,121 @@ mod tests {
 
         assert!(selected.is_none());
     }
+
+    #[tokio::test]
+    async fn test_user_agents_are_routed_and_replaced() {
+        let mut router = AgentRouter::new();
+        router.register_agent(Arc::new(MockAgent {
+            id: AgentId::from("builtin"),
+            score: 0.7,
+            permissions: AgentPermissions::default(),
+        }));
+
+        router
+            .set_user_agents(vec![
+                Arc::new(MockAgent {
+                    id: AgentId::from("custom"),
+                    score: 0.9,
+                    permissions: AgentPermissions::default(),
+                }),
+                Arc::new(MockAgent {
+                    id: AgentId::from("builtin"),
+                    score: 1.0,
+                    permissions: AgentPermissions::default(),
+                }),
+            ])
+            .await;
+
+        let context = TaskContext::default();
+        let selected = router.select_agent(&context).await.unwrap();
+        assert_eq!(selected.id(), AgentId::from("custom"));
+        assert_eq!(router.agents().await.len(), 2);
+
+        router.set_user_agents(Vec::new()).await;
+        assert!(router.get_agent(&AgentId::from("custom")).await.is_none());
+        let selected = router.select_agent(&context).await.unwrap();
+        assert_eq!(selected.id(), AgentId::from("builtin"));
+    }
+
+    struct FixedClassifier(HashMap<AgentId, f64>);
+
+    #[async_trait]
+    impl IntentClassifier for FixedClassifier {
+        async fn classify(
+            &self,
+            _context: &TaskContext,
+            _agents: &[Arc<dyn Agent>],
+        ) -> anyhow::Result<HashMap<AgentId, f64>> {
+            Ok(self.0.clone())
+        }
+    }
+
+    fn semantic_router() -> AgentRouter {
+        let mut router = AgentRouter::new();
+        for id in ["alpha", "beta"] {
+            router.register_agent(Arc::new(MockAgent {
+                id: AgentId::from(id),
+                score: 0.5,
+                permissions: AgentPermissions::default(),
+            }));
+        }
+        router.set_routing_mode(AgentRoutingMode::Semantic);
+        router
+    }
+
+    #[tokio::test]
+    async fn test_semantic_routing_uses_activation_hints() {
+        let router = semantic_router();
+        let context = TaskContext {
+            activation_hints: vec!["beta".to_string()],
+            ..Default::default()
+        };
+
+        let selected = router.select_agent(&context).await.unwrap();
+        assert_eq!(selected.id(), AgentId::from("beta"));
+
+        let suggestions = router.suggest_agents(&context, 2).await;
+        assert_eq!(suggestions[0].agent_id, AgentId::from("beta"));
+        assert_eq!(suggestions[0].score, suggestions[0].breakdown.total());
+        assert!(suggestions[0].breakdown.activation_hints > 0.0);
+        assert!(
+            suggestions[0]
+                .breakdown
+                .reasons
+                .iter()
+                .any(|r| r.contains("command hints matched: beta"))
+        );
+        assert_eq!(suggestions[1].breakdown.activation_hints, 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_semantic_routing_uses_intent_classifier() {
+        let mut router = semantic_router();
+        router.set_intent_classifier(Arc::new(FixedClassifier(HashMap::from([(
+            AgentId::from("alpha"),
+            1.0,
+        )]))));
+
+        let context = TaskContext::default();
+        let selected = router.select_agent(&context).await.unwrap();
+        assert_eq!(selected.id(), AgentId::from("alpha"));
+
+        let suggestions = router.suggest_agents(&context, 1).await;
+        assert_eq!(suggestions.len(), 1);
+        assert!(suggestions[0].breakdown.model_intent.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_keyword_routing_ignores_semantic_signals() {
+        let mut router = semantic_router();
+        router.set_routing_mode(AgentRoutingMode::Keyword);
+        let context = TaskContext {
+            activation_hints: vec!["beta".to_string()],
+            ..Default::default()
+        };
+
+        assert!(router.select_agent(&context).await.is_none());
+        let suggestions = router.suggest_agents(&context, 2).await;
+        assert!(suggestions.iter().all(|s| s.score == 0.5));
+    }
 }
@@ -0,0 +1,357 @@
+//! Semantic agent routing with explainable scores.
+//!
+//! In addition to an agent's own `can_handle` score, semantic routing weighs
+//! how well the task's files, the git changes and the command's activation
+//! hints fit the agent's [`RoutingHints`], plus an optional intent
+//! classification from the model. Every contribution is recorded in a
+//! [`ScoreBreakdown`] so callers can show why an agent was picked.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use serde::Deserialize;
+
+use super::Agent;
+use super::AgentId;
+use super::TaskContext;
+use super::runtime::collect_response;
+use crate::client::ModelClient;
+use crate::client_common::Prompt;
+
+/// Maximum boost when every task file has a type the agent handles.
+const FILE_TYPE_WEIGHT: f64 = 0.2;
+
+/// Maximum boost when every changed file has a type the agent handles.
+const CHANGED_FILES_WEIGHT: f64 = 0.1;
+
+/// Maximum boost when every command activation hint matches the agent.
+const ACTIVATION_HINT_WEIGHT: f64 = 0.3;
+
+/// Maximum boost when the model is fully confident in the agent.
+const MODEL_INTENT_WEIGHT: f64 = 0.4;
+
+/// Routing signals an agent declares in addition to `can_handle`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RoutingHints {
+    /// Words that describe the agent's domain, matched against command
+    /// activation hints.
+    pub keywords: Vec<String>,
+    /// File extensions the agent specializes in, without the leading dot.
+    pub file_extensions: Vec<String>,
+}
+
+/// Per-signal contributions to an agent's routing score.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScoreBreakdown {
+    /// Score from the agent's own `can_handle`.
+    pub keyword: f64,
+    /// Boost from the types of the task's files.
+    pub file_types: f64,
+    /// Boost from the types of files changed in git.
+    pub changed_files: f64,
+    /// Boost from the command's activation hints.
+    pub activation_hints: f64,
+    /// Boost from the model's intent classification, when it ran.
+    pub model_intent: Option<f64>,
+    /// Human-readable explanation of each non-zero contribution.
+    pub reasons: Vec<String>,
+}
+
+impl ScoreBreakdown {
+    /// Creates a breakdown that only uses the agent's `can_handle` score.
+    pub fn keyword_only(score: f64) -> Self {
+        let mut breakdown = Self {
+            keyword: score,
+            ..Default::default()
+        };
+        if score > 0.0 {
+            breakdown
+                .reasons
+                .push(format!("agent keywords matched the request (+{score:.2})"));
+        }
+        breakdown
+    }
+
+    /// Returns the combined score, clamped to [0.0, 1.0].
+    pub fn total(&self) -> f64 {
+        (self.keyword
+            + self.file_types
+            + self.changed_files
+            + self.activation_hints
+            + self.model_intent.unwrap_or(0.0))
+        .clamp(0.0, 1.0)
+    }
+}
+
+/// Classifies a request's intent against the available agents.
+#[async_trait]
+pub trait IntentClassifier: Send + Sync {
+    /// Returns a confidence in [0.0, 1.0] for each agent that fits `context`.
+    ///
+    /// Agents missing from the result are treated as a confidence of 0.0.
+    async fn classify(
+        &self,
+        context: &TaskContext,
+        agents: &[Arc<dyn Agent>],
+    ) -> anyhow::Result<HashMap<AgentId, f64>>;
+}
+
+/// Intent classifier that asks the model which agents fit a request.
+pub struct ModelIntentClassifier {
+    client: ModelClient,
+}
+
+impl ModelIntentClassifier {
+    /// Creates a classifier that talks to the model through `client`.
+    pub fn new(client: ModelClient) -> Self {
+        Self { client }
+    }
+}
+
+/// Instructions for the intent classification request.
+const CLASSIFIER_INSTRUCTIONS: &str = r#"You route developer requests to specialist agents. Given a request and a list of agents, rate how well each agent fits the request.
+
+Reply with a single JSON object and nothing else: {"scores": {"<agent id>": <confidence between 0 and 1>}}"#;
+
+#[async_trait]
+impl IntentClassifier for ModelIntentClassifier {
+    async fn classify(
+        &self,
+        context: &TaskContext,
+        agents: &[Arc<dyn Agent>],
+    ) -> anyhow::Result<HashMap<AgentId, f64>> {
+        let mut message = format!("Request: {}\n", context.user_intent);
+        if !context.file_paths.is_empty() {
+            message.push_str("\nFiles:\n");
+            for path in &context.file_paths {
+                message.push_str(&format!("- {}\n", path.display()));
+            }
+        }
+        message.push_str("\nAgents:\n");
+        for agent in agents {
+            message.push_str(&format!(
+                "- {}: {}\n",
+                agent.id().as_str(),
+                agent.description()
+            ));
+        }
+
+        let prompt = Prompt {
+            input: vec![ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![ContentItem::InputText { text: message }],
+            }],
+            tools: Vec::new(),
+            parallel_tool_calls: false,
+            base_instructions_override: Some(CLASSIFIER_INSTRUCTIONS.to_string()),
+            output_schema: None,
+            max_output_tokens: None,
+        };
+
+        let reply = collect_response(&self.client, &prompt)
+            .await?
+            .into_iter()
+            .filter_map(|item| match item {
+                ResponseItem::Message { role, content, .. } if role == "assistant" => Some(content),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|content| match content {
+                ContentItem::OutputText { text } => Some(text),
+                _ => None,
+            })
+            .collect::<String>();
+
+        parse_classification(&reply)
+    }
+}
+
+#[derive(Deserialize)]
+struct ClassificationOutput {
+    scores: HashMap<String, f64>,
+}
+
+/// Parses the classifier's reply, tolerating prose around the JSON object.
+fn parse_classification(text: &str) -> anyhow::Result<HashMap<AgentId, f64>> {
+    let parsed = serde_json::from_str::<ClassificationOutput>(text)
+        .ok()
+        .or_else(|| {
+            let start = text.find('{')?;
+            let end = text.rfind('}')?;
+            serde_json::from_str::<ClassificationOutput>(text.get(start..=end)?).ok()
+        })
+        .ok_or_else(|| anyhow::anyhow!("unrecognized intent classification: {text}"))?;
+
+    Ok(parsed
+        .scores
+        .into_iter()
+        .map(|(id, score)| (AgentId::from(&id), score.clamp(0.0, 1.0)))
+        .collect())
+}
+
+/// Scores `agent` for `context` using every routing signal.
+///
+/// `model_confidence` is the intent classifier's confidence in the agent, if
+/// the classifier ran.
+pub fn semantic_score(
+    agent: &dyn Agent,
+    context: &TaskContext,
+    model_confidence: Option<f64>,
+) -> ScoreBreakdown {
+    let hints = agent.routing_hints();
+    let mut breakdown = ScoreBreakdown::keyword_only(agent.can_handle(context).0);
+
+    if let Some((matched, total)) = file_type_matches(&context.file_paths, &hints) {
+        breakdown.file_types = FILE_TYPE_WEIGHT * matched as f64 / total as f64;
+        breakdown.reasons.push(format!(
+            "{matched} of {total} files are types the agent handles (+{:.2})",
+            breakdown.file_types
+        ));
+    }
+
+    if let Some(git) = &context.git_context
+        && let Some((matched, total)) = file_type_matches(&git.changed_files, &hints)
+    {
+        breakdown.changed_files = CHANGED_FILES_WEIGHT * matched as f64 / total as f64;
+        breakdown.reasons.push(format!(
+            "{matched} of {total} changed files are types the agent handles (+{:.2})",
+            breakdown.changed_files
+        ));
+    }
+
+    let matched_hints: Vec<&str> = context
+        .activation_hints
+        .iter()
+        .map(String::as_str)
+        .filter(|hint| hint_matches(hint, &agent.id(), &hints))
+        .collect();
+    if !matched_hints.is_empty() {
+        breakdown.activation_hints = ACTIVATION_HINT_WEIGHT * matched_hints.len() as f64
+            / context.activation_hints.len() as f64;
+        breakdown.reasons.push(format!(
+            "command hints matched: {} (+{:.2})",
+            matched_hints.join(", "),
+            breakdown.activation_hints
+        ));
+    }
+
+    if let Some(confidence) = model_confidence {
+        let boost = MODEL_INTENT_WEIGHT * confidence;
+        breakdown.model_intent = Some(boost);
+        if boost > 0.0 {
+            breakdown.reasons.push(format!(
+                "model rated the intent a {:.0}% fit (+{boost:.2})",
+                confidence * 100.0
+            ));
+        }
+    }
+
+    breakdown
+}
+
+/// Returns how many of `paths` have an extension listed in `hints`, and how
+/// many paths there are. Returns `None` when nothing matches.
+fn file_type_matches(paths: &[PathBuf], hints: &RoutingHints) -> Option<(usize, usize)> {
+    if paths.is_empty() || hints.file_extensions.is_empty() {
+        return None;
+    }
+    let matched = paths
+        .iter()
+        .filter(|path| has_extension(path, &hints.file_extensions))
+        .count();
+    (matched > 0).then_some((matched, paths.len()))
+}
+
+fn has_extension(path: &Path, extensions: &[String]) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| extensions.iter().any(|e| e.eq_ignore_ascii_case(ext)))
+}
+
+/// Returns true if `hint` names the agent or one of its keywords.
+fn hint_matches(hint: &str, agent_id: &AgentId, hints: &RoutingHints) -> bool {
+    let hint = hint.to_lowercase();
+    if hint.is_empty() {
+        return false;
+    }
+    std::iter::once(agent_id.as_str())
+        .chain(hints.keywords.iter().map(String::as_str))
+        .map(str::to_lowercase)
+        .any(|word| word.contains(&hint) || hint.contains(&word))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::GitContext;
+    use crate::agents::builtin::SecurityAgent;
+
+    #[test]
+    fn test_keyword_only_breakdown() {
+        let breakdown = ScoreBreakdown::keyword_only(0.5);
+        assert_eq!(breakdown.total(), 0.5);
+        assert_eq!(breakdown.reasons.len(), 1);
+        assert!(ScoreBreakdown::keyword_only(0.0).reasons.is_empty());
+    }
+
+    #[test]
+    fn test_semantic_score_explains_each_signal() {
+        let agent = SecurityAgent::new();
+        let context = TaskContext {
+            file_paths: vec![PathBuf::from("src/auth.rs"), PathBuf::from("README")],
+            git_context: Some(GitContext {
+                diff: String::new(),
+                branch: "main".to_string(),
+                changed_files: vec![PathBuf::from("src/auth.rs")],
+            }),
+            activation_hints: vec!["security".to_string(), "docs".to_string()],
+            user_intent: "look at this".to_string(),
+            ..Default::default()
+        };
+
+        let breakdown = semantic_score(&agent, &context, Some(0.5));
+
+        assert_eq!(breakdown.file_types, FILE_TYPE_WEIGHT / 2.0);
+        assert_eq!(breakdown.changed_files, CHANGED_FILES_WEIGHT);
+        assert_eq!(breakdown.activation_hints, ACTIVATION_HINT_WEIGHT / 2.0);
+        assert_eq!(breakdown.model_intent, Some(MODEL_INTENT_WEIGHT / 2.0));
+        assert!(breakdown.reasons.iter().any(|r| r.contains("1 of 2 files")));
+        assert!(
+            breakdown
+                .reasons
+                .iter()
+                .any(|r| r.contains("command hints matched: security"))
+        );
+        assert!(breakdown.total() > breakdown.keyword);
+    }
+
+    #[test]
+    fn test_semantic_score_without_signals_matches_keyword_score() {
+        let agent = SecurityAgent::new();
+        let context = TaskContext {
+            user_intent: "check for vulnerabilities".to_string(),
+            ..Default::default()
+        };
+
+        let breakdown = semantic_score(&agent, &context, None);
+        assert_eq!(breakdown.total(), agent.can_handle(&context).0);
+    }
+
+    #[test]
+    fn test_parse_classification() {
+        let scores = parse_classification(
+            "Sure:\n```json\n{\"scores\": {\"security\": 0.9, \"review\": 1.5}}\n```",
+        )
+        .unwrap();
+        assert_eq!(scores.get(&AgentId::from("security")), Some(&0.9));
+        assert_eq!(scores.get(&AgentId::from("review")), Some(&1.0));
+
+        assert!(parse_classification("no idea").is_err());
+    }
+}
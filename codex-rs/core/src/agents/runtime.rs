@@ -0,0 +1,579 @@
+//! Model-driven execution loop for agents.
+//!
+//! The runtime drives a multi-turn conversation with the model on behalf of
+//! an [`Agent`]: the agent's system prompt becomes the instructions, the task
+//! context becomes the first user message, and any tool calls the model makes
+//! are served through the agent's [`AgentToolkit`]. The loop ends when the
+//! model replies without requesting a tool, or fails once the agent's
+//! `max_iterations` budget is exhausted.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use futures::StreamExt;
+use serde::Deserialize;
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputPayload;
+use codex_protocol::models::ResponseItem;
+
+use super::Agent;
+use super::AgentId;
+use super::AgentResult;
+use super::AgentToolkit;
+use super::CodeReviewFinding;
+use super::Severity;
+use super::Suggestion;
+use super::Task;
+use crate::client::ModelClient;
+use crate::client_common::Prompt;
+use crate::client_common::ResponseEvent;
+use crate::client_common::tools::ResponsesApiTool;
+use crate::client_common::tools::ToolSpec;
+use crate::commands::agents::AgentResultFormatter;
+use crate::commands::agents::OutputFormat;
+use crate::protocol::TokenUsage;
+use crate::tools::spec::JsonSchema;
+
+/// Name of the tool that reads a file through the toolkit.
+pub const READ_FILE_TOOL: &str = "read_file";
+
+/// Name of the tool that runs a command through the toolkit.
+pub const RUN_COMMAND_TOOL: &str = "run_command";
+
+/// Name of the tool that hands a sub-task to another agent.
+pub const DELEGATE_TOOL: &str = "delegate_to_agent";
+
+/// Maximum number of characters of the git diff included in the task message.
+const MAX_DIFF_CHARS: usize = 20_000;
+
+/// Instructions appended to every agent system prompt describing the
+/// structured reply the runtime knows how to parse.
+const OUTPUT_INSTRUCTIONS: &str = r#"When you are done, reply with a single JSON object and nothing else, using one of these shapes:
+
+{"findings": [{"severity": "error" | "warning" | "info", "category": string, "message": string, "file": string | null, "line": number | null}]}
+{"suggestions": [{"title": string, "description": string, "code_change": string | null}]}
+{"summary": string, "details": {string: string}}
+
+Use "findings" for review results, "suggestions" for proposed improvements, and "summary" for anything else. A "code_change" must be an apply_patch envelope ("*** Begin Patch" ... "*** End Patch") with paths relative to the workspace root."#;
+
+/// Runs agents by conversing with the model.
+pub struct AgentRuntime {
+    client: ModelClient,
+}
+
+impl AgentRuntime {
+    /// Creates a runtime that talks to the model through `client`.
+    pub fn new(client: ModelClient) -> Self {
+        Self { client }
+    }
+
+    /// Runs `agent` on `task`, serving tool calls through `toolkit`.
+    ///
+    /// Each model request counts as one iteration; the run fails if the model
+    /// is still calling tools after `max_iterations` requests.
+    pub async fn run(
+        &self,
+        agent: &dyn Agent,
+        task: Task,
+        toolkit: &AgentToolkit,
+    ) -> anyhow::Result<AgentResult> {
+        let permissions = agent.permissions();
+        let tools = tools_for_agent(agent, toolkit);
+        let instructions = format!("{}\n\n{OUTPUT_INSTRUCTIONS}", agent.system_prompt());
+        let mut input = vec![ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: build_task_message(&task),
+            }],
+        }];
+
+        for _ in 0..permissions.max_iterations {
+            let prompt = Prompt {
+                input: input.clone(),
+                tools: tools.clone(),
+                parallel_tool_calls: false,
+                base_instructions_override: Some(instructions.clone()),
+                output_schema: None,
+                max_output_tokens: None,
+            };
+
+            let output = collect_response(&self.client, &prompt).await?;
+            let mut calls = Vec::new();
+            let mut last_message = None;
+            for item in output {
+                match &item {
+                    ResponseItem::FunctionCall {
+                        name,
+                        arguments,
+                        call_id,
+                        ..
+                    } => calls.push((name.clone(), arguments.clone(), call_id.clone())),
+                    ResponseItem::Message { role, content, .. } if role == "assistant" => {
+                        last_message = content.iter().rev().find_map(|c| match c {
+                            ContentItem::OutputText { text } => Some(text.clone()),
+                            _ => None,
+                        });
+                    }
+                    _ => {}
+                }
+                input.push(item);
+            }
+
+            if calls.is_empty() {
+                return Ok(parse_agent_output(&last_message.unwrap_or_default()));
+            }
+
+            for (name, arguments, call_id) in calls {
+                let output = match handle_tool_call(&name, &arguments, &task, toolkit).await {
+                    Ok(content) => FunctionCallOutputPayload {
+                        content,
+                        success: Some(true),
+                    },
+                    Err(e) => FunctionCallOutputPayload {
+                        content: format!("{e:#}"),
+                        success: Some(false),
+                    },
+                };
+                input.push(ResponseItem::FunctionCallOutput { call_id, output });
+            }
+        }
+
+        anyhow::bail!(
+            "Agent '{}' did not produce a final answer within {} iterations",
+            agent.id().as_str(),
+            permissions.max_iterations
+        )
+    }
+}
+
+/// Streams a single model response and collects its output items.
+pub(crate) async fn collect_response(
+    client: &ModelClient,
+    prompt: &Prompt,
+) -> anyhow::Result<Vec<ResponseItem>> {
+    let (items, _) = collect_response_with_usage(client, prompt).await?;
+    Ok(items)
+}
+
+/// Like [`collect_response`], also returning the token usage the model
+/// reported for the response.
+pub(crate) async fn collect_response_with_usage(
+    client: &ModelClient,
+    prompt: &Prompt,
+) -> anyhow::Result<(Vec<ResponseItem>, Option<TokenUsage>)> {
+    let mut stream = client.stream(prompt).await?;
+    let mut items = Vec::new();
+
+    while let Some(event) = stream.next().await {
+        match event? {
+            ResponseEvent::OutputItemDone(item) => items.push(item),
+            ResponseEvent::Completed { token_usage, .. } => return Ok((items, token_usage)),
+            _ => {}
+        }
+    }
+
+    anyhow::bail!("stream closed before response.completed")
+}
+
+/// Runs an agent through the model when a runtime is available, falling back
+/// to the agent's own offline `execute` implementation otherwise or on error.
+pub async fn run_with_fallback(
+    runtime: Option<&AgentRuntime>,
+    agent: &dyn Agent,
+    task: Task,
+    toolkit: &AgentToolkit,
+) -> anyhow::Result<AgentResult> {
+    if let Some(runtime) = runtime {
+        match runtime.run(agent, task.clone(), toolkit).await {
+            Ok(result) => return Ok(result),
+            Err(e) => {
+                tracing::warn!(
+                    "model-driven run of agent '{}' failed, using offline analysis: {e:#}",
+                    agent.id().as_str()
+                );
+            }
+        }
+    }
+
+    agent.execute(task, toolkit).await
+}
+
+/// Builds the tool list exposed to the model for `agent`.
+///
+/// Tools are offered only when the agent's permissions allow the underlying
+/// operation, and delegation only when `toolkit` has somewhere to delegate
+/// to. A non-empty `allowed_tools` list further restricts the set.
+fn tools_for_agent(agent: &dyn Agent, toolkit: &AgentToolkit) -> Vec<ToolSpec> {
+    let permissions = agent.permissions();
+    let mut tools = Vec::new();
+
+    if !matches!(
+        permissions.file_access,
+        super::permissions::FileAccessPolicy::NoAccess
+    ) {
+        tools.push(create_read_file_tool());
+    }
+    if permissions.shell_execution {
+        tools.push(create_run_command_tool());
+    }
+    if permissions.can_delegate && toolkit.can_delegate() {
+        tools.push(create_delegate_tool());
+    }
+
+    if !permissions.allowed_tools.is_empty() {
+        tools.retain(|tool| permissions.allowed_tools.iter().any(|t| t == tool.name()));
+    }
+
+    tools
+}
+
+fn create_read_file_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "file_path".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Path to the file, absolute or relative to the workspace root".to_string(),
+            ),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: READ_FILE_TOOL.to_string(),
+        description: "Reads the full contents of a file in the workspace.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["file_path".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_run_command_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "command".to_string(),
+        JsonSchema::Array {
+            items: Box::new(JsonSchema::String { description: None }),
+            description: Some("The command to execute as an argv array".to_string()),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: RUN_COMMAND_TOOL.to_string(),
+        description: "Runs a command in the workspace root and returns its output.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["command".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_delegate_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "agent_id".to_string(),
+        JsonSchema::String {
+            description: Some("ID of the agent to hand the sub-task to".to_string()),
+        },
+    );
+    properties.insert(
+        "instructions".to_string(),
+        JsonSchema::String {
+            description: Some("What the agent should focus on".to_string()),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: DELEGATE_TOOL.to_string(),
+        description: "Hands a sub-task on the same files to another agent and returns its result."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["agent_id".to_string(), "instructions".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+#[derive(Deserialize)]
+struct ReadFileArgs {
+    file_path: PathBuf,
+}
+
+#[derive(Deserialize)]
+struct RunCommandArgs {
+    command: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct DelegateArgs {
+    agent_id: String,
+    instructions: String,
+}
+
+/// Executes a model tool call through the toolkit and returns the text sent
+/// back to the model.
+async fn handle_tool_call(
+    name: &str,
+    arguments: &str,
+    task: &Task,
+    toolkit: &AgentToolkit,
+) -> anyhow::Result<String> {
+    match name {
+        READ_FILE_TOOL => {
+            let args: ReadFileArgs = serde_json::from_str(arguments)?;
+            toolkit.read_file(&args.file_path).await
+        }
+        RUN_COMMAND_TOOL => {
+            let args: RunCommandArgs = serde_json::from_str(arguments)?;
+            let Some((cmd, rest)) = args.command.split_first() else {
+                anyhow::bail!("command must not be empty");
+            };
+            let output = toolkit.execute_command(cmd, rest).await?;
+            Ok(format!(
+                "Exit code: {}\nStdout:\n{}\nStderr:\n{}",
+                output
+                    .exit_code
+                    .map(|c| c.to_string())
+                    .unwrap_or_else(|| "unknown".to_string()),
+                output.stdout,
+                output.stderr
+            ))
+        }
+        DELEGATE_TOOL => {
+            let args: DelegateArgs = serde_json::from_str(arguments)?;
+            let sub_task = Task {
+                context: task.context.clone(),
+                additional_instructions: Some(args.instructions),
+            };
+            let result = toolkit
+                .delegate(&AgentId::from(&args.agent_id), sub_task)
+                .await?;
+            Ok(AgentResultFormatter::format(
+                &result,
+                OutputFormat::Markdown,
+            ))
+        }
+        other => anyhow::bail!("unsupported tool: {other}"),
+    }
+}
+
+/// Renders the task context as the opening user message.
+fn build_task_message(task: &Task) -> String {
+    let context = &task.context;
+    let mut message = context.user_intent.clone();
+
+    if let Some(instructions) = &task.additional_instructions {
+        message.push_str("\n\n");
+        message.push_str(instructions);
+    }
+
+    if !context.file_paths.is_empty() {
+        message.push_str("\n\nFiles:\n");
+        for path in &context.file_paths {
+            message.push_str(&format!("- {}\n", path.display()));
+        }
+    }
+
+    if let Some(git) = &context.git_context
+        && !git.diff.is_empty()
+    {
+        let diff = match git.diff.char_indices().nth(MAX_DIFF_CHARS) {
+            Some((idx, _)) => &git.diff[..idx],
+            None => git.diff.as_str(),
+        };
+        message.push_str(&format!(
+            "\n\nGit diff (branch {}):\n```diff\n{diff}\n```",
+            git.branch
+        ));
+    }
+
+    message
+}
+
+#[derive(Deserialize)]
+struct AgentOutput {
+    #[serde(default)]
+    findings: Option<Vec<FindingOutput>>,
+    #[serde(default)]
+    suggestions: Option<Vec<SuggestionOutput>>,
+    #[serde(default)]
+    summary: Option<String>,
+    #[serde(default)]
+    details: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct FindingOutput {
+    #[serde(default)]
+    severity: Option<String>,
+    #[serde(default)]
+    category: Option<String>,
+    message: String,
+    #[serde(default)]
+    file: Option<PathBuf>,
+    #[serde(default)]
+    line: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct SuggestionOutput {
+    title: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    code_change: Option<String>,
+}
+
+/// Parses the model's final reply into an [`AgentResult`].
+///
+/// The reply may be bare JSON or JSON embedded in prose/markdown fences. Any
+/// reply that is not recognized is returned as an `Analysis` summary.
+pub(crate) fn parse_agent_output(text: &str) -> AgentResult {
+    let parsed = serde_json::from_str::<AgentOutput>(text).ok().or_else(|| {
+        let start = text.find('{')?;
+        let end = text.rfind('}')?;
+        let slice = text.get(start..=end)?;
+        serde_json::from_str::<AgentOutput>(slice).ok()
+    });
+
+    match parsed {
+        Some(AgentOutput {
+            findings: Some(findings),
+            ..
+        }) => AgentResult::CodeReview {
+            findings: findings
+                .into_iter()
+                .map(|f| CodeReviewFinding {
+                    severity: parse_severity(f.severity.as_deref()),
+                    category: f.category.unwrap_or_else(|| "General".to_string()),
+                    message: f.message,
+                    location: f.file,
+                    line_number: f.line,
+                })
+                .collect(),
+        },
+        Some(AgentOutput {
+            suggestions: Some(suggestions),
+            ..
+        }) => AgentResult::Suggestions {
+            items: suggestions
+                .into_iter()
+                .map(|s| Suggestion {
+                    title: s.title,
+                    description: s.description,
+                    code_change: s.code_change,
+                })
+                .collect(),
+        },
+        Some(AgentOutput {
+            summary: Some(summary),
+            details,
+            ..
+        }) => AgentResult::Analysis { summary, details },
+        _ => AgentResult::Analysis {
+            summary: text.trim().to_string(),
+            details: HashMap::new(),
+        },
+    }
+}
+
+fn parse_severity(severity: Option<&str>) -> Severity {
+    match severity.map(str::to_ascii_lowercase).as_deref() {
+        Some("error" | "critical" | "high") => Severity::Error,
+        Some("warning" | "medium") => Severity::Warning,
+        _ => Severity::Info,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::AgentRouter;
+    use crate::agents::TaskContext;
+    use crate::agents::builtin::ReviewAgent;
+
+    #[test]
+    fn test_parse_findings() {
+        let text = r#"{"findings": [{"severity": "error", "category": "Safety", "message": "unchecked index", "file": "src/lib.rs", "line": 12}]}"#;
+
+        let AgentResult::CodeReview { findings } = parse_agent_output(text) else {
+            panic!("expected code review result");
+        };
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Error);
+        assert_eq!(findings[0].category, "Safety");
+        assert_eq!(findings[0].location, Some(PathBuf::from("src/lib.rs")));
+        assert_eq!(findings[0].line_number, Some(12));
+    }
+
+    #[test]
+    fn test_parse_suggestions_inside_fences() {
+        let text = "Here you go:\n```json\n{\"suggestions\": [{\"title\": \"Extract helper\", \"description\": \"dedupe\"}]}\n```";
+
+        let AgentResult::Suggestions { items } = parse_agent_output(text) else {
+            panic!("expected suggestions result");
+        };
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].title, "Extract helper");
+        assert_eq!(items[0].code_change, None);
+    }
+
+    #[test]
+    fn test_parse_plain_text_falls_back_to_analysis() {
+        let AgentResult::Analysis { summary, details } = parse_agent_output("  Looks good.  ")
+        else {
+            panic!("expected analysis result");
+        };
+        assert_eq!(summary, "Looks good.");
+        assert!(details.is_empty());
+    }
+
+    #[test]
+    fn test_tools_follow_permissions() {
+        let agent = ReviewAgent::new();
+        let toolkit = AgentToolkit::new(
+            agent.id(),
+            agent.permissions().clone(),
+            PathBuf::from("/workspace"),
+        );
+        let names = |toolkit: &AgentToolkit| -> Vec<String> {
+            tools_for_agent(&agent, toolkit)
+                .iter()
+                .map(|t| t.name().to_string())
+                .collect()
+        };
+        assert_eq!(names(&toolkit), vec![READ_FILE_TOOL.to_string()]);
+
+        let toolkit = toolkit.with_delegation(std::sync::Arc::new(AgentRouter::new()), None);
+        assert_eq!(
+            names(&toolkit),
+            vec![READ_FILE_TOOL.to_string(), DELEGATE_TOOL.to_string()]
+        );
+    }
+
+    #[test]
+    fn test_task_message_includes_files_and_instructions() {
+        let task = Task {
+            context: TaskContext {
+                file_paths: vec![PathBuf::from("src/main.rs")],
+                user_intent: "Review this".to_string(),
+                ..Default::default()
+            },
+            additional_instructions: Some("Focus on errors".to_string()),
+        };
+
+        let message = build_task_message(&task);
+        assert!(message.starts_with("Review this"));
+        assert!(message.contains("Focus on errors"));
+        assert!(message.contains("- src/main.rs"));
+    }
+}
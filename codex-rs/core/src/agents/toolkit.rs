@@ -1,8 +1,53 @@
 //! Agent execution toolkit with permission enforcement.
 
-use std::path::{Path, PathBuf};
+use std::collections::HashSet;
+use std::future::Future;
+use std::path::Path;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
 
-use super::{AgentId, AgentPermissions};
+use super::Agent;
+use super::AgentId;
+use super::AgentPermissions;
+use super::AgentResult;
+use super::AgentRouter;
+use super::AgentRuntime;
+use super::Task;
+use super::permissions::resolve_in_workspace;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::config_types::SandboxLimits;
+use crate::config_types::ShellEnvironmentPolicy;
+use crate::error::CodexErr;
+use crate::error::SandboxErr;
+use crate::exec::ExecParams;
+use crate::exec::ExecToolCallOutput;
+use crate::exec::StdoutStream;
+use crate::exec::process_exec_tool_call;
+use crate::exec_env::create_env;
+use crate::exec_policy::ExecPolicy;
+use crate::executor::ExecutionMode;
+use crate::executor::errors::ExecError;
+use crate::executor::linkers::PreparedExec;
+use crate::function_tool::FunctionCallError;
+use crate::protocol::AskForApproval;
+use crate::protocol::SandboxPolicy;
+use crate::safety::SafetyCheck;
+use crate::safety::assess_command_safety;
+use crate::tools::context::ExecCommandContext;
+use crate::tools::context::SharedTurnDiffTracker;
+use crate::tools::handle_container_exec_with_params;
+use codex_apply_patch::ApplyPatchFileChange;
+use codex_apply_patch::MaybeApplyPatchVerified;
+use codex_apply_patch::maybe_parse_apply_patch_verified;
+
+/// Tool name reported in exec events and telemetry for agent commands.
+const AGENT_COMMAND_TOOL_NAME: &str = "agent_command";
+
+/// Maximum number of agents in a delegation chain, including the agent that
+/// started it.
+pub const MAX_DELEGATION_DEPTH: usize = 3;
 
 /// Toolkit for agent execution.
 ///
@@ -12,27 +57,151 @@ pub struct AgentToolkit {
     agent_id: AgentId,
     permissions: AgentPermissions,
     workspace_root: PathBuf,
+    sandbox_policy: SandboxPolicy,
+    sandbox_limits: SandboxLimits,
+    codex_linux_sandbox_exe: Option<PathBuf>,
+    shell_environment_policy: ShellEnvironmentPolicy,
+    exec_policy: Arc<ExecPolicy>,
+    exec_context: Option<AgentExecContext>,
+    delegation: Option<DelegationScope>,
+}
+
+/// Router and runtime used to hand sub-tasks to other agents, along with the
+/// chain of agents that led to the current one.
+#[derive(Clone)]
+struct DelegationScope {
+    router: Arc<AgentRouter>,
+    runtime: Option<Arc<AgentRuntime>>,
+    chain: Vec<AgentId>,
+}
+
+/// Session state used to run agent commands through the same exec pipeline
+/// as the model's `shell` tool.
+#[derive(Clone)]
+pub(crate) struct AgentExecContext {
+    pub(crate) session: Arc<Session>,
+    pub(crate) turn_context: Arc<TurnContext>,
+    pub(crate) turn_diff_tracker: SharedTurnDiffTracker,
+    pub(crate) sub_id: String,
 }
 
 impl AgentToolkit {
     /// Creates a new agent toolkit.
+    ///
+    /// Commands run under a read-only sandbox policy until a different policy
+    /// is supplied with [`AgentToolkit::with_sandbox_policy`].
     pub fn new(agent_id: AgentId, permissions: AgentPermissions, workspace_root: PathBuf) -> Self {
         Self {
             agent_id,
             permissions,
             workspace_root,
+            sandbox_policy: SandboxPolicy::new_read_only_policy(),
+            sandbox_limits: SandboxLimits::default(),
+            codex_linux_sandbox_exe: None,
+            shell_environment_policy: ShellEnvironmentPolicy::default(),
+            exec_policy: Arc::default(),
+            exec_context: None,
+            delegation: None,
         }
     }
 
+    /// Returns a toolkit for `agent` that shares this toolkit's workspace,
+    /// sandbox and session but enforces `agent`'s own permissions.
+    ///
+    /// When delegation is configured, `agent` is appended to the delegation
+    /// chain.
+    pub fn for_agent(&self, agent: &dyn Agent) -> AgentToolkit {
+        let agent_id = agent.id();
+        let delegation = self.delegation.clone().map(|mut scope| {
+            scope.chain.push(agent_id.clone());
+            scope
+        });
+        Self {
+            agent_id,
+            permissions: agent.permissions().clone(),
+            workspace_root: self.workspace_root.clone(),
+            sandbox_policy: self.sandbox_policy.clone(),
+            sandbox_limits: self.sandbox_limits,
+            codex_linux_sandbox_exe: self.codex_linux_sandbox_exe.clone(),
+            shell_environment_policy: self.shell_environment_policy.clone(),
+            exec_policy: Arc::clone(&self.exec_policy),
+            exec_context: self.exec_context.clone(),
+            delegation,
+        }
+    }
+
+    /// Lets the agent delegate sub-tasks to agents registered in `router`,
+    /// running them through `runtime` when one is available.
+    pub fn with_delegation(
+        mut self,
+        router: Arc<AgentRouter>,
+        runtime: Option<Arc<AgentRuntime>>,
+    ) -> Self {
+        self.delegation = Some(DelegationScope {
+            router,
+            runtime,
+            chain: vec![self.agent_id.clone()],
+        });
+        self
+    }
+
+    /// Sets the sandbox used for commands run outside of a session.
+    pub fn with_sandbox_policy(
+        mut self,
+        sandbox_policy: SandboxPolicy,
+        codex_linux_sandbox_exe: Option<PathBuf>,
+    ) -> Self {
+        self.sandbox_policy = sandbox_policy;
+        self.codex_linux_sandbox_exe = codex_linux_sandbox_exe;
+        self
+    }
+
+    /// Sets how the environment is built for commands run outside of a
+    /// session.
+    pub fn with_shell_environment_policy(
+        mut self,
+        shell_environment_policy: ShellEnvironmentPolicy,
+    ) -> Self {
+        self.shell_environment_policy = shell_environment_policy;
+        self
+    }
+
+    /// Sets the resource limits for commands run outside of a session.
+    pub fn with_sandbox_limits(mut self, sandbox_limits: SandboxLimits) -> Self {
+        self.sandbox_limits = sandbox_limits;
+        self
+    }
+
+    /// Sets the exec policy consulted for commands run outside of a session.
+    pub fn with_exec_policy(mut self, exec_policy: Arc<ExecPolicy>) -> Self {
+        self.exec_policy = exec_policy;
+        self
+    }
+
+    /// Runs commands through the session's executor so they honor its
+    /// sandbox and approval policy and emit exec events.
+    pub(crate) fn with_exec_context(mut self, exec_context: AgentExecContext) -> Self {
+        self.exec_context = Some(exec_context);
+        self
+    }
+
     /// Reads a file with permission validation.
+    ///
+    /// Relative paths are resolved against the workspace root; paths that
+    /// resolve outside of it, including through symlinks, are rejected.
     pub async fn read_file(&self, path: &Path) -> anyhow::Result<String> {
         // Validate permissions
-        if !self.permissions.can_read_file(path) {
+        let resolved = self.resolve_path(path)?;
+        if !self
+            .permissions
+            .can_read_file(&self.workspace_relative(&resolved))
+            || self.permissions.is_denied(&resolved)
+        {
             anyhow::bail!("Permission denied: cannot read {:?}", path);
         }
 
         // Read file
-        tokio::fs::read_to_string(path)
+        tokio::fs::read_to_string(&resolved)
             .await
             .map_err(|e| anyhow::anyhow!("Failed to read file: {}", e))
     }
@@ -40,17 +209,42 @@ impl AgentToolkit {
     /// Writes a file with permission validation.
     pub async fn write_file(&self, path: &Path, content: &str) -> anyhow::Result<()> {
         // Validate permissions
-        if !self.permissions.can_write_file(path) {
+        let resolved = self.resolve_path(path)?;
+        if !self
+            .permissions
+            .can_write_file(&self.workspace_relative(&resolved))
+            || self.permissions.is_denied(&resolved)
+        {
             anyhow::bail!("Permission denied: cannot write to {:?}", path);
         }
 
         // Write file
-        tokio::fs::write(path, content)
+        tokio::fs::write(&resolved, content)
             .await
             .map_err(|e| anyhow::anyhow!("Failed to write file: {}", e))
     }
 
+    fn resolve_path(&self, path: &Path) -> anyhow::Result<PathBuf> {
+        resolve_in_workspace(&self.workspace_root, path)
+            .map_err(|e| anyhow::anyhow!("Permission denied: cannot access {:?}: {}", path, e))
+    }
+
+    /// Returns `resolved` relative to the workspace so allow and deny
+    /// patterns such as `src/**` apply the same way for any checkout.
+    fn workspace_relative(&self, resolved: &Path) -> PathBuf {
+        dunce::canonicalize(&self.workspace_root)
+            .ok()
+            .and_then(|root| resolved.strip_prefix(root).ok().map(Path::to_path_buf))
+            .unwrap_or_else(|| resolved.to_path_buf())
+    }
+
     /// Executes a shell command with permission validation.
+    ///
+    /// Inside a session the command goes through the session executor, which
+    /// applies the sandbox and approval policy and emits `ExecCommandBegin`,
+    /// `ExecCommandEnd` and, when needed, `ExecApprovalRequest` events.
+    /// Outside a session there is nobody to ask for approval, so commands
+    /// that would require it are rejected.
     pub async fn execute_command(
         &self,
         cmd: &str,
@@ -61,24 +255,251 @@ impl AgentToolkit {
             anyhow::bail!("Permission denied: shell execution not allowed");
         }
 
-        // TODO: Validate through execpolicy
-        // For now, execute directly
-        self.execute_shell(cmd, args).await
+        let mut command = vec![cmd.to_string()];
+        command.extend(args.iter().cloned());
+
+        match &self.exec_context {
+            Some(exec_context) => self.execute_in_session(exec_context, command).await,
+            None => self.execute_standalone(command).await,
+        }
+    }
+
+    async fn execute_in_session(
+        &self,
+        exec_context: &AgentExecContext,
+        command: Vec<String>,
+    ) -> anyhow::Result<CommandOutput> {
+        let AgentExecContext {
+            session,
+            turn_context,
+            turn_diff_tracker,
+            sub_id,
+        } = exec_context;
+        let call_id = format!("agent-{}-{}", self.agent_id.as_str(), uuid::Uuid::new_v4());
+
+        let params = ExecParams {
+            command: command.clone(),
+            cwd: self.workspace_root.clone(),
+            timeout_ms: None,
+            env: create_env(&turn_context.shell_environment_policy),
+            with_escalated_permissions: None,
+            justification: Some(format!("requested by agent '{}'", self.agent_id.as_str())),
+        };
+        let context = ExecCommandContext {
+            sub_id: sub_id.clone(),
+            call_id: call_id.clone(),
+            command_for_display: command.clone(),
+            cwd: self.workspace_root.clone(),
+            apply_patch: None,
+            tool_name: AGENT_COMMAND_TOOL_NAME.to_string(),
+            otel_event_manager: turn_context.client.get_otel_event_manager(),
+        };
+
+        session
+            .services
+            .executor
+            .update_environment(
+                turn_context.sandbox_policy.clone(),
+                turn_context.cwd.clone(),
+            )
+            .await;
+
+        let prepared = PreparedExec::new(
+            context,
+            params,
+            command,
+            ExecutionMode::Shell,
+            Some(StdoutStream {
+                sub_id: sub_id.clone(),
+                call_id,
+                tx_event: session.get_tx_event(),
+            }),
+            turn_context.shell_environment_policy.use_profile,
+        );
+
+        match session
+            .run_exec_with_events(
+                Arc::clone(turn_diff_tracker),
+                prepared,
+                turn_context.approval_policy,
+            )
+            .await
+        {
+            Ok(output) => Ok(output.into()),
+            Err(ExecError::Codex(CodexErr::Sandbox(
+                SandboxErr::Timeout { output } | SandboxErr::LimitExceeded { output, .. },
+            ))) => Ok((*output).into()),
+            Err(ExecError::Function(FunctionCallError::RespondToModel(message))) => {
+                anyhow::bail!("Command rejected: {message}")
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn execute_standalone(&self, command: Vec<String>) -> anyhow::Result<CommandOutput> {
+        let sandbox_type = match assess_command_safety(
+            &command,
+            AskForApproval::Never,
+            &self.sandbox_policy,
+            &self.exec_policy,
+            &HashSet::new(),
+            false,
+        ) {
+            SafetyCheck::AutoApprove { sandbox_type, .. } => sandbox_type,
+            SafetyCheck::AskUser => {
+                anyhow::bail!("Command rejected: approval required but no session is available")
+            }
+            SafetyCheck::Reject { reason } => anyhow::bail!("Command rejected: {reason}"),
+        };
+
+        let params = ExecParams {
+            command,
+            cwd: self.workspace_root.clone(),
+            timeout_ms: None,
+            env: create_env(&self.shell_environment_policy),
+            with_escalated_permissions: None,
+            justification: None,
+        };
+
+        match process_exec_tool_call(
+            params,
+            sandbox_type,
+            &self.sandbox_policy,
+            &self.sandbox_limits,
+            &self.workspace_root,
+            &self.codex_linux_sandbox_exe,
+            None,
+        )
+        .await
+        {
+            Ok(output) => Ok(output.into()),
+            Err(CodexErr::Sandbox(
+                SandboxErr::Timeout { output } | SandboxErr::LimitExceeded { output, .. },
+            )) => Ok((*output).into()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Applies an `apply_patch` envelope with permission validation.
+    ///
+    /// Every path touched by the patch must be writable by the agent. Inside
+    /// a session the patch goes through the same approval flow as the
+    /// model's `apply_patch` tool and emits `PatchApplyBegin`/`PatchApplyEnd`
+    /// events; outside a session patches are rejected.
+    pub async fn apply_patch(&self, patch: &str) -> anyhow::Result<String> {
+        let argv = vec!["apply_patch".to_string(), patch.to_string()];
+        let action = match maybe_parse_apply_patch_verified(&argv, &self.workspace_root) {
+            MaybeApplyPatchVerified::Body(action) => action,
+            MaybeApplyPatchVerified::CorrectnessError(e) => {
+                anyhow::bail!("Invalid patch: {e}")
+            }
+            MaybeApplyPatchVerified::ShellParseError(_)
+            | MaybeApplyPatchVerified::NotApplyPatch => {
+                anyhow::bail!("Invalid patch: not an apply_patch envelope")
+            }
+        };
+
+        for (path, change) in action.changes() {
+            let move_path = match change {
+                ApplyPatchFileChange::Update { move_path, .. } => move_path.as_deref(),
+                _ => None,
+            };
+            for path in std::iter::once(path.as_path()).chain(move_path) {
+                let resolved = self.resolve_path(path)?;
+                if !self
+                    .permissions
+                    .can_write_file(&self.workspace_relative(&resolved))
+                    || self.permissions.is_denied(&resolved)
+                {
+                    anyhow::bail!("Permission denied: cannot write to {:?}", path);
+                }
+            }
+        }
+
+        match &self.exec_context {
+            Some(exec_context) => {
+                let params = ExecParams {
+                    command: argv,
+                    cwd: self.workspace_root.clone(),
+                    timeout_ms: None,
+                    env: create_env(&exec_context.turn_context.shell_environment_policy),
+                    with_escalated_permissions: None,
+                    justification: None,
+                };
+                handle_container_exec_with_params(
+                    AGENT_COMMAND_TOOL_NAME,
+                    params,
+                    Arc::clone(&exec_context.session),
+                    Arc::clone(&exec_context.turn_context),
+                    Arc::clone(&exec_context.turn_diff_tracker),
+                    exec_context.sub_id.clone(),
+                    format!("agent-{}-{}", self.agent_id.as_str(), uuid::Uuid::new_v4()),
+                )
+                .await
+                .map_err(|e| anyhow::anyhow!("Patch failed: {e}"))
+            }
+            // Without a session there is no sandboxed exec pipeline or
+            // approval flow to apply the patch through.
+            None => anyhow::bail!("Patch rejected: no session is available to apply it"),
+        }
     }
 
-    async fn execute_shell(&self, cmd: &str, args: &[String]) -> anyhow::Result<CommandOutput> {
-        use tokio::process::Command;
+    /// Returns true if the agent may hand sub-tasks to other agents.
+    pub fn can_delegate(&self) -> bool {
+        self.permissions.can_delegate && self.delegation.is_some()
+    }
 
-        let output = Command::new(cmd)
-            .args(args)
-            .current_dir(&self.workspace_root)
-            .output()
-            .await?;
+    /// Runs `task` on the agent registered as `agent_id` and returns its
+    /// result.
+    ///
+    /// The delegate runs with its own permissions. Delegation fails if the
+    /// calling agent lacks `can_delegate`, if the chain would exceed
+    /// [`MAX_DELEGATION_DEPTH`], or if `agent_id` already appears in the
+    /// chain.
+    pub fn delegate<'a>(
+        &'a self,
+        agent_id: &'a AgentId,
+        task: Task,
+    ) -> Pin<Box<dyn Future<Output = anyhow::Result<AgentResult>> + Send + 'a>> {
+        // Boxed because a delegate's runtime may call back into `delegate`.
+        Box::pin(async move {
+            if !self.permissions.can_delegate {
+                anyhow::bail!(
+                    "Permission denied: agent '{}' cannot delegate",
+                    self.agent_id.as_str()
+                );
+            }
+            let Some(scope) = &self.delegation else {
+                anyhow::bail!("Delegation is not available outside of an agent command");
+            };
 
-        Ok(CommandOutput {
-            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
-            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
-            exit_code: output.status.code(),
+            let chain = scope
+                .chain
+                .iter()
+                .chain(std::iter::once(agent_id))
+                .map(AgentId::as_str)
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            if scope.chain.contains(agent_id) {
+                anyhow::bail!("Delegation cycle detected: {chain}");
+            }
+            if scope.chain.len() >= MAX_DELEGATION_DEPTH {
+                anyhow::bail!("Delegation depth limit of {MAX_DELEGATION_DEPTH} exceeded: {chain}");
+            }
+
+            let Some(agent) = scope.router.get_agent(agent_id).await else {
+                anyhow::bail!("Unknown agent: {}", agent_id.as_str());
+            };
+            tracing::info!("Delegating to agent: {chain}");
+
+            let toolkit = self.for_agent(agent.as_ref());
+            super::runtime::run_with_fallback(
+                scope.runtime.as_deref(),
+                agent.as_ref(),
+                task,
+                &toolkit,
+            )
+            .await
         })
     }
 
@@ -101,11 +522,38 @@ pub struct CommandOutput {
     pub exit_code: Option<i32>,
 }
 
+impl From<ExecToolCallOutput> for CommandOutput {
+    fn from(output: ExecToolCallOutput) -> Self {
+        Self {
+            stdout: output.stdout.text,
+            stderr: output.stderr.text,
+            exit_code: Some(output.exit_code),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::agents::ActivationScore;
+    use crate::agents::TaskContext;
     use crate::agents::permissions::FileAccessPolicy;
+    use crate::codex::make_session_and_context_with_rx;
+    use crate::protocol::EventMsg;
+    use crate::turn_diff_tracker::TurnDiffTracker;
     use tempfile::TempDir;
+    use tokio::sync::Mutex;
+
+    fn shell_permissions() -> AgentPermissions {
+        AgentPermissions {
+            file_access: FileAccessPolicy::read_only(),
+            shell_execution: true,
+            network_access: false,
+            allowed_tools: vec![],
+            max_iterations: 5,
+            can_delegate: false,
+        }
+    }
 
     #[tokio::test]
     async fn test_file_read_with_permissions() {
@@ -114,7 +562,7 @@ mod tests {
         tokio::fs::write(&file_path, "test content").await.unwrap();
 
         let permissions = AgentPermissions {
-            file_access: FileAccessPolicy::ReadOnly,
+            file_access: FileAccessPolicy::read_only(),
             shell_execution: false,
             network_access: false,
             allowed_tools: vec![],
@@ -138,7 +586,7 @@ mod tests {
         let file_path = temp.path().join("test.txt");
 
         let permissions = AgentPermissions {
-            file_access: FileAccessPolicy::ReadOnly,
+            file_access: FileAccessPolicy::read_only(),
             shell_execution: false,
             network_access: false,
             allowed_tools: vec![],
@@ -155,4 +603,340 @@ mod tests {
         let result = toolkit.write_file(&file_path, "test").await;
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_file_read_denies_escapes_and_secrets() {
+        let outside = TempDir::new().unwrap();
+        let temp = TempDir::new().unwrap();
+        tokio::fs::write(outside.path().join("secret.txt"), "secret")
+            .await
+            .unwrap();
+        tokio::fs::write(temp.path().join(".env"), "TOKEN=1")
+            .await
+            .unwrap();
+
+        let toolkit = AgentToolkit::new(
+            AgentId::from("test-agent"),
+            AgentPermissions {
+                file_access: FileAccessPolicy::read_only(),
+                ..Default::default()
+            },
+            temp.path().to_path_buf(),
+        );
+
+        assert!(toolkit.read_file(Path::new(".env")).await.is_err());
+        assert!(
+            toolkit
+                .read_file(
+                    &temp
+                        .path()
+                        .join("../")
+                        .join(outside.path().file_name().unwrap())
+                        .join("secret.txt")
+                )
+                .await
+                .is_err()
+        );
+        assert!(
+            toolkit
+                .read_file(&outside.path().join("secret.txt"))
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_apply_patch_with_permissions() {
+        let temp = TempDir::new().unwrap();
+        tokio::fs::write(temp.path().join("lib.rs"), "fn a() {}\nfn b() {}\n")
+            .await
+            .unwrap();
+        let patch =
+            "*** Begin Patch\n*** Update File: lib.rs\n@@\n fn a() {}\n-fn b() {}\n*** End Patch";
+
+        let read_only = AgentToolkit::new(
+            AgentId::from("test-agent"),
+            AgentPermissions {
+                file_access: FileAccessPolicy::read_only(),
+                ..Default::default()
+            },
+            temp.path().to_path_buf(),
+        );
+        assert!(read_only.apply_patch(patch).await.is_err());
+
+        let read_write = AgentToolkit::new(
+            AgentId::from("test-agent"),
+            AgentPermissions {
+                file_access: FileAccessPolicy::ReadWrite {
+                    allow_patterns: vec!["*.rs".to_string()],
+                    deny_patterns: vec![],
+                },
+                ..Default::default()
+            },
+            temp.path().to_path_buf(),
+        );
+        let err = read_write.apply_patch(patch).await.unwrap_err();
+        assert!(err.to_string().starts_with("Patch rejected"));
+        let content = tokio::fs::read_to_string(temp.path().join("lib.rs"))
+            .await
+            .unwrap();
+        assert_eq!(content, "fn a() {}\nfn b() {}\n");
+
+        assert!(
+            read_write
+                .apply_patch("not a patch")
+                .await
+                .unwrap_err()
+                .to_string()
+                .starts_with("Invalid patch")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_command_denied_without_shell_permission() {
+        let temp = TempDir::new().unwrap();
+        let permissions = AgentPermissions {
+            shell_execution: false,
+            ..shell_permissions()
+        };
+        let toolkit = AgentToolkit::new(
+            AgentId::from("test-agent"),
+            permissions,
+            temp.path().to_path_buf(),
+        );
+
+        let err = toolkit
+            .execute_command("echo", &["hi".to_string()])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("shell execution not allowed"));
+    }
+
+    #[tokio::test]
+    async fn test_execute_command_runs_known_safe_command() {
+        let temp = TempDir::new().unwrap();
+        let toolkit = AgentToolkit::new(
+            AgentId::from("test-agent"),
+            shell_permissions(),
+            temp.path().to_path_buf(),
+        );
+
+        let output = toolkit
+            .execute_command("echo", &["hello".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(output.exit_code, Some(0));
+        assert_eq!(output.stdout.trim(), "hello");
+    }
+
+    #[tokio::test]
+    async fn test_execute_command_rejects_without_session_approval() {
+        let temp = TempDir::new().unwrap();
+        let target = temp.path().join("keep");
+        tokio::fs::create_dir(&target).await.unwrap();
+        let toolkit = AgentToolkit::new(
+            AgentId::from("test-agent"),
+            shell_permissions(),
+            temp.path().to_path_buf(),
+        );
+
+        let err = toolkit
+            .execute_command("rm", &["-rf".to_string(), "keep".to_string()])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().starts_with("Command rejected"));
+        assert!(target.exists());
+    }
+
+    #[tokio::test]
+    async fn test_execute_command_rejects_forbidden_by_exec_policy() {
+        let temp = TempDir::new().unwrap();
+        let policy_path = temp.path().join("deny.policy");
+        tokio::fs::write(
+            &policy_path,
+            r#"define_program(program="echo", args=["hello"], forbidden="no greetings")"#,
+        )
+        .await
+        .unwrap();
+        let toolkit = AgentToolkit::new(
+            AgentId::from("test-agent"),
+            shell_permissions(),
+            temp.path().to_path_buf(),
+        )
+        .with_exec_policy(Arc::new(ExecPolicy::load_files(&[policy_path]).unwrap()));
+
+        let err = toolkit
+            .execute_command("echo", &["hello".to_string()])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().starts_with("Command rejected"));
+        assert!(err.to_string().contains("no greetings"));
+    }
+
+    #[tokio::test]
+    async fn test_execute_command_in_session_emits_exec_events() {
+        let (session, turn_context, rx) = make_session_and_context_with_rx();
+        let temp = TempDir::new().unwrap();
+        let toolkit = AgentToolkit::new(
+            AgentId::from("test-agent"),
+            shell_permissions(),
+            temp.path().to_path_buf(),
+        )
+        .with_exec_context(AgentExecContext {
+            session,
+            turn_context,
+            turn_diff_tracker: Arc::new(Mutex::new(TurnDiffTracker::new())),
+            sub_id: "sub-1".to_string(),
+        });
+
+        let output = toolkit
+            .execute_command("echo", &["hello".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(output.exit_code, Some(0));
+        assert_eq!(output.stdout.trim(), "hello");
+
+        let mut saw_begin = false;
+        let mut saw_end = false;
+        while let Ok(event) = rx.try_recv() {
+            match event.msg {
+                EventMsg::ExecCommandBegin(begin) => {
+                    assert_eq!(begin.command, vec!["echo", "hello"]);
+                    assert!(begin.call_id.starts_with("agent-test-agent-"));
+                    saw_begin = true;
+                }
+                EventMsg::ExecCommandEnd(end) => {
+                    assert_eq!(end.exit_code, 0);
+                    saw_end = true;
+                }
+                _ => {}
+            }
+        }
+        assert!(saw_begin && saw_end);
+    }
+
+    /// Agent that delegates to `target`, or reports its own ID when it has none.
+    struct ChainAgent {
+        id: &'static str,
+        target: Option<&'static str>,
+        permissions: AgentPermissions,
+    }
+
+    #[async_trait::async_trait]
+    impl Agent for ChainAgent {
+        fn id(&self) -> AgentId {
+            AgentId::from(self.id)
+        }
+
+        fn name(&self) -> &str {
+            self.id
+        }
+
+        fn description(&self) -> &str {
+            "Delegates along a chain"
+        }
+
+        fn can_handle(&self, _context: &TaskContext) -> ActivationScore {
+            ActivationScore::new(0.0)
+        }
+
+        async fn execute(&self, task: Task, toolkit: &AgentToolkit) -> anyhow::Result<AgentResult> {
+            match self.target {
+                Some(target) => toolkit.delegate(&AgentId::from(target), task).await,
+                None => Ok(AgentResult::Analysis {
+                    summary: self.id.to_string(),
+                    details: Default::default(),
+                }),
+            }
+        }
+
+        fn permissions(&self) -> &AgentPermissions {
+            &self.permissions
+        }
+
+        fn system_prompt(&self) -> &str {
+            "Chain"
+        }
+    }
+
+    fn chain_router(links: &[(&'static str, Option<&'static str>)]) -> Arc<AgentRouter> {
+        let mut router = AgentRouter::new();
+        for (id, target) in links {
+            router.register_agent(Arc::new(ChainAgent {
+                id,
+                target: *target,
+                permissions: AgentPermissions {
+                    can_delegate: true,
+                    ..AgentPermissions::default()
+                },
+            }));
+        }
+        Arc::new(router)
+    }
+
+    async fn run_chain(router: Arc<AgentRouter>, start: &str) -> anyhow::Result<AgentResult> {
+        let agent = router.get_agent(&AgentId::from(start)).await.unwrap();
+        let toolkit =
+            AgentToolkit::new(agent.id(), agent.permissions().clone(), PathBuf::from("/"))
+                .with_delegation(router, None);
+        let task = Task {
+            context: Default::default(),
+            additional_instructions: None,
+        };
+        agent.execute(task, &toolkit).await
+    }
+
+    #[tokio::test]
+    async fn test_delegate_runs_target_agent() {
+        let router = chain_router(&[("a", Some("b")), ("b", None)]);
+
+        let result = run_chain(router, "a").await.unwrap();
+        let AgentResult::Analysis { summary, .. } = result else {
+            panic!("expected analysis");
+        };
+        assert_eq!(summary, "b");
+    }
+
+    #[tokio::test]
+    async fn test_delegate_detects_cycles() {
+        let router = chain_router(&[("a", Some("b")), ("b", Some("a"))]);
+
+        let err = run_chain(router, "a").await.unwrap_err();
+        assert!(err.to_string().contains("cycle detected: a -> b -> a"));
+    }
+
+    #[tokio::test]
+    async fn test_delegate_enforces_depth_limit() {
+        let router = chain_router(&[
+            ("a", Some("b")),
+            ("b", Some("c")),
+            ("c", Some("d")),
+            ("d", None),
+        ]);
+
+        let err = run_chain(router, "a").await.unwrap_err();
+        assert!(err.to_string().contains("depth limit"));
+    }
+
+    #[tokio::test]
+    async fn test_delegate_requires_permission() {
+        let router = chain_router(&[("b", None)]);
+        let toolkit = AgentToolkit::new(
+            AgentId::from("a"),
+            AgentPermissions::default(),
+            PathBuf::from("/"),
+        )
+        .with_delegation(router, None);
+        let task = Task {
+            context: Default::default(),
+            additional_instructions: None,
+        };
+
+        assert!(!toolkit.can_delegate());
+        let err = toolkit
+            .delegate(&AgentId::from("b"), task)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("cannot delegate"));
+    }
 }
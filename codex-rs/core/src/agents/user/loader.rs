@@ -0,0 +1,339 @@
+//! User agent loader from filesystem.
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use super::parser::AgentDefinition;
+use super::parser::AgentParser;
+use crate::agents::ActivationScore;
+use crate::agents::Agent;
+use crate::agents::AgentId;
+use crate::agents::AgentPermissions;
+use crate::agents::AgentResult;
+use crate::agents::AgentToolkit;
+use crate::agents::Task;
+use crate::agents::TaskContext;
+use crate::agents::routing::RoutingHints;
+
+/// Loads user-defined agents from one or more directories.
+///
+/// Directories are read in order, so an agent defined in a later directory
+/// replaces one with the same ID from an earlier directory. This lets a
+/// repository's `.codex/agents` override agents from `~/.codex/agents`.
+pub struct UserAgentLoader {
+    agents_dirs: Vec<PathBuf>,
+}
+
+impl UserAgentLoader {
+    /// Creates a new user agent loader.
+    ///
+    /// # Arguments
+    /// * `agents_dirs` - Directories containing agent files, lowest precedence first
+    pub fn new(agents_dirs: Vec<PathBuf>) -> Self {
+        Self { agents_dirs }
+    }
+
+    /// Returns the standard agent directories: `<codex_home>/agents` followed
+    /// by `<workspace_root>/.codex/agents`. Pass `None` to leave out the
+    /// workspace's own agents, e.g. when the workspace is not trusted.
+    pub fn default_dirs(codex_home: &Path, workspace_root: Option<&Path>) -> Vec<PathBuf> {
+        let mut dirs = vec![codex_home.join("agents")];
+        if let Some(workspace_root) = workspace_root {
+            dirs.push(workspace_root.join(".codex").join("agents"));
+        }
+        dirs
+    }
+
+    /// Returns the directories this loader reads from.
+    pub fn agents_dirs(&self) -> &[PathBuf] {
+        &self.agents_dirs
+    }
+
+    /// Loads all agents from the agent directories.
+    ///
+    /// Missing directories are skipped and files that fail to parse are
+    /// logged and ignored.
+    pub async fn load_all(&self) -> anyhow::Result<Vec<Arc<dyn Agent>>> {
+        let mut agents: HashMap<AgentId, Arc<dyn Agent>> = HashMap::new();
+
+        for dir in &self.agents_dirs {
+            if !tokio::fs::try_exists(dir).await.unwrap_or(false) {
+                continue;
+            }
+
+            let mut paths = Vec::new();
+            let mut entries = tokio::fs::read_dir(dir).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+
+                // Only process .md files
+                if path.extension().and_then(|s| s.to_str()) == Some("md") && path.is_file() {
+                    paths.push(path);
+                }
+            }
+            paths.sort();
+
+            for path in paths {
+                match Self::load_agent(&path).await {
+                    Ok(agent) => {
+                        agents.insert(agent.id(), Arc::new(agent));
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to load agent from {:?}: {}", path, e);
+                    }
+                }
+            }
+        }
+
+        Ok(agents.into_values().collect())
+    }
+
+    async fn load_agent(path: &Path) -> anyhow::Result<UserAgent> {
+        let content = tokio::fs::read_to_string(path)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to read file {:?}: {}", path, e))?;
+
+        let parsed = AgentParser::parse(&content)
+            .map_err(|e| anyhow::anyhow!("Failed to parse agent from {:?}: {}", path, e))?;
+
+        Ok(UserAgent::new(
+            parsed.definition,
+            parsed.system_prompt,
+            path.to_path_buf(),
+        ))
+    }
+}
+
+/// User-defined agent loaded from a Markdown file.
+///
+/// User agents have no offline implementation; they only run through the
+/// model runtime using their system prompt.
+#[derive(Debug, Clone)]
+pub struct UserAgent {
+    definition: AgentDefinition,
+    permissions: AgentPermissions,
+    system_prompt: String,
+    source: PathBuf,
+}
+
+impl UserAgent {
+    /// Creates a user agent from a parsed definition.
+    pub fn new(definition: AgentDefinition, system_prompt: String, source: PathBuf) -> Self {
+        let permissions = definition.agent_permissions();
+        Self {
+            definition,
+            permissions,
+            system_prompt,
+            source,
+        }
+    }
+
+    /// Returns the file the agent was loaded from.
+    pub fn source(&self) -> &Path {
+        &self.source
+    }
+}
+
+#[async_trait]
+impl Agent for UserAgent {
+    fn id(&self) -> AgentId {
+        AgentId::from(&self.definition.id)
+    }
+
+    fn name(&self) -> &str {
+        &self.definition.name
+    }
+
+    fn description(&self) -> &str {
+        &self.definition.description
+    }
+
+    fn can_handle(&self, context: &TaskContext) -> ActivationScore {
+        let intent = context.user_intent.to_lowercase();
+        let paths: Vec<String> = context
+            .file_paths
+            .iter()
+            .map(|p| p.to_string_lossy().to_lowercase())
+            .collect();
+
+        let matches = self
+            .definition
+            .activation_keywords
+            .iter()
+            .map(|k| k.to_lowercase())
+            .filter(|k| intent.contains(k.as_str()) || paths.iter().any(|p| p.contains(k.as_str())))
+            .count();
+
+        // A single keyword is enough to activate: the author chose them explicitly.
+        if matches == 0 {
+            ActivationScore::new(0.0)
+        } else {
+            ActivationScore::new(0.6 + matches as f64 * 0.1)
+        }
+    }
+
+    async fn execute(&self, _task: Task, _toolkit: &AgentToolkit) -> anyhow::Result<AgentResult> {
+        anyhow::bail!(
+            "Agent '{}' is defined in {} and requires the model runtime",
+            self.definition.id,
+            self.source.display()
+        )
+    }
+
+    fn permissions(&self) -> &AgentPermissions {
+        &self.permissions
+    }
+
+    fn system_prompt(&self) -> &str {
+        &self.system_prompt
+    }
+
+    fn routing_hints(&self) -> RoutingHints {
+        RoutingHints {
+            keywords: self.definition.activation_keywords.clone(),
+            file_extensions: self
+                .definition
+                .file_types
+                .iter()
+                .map(|t| {
+                    t.trim_start_matches("*.")
+                        .trim_start_matches('.')
+                        .to_string()
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const MIGRATION_AGENT: &str = r#"---
+id: migration-reviewer
+name: Migration Reviewer
+description: Reviews database migrations
+activation_keywords: [migration]
+---
+
+Review migrations."#;
+
+    #[tokio::test]
+    async fn test_load_skips_missing_directories() {
+        let temp = TempDir::new().unwrap();
+        let agents_dir = temp.path().join("agents");
+
+        let loader = UserAgentLoader::new(vec![agents_dir.clone()]);
+        let agents = loader.load_all().await.unwrap();
+
+        assert!(agents.is_empty());
+        assert!(!agents_dir.exists());
+    }
+
+    #[tokio::test]
+    async fn test_load_valid_and_invalid_agents() {
+        let temp = TempDir::new().unwrap();
+        tokio::fs::write(temp.path().join("migration.md"), MIGRATION_AGENT)
+            .await
+            .unwrap();
+        tokio::fs::write(temp.path().join("broken.md"), "no frontmatter")
+            .await
+            .unwrap();
+        tokio::fs::write(temp.path().join("notes.txt"), MIGRATION_AGENT)
+            .await
+            .unwrap();
+
+        let loader = UserAgentLoader::new(vec![temp.path().to_path_buf()]);
+        let agents = loader.load_all().await.unwrap();
+
+        assert_eq!(agents.len(), 1);
+        assert_eq!(agents[0].id(), AgentId::from("migration-reviewer"));
+        assert_eq!(agents[0].system_prompt(), "Review migrations.");
+    }
+
+    #[tokio::test]
+    async fn test_later_directories_override_earlier_ones() {
+        let home = TempDir::new().unwrap();
+        let repo = TempDir::new().unwrap();
+        tokio::fs::write(home.path().join("migration.md"), MIGRATION_AGENT)
+            .await
+            .unwrap();
+        tokio::fs::write(
+            repo.path().join("migration.md"),
+            MIGRATION_AGENT.replace("Review migrations.", "Repo prompt."),
+        )
+        .await
+        .unwrap();
+
+        let loader =
+            UserAgentLoader::new(vec![home.path().to_path_buf(), repo.path().to_path_buf()]);
+        let agents = loader.load_all().await.unwrap();
+
+        assert_eq!(agents.len(), 1);
+        assert_eq!(agents[0].system_prompt(), "Repo prompt.");
+    }
+
+    #[test]
+    fn test_default_dirs_skip_untrusted_workspace() {
+        let home = Path::new("/home/me/.codex");
+        let repo = Path::new("/repo");
+
+        assert_eq!(
+            UserAgentLoader::default_dirs(home, Some(repo)),
+            vec![home.join("agents"), repo.join(".codex").join("agents")]
+        );
+        assert_eq!(
+            UserAgentLoader::default_dirs(home, None),
+            vec![home.join("agents")]
+        );
+    }
+
+    #[test]
+    fn test_activation_keywords() {
+        let parsed = AgentParser::parse(MIGRATION_AGENT).unwrap();
+        let agent = UserAgent::new(
+            parsed.definition,
+            parsed.system_prompt,
+            PathBuf::from("migration.md"),
+        );
+
+        let by_intent = TaskContext {
+            user_intent: "Check this Migration".to_string(),
+            ..Default::default()
+        };
+        let by_path = TaskContext {
+            file_paths: vec![PathBuf::from("db/migrations/001.sql")],
+            ..Default::default()
+        };
+        let unrelated = TaskContext {
+            user_intent: "Explain main.rs".to_string(),
+            ..Default::default()
+        };
+
+        assert!(agent.can_handle(&by_intent).0 >= 0.6);
+        assert!(agent.can_handle(&by_path).0 >= 0.6);
+        assert_eq!(agent.can_handle(&unrelated).0, 0.0);
+    }
+
+    #[test]
+    fn test_routing_hints_normalize_file_types() {
+        let parsed = AgentParser::parse(&MIGRATION_AGENT.replace(
+            "---\n\n",
+            "file_types: [\".sql\", \"*.ddl\", psql]\n---\n\n",
+        ))
+        .unwrap();
+        let agent = UserAgent::new(
+            parsed.definition,
+            parsed.system_prompt,
+            PathBuf::from("migration.md"),
+        );
+
+        let hints = agent.routing_hints();
+        assert_eq!(hints.keywords, vec!["migration"]);
+        assert_eq!(hints.file_extensions, vec!["sql", "ddl", "psql"]);
+    }
+}
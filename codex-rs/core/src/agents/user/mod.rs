@@ -0,0 +1,13 @@
+//! User-defined agent loading.
+//!
+//! This module handles discovery and loading of agents declared as Markdown
+//! files in `~/.codex/agents` and in a repository's `.codex/agents`
+//! directory.
+
+pub mod loader;
+pub mod parser;
+
+pub use loader::UserAgent;
+pub use loader::UserAgentLoader;
+pub use parser::AgentDefinition;
+pub use parser::AgentParser;
@@ -0,0 +1,264 @@
+//! Markdown agent file parser with YAML frontmatter support.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::agents::AgentPermissions;
+use crate::agents::permissions::FileAccessPolicy;
+use crate::commands::parser::CommandParser;
+
+/// Parsed agent from a Markdown file.
+#[derive(Debug, Clone)]
+pub struct ParsedAgent {
+    pub definition: AgentDefinition,
+    pub system_prompt: String,
+}
+
+/// Agent definition from YAML frontmatter.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AgentDefinition {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// Keywords that activate the agent when found in the user's intent.
+    #[serde(default)]
+    pub activation_keywords: Vec<String>,
+    /// File extensions the agent specializes in, used by semantic routing.
+    #[serde(default)]
+    pub file_types: Vec<String>,
+    #[serde(default)]
+    pub permissions: PermissionsDefinition,
+    /// Tools the agent may call; empty means every tool its permissions allow.
+    #[serde(default)]
+    pub allowed_tools: Vec<String>,
+}
+
+/// Agent permissions as written in frontmatter.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PermissionsDefinition {
+    #[serde(default)]
+    pub file_access: FileAccessKind,
+    #[serde(default)]
+    pub allow_patterns: Vec<String>,
+    #[serde(default)]
+    pub deny_patterns: Vec<String>,
+    #[serde(default)]
+    pub shell_execution: bool,
+    #[serde(default)]
+    pub network_access: bool,
+    #[serde(default = "default_max_iterations")]
+    pub max_iterations: u32,
+    #[serde(default)]
+    pub can_delegate: bool,
+}
+
+impl Default for PermissionsDefinition {
+    fn default() -> Self {
+        Self {
+            file_access: FileAccessKind::default(),
+            allow_patterns: Vec::new(),
+            deny_patterns: Vec::new(),
+            shell_execution: false,
+            network_access: false,
+            max_iterations: default_max_iterations(),
+            can_delegate: false,
+        }
+    }
+}
+
+fn default_max_iterations() -> u32 {
+    AgentPermissions::default().max_iterations
+}
+
+/// File access level as written in frontmatter.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileAccessKind {
+    None,
+    #[default]
+    ReadOnly,
+    ReadWrite,
+}
+
+impl AgentDefinition {
+    /// Converts the frontmatter permissions into [`AgentPermissions`].
+    ///
+    /// Omitting `allow_patterns` grants access to every file that is not
+    /// denied.
+    pub fn agent_permissions(&self) -> AgentPermissions {
+        let permissions = &self.permissions;
+        let allow_patterns = if permissions.allow_patterns.is_empty() {
+            vec!["**".to_string()]
+        } else {
+            permissions.allow_patterns.clone()
+        };
+        let deny_patterns = permissions.deny_patterns.clone();
+        let file_access = match permissions.file_access {
+            FileAccessKind::None => FileAccessPolicy::NoAccess,
+            FileAccessKind::ReadOnly => FileAccessPolicy::ReadOnly {
+                allow_patterns,
+                deny_patterns,
+            },
+            FileAccessKind::ReadWrite => FileAccessPolicy::ReadWrite {
+                allow_patterns,
+                deny_patterns,
+            },
+        };
+
+        AgentPermissions {
+            file_access,
+            shell_execution: permissions.shell_execution,
+            network_access: permissions.network_access,
+            allowed_tools: self.allowed_tools.clone(),
+            max_iterations: permissions.max_iterations,
+            can_delegate: permissions.can_delegate,
+        }
+    }
+}
+
+/// Agent parser for Markdown files with YAML frontmatter.
+pub struct AgentParser;
+
+impl AgentParser {
+    /// Parses an agent from Markdown content.
+    ///
+    /// # Format
+    /// ```markdown
+    /// ---
+    /// id: migration-reviewer
+    /// name: Migration Reviewer
+    /// description: Reviews database migrations
+    /// activation_keywords: [migration, schema]
+    /// file_types: [sql]
+    /// permissions:
+    ///   file_access: read_only
+    ///   deny_patterns: ["**/*.pem"]
+    /// allowed_tools: [read_file]
+    /// ---
+    ///
+    /// System prompt for the agent.
+    /// ```
+    pub fn parse(content: &str) -> anyhow::Result<ParsedAgent> {
+        let (frontmatter, body) = CommandParser::split_frontmatter(content)?;
+
+        let definition: AgentDefinition = serde_yaml::from_str(&frontmatter)
+            .map_err(|e| anyhow::anyhow!("Failed to parse YAML frontmatter: {}", e))?;
+        let system_prompt = body.trim().to_string();
+
+        Self::validate(&definition, &system_prompt)?;
+
+        Ok(ParsedAgent {
+            definition,
+            system_prompt,
+        })
+    }
+
+    fn validate(definition: &AgentDefinition, system_prompt: &str) -> anyhow::Result<()> {
+        if definition.id.is_empty() {
+            anyhow::bail!("Agent id cannot be empty");
+        }
+
+        if !definition
+            .id
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+        {
+            anyhow::bail!(
+                "Agent id '{}' contains invalid characters (only alphanumeric, '-', '_' allowed)",
+                definition.id
+            );
+        }
+
+        if definition.name.is_empty() {
+            anyhow::bail!("Agent name cannot be empty");
+        }
+
+        if definition.description.is_empty() {
+            anyhow::bail!("Agent description cannot be empty");
+        }
+
+        if system_prompt.is_empty() {
+            anyhow::bail!("Agent system prompt cannot be empty");
+        }
+
+        if definition.permissions.max_iterations == 0 {
+            anyhow::bail!("Agent max_iterations must be at least 1");
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_parse_valid_agent() {
+        let input = r#"---
+id: migration-reviewer
+name: Migration Reviewer
+description: Reviews database migrations
+activation_keywords: [migration, schema]
+file_types: [".sql"]
+permissions:
+  file_access: read_only
+  allow_patterns: ["migrations/**"]
+  max_iterations: 3
+allowed_tools: [read_file]
+---
+
+You review SQL migrations for locking issues."#;
+
+        let parsed = AgentParser::parse(input).unwrap();
+        assert_eq!(parsed.definition.id, "migration-reviewer");
+        assert_eq!(
+            parsed.definition.activation_keywords,
+            vec!["migration", "schema"]
+        );
+        assert_eq!(
+            parsed.system_prompt,
+            "You review SQL migrations for locking issues."
+        );
+
+        assert_eq!(parsed.definition.file_types, vec![".sql"]);
+
+        let permissions = parsed.definition.agent_permissions();
+        assert_eq!(permissions.max_iterations, 3);
+        assert_eq!(permissions.allowed_tools, vec!["read_file"]);
+        assert!(!permissions.shell_execution);
+        assert!(permissions.can_read_file(Path::new("migrations/001.sql")));
+        assert!(!permissions.can_read_file(Path::new("src/main.rs")));
+        assert!(!permissions.can_write_file(Path::new("migrations/001.sql")));
+    }
+
+    #[test]
+    fn test_parse_defaults_to_read_only() {
+        let input = "---\nid: helper\nname: Helper\ndescription: Helps\n---\nBe helpful.";
+
+        let parsed = AgentParser::parse(input).unwrap();
+        let permissions = parsed.definition.agent_permissions();
+        assert_eq!(
+            permissions.max_iterations,
+            AgentPermissions::default().max_iterations
+        );
+        assert!(permissions.can_read_file(Path::new("src/main.rs")));
+        assert!(!permissions.can_read_file(Path::new(".env")));
+        assert!(!permissions.can_write_file(Path::new("src/main.rs")));
+    }
+
+    #[test]
+    fn test_invalid_agent_id() {
+        let input = "---\nid: bad id\nname: Bad\ndescription: Bad\n---\nPrompt";
+        let err = AgentParser::parse(input).unwrap_err();
+        assert!(err.to_string().contains("invalid characters"));
+    }
+
+    #[test]
+    fn test_missing_system_prompt() {
+        let input = "---\nid: empty\nname: Empty\ndescription: Empty\n---\n";
+        let err = AgentParser::parse(input).unwrap_err();
+        assert!(err.to_string().contains("system prompt"));
+    }
+}
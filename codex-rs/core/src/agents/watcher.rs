@@ -0,0 +1,229 @@
+//! Agent file watcher for hot-reload functionality.
+//!
+//! This module reloads user-defined agents into an [`AgentRouter`] when agent
+//! files in `~/.codex/agents/` or a repository's `.codex/agents/` are
+//! created, modified, or deleted.
+
+use notify::Event;
+use notify::EventKind;
+use notify::RecommendedWatcher;
+use notify::RecursiveMode;
+use notify::Watcher;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+use tokio::sync::mpsc;
+use tracing::debug;
+use tracing::error;
+use tracing::info;
+
+use super::router::AgentRouter;
+use super::user::UserAgentLoader;
+
+/// Debounce duration for file system events (milliseconds).
+const DEBOUNCE_MS: u64 = 300;
+
+/// Agent file watcher that monitors agent directories for changes and
+/// reloads user-defined agents into the router.
+pub struct AgentWatcher {
+    _watcher: RecommendedWatcher,
+    _shutdown_tx: mpsc::UnboundedSender<()>,
+}
+
+impl AgentWatcher {
+    /// Creates a new agent watcher for the loader's directories.
+    ///
+    /// Directories that do not exist are not watched; agents added to them
+    /// are picked up the next time the watcher is created.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use std::path::Path;
+    /// # use codex_core::agents::AgentRouter;
+    /// # use codex_core::agents::user::UserAgentLoader;
+    /// # use codex_core::agents::watcher::AgentWatcher;
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let router = Arc::new(AgentRouter::new());
+    /// let loader = UserAgentLoader::new(UserAgentLoader::default_dirs(
+    ///     Path::new("/home/me/.codex"),
+    ///     Some(Path::new("/workspace")),
+    /// ));
+    /// router.set_user_agents(loader.load_all().await?).await;
+    /// let watcher = AgentWatcher::new(loader, router)?;
+    /// // Watcher runs in background, automatically reloading on file changes
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(loader: UserAgentLoader, router: Arc<AgentRouter>) -> Result<Self, notify::Error> {
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
+        let (shutdown_tx, shutdown_rx) = mpsc::unbounded_channel();
+
+        let mut watcher = RecommendedWatcher::new(
+            move |res: Result<Event, notify::Error>| {
+                if let Ok(event) = res
+                    && Self::is_relevant_event(&event)
+                {
+                    let _ = event_tx.send(event);
+                }
+            },
+            notify::Config::default(),
+        )?;
+
+        for dir in loader.agents_dirs() {
+            if !dir.is_dir() {
+                debug!(
+                    "Agent directory does not exist, not watching: {}",
+                    dir.display()
+                );
+                continue;
+            }
+            watcher.watch(dir, RecursiveMode::NonRecursive)?;
+            info!("Agent watcher started for directory: {}", dir.display());
+        }
+
+        tokio::spawn(Self::handle_events(
+            event_rx,
+            shutdown_rx,
+            loader,
+            router,
+            Duration::from_millis(DEBOUNCE_MS),
+        ));
+
+        Ok(Self {
+            _watcher: watcher,
+            _shutdown_tx: shutdown_tx,
+        })
+    }
+
+    /// Checks if an event is relevant for agent reload.
+    fn is_relevant_event(event: &Event) -> bool {
+        matches!(
+            event.kind,
+            EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+        ) && event.paths.iter().any(|p| Self::is_agent_file(p))
+    }
+
+    /// Checks if a path is an agent file (.md extension).
+    fn is_agent_file(path: &Path) -> bool {
+        path.extension().and_then(|s| s.to_str()) == Some("md")
+    }
+
+    /// Background task that handles file system events with debouncing.
+    async fn handle_events(
+        mut event_rx: mpsc::UnboundedReceiver<Event>,
+        mut shutdown_rx: mpsc::UnboundedReceiver<()>,
+        loader: UserAgentLoader,
+        router: Arc<AgentRouter>,
+        debounce_duration: Duration,
+    ) {
+        let mut pending_reloads: HashMap<PathBuf, Instant> = HashMap::new();
+        let mut reload_timer = tokio::time::interval(Duration::from_millis(50));
+
+        loop {
+            tokio::select! {
+                _ = shutdown_rx.recv() => {
+                    debug!("Agent watcher shutting down");
+                    break;
+                }
+
+                Some(event) = event_rx.recv() => {
+                    let now = Instant::now();
+                    for path in event.paths {
+                        if Self::is_agent_file(&path) {
+                            debug!("File event for agent file: {}", path.display());
+                            pending_reloads.insert(path, now);
+                        }
+                    }
+                }
+
+                _ = reload_timer.tick() => {
+                    let now = Instant::now();
+                    let before = pending_reloads.len();
+                    pending_reloads
+                        .retain(|_, &mut event_time| now.duration_since(event_time) < debounce_duration);
+
+                    if pending_reloads.len() < before {
+                        info!("Reloading user-defined agents due to file changes");
+                        match loader.load_all().await {
+                            Ok(agents) => {
+                                info!("Loaded {} user-defined agents", agents.len());
+                                router.set_user_agents(agents).await;
+                            }
+                            Err(e) => {
+                                error!("Failed to reload user-defined agents: {}", e);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        debug!("Agent watcher event handler terminated");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::AgentId;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_is_relevant_event() {
+        let create = Event {
+            kind: EventKind::Create(notify::event::CreateKind::File),
+            paths: vec![PathBuf::from("reviewer.md")],
+            attrs: Default::default(),
+        };
+        let access = Event {
+            kind: EventKind::Access(notify::event::AccessKind::Any),
+            paths: vec![PathBuf::from("reviewer.md")],
+            attrs: Default::default(),
+        };
+        let other_file = Event {
+            kind: EventKind::Create(notify::event::CreateKind::File),
+            paths: vec![PathBuf::from("reviewer.txt")],
+            attrs: Default::default(),
+        };
+
+        assert!(AgentWatcher::is_relevant_event(&create));
+        assert!(!AgentWatcher::is_relevant_event(&access));
+        assert!(!AgentWatcher::is_relevant_event(&other_file));
+    }
+
+    #[tokio::test]
+    async fn test_watcher_skips_missing_directories() {
+        let temp = TempDir::new().unwrap();
+        let loader = UserAgentLoader::new(vec![temp.path().join("missing")]);
+
+        let watcher = AgentWatcher::new(loader, Arc::new(AgentRouter::new()));
+        assert!(watcher.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_watcher_reloads_agents() {
+        let temp = TempDir::new().unwrap();
+        let router = Arc::new(AgentRouter::new());
+        let loader = UserAgentLoader::new(vec![temp.path().to_path_buf()]);
+        let _watcher = AgentWatcher::new(loader, router.clone()).unwrap();
+
+        tokio::fs::write(
+            temp.path().join("helper.md"),
+            "---\nid: helper\nname: Helper\ndescription: Helps\n---\nBe helpful.",
+        )
+        .await
+        .unwrap();
+
+        let id = AgentId::from("helper");
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while router.get_agent(&id).await.is_none() {
+            assert!(Instant::now() < deadline, "agent was not reloaded");
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    }
+}
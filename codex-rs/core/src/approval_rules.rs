@@ -0,0 +1,350 @@
+//! Persistent, per-project approval rules for shell commands.
+//!
+//! Rules live in `$CODEX_HOME/approval_rules.toml` and are scoped to a
+//! project root (the git repository root, or the working directory outside a
+//! repository):
+//!
+//! ```toml
+//! [[rules]]
+//! project = "/home/me/src/app"
+//! pattern = "cargo test *"
+//! created_at = "2025-01-01T00:00:00Z"
+//! expires_at = "2025-02-01T00:00:00Z"
+//! ```
+//!
+//! A pattern is split into words like a shell command line. Each word is a
+//! glob matched against the corresponding argument of the command, where `*`
+//! matches any run of characters, `?` matches a single character, and a
+//! backslash makes the next character literal. A final word consisting of a
+//! lone `*` matches any number of remaining arguments, including none, so
+//! `cargo test *` matches both `cargo test` and `cargo test -p codex-core`.
+//!
+//! The executor consults these rules only for commands that would otherwise
+//! require the user's approval.
+
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use chrono::DateTime;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+use tempfile::NamedTempFile;
+
+use crate::git_info::resolve_root_git_project_for_trust;
+
+/// File in `CODEX_HOME` that stores approval rules.
+pub const APPROVAL_RULES_FILE: &str = "approval_rules.toml";
+
+/// A command pattern the user approved for a project.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ApprovalRule {
+    /// Project root the rule applies to.
+    pub project: PathBuf,
+    /// Shell-style pattern matched against the command's arguments.
+    pub pattern: String,
+    /// When the rule was added.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+    /// The rule no longer applies after this time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl ApprovalRule {
+    pub fn new(project: PathBuf, pattern: impl Into<String>) -> Self {
+        Self {
+            project,
+            pattern: pattern.into(),
+            created_at: Some(Utc::now()),
+            expires_at: None,
+        }
+    }
+
+    /// Creates a rule that matches exactly `command` and nothing else.
+    pub fn exact(project: PathBuf, command: &[String]) -> Option<Self> {
+        let escaped: Vec<String> = command.iter().map(|word| escape_glob(word)).collect();
+        let pattern = shlex::try_join(escaped.iter().map(String::as_str)).ok()?;
+        Some(Self::new(project, pattern))
+    }
+
+    pub fn with_expiry(mut self, expires_at: DateTime<Utc>) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+
+    /// Whether the rule's pattern matches `command`. Patterns that cannot be
+    /// parsed match nothing.
+    pub fn matches(&self, command: &[String]) -> bool {
+        let Some(words) = shlex::split(&self.pattern) else {
+            return false;
+        };
+        match words.split_last() {
+            None => false,
+            Some((last, prefix)) if last == "*" => {
+                command.len() >= prefix.len()
+                    && prefix
+                        .iter()
+                        .zip(command)
+                        .all(|(pattern, arg)| glob_matches(pattern, arg))
+            }
+            Some(_) => {
+                command.len() == words.len()
+                    && words
+                        .iter()
+                        .zip(command)
+                        .all(|(pattern, arg)| glob_matches(pattern, arg))
+            }
+        }
+    }
+}
+
+/// The approval rules stored in `CODEX_HOME`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ApprovalRules {
+    #[serde(default)]
+    pub rules: Vec<ApprovalRule>,
+}
+
+impl ApprovalRules {
+    /// Loads the rules from `codex_home`. A missing file yields no rules.
+    pub fn load(codex_home: &Path) -> io::Result<Self> {
+        let contents = match std::fs::read_to_string(codex_home.join(APPROVAL_RULES_FILE)) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(err),
+        };
+        toml::from_str(&contents).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// Writes the rules to `codex_home`, replacing the previous file
+    /// atomically.
+    pub fn save(&self, codex_home: &Path) -> io::Result<()> {
+        let contents = toml::to_string_pretty(self).map_err(io::Error::other)?;
+        std::fs::create_dir_all(codex_home)?;
+        let tmp_file = NamedTempFile::new_in(codex_home)?;
+        std::fs::write(tmp_file.path(), contents)?;
+        tmp_file
+            .persist(codex_home.join(APPROVAL_RULES_FILE))
+            .map_err(|err| err.error)?;
+        Ok(())
+    }
+
+    /// Rules that apply to `project`, including expired ones.
+    pub fn for_project<'a>(&'a self, project: &'a Path) -> impl Iterator<Item = &'a ApprovalRule> {
+        self.rules
+            .iter()
+            .filter(move |rule| rule.project == project)
+    }
+
+    /// The first unexpired rule for `project` that matches `command`.
+    pub fn find_match(
+        &self,
+        project: &Path,
+        command: &[String],
+        now: DateTime<Utc>,
+    ) -> Option<&ApprovalRule> {
+        self.rules
+            .iter()
+            .find(|rule| rule.project == project && !rule.is_expired(now) && rule.matches(command))
+    }
+
+    /// Adds `rule`, replacing any existing rule with the same project and
+    /// pattern.
+    pub fn add(&mut self, rule: ApprovalRule) {
+        self.revoke(&rule.project, &rule.pattern);
+        self.rules.push(rule);
+    }
+
+    /// Removes the rule for `project` with `pattern`. Returns whether a rule
+    /// was removed.
+    pub fn revoke(&mut self, project: &Path, pattern: &str) -> bool {
+        let before = self.rules.len();
+        self.rules
+            .retain(|rule| !(rule.project == project && rule.pattern == pattern));
+        self.rules.len() != before
+    }
+
+    /// Removes every rule that has expired by `now`.
+    pub fn prune_expired(&mut self, now: DateTime<Utc>) {
+        self.rules.retain(|rule| !rule.is_expired(now));
+    }
+}
+
+/// The project that approval rules for commands run in `cwd` are scoped to.
+pub fn project_for_cwd(cwd: &Path) -> PathBuf {
+    resolve_root_git_project_for_trust(cwd).unwrap_or_else(|| cwd.to_path_buf())
+}
+
+/// Loads the rules for `codex_home`, removes the one for the project of
+/// `cwd` with `pattern`, and saves the result.
+pub fn revoke_rule(codex_home: &Path, cwd: &Path, pattern: &str) -> io::Result<bool> {
+    let mut rules = ApprovalRules::load(codex_home)?;
+    let removed = rules.revoke(&project_for_cwd(cwd), pattern);
+    if removed {
+        rules.save(codex_home)?;
+    }
+    Ok(removed)
+}
+
+fn escape_glob(word: &str) -> String {
+    let mut escaped = String::with_capacity(word.len());
+    for c in word.chars() {
+        if matches!(c, '*' | '?' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Matches `text` against a glob where `*` matches any run of characters,
+/// `?` matches one character, and `\` escapes the next character.
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Position after the most recent `*` and the text position it resumed at.
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                p += 1;
+                backtrack = Some((p, t));
+                continue;
+            }
+            Some('?') => {
+                p += 1;
+                t += 1;
+                continue;
+            }
+            Some('\\') if pattern.get(p + 1) == Some(&text[t]) => {
+                p += 2;
+                t += 1;
+                continue;
+            }
+            Some(c) if *c != '\\' && *c == text[t] => {
+                p += 1;
+                t += 1;
+                continue;
+            }
+            _ => {}
+        }
+        match backtrack {
+            Some((resume_p, resume_t)) => {
+                p = resume_p;
+                t = resume_t + 1;
+                backtrack = Some((resume_p, resume_t + 1));
+            }
+            None => return false,
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn cmd(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    fn rule(pattern: &str) -> ApprovalRule {
+        ApprovalRule::new(PathBuf::from("/repo"), pattern)
+    }
+
+    #[test]
+    fn glob_matching() {
+        assert!(glob_matches("test", "test"));
+        assert!(glob_matches("test*", "test:unit"));
+        assert!(glob_matches("*.rs", "src/lib.rs"));
+        assert!(glob_matches("?at", "cat"));
+        assert!(glob_matches("a*b*c", "aXbYbc"));
+        assert!(!glob_matches("test", "tests"));
+        assert!(!glob_matches("*.rs", "lib.rs.bak"));
+        assert!(glob_matches("\\*.rs", "*.rs"));
+        assert!(!glob_matches("\\*.rs", "lib.rs"));
+    }
+
+    #[test]
+    fn trailing_star_matches_remaining_args() {
+        let rule = rule("cargo test *");
+
+        assert!(rule.matches(&cmd(&["cargo", "test"])));
+        assert!(rule.matches(&cmd(&["cargo", "test", "-p", "codex-core"])));
+        assert!(!rule.matches(&cmd(&["cargo", "build"])));
+        assert!(!rule.matches(&cmd(&["cargo"])));
+    }
+
+    #[test]
+    fn pattern_without_trailing_star_matches_whole_command() {
+        let rule = rule("npm run 'test:*'");
+
+        assert!(rule.matches(&cmd(&["npm", "run", "test:unit"])));
+        assert!(!rule.matches(&cmd(&["npm", "run", "test:unit", "--watch"])));
+        assert!(!rule.matches(&cmd(&["npm", "run", "build"])));
+    }
+
+    #[test]
+    fn exact_rule_treats_glob_characters_literally() {
+        let rule = ApprovalRule::exact(PathBuf::from("/repo"), &cmd(&["ls", "*.rs", "a b"]))
+            .expect("rule");
+
+        assert!(rule.matches(&cmd(&["ls", "*.rs", "a b"])));
+        assert!(!rule.matches(&cmd(&["ls", "lib.rs", "a b"])));
+    }
+
+    #[test]
+    fn find_match_skips_other_projects_and_expired_rules() {
+        let now = Utc::now();
+        let rules = ApprovalRules {
+            rules: vec![
+                ApprovalRule::new(PathBuf::from("/other"), "make *"),
+                rule("make *").with_expiry(now - Duration::hours(1)),
+                rule("make test"),
+            ],
+        };
+
+        let found = rules.find_match(Path::new("/repo"), &cmd(&["make", "test"]), now);
+        assert_eq!(found.map(|rule| rule.pattern.as_str()), Some("make test"));
+        assert!(
+            rules
+                .find_match(Path::new("/repo"), &cmd(&["make", "lint"]), now)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn save_load_and_revoke() {
+        let codex_home = TempDir::new().unwrap();
+        assert_eq!(
+            ApprovalRules::load(codex_home.path()).unwrap(),
+            ApprovalRules::default()
+        );
+
+        let mut rules = ApprovalRules::default();
+        rules.add(rule("cargo test *"));
+        rules.add(rule("cargo test *"));
+        rules.add(rule("git status"));
+        rules.save(codex_home.path()).unwrap();
+
+        let mut loaded = ApprovalRules::load(codex_home.path()).unwrap();
+        assert_eq!(loaded, rules);
+        assert_eq!(loaded.rules.len(), 2);
+
+        assert!(loaded.revoke(Path::new("/repo"), "git status"));
+        assert!(!loaded.revoke(Path::new("/repo"), "git status"));
+        assert_eq!(loaded.for_project(Path::new("/repo")).count(), 1);
+    }
+}
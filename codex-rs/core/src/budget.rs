@@ -0,0 +1,583 @@
+//! Enforcement of the `[budget]` ceilings on tokens, turns, tool calls,
+//! wall-clock time and estimated cost.
+//!
+//! The tracker only records usage and reports violations; stopping the turn
+//! is left to the task runner so it can report the abort to the client.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+
+use tokio::time::Instant;
+use tracing::warn;
+
+use crate::config_types::BudgetLimits;
+use crate::config_types::ModelPrice;
+use crate::protocol::BudgetRemaining;
+use crate::protocol::TokenUsage;
+
+/// Built-in prices in USD per million tokens, matched by model slug prefix.
+/// More specific prefixes come first.
+const BUILTIN_MODEL_PRICES: &[(&str, ModelPrice)] = &[
+    ("gpt-5-codex", price(1.25, 0.125, 10.0)),
+    ("gpt-5-mini", price(0.25, 0.025, 2.0)),
+    ("gpt-5-nano", price(0.05, 0.005, 0.4)),
+    ("gpt-5", price(1.25, 0.125, 10.0)),
+    ("codex-mini", price(1.5, 0.375, 6.0)),
+    ("gpt-4.1-mini", price(0.4, 0.1, 1.6)),
+    ("gpt-4.1-nano", price(0.1, 0.025, 0.4)),
+    ("gpt-4.1", price(2.0, 0.5, 8.0)),
+    ("gpt-4o-mini", price(0.15, 0.075, 0.6)),
+    ("gpt-4o", price(2.5, 1.25, 10.0)),
+    ("o4-mini", price(1.1, 0.275, 4.4)),
+    ("o3", price(2.0, 0.5, 8.0)),
+];
+
+const fn price(input: f64, cached_input: f64, output: f64) -> ModelPrice {
+    ModelPrice {
+        input,
+        cached_input: Some(cached_input),
+        output,
+    }
+}
+
+/// Returns the price of `model`, preferring `overrides` over the built-in table.
+pub(crate) fn model_price(
+    overrides: &HashMap<String, ModelPrice>,
+    model: &str,
+) -> Option<ModelPrice> {
+    overrides.get(model).copied().or_else(|| {
+        BUILTIN_MODEL_PRICES
+            .iter()
+            .find(|(prefix, _)| model.starts_with(prefix))
+            .map(|(_, price)| *price)
+    })
+}
+
+/// Estimated cost of `usage` in USD.
+pub(crate) fn estimate_cost(price: &ModelPrice, usage: &TokenUsage) -> f64 {
+    let cached_input = price.cached_input.unwrap_or(price.input);
+    (usage.non_cached_input() as f64 * price.input
+        + usage.cached_input() as f64 * cached_input
+        + usage.output_tokens as f64 * price.output)
+        / 1_000_000.0
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BudgetScope {
+    Session,
+    Turn,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BudgetResource {
+    Tokens,
+    Turns,
+    ToolCalls,
+    WallClock,
+    Cost,
+}
+
+/// A budget that ran out, with the amount used and the configured limit.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct BudgetExceeded {
+    pub(crate) scope: BudgetScope,
+    pub(crate) resource: BudgetResource,
+    pub(crate) used: f64,
+    pub(crate) limit: f64,
+}
+
+impl fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let scope = match self.scope {
+            BudgetScope::Session => "Session",
+            BudgetScope::Turn => "Turn",
+        };
+        let (resource, used, limit) = match self.resource {
+            BudgetResource::Tokens => ("token", self.used.to_string(), self.limit.to_string()),
+            BudgetResource::Turns => ("turn", self.used.to_string(), self.limit.to_string()),
+            BudgetResource::ToolCalls => {
+                ("tool call", self.used.to_string(), self.limit.to_string())
+            }
+            BudgetResource::WallClock => (
+                "wall-clock",
+                format!("{}s", self.used.round()),
+                format!("{}s", self.limit),
+            ),
+            BudgetResource::Cost => (
+                "cost",
+                format!("${:.2}", self.used),
+                format!("${:.2}", self.limit),
+            ),
+        };
+        write!(
+            f,
+            "{scope} {resource} budget exhausted ({used} of {limit} used)"
+        )
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Usage {
+    tokens: u64,
+    tool_calls: u64,
+    cost_usd: f64,
+}
+
+/// Usage counted against [`BudgetLimits`] over a session and its current turn.
+#[derive(Debug)]
+pub(crate) struct BudgetTracker {
+    limits: BudgetLimits,
+    prices: HashMap<String, ModelPrice>,
+    session_started_at: Instant,
+    turn_started_at: Instant,
+    session: Usage,
+    turn: Usage,
+    turns: u64,
+    /// First budget that ran out since the turn started.
+    exceeded: Option<BudgetExceeded>,
+    warned_unpriced_model: bool,
+}
+
+impl Default for BudgetTracker {
+    fn default() -> Self {
+        Self::new(BudgetLimits::default(), HashMap::new())
+    }
+}
+
+impl BudgetTracker {
+    pub(crate) fn new(limits: BudgetLimits, prices: HashMap<String, ModelPrice>) -> Self {
+        let now = Instant::now();
+        Self {
+            limits,
+            prices,
+            session_started_at: now,
+            turn_started_at: now,
+            session: Usage::default(),
+            turn: Usage::default(),
+            turns: 0,
+            exceeded: None,
+            warned_unpriced_model: false,
+        }
+    }
+
+    /// Starts counting a new turn. Fails if the session budget is already
+    /// spent; `counts_as_turn` is false for tasks such as compaction that
+    /// should not use up `session_turns`.
+    pub(crate) fn start_turn(&mut self, counts_as_turn: bool) -> Result<(), BudgetExceeded> {
+        let now = Instant::now();
+        self.turn = Usage::default();
+        self.turn_started_at = now;
+        self.exceeded = None;
+
+        let limits = self.limits;
+        let session = self.session;
+        let elapsed = now.duration_since(self.session_started_at).as_secs_f64();
+        let checks = [
+            (
+                BudgetResource::Turns,
+                counts_as_turn.then_some(self.turns as f64),
+                limits.session_turns.map(|limit| limit as f64),
+            ),
+            (
+                BudgetResource::Tokens,
+                Some(session.tokens as f64),
+                limits.session_tokens.map(|limit| limit as f64),
+            ),
+            (
+                BudgetResource::ToolCalls,
+                Some(session.tool_calls as f64),
+                limits.session_tool_calls.map(|limit| limit as f64),
+            ),
+            (
+                BudgetResource::WallClock,
+                Some(elapsed),
+                limits.session_wall_clock_secs.map(|limit| limit as f64),
+            ),
+            (
+                BudgetResource::Cost,
+                Some(session.cost_usd),
+                limits.session_cost_usd,
+            ),
+        ];
+        for (resource, used, limit) in checks {
+            if let (Some(used), Some(limit)) = (used, limit)
+                && used >= limit
+            {
+                return Err(self.exceed(BudgetScope::Session, resource, used, limit));
+            }
+        }
+
+        if counts_as_turn {
+            self.turns += 1;
+        }
+        Ok(())
+    }
+
+    /// Counts the tokens and estimated cost of one model response.
+    pub(crate) fn record_token_usage(&mut self, model: &str, usage: &TokenUsage) {
+        let tokens = usage.blended_total();
+        let cost = match model_price(&self.prices, model) {
+            Some(price) => estimate_cost(&price, usage),
+            None => {
+                let limits_cost =
+                    self.limits.session_cost_usd.is_some() || self.limits.turn_cost_usd.is_some();
+                if limits_cost && !self.warned_unpriced_model {
+                    warn!("no price known for model {model}; cost budgets are not enforced");
+                    self.warned_unpriced_model = true;
+                }
+                0.0
+            }
+        };
+        for usage in [&mut self.session, &mut self.turn] {
+            usage.tokens += tokens;
+            usage.cost_usd += cost;
+        }
+
+        let limits = self.limits;
+        let checks = [
+            (
+                BudgetScope::Session,
+                BudgetResource::Tokens,
+                self.session.tokens as f64,
+                limits.session_tokens.map(|limit| limit as f64),
+            ),
+            (
+                BudgetScope::Session,
+                BudgetResource::Cost,
+                self.session.cost_usd,
+                limits.session_cost_usd,
+            ),
+            (
+                BudgetScope::Turn,
+                BudgetResource::Tokens,
+                self.turn.tokens as f64,
+                limits.turn_tokens.map(|limit| limit as f64),
+            ),
+            (
+                BudgetScope::Turn,
+                BudgetResource::Cost,
+                self.turn.cost_usd,
+                limits.turn_cost_usd,
+            ),
+        ];
+        for (scope, resource, used, limit) in checks {
+            if let Some(limit) = limit
+                && used >= limit
+            {
+                self.exceed(scope, resource, used, limit);
+                return;
+            }
+        }
+    }
+
+    /// Fails if the session's token or cost budget is spent, or a budget ran
+    /// out during the current turn. Model requests made on behalf of others,
+    /// such as MCP sampling, check this before spending more.
+    pub(crate) fn check_model_request(&self) -> Result<(), BudgetExceeded> {
+        if let Some(exceeded) = &self.exceeded {
+            return Err(exceeded.clone());
+        }
+        let checks = [
+            (
+                BudgetResource::Tokens,
+                self.session.tokens as f64,
+                self.limits.session_tokens.map(|limit| limit as f64),
+            ),
+            (
+                BudgetResource::Cost,
+                self.session.cost_usd,
+                self.limits.session_cost_usd,
+            ),
+        ];
+        for (resource, used, limit) in checks {
+            if let Some(limit) = limit
+                && used >= limit
+            {
+                return Err(BudgetExceeded {
+                    scope: BudgetScope::Session,
+                    resource,
+                    used,
+                    limit,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Counts a tool call, refusing it if it would go over a tool call budget.
+    pub(crate) fn record_tool_call(&mut self) -> Result<(), BudgetExceeded> {
+        let checks = [
+            (
+                BudgetScope::Session,
+                self.session.tool_calls,
+                self.limits.session_tool_calls,
+            ),
+            (
+                BudgetScope::Turn,
+                self.turn.tool_calls,
+                self.limits.turn_tool_calls,
+            ),
+        ];
+        for (scope, used, limit) in checks {
+            if let Some(limit) = limit
+                && used >= limit
+            {
+                return Err(self.exceed(
+                    scope,
+                    BudgetResource::ToolCalls,
+                    used as f64,
+                    limit as f64,
+                ));
+            }
+        }
+        self.session.tool_calls += 1;
+        self.turn.tool_calls += 1;
+        Ok(())
+    }
+
+    /// When the current turn runs out of wall-clock time, if it is limited.
+    pub(crate) fn deadline(&self) -> Option<Instant> {
+        let session = self
+            .limits
+            .session_wall_clock_secs
+            .map(|secs| self.session_started_at + Duration::from_secs(secs));
+        let turn = self
+            .limits
+            .turn_wall_clock_secs
+            .map(|secs| self.turn_started_at + Duration::from_secs(secs));
+        match (session, turn) {
+            (Some(session), Some(turn)) => Some(session.min(turn)),
+            (deadline, None) | (None, deadline) => deadline,
+        }
+    }
+
+    /// Records that the turn reached its [`Self::deadline`].
+    pub(crate) fn record_deadline_reached(&mut self) -> BudgetExceeded {
+        let now = Instant::now();
+        let session_elapsed = now.duration_since(self.session_started_at).as_secs_f64();
+        let turn_elapsed = now.duration_since(self.turn_started_at).as_secs_f64();
+        let (scope, used, limit) = match (
+            self.limits.session_wall_clock_secs,
+            self.limits.turn_wall_clock_secs,
+        ) {
+            (Some(session), _) if session_elapsed >= session as f64 => {
+                (BudgetScope::Session, session_elapsed, session as f64)
+            }
+            (_, Some(turn)) => (BudgetScope::Turn, turn_elapsed, turn as f64),
+            (session, None) => (
+                BudgetScope::Session,
+                session_elapsed,
+                session.unwrap_or_default() as f64,
+            ),
+        };
+        self.exceed(scope, BudgetResource::WallClock, used, limit)
+    }
+
+    /// Takes the budget that ran out during the current turn, if any.
+    pub(crate) fn take_exceeded(&mut self) -> Option<BudgetExceeded> {
+        self.exceeded.take()
+    }
+
+    pub(crate) fn is_exceeded(&self) -> bool {
+        self.exceeded.is_some()
+    }
+
+    /// What is left of the session budget, or `None` if it is unlimited.
+    pub(crate) fn remaining(&self) -> Option<BudgetRemaining> {
+        let limits = &self.limits;
+        let remaining = BudgetRemaining {
+            tokens: limits
+                .session_tokens
+                .map(|limit| limit.saturating_sub(self.session.tokens)),
+            turns: limits
+                .session_turns
+                .map(|limit| limit.saturating_sub(self.turns)),
+            tool_calls: limits
+                .session_tool_calls
+                .map(|limit| limit.saturating_sub(self.session.tool_calls)),
+            wall_clock_secs: limits
+                .session_wall_clock_secs
+                .map(|limit| limit.saturating_sub(self.session_started_at.elapsed().as_secs())),
+            cost_usd: limits
+                .session_cost_usd
+                .map(|limit| (limit - self.session.cost_usd).max(0.0)),
+        };
+        (remaining != BudgetRemaining::default()).then_some(remaining)
+    }
+
+    fn exceed(
+        &mut self,
+        scope: BudgetScope,
+        resource: BudgetResource,
+        used: f64,
+        limit: f64,
+    ) -> BudgetExceeded {
+        let exceeded = BudgetExceeded {
+            scope,
+            resource,
+            used,
+            limit,
+        };
+        self.exceeded.get_or_insert_with(|| exceeded.clone());
+        exceeded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn usage(input: u64, cached_input: u64, output: u64) -> TokenUsage {
+        TokenUsage {
+            input_tokens: input,
+            cached_input_tokens: cached_input,
+            output_tokens: output,
+            reasoning_output_tokens: 0,
+            total_tokens: input + output,
+        }
+    }
+
+    #[test]
+    fn configured_prices_override_builtin_prefix_matches() {
+        let overrides = HashMap::from([(
+            "gpt-5".to_string(),
+            ModelPrice {
+                input: 2.0,
+                cached_input: None,
+                output: 4.0,
+            },
+        )]);
+
+        assert_eq!(model_price(&overrides, "gpt-5").map(|p| p.input), Some(2.0));
+        assert_eq!(
+            model_price(&overrides, "gpt-5-codex-2025").map(|p| p.output),
+            Some(10.0)
+        );
+        assert_eq!(model_price(&overrides, "unknown-model"), None);
+
+        let cost = estimate_cost(&overrides["gpt-5"], &usage(1_000_000, 500_000, 500_000));
+        assert_eq!(cost, 4.0);
+    }
+
+    #[test]
+    fn token_and_cost_budgets_are_checked_after_each_response() {
+        let mut tracker = BudgetTracker::new(
+            BudgetLimits {
+                turn_tokens: Some(1_000),
+                session_cost_usd: Some(1.0),
+                ..Default::default()
+            },
+            HashMap::new(),
+        );
+        tracker.start_turn(true).expect("within budget");
+
+        tracker.record_token_usage("gpt-5", &usage(400, 0, 100));
+        assert!(!tracker.is_exceeded());
+
+        tracker.record_token_usage("gpt-5", &usage(400, 0, 100));
+        assert_eq!(
+            tracker.take_exceeded(),
+            Some(BudgetExceeded {
+                scope: BudgetScope::Turn,
+                resource: BudgetResource::Tokens,
+                used: 1_000.0,
+                limit: 1_000.0,
+            })
+        );
+
+        // A new turn starts with a fresh turn budget.
+        tracker.start_turn(true).expect("within budget");
+        tracker.record_token_usage("gpt-5", &usage(0, 0, 110_000));
+        let exceeded = tracker.take_exceeded().expect("cost budget spent");
+        assert_eq!(exceeded.resource, BudgetResource::Cost);
+        assert_eq!(
+            exceeded.to_string(),
+            "Session cost budget exhausted ($1.10 of $1.00 used)"
+        );
+
+        // And the session budget keeps later turns from starting.
+        let err = tracker.start_turn(true).expect_err("session budget spent");
+        assert_eq!(err.scope, BudgetScope::Session);
+        assert_eq!(err.resource, BudgetResource::Cost);
+    }
+
+    #[test]
+    fn model_requests_are_refused_once_the_session_budget_is_spent() {
+        let mut tracker = BudgetTracker::new(
+            BudgetLimits {
+                session_tokens: Some(1_000),
+                ..Default::default()
+            },
+            HashMap::new(),
+        );
+        assert_eq!(tracker.check_model_request(), Ok(()));
+
+        tracker.record_token_usage("gpt-5", &usage(900, 0, 100));
+        // The session budget stays spent once the turn has reported it.
+        tracker.take_exceeded();
+        assert_eq!(
+            tracker.check_model_request(),
+            Err(BudgetExceeded {
+                scope: BudgetScope::Session,
+                resource: BudgetResource::Tokens,
+                used: 1_000.0,
+                limit: 1_000.0,
+            })
+        );
+    }
+
+    #[test]
+    fn tool_calls_over_budget_are_refused() {
+        let mut tracker = BudgetTracker::new(
+            BudgetLimits {
+                turn_tool_calls: Some(2),
+                session_turns: Some(2),
+                ..Default::default()
+            },
+            HashMap::new(),
+        );
+        tracker.start_turn(true).expect("first turn");
+        tracker.record_tool_call().expect("first call");
+        tracker.record_tool_call().expect("second call");
+        let err = tracker.record_tool_call().expect_err("third call");
+        assert_eq!(
+            err.to_string(),
+            "Turn tool call budget exhausted (2 of 2 used)"
+        );
+        assert!(tracker.is_exceeded());
+
+        tracker
+            .start_turn(false)
+            .expect("compaction does not count as a turn");
+        tracker.start_turn(true).expect("second turn");
+        assert_eq!(
+            tracker.remaining(),
+            Some(BudgetRemaining {
+                turns: Some(0),
+                ..Default::default()
+            })
+        );
+        let err = tracker.start_turn(true).expect_err("no turns left");
+        assert_eq!(err.resource, BudgetResource::Turns);
+    }
+
+    #[test]
+    fn deadline_is_the_earlier_wall_clock_limit() {
+        let tracker = BudgetTracker::new(
+            BudgetLimits {
+                session_wall_clock_secs: Some(600),
+                turn_wall_clock_secs: Some(60),
+                ..Default::default()
+            },
+            HashMap::new(),
+        );
+        assert_eq!(
+            tracker.deadline(),
+            Some(tracker.turn_started_at + Duration::from_secs(60))
+        );
+        assert_eq!(BudgetTracker::default().deadline(), None);
+        assert_eq!(BudgetTracker::default().remaining(), None);
+    }
+}
@@ -113,6 +113,12 @@ impl ModelClient {
             .or_else(|| get_model_info(&self.config.model_family).map(|info| info.context_window))
     }
 
+    pub fn get_model_max_output_tokens(&self) -> Option<u64> {
+        self.config
+            .model_max_output_tokens
+            .or_else(|| get_model_info(&self.config.model_family).map(|info| info.max_output_tokens))
+    }
+
     pub fn get_auto_compact_token_limit(&self) -> Option<i64> {
         self.config.model_auto_compact_token_limit.or_else(|| {
             get_model_info(&self.config.model_family).and_then(|info| info.auto_compact_token_limit)
@@ -234,6 +240,7 @@ impl ModelClient {
             include,
             prompt_cache_key: Some(self.conversation_id.to_string()),
             text,
+            max_output_tokens: prompt.max_output_tokens,
         };
 
         let mut payload_json = serde_json::to_value(&payload)?;
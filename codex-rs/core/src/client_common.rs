@@ -41,6 +41,10 @@ pub struct Prompt {
 
     /// Optional the output schema for the model's response.
     pub output_schema: Option<Value>,
+
+    /// Optional cap on the tokens the model may generate, when less than
+    /// the model allows is wanted.
+    pub max_output_tokens: Option<u64>,
 }
 
 impl Prompt {
@@ -278,6 +282,8 @@ pub(crate) struct ResponsesApiRequest<'a> {
     pub(crate) prompt_cache_key: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) text: Option<TextControls>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) max_output_tokens: Option<u64>,
 }
 
 pub(crate) mod tools {
@@ -468,6 +474,7 @@ mod tests {
                 verbosity: Some(OpenAiVerbosity::Low),
                 format: None,
             }),
+            max_output_tokens: None,
         };
 
         let v = serde_json::to_value(&req).expect("json");
@@ -506,6 +513,7 @@ mod tests {
             include: vec![],
             prompt_cache_key: None,
             text: Some(text_controls),
+            max_output_tokens: None,
         };
 
         let v = serde_json::to_value(&req).expect("json");
@@ -542,6 +550,7 @@ mod tests {
             include: vec![],
             prompt_cache_key: None,
             text: None,
+            max_output_tokens: None,
         };
 
         let v = serde_json::to_value(&req).expect("json");
@@ -30,15 +30,20 @@ use mcp_types::CallToolResult;
 use serde_json;
 use serde_json::Value;
 use tokio::sync::Mutex;
+use tokio::sync::mpsc;
 use tokio::sync::oneshot;
 use tracing::debug;
 use tracing::error;
 use tracing::info;
 use tracing::trace;
 use tracing::warn;
+use uuid::Uuid;
 
 use crate::ModelProviderInfo;
 use crate::apply_patch::convert_apply_patch_to_protocol;
+use crate::approval_rules::project_for_cwd;
+use crate::budget::BudgetExceeded;
+use crate::budget::BudgetTracker;
 use crate::client::ModelClient;
 use crate::client_common::Prompt;
 use crate::client_common::ResponseEvent;
@@ -54,11 +59,16 @@ use crate::exec::StreamOutput;
 use crate::exec_command::ExecCommandParams;
 use crate::exec_command::ExecSessionManager;
 use crate::exec_command::WriteStdinParams;
+use crate::exec_policy::ExecPolicy;
 use crate::executor::Executor;
 use crate::executor::ExecutorConfig;
 use crate::executor::normalize_exec_result;
 use crate::mcp_connection_manager::McpConnectionManager;
+use crate::mcp_server_requests::McpServerRequestReceiver;
+use crate::mcp_server_requests::McpServerRequestSender;
+use crate::mcp_server_requests::handle_mcp_server_request;
 use crate::model_family::find_family_for_model;
+use crate::network_proxy::NetworkProxy;
 use crate::openai_model_info::get_model_info;
 use crate::openai_tools::ToolsConfig;
 use crate::openai_tools::ToolsConfigParams;
@@ -71,6 +81,8 @@ use crate::protocol::AgentReasoningSectionBreakEvent;
 use crate::protocol::ApplyPatchApprovalRequestEvent;
 use crate::protocol::AskForApproval;
 use crate::protocol::BackgroundEventEvent;
+use crate::protocol::ElicitationAction;
+use crate::protocol::ElicitationRequestEvent;
 use crate::protocol::ErrorEvent;
 use crate::protocol::Event;
 use crate::protocol::EventMsg;
@@ -79,6 +91,8 @@ use crate::protocol::ExecCommandBeginEvent;
 use crate::protocol::ExecCommandEndEvent;
 use crate::protocol::InputItem;
 use crate::protocol::ListCustomPromptsResponseEvent;
+use crate::protocol::McpSamplingRequestEvent;
+use crate::protocol::NetworkAccessBlockedEvent;
 use crate::protocol::Op;
 use crate::protocol::PatchApplyBeginEvent;
 use crate::protocol::PatchApplyEndEvent;
@@ -142,6 +156,8 @@ pub struct CodexSpawnOk {
 
 pub(crate) const INITIAL_SUBMIT_ID: &str = "";
 pub(crate) const SUBMISSION_CHANNEL_CAPACITY: usize = 64;
+/// Identity of the toolkit that agent-backed slash commands start from.
+const SESSION_TOOLKIT_ID: &str = "session-command";
 
 impl Codex {
     /// Spawn a new [`Codex`] and initialize the session.
@@ -153,6 +169,7 @@ impl Codex {
     ) -> CodexResult<CodexSpawnOk> {
         let (tx_sub, rx_sub) = async_channel::bounded(SUBMISSION_CHANNEL_CAPACITY);
         let (tx_event, rx_event) = async_channel::unbounded();
+        let (tx_mcp_requests, rx_mcp_requests) = mpsc::unbounded_channel();
 
         let user_instructions = get_user_instructions(&config).await;
 
@@ -177,6 +194,7 @@ impl Codex {
             config.clone(),
             auth_manager.clone(),
             tx_event.clone(),
+            tx_mcp_requests,
             conversation_history,
             session_source,
         )
@@ -188,7 +206,13 @@ impl Codex {
         let conversation_id = session.conversation_id;
 
         // This task will run until Op::Shutdown is received.
-        tokio::spawn(submission_loop(session, turn_context, config, rx_sub));
+        tokio::spawn(submission_loop(
+            session,
+            turn_context,
+            config,
+            rx_sub,
+            rx_mcp_requests,
+        ));
         let codex = Codex {
             next_id: AtomicU64::new(0),
             tx_sub,
@@ -312,6 +336,7 @@ impl Session {
         config: Arc<Config>,
         auth_manager: Arc<AuthManager>,
         tx_event: Sender<Event>,
+        mcp_server_requests: McpServerRequestSender,
         initial_history: InitialHistory,
         session_source: SessionSource,
     ) -> anyhow::Result<(Arc<Self>, TurnContext)> {
@@ -366,6 +391,7 @@ impl Session {
             config.mcp_servers.clone(),
             config.use_experimental_use_rmcp_client,
             config.mcp_oauth_credentials_store_mode,
+            mcp_server_requests,
         );
         let default_shell_fut = shell::default_user_shell();
         let history_meta_fut = crate::message_history::history_metadata(&config);
@@ -408,7 +434,8 @@ impl Session {
         })?;
         let rollout_path = rollout_recorder.rollout_path.clone();
         // Create the mutable state for the Session.
-        let state = SessionState::new();
+        let mut state = SessionState::new();
+        state.budget = BudgetTracker::new(config.budget, config.model_prices.clone());
 
         // Handle MCP manager result and record any startup failures.
         let (mcp_connection_manager, failed_clients) = match mcp_res {
@@ -436,6 +463,45 @@ impl Session {
             }
         }
 
+        // Commands limited to a network allowlist reach it through a proxy
+        // that lives as long as the session. A policy that only gains an
+        // allowlist later starts it on first use instead.
+        let network_proxy = if sandbox_policy.network_allowlist().is_empty() {
+            None
+        } else {
+            match NetworkProxy::start(sandbox_policy.network_allowlist()).await {
+                Ok(proxy) => Some(Arc::new(proxy)),
+                Err(e) => {
+                    let message = format!(
+                        "Failed to start the sandbox network proxy; sandboxed commands will have no network access: {e:#}"
+                    );
+                    error!("{message}");
+                    post_session_configured_error_events.push(Event {
+                        id: INITIAL_SUBMIT_ID.to_owned(),
+                        msg: EventMsg::Error(ErrorEvent { message }),
+                    });
+                    None
+                }
+            }
+        };
+
+        // Personal policies always apply; a project's own policies only once
+        // the user trusts the project, since they can allow commands to run
+        // unsandboxed.
+        let project_root = config.cwd_trusted.then(|| project_for_cwd(&config.cwd));
+        let (exec_policy, policy_errors) = ExecPolicy::load(&ExecPolicy::policy_dirs(
+            &config.codex_home,
+            project_root.as_deref(),
+        ));
+        for err in policy_errors {
+            let message = format!("{err}; its rules are ignored");
+            error!("{message}");
+            post_session_configured_error_events.push(Event {
+                id: INITIAL_SUBMIT_ID.to_owned(),
+                msg: EventMsg::Error(ErrorEvent { message }),
+            });
+        }
+
         let otel_event_manager = OtelEventManager::new(
             conversation_id,
             config.model.as_str(),
@@ -505,6 +571,40 @@ impl Session {
             None
         };
 
+        // Load built-in and user-defined agents alongside the command system
+        let (agent_router, agent_watcher) = if config.experimental_command_system_enabled {
+            let mut router = crate::agents::AgentRouter::with_builtin_agents();
+            router.set_routing_mode(config.agents.routing);
+            if config.agents.classify_intent {
+                router.set_intent_classifier(Arc::new(
+                    crate::agents::routing::ModelIntentClassifier::new(turn_context.client.clone()),
+                ));
+            }
+            let router = Arc::new(router);
+            // Like project exec policies, a workspace's own agents only load
+            // once the user trusts it, since they can run commands.
+            let loader = crate::agents::user::UserAgentLoader::new(
+                crate::agents::user::UserAgentLoader::default_dirs(
+                    &config.codex_home,
+                    config.cwd_trusted.then_some(turn_context.cwd.as_path()),
+                ),
+            );
+            match loader.load_all().await {
+                Ok(agents) => router.set_user_agents(agents).await,
+                Err(e) => warn!("Failed to load user-defined agents: {e:#}"),
+            }
+            let watcher = match crate::agents::watcher::AgentWatcher::new(loader, router.clone()) {
+                Ok(watcher) => Some(watcher),
+                Err(e) => {
+                    warn!("Failed to initialize agent watcher: {e:#}");
+                    None
+                }
+            };
+            (Some(router), watcher)
+        } else {
+            (None, None)
+        };
+
         let services = SessionServices {
             mcp_connection_manager,
             session_manager: ExecSessionManager::default(),
@@ -517,9 +617,15 @@ impl Session {
                 turn_context.sandbox_policy.clone(),
                 turn_context.cwd.clone(),
                 config.codex_linux_sandbox_exe.clone(),
-            )),
+            ))
+            .with_approval_rules(config.codex_home.clone())
+            .with_sandbox_limits(config.sandbox_limits)
+            .with_network_proxy(network_proxy)
+            .with_exec_policy(Arc::new(exec_policy)),
             command_registry,
             command_watcher,
+            agent_router,
+            agent_watcher,
         };
 
         let sess = Arc::new(Session {
@@ -710,9 +816,90 @@ impl Session {
         }
     }
 
+    /// Ask the user whether an MCP server may sample the model and await the
+    /// answer. Returns `Denied` if the request is dropped.
+    pub(crate) async fn request_mcp_sampling_approval(
+        &self,
+        request: McpSamplingRequestEvent,
+    ) -> ReviewDecision {
+        let id = format!("mcp-sampling-{}", Uuid::new_v4());
+        let (tx, rx) = oneshot::channel();
+        self.state
+            .lock()
+            .await
+            .insert_pending_mcp_sampling(id.clone(), tx);
+        self.send_event(Event {
+            id,
+            msg: EventMsg::McpSamplingRequest(request),
+        })
+        .await;
+        rx.await.unwrap_or_default()
+    }
+
+    pub async fn notify_mcp_sampling_approval(&self, id: &str, decision: ReviewDecision) {
+        let entry = self.state.lock().await.remove_pending_mcp_sampling(id);
+        match entry {
+            Some(tx) => {
+                tx.send(decision).ok();
+            }
+            None => warn!("No pending MCP sampling request found for id: {id}"),
+        }
+    }
+
+    pub(crate) async fn is_mcp_sampling_approved_for_session(&self, server: &str) -> bool {
+        self.state
+            .lock()
+            .await
+            .approved_sampling_servers
+            .contains(server)
+    }
+
+    pub(crate) async fn approve_mcp_sampling_for_session(&self, server: String) {
+        self.state
+            .lock()
+            .await
+            .approved_sampling_servers
+            .insert(server);
+    }
+
+    /// Show an MCP server's form to the user and await what they submit.
+    /// Returns `Cancel` if the request is dropped.
+    pub(crate) async fn request_elicitation(
+        &self,
+        request: ElicitationRequestEvent,
+    ) -> (ElicitationAction, Option<Value>) {
+        let id = format!("mcp-elicitation-{}", Uuid::new_v4());
+        let (tx, rx) = oneshot::channel();
+        self.state
+            .lock()
+            .await
+            .insert_pending_elicitation(id.clone(), tx);
+        self.send_event(Event {
+            id,
+            msg: EventMsg::ElicitationRequest(request),
+        })
+        .await;
+        rx.await.unwrap_or((ElicitationAction::Cancel, None))
+    }
+
+    pub async fn resolve_elicitation(
+        &self,
+        id: &str,
+        action: ElicitationAction,
+        content: Option<Value>,
+    ) {
+        let entry = self.state.lock().await.remove_pending_elicitation(id);
+        match entry {
+            Some(tx) => {
+                tx.send((action, content)).ok();
+            }
+            None => warn!("No pending elicitation found for id: {id}"),
+        }
+    }
+
     /// Records input items: always append to conversation history and
     /// persist these response items to rollout.
-    async fn record_conversation_items(&self, items: &[ResponseItem]) {
+    pub(crate) async fn record_conversation_items(&self, items: &[ResponseItem]) {
         self.record_into_history(items).await;
         self.persist_rollout_response_items(items).await;
     }
@@ -795,7 +982,7 @@ impl Session {
         state.history_snapshot()
     }
 
-    async fn update_token_usage_info(
+    pub(crate) async fn update_token_usage_info(
         &self,
         sub_id: &str,
         turn_context: &TurnContext,
@@ -808,11 +995,52 @@ impl Session {
                     token_usage,
                     turn_context.client.get_model_context_window(),
                 );
+                state
+                    .budget
+                    .record_token_usage(&turn_context.client.get_model(), token_usage);
             }
         }
         self.send_token_count_event(sub_id).await;
     }
 
+    /// Starts counting a turn against the budget and returns when it runs out
+    /// of wall-clock time, if it is limited.
+    pub(crate) async fn start_budget_turn(
+        &self,
+        counts_as_turn: bool,
+    ) -> Result<Option<tokio::time::Instant>, BudgetExceeded> {
+        let mut state = self.state.lock().await;
+        state.budget.start_turn(counts_as_turn)?;
+        Ok(state.budget.deadline())
+    }
+
+    /// Fails once the budget leaves nothing for another model request.
+    pub(crate) async fn check_model_request_budget(&self) -> Result<(), BudgetExceeded> {
+        let state = self.state.lock().await;
+        state.budget.check_model_request()
+    }
+
+    /// Counts a tool call, refusing it once a tool call budget is spent.
+    pub(crate) async fn record_tool_call(&self) -> Result<(), BudgetExceeded> {
+        let mut state = self.state.lock().await;
+        state.budget.record_tool_call()
+    }
+
+    pub(crate) async fn record_budget_deadline_reached(&self) -> BudgetExceeded {
+        let mut state = self.state.lock().await;
+        state.budget.record_deadline_reached()
+    }
+
+    pub(crate) async fn budget_exceeded(&self) -> bool {
+        let state = self.state.lock().await;
+        state.budget.is_exceeded()
+    }
+
+    pub(crate) async fn take_budget_exceeded(&self) -> Option<BudgetExceeded> {
+        let mut state = self.state.lock().await;
+        state.budget.take_exceeded()
+    }
+
     async fn update_rate_limits(&self, sub_id: &str, new_rate_limits: RateLimitSnapshot) {
         {
             let mut state = self.state.lock().await;
@@ -822,13 +1050,18 @@ impl Session {
     }
 
     async fn send_token_count_event(&self, sub_id: &str) {
-        let (info, rate_limits) = {
+        let (info, rate_limits, budget) = {
             let state = self.state.lock().await;
-            state.token_info_and_rate_limits()
+            let (info, rate_limits) = state.token_info_and_rate_limits();
+            (info, rate_limits, state.budget.remaining())
         };
         let event = Event {
             id: sub_id.to_string(),
-            msg: EventMsg::TokenCount(TokenCountEvent { info, rate_limits }),
+            msg: EventMsg::TokenCount(TokenCountEvent {
+                info,
+                rate_limits,
+                budget,
+            }),
         };
         self.send_event(event).await;
     }
@@ -846,7 +1079,10 @@ impl Session {
 
     /// Record a user input item to conversation history and also persist a
     /// corresponding UserMessage EventMsg to rollout.
-    async fn record_input_and_rollout_usermsg(&self, response_input: &ResponseInputItem) {
+    pub(crate) async fn record_input_and_rollout_usermsg(
+        &self,
+        response_input: &ResponseInputItem,
+    ) {
         let response_item: ResponseItem = response_input.clone().into();
         // Add to conversation history and persist response item to rollout
         self.record_conversation_items(std::slice::from_ref(&response_item))
@@ -928,6 +1164,7 @@ impl Session {
             duration,
             exit_code,
             timed_out: _,
+            limit_exceeded,
         } = output;
         // Send full stdout/stderr to clients; do not truncate.
         let stdout = stdout.text.clone();
@@ -951,6 +1188,7 @@ impl Session {
                 exit_code: *exit_code,
                 duration: *duration,
                 formatted_output,
+                limit_exceeded: *limit_exceeded,
             })
         };
 
@@ -995,11 +1233,17 @@ impl Session {
         self.on_exec_command_begin(turn_diff_tracker.clone(), context.clone())
             .await;
 
-        let result = self
+        let run = self
             .services
             .executor
-            .run(request, self, approval_policy, &context)
-            .await;
+            .run(request, self, approval_policy, &context);
+        let result = match self.services.executor.network_proxy() {
+            Some(proxy) => {
+                self.report_blocked_connections(&proxy, run, &sub_id, &call_id)
+                    .await
+            }
+            None => run.await,
+        };
 
         let normalized = normalize_exec_result(&result);
         let borrowed = normalized.event_output();
@@ -1018,6 +1262,35 @@ impl Session {
         result
     }
 
+    /// Drives `run` to completion, emitting a [`NetworkAccessBlockedEvent`]
+    /// for every connection the egress proxy refuses in the meantime.
+    async fn report_blocked_connections<T>(
+        &self,
+        proxy: &NetworkProxy,
+        run: impl Future<Output = T>,
+        sub_id: &str,
+        call_id: &str,
+    ) -> T {
+        let mut blocked = proxy.subscribe();
+        tokio::pin!(run);
+        loop {
+            tokio::select! {
+                output = &mut run => return output,
+                Ok(connection) = blocked.recv() => {
+                    self.send_event(Event {
+                        id: sub_id.to_string(),
+                        msg: EventMsg::NetworkAccessBlocked(NetworkAccessBlockedEvent {
+                            call_id: call_id.to_string(),
+                            host: connection.host,
+                            port: connection.port,
+                        }),
+                    })
+                    .await;
+                }
+            }
+        }
+    }
+
     /// Helper that emits a BackgroundEvent with the given message. This keeps
     /// the call‑sites terse so adding more diagnostics does not clutter the
     /// core agent logic.
@@ -1087,6 +1360,17 @@ impl Session {
             .await
     }
 
+    pub(crate) async fn read_mcp_resource(
+        &self,
+        server: &str,
+        uri: &str,
+    ) -> anyhow::Result<mcp_types::ReadResourceResult> {
+        self.services
+            .mcp_connection_manager
+            .read_resource(server, uri)
+            .await
+    }
+
     pub(crate) fn parse_mcp_tool_name(&self, tool_name: &str) -> Option<(String, String)> {
         self.services
             .mcp_connection_manager
@@ -1214,6 +1498,69 @@ async fn extract_conversation_context(
     None
 }
 
+/// Builds the executor for agent-backed slash commands, running the agents'
+/// shell commands through this turn's exec pipeline.
+fn session_agent_executor(
+    sess: &Arc<Session>,
+    turn_context: &Arc<TurnContext>,
+    sub_id: &str,
+    router: Arc<crate::agents::AgentRouter>,
+    workspace_root: PathBuf,
+) -> crate::commands::agents::AgentCommandExecutor {
+    let toolkit = crate::agents::AgentToolkit::new(
+        crate::agents::AgentId::from(SESSION_TOOLKIT_ID),
+        crate::agents::AgentPermissions::default(),
+        workspace_root,
+    )
+    .with_exec_context(crate::agents::toolkit::AgentExecContext {
+        session: Arc::clone(sess),
+        turn_context: Arc::clone(turn_context),
+        turn_diff_tracker: Arc::new(tokio::sync::Mutex::new(TurnDiffTracker::new())),
+        sub_id: sub_id.to_string(),
+    });
+    crate::commands::agents::AgentCommandExecutor::new(router, Arc::new(toolkit)).with_runtime(
+        Arc::new(crate::agents::AgentRuntime::new(
+            turn_context.client.clone(),
+        )),
+    )
+}
+
+/// Builds a task for `command_text` if it names an agent-backed command.
+///
+/// Returns `None` when agents are unavailable, the command cannot be parsed,
+/// or it is a template command, so the caller falls back to prompt expansion.
+async fn agent_command_task(
+    sess: &Arc<Session>,
+    turn_context: &Arc<TurnContext>,
+    sub_id: &str,
+    command_text: &str,
+    registry: Arc<crate::commands::CommandRegistry>,
+    exec_context: crate::commands::ExecutionContext,
+) -> Option<crate::tasks::AgentCommandTask> {
+    let router = sess.services.agent_router.as_ref()?;
+    let invocation = crate::commands::invocation::InvocationParser::parse(command_text).ok()?;
+
+    let agent_executor = session_agent_executor(
+        sess,
+        turn_context,
+        sub_id,
+        Arc::clone(router),
+        exec_context.workspace_root.clone(),
+    );
+    let executor = crate::commands::CommandExecutor::new(registry)
+        .with_agent_executor(Arc::new(agent_executor));
+    if !executor.is_agent_command(&invocation.command_name).await {
+        return None;
+    }
+
+    Some(crate::tasks::AgentCommandTask::new(
+        executor,
+        command_text.to_string(),
+        invocation,
+        exec_context,
+    ))
+}
+
 /// Collect safe environment variables for command context.
 ///
 /// Returns a HashMap of whitelisted environment variables that are safe
@@ -1251,16 +1598,41 @@ fn collect_safe_env_vars() -> std::collections::HashMap<String, String> {
     vars
 }
 
+fn mcp_list_tools_response(sess: &Session) -> crate::protocol::McpListToolsResponseEvent {
+    let manager = &sess.services.mcp_connection_manager;
+    crate::protocol::McpListToolsResponseEvent {
+        tools: manager.list_all_tools(),
+        server_statuses: manager.server_statuses(),
+    }
+}
+
 async fn submission_loop(
     sess: Arc<Session>,
     turn_context: TurnContext,
     config: Arc<Config>,
     rx_sub: Receiver<Submission>,
+    mut mcp_server_requests: McpServerRequestReceiver,
 ) {
     // Wrap once to avoid cloning TurnContext for each task.
     let mut turn_context = Arc::new(turn_context);
     // To break out of this loop, send Op::Shutdown.
-    while let Ok(sub) = rx_sub.recv().await {
+    loop {
+        let sub = tokio::select! {
+            sub = rx_sub.recv() => match sub {
+                Ok(sub) => sub,
+                Err(_) => break,
+            },
+            Some(request) = mcp_server_requests.recv() => {
+                // Answered in the background: it may wait on the user and the
+                // model while submissions keep flowing.
+                tokio::spawn(handle_mcp_server_request(
+                    Arc::clone(&sess),
+                    Arc::clone(&turn_context),
+                    request,
+                ));
+                continue;
+            }
+        };
         debug!(?sub, "Submission");
         match sub.op {
             Op::Interrupt => {
@@ -1407,6 +1779,27 @@ async fn submission_loop(
                     // Collect safe environment variables for command context
                     let env_vars = collect_safe_env_vars();
 
+                    // Agent-backed commands run as their own task and report a
+                    // structured result instead of expanding into a prompt.
+                    if let Some(task) = agent_command_task(
+                        &sess,
+                        &turn_context,
+                        &sub.id,
+                        &command_text,
+                        Arc::clone(registry),
+                        crate::commands::ExecutionContext::new(cwd.clone())
+                            .with_git_diff(git_diff.clone())
+                            .with_files(current_files.clone())
+                            .with_conversation_context(conversation_context.clone())
+                            .with_env_vars(env_vars.clone()),
+                    )
+                    .await
+                    {
+                        sess.spawn_task(Arc::clone(&turn_context), sub.id, items, task)
+                            .await;
+                        continue;
+                    }
+
                     // Execute slash command and replace with expanded prompt
                     match crate::commands::execute_slash_command(
                         &command_text,
@@ -1426,7 +1819,7 @@ async fn submission_loop(
                             );
                         }
                         Err(e) => {
-                            // Send error event and return
+                            // Send error event and wait for the next submission
                             let error_msg = format!("Slash command error: {e:#}");
                             error!("{error_msg}");
                             let _ = sess
@@ -1436,7 +1829,7 @@ async fn submission_loop(
                                     msg: EventMsg::Error(ErrorEvent { message: error_msg }),
                                 })
                                 .await;
-                            return;
+                            continue;
                         }
                     }
                 }
@@ -1539,6 +1932,19 @@ async fn submission_loop(
                 }
                 other => sess.notify_approval(&id, other).await,
             },
+            Op::ReloadApprovalRules => {
+                sess.services.executor.reload_approval_rules();
+            }
+            Op::McpSamplingApproval { id, decision } => {
+                sess.notify_mcp_sampling_approval(&id, decision).await;
+            }
+            Op::ResolveElicitation {
+                id,
+                action,
+                content,
+            } => {
+                sess.resolve_elicitation(&id, action, content).await;
+            }
             Op::AddToHistory { text } => {
                 let id = sess.conversation_id;
                 let config = config.clone();
@@ -1587,11 +1993,74 @@ async fn submission_loop(
                 let sub_id = sub.id.clone();
 
                 // This is a cheap lookup from the connection manager's cache.
-                let tools = sess.services.mcp_connection_manager.list_all_tools();
                 let event = Event {
                     id: sub_id,
-                    msg: EventMsg::McpListToolsResponse(
-                        crate::protocol::McpListToolsResponseEvent { tools },
+                    msg: EventMsg::McpListToolsResponse(mcp_list_tools_response(&sess)),
+                };
+                sess.send_event(event).await;
+            }
+            Op::ReloadMcpServers => {
+                let sess_clone = sess.clone();
+                let sub_id = sub.id.clone();
+                let codex_home = config.codex_home.clone();
+                let cli_overrides = config.cli_overrides.clone();
+
+                tokio::spawn(async move {
+                    // Re-read the servers the way the session's config was
+                    // loaded, so `-c mcp_servers.*` overrides still apply.
+                    match crate::config::load_mcp_servers_with_cli_overrides(
+                        &codex_home,
+                        cli_overrides,
+                    )
+                    .await
+                    {
+                        Ok(mcp_servers) => {
+                            let failed_clients = sess_clone
+                                .services
+                                .mcp_connection_manager
+                                .reload(mcp_servers.into_iter().collect())
+                                .await;
+                            for (server_name, err) in failed_clients {
+                                let message = format!(
+                                    "MCP client for `{server_name}` failed to start: {err:#}"
+                                );
+                                error!("{message}");
+                                sess_clone
+                                    .send_event(Event {
+                                        id: sub_id.clone(),
+                                        msg: EventMsg::Error(ErrorEvent { message }),
+                                    })
+                                    .await;
+                            }
+                        }
+                        Err(e) => {
+                            let message = format!("Failed to reload MCP servers: {e:#}");
+                            error!("{message}");
+                            sess_clone
+                                .send_event(Event {
+                                    id: sub_id.clone(),
+                                    msg: EventMsg::Error(ErrorEvent { message }),
+                                })
+                                .await;
+                        }
+                    }
+
+                    let event = Event {
+                        id: sub_id,
+                        msg: EventMsg::McpListToolsResponse(mcp_list_tools_response(&sess_clone)),
+                    };
+                    sess_clone.send_event(event).await;
+                });
+            }
+            Op::ListMcpResources => {
+                let sub_id = sub.id.clone();
+
+                // Resources are cached and refreshed when a server reports changes.
+                let resources = sess.services.mcp_connection_manager.list_all_resources();
+                let event = Event {
+                    id: sub_id,
+                    msg: EventMsg::McpListResourcesResponse(
+                        crate::protocol::McpListResourcesResponseEvent { resources },
                     ),
                 };
                 sess.send_event(event).await;
@@ -1606,14 +2075,47 @@ async fn submission_loop(
                         Vec::new()
                     };
 
+                let mcp_prompts = sess.services.mcp_connection_manager.list_all_prompts();
+
                 let event = Event {
                     id: sub_id,
                     msg: EventMsg::ListCustomPromptsResponse(ListCustomPromptsResponseEvent {
                         custom_prompts,
+                        mcp_prompts,
                     }),
                 };
                 sess.send_event(event).await;
             }
+            Op::GetMcpPrompt {
+                server,
+                name,
+                arguments,
+            } => {
+                let sess_clone = sess.clone();
+                let sub_id = sub.id.clone();
+
+                tokio::spawn(async move {
+                    let arguments = (!arguments.is_empty())
+                        .then(|| serde_json::to_value(arguments).unwrap_or_default());
+                    let result = sess_clone
+                        .services
+                        .mcp_connection_manager
+                        .get_prompt(&server, &name, arguments)
+                        .await
+                        .map_err(|e| format!("{e:#}"));
+                    let event = Event {
+                        id: sub_id,
+                        msg: EventMsg::GetMcpPromptResponse(
+                            crate::protocol::GetMcpPromptResponseEvent {
+                                server,
+                                name,
+                                result,
+                            },
+                        ),
+                    };
+                    sess_clone.send_event(event).await;
+                });
+            }
             Op::Compact => {
                 // Attempt to inject input into current task
                 if let Err(items) = sess
@@ -2015,6 +2517,13 @@ pub(crate) async fn run_task(
                     }
                 }
 
+                // Stop before handing tool output back to the model once a
+                // budget runs out; the task runner reports the abort.
+                let turn_finished = responses.is_empty() && !token_limit_reached;
+                if !turn_finished && sess.budget_exceeded().await {
+                    break;
+                }
+
                 if token_limit_reached {
                     if auto_compact_recently_attempted {
                         let limit_str = limit.to_string();
@@ -2040,6 +2549,9 @@ pub(crate) async fn run_task(
                 auto_compact_recently_attempted = false;
 
                 if responses.is_empty() {
+                    // The turn produced its answer; a session budget spent by
+                    // this last response stops the next turn instead.
+                    sess.take_budget_exceeded().await;
                     last_agent_message = get_last_assistant_message_from_turn(
                         &items_to_record_in_conversation_history,
                     );
@@ -2123,6 +2635,7 @@ async fn run_turn(
     let router = Arc::new(ToolRouter::from_config(
         &turn_context.tools_config,
         Some(mcp_tools),
+        sess.services.mcp_connection_manager.has_resources(),
     ));
 
     let model_supports_parallel = turn_context
@@ -2136,6 +2649,7 @@ async fn run_turn(
         parallel_tool_calls,
         base_instructions_override: turn_context.base_instructions.clone(),
         output_schema: turn_context.final_output_json_schema.clone(),
+        max_output_tokens: None,
     };
 
     let mut retries = 0;
@@ -2622,6 +3136,8 @@ use crate::tools::context::ApplyPatchCommandContext;
 use crate::tools::context::ExecCommandContext;
 #[cfg(test)]
 pub(crate) use tests::make_session_and_context;
+#[cfg(test)]
+pub(crate) use tests::make_session_and_context_with_rx;
 
 #[cfg(test)]
 mod tests {
@@ -2630,6 +3146,7 @@ mod tests {
     use crate::config::ConfigToml;
 
     use crate::protocol::CompactedItem;
+    use crate::protocol::ExecResourceLimit;
     use crate::protocol::InitialHistory;
     use crate::protocol::ResumedHistory;
     use crate::state::TaskKind;
@@ -2736,6 +3253,7 @@ mod tests {
             aggregated_output: StreamOutput::new(full),
             duration: StdDuration::from_secs(1),
             timed_out: false,
+            limit_exceeded: None,
         };
 
         let out = format_exec_output_str(&exec);
@@ -2785,6 +3303,7 @@ mod tests {
             aggregated_output: StreamOutput::new(full.clone()),
             duration: StdDuration::from_secs(1),
             timed_out: false,
+            limit_exceeded: None,
         };
 
         let out = format_exec_output_str(&exec);
@@ -2821,6 +3340,7 @@ mod tests {
             aggregated_output: StreamOutput::new("Command output".to_string()),
             duration: StdDuration::from_secs(1),
             timed_out: true,
+            limit_exceeded: None,
         };
 
         let out = format_exec_output_str(&exec);
@@ -2831,6 +3351,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn includes_limit_exceeded_message() {
+        let exec = ExecToolCallOutput {
+            exit_code: 137,
+            stdout: StreamOutput::new(String::new()),
+            stderr: StreamOutput::new(String::new()),
+            aggregated_output: StreamOutput::new("Command output".to_string()),
+            duration: StdDuration::from_secs(1),
+            timed_out: false,
+            limit_exceeded: Some(ExecResourceLimit::Memory),
+        };
+
+        let out = format_exec_output_str(&exec);
+
+        assert_eq!(
+            out,
+            "command was killed after exceeding its sandbox memory limit\nCommand output"
+        );
+    }
+
     #[test]
     fn falls_back_to_content_when_structured_is_null() {
         let ctr = CallToolResult {
@@ -2960,6 +3500,8 @@ mod tests {
             )),
             command_registry: None,
             command_watcher: None,
+            agent_router: None,
+            agent_watcher: None,
         };
         let session = Session {
             conversation_id,
@@ -2974,7 +3516,7 @@ mod tests {
 
     // Like make_session_and_context, but returns Arc<Session> and the event receiver
     // so tests can assert on emitted events.
-    fn make_session_and_context_with_rx() -> (
+    pub(crate) fn make_session_and_context_with_rx() -> (
         Arc<Session>,
         Arc<TurnContext>,
         async_channel::Receiver<Event>,
@@ -3035,6 +3577,8 @@ mod tests {
             )),
             command_registry: None,
             command_watcher: None,
+            agent_router: None,
+            agent_watcher: None,
         };
         let session = Arc::new(Session {
             conversation_id,
@@ -3148,12 +3692,154 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn agent_command_task_reports_structured_result() {
+        use crate::agents::ActivationScore;
+        use crate::agents::Agent;
+        use crate::agents::AgentId;
+        use crate::agents::AgentPermissions;
+        use crate::agents::AgentResult;
+        use crate::agents::AgentRouter;
+        use crate::agents::AgentToolkit;
+        use crate::agents::CodeReviewFinding;
+        use crate::agents::Severity;
+        use crate::agents::Task;
+        use crate::agents::TaskContext;
+        use crate::commands::CommandExecutor;
+        use crate::commands::CommandRegistry;
+        use crate::commands::ExecutionContext;
+        use crate::commands::agents::AgentCommandExecutor;
+        use crate::commands::invocation::InvocationParser;
+        use crate::tasks::AgentCommandTask;
+        use codex_protocol::agents::AgentFindingSeverity;
+        use codex_protocol::agents::AgentRunResult;
+
+        struct ReviewAgent(AgentPermissions);
+
+        #[async_trait::async_trait]
+        impl Agent for ReviewAgent {
+            fn id(&self) -> AgentId {
+                AgentId::from("review-agent")
+            }
+
+            fn name(&self) -> &str {
+                "Review Agent"
+            }
+
+            fn description(&self) -> &str {
+                "Reports a single finding"
+            }
+
+            fn can_handle(&self, _context: &TaskContext) -> ActivationScore {
+                ActivationScore(0.9)
+            }
+
+            async fn execute(
+                &self,
+                _task: Task,
+                _toolkit: &AgentToolkit,
+            ) -> anyhow::Result<AgentResult> {
+                Ok(AgentResult::CodeReview {
+                    findings: vec![CodeReviewFinding {
+                        severity: Severity::Error,
+                        category: "Security".to_string(),
+                        message: "SQL injection".to_string(),
+                        location: Some(PathBuf::from("src/db.rs")),
+                        line_number: Some(12),
+                    }],
+                })
+            }
+
+            fn permissions(&self) -> &AgentPermissions {
+                &self.0
+            }
+
+            fn system_prompt(&self) -> &str {
+                "Review code"
+            }
+        }
+
+        let temp_dir = tempfile::TempDir::new().expect("tempdir");
+        std::fs::write(
+            temp_dir.path().join("check.md"),
+            "---\nname: check\ndescription: Review code\ncategory: agents\nagent: true\nagent_id: review-agent\n---\nReview the code\n",
+        )
+        .expect("write command");
+        let registry = Arc::new(
+            CommandRegistry::new(temp_dir.path().to_path_buf())
+                .await
+                .expect("registry"),
+        );
+        let mut router = AgentRouter::new();
+        router.register_agent(Arc::new(ReviewAgent(AgentPermissions::default())));
+        let agent_executor = AgentCommandExecutor::new(
+            Arc::new(router),
+            Arc::new(AgentToolkit::new(
+                AgentId::from("test-toolkit"),
+                AgentPermissions::default(),
+                PathBuf::from("/workspace"),
+            )),
+        );
+        let executor = CommandExecutor::new(registry).with_agent_executor(Arc::new(agent_executor));
+        let task = AgentCommandTask::new(
+            executor,
+            "/check".to_string(),
+            InvocationParser::parse("/check").expect("parse"),
+            ExecutionContext::new(PathBuf::from("/workspace")),
+        );
+
+        let (sess, tc, rx) = make_session_and_context_with_rx();
+        let input = vec![InputItem::Text {
+            text: "/check".to_string(),
+        }];
+        sess.spawn_task(Arc::clone(&tc), "sub-agent".to_string(), input, task)
+            .await;
+
+        let mut end = None;
+        let mut last_agent_message = None;
+        while let Ok(event) = rx.recv().await {
+            match event.msg {
+                EventMsg::AgentRunBegin(ev) => {
+                    assert_eq!(ev.command, "check");
+                    assert_eq!(ev.invocation, "/check");
+                }
+                EventMsg::AgentRunEnd(ev) => end = Some(ev),
+                EventMsg::TaskComplete(ev) => {
+                    last_agent_message = ev.last_agent_message;
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        let end = end.expect("agent run end event");
+        assert_eq!(end.command, "check");
+        let result = end.result.expect("agent result");
+        assert!(matches!(result, AgentRunResult::CodeReview { .. }));
+        let findings = result.findings();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, AgentFindingSeverity::Error);
+        assert_eq!(findings[0].line, Some(12));
+        assert!(
+            last_agent_message
+                .as_deref()
+                .is_some_and(|message| message.contains("SQL injection"))
+        );
+
+        let history = sess.history_snapshot().await;
+        assert!(history.iter().any(|item| matches!(
+            item,
+            ResponseItem::Message { role, .. } if role == "assistant"
+        )));
+    }
+
     #[tokio::test]
     async fn fatal_tool_error_stops_turn_and_reports_error() {
         let (session, turn_context, _rx) = make_session_and_context_with_rx();
         let router = ToolRouter::from_config(
             &turn_context.tools_config,
             Some(session.services.mcp_connection_manager.list_all_tools()),
+            false,
         );
         let item = ResponseItem::CustomToolCall {
             id: None,
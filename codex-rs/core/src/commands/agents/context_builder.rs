@@ -45,6 +45,7 @@ impl AgentContextBuilder {
     ///     agent: true,
     ///     agent_id: Some("code-review".to_string()),
     ///     activation_hints: vec!["review".to_string()],
+    ///     pipeline: None,
     /// };
     ///
     /// let task = AgentContextBuilder::build_task(
@@ -80,6 +81,7 @@ impl AgentContextBuilder {
             git_context,
             execution_mode: ExecutionMode::Interactive,
             user_intent,
+            activation_hints: metadata.activation_hints.clone(),
         };
 
         // Step 5: Build Task
@@ -148,6 +150,7 @@ mod tests {
             agent: true,
             agent_id: Some("code-review".to_string()),
             activation_hints: vec!["review".to_string()],
+            pipeline: None,
         }
     }
 
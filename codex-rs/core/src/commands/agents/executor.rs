@@ -1,10 +1,14 @@
 //! Agent command executor for routing commands to AI agents.
 
 use crate::agents::Agent;
+use crate::agents::AgentId;
+use crate::agents::AgentPipeline;
 use crate::agents::AgentResult;
 use crate::agents::AgentRouter;
+use crate::agents::AgentRuntime;
 use crate::agents::AgentToolkit;
 use crate::agents::GitContext;
+use crate::agents::runtime::run_with_fallback;
 use crate::commands::invocation::CommandInvocation;
 use crate::commands::parser::CommandMetadata;
 use anyhow::Context;
@@ -18,12 +22,25 @@ use super::context_builder::AgentContextBuilder;
 pub struct AgentCommandExecutor {
     router: Arc<AgentRouter>,
     toolkit: Arc<AgentToolkit>,
+    runtime: Option<Arc<AgentRuntime>>,
 }
 
 impl AgentCommandExecutor {
     /// Creates a new AgentCommandExecutor with the given router and toolkit.
     pub fn new(router: Arc<AgentRouter>, toolkit: Arc<AgentToolkit>) -> Self {
-        Self { router, toolkit }
+        Self {
+            router,
+            toolkit,
+            runtime: None,
+        }
+    }
+
+    /// Runs agents through the model using `runtime`.
+    ///
+    /// Without a runtime, agents use their offline `execute` implementation.
+    pub fn with_runtime(mut self, runtime: Arc<AgentRuntime>) -> Self {
+        self.runtime = Some(runtime);
+        self
     }
 
     /// Executes an agent-backed command.
@@ -31,8 +48,11 @@ impl AgentCommandExecutor {
     /// # Execution Flow
     ///
     /// 1. Build TaskContext from command invocation
-    /// 2. Select appropriate agent (explicit agent_id or router selection)
-    /// 3. Execute agent with context
+    /// 2. Run the command's `pipeline` if it declares one; otherwise select
+    ///    an agent (explicit agent_id or router selection)
+    /// 3. Execute agent with context (through the model runtime when
+    ///    configured, falling back to the agent's offline analysis). The
+    ///    agent runs with its own permissions and may delegate sub-tasks.
     /// 4. Return agent result
     ///
     /// # Arguments
@@ -71,6 +91,7 @@ impl AgentCommandExecutor {
     ///     agent: true,
     ///     agent_id: Some("code-review".to_string()),
     ///     activation_hints: vec![],
+    ///     pipeline: None,
     /// };
     ///
     /// let result = executor.execute_agent_command(
@@ -101,22 +122,37 @@ impl AgentCommandExecutor {
         )
         .context("Failed to build agent task")?;
 
-        // Step 2: Select agent based on task context
-        let agent: Arc<dyn Agent> =
-            self.router
-                .select_agent(&task.context)
+        // Step 2: Run the declared pipeline, or select a single agent
+        if let Some(pipeline) = &metadata.pipeline {
+            let pipeline = AgentPipeline::parse(pipeline)?;
+            return pipeline
+                .run(&self.router, self.runtime.as_deref(), task, &self.toolkit)
                 .await
-                .with_context(|| {
-                    if let Some(agent_id) = &metadata.agent_id {
-                        format!("Agent '{agent_id}' not found or not available")
-                    } else {
-                        "No suitable agent found for command".to_string()
-                    }
-                })?;
+                .context("Agent pipeline failed");
+        }
+
+        let explicit = match &metadata.agent_id {
+            Some(agent_id) => self.router.get_agent(&AgentId::from(agent_id)).await,
+            None => None,
+        };
+        let selected = match explicit {
+            Some(agent) => Some(agent),
+            None => self.router.select_agent(&task.context).await,
+        };
+        let agent: Arc<dyn Agent> = selected.with_context(|| {
+            if let Some(agent_id) = &metadata.agent_id {
+                format!("Agent '{agent_id}' not found or not available")
+            } else {
+                "No suitable agent found for command".to_string()
+            }
+        })?;
 
         // Step 3: Execute agent with task and toolkit
-        let result = agent
-            .execute(task, &self.toolkit)
+        let toolkit = self
+            .toolkit
+            .for_agent(agent.as_ref())
+            .with_delegation(Arc::clone(&self.router), self.runtime.clone());
+        let result = run_with_fallback(self.runtime.as_deref(), agent.as_ref(), task, &toolkit)
             .await
             .context("Agent execution failed")?;
 
@@ -198,6 +234,7 @@ mod tests {
             agent: true,
             agent_id: Some(agent_id.to_string()),
             activation_hints: vec![],
+            pipeline: None,
         }
     }
 
@@ -217,6 +254,7 @@ mod tests {
             agent: true,
             agent_id: None,
             activation_hints: vec!["analyze".to_string(), "inspect".to_string()],
+            pipeline: None,
         }
     }
 
@@ -294,6 +332,45 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_execute_pipeline_runs_each_stage() {
+        let mut router = AgentRouter::new();
+        for id in ["first", "second"] {
+            router.register_agent(Arc::new(MockAgent {
+                id: id.to_string(),
+                can_handle_score: 0.0,
+                permissions: crate::agents::AgentPermissions::default(),
+            }));
+        }
+        let toolkit = Arc::new(AgentToolkit::new(
+            AgentId::from("test-agent"),
+            crate::agents::AgentPermissions::default(),
+            PathBuf::from("/workspace"),
+        ));
+        let executor = AgentCommandExecutor::new(Arc::new(router), toolkit);
+
+        let invocation = InvocationParser::parse("/review src/main.rs").unwrap();
+        let mut metadata = create_test_metadata_with_agent_id("first");
+        metadata.pipeline = Some("first -> second".to_string());
+
+        let result = executor
+            .execute_agent_command(
+                &invocation,
+                &metadata,
+                "Review: {{files}}",
+                None,
+                &PathBuf::from("/workspace"),
+            )
+            .await
+            .unwrap();
+
+        let AgentResult::Combined { results } = result else {
+            panic!("Expected Combined result");
+        };
+        let ids: Vec<_> = results.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, vec!["first", "second"]);
+    }
+
     #[tokio::test]
     async fn test_execute_agent_not_found() {
         let router = Arc::new(AgentRouter::new());
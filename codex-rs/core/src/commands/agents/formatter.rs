@@ -1,5 +1,8 @@
 //! Agent result formatting for different output formats.
 
+use std::str::FromStr;
+
+use super::reports;
 use crate::agents::AgentResult;
 use crate::agents::CodeReviewFinding;
 use crate::agents::Severity;
@@ -13,6 +16,36 @@ pub enum OutputFormat {
     Json,
     /// Plain text format for simple display.
     PlainText,
+    /// SARIF 2.1.0 log of code review findings, for code scanning tools.
+    Sarif,
+    /// GitHub Actions workflow commands that annotate code review findings.
+    GitHub,
+    /// GitLab Code Quality report of code review findings.
+    GitLab,
+}
+
+impl OutputFormat {
+    /// Names accepted by [`OutputFormat::from_str`].
+    pub const NAMES: &[&str] = &["markdown", "json", "text", "sarif", "github", "gitlab"];
+}
+
+impl FromStr for OutputFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "markdown" | "md" => Ok(Self::Markdown),
+            "json" => Ok(Self::Json),
+            "text" | "plain" => Ok(Self::PlainText),
+            "sarif" => Ok(Self::Sarif),
+            "github" => Ok(Self::GitHub),
+            "gitlab" => Ok(Self::GitLab),
+            other => anyhow::bail!(
+                "Unknown output format '{other}', expected one of: {}",
+                Self::NAMES.join(", ")
+            ),
+        }
+    }
 }
 
 /// Formats agent results into different output formats.
@@ -49,6 +82,9 @@ impl AgentResultFormatter {
             OutputFormat::Markdown => Self::format_markdown(result),
             OutputFormat::Json => Self::format_json(result),
             OutputFormat::PlainText => Self::format_plain(result),
+            OutputFormat::Sarif => reports::sarif(result),
+            OutputFormat::GitHub => reports::github_annotations(result),
+            OutputFormat::GitLab => reports::gitlab_code_quality(result),
         }
     }
 
@@ -129,6 +165,22 @@ impl AgentResultFormatter {
                 }
                 output
             }
+            AgentResult::Combined { results } => {
+                let mut output = String::from("# Combined Results\n\n");
+                for (agent_id, result) in results {
+                    output.push_str(&format!("## Agent `{id}`\n\n", id = agent_id.as_str()));
+                    // Demote nested headings so each agent's output stays under its section.
+                    for line in Self::format_markdown(result).lines() {
+                        if line.starts_with('#') {
+                            output.push_str("##");
+                        }
+                        output.push_str(line);
+                        output.push('\n');
+                    }
+                    output.push('\n');
+                }
+                output
+            }
         }
     }
 
@@ -215,6 +267,25 @@ impl AgentResultFormatter {
                 });
                 serde_json::to_string_pretty(&obj).unwrap_or_else(|_| "{}".to_string())
             }
+            AgentResult::Combined { results } => {
+                let results_json: Vec<_> = results
+                    .iter()
+                    .map(|(agent_id, result)| {
+                        let mut obj: serde_json::Value =
+                            serde_json::from_str(&Self::format_json(result))
+                                .unwrap_or_else(|_| serde_json::json!({}));
+                        obj["agent"] = serde_json::Value::String(agent_id.as_str().to_string());
+                        obj
+                    })
+                    .collect();
+
+                let obj = serde_json::json!({
+                    "type": "combined",
+                    "results": results_json,
+                    "count": results.len(),
+                });
+                serde_json::to_string_pretty(&obj).unwrap_or_else(|_| "{}".to_string())
+            }
         }
     }
 
@@ -291,6 +362,19 @@ impl AgentResultFormatter {
                 }
                 output
             }
+            AgentResult::Combined { results } => {
+                let mut output = String::new();
+                for (agent_id, result) in results {
+                    let header = format!("[{id}]", id = agent_id.as_str());
+                    output.push_str(&format!(
+                        "{header}\n{sep}\n\n",
+                        sep = "-".repeat(header.chars().count())
+                    ));
+                    output.push_str(&Self::format_plain(result));
+                    output.push('\n');
+                }
+                output
+            }
         }
     }
 }
@@ -298,6 +382,7 @@ impl AgentResultFormatter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::agents::AgentId;
     use crate::agents::Suggestion;
     use std::collections::HashMap;
     use std::path::PathBuf;
@@ -617,6 +702,49 @@ mod tests {
         assert!(!output.starts_with("#"));
     }
 
+    #[test]
+    fn test_combined_results_all_formats() {
+        let result = AgentResult::Combined {
+            results: vec![
+                (
+                    AgentId::from("review"),
+                    AgentResult::CodeReview {
+                        findings: vec![CodeReviewFinding {
+                            severity: Severity::Warning,
+                            category: "Style".to_string(),
+                            message: "Long line".to_string(),
+                            location: None,
+                            line_number: None,
+                        }],
+                    },
+                ),
+                (
+                    AgentId::from("summarize"),
+                    AgentResult::Analysis {
+                        summary: "All good".to_string(),
+                        details: HashMap::new(),
+                    },
+                ),
+            ],
+        };
+
+        let markdown = AgentResultFormatter::format(&result, OutputFormat::Markdown);
+        assert!(markdown.contains("## Agent `review`"));
+        assert!(markdown.contains("### Code Review"));
+        assert!(markdown.contains("### Agent Analysis"));
+
+        let json = AgentResultFormatter::format(&result, OutputFormat::Json);
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["type"], "combined");
+        assert_eq!(parsed["results"][0]["agent"], "review");
+        assert_eq!(parsed["results"][0]["type"], "code_review");
+        assert_eq!(parsed["results"][1]["summary"], "All good");
+
+        let plain = AgentResultFormatter::format(&result, OutputFormat::PlainText);
+        assert!(plain.contains("[summarize]"));
+        assert!(plain.contains("WARNING"));
+    }
+
     #[test]
     fn test_plain_unicode_safe() {
         let findings = vec![CodeReviewFinding {
@@ -638,4 +766,25 @@ mod tests {
         // (Plain text uses text labels instead)
         assert!(!output.contains("ℹ️"));
     }
+
+    #[test]
+    fn test_output_format_from_str() {
+        assert_eq!(
+            "sarif".parse::<OutputFormat>().unwrap(),
+            OutputFormat::Sarif
+        );
+        assert_eq!(
+            "GitHub".parse::<OutputFormat>().unwrap(),
+            OutputFormat::GitHub
+        );
+        assert_eq!(
+            "plain".parse::<OutputFormat>().unwrap(),
+            OutputFormat::PlainText
+        );
+        for name in OutputFormat::NAMES {
+            assert!(name.parse::<OutputFormat>().is_ok());
+        }
+        let err = "xml".parse::<OutputFormat>().unwrap_err();
+        assert!(err.to_string().contains("sarif"));
+    }
 }
@@ -6,6 +6,7 @@
 mod context_builder;
 mod executor;
 mod formatter;
+mod reports;
 
 pub use context_builder::AgentContextBuilder;
 pub use executor::AgentCommandExecutor;
@@ -0,0 +1,357 @@
+//! Machine-readable reports of code review findings for CI systems.
+//!
+//! These formats only carry [`CodeReviewFinding`]s; analysis summaries and
+//! suggestions have no equivalent in them and are left out.
+
+use std::path::Path;
+
+use sha1::Digest;
+use sha1::Sha1;
+
+use crate::agents::AgentId;
+use crate::agents::AgentResult;
+use crate::agents::CodeReviewFinding;
+use crate::agents::Severity;
+
+/// Tool name reported in SARIF runs.
+const TOOL_NAME: &str = "codex";
+
+/// Homepage reported in SARIF runs.
+const TOOL_INFORMATION_URI: &str = "https://github.com/openai/codex";
+
+/// SARIF schema the report conforms to.
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Collects the findings in `result`, grouped by the agent that produced
+/// them. Findings of a plain `CodeReview` result have no agent.
+fn findings_by_agent(result: &AgentResult) -> Vec<(Option<&AgentId>, &[CodeReviewFinding])> {
+    match result {
+        AgentResult::CodeReview { findings } => vec![(None, findings.as_slice())],
+        AgentResult::Combined { results } => results
+            .iter()
+            .flat_map(|(id, result)| {
+                findings_by_agent(result)
+                    .into_iter()
+                    .map(move |(inner, findings)| (inner.or(Some(id)), findings))
+            })
+            .collect(),
+        AgentResult::Analysis { .. } | AgentResult::Suggestions { .. } => Vec::new(),
+    }
+}
+
+/// Renders a path with forward slashes, as CI systems expect.
+fn portable_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+/// Turns a finding category such as "Weak Cryptography (MD5)" into a rule ID.
+fn rule_id(category: &str) -> String {
+    let mut id = String::new();
+    for c in category.chars() {
+        if c.is_ascii_alphanumeric() {
+            id.push(c.to_ascii_lowercase());
+        } else if !id.is_empty() && !id.ends_with('-') {
+            id.push('-');
+        }
+    }
+    id.trim_end_matches('-').to_string()
+}
+
+/// Formats findings as a SARIF 2.1.0 log with one run per agent.
+pub(super) fn sarif(result: &AgentResult) -> String {
+    let runs: Vec<_> = findings_by_agent(result)
+        .into_iter()
+        .map(|(agent_id, findings)| {
+            let mut rules: Vec<serde_json::Value> = Vec::new();
+            let mut rule_ids: Vec<String> = Vec::new();
+            let results: Vec<_> = findings
+                .iter()
+                .map(|finding| {
+                    let id = rule_id(&finding.category);
+                    if !rule_ids.contains(&id) {
+                        rules.push(serde_json::json!({
+                            "id": id,
+                            "name": finding.category,
+                            "shortDescription": { "text": finding.category },
+                        }));
+                        rule_ids.push(id.clone());
+                    }
+
+                    let mut obj = serde_json::json!({
+                        "ruleId": id,
+                        "level": match finding.severity {
+                            Severity::Error => "error",
+                            Severity::Warning => "warning",
+                            Severity::Info => "note",
+                        },
+                        "message": { "text": finding.message },
+                    });
+                    if let Some(location) = &finding.location {
+                        let mut physical = serde_json::json!({
+                            "artifactLocation": { "uri": portable_path(location) },
+                        });
+                        if let Some(line) = finding.line_number {
+                            physical["region"] = serde_json::json!({ "startLine": line });
+                        }
+                        obj["locations"] = serde_json::json!([{ "physicalLocation": physical }]);
+                    }
+                    obj
+                })
+                .collect();
+
+            let name = match agent_id {
+                Some(id) => format!("{TOOL_NAME}/{}", id.as_str()),
+                None => TOOL_NAME.to_string(),
+            };
+            serde_json::json!({
+                "tool": {
+                    "driver": {
+                        "name": name,
+                        "informationUri": TOOL_INFORMATION_URI,
+                        "rules": rules,
+                    },
+                },
+                "results": results,
+            })
+        })
+        .collect();
+
+    let obj = serde_json::json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": runs,
+    });
+    serde_json::to_string_pretty(&obj).unwrap_or_else(|_| "{}".to_string())
+}
+
+/// Escapes data in a GitHub Actions workflow command.
+fn escape_github_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escapes a property value in a GitHub Actions workflow command.
+fn escape_github_property(value: &str) -> String {
+    escape_github_data(value)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
+
+/// Formats findings as GitHub Actions workflow commands, which GitHub shows
+/// as annotations on the pull request diff.
+pub(super) fn github_annotations(result: &AgentResult) -> String {
+    let mut output = String::new();
+    for (agent_id, findings) in findings_by_agent(result) {
+        for finding in findings {
+            let command = match finding.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Info => "notice",
+            };
+            let mut properties = Vec::new();
+            if let Some(location) = &finding.location {
+                properties.push(format!(
+                    "file={}",
+                    escape_github_property(&portable_path(location))
+                ));
+                if let Some(line) = finding.line_number {
+                    properties.push(format!("line={line}"));
+                }
+            }
+            let title = match agent_id {
+                Some(id) => format!("{} ({})", finding.category, id.as_str()),
+                None => finding.category.clone(),
+            };
+            properties.push(format!("title={}", escape_github_property(&title)));
+
+            output.push_str(&format!(
+                "::{command} {}::{}\n",
+                properties.join(","),
+                escape_github_data(&finding.message)
+            ));
+        }
+    }
+    output
+}
+
+/// Formats findings as a GitLab Code Quality report.
+pub(super) fn gitlab_code_quality(result: &AgentResult) -> String {
+    let issues: Vec<_> = findings_by_agent(result)
+        .into_iter()
+        .flat_map(|(_, findings)| findings)
+        .map(|finding| {
+            let path = finding
+                .location
+                .as_deref()
+                .map(portable_path)
+                .unwrap_or_default();
+            let line = finding.line_number.unwrap_or(1);
+            serde_json::json!({
+                "description": finding.message,
+                "check_name": rule_id(&finding.category),
+                "fingerprint": fingerprint(finding, &path, line),
+                "severity": match finding.severity {
+                    Severity::Error => "critical",
+                    Severity::Warning => "major",
+                    Severity::Info => "info",
+                },
+                "location": {
+                    "path": path,
+                    "lines": { "begin": line },
+                },
+            })
+        })
+        .collect();
+
+    serde_json::to_string_pretty(&issues).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Returns a stable identifier for a finding so GitLab can track it across
+/// pipelines.
+fn fingerprint(finding: &CodeReviewFinding, path: &str, line: usize) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(finding.category.as_bytes());
+    hasher.update([0]);
+    hasher.update(path.as_bytes());
+    hasher.update([0]);
+    hasher.update(line.to_string().as_bytes());
+    hasher.update([0]);
+    hasher.update(finding.message.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn finding(
+        severity: Severity,
+        location: Option<&str>,
+        line: Option<usize>,
+    ) -> CodeReviewFinding {
+        CodeReviewFinding {
+            severity,
+            category: "Weak Cryptography (MD5)".to_string(),
+            message: "MD5 is broken, use SHA-256".to_string(),
+            location: location.map(PathBuf::from),
+            line_number: line,
+        }
+    }
+
+    #[test]
+    fn test_rule_id() {
+        assert_eq!(rule_id("Weak Cryptography (MD5)"), "weak-cryptography-md5");
+        assert_eq!(rule_id("Code Quality"), "code-quality");
+    }
+
+    #[test]
+    fn test_sarif_report() {
+        let result = AgentResult::CodeReview {
+            findings: vec![
+                finding(Severity::Error, Some("src/auth.rs"), Some(12)),
+                finding(Severity::Info, None, None),
+            ],
+        };
+
+        let sarif: serde_json::Value = serde_json::from_str(&sarif(&result)).unwrap();
+
+        assert_eq!(sarif["version"], "2.1.0");
+        let run = &sarif["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], "codex");
+        assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 1);
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results[0]["ruleId"], "weak-cryptography-md5");
+        assert_eq!(results[0]["level"], "error");
+        assert_eq!(
+            results[0]["locations"][0]["physicalLocation"],
+            serde_json::json!({
+                "artifactLocation": { "uri": "src/auth.rs" },
+                "region": { "startLine": 12 },
+            })
+        );
+        assert_eq!(results[1]["level"], "note");
+        assert!(results[1].get("locations").is_none());
+    }
+
+    #[test]
+    fn test_sarif_run_per_agent() {
+        let result = AgentResult::Combined {
+            results: vec![
+                (
+                    AgentId::from("security"),
+                    AgentResult::CodeReview {
+                        findings: vec![finding(Severity::Warning, Some("a.rs"), Some(1))],
+                    },
+                ),
+                (
+                    AgentId::from("summarize"),
+                    AgentResult::Analysis {
+                        summary: "ok".to_string(),
+                        details: Default::default(),
+                    },
+                ),
+            ],
+        };
+
+        let sarif: serde_json::Value = serde_json::from_str(&sarif(&result)).unwrap();
+        let runs = sarif["runs"].as_array().unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0]["tool"]["driver"]["name"], "codex/security");
+    }
+
+    #[test]
+    fn test_github_annotations() {
+        let mut with_newline = finding(Severity::Warning, Some("src/a,b.rs"), Some(3));
+        with_newline.message = "first line\nsecond: 100%".to_string();
+        let result = AgentResult::CodeReview {
+            findings: vec![with_newline, finding(Severity::Info, None, None)],
+        };
+
+        assert_eq!(
+            github_annotations(&result),
+            "::warning file=src/a%2Cb.rs,line=3,title=Weak Cryptography (MD5)::first line%0Asecond: 100%25\n\
+             ::notice title=Weak Cryptography (MD5)::MD5 is broken, use SHA-256\n"
+        );
+    }
+
+    #[test]
+    fn test_gitlab_code_quality() {
+        let result = AgentResult::CodeReview {
+            findings: vec![
+                finding(Severity::Error, Some("src/auth.rs"), Some(12)),
+                finding(Severity::Error, Some("src/auth.rs"), Some(40)),
+            ],
+        };
+
+        let report: serde_json::Value =
+            serde_json::from_str(&gitlab_code_quality(&result)).unwrap();
+        let issues = report.as_array().unwrap();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0]["severity"], "critical");
+        assert_eq!(issues[0]["check_name"], "weak-cryptography-md5");
+        assert_eq!(
+            issues[0]["location"],
+            serde_json::json!({ "path": "src/auth.rs", "lines": { "begin": 12 } })
+        );
+        assert_eq!(issues[0]["fingerprint"].as_str().unwrap().len(), 40);
+        assert_ne!(issues[0]["fingerprint"], issues[1]["fingerprint"]);
+
+        // Fingerprints are stable across runs.
+        let again: serde_json::Value = serde_json::from_str(&gitlab_code_quality(&result)).unwrap();
+        assert_eq!(again[0]["fingerprint"], issues[0]["fingerprint"]);
+    }
+
+    #[test]
+    fn test_non_review_results_produce_empty_reports() {
+        let result = AgentResult::Suggestions { items: vec![] };
+
+        assert_eq!(github_annotations(&result), "");
+        assert_eq!(gitlab_code_quality(&result), "[]");
+        let sarif: serde_json::Value = serde_json::from_str(&sarif(&result)).unwrap();
+        assert_eq!(sarif["runs"], serde_json::json!([]));
+    }
+}
@@ -154,6 +154,7 @@ mod tests {
             agent: false,
             agent_id: None,
             activation_hints: vec![],
+            pipeline: None,
         }
     }
 
@@ -253,6 +254,7 @@ mod tests {
             agent: false,
             agent_id: None,
             activation_hints: vec![],
+            pipeline: None,
         };
 
         let invocation = InvocationParser::parse("/review src/main.rs deep").unwrap();
@@ -288,6 +290,7 @@ mod tests {
             agent: false,
             agent_id: None,
             activation_hints: vec![],
+            pipeline: None,
         };
 
         let invocation = InvocationParser::parse("/format").unwrap();
@@ -15,6 +15,7 @@ use super::context::CommandContext;
 use super::expander::TemplateExpander;
 use super::invocation::CommandInvocation;
 use super::registry::CommandRegistry;
+use crate::agents::AgentResult;
 use crate::agents::GitContext;
 use anyhow::Context;
 use anyhow::Result;
@@ -129,6 +130,50 @@ impl CommandExecutor {
             .await
     }
 
+    /// Runs an agent-backed command and returns the agent's result rather
+    /// than a formatted prompt.
+    ///
+    /// Returns `Ok(None)` when the command exists but is not agent-backed;
+    /// such commands go through [`CommandExecutor::execute`] instead.
+    pub async fn execute_agent(
+        &self,
+        invocation: &CommandInvocation,
+        exec_context: &ExecutionContext,
+    ) -> Result<Option<AgentResult>> {
+        let command = self
+            .registry
+            .get(&invocation.command_name)
+            .await
+            .context(format!(
+                "Command '{}' not found. Run `codex commands list` to see available commands.",
+                invocation.command_name
+            ))?;
+
+        match command
+            .as_any()
+            .downcast_ref::<super::user::loader::UserCommand>()
+        {
+            Some(user_cmd) if user_cmd.metadata.agent => self
+                .run_agent_command(invocation, user_cmd, exec_context)
+                .await
+                .map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns true if `command_name` names an agent-backed command.
+    pub async fn is_agent_command(&self, command_name: &str) -> bool {
+        self.registry
+            .get(command_name)
+            .await
+            .is_some_and(|command| {
+                command
+                    .as_any()
+                    .downcast_ref::<super::user::loader::UserCommand>()
+                    .is_some_and(|user_cmd| user_cmd.metadata.agent)
+            })
+    }
+
     /// Executes an agent-backed command.
     async fn execute_agent_command(
         &self,
@@ -136,6 +181,24 @@ impl CommandExecutor {
         user_cmd: &super::user::loader::UserCommand,
         exec_context: &ExecutionContext,
     ) -> Result<String> {
+        let agent_result = self
+            .run_agent_command(invocation, user_cmd, exec_context)
+            .await?;
+
+        // Format result using AgentResultFormatter
+        Ok(AgentResultFormatter::format(
+            &agent_result,
+            OutputFormat::Markdown,
+        ))
+    }
+
+    /// Runs an agent-backed command through the agent executor.
+    async fn run_agent_command(
+        &self,
+        invocation: &CommandInvocation,
+        user_cmd: &super::user::loader::UserCommand,
+        exec_context: &ExecutionContext,
+    ) -> Result<AgentResult> {
         // Check if agent executor is available
         let agent_executor = self.agent_executor.as_ref().ok_or_else(|| {
             anyhow::anyhow!(
@@ -152,7 +215,7 @@ impl CommandExecutor {
         });
 
         // Execute agent command
-        let agent_result = agent_executor
+        agent_executor
             .execute_agent_command(
                 invocation,
                 &user_cmd.metadata,
@@ -161,13 +224,7 @@ impl CommandExecutor {
                 &exec_context.workspace_root,
             )
             .await
-            .context("Agent execution failed")?;
-
-        // Format result using AgentResultFormatter
-        Ok(AgentResultFormatter::format(
-            &agent_result,
-            OutputFormat::Markdown,
-        ))
+            .context("Agent execution failed")
     }
 
     /// Executes a template-based command.
@@ -557,6 +614,7 @@ Analyze the code at: {{target}}
         // Verify agent execution
         assert!(result.contains("Agent Analysis"));
         assert!(result.contains("Agent executed successfully"));
+        assert!(executor.is_agent_command("analyze").await);
     }
 
     #[tokio::test]
@@ -613,6 +671,13 @@ Hello {{args.name}}!
         // Should execute as template, not agent
         assert_eq!(result.trim(), "Hello World!");
         assert!(!result.contains("Agent Analysis"));
+
+        // There is no agent result for template commands
+        let invocation = InvocationParser::parse("/hello World").unwrap();
+        let agent_result = executor.execute_agent(&invocation, &context).await.unwrap();
+        assert!(agent_result.is_none());
+        assert!(!executor.is_agent_command("hello").await);
+        assert!(!executor.is_agent_command("missing").await);
     }
 
     #[tokio::test]
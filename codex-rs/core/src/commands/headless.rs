@@ -0,0 +1,263 @@
+//! Headless execution of agent-backed slash commands.
+//!
+//! `codex exec "/review src/"` runs agent commands without an interactive
+//! session: the command is resolved through the [`CommandRegistry`], run by
+//! the [`AgentCommandExecutor`], and its [`AgentResult`] is returned to the
+//! caller for formatting instead of being sent to the model as a prompt.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::Context;
+use anyhow::Result;
+use codex_otel::otel_event_manager::OtelEventManager;
+use codex_protocol::ConversationId;
+
+use super::agents::AgentCommandExecutor;
+use super::executor::CommandExecutor;
+use super::executor::ExecutionContext;
+use super::git_utils::get_git_diff;
+use super::invocation::InvocationParser;
+use super::registry::CommandRegistry;
+use crate::AuthManager;
+use crate::CodexAuth;
+use crate::agents::AgentId;
+use crate::agents::AgentPermissions;
+use crate::agents::AgentResult;
+use crate::agents::AgentRouter;
+use crate::agents::AgentRuntime;
+use crate::agents::AgentToolkit;
+use crate::agents::user::UserAgentLoader;
+use crate::approval_rules::project_for_cwd;
+use crate::client::ModelClient;
+use crate::config::Config;
+use crate::exec_policy::ExecPolicy;
+use crate::terminal;
+use tracing::warn;
+
+/// Identity of the toolkit that agent commands start from.
+const HEADLESS_TOOLKIT_ID: &str = "headless-command";
+
+/// Result of a headless agent command.
+#[derive(Debug, Clone)]
+pub struct AgentCommandOutput {
+    /// Name of the command that ran, without the leading slash.
+    pub command_name: String,
+    /// Result produced by the command's agent or pipeline.
+    pub result: AgentResult,
+}
+
+/// Runs agent-backed slash commands outside of a session.
+pub struct HeadlessCommandRunner {
+    registry: Arc<CommandRegistry>,
+    executor: CommandExecutor,
+    workspace_root: PathBuf,
+}
+
+impl HeadlessCommandRunner {
+    /// Creates a runner that resolves commands in `registry` and runs agent
+    /// commands through `agent_executor`.
+    pub fn new(
+        registry: Arc<CommandRegistry>,
+        agent_executor: Arc<AgentCommandExecutor>,
+        workspace_root: PathBuf,
+    ) -> Self {
+        Self {
+            executor: CommandExecutor::new(Arc::clone(&registry))
+                .with_agent_executor(agent_executor),
+            registry,
+            workspace_root,
+        }
+    }
+
+    /// Creates a runner with the commands in `~/.codex/commands`, the
+    /// built-in and user-defined agents, and a model runtime for `config`.
+    pub async fn from_config(config: &Config, auth_manager: Arc<AuthManager>) -> Result<Self> {
+        let registry = CommandRegistry::new(config.codex_home.join("commands"))
+            .await
+            .context("Failed to load commands")?;
+
+        let mut router = AgentRouter::with_builtin_agents();
+        router.set_routing_mode(config.agents.routing);
+        let router = Arc::new(router);
+        let loader = UserAgentLoader::new(UserAgentLoader::default_dirs(
+            &config.codex_home,
+            config.cwd_trusted.then_some(config.cwd.as_path()),
+        ));
+        match loader.load_all().await {
+            Ok(agents) => router.set_user_agents(agents).await,
+            Err(e) => warn!("Failed to load user-defined agents: {e:#}"),
+        }
+
+        let conversation_id = ConversationId::new();
+        let auth = auth_manager.auth();
+        let otel_event_manager = OtelEventManager::new(
+            conversation_id,
+            config.model.as_str(),
+            config.model_family.slug.as_str(),
+            auth.as_ref().and_then(CodexAuth::get_account_id),
+            auth.as_ref().map(|a| a.mode),
+            config.otel.log_user_prompt,
+            terminal::user_agent(),
+        );
+        let runtime = AgentRuntime::new(ModelClient::new(
+            Arc::new(config.clone()),
+            Some(auth_manager),
+            otel_event_manager,
+            config.model_provider.clone(),
+            config.model_reasoning_effort,
+            config.model_reasoning_summary,
+            conversation_id,
+        ));
+
+        // As in interactive sessions, a project's own policies only apply
+        // once the user trusts the project.
+        let project_root = config.cwd_trusted.then(|| project_for_cwd(&config.cwd));
+        let (exec_policy, policy_errors) = ExecPolicy::load(&ExecPolicy::policy_dirs(
+            &config.codex_home,
+            project_root.as_deref(),
+        ));
+        for err in policy_errors {
+            warn!("{err}; its rules are ignored");
+        }
+
+        let toolkit = AgentToolkit::new(
+            AgentId::from(HEADLESS_TOOLKIT_ID),
+            AgentPermissions::default(),
+            config.cwd.clone(),
+        )
+        .with_sandbox_policy(
+            config.sandbox_policy.clone(),
+            config.codex_linux_sandbox_exe.clone(),
+        )
+        .with_sandbox_limits(config.sandbox_limits)
+        .with_shell_environment_policy(config.shell_environment_policy.clone())
+        .with_exec_policy(Arc::new(exec_policy));
+        let agent_executor =
+            AgentCommandExecutor::new(router, Arc::new(toolkit)).with_runtime(Arc::new(runtime));
+
+        Ok(Self::new(
+            Arc::new(registry),
+            Arc::new(agent_executor),
+            config.cwd.clone(),
+        ))
+    }
+
+    /// Runs `prompt` if it is a slash command naming an agent-backed command.
+    ///
+    /// Returns `Ok(None)` for anything else, including unknown commands and
+    /// template commands, so the caller can send the prompt to the model as
+    /// usual.
+    pub async fn run(&self, prompt: &str) -> Result<Option<AgentCommandOutput>> {
+        let prompt = prompt.trim();
+        if !prompt.starts_with('/') {
+            return Ok(None);
+        }
+        let Ok(invocation) = InvocationParser::parse(prompt) else {
+            return Ok(None);
+        };
+        if self.registry.get(&invocation.command_name).await.is_none() {
+            return Ok(None);
+        }
+
+        let git_diff = match get_git_diff().await {
+            Ok((true, diff)) if !diff.is_empty() => Some(diff),
+            _ => None,
+        };
+        let exec_context =
+            ExecutionContext::new(self.workspace_root.clone()).with_git_diff(git_diff);
+
+        let result = self
+            .executor
+            .execute_agent(&invocation, &exec_context)
+            .await?;
+        Ok(result.map(|result| AgentCommandOutput {
+            command_name: invocation.command_name,
+            result,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::Severity;
+    use tempfile::TempDir;
+
+    async fn runner(temp: &TempDir) -> HeadlessCommandRunner {
+        let commands_dir = temp.path().join("commands");
+        std::fs::create_dir_all(&commands_dir).unwrap();
+        std::fs::write(
+            commands_dir.join("security-scan.md"),
+            "---\nname: security-scan\ndescription: Security scan\ncategory: agents\nagent: true\nagent_id: security\nargs:\n  - name: files\n    type: string\n    required: true\n    description: Files to scan\n---\nScan {{files}}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            commands_dir.join("hello.md"),
+            "---\nname: hello\ndescription: Greeting\ncategory: utility\n---\nHello!\n",
+        )
+        .unwrap();
+        std::fs::write(
+            temp.path().join("auth.rs"),
+            "fn hash(p: &str) -> String { md5(p) }\n",
+        )
+        .unwrap();
+
+        let registry = Arc::new(CommandRegistry::new(commands_dir).await.unwrap());
+        let toolkit = AgentToolkit::new(
+            AgentId::from(HEADLESS_TOOLKIT_ID),
+            AgentPermissions::default(),
+            temp.path().to_path_buf(),
+        );
+        let agent_executor = Arc::new(AgentCommandExecutor::new(
+            Arc::new(AgentRouter::with_builtin_agents()),
+            Arc::new(toolkit),
+        ));
+        HeadlessCommandRunner::new(registry, agent_executor, temp.path().to_path_buf())
+    }
+
+    #[tokio::test]
+    async fn test_run_agent_command() {
+        let temp = TempDir::new().unwrap();
+        let runner = runner(&temp).await;
+        let file = temp.path().join("auth.rs");
+
+        let output = runner
+            .run(&format!("/security-scan {}", file.display()))
+            .await
+            .unwrap()
+            .expect("agent command should run");
+
+        assert_eq!(output.command_name, "security-scan");
+        assert!(output.result.has_findings_at_least(Severity::Warning));
+    }
+
+    #[tokio::test]
+    async fn test_run_ignores_other_prompts() {
+        let temp = TempDir::new().unwrap();
+        let runner = runner(&temp).await;
+
+        assert!(runner.run("explain this code").await.unwrap().is_none());
+        assert!(runner.run("/unknown src/").await.unwrap().is_none());
+        assert!(runner.run("/hello").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_from_config_survives_unreadable_agents_dir() {
+        let codex_home = TempDir::new().unwrap();
+        std::fs::write(codex_home.path().join("agents"), "not a directory").unwrap();
+        let config = Config::load_from_base_config_with_overrides(
+            crate::config::ConfigToml::default(),
+            crate::config::ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .unwrap();
+        let auth_manager = AuthManager::from_auth_for_testing(CodexAuth::from_api_key("test"));
+
+        assert!(
+            HeadlessCommandRunner::from_config(&config, auth_manager)
+                .await
+                .is_ok()
+        );
+    }
+}
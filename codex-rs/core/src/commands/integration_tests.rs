@@ -333,7 +333,7 @@ Hello, {{person}}! Welcome to Codex.
         assert_eq!(command_text.unwrap(), "/explain screenshot.png");
 
         let expanded = "Explain this image...".to_string();
-        let result = replace_with_expanded_prompt(items.clone(), expanded);
+        let result = replace_with_expanded_prompt(items.clone(), expanded.clone());
 
         // Should preserve images
         assert_eq!(result.len(), 3);
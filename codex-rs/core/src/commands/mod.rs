@@ -15,6 +15,7 @@
 //! - `executor`: Command execution pipeline
 //! - `git_utils`: Git diff extraction for context enhancement
 //! - `integration`: Integration with exec_command flow
+//! - `headless`: Agent command execution for `codex exec`
 //! - `builtin`: Built-in commands shipped with Codex
 //! - `user`: User-defined command loading
 //! - `agents`: Agent integration for AI-powered command execution
@@ -26,6 +27,7 @@ pub mod context;
 pub mod executor;
 pub mod expander;
 pub mod git_utils;
+pub mod headless;
 pub mod integration;
 pub mod invocation;
 pub mod parser;
@@ -43,6 +45,7 @@ pub use context::CommandContext;
 pub use executor::{CommandExecutor, ConversationContext, ExecutionContext, MessageSummary};
 pub use expander::TemplateExpander;
 pub use git_utils::get_git_diff;
+pub use headless::{AgentCommandOutput, HeadlessCommandRunner};
 pub use integration::{detect_slash_command, execute_slash_command, replace_with_expanded_prompt};
 pub use invocation::CommandInvocation;
 pub use parser::{CommandMetadata, ParsedCommand};
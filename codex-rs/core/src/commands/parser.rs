@@ -3,6 +3,8 @@
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::agents::AgentPipeline;
+
 /// Parsed command from a Markdown file.
 #[derive(Debug, Clone)]
 pub struct ParsedCommand {
@@ -29,6 +31,10 @@ pub struct CommandMetadata {
     /// Activation hints for agent selection (keywords for context matching).
     #[serde(default)]
     pub activation_hints: Vec<String>,
+    /// Agents to run in sequence instead of a single agent, written as
+    /// `review -> security -> summarize`.
+    #[serde(default)]
+    pub pipeline: Option<String>,
 }
 
 /// Command permissions model.
@@ -121,6 +127,10 @@ impl CommandParser {
             anyhow::bail!("Command category cannot be empty");
         }
 
+        if let Some(pipeline) = &metadata.pipeline {
+            AgentPipeline::parse(pipeline)?;
+        }
+
         // Validate argument definitions
         for arg in &metadata.args {
             if arg.name.is_empty() {
@@ -137,7 +147,7 @@ impl CommandParser {
         Ok(())
     }
 
-    fn split_frontmatter(content: &str) -> anyhow::Result<(String, String)> {
+    pub(crate) fn split_frontmatter(content: &str) -> anyhow::Result<(String, String)> {
         let lines: Vec<&str> = content.lines().collect();
 
         if lines.first().is_none_or(|l| l.trim() != "---") {
@@ -371,6 +381,21 @@ Body"#;
         );
     }
 
+    #[test]
+    fn test_parse_pipeline_command() {
+        let input = "---\nname: audit\ndescription: Audit\ncategory: agents\nagent: true\npipeline: review -> security\n---\n\nAudit {{files}}";
+
+        let parsed = CommandParser::parse(input).unwrap();
+        assert_eq!(
+            parsed.metadata.pipeline.as_deref(),
+            Some("review -> security")
+        );
+
+        let invalid = input.replace("review -> security", "review -> review");
+        let err = CommandParser::parse(&invalid).unwrap_err();
+        assert!(err.to_string().contains("more than once"));
+    }
+
     #[test]
     fn test_parse_agent_command() {
         let input = r#"---
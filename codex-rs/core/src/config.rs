@@ -3,15 +3,19 @@ pub use crate::config_loader::load_config_as_toml;
 use crate::config_loader::load_config_layers_with_overrides;
 use crate::config_loader::merge_toml_values;
 use crate::config_profile::ConfigProfile;
+use crate::config_types::Agents;
+use crate::config_types::BudgetLimits;
 use crate::config_types::DEFAULT_OTEL_ENVIRONMENT;
 use crate::config_types::History;
 use crate::config_types::McpServerConfig;
 use crate::config_types::McpServerTransportConfig;
+use crate::config_types::ModelPrice;
 use crate::config_types::Notifications;
 use crate::config_types::OtelConfig;
 use crate::config_types::OtelConfigToml;
 use crate::config_types::OtelExporterKind;
 use crate::config_types::ReasoningSummaryFormat;
+use crate::config_types::SandboxLimits;
 use crate::config_types::SandboxWorkspaceWrite;
 use crate::config_types::ShellEnvironmentPolicy;
 use crate::config_types::ShellEnvironmentPolicyToml;
@@ -23,6 +27,7 @@ use crate::model_family::derive_default_model_family;
 use crate::model_family::find_family_for_model;
 use crate::model_provider_info::ModelProviderInfo;
 use crate::model_provider_info::built_in_model_providers;
+use crate::network_proxy::NetworkAllowlist;
 use crate::openai_model_info::get_model_info;
 use crate::protocol::AskForApproval;
 use crate::protocol::SandboxPolicy;
@@ -94,6 +99,15 @@ pub struct Config {
 
     pub sandbox_policy: SandboxPolicy,
 
+    /// Resource ceilings applied to each command the agent runs.
+    pub sandbox_limits: SandboxLimits,
+
+    /// Usage ceilings for each session and turn.
+    pub budget: BudgetLimits,
+
+    /// Per-model prices overriding the built-in table, keyed by model slug.
+    pub model_prices: HashMap<String, ModelPrice>,
+
     pub shell_environment_policy: ShellEnvironmentPolicy,
 
     /// When `true`, `AgentReasoning` events emitted by the backend will be
@@ -142,6 +156,10 @@ pub struct Config {
     /// resolved against this path.
     pub cwd: PathBuf,
 
+    /// Whether the user marked the project containing `cwd` as trusted in
+    /// `[projects]`.
+    pub cwd_trusted: bool,
+
     /// Definition for MCP servers that Codex can reach out to for tool calls.
     pub mcp_servers: HashMap<String, McpServerConfig>,
 
@@ -223,9 +241,16 @@ pub struct Config {
     /// and executed through the command registry before being sent to the LLM.
     pub experimental_command_system_enabled: bool,
 
+    /// Routing settings for agent-backed commands.
+    pub agents: Agents,
+
     /// The active profile name used to derive this `Config` (if any).
     pub active_profile: Option<String>,
 
+    /// The `-c key=value` overrides this `Config` was loaded with, so that
+    /// settings re-read during a session, such as MCP servers, honor them.
+    pub cli_overrides: Vec<(String, TomlValue)>,
+
     /// Tracks whether the Windows onboarding screen has been acknowledged.
     pub windows_wsl_setup_acknowledged: bool,
 
@@ -247,7 +272,7 @@ impl Config {
 
         let root_value = load_resolved_config(
             &codex_home,
-            cli_overrides,
+            cli_overrides.clone(),
             crate::config_loader::LoaderOverrides::default(),
         )
         .await?;
@@ -257,7 +282,9 @@ impl Config {
             std::io::Error::new(std::io::ErrorKind::InvalidData, e)
         })?;
 
-        Self::load_from_base_config_with_overrides(cfg, overrides, codex_home)
+        let mut config = Self::load_from_base_config_with_overrides(cfg, overrides, codex_home)?;
+        config.cli_overrides = cli_overrides;
+        Ok(config)
     }
 }
 
@@ -314,6 +341,27 @@ pub async fn load_global_mcp_servers(
     codex_home: &Path,
 ) -> std::io::Result<BTreeMap<String, McpServerConfig>> {
     let root_value = load_config_as_toml(codex_home).await?;
+    mcp_servers_from_toml(&root_value)
+}
+
+/// Loads the MCP servers a session started with `cli_overrides` uses, with
+/// the same layering as [`Config::load_with_cli_overrides`].
+pub async fn load_mcp_servers_with_cli_overrides(
+    codex_home: &Path,
+    cli_overrides: Vec<(String, TomlValue)>,
+) -> std::io::Result<BTreeMap<String, McpServerConfig>> {
+    let root_value = load_resolved_config(
+        codex_home,
+        cli_overrides,
+        crate::config_loader::LoaderOverrides::default(),
+    )
+    .await?;
+    mcp_servers_from_toml(&root_value)
+}
+
+fn mcp_servers_from_toml(
+    root_value: &TomlValue,
+) -> std::io::Result<BTreeMap<String, McpServerConfig>> {
     let Some(servers_value) = root_value.get("mcp_servers") else {
         return Ok(BTreeMap::new());
     };
@@ -414,6 +462,10 @@ pub fn write_global_mcp_servers(
                 entry["tool_timeout_sec"] = toml_edit::value(timeout.as_secs_f64());
             }
 
+            if config.allow_sampling {
+                entry["allow_sampling"] = toml_edit::value(true);
+            }
+
             doc["mcp_servers"][name.as_str()] = TomlItem::Table(entry);
         }
     }
@@ -726,6 +778,16 @@ pub struct ConfigToml {
     /// Sandbox configuration to apply if `sandbox` is `WorkspaceWrite`.
     pub sandbox_workspace_write: Option<SandboxWorkspaceWrite>,
 
+    /// Resource ceilings for each command the agent runs.
+    pub sandbox_limits: Option<SandboxLimits>,
+
+    /// Usage ceilings for each session and turn.
+    pub budget: Option<BudgetLimits>,
+
+    /// Per-model prices used to estimate cost against `budget`.
+    #[serde(default)]
+    pub model_prices: HashMap<String, ModelPrice>,
+
     /// Optional external command to spawn for end-user notifications.
     #[serde(default)]
     pub notify: Option<Vec<String>>,
@@ -773,6 +835,9 @@ pub struct ConfigToml {
     /// Collection of settings that are specific to the TUI.
     pub tui: Option<Tui>,
 
+    /// Settings for agent-backed commands.
+    pub agents: Option<Agents>,
+
     /// When set to `true`, `AgentReasoning` events will be hidden from the
     /// UI/output. Defaults to `false`.
     pub hide_agent_reasoning: Option<bool>,
@@ -868,6 +933,35 @@ impl From<ToolsToml> for Tools {
     }
 }
 
+/// Whether `resolved_cwd`, or the main checkout of the git worktree it lives
+/// in, is marked as trusted in `projects`.
+fn is_cwd_trusted_in(
+    projects: Option<&HashMap<String, ProjectConfig>>,
+    resolved_cwd: &Path,
+) -> bool {
+    let is_path_trusted = |path: &Path| {
+        let path_str = path.to_string_lossy().to_string();
+        projects
+            .and_then(|projects| projects.get(&path_str))
+            .map(|p| p.trust_level.as_deref() == Some("trusted"))
+            .unwrap_or(false)
+    };
+
+    // Fast path: exact cwd match
+    if is_path_trusted(resolved_cwd) {
+        return true;
+    }
+
+    // If cwd lives inside a git worktree, check whether the root git project
+    // (the primary repository working directory) is trusted. This lets
+    // worktrees inherit trust from the main project.
+    if let Some(root_project) = resolve_root_git_project_for_trust(resolved_cwd) {
+        return is_path_trusted(&root_project);
+    }
+
+    false
+}
+
 impl ConfigToml {
     /// Derive the effective sandbox policy from the configuration.
     fn derive_sandbox_policy(&self, sandbox_mode_override: Option<SandboxMode>) -> SandboxPolicy {
@@ -880,11 +974,13 @@ impl ConfigToml {
                 Some(SandboxWorkspaceWrite {
                     writable_roots,
                     network_access,
+                    network_allowlist,
                     exclude_tmpdir_env_var,
                     exclude_slash_tmp,
                 }) => SandboxPolicy::WorkspaceWrite {
                     writable_roots: writable_roots.clone(),
                     network_access: *network_access,
+                    network_allowlist: network_allowlist.clone(),
                     exclude_tmpdir_env_var: *exclude_tmpdir_env_var,
                     exclude_slash_tmp: *exclude_slash_tmp,
                 },
@@ -895,29 +991,7 @@ impl ConfigToml {
     }
 
     pub fn is_cwd_trusted(&self, resolved_cwd: &Path) -> bool {
-        let projects = self.projects.clone().unwrap_or_default();
-
-        let is_path_trusted = |path: &Path| {
-            let path_str = path.to_string_lossy().to_string();
-            projects
-                .get(&path_str)
-                .map(|p| p.trust_level.as_deref() == Some("trusted"))
-                .unwrap_or(false)
-        };
-
-        // Fast path: exact cwd match
-        if is_path_trusted(resolved_cwd) {
-            return true;
-        }
-
-        // If cwd lives inside a git worktree, check whether the root git project
-        // (the primary repository working directory) is trusted. This lets
-        // worktrees inherit trust from the main project.
-        if let Some(root_project) = resolve_root_git_project_for_trust(resolved_cwd) {
-            return is_path_trusted(&root_project);
-        }
-
-        false
+        is_cwd_trusted_in(self.projects.as_ref(), resolved_cwd)
     }
 
     pub fn get_config_profile(
@@ -1008,6 +1082,8 @@ impl Config {
         };
 
         let sandbox_policy = cfg.derive_sandbox_policy(sandbox_mode);
+        NetworkAllowlist::new(sandbox_policy.network_allowlist())
+            .map_err(|message| std::io::Error::new(std::io::ErrorKind::InvalidData, message))?;
 
         let mut model_providers = built_in_model_providers();
         // Merge user-defined providers into the built-in list.
@@ -1050,6 +1126,8 @@ impl Config {
             }
         };
 
+        let cwd_trusted = is_cwd_trusted_in(cfg.projects.as_ref(), &resolved_cwd);
+
         let history = cfg.history.unwrap_or_default();
 
         let tools_web_search_request = override_tools_web_search_request
@@ -1115,12 +1193,22 @@ impl Config {
             model_auto_compact_token_limit,
             model_provider_id,
             model_provider,
+            cwd_trusted,
             cwd: resolved_cwd,
             approval_policy: approval_policy
                 .or(config_profile.approval_policy)
                 .or(cfg.approval_policy)
                 .unwrap_or_else(AskForApproval::default),
             sandbox_policy,
+            sandbox_limits: config_profile
+                .sandbox_limits
+                .unwrap_or_default()
+                .or(cfg.sandbox_limits.unwrap_or_default()),
+            budget: config_profile
+                .budget
+                .unwrap_or_default()
+                .or(cfg.budget.unwrap_or_default()),
+            model_prices: cfg.model_prices,
             shell_environment_policy,
             notify: cfg.notify,
             user_instructions,
@@ -1182,7 +1270,9 @@ impl Config {
             experimental_command_system_enabled: cfg
                 .experimental_command_system_enabled
                 .unwrap_or(false),
+            agents: cfg.agents.unwrap_or_default(),
             active_profile: active_profile_name,
+            cli_overrides: Vec::new(),
             windows_wsl_setup_acknowledged: cfg.windows_wsl_setup_acknowledged.unwrap_or(false),
             disable_paste_burst: cfg.disable_paste_burst.unwrap_or(false),
             tui_notifications: cfg
@@ -1415,6 +1505,7 @@ exclude_slash_tmp = true
             SandboxPolicy::WorkspaceWrite {
                 writable_roots: vec![PathBuf::from("/my/workspace")],
                 network_access: false,
+                network_allowlist: vec![],
                 exclude_tmpdir_env_var: true,
                 exclude_slash_tmp: true,
             },
@@ -1511,6 +1602,36 @@ exclude_slash_tmp = true
         Ok(())
     }
 
+    #[tokio::test]
+    async fn load_mcp_servers_applies_cli_overrides() -> anyhow::Result<()> {
+        let codex_home = TempDir::new()?;
+        std::fs::write(
+            codex_home.path().join(CONFIG_TOML_FILE),
+            "[mcp_servers.docs]\ncommand = \"docs-server\"\n",
+        )?;
+
+        let servers = load_mcp_servers_with_cli_overrides(
+            codex_home.path(),
+            vec![(
+                "mcp_servers.extra.command".to_string(),
+                TomlValue::String("extra-server".to_string()),
+            )],
+        )
+        .await?;
+
+        assert_eq!(
+            servers.keys().map(String::as_str).collect::<Vec<_>>(),
+            vec!["docs", "extra"]
+        );
+        assert!(matches!(
+            &servers["extra"].transport,
+            McpServerTransportConfig::Stdio { command, .. } if command == "extra-server"
+        ));
+        assert_eq!(load_global_mcp_servers(codex_home.path()).await?.len(), 1);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn write_global_mcp_servers_round_trips_entries() -> anyhow::Result<()> {
         let codex_home = TempDir::new()?;
@@ -1526,6 +1647,7 @@ exclude_slash_tmp = true
                 },
                 startup_timeout_sec: Some(Duration::from_secs(3)),
                 tool_timeout_sec: Some(Duration::from_secs(5)),
+                allow_sampling: true,
             },
         );
 
@@ -1544,6 +1666,7 @@ exclude_slash_tmp = true
         }
         assert_eq!(docs.startup_timeout_sec, Some(Duration::from_secs(3)));
         assert_eq!(docs.tool_timeout_sec, Some(Duration::from_secs(5)));
+        assert!(docs.allow_sampling);
 
         let empty = BTreeMap::new();
         write_global_mcp_servers(codex_home.path(), &empty)?;
@@ -1650,6 +1773,7 @@ bearer_token = "secret"
                 },
                 startup_timeout_sec: None,
                 tool_timeout_sec: None,
+                allow_sampling: false,
             },
         )]);
 
@@ -1700,6 +1824,7 @@ ZIG_VAR = "3"
                 },
                 startup_timeout_sec: Some(Duration::from_secs(2)),
                 tool_timeout_sec: None,
+                allow_sampling: false,
             },
         )]);
 
@@ -1739,6 +1864,7 @@ startup_timeout_sec = 2.0
                 },
                 startup_timeout_sec: None,
                 tool_timeout_sec: None,
+                allow_sampling: false,
             },
         );
         write_global_mcp_servers(codex_home.path(), &servers)?;
@@ -2059,10 +2185,14 @@ model_verbosity = "high"
                 model_provider: fixture.openai_provider.clone(),
                 approval_policy: AskForApproval::Never,
                 sandbox_policy: SandboxPolicy::new_read_only_policy(),
+                sandbox_limits: SandboxLimits::default(),
+                budget: BudgetLimits::default(),
+                model_prices: HashMap::new(),
                 shell_environment_policy: ShellEnvironmentPolicy::default(),
                 user_instructions: None,
                 notify: None,
                 cwd: fixture.cwd(),
+                cwd_trusted: false,
                 mcp_servers: HashMap::new(),
                 mcp_oauth_credentials_store_mode: Default::default(),
                 model_providers: fixture.model_provider_map.clone(),
@@ -2087,7 +2217,9 @@ model_verbosity = "high"
                 use_experimental_use_rmcp_client: false,
                 include_view_image_tool: true,
                 experimental_command_system_enabled: false,
+                agents: Agents::default(),
                 active_profile: Some("o3".to_string()),
+                cli_overrides: Vec::new(),
                 windows_wsl_setup_acknowledged: false,
                 disable_paste_burst: false,
                 tui_notifications: Default::default(),
@@ -2123,10 +2255,14 @@ model_verbosity = "high"
             model_provider: fixture.openai_chat_completions_provider.clone(),
             approval_policy: AskForApproval::UnlessTrusted,
             sandbox_policy: SandboxPolicy::new_read_only_policy(),
+            sandbox_limits: SandboxLimits::default(),
+            budget: BudgetLimits::default(),
+            model_prices: HashMap::new(),
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             user_instructions: None,
             notify: None,
             cwd: fixture.cwd(),
+            cwd_trusted: false,
             mcp_servers: HashMap::new(),
             mcp_oauth_credentials_store_mode: Default::default(),
             model_providers: fixture.model_provider_map.clone(),
@@ -2151,7 +2287,9 @@ model_verbosity = "high"
             use_experimental_use_rmcp_client: false,
             include_view_image_tool: true,
             experimental_command_system_enabled: false,
+            agents: Agents::default(),
             active_profile: Some("gpt3".to_string()),
+            cli_overrides: Vec::new(),
             windows_wsl_setup_acknowledged: false,
             disable_paste_burst: false,
             tui_notifications: Default::default(),
@@ -2202,10 +2340,14 @@ model_verbosity = "high"
             model_provider: fixture.openai_provider.clone(),
             approval_policy: AskForApproval::OnFailure,
             sandbox_policy: SandboxPolicy::new_read_only_policy(),
+            sandbox_limits: SandboxLimits::default(),
+            budget: BudgetLimits::default(),
+            model_prices: HashMap::new(),
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             user_instructions: None,
             notify: None,
             cwd: fixture.cwd(),
+            cwd_trusted: false,
             mcp_servers: HashMap::new(),
             mcp_oauth_credentials_store_mode: Default::default(),
             model_providers: fixture.model_provider_map.clone(),
@@ -2230,7 +2372,9 @@ model_verbosity = "high"
             use_experimental_use_rmcp_client: false,
             include_view_image_tool: true,
             experimental_command_system_enabled: false,
+            agents: Agents::default(),
             active_profile: Some("zdr".to_string()),
+            cli_overrides: Vec::new(),
             windows_wsl_setup_acknowledged: false,
             disable_paste_burst: false,
             tui_notifications: Default::default(),
@@ -2267,10 +2411,14 @@ model_verbosity = "high"
             model_provider: fixture.openai_provider.clone(),
             approval_policy: AskForApproval::OnFailure,
             sandbox_policy: SandboxPolicy::new_read_only_policy(),
+            sandbox_limits: SandboxLimits::default(),
+            budget: BudgetLimits::default(),
+            model_prices: HashMap::new(),
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             user_instructions: None,
             notify: None,
             cwd: fixture.cwd(),
+            cwd_trusted: false,
             mcp_servers: HashMap::new(),
             mcp_oauth_credentials_store_mode: Default::default(),
             model_providers: fixture.model_provider_map.clone(),
@@ -2295,7 +2443,9 @@ model_verbosity = "high"
             use_experimental_use_rmcp_client: false,
             include_view_image_tool: true,
             experimental_command_system_enabled: false,
+            agents: Agents::default(),
             active_profile: Some("gpt5".to_string()),
+            cli_overrides: Vec::new(),
             windows_wsl_setup_acknowledged: false,
             disable_paste_burst: false,
             tui_notifications: Default::default(),
@@ -1,6 +1,8 @@
 use serde::Deserialize;
 use std::path::PathBuf;
 
+use crate::config_types::BudgetLimits;
+use crate::config_types::SandboxLimits;
 use crate::protocol::AskForApproval;
 use codex_protocol::config_types::ReasoningEffort;
 use codex_protocol::config_types::ReasoningSummary;
@@ -20,6 +22,12 @@ pub struct ConfigProfile {
     pub model_verbosity: Option<Verbosity>,
     pub chatgpt_base_url: Option<String>,
     pub experimental_instructions_file: Option<PathBuf>,
+    /// Resource limits for commands; fields set here override the
+    /// top-level `[sandbox_limits]` table.
+    pub sandbox_limits: Option<SandboxLimits>,
+    /// Usage ceilings; fields set here override the top-level `[budget]`
+    /// table.
+    pub budget: Option<BudgetLimits>,
 }
 
 impl From<ConfigProfile> for codex_app_server_protocol::Profile {
@@ -3,6 +3,7 @@
 // Note this file should generally be restricted to simple struct/enum
 // definitions that do not contain business logic.
 
+use crate::agents::Severity;
 use serde::Deserializer;
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -31,6 +32,12 @@ pub struct McpServerConfig {
     /// Default timeout for MCP tool calls initiated via this server.
     #[serde(default, with = "option_duration_secs")]
     pub tool_timeout_sec: Option<Duration>,
+
+    /// Let this server ask the model for completions (`sampling/createMessage`).
+    /// Each request still needs the user's approval unless the approval
+    /// policy is `never`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub allow_sampling: bool,
 }
 
 impl<'de> Deserialize<'de> for McpServerConfig {
@@ -56,6 +63,8 @@ impl<'de> Deserialize<'de> for McpServerConfig {
             startup_timeout_ms: Option<u64>,
             #[serde(default, with = "option_duration_secs")]
             tool_timeout_sec: Option<Duration>,
+            #[serde(default)]
+            allow_sampling: bool,
         }
 
         let raw = RawMcpServerConfig::deserialize(deserializer)?;
@@ -127,6 +136,7 @@ impl<'de> Deserialize<'de> for McpServerConfig {
             transport,
             startup_timeout_sec,
             tool_timeout_sec: raw.tool_timeout_sec,
+            allow_sampling: raw.allow_sampling,
         })
     }
 }
@@ -311,6 +321,122 @@ pub struct Tui {
     pub notifications: Notifications,
 }
 
+/// Settings for agent-backed commands.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Agents {
+    /// How the agent router picks an agent for a command.
+    #[serde(default)]
+    pub routing: AgentRoutingMode,
+    /// Ask the model to classify the user's intent during semantic routing.
+    /// Defaults to `false`.
+    #[serde(default)]
+    pub classify_intent: bool,
+    /// Lowest finding severity that makes a headless agent command
+    /// (`codex exec "/review"`) exit with a non-zero status. Unset means
+    /// findings never fail the run.
+    #[serde(default)]
+    pub fail_on: Option<Severity>,
+}
+
+/// Strategy the agent router uses to score agents.
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum AgentRoutingMode {
+    /// Use each agent's own keyword-based activation score.
+    #[default]
+    Keyword,
+    /// Also weigh file types, changed files, command activation hints and,
+    /// when enabled, the model's intent classification.
+    Semantic,
+}
+
+/// Per-command resource ceilings from the `[sandbox_limits]` table. Unset
+/// fields leave the corresponding resource unlimited.
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct SandboxLimits {
+    /// CPU time, in seconds, each process of a command may use.
+    pub cpu_time_secs: Option<u64>,
+    /// Memory a command may use, in MiB.
+    pub memory_mb: Option<u64>,
+    /// Number of processes and threads a command may run at once.
+    pub max_processes: Option<u64>,
+    /// Combined bytes a command may write to stdout and stderr.
+    pub max_output_bytes: Option<u64>,
+}
+
+impl SandboxLimits {
+    /// Fills the fields unset in `self` from `fallback`.
+    pub fn or(self, fallback: SandboxLimits) -> SandboxLimits {
+        SandboxLimits {
+            cpu_time_secs: self.cpu_time_secs.or(fallback.cpu_time_secs),
+            memory_mb: self.memory_mb.or(fallback.memory_mb),
+            max_processes: self.max_processes.or(fallback.max_processes),
+            max_output_bytes: self.max_output_bytes.or(fallback.max_output_bytes),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == SandboxLimits::default()
+    }
+}
+
+/// Usage ceilings from the `[budget]` table. Unset fields leave the
+/// corresponding resource unlimited.
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Default)]
+pub struct BudgetLimits {
+    /// Tokens the whole session may use.
+    pub session_tokens: Option<u64>,
+    /// Turns the session may run.
+    pub session_turns: Option<u64>,
+    /// Tool calls the whole session may make.
+    pub session_tool_calls: Option<u64>,
+    /// Seconds the session may run for, counted from its start.
+    pub session_wall_clock_secs: Option<u64>,
+    /// Estimated cost, in USD, of the whole session.
+    pub session_cost_usd: Option<f64>,
+    /// Tokens a single turn may use.
+    pub turn_tokens: Option<u64>,
+    /// Tool calls a single turn may make.
+    pub turn_tool_calls: Option<u64>,
+    /// Seconds a single turn may run for.
+    pub turn_wall_clock_secs: Option<u64>,
+    /// Estimated cost, in USD, of a single turn.
+    pub turn_cost_usd: Option<f64>,
+}
+
+impl BudgetLimits {
+    /// Fills the fields unset in `self` from `fallback`.
+    pub fn or(self, fallback: BudgetLimits) -> BudgetLimits {
+        BudgetLimits {
+            session_tokens: self.session_tokens.or(fallback.session_tokens),
+            session_turns: self.session_turns.or(fallback.session_turns),
+            session_tool_calls: self.session_tool_calls.or(fallback.session_tool_calls),
+            session_wall_clock_secs: self
+                .session_wall_clock_secs
+                .or(fallback.session_wall_clock_secs),
+            session_cost_usd: self.session_cost_usd.or(fallback.session_cost_usd),
+            turn_tokens: self.turn_tokens.or(fallback.turn_tokens),
+            turn_tool_calls: self.turn_tool_calls.or(fallback.turn_tool_calls),
+            turn_wall_clock_secs: self.turn_wall_clock_secs.or(fallback.turn_wall_clock_secs),
+            turn_cost_usd: self.turn_cost_usd.or(fallback.turn_cost_usd),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == BudgetLimits::default()
+    }
+}
+
+/// Price of a model from the `[model_prices]` table, in USD per million
+/// tokens. Used to estimate the cost counted against `[budget]`.
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct ModelPrice {
+    pub input: f64,
+    /// Price of cached input tokens. Defaults to `input`.
+    pub cached_input: Option<f64>,
+    pub output: f64,
+}
+
 #[derive(Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct SandboxWorkspaceWrite {
     #[serde(default)]
@@ -318,6 +444,8 @@ pub struct SandboxWorkspaceWrite {
     #[serde(default)]
     pub network_access: bool,
     #[serde(default)]
+    pub network_allowlist: Vec<String>,
+    #[serde(default)]
     pub exclude_tmpdir_env_var: bool,
     #[serde(default)]
     pub exclude_slash_tmp: bool,
@@ -328,6 +456,7 @@ impl From<SandboxWorkspaceWrite> for codex_app_server_protocol::SandboxSettings
         Self {
             writable_roots: sandbox_workspace_write.writable_roots,
             network_access: Some(sandbox_workspace_write.network_access),
+            network_allowlist: sandbox_workspace_write.network_allowlist,
             exclude_tmpdir_env_var: Some(sandbox_workspace_write.exclude_tmpdir_env_var),
             exclude_slash_tmp: Some(sandbox_workspace_write.exclude_slash_tmp),
         }
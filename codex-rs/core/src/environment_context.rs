@@ -27,6 +27,7 @@ pub(crate) struct EnvironmentContext {
     pub approval_policy: Option<AskForApproval>,
     pub sandbox_mode: Option<SandboxMode>,
     pub network_access: Option<NetworkAccess>,
+    pub network_allowlist: Option<Vec<String>>,
     pub writable_roots: Option<Vec<PathBuf>>,
     pub shell: Option<Shell>,
 }
@@ -59,6 +60,12 @@ impl EnvironmentContext {
                 }
                 None => None,
             },
+            network_allowlist: match &sandbox_policy {
+                Some(policy) if !policy.network_allowlist().is_empty() => {
+                    Some(policy.network_allowlist().to_vec())
+                }
+                _ => None,
+            },
             writable_roots: match sandbox_policy {
                 Some(SandboxPolicy::WorkspaceWrite { writable_roots, .. }) => {
                     if writable_roots.is_empty() {
@@ -82,6 +89,7 @@ impl EnvironmentContext {
             approval_policy,
             sandbox_mode,
             network_access,
+            network_allowlist,
             writable_roots,
             // should compare all fields except shell
             shell: _,
@@ -91,6 +99,7 @@ impl EnvironmentContext {
             && self.approval_policy == *approval_policy
             && self.sandbox_mode == *sandbox_mode
             && self.network_access == *network_access
+            && self.network_allowlist == *network_allowlist
             && self.writable_roots == *writable_roots
     }
 }
@@ -119,6 +128,7 @@ impl EnvironmentContext {
     ///   <sandbox_mode>...</sandbox_mode>
     ///   <writable_roots>...</writable_roots>
     ///   <network_access>...</network_access>
+    ///   <network_allowlist>...</network_allowlist>
     ///   <shell>...</shell>
     /// </environment_context>
     /// ```
@@ -140,6 +150,13 @@ impl EnvironmentContext {
                 "  <network_access>{network_access}</network_access>"
             ));
         }
+        if let Some(network_allowlist) = self.network_allowlist {
+            lines.push("  <network_allowlist>".to_string());
+            for host in network_allowlist {
+                lines.push(format!("    <host>{host}</host>"));
+            }
+            lines.push("  </network_allowlist>".to_string());
+        }
         if let Some(writable_roots) = self.writable_roots {
             lines.push("  <writable_roots>".to_string());
             for writable_root in writable_roots {
@@ -184,6 +201,7 @@ mod tests {
         SandboxPolicy::WorkspaceWrite {
             writable_roots: writable_roots.into_iter().map(PathBuf::from).collect(),
             network_access,
+            network_allowlist: vec![],
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
         }
@@ -2,6 +2,7 @@ use crate::exec::ExecToolCallOutput;
 use crate::token_data::KnownPlan;
 use crate::token_data::PlanType;
 use codex_protocol::ConversationId;
+use codex_protocol::protocol::ExecResourceLimit;
 use codex_protocol::protocol::RateLimitSnapshot;
 use reqwest::StatusCode;
 use serde_json;
@@ -35,6 +36,13 @@ pub enum SandboxErr {
     #[error("command timed out")]
     Timeout { output: Box<ExecToolCallOutput> },
 
+    /// Command was stopped for exceeding one of the `[sandbox_limits]`
+    #[error("command exceeded its {limit} limit")]
+    LimitExceeded {
+        limit: ExecResourceLimit,
+        output: Box<ExecToolCallOutput>,
+    },
+
     /// Command was killed by a signal
     #[error("command was killed by a signal")]
     Signal(i32),
@@ -311,6 +319,9 @@ pub fn get_error_message_ui(e: &CodexErr) -> String {
             "error: command timed out after {} ms",
             output.duration.as_millis()
         ),
+        CodexErr::Sandbox(SandboxErr::LimitExceeded { limit, .. }) => {
+            format!("error: command was killed after exceeding its {limit} limit")
+        }
         _ => e.to_string(),
     }
 }
@@ -6,6 +6,9 @@ use std::io;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::ExitStatus;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::time::Duration;
 use std::time::Instant;
 
@@ -14,7 +17,9 @@ use tokio::io::AsyncRead;
 use tokio::io::AsyncReadExt;
 use tokio::io::BufReader;
 use tokio::process::Child;
+use tokio::sync::Notify;
 
+use crate::config_types::SandboxLimits;
 use crate::error::CodexErr;
 use crate::error::Result;
 use crate::error::SandboxErr;
@@ -23,7 +28,9 @@ use crate::protocol::Event;
 use crate::protocol::EventMsg;
 use crate::protocol::ExecCommandOutputDeltaEvent;
 use crate::protocol::ExecOutputStream;
+use crate::protocol::ExecResourceLimit;
 use crate::protocol::SandboxPolicy;
+use crate::resource_limits::ResourceLimiter;
 use crate::seatbelt::spawn_command_under_seatbelt;
 use crate::spawn::StdioPolicy;
 use crate::spawn::spawn_child_async;
@@ -83,6 +90,7 @@ pub async fn process_exec_tool_call(
     params: ExecParams,
     sandbox_type: SandboxType,
     sandbox_policy: &SandboxPolicy,
+    sandbox_limits: &SandboxLimits,
     sandbox_cwd: &Path,
     codex_linux_sandbox_exe: &Option<PathBuf>,
     stdout_stream: Option<StdoutStream>,
@@ -90,10 +98,12 @@ pub async fn process_exec_tool_call(
     let start = Instant::now();
 
     let timeout_duration = params.timeout_duration();
+    // Owns the command's cgroup, if any, until the command has been reaped.
+    let limiter = ResourceLimiter::new(*sandbox_limits);
 
     let raw_output_result: std::result::Result<RawExecToolCallOutput, CodexErr> = match sandbox_type
     {
-        SandboxType::None => exec(params, sandbox_policy, stdout_stream.clone()).await,
+        SandboxType::None => exec(params, sandbox_policy, &limiter, stdout_stream.clone()).await,
         SandboxType::MacosSeatbelt => {
             let ExecParams {
                 command,
@@ -108,9 +118,10 @@ pub async fn process_exec_tool_call(
                 sandbox_cwd,
                 StdioPolicy::RedirectForShellTool,
                 env,
+                &limiter,
             )
             .await?;
-            consume_truncated_output(child, timeout_duration, stdout_stream.clone()).await
+            consume_truncated_output(child, timeout_duration, &limiter, stdout_stream.clone()).await
         }
         SandboxType::LinuxSeccomp => {
             let ExecParams {
@@ -131,10 +142,11 @@ pub async fn process_exec_tool_call(
                 sandbox_cwd,
                 StdioPolicy::RedirectForShellTool,
                 env,
+                &limiter,
             )
             .await?;
 
-            consume_truncated_output(child, timeout_duration, stdout_stream).await
+            consume_truncated_output(child, timeout_duration, &limiter, stdout_stream).await
         }
     };
     let duration = start.elapsed();
@@ -142,19 +154,30 @@ pub async fn process_exec_tool_call(
         Ok(raw_output) => {
             #[allow(unused_mut)]
             let mut timed_out = raw_output.timed_out;
+            let limit_exceeded = if raw_output.output_limit_exceeded {
+                Some(ExecResourceLimit::OutputSize)
+            } else if timed_out {
+                None
+            } else {
+                limiter.violation(raw_output.exit_status, &raw_output.stderr.text)
+            };
+
+            #[allow(unused_mut)]
+            let mut exit_code = raw_output.exit_status.code().unwrap_or(-1);
 
             #[cfg(target_family = "unix")]
             {
                 if let Some(signal) = raw_output.exit_status.signal() {
                     if signal == TIMEOUT_CODE {
                         timed_out = true;
+                    } else if limit_exceeded.is_some() {
+                        exit_code = EXIT_CODE_SIGNAL_BASE + signal;
                     } else {
                         return Err(CodexErr::Sandbox(SandboxErr::Signal(signal)));
                     }
                 }
             }
 
-            let mut exit_code = raw_output.exit_status.code().unwrap_or(-1);
             if timed_out {
                 exit_code = EXEC_TIMEOUT_EXIT_CODE;
             }
@@ -169,6 +192,7 @@ pub async fn process_exec_tool_call(
                 aggregated_output,
                 duration,
                 timed_out,
+                limit_exceeded,
             };
 
             if timed_out {
@@ -177,6 +201,13 @@ pub async fn process_exec_tool_call(
                 }));
             }
 
+            if let Some(limit) = limit_exceeded {
+                return Err(CodexErr::Sandbox(SandboxErr::LimitExceeded {
+                    limit,
+                    output: Box::new(exec_output),
+                }));
+            }
+
             if exit_code != 0 && is_likely_sandbox_denied(sandbox_type, exit_code) {
                 return Err(CodexErr::Sandbox(SandboxErr::Denied {
                     output: Box::new(exec_output),
@@ -224,6 +255,8 @@ struct RawExecToolCallOutput {
     pub stderr: StreamOutput<Vec<u8>>,
     pub aggregated_output: StreamOutput<Vec<u8>>,
     pub timed_out: bool,
+    /// The command was killed for writing more than `max_output_bytes`.
+    pub output_limit_exceeded: bool,
 }
 
 impl StreamOutput<String> {
@@ -257,11 +290,14 @@ pub struct ExecToolCallOutput {
     pub aggregated_output: StreamOutput<String>,
     pub duration: Duration,
     pub timed_out: bool,
+    /// The resource limit the command was stopped for exceeding, if any.
+    pub limit_exceeded: Option<ExecResourceLimit>,
 }
 
 async fn exec(
     params: ExecParams,
     sandbox_policy: &SandboxPolicy,
+    limiter: &ResourceLimiter,
     stdout_stream: Option<StdoutStream>,
 ) -> Result<RawExecToolCallOutput> {
     let timeout = params.timeout_duration();
@@ -284,16 +320,19 @@ async fn exec(
         sandbox_policy,
         StdioPolicy::RedirectForShellTool,
         env,
+        limiter,
     )
     .await?;
-    consume_truncated_output(child, timeout, stdout_stream).await
+    consume_truncated_output(child, timeout, limiter, stdout_stream).await
 }
 
 /// Consumes the output of a child process, truncating it so it is suitable for
-/// use as the output of a `shell` tool call. Also enforces specified timeout.
+/// use as the output of a `shell` tool call. Also enforces specified timeout
+/// and the output size limit of `limiter`.
 async fn consume_truncated_output(
     mut child: Child,
     timeout: Duration,
+    limiter: &ResourceLimiter,
     stdout_stream: Option<StdoutStream>,
 ) -> Result<RawExecToolCallOutput> {
     // Both stdout and stderr were configured with `Stdio::piped()`
@@ -312,38 +351,48 @@ async fn consume_truncated_output(
     })?;
 
     let (agg_tx, agg_rx) = async_channel::unbounded::<Vec<u8>>();
+    let output_limit = limiter.max_output_bytes().map(OutputLimit::new);
 
     let stdout_handle = tokio::spawn(read_capped(
         BufReader::new(stdout_reader),
         stdout_stream.clone(),
         false,
         Some(agg_tx.clone()),
+        output_limit.clone(),
     ));
     let stderr_handle = tokio::spawn(read_capped(
         BufReader::new(stderr_reader),
         stdout_stream.clone(),
         true,
         Some(agg_tx.clone()),
+        output_limit.clone(),
     ));
 
-    let (exit_status, timed_out) = tokio::select! {
+    let (exit_status, timed_out, output_limit_exceeded) = tokio::select! {
         result = tokio::time::timeout(timeout, child.wait()) => {
             match result {
                 Ok(status_result) => {
                     let exit_status = status_result?;
-                    (exit_status, false)
+                    (exit_status, false, false)
                 }
                 Err(_) => {
                     // timeout
                     child.start_kill()?;
+                    limiter.kill_all();
                     // Debatable whether `child.wait().await` should be called here.
-                    (synthetic_exit_status(EXIT_CODE_SIGNAL_BASE + TIMEOUT_CODE), true)
+                    (synthetic_exit_status(EXIT_CODE_SIGNAL_BASE + TIMEOUT_CODE), true, false)
                 }
             }
         }
+        _ = OutputLimit::exceeded(output_limit.as_ref()) => {
+            child.start_kill()?;
+            limiter.kill_all();
+            (synthetic_exit_status(EXIT_CODE_SIGNAL_BASE + SIGKILL_CODE), false, true)
+        }
         _ = tokio::signal::ctrl_c() => {
             child.start_kill()?;
-            (synthetic_exit_status(EXIT_CODE_SIGNAL_BASE + SIGKILL_CODE), false)
+            limiter.kill_all();
+            (synthetic_exit_status(EXIT_CODE_SIGNAL_BASE + SIGKILL_CODE), false, false)
         }
     };
 
@@ -367,14 +416,54 @@ async fn consume_truncated_output(
         stderr,
         aggregated_output,
         timed_out,
+        output_limit_exceeded,
     })
 }
 
+/// Output budget shared by the stdout and stderr readers of one command.
+#[derive(Clone)]
+struct OutputLimit {
+    max_bytes: u64,
+    written: Arc<AtomicU64>,
+    exceeded: Arc<Notify>,
+}
+
+impl OutputLimit {
+    fn new(max_bytes: u64) -> Self {
+        Self {
+            max_bytes,
+            written: Arc::new(AtomicU64::new(0)),
+            exceeded: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Records a chunk of `len` bytes and returns how many of them fit in
+    /// the budget, signalling [`Self::exceeded`] once it runs out.
+    fn admit(&self, len: usize) -> usize {
+        let before = self.written.fetch_add(len as u64, Ordering::SeqCst);
+        let fits = self.max_bytes.saturating_sub(before).min(len as u64) as usize;
+        if fits < len {
+            self.exceeded.notify_one();
+        }
+        fits
+    }
+
+    /// Resolves once the budget of `limit` has been exceeded; never
+    /// resolves without a limit.
+    async fn exceeded(limit: Option<&OutputLimit>) {
+        match limit {
+            Some(limit) => limit.exceeded.notified().await,
+            None => std::future::pending().await,
+        }
+    }
+}
+
 async fn read_capped<R: AsyncRead + Unpin + Send + 'static>(
     mut reader: R,
     stream: Option<StdoutStream>,
     is_stderr: bool,
     aggregate_tx: Option<Sender<Vec<u8>>>,
+    output_limit: Option<OutputLimit>,
 ) -> io::Result<StreamOutput<Vec<u8>>> {
     let mut buf = Vec::with_capacity(AGGREGATE_BUFFER_INITIAL_CAPACITY);
     let mut tmp = [0u8; READ_CHUNK_SIZE];
@@ -383,10 +472,18 @@ async fn read_capped<R: AsyncRead + Unpin + Send + 'static>(
     // No caps: append all bytes
 
     loop {
-        let n = reader.read(&mut tmp).await?;
-        if n == 0 {
+        let read = reader.read(&mut tmp).await?;
+        if read == 0 {
             break;
         }
+        // Past the output limit, keep draining the pipe but drop the bytes.
+        let n = match &output_limit {
+            Some(limit) => limit.admit(read),
+            None => read,
+        };
+        if n == 0 {
+            continue;
+        }
 
         if let Some(stream) = &stream
             && emitted_deltas < MAX_EXEC_OUTPUT_DELTAS_PER_CALL
@@ -0,0 +1,658 @@
+//! Layered execpolicy rules for shell commands.
+//!
+//! Commands are checked against the built-in `default.policy` of
+//! `codex-execpolicy`, then every `*.policy` file in `$CODEX_HOME/policy`,
+//! then every `*.policy` file in the project's `.codex/policy` directory.
+//! Within a directory, files are loaded in name order. Each rule decides one
+//! of:
+//!
+//! - allow: the command matched a rule and writes no files, so it runs
+//!   without asking, like the built-in list of known-safe commands;
+//! - ask: the rule sets `ask="reason"`, so the user must approve the command;
+//! - forbid: the rule sets `forbidden="reason"`, so the command is rejected.
+//!
+//! When several layers match a command, the most restrictive decision wins,
+//! so a personal or project policy can never loosen a rule that forbids a
+//! command. A command that matched a rule but may write files is left to the
+//! sandbox and approval policy, as is a command no rule matches.
+//!
+//! Rules match a command from its first word, so a restricted command can
+//! also hide behind a wrapper (`env make deploy`, `sh -c 'make deploy'`) or
+//! inside a script too complex to split into plain commands. Every run of
+//! words in the command and in any script it carries is therefore also
+//! checked, and a match for an ask or forbid rule there asks the user.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_execpolicy::ExecCall;
+use codex_execpolicy::MatchedExec;
+use codex_execpolicy::Policy;
+use codex_execpolicy::PolicyParser;
+use codex_execpolicy::get_default_policy;
+
+use crate::bash::parse_bash_lc_plain_commands;
+
+/// Directory holding `*.policy` files, both in `CODEX_HOME` and in a
+/// project's `.codex` directory.
+pub const POLICY_DIR: &str = "policy";
+
+/// Extension of execpolicy files.
+const POLICY_EXTENSION: &str = "policy";
+
+/// Name under which the built-in policy is reported.
+const BUILTIN_POLICY_SOURCE: &str = "#default";
+
+/// How many scripts nested in arguments (`sh -c '…'`) are looked into.
+const MAX_SCRIPT_DEPTH: usize = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExecPolicyDecision {
+    Allow,
+    Ask { reason: String },
+    Forbid { reason: String },
+}
+
+impl ExecPolicyDecision {
+    fn restrictiveness(&self) -> u8 {
+        match self {
+            ExecPolicyDecision::Allow => 0,
+            ExecPolicyDecision::Ask { .. } => 1,
+            ExecPolicyDecision::Forbid { .. } => 2,
+        }
+    }
+
+    fn most_restrictive(self, other: Self) -> Self {
+        if other.restrictiveness() > self.restrictiveness() {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+/// One parsed policy file.
+pub struct PolicyLayer {
+    /// Path of the file, or `#default` for the built-in policy.
+    pub source: String,
+    policy: Policy,
+}
+
+impl PolicyLayer {
+    /// Runs the `should_match` and `should_not_match` examples of every rule
+    /// and describes each one that does not behave as declared.
+    pub fn check_examples(&self) -> Vec<String> {
+        let mut failures: Vec<String> = self
+            .policy
+            .check_each_good_list_individually()
+            .into_iter()
+            .map(|check| {
+                format!(
+                    "should_match example `{}` did not match: {:?}",
+                    format_exec(&check.program, &check.args),
+                    check.error
+                )
+            })
+            .collect();
+        failures.extend(
+            self.policy
+                .check_each_bad_list_individually()
+                .into_iter()
+                .map(|check| {
+                    format!(
+                        "should_not_match example `{}` matched",
+                        format_exec(&check.program, &check.args)
+                    )
+                }),
+        );
+        failures
+    }
+
+    fn check(&self, command: &[String]) -> Option<ExecPolicyDecision> {
+        let (program, args) = command.split_first()?;
+        let exec_call = ExecCall {
+            program: program.clone(),
+            args: args.to_vec(),
+        };
+        match self.policy.check(&exec_call).ok()? {
+            MatchedExec::Match { exec } if !exec.might_write_files() => {
+                Some(ExecPolicyDecision::Allow)
+            }
+            MatchedExec::Match { .. } => None,
+            MatchedExec::Ask { reason, .. } => Some(ExecPolicyDecision::Ask { reason }),
+            MatchedExec::Forbidden { reason, .. } => Some(ExecPolicyDecision::Forbid { reason }),
+        }
+    }
+}
+
+/// A policy file that could not be read or parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyLoadError {
+    pub path: PathBuf,
+    pub message: String,
+}
+
+impl fmt::Display for PolicyLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to load exec policy {}: {}",
+            self.path.display(),
+            self.message
+        )
+    }
+}
+
+impl std::error::Error for PolicyLoadError {}
+
+/// The stack of policies consulted before running a shell command. The
+/// default value has no layers and never decides anything.
+#[derive(Default)]
+pub struct ExecPolicy {
+    layers: Vec<PolicyLayer>,
+}
+
+impl fmt::Debug for ExecPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExecPolicy")
+            .field(
+                "layers",
+                &self
+                    .layers
+                    .iter()
+                    .map(|layer| layer.source.as_str())
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl ExecPolicy {
+    /// Directories whose policy files apply to sessions in `project_root`,
+    /// in the order they are layered. Pass `None` to leave out the project's
+    /// own policies, e.g. when the project is not trusted.
+    pub fn policy_dirs(codex_home: &Path, project_root: Option<&Path>) -> Vec<PathBuf> {
+        let mut dirs = vec![codex_home.join(POLICY_DIR)];
+        if let Some(project_root) = project_root {
+            dirs.push(project_root.join(".codex").join(POLICY_DIR));
+        }
+        dirs
+    }
+
+    /// Loads the built-in policy followed by the `*.policy` files in each of
+    /// `dirs`. Files that cannot be loaded are left out and reported.
+    pub fn load(dirs: &[PathBuf]) -> (Self, Vec<PolicyLoadError>) {
+        let mut layers = Vec::new();
+        let mut errors = Vec::new();
+        match get_default_policy() {
+            Ok(policy) => layers.push(PolicyLayer {
+                source: BUILTIN_POLICY_SOURCE.to_string(),
+                policy,
+            }),
+            Err(err) => errors.push(PolicyLoadError {
+                path: PathBuf::from(BUILTIN_POLICY_SOURCE),
+                message: err.to_string(),
+            }),
+        }
+
+        for dir in dirs {
+            let files = match policy_files(dir) {
+                Ok(files) => files,
+                Err(err) => {
+                    errors.push(PolicyLoadError {
+                        path: dir.clone(),
+                        message: err.to_string(),
+                    });
+                    continue;
+                }
+            };
+            for path in files {
+                match load_policy_file(&path) {
+                    Ok(layer) => layers.push(layer),
+                    Err(err) => errors.push(err),
+                }
+            }
+        }
+
+        (Self { layers }, errors)
+    }
+
+    /// Loads exactly the given policy files, without the built-in policy.
+    pub fn load_files(paths: &[PathBuf]) -> Result<Self, PolicyLoadError> {
+        let layers = paths
+            .iter()
+            .map(|path| load_policy_file(path))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { layers })
+    }
+
+    pub fn layers(&self) -> &[PolicyLayer] {
+        &self.layers
+    }
+
+    /// Decides `command`, or returns `None` when no rule has an opinion.
+    ///
+    /// For `bash -lc` scripts made only of plain commands, each command is
+    /// checked on its own: the script is allowed only if every command is,
+    /// and asks or is forbidden if any command does. A command that is not
+    /// forbidden outright still asks when an ask or forbid rule matches any
+    /// run of its words.
+    pub fn check(&self, command: &[String]) -> Option<ExecPolicyDecision> {
+        let decision = self.check_commands(command);
+        if matches!(decision, Some(ExecPolicyDecision::Forbid { .. })) {
+            return decision;
+        }
+        match (decision, self.check_word_runs(command)) {
+            (Some(decision), Some(nested)) => Some(decision.most_restrictive(nested)),
+            (decision, nested) => nested.or(decision),
+        }
+    }
+
+    fn check_commands(&self, command: &[String]) -> Option<ExecPolicyDecision> {
+        let commands = match parse_bash_lc_plain_commands(command) {
+            Some(commands) if !commands.is_empty() => commands,
+            _ => vec![command.to_vec()],
+        };
+
+        let mut decision: Option<ExecPolicyDecision> = None;
+        let mut all_allowed = true;
+        for command in &commands {
+            match self.check_exec(command) {
+                Some(ExecPolicyDecision::Allow) => {}
+                Some(restricted) => {
+                    decision = Some(match decision {
+                        Some(decision) => decision.most_restrictive(restricted),
+                        None => restricted,
+                    });
+                }
+                None => all_allowed = false,
+            }
+        }
+        match decision {
+            Some(decision) => Some(decision),
+            None if all_allowed => Some(ExecPolicyDecision::Allow),
+            None => None,
+        }
+    }
+
+    /// Asks when an ask or forbid rule matches a run of words that a shell
+    /// could end up executing: any suffix of `command`, or of a command in a
+    /// script passed as one of its arguments.
+    fn check_word_runs(&self, command: &[String]) -> Option<ExecPolicyDecision> {
+        let mut sequences = Vec::new();
+        collect_word_sequences(command, 0, &mut sequences);
+        sequences.iter().find_map(|words| {
+            (0..words.len()).find_map(|start| match self.check_exec(&words[start..])? {
+                ExecPolicyDecision::Allow => None,
+                ExecPolicyDecision::Ask { reason } => Some(ExecPolicyDecision::Ask { reason }),
+                ExecPolicyDecision::Forbid { reason } => Some(ExecPolicyDecision::Ask {
+                    reason: format!(
+                        "`{}` may run a forbidden command: {reason}",
+                        words[start..].join(" ")
+                    ),
+                }),
+            })
+        })
+    }
+
+    fn check_exec(&self, command: &[String]) -> Option<ExecPolicyDecision> {
+        self.layers
+            .iter()
+            .filter_map(|layer| layer.check(command))
+            .reduce(ExecPolicyDecision::most_restrictive)
+    }
+}
+
+/// Adds `words` to `sequences`, followed by the word sequences of every
+/// argument that looks like a shell script.
+fn collect_word_sequences(words: &[String], depth: usize, sequences: &mut Vec<Vec<String>>) {
+    sequences.push(words.to_vec());
+    if depth >= MAX_SCRIPT_DEPTH {
+        return;
+    }
+    for word in words {
+        if word.contains(|c: char| c.is_whitespace() || ";&|()<>`$".contains(c)) {
+            for sequence in split_script(word) {
+                collect_word_sequences(&sequence, depth + 1, sequences);
+            }
+        }
+    }
+}
+
+/// Splits `script` into the word sequences a shell could run without
+/// parsing it properly: control operators, parentheses, command
+/// substitutions and newlines end a sequence, redirections are dropped along
+/// with their targets and quotes only group words.
+fn split_script(script: &str) -> Vec<Vec<String>> {
+    let mut splitter = ScriptSplitter::default();
+    let mut quote: Option<char> = None;
+    let mut chars = script.chars().peekable();
+    while let Some(c) = chars.next() {
+        let substitution = c == '`' || (c == '$' && chars.peek() == Some(&'('));
+        match (quote, c) {
+            (Some(open), c) if c == open => quote = None,
+            (Some('\''), c) => splitter.word.push(c),
+            _ if substitution => {
+                // A command substitution runs on its own, even within quotes.
+                chars.next_if_eq(&'(');
+                quote = None;
+                splitter.end_sequence();
+            }
+            (_, '\\') => {
+                splitter.in_word = true;
+                if let Some(escaped) = chars.next() {
+                    splitter.word.push(escaped);
+                }
+            }
+            (Some(_), c) => splitter.word.push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                splitter.in_word = true;
+            }
+            (None, ';' | '&' | '|' | '(' | ')' | '\n') => splitter.end_sequence(),
+            (None, '<' | '>') => splitter.start_redirect(),
+            (None, c) if c.is_whitespace() => splitter.end_word(),
+            (None, c) => {
+                splitter.word.push(c);
+                splitter.in_word = true;
+            }
+        }
+    }
+    splitter.end_sequence();
+    splitter.sequences
+}
+
+#[derive(Default)]
+struct ScriptSplitter {
+    sequences: Vec<Vec<String>>,
+    words: Vec<String>,
+    word: String,
+    in_word: bool,
+    /// The next word is the target of a redirection.
+    redirect: bool,
+}
+
+impl ScriptSplitter {
+    fn end_word(&mut self) {
+        if !self.in_word {
+            return;
+        }
+        let word = std::mem::take(&mut self.word);
+        self.in_word = false;
+        if self.redirect {
+            self.redirect = false;
+        } else {
+            self.words.push(word);
+        }
+    }
+
+    fn start_redirect(&mut self) {
+        // A file descriptor number like the `2` of `2>` belongs to the
+        // redirection.
+        if self.in_word && !self.word.is_empty() && self.word.chars().all(|c| c.is_ascii_digit()) {
+            self.word.clear();
+            self.in_word = false;
+        } else {
+            self.end_word();
+        }
+        self.redirect = true;
+    }
+
+    fn end_sequence(&mut self) {
+        self.end_word();
+        self.redirect = false;
+        if !self.words.is_empty() {
+            self.sequences.push(std::mem::take(&mut self.words));
+        }
+    }
+}
+
+fn policy_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let mut files = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext == POLICY_EXTENSION) {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn load_policy_file(path: &Path) -> Result<PolicyLayer, PolicyLoadError> {
+    let load_error = |message: String| PolicyLoadError {
+        path: path.to_path_buf(),
+        message,
+    };
+    let source = path.display().to_string();
+    let contents = fs::read_to_string(path).map_err(|err| load_error(err.to_string()))?;
+    let policy = PolicyParser::new(&source, &contents)
+        .parse()
+        .map_err(|err| load_error(err.to_string()))?;
+    Ok(PolicyLayer { source, policy })
+}
+
+fn format_exec(program: &str, args: &[String]) -> String {
+    std::iter::once(program)
+        .chain(args.iter().map(String::as_str))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn vec_str(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    fn write_policy(dir: &Path, name: &str, contents: &str) {
+        fs::create_dir_all(dir).expect("create policy dir");
+        fs::write(dir.join(name), contents).expect("write policy");
+    }
+
+    const GIT_POLICY: &str = r#"
+define_program(
+    program="git",
+    args=["status"],
+    should_match=[["status"]],
+)
+
+define_program(
+    program="git",
+    args=["push"],
+    ask="pushing publishes commits",
+    should_match=[["push"]],
+)
+"#;
+
+    #[test]
+    fn layers_decide_allow_ask_and_forbid() {
+        let codex_home = tempfile::tempdir().expect("codex home");
+        let project = tempfile::tempdir().expect("project");
+        let dirs = ExecPolicy::policy_dirs(codex_home.path(), Some(project.path()));
+        write_policy(&dirs[0], "git.policy", GIT_POLICY);
+        write_policy(
+            &dirs[1],
+            "deploy.policy",
+            r#"
+define_program(
+    program="git",
+    args=["push"],
+    forbidden="this repository is pushed by CI only",
+)
+"#,
+        );
+
+        let (policy, errors) = ExecPolicy::load(&dirs);
+        assert_eq!(errors, Vec::new());
+
+        assert_eq!(
+            policy.check(&vec_str(&["git", "status"])),
+            Some(ExecPolicyDecision::Allow)
+        );
+        // The project's forbid rule is stricter than the personal ask rule.
+        assert_eq!(
+            policy.check(&vec_str(&["git", "push"])),
+            Some(ExecPolicyDecision::Forbid {
+                reason: "this repository is pushed by CI only".to_string()
+            })
+        );
+        // Built-in rules still apply.
+        assert_eq!(
+            policy.check(&vec_str(&["ls", "-l"])),
+            Some(ExecPolicyDecision::Allow)
+        );
+        assert_eq!(policy.check(&vec_str(&["git", "commit"])), None);
+    }
+
+    #[test]
+    fn bash_scripts_are_checked_command_by_command() {
+        let codex_home = tempfile::tempdir().expect("codex home");
+        let dirs = ExecPolicy::policy_dirs(codex_home.path(), None);
+        write_policy(&dirs[0], "git.policy", GIT_POLICY);
+        let (policy, _) = ExecPolicy::load(&dirs);
+
+        assert_eq!(
+            policy.check(&vec_str(&["bash", "-lc", "git status && ls"])),
+            Some(ExecPolicyDecision::Allow)
+        );
+        assert_eq!(
+            policy.check(&vec_str(&["bash", "-lc", "git status && git push"])),
+            Some(ExecPolicyDecision::Ask {
+                reason: "pushing publishes commits".to_string()
+            })
+        );
+        assert_eq!(
+            policy.check(&vec_str(&["bash", "-lc", "git status && make"])),
+            None
+        );
+    }
+
+    #[test]
+    fn restricted_commands_behind_wrappers_and_scripts_ask() {
+        let codex_home = tempfile::tempdir().expect("codex home");
+        let dirs = ExecPolicy::policy_dirs(codex_home.path(), None);
+        write_policy(&dirs[0], "git.policy", GIT_POLICY);
+        write_policy(
+            &dirs[0],
+            "make.policy",
+            r#"
+define_program(
+    program="make",
+    args=["deploy"],
+    forbidden="deploys run from CI",
+)
+"#,
+        );
+        let (policy, _) = ExecPolicy::load(&dirs);
+        let forbidden = ExecPolicyDecision::Forbid {
+            reason: "deploys run from CI".to_string(),
+        };
+
+        assert_eq!(
+            policy.check(&vec_str(&["make", "deploy"])),
+            Some(forbidden.clone())
+        );
+        assert_eq!(
+            policy.check(&vec_str(&["bash", "-lc", "make deploy"])),
+            Some(forbidden)
+        );
+
+        for command in [
+            vec_str(&["env", "make", "deploy"]),
+            vec_str(&["env", "STAGE=prod", "make", "deploy"]),
+            vec_str(&["sh", "-c", "make deploy"]),
+            vec_str(&["bash", "-lc", "make deploy > deploy.log 2>&1"]),
+            vec_str(&["bash", "-lc", "echo \"$(make deploy)\""]),
+            vec_str(&["bash", "-lc", "(cd app && make deploy)"]),
+            vec_str(&["bash", "-lc", "sh -c 'make deploy' | tee log"]),
+        ] {
+            assert_eq!(
+                policy.check(&command),
+                Some(ExecPolicyDecision::Ask {
+                    reason: "`make deploy` may run a forbidden command: deploys run from CI"
+                        .to_string()
+                }),
+                "{command:?}"
+            );
+        }
+        assert_eq!(
+            policy.check(&vec_str(&["bash", "-lc", "cat <(git push)"])),
+            Some(ExecPolicyDecision::Ask {
+                reason: "pushing publishes commits".to_string()
+            })
+        );
+
+        // Other commands are unaffected.
+        assert_eq!(
+            policy.check(&vec_str(&["bash", "-lc", "git status > status.txt"])),
+            None
+        );
+        assert_eq!(policy.check(&vec_str(&["env", "git", "status"])), None);
+        // A quoted mention looks just like the script of `sh -c`.
+        assert_eq!(
+            policy.check(&vec_str(&["bash", "-lc", "echo 'make deploy'"])),
+            Some(ExecPolicyDecision::Ask {
+                reason: "`make deploy` may run a forbidden command: deploys run from CI"
+                    .to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn broken_policy_files_are_reported_and_skipped() {
+        let codex_home = tempfile::tempdir().expect("codex home");
+        let dirs = ExecPolicy::policy_dirs(codex_home.path(), None);
+        write_policy(&dirs[0], "broken.policy", "define_program(");
+        write_policy(&dirs[0], "git.policy", GIT_POLICY);
+        write_policy(&dirs[0], "notes.txt", "not a policy");
+
+        let (policy, errors) = ExecPolicy::load(&dirs);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, dirs[0].join("broken.policy"));
+        let sources: Vec<&str> = policy
+            .layers()
+            .iter()
+            .map(|layer| layer.source.as_str())
+            .collect();
+        let git_policy = dirs[0].join("git.policy").display().to_string();
+        assert_eq!(sources, vec![BUILTIN_POLICY_SOURCE, git_policy.as_str()]);
+    }
+
+    #[test]
+    fn check_examples_reports_failing_examples() {
+        let dir = tempfile::tempdir().expect("policy dir");
+        write_policy(
+            dir.path(),
+            "git.policy",
+            r#"
+define_program(
+    program="git",
+    args=["status"],
+    should_match=[["push"]],
+    should_not_match=[["status"]],
+)
+"#,
+        );
+        let policy =
+            ExecPolicy::load_files(&[dir.path().join("git.policy")]).expect("load policy file");
+
+        let failures = policy.layers()[0].check_examples();
+
+        assert_eq!(failures.len(), 2);
+        assert!(failures[0].starts_with("should_match example `git push` did not match"));
+        assert_eq!(failures[1], "should_not_match example `git status` matched");
+    }
+}
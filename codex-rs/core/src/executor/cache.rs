@@ -1,15 +1,39 @@
 use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::Mutex;
 
+use chrono::Utc;
+use tracing::warn;
+
+use crate::approval_rules::ApprovalRule;
+use crate::approval_rules::ApprovalRules;
+use crate::approval_rules::project_for_cwd;
+
 #[derive(Clone, Debug, Default)]
 /// Thread-safe store of user approvals so repeated commands can reuse
-/// previously granted trust.
+/// previously granted trust. Session approvals are kept in memory; project
+/// approvals are persisted as [`ApprovalRule`]s under `CODEX_HOME`.
 pub(crate) struct ApprovalCache {
     inner: Arc<Mutex<HashSet<Vec<String>>>>,
+    codex_home: Option<PathBuf>,
+    /// Rules loaded from `codex_home`; read on first use and kept until a
+    /// rule is saved or [`ApprovalCache::reload_rules`] is called.
+    rules: Arc<Mutex<Option<ApprovalRules>>>,
 }
 
 impl ApprovalCache {
+    /// Creates a cache that also consults and records the approval rules
+    /// stored in `codex_home`.
+    pub(crate) fn with_rules(codex_home: PathBuf) -> Self {
+        Self {
+            inner: Arc::default(),
+            codex_home: Some(codex_home),
+            rules: Arc::default(),
+        }
+    }
+
     pub(crate) fn insert(&self, command: Vec<String>) {
         if command.is_empty() {
             return;
@@ -22,6 +46,59 @@ impl ApprovalCache {
     pub(crate) fn snapshot(&self) -> HashSet<Vec<String>> {
         self.inner.lock().map(|g| g.clone()).unwrap_or_default()
     }
+
+    /// Returns the unexpired rule for the project of `cwd` that approves
+    /// `command`, if any.
+    pub(crate) fn project_rule(&self, cwd: &Path, command: &[String]) -> Option<ApprovalRule> {
+        let codex_home = self.codex_home.as_ref()?;
+        let mut guard = self.rules.lock().ok()?;
+        let rules = guard.get_or_insert_with(|| {
+            ApprovalRules::load(codex_home).unwrap_or_else(|err| {
+                warn!("failed to load approval rules: {err}");
+                ApprovalRules::default()
+            })
+        });
+        rules
+            .find_match(&project_for_cwd(cwd), command, Utc::now())
+            .cloned()
+    }
+
+    /// Persists an approval of exactly `command` for the project of `cwd`
+    /// and approves it for the rest of the session.
+    pub(crate) fn insert_for_project(&self, cwd: &Path, command: Vec<String>) {
+        if command.is_empty() {
+            return;
+        }
+        if let Some(codex_home) = self.codex_home.as_ref()
+            && let Some(rule) = ApprovalRule::exact(project_for_cwd(cwd), &command)
+        {
+            // Start from the file so rules saved elsewhere since the last
+            // load are kept.
+            let result = ApprovalRules::load(codex_home).and_then(|mut rules| {
+                rules.prune_expired(Utc::now());
+                rules.add(rule);
+                rules.save(codex_home)?;
+                Ok(rules)
+            });
+            match result {
+                Ok(rules) => {
+                    if let Ok(mut guard) = self.rules.lock() {
+                        *guard = Some(rules);
+                    }
+                }
+                Err(err) => warn!("failed to save approval rule: {err}"),
+            }
+        }
+        self.insert(command);
+    }
+
+    /// Drops the loaded rules so the next lookup reads them from
+    /// `codex_home` again, e.g. after a rule was revoked.
+    pub(crate) fn reload_rules(&self) {
+        if let Ok(mut guard) = self.rules.lock() {
+            *guard = None;
+        }
+    }
 }
 
 #[cfg(test)]
@@ -48,4 +125,45 @@ mod tests {
         let snap2 = cache.snapshot();
         assert_eq!(snap1, snap2);
     }
+
+    #[test]
+    fn project_approvals_persist_across_caches() {
+        let codex_home = tempfile::tempdir().expect("codex home");
+        let project = tempfile::tempdir().expect("project");
+        let cmd = vec!["make".to_string(), "deploy".to_string()];
+
+        let cache = ApprovalCache::with_rules(codex_home.path().to_path_buf());
+        assert!(cache.project_rule(project.path(), &cmd).is_none());
+        cache.insert_for_project(project.path(), cmd.clone());
+        assert!(cache.snapshot().contains(&cmd));
+
+        let fresh = ApprovalCache::with_rules(codex_home.path().to_path_buf());
+        let rule = fresh.project_rule(project.path(), &cmd).expect("rule");
+        assert_eq!(rule.pattern, "make deploy");
+        assert!(
+            ApprovalCache::default()
+                .project_rule(project.path(), &cmd)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn revoked_rules_apply_after_reload() {
+        let codex_home = tempfile::tempdir().expect("codex home");
+        let project = tempfile::tempdir().expect("project");
+        let cmd = vec!["make".to_string(), "deploy".to_string()];
+
+        let cache = ApprovalCache::with_rules(codex_home.path().to_path_buf());
+        cache.insert_for_project(project.path(), cmd.clone());
+        assert!(cache.project_rule(project.path(), &cmd).is_some());
+
+        assert!(
+            crate::approval_rules::revoke_rule(codex_home.path(), project.path(), "make deploy")
+                .expect("revoke")
+        );
+        // Rules stay cached until they are reloaded.
+        assert!(cache.project_rule(project.path(), &cmd).is_some());
+        cache.reload_rules();
+        assert!(cache.project_rule(project.path(), &cmd).is_none());
+    }
 }
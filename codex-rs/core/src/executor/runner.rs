@@ -7,6 +7,7 @@ use super::backends::ExecutionMode;
 use super::backends::backend_for_mode;
 use super::cache::ApprovalCache;
 use crate::codex::Session;
+use crate::config_types::SandboxLimits;
 use crate::error::CodexErr;
 use crate::error::SandboxErr;
 use crate::error::get_error_message_ui;
@@ -16,13 +17,17 @@ use crate::exec::SandboxType;
 use crate::exec::StdoutStream;
 use crate::exec::StreamOutput;
 use crate::exec::process_exec_tool_call;
+use crate::exec_policy::ExecPolicy;
 use crate::executor::errors::ExecError;
 use crate::executor::sandbox::select_sandbox;
 use crate::function_tool::FunctionCallError;
+use crate::network_proxy::NetworkAllowlist;
+use crate::network_proxy::NetworkProxy;
 use crate::protocol::AskForApproval;
 use crate::protocol::ReviewDecision;
 use crate::protocol::SandboxPolicy;
 use crate::shell;
+use crate::spawn::CODEX_SANDBOX_NETWORK_PROXY_PORT_ENV_VAR;
 use crate::tools::context::ExecCommandContext;
 use codex_otel::otel_event_manager::ToolDecisionSource;
 
@@ -31,6 +36,9 @@ pub(crate) struct ExecutorConfig {
     pub(crate) sandbox_policy: SandboxPolicy,
     pub(crate) sandbox_cwd: PathBuf,
     codex_linux_sandbox_exe: Option<PathBuf>,
+    sandbox_limits: SandboxLimits,
+    network_proxy: Option<Arc<NetworkProxy>>,
+    pub(crate) exec_policy: Arc<ExecPolicy>,
 }
 
 impl ExecutorConfig {
@@ -43,6 +51,9 @@ impl ExecutorConfig {
             sandbox_policy,
             sandbox_cwd,
             codex_linux_sandbox_exe,
+            sandbox_limits: SandboxLimits::default(),
+            network_proxy: None,
+            exec_policy: Arc::default(),
         }
     }
 }
@@ -62,10 +73,83 @@ impl Executor {
         }
     }
 
+    /// Persists "always allow in this project" approvals as rules under
+    /// `codex_home` and honours the rules already stored there.
+    pub(crate) fn with_approval_rules(mut self, codex_home: PathBuf) -> Self {
+        self.approval_cache = ApprovalCache::with_rules(codex_home);
+        self
+    }
+
+    /// Applies `sandbox_limits` to every command, sandboxed or not.
+    pub(crate) fn with_sandbox_limits(self, sandbox_limits: SandboxLimits) -> Self {
+        if let Ok(mut cfg) = self.config.write() {
+            cfg.sandbox_limits = sandbox_limits;
+        }
+        self
+    }
+
+    /// Routes network access of sandboxed commands through `network_proxy`
+    /// when the sandbox policy has a network allowlist.
+    pub(crate) fn with_network_proxy(self, network_proxy: Option<Arc<NetworkProxy>>) -> Self {
+        if let Ok(mut cfg) = self.config.write() {
+            cfg.network_proxy = network_proxy;
+        }
+        self
+    }
+
+    /// Consults `exec_policy` before running shell commands.
+    pub(crate) fn with_exec_policy(self, exec_policy: Arc<ExecPolicy>) -> Self {
+        if let Ok(mut cfg) = self.config.write() {
+            cfg.exec_policy = exec_policy;
+        }
+        self
+    }
+
+    /// Re-reads the project approval rules before the next lookup.
+    pub(crate) fn reload_approval_rules(&self) {
+        self.approval_cache.reload_rules();
+    }
+
+    /// The egress proxy sandboxed commands with a network allowlist go
+    /// through, once one has been started.
+    pub(crate) fn network_proxy(&self) -> Option<Arc<NetworkProxy>> {
+        self.config
+            .read()
+            .ok()
+            .and_then(|cfg| cfg.network_proxy.clone())
+    }
+
     /// Updates the sandbox policy and working directory used for future
-    /// executions without recreating the executor.
-    pub(crate) fn update_environment(&self, sandbox_policy: SandboxPolicy, sandbox_cwd: PathBuf) {
+    /// executions without recreating the executor. Starts the network proxy
+    /// if `sandbox_policy` is the first to come with a network allowlist.
+    pub(crate) async fn update_environment(
+        &self,
+        sandbox_policy: SandboxPolicy,
+        sandbox_cwd: PathBuf,
+    ) {
+        if !sandbox_policy.network_allowlist().is_empty() && self.network_proxy().is_none() {
+            match NetworkProxy::start(sandbox_policy.network_allowlist()).await {
+                Ok(proxy) => {
+                    if let Ok(mut cfg) = self.config.write()
+                        && cfg.network_proxy.is_none()
+                    {
+                        cfg.network_proxy = Some(Arc::new(proxy));
+                    }
+                }
+                Err(err) => tracing::error!(
+                    "Failed to start the sandbox network proxy; sandboxed commands will have no network access: {err:#}"
+                ),
+            }
+        }
         if let Ok(mut cfg) = self.config.write() {
+            if let Some(proxy) = &cfg.network_proxy {
+                let allowlist = NetworkAllowlist::new(sandbox_policy.network_allowlist())
+                    .unwrap_or_else(|err| {
+                        tracing::warn!("{err}; blocking all network access");
+                        NetworkAllowlist::default()
+                    });
+                proxy.set_allowlist(allowlist);
+            }
             cfg.sandbox_policy = sandbox_policy;
             cfg.sandbox_cwd = sandbox_cwd;
         }
@@ -108,7 +192,7 @@ impl Executor {
         let sandbox_decision = select_sandbox(
             &request,
             approval_policy,
-            self.approval_cache.snapshot(),
+            &self.approval_cache,
             &config,
             session,
             &context.sub_id,
@@ -116,7 +200,10 @@ impl Executor {
             &context.otel_event_manager,
         )
         .await?;
-        if sandbox_decision.record_session_approval {
+        if sandbox_decision.record_project_approval {
+            self.approval_cache
+                .insert_for_project(&config.sandbox_cwd, request.approval_command.clone());
+        } else if sandbox_decision.record_session_approval {
             self.approval_cache.insert(request.approval_command.clone());
         }
 
@@ -136,6 +223,10 @@ impl Executor {
             Err(CodexErr::Sandbox(SandboxErr::Timeout { output })) => {
                 Err(CodexErr::Sandbox(SandboxErr::Timeout { output }).into())
             }
+            // Running without the sandbox would not lift the limit.
+            Err(CodexErr::Sandbox(error @ SandboxErr::LimitExceeded { .. })) => {
+                Err(CodexErr::Sandbox(error).into())
+            }
             Err(CodexErr::Sandbox(error)) => {
                 if sandbox_decision.escalate_on_failure {
                     self.retry_without_sandbox(
@@ -190,9 +281,20 @@ impl Executor {
             ToolDecisionSource::User,
         );
         match decision {
-            ReviewDecision::Approved | ReviewDecision::ApprovedForSession => {
-                if matches!(decision, ReviewDecision::ApprovedForSession) {
-                    self.approval_cache.insert(request.approval_command.clone());
+            ReviewDecision::Approved
+            | ReviewDecision::ApprovedForSession
+            | ReviewDecision::ApprovedForProject => {
+                match decision {
+                    ReviewDecision::ApprovedForSession => {
+                        self.approval_cache.insert(request.approval_command.clone());
+                    }
+                    ReviewDecision::ApprovedForProject => {
+                        self.approval_cache.insert_for_project(
+                            &config.sandbox_cwd,
+                            request.approval_command.clone(),
+                        );
+                    }
+                    _ => {}
                 }
                 session
                     .notify_background_event(&context.sub_id, "retrying command without sandbox")
@@ -217,15 +319,25 @@ impl Executor {
 
     async fn spawn(
         &self,
-        params: ExecParams,
+        mut params: ExecParams,
         sandbox: SandboxType,
         config: &ExecutorConfig,
         stdout_stream: Option<StdoutStream>,
     ) -> Result<ExecToolCallOutput, CodexErr> {
+        // Only Codex decides which port the sandbox opens up.
+        params.env.remove(CODEX_SANDBOX_NETWORK_PROXY_PORT_ENV_VAR);
+        if sandbox != SandboxType::None
+            && !config.sandbox_policy.network_allowlist().is_empty()
+            && let Some(proxy) = &config.network_proxy
+        {
+            proxy.apply_env(&mut params.env);
+        }
+
         process_exec_tool_call(
             params,
             sandbox,
             &config.sandbox_policy,
+            &config.sandbox_limits,
             &config.sandbox_cwd,
             &config.codex_linux_sandbox_exe,
             stdout_stream,
@@ -293,12 +405,12 @@ pub(crate) fn normalize_exec_result(
             borrowed: Some(output),
             synthetic: None,
         },
-        Err(ExecError::Codex(CodexErr::Sandbox(SandboxErr::Timeout { output }))) => {
-            NormalizedExecOutput {
-                borrowed: Some(output.as_ref()),
-                synthetic: None,
-            }
-        }
+        Err(ExecError::Codex(CodexErr::Sandbox(
+            SandboxErr::Timeout { output } | SandboxErr::LimitExceeded { output, .. },
+        ))) => NormalizedExecOutput {
+            borrowed: Some(output.as_ref()),
+            synthetic: None,
+        },
         Err(err) => {
             let message = match err {
                 ExecError::Function(FunctionCallError::RespondToModel(msg)) => msg.clone(),
@@ -312,6 +424,7 @@ pub(crate) fn normalize_exec_result(
                 aggregated_output: StreamOutput::new(message),
                 duration: Duration::default(),
                 timed_out: false,
+                limit_exceeded: None,
             };
             NormalizedExecOutput {
                 borrowed: None,
@@ -338,6 +451,7 @@ mod tests {
             aggregated_output: StreamOutput::new(text.to_string()),
             duration: Duration::from_millis(123),
             timed_out: false,
+            limit_exceeded: None,
         }
     }
 
@@ -372,6 +486,7 @@ mod tests {
             aggregated_output: StreamOutput::new(String::new()),
             duration: Duration::from_millis(10),
             timed_out: false,
+            limit_exceeded: None,
         };
         let err = SandboxErr::Denied {
             output: Box::new(output),
@@ -380,6 +495,38 @@ mod tests {
         assert_eq!(message, "failed in sandbox: sandbox stderr");
     }
 
+    #[tokio::test]
+    async fn update_environment_starts_proxy_for_first_allowlist() {
+        let executor = Executor::new(ExecutorConfig::new(
+            SandboxPolicy::new_workspace_write_policy(),
+            PathBuf::from("/tmp"),
+            None,
+        ));
+        assert!(executor.network_proxy().is_none());
+
+        let with_allowlist = |host: &str| SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![],
+            network_access: false,
+            network_allowlist: vec![host.to_string()],
+            exclude_tmpdir_env_var: false,
+            exclude_slash_tmp: false,
+        };
+        executor
+            .update_environment(with_allowlist("example.com"), PathBuf::from("/tmp"))
+            .await;
+        let Some(proxy) = executor.network_proxy() else {
+            panic!("expected the proxy to start");
+        };
+
+        executor
+            .update_environment(with_allowlist("example.org"), PathBuf::from("/tmp"))
+            .await;
+        let Some(same_proxy) = executor.network_proxy() else {
+            panic!("expected the proxy to keep running");
+        };
+        assert!(Arc::ptr_eq(&proxy, &same_proxy));
+    }
+
     #[test]
     fn normalize_function_error_synthesizes_payload() {
         let err = FunctionCallError::RespondToModel("boom".to_string());
@@ -4,15 +4,20 @@ use crate::exec::SandboxType;
 use crate::executor::ExecutionMode;
 use crate::executor::ExecutionRequest;
 use crate::executor::ExecutorConfig;
+use crate::executor::cache::ApprovalCache;
 use crate::executor::errors::ExecError;
+use crate::protocol::SandboxPolicy;
 use crate::safety::SafetyCheck;
 use crate::safety::assess_command_safety;
 use crate::safety::assess_patch_safety;
+use crate::safety::get_platform_sandbox;
 use codex_otel::otel_event_manager::OtelEventManager;
 use codex_otel::otel_event_manager::ToolDecisionSource;
 use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::Event;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::ExecApprovalRuleAppliedEvent;
 use codex_protocol::protocol::ReviewDecision;
-use std::collections::HashSet;
 
 /// Sandbox placement options selected for an execution run, including whether
 /// to escalate after failures and whether approvals should persist.
@@ -20,6 +25,7 @@ pub(crate) struct SandboxDecision {
     pub(crate) initial_sandbox: SandboxType,
     pub(crate) escalate_on_failure: bool,
     pub(crate) record_session_approval: bool,
+    pub(crate) record_project_approval: bool,
 }
 
 impl SandboxDecision {
@@ -28,6 +34,7 @@ impl SandboxDecision {
             initial_sandbox: sandbox,
             escalate_on_failure,
             record_session_approval: false,
+            record_project_approval: false,
         }
     }
 
@@ -36,6 +43,7 @@ impl SandboxDecision {
             initial_sandbox: SandboxType::None,
             escalate_on_failure: false,
             record_session_approval,
+            record_project_approval: false,
         }
     }
 }
@@ -56,7 +64,7 @@ fn should_escalate_on_failure(approval: AskForApproval, sandbox: SandboxType) ->
 pub async fn select_sandbox(
     request: &ExecutionRequest,
     approval_policy: AskForApproval,
-    approval_cache: HashSet<Vec<String>>,
+    approval_cache: &ApprovalCache,
     config: &ExecutorConfig,
     session: &Session,
     sub_id: &str,
@@ -87,7 +95,7 @@ pub async fn select_sandbox(
 async fn select_shell_sandbox(
     request: &ExecutionRequest,
     approval_policy: AskForApproval,
-    approved_snapshot: HashSet<Vec<String>>,
+    approval_cache: &ApprovalCache,
     config: &ExecutorConfig,
     session: &Session,
     sub_id: &str,
@@ -104,7 +112,8 @@ async fn select_shell_sandbox(
         &command_for_safety,
         approval_policy,
         &config.sandbox_policy,
-        &approved_snapshot,
+        &config.exec_policy,
+        &approval_cache.snapshot(),
         request.params.with_escalated_permissions.unwrap_or(false),
     );
 
@@ -129,6 +138,38 @@ async fn select_shell_sandbox(
             Ok(decision)
         }
         SafetyCheck::AskUser => {
+            if let Some(rule) =
+                approval_cache.project_rule(&config.sandbox_cwd, &command_for_safety)
+            {
+                session
+                    .send_event(Event {
+                        id: sub_id.to_string(),
+                        msg: EventMsg::ExecApprovalRuleApplied(ExecApprovalRuleAppliedEvent {
+                            call_id: call_id.to_string(),
+                            command: command_for_safety,
+                            project: rule.project,
+                            pattern: rule.pattern,
+                        }),
+                    })
+                    .await;
+                otel_event_manager.tool_decision(
+                    "local_shell",
+                    call_id,
+                    ReviewDecision::ApprovedForProject,
+                    ToolDecisionSource::Config,
+                );
+                // A saved rule only stands in for the approval prompt; the
+                // command still runs in the platform sandbox.
+                let sandbox_type = match config.sandbox_policy {
+                    SandboxPolicy::DangerFullAccess => SandboxType::None,
+                    _ => get_platform_sandbox().unwrap_or(SandboxType::None),
+                };
+                return Ok(SandboxDecision::auto(
+                    sandbox_type,
+                    should_escalate_on_failure(approval_policy, sandbox_type),
+                ));
+            }
+
             let decision = session
                 .request_command_approval(
                     sub_id.to_string(),
@@ -148,6 +189,11 @@ async fn select_shell_sandbox(
             match decision {
                 ReviewDecision::Approved => Ok(SandboxDecision::user_override(false)),
                 ReviewDecision::ApprovedForSession => Ok(SandboxDecision::user_override(true)),
+                ReviewDecision::ApprovedForProject => {
+                    let mut decision = SandboxDecision::user_override(false);
+                    decision.record_project_approval = true;
+                    Ok(decision)
+                }
                 ReviewDecision::Denied | ReviewDecision::Abort => {
                     Err(ExecError::rejection("exec command rejected by user"))
                 }
@@ -190,10 +236,12 @@ fn select_apply_patch_sandbox(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::approval_rules::ApprovalRule;
+    use crate::approval_rules::ApprovalRules;
+    use crate::approval_rules::project_for_cwd;
     use crate::codex::make_session_and_context;
     use crate::exec::ExecParams;
     use crate::function_tool::FunctionCallError;
-    use crate::protocol::SandboxPolicy;
     use codex_apply_patch::ApplyPatchAction;
     use pretty_assertions::assert_eq;
 
@@ -226,7 +274,7 @@ mod tests {
         let decision = select_sandbox(
             &request,
             AskForApproval::OnRequest,
-            Default::default(),
+            &Default::default(),
             &cfg,
             &session,
             "sub",
@@ -269,7 +317,7 @@ mod tests {
         let decision = select_sandbox(
             &request,
             AskForApproval::OnRequest,
-            Default::default(),
+            &Default::default(),
             &cfg,
             &session,
             "sub",
@@ -313,7 +361,7 @@ mod tests {
         let result = select_sandbox(
             &request,
             AskForApproval::UnlessTrusted,
-            Default::default(),
+            &Default::default(),
             &cfg,
             &session,
             "sub",
@@ -352,7 +400,7 @@ mod tests {
         let decision = select_sandbox(
             &request,
             AskForApproval::OnRequest,
-            Default::default(),
+            &Default::default(),
             &cfg,
             &session,
             "sub",
@@ -389,7 +437,7 @@ mod tests {
         let decision = select_sandbox(
             &request,
             AskForApproval::OnFailure,
-            Default::default(),
+            &Default::default(),
             &cfg,
             &session,
             "sub",
@@ -402,4 +450,49 @@ mod tests {
         assert_ne!(decision.initial_sandbox, SandboxType::None);
         assert_eq!(decision.escalate_on_failure, true);
     }
+
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
+    #[tokio::test]
+    async fn select_shell_keeps_platform_sandbox_for_project_rule() {
+        let (session, ctx) = make_session_and_context();
+        let codex_home = tempfile::tempdir().expect("codex home");
+        let project = tempfile::tempdir().expect("project");
+        let command = vec!["some-unknown".to_string(), "--flag".to_string()];
+        let mut rules = ApprovalRules::default();
+        rules.add(ApprovalRule::exact(project_for_cwd(project.path()), &command).expect("rule"));
+        rules.save(codex_home.path()).expect("save rules");
+
+        let cfg = ExecutorConfig::new(SandboxPolicy::ReadOnly, project.path().to_path_buf(), None);
+        let request = ExecutionRequest {
+            params: ExecParams {
+                command: command.clone(),
+                cwd: project.path().to_path_buf(),
+                timeout_ms: None,
+                env: std::collections::HashMap::new(),
+                with_escalated_permissions: None,
+                justification: None,
+            },
+            approval_command: command,
+            mode: ExecutionMode::Shell,
+            stdout_stream: None,
+            use_shell_profile: false,
+        };
+        let otel_event_manager = ctx.client.get_otel_event_manager();
+        let decision = select_sandbox(
+            &request,
+            AskForApproval::UnlessTrusted,
+            &ApprovalCache::with_rules(codex_home.path().to_path_buf()),
+            &cfg,
+            &session,
+            "sub",
+            "call",
+            &otel_event_manager,
+        )
+        .await
+        .expect("ok");
+        // The rule skips the prompt but not the sandbox
+        assert_ne!(decision.initial_sandbox, SandboxType::None);
+        assert_eq!(decision.escalate_on_failure, true);
+        assert_eq!(decision.record_session_approval, false);
+    }
 }
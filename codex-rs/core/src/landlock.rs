@@ -1,4 +1,5 @@
 use crate::protocol::SandboxPolicy;
+use crate::resource_limits::ResourceLimiter;
 use crate::spawn::StdioPolicy;
 use crate::spawn::spawn_child_async;
 use std::collections::HashMap;
@@ -13,6 +14,7 @@ use tokio::process::Child;
 /// helper accepts a list of `--sandbox-permission`/`-s` flags mirroring the
 /// public CLI. We convert the internal [`SandboxPolicy`] representation into
 /// the equivalent CLI options.
+#[allow(clippy::too_many_arguments)]
 pub async fn spawn_command_under_linux_sandbox<P>(
     codex_linux_sandbox_exe: P,
     command: Vec<String>,
@@ -21,6 +23,7 @@ pub async fn spawn_command_under_linux_sandbox<P>(
     sandbox_policy_cwd: &Path,
     stdio_policy: StdioPolicy,
     env: HashMap<String, String>,
+    limiter: &ResourceLimiter,
 ) -> std::io::Result<Child>
 where
     P: AsRef<Path>,
@@ -35,6 +38,7 @@ where
         sandbox_policy,
         stdio_policy,
         env,
+        limiter,
     )
     .await
 }
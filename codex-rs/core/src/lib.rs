@@ -7,8 +7,10 @@
 
 pub mod agents;
 mod apply_patch;
+pub mod approval_rules;
 pub mod auth;
 pub mod bash;
+mod budget;
 mod chat_completions;
 mod client;
 mod client_common;
@@ -30,14 +32,17 @@ pub mod error;
 pub mod exec;
 mod exec_command;
 pub mod exec_env;
+pub mod exec_policy;
 pub mod executor;
 mod flags;
 pub mod git_info;
 pub mod landlock;
 mod mcp_connection_manager;
+mod mcp_server_requests;
 mod mcp_tool_call;
 mod message_history;
 mod model_provider_info;
+pub mod network_proxy;
 pub mod parse_command;
 mod truncate;
 mod unified_exec;
@@ -61,6 +66,7 @@ pub mod model_family;
 mod openai_model_info;
 mod openai_tools;
 pub mod project_doc;
+pub mod resource_limits;
 mod rollout;
 pub(crate) mod safety;
 pub mod seatbelt;
@@ -78,6 +84,11 @@ pub use rollout::find_conversation_path_by_id_str;
 pub use rollout::list::ConversationItem;
 pub use rollout::list::ConversationsPage;
 pub use rollout::list::Cursor;
+pub use rollout::search::SearchHitKind;
+pub use rollout::search::SearchSnippet;
+pub use rollout::search::SessionSearchHit;
+pub use rollout::search::SessionSearchIndex;
+pub use rollout::usage;
 mod function_tool;
 mod state;
 mod tasks;
@@ -4,14 +4,25 @@
 //! configured server (keyed by the *server name*). It offers convenience
 //! helpers to query the available tools across *all* servers and returns them
 //! in a single aggregated map using the fully-qualified tool name
-//! `"<server><MCP_TOOL_NAME_DELIMITER><tool>"` as the key.
+//! `"<server><MCP_TOOL_NAME_DELIMITER><tool>"` as the key. Resources and
+//! prompts of servers that advertise them are kept per server.
+//!
+//! Every running server is watched by a supervisor task: `list_changed`
+//! notifications refresh the cached listings, and a server that exits is
+//! restarted with exponential backoff. The health of each server is reported
+//! as a [`McpServerStatus`].
 
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::env;
 use std::ffi::OsString;
 use std::sync::Arc;
+use std::sync::PoisonError;
+use std::sync::RwLock;
+use std::sync::RwLockReadGuard;
+use std::sync::RwLockWriteGuard;
 use std::time::Duration;
+use std::time::Instant;
 
 use anyhow::Context;
 use anyhow::Result;
@@ -19,19 +30,31 @@ use anyhow::anyhow;
 use codex_mcp_client::McpClient;
 use codex_rmcp_client::OAuthCredentialsStoreMode;
 use codex_rmcp_client::RmcpClient;
+use codex_rmcp_client::ServerRequestHandler;
 use mcp_types::ClientCapabilities;
+use mcp_types::GetPromptRequestParams;
 use mcp_types::Implementation;
+use mcp_types::Prompt;
+use mcp_types::ReadResourceRequestParams;
+use mcp_types::Resource;
+use mcp_types::ServerCapabilities;
+use mcp_types::ServerNotification;
 use mcp_types::Tool;
 
 use serde_json::json;
 use sha1::Digest;
 use sha1::Sha1;
+use tokio::sync::broadcast;
+use tokio::task::AbortHandle;
 use tokio::task::JoinSet;
 use tracing::info;
 use tracing::warn;
 
 use crate::config_types::McpServerConfig;
 use crate::config_types::McpServerTransportConfig;
+use crate::mcp_server_requests::ForwardingRequestHandler;
+use crate::mcp_server_requests::McpServerRequestSender;
+use crate::protocol::McpServerStatus;
 
 /// Delimiter used to separate the server name from the tool name in a fully
 /// qualified tool name.
@@ -47,6 +70,23 @@ const DEFAULT_STARTUP_TIMEOUT: Duration = Duration::from_secs(10);
 /// Default timeout for individual tool calls.
 const DEFAULT_TOOL_TIMEOUT: Duration = Duration::from_secs(60);
 
+/// How often a supervisor checks whether its server is still alive.
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Delay before the first restart of a crashed server. Doubled after every
+/// failed attempt, up to [`RESTART_MAX_BACKOFF`].
+const RESTART_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const RESTART_MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Restarts attempted before a crashed server is left in the failed state.
+const MAX_RESTART_ATTEMPTS: u32 = 5;
+
+/// A server that stayed up at least this long gets a fresh restart budget.
+const RESTART_RESET_AFTER: Duration = Duration::from_secs(60);
+
+/// Time given to a server that failed to start to flush its stderr.
+const STDERR_FLUSH_GRACE: Duration = Duration::from_millis(100);
+
 /// Map that holds a startup error for every MCP server that could **not** be
 /// spawned successfully.
 pub type ClientStartErrors = HashMap<String, anyhow::Error>;
@@ -89,8 +129,10 @@ struct ToolInfo {
     tool: Tool,
 }
 
+#[derive(Clone)]
 struct ManagedClient {
     client: McpClientAdapter,
+    capabilities: ServerCapabilities,
     startup_timeout: Duration,
     tool_timeout: Option<Duration>,
 }
@@ -107,34 +149,69 @@ impl McpClientAdapter {
         program: OsString,
         args: Vec<OsString>,
         env: Option<HashMap<String, String>>,
-        params: mcp_types::InitializeRequestParams,
-        startup_timeout: Duration,
+        request_handler: Option<Arc<dyn ServerRequestHandler>>,
     ) -> Result<Self> {
         if use_rmcp_client {
-            let client = Arc::new(RmcpClient::new_stdio_client(program, args, env).await?);
-            client.initialize(params, Some(startup_timeout)).await?;
-            Ok(McpClientAdapter::Rmcp(client))
+            let client = RmcpClient::new_stdio_client(program, args, env).await?;
+            let client = match request_handler {
+                Some(handler) => client.with_request_handler(handler),
+                None => client,
+            };
+            Ok(McpClientAdapter::Rmcp(Arc::new(client)))
         } else {
-            let client = Arc::new(McpClient::new_stdio_client(program, args, env).await?);
-            client.initialize(params, Some(startup_timeout)).await?;
-            Ok(McpClientAdapter::Legacy(client))
+            let client = McpClient::new_stdio_client(program, args, env).await?;
+            Ok(McpClientAdapter::Legacy(Arc::new(client)))
         }
     }
 
     async fn new_streamable_http_client(
-        server_name: String,
-        url: String,
+        server_name: &str,
+        url: &str,
         bearer_token: Option<String>,
-        params: mcp_types::InitializeRequestParams,
-        startup_timeout: Duration,
         store_mode: OAuthCredentialsStoreMode,
+        request_handler: Option<Arc<dyn ServerRequestHandler>>,
     ) -> Result<Self> {
-        let client = Arc::new(
-            RmcpClient::new_streamable_http_client(&server_name, &url, bearer_token, store_mode)
-                .await?,
-        );
-        client.initialize(params, Some(startup_timeout)).await?;
-        Ok(McpClientAdapter::Rmcp(client))
+        let client =
+            RmcpClient::new_streamable_http_client(server_name, url, bearer_token, store_mode)
+                .await?;
+        let client = match request_handler {
+            Some(handler) => client.with_request_handler(handler),
+            None => client,
+        };
+        Ok(McpClientAdapter::Rmcp(Arc::new(client)))
+    }
+
+    async fn initialize(
+        &self,
+        params: mcp_types::InitializeRequestParams,
+        timeout: Duration,
+    ) -> Result<ServerCapabilities> {
+        let result = match self {
+            McpClientAdapter::Legacy(client) => client.initialize(params, Some(timeout)).await,
+            McpClientAdapter::Rmcp(client) => client.initialize(params, Some(timeout)).await,
+        }?;
+        Ok(result.capabilities)
+    }
+
+    fn subscribe_notifications(&self) -> broadcast::Receiver<ServerNotification> {
+        match self {
+            McpClientAdapter::Legacy(client) => client.subscribe_notifications(),
+            McpClientAdapter::Rmcp(client) => client.subscribe_notifications(),
+        }
+    }
+
+    async fn is_closed(&self) -> bool {
+        match self {
+            McpClientAdapter::Legacy(client) => client.is_closed(),
+            McpClientAdapter::Rmcp(client) => client.is_closed().await,
+        }
+    }
+
+    fn stderr_tail(&self) -> Vec<String> {
+        match self {
+            McpClientAdapter::Legacy(client) => client.stderr_tail(),
+            McpClientAdapter::Rmcp(client) => client.stderr_tail(),
+        }
     }
 
     async fn list_tools(
@@ -159,21 +236,246 @@ impl McpClientAdapter {
             McpClientAdapter::Rmcp(client) => client.call_tool(name, arguments, timeout).await,
         }
     }
+
+    async fn list_resources(&self, timeout: Option<Duration>) -> Result<Vec<Resource>> {
+        let result = match self {
+            McpClientAdapter::Legacy(client) => client.list_resources(None, timeout).await,
+            McpClientAdapter::Rmcp(client) => client.list_resources(None, timeout).await,
+        }?;
+        Ok(result.resources)
+    }
+
+    async fn read_resource(
+        &self,
+        uri: String,
+        timeout: Option<Duration>,
+    ) -> Result<mcp_types::ReadResourceResult> {
+        let params = ReadResourceRequestParams { uri };
+        match self {
+            McpClientAdapter::Legacy(client) => client.read_resource(params, timeout).await,
+            McpClientAdapter::Rmcp(client) => client.read_resource(params, timeout).await,
+        }
+    }
+
+    async fn list_prompts(&self, timeout: Option<Duration>) -> Result<Vec<Prompt>> {
+        let result = match self {
+            McpClientAdapter::Legacy(client) => client.list_prompts(None, timeout).await,
+            McpClientAdapter::Rmcp(client) => client.list_prompts(None, timeout).await,
+        }?;
+        Ok(result.prompts)
+    }
+
+    async fn get_prompt(
+        &self,
+        name: String,
+        arguments: Option<serde_json::Value>,
+        timeout: Option<Duration>,
+    ) -> Result<mcp_types::GetPromptResult> {
+        let params = GetPromptRequestParams { arguments, name };
+        match self {
+            McpClientAdapter::Legacy(client) => client.get_prompt(params, timeout).await,
+            McpClientAdapter::Rmcp(client) => client.get_prompt(params, timeout).await,
+        }
+    }
+}
+
+/// Why a server could not be started, along with what it wrote to stderr.
+struct StartFailure {
+    error: anyhow::Error,
+    stderr_tail: Vec<String>,
+}
+
+impl From<anyhow::Error> for StartFailure {
+    fn from(error: anyhow::Error) -> Self {
+        Self {
+            error,
+            stderr_tail: Vec::new(),
+        }
+    }
+}
+
+impl StartFailure {
+    fn status(&self) -> McpServerStatus {
+        McpServerStatus::Failed {
+            error: format!("{:#}", self.error),
+            stderr_tail: self.stderr_tail.clone(),
+        }
+    }
+}
+
+/// Settings shared by every server of a manager, kept around so servers can
+/// be restarted and reloaded later on.
+#[derive(Clone, Default)]
+struct StartOptions {
+    use_rmcp_client: bool,
+    store_mode: OAuthCredentialsStoreMode,
+
+    /// Where servers send their sampling and elicitation requests. Only the
+    /// RMCP client can receive such requests.
+    server_requests: Option<McpServerRequestSender>,
+}
+
+/// What a server listed right after it started or when it reported changes.
+/// `None` means the server does not offer the listing or failed to answer.
+struct ServerListings {
+    tools: Option<Vec<Tool>>,
+    resources: Option<Vec<Resource>>,
+    prompts: Option<Vec<Prompt>>,
+}
+
+/// Everything the manager knows about one configured server.
+struct ServerEntry {
+    config: McpServerConfig,
+    status: McpServerStatus,
+
+    /// `None` while the server is starting or after it failed.
+    client: Option<ManagedClient>,
+
+    tools: Vec<Tool>,
+    resources: Option<Vec<Resource>>,
+    prompts: Option<Vec<Prompt>>,
+
+    /// Task watching the running server, aborted when the entry goes away.
+    supervisor: Option<AbortHandle>,
+}
+
+impl ServerEntry {
+    fn starting(config: McpServerConfig) -> Self {
+        Self {
+            config,
+            status: McpServerStatus::Starting,
+            client: None,
+            tools: Vec::new(),
+            resources: None,
+            prompts: None,
+            supervisor: None,
+        }
+    }
+
+    fn install(&mut self, managed: ManagedClient, listings: ServerListings) {
+        self.status = McpServerStatus::Ready;
+        self.client = Some(managed);
+        self.tools = listings.tools.unwrap_or_default();
+        self.resources = listings.resources;
+        self.prompts = listings.prompts;
+    }
+
+    fn refresh(&mut self, listings: ServerListings) {
+        if let Some(tools) = listings.tools {
+            self.tools = tools;
+        }
+        if let Some(resources) = listings.resources {
+            self.resources = Some(resources);
+        }
+        if let Some(prompts) = listings.prompts {
+            self.prompts = Some(prompts);
+        }
+    }
+
+    fn fail(&mut self, status: McpServerStatus) {
+        self.status = status;
+        self.client = None;
+        self.tools.clear();
+        self.resources = None;
+        self.prompts = None;
+    }
+}
+
+impl Drop for ServerEntry {
+    fn drop(&mut self) {
+        if let Some(supervisor) = &self.supervisor {
+            supervisor.abort();
+        }
+    }
 }
 
-/// A thin wrapper around a set of running [`McpClient`] instances.
 #[derive(Default)]
-pub(crate) struct McpConnectionManager {
-    /// Server-name -> client instance.
+struct ManagerState {
+    /// Server name -> server.
     ///
     /// The server name originates from the keys of the `mcp_servers` map in
     /// the user configuration.
-    clients: HashMap<String, ManagedClient>,
+    servers: HashMap<String, ServerEntry>,
 
-    /// Fully qualified tool name -> tool instance.
+    /// Fully qualified tool name -> tool instance, rebuilt whenever the tools
+    /// of a server change.
     tools: HashMap<String, ToolInfo>,
 }
 
+impl ManagerState {
+    fn rebuild_tools(&mut self) {
+        // Qualify in a stable order so duplicates resolve the same way on
+        // every rebuild.
+        let mut servers: Vec<_> = self.servers.iter().collect();
+        servers.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let all_tools: Vec<ToolInfo> = servers
+            .into_iter()
+            .flat_map(|(server_name, entry)| {
+                entry.tools.iter().map(|tool| ToolInfo {
+                    server_name: server_name.clone(),
+                    tool_name: tool.name.clone(),
+                    tool: tool.clone(),
+                })
+            })
+            .collect();
+
+        info!(
+            "aggregated {} tools from {} servers",
+            all_tools.len(),
+            self.servers.len()
+        );
+
+        self.tools = qualify_tools(all_tools);
+    }
+}
+
+fn read_state(state: &RwLock<ManagerState>) -> RwLockReadGuard<'_, ManagerState> {
+    state.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn write_state(state: &RwLock<ManagerState>) -> RwLockWriteGuard<'_, ManagerState> {
+    state.write().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Apply `f` to the entry of `server_name`, provided it still runs with
+/// `config`. Returns `false` when the server was removed or reconfigured in
+/// the meantime.
+fn update_entry(
+    state: &RwLock<ManagerState>,
+    server_name: &str,
+    config: &McpServerConfig,
+    f: impl FnOnce(&mut ServerEntry),
+) -> bool {
+    let mut state = write_state(state);
+    let Some(entry) = state.servers.get_mut(server_name) else {
+        return false;
+    };
+    if entry.config != *config {
+        return false;
+    }
+    f(entry);
+    state.rebuild_tools();
+    true
+}
+
+/// A thin wrapper around a set of running [`McpClient`] instances.
+#[derive(Default)]
+pub(crate) struct McpConnectionManager {
+    /// Shared with the supervisor tasks, which update it as servers change.
+    state: Arc<RwLock<ManagerState>>,
+
+    options: StartOptions,
+}
+
+impl Drop for McpConnectionManager {
+    fn drop(&mut self) {
+        // Dropping the entries aborts the supervisors, which would otherwise
+        // keep the state and the server processes alive.
+        write_state(&self.state).servers.clear();
+    }
+}
+
 impl McpConnectionManager {
     /// Spawn a [`McpClient`] for each configured server.
     ///
@@ -187,142 +489,109 @@ impl McpConnectionManager {
         mcp_servers: HashMap<String, McpServerConfig>,
         use_rmcp_client: bool,
         store_mode: OAuthCredentialsStoreMode,
+        server_requests: McpServerRequestSender,
     ) -> Result<(Self, ClientStartErrors)> {
-        // Early exit if no servers are configured.
-        if mcp_servers.is_empty() {
-            return Ok((Self::default(), ClientStartErrors::default()));
-        }
+        let manager = Self {
+            state: Arc::default(),
+            options: StartOptions {
+                use_rmcp_client,
+                store_mode,
+                server_requests: Some(server_requests),
+            },
+        };
+        let errors = manager.reload(mcp_servers).await;
+        Ok((manager, errors))
+    }
 
-        // Launch all configured servers concurrently.
-        let mut join_set = JoinSet::new();
+    /// Bring the running servers in line with `mcp_servers`: servers that
+    /// are no longer configured are stopped, new servers and servers whose
+    /// configuration changed are (re)started, and all others keep running.
+    ///
+    /// Servers that fail to start are reported in `ClientStartErrors`.
+    pub async fn reload(&self, mcp_servers: HashMap<String, McpServerConfig>) -> ClientStartErrors {
         let mut errors = ClientStartErrors::new();
-
-        for (server_name, cfg) in mcp_servers {
-            // Validate server name before spawning
-            if !is_valid_mcp_server_name(&server_name) {
-                let error = anyhow::anyhow!(
-                    "invalid server name '{server_name}': must match pattern ^[a-zA-Z0-9_-]+$"
-                );
-                errors.insert(server_name, error);
-                continue;
+        let mut to_start = Vec::new();
+        {
+            let mut state = write_state(&self.state);
+            state
+                .servers
+                .retain(|server_name, entry| mcp_servers.get(server_name) == Some(&entry.config));
+
+            for (server_name, cfg) in mcp_servers {
+                if state.servers.contains_key(&server_name) {
+                    continue;
+                }
+                // Validate server name before spawning
+                if !is_valid_mcp_server_name(&server_name) {
+                    let error = anyhow::anyhow!(
+                        "invalid server name '{server_name}': must match pattern ^[a-zA-Z0-9_-]+$"
+                    );
+                    errors.insert(server_name, error);
+                    continue;
+                }
+                state
+                    .servers
+                    .insert(server_name.clone(), ServerEntry::starting(cfg.clone()));
+                to_start.push((server_name, cfg));
             }
+            state.rebuild_tools();
+        }
 
-            let startup_timeout = cfg.startup_timeout_sec.unwrap_or(DEFAULT_STARTUP_TIMEOUT);
-            let tool_timeout = cfg.tool_timeout_sec.unwrap_or(DEFAULT_TOOL_TIMEOUT);
-
-            let resolved_bearer_token = match &cfg.transport {
-                McpServerTransportConfig::StreamableHttp {
-                    bearer_token_env_var,
-                    ..
-                } => resolve_bearer_token(&server_name, bearer_token_env_var.as_deref()),
-                _ => Ok(None),
-            };
-
+        // Launch all servers concurrently.
+        let mut join_set = JoinSet::new();
+        for (server_name, cfg) in to_start {
+            let state = Arc::clone(&self.state);
+            let options = self.options.clone();
             join_set.spawn(async move {
-                let McpServerConfig { transport, .. } = cfg;
-                let params = mcp_types::InitializeRequestParams {
-                    capabilities: ClientCapabilities {
-                        experimental: None,
-                        roots: None,
-                        sampling: None,
-                        // https://modelcontextprotocol.io/specification/2025-06-18/client/elicitation#capabilities
-                        // indicates this should be an empty object.
-                        elicitation: Some(json!({})),
-                    },
-                    client_info: Implementation {
-                        name: "codex-mcp-client".to_owned(),
-                        version: env!("CARGO_PKG_VERSION").to_owned(),
-                        title: Some("Codex".into()),
-                        // This field is used by Codex when it is an MCP
-                        // server: it should not be used when Codex is
-                        // an MCP client.
-                        user_agent: None,
-                    },
-                    protocol_version: mcp_types::MCP_SCHEMA_VERSION.to_owned(),
-                };
-
-                let client = match transport {
-                    McpServerTransportConfig::Stdio { command, args, env } => {
-                        let command_os: OsString = command.into();
-                        let args_os: Vec<OsString> = args.into_iter().map(Into::into).collect();
-                        McpClientAdapter::new_stdio_client(
-                            use_rmcp_client,
-                            command_os,
-                            args_os,
-                            env,
-                            params,
-                            startup_timeout,
-                        )
-                        .await
-                    }
-                    McpServerTransportConfig::StreamableHttp { url, .. } => {
-                        McpClientAdapter::new_streamable_http_client(
-                            server_name.clone(),
-                            url,
-                            resolved_bearer_token.unwrap_or_default(),
-                            params,
-                            startup_timeout,
-                            store_mode,
-                        )
-                        .await
-                    }
-                }
-                .map(|c| (c, startup_timeout));
-
-                ((server_name, tool_timeout), client)
+                let res = start_server(state, server_name.clone(), cfg, options).await;
+                (server_name, res)
             });
         }
 
-        let mut clients: HashMap<String, ManagedClient> = HashMap::with_capacity(join_set.len());
-
         while let Some(res) = join_set.join_next().await {
-            let ((server_name, tool_timeout), client_res) = match res {
-                Ok(result) => result,
-                Err(e) => {
-                    warn!("Task panic when starting MCP server: {e:#}");
-                    continue;
-                }
-            };
-
-            match client_res {
-                Ok((client, startup_timeout)) => {
-                    clients.insert(
-                        server_name,
-                        ManagedClient {
-                            client,
-                            startup_timeout,
-                            tool_timeout: Some(tool_timeout),
-                        },
-                    );
-                }
-                Err(e) => {
+            match res {
+                Ok((_, Ok(()))) => {}
+                Ok((server_name, Err(e))) => {
                     errors.insert(server_name, e);
                 }
+                Err(e) => warn!("Task panic when starting MCP server: {e:#}"),
             }
         }
 
-        let all_tools = match list_all_tools(&clients).await {
-            Ok(tools) => tools,
-            Err(e) => {
-                warn!("Failed to list tools from some MCP servers: {e:#}");
-                Vec::new()
-            }
-        };
-
-        let tools = qualify_tools(all_tools);
-
-        Ok((Self { clients, tools }, errors))
+        errors
     }
 
     /// Returns a single map that contains **all** tools. Each key is the
     /// fully-qualified name for the tool.
     pub fn list_all_tools(&self) -> HashMap<String, Tool> {
-        self.tools
+        read_state(&self.state)
+            .tools
             .iter()
             .map(|(name, tool)| (name.clone(), tool.tool.clone()))
             .collect()
     }
 
+    /// Returns the health of every configured server, keyed by server name.
+    pub fn server_statuses(&self) -> HashMap<String, McpServerStatus> {
+        read_state(&self.state)
+            .servers
+            .iter()
+            .map(|(name, entry)| (name.clone(), entry.status.clone()))
+            .collect()
+    }
+
+    fn running_client(&self, server: &str) -> Result<ManagedClient> {
+        let state = read_state(&self.state);
+        let entry = state
+            .servers
+            .get(server)
+            .ok_or_else(|| anyhow!("unknown MCP server '{server}'"))?;
+        entry
+            .client
+            .clone()
+            .ok_or_else(|| anyhow!("MCP server '{server}' is not running"))
+    }
+
     /// Invoke the tool indicated by the (server, tool) pair.
     pub async fn call_tool(
         &self,
@@ -330,24 +599,83 @@ impl McpConnectionManager {
         tool: &str,
         arguments: Option<serde_json::Value>,
     ) -> Result<mcp_types::CallToolResult> {
-        let managed = self
-            .clients
-            .get(server)
-            .ok_or_else(|| anyhow!("unknown MCP server '{server}'"))?;
-        let client = managed.client.clone();
-        let timeout = managed.tool_timeout;
+        let managed = self.running_client(server)?;
 
-        client
-            .call_tool(tool.to_string(), arguments, timeout)
+        managed
+            .client
+            .call_tool(tool.to_string(), arguments, managed.tool_timeout)
             .await
             .with_context(|| format!("tool call failed for `{server}/{tool}`"))
     }
 
     pub fn parse_tool_name(&self, tool_name: &str) -> Option<(String, String)> {
-        self.tools
+        read_state(&self.state)
+            .tools
             .get(tool_name)
             .map(|tool| (tool.server_name.clone(), tool.tool_name.clone()))
     }
+
+    /// Returns the resources of every server, keyed by server name.
+    pub fn list_all_resources(&self) -> HashMap<String, Vec<Resource>> {
+        read_state(&self.state)
+            .servers
+            .iter()
+            .filter_map(|(name, entry)| Some((name.clone(), entry.resources.clone()?)))
+            .collect()
+    }
+
+    /// Whether any server offers resources.
+    pub fn has_resources(&self) -> bool {
+        read_state(&self.state).servers.values().any(|entry| {
+            entry
+                .client
+                .as_ref()
+                .is_some_and(|managed| managed.capabilities.resources.is_some())
+        })
+    }
+
+    /// Read the resource at `uri` from `server`.
+    pub async fn read_resource(
+        &self,
+        server: &str,
+        uri: &str,
+    ) -> Result<mcp_types::ReadResourceResult> {
+        let managed = self.running_client(server)?;
+        if managed.capabilities.resources.is_none() {
+            return Err(anyhow!("MCP server '{server}' does not offer resources"));
+        }
+
+        managed
+            .client
+            .read_resource(uri.to_string(), managed.tool_timeout)
+            .await
+            .with_context(|| format!("reading resource `{uri}` from `{server}` failed"))
+    }
+
+    /// Returns the prompts of every server, keyed by server name.
+    pub fn list_all_prompts(&self) -> HashMap<String, Vec<Prompt>> {
+        read_state(&self.state)
+            .servers
+            .iter()
+            .filter_map(|(name, entry)| Some((name.clone(), entry.prompts.clone()?)))
+            .collect()
+    }
+
+    /// Render the prompt `name` of `server` with the given arguments.
+    pub async fn get_prompt(
+        &self,
+        server: &str,
+        name: &str,
+        arguments: Option<serde_json::Value>,
+    ) -> Result<mcp_types::GetPromptResult> {
+        let managed = self.running_client(server)?;
+
+        managed
+            .client
+            .get_prompt(name.to_string(), arguments, managed.tool_timeout)
+            .await
+            .with_context(|| format!("getting prompt `{name}` from `{server}` failed"))
+    }
 }
 
 fn resolve_bearer_token(
@@ -377,58 +705,328 @@ fn resolve_bearer_token(
     }
 }
 
-/// Query every server for its available tools and return a single map that
-/// contains **all** tools. Each key is the fully-qualified name for the tool.
-async fn list_all_tools(clients: &HashMap<String, ManagedClient>) -> Result<Vec<ToolInfo>> {
-    let mut join_set = JoinSet::new();
+fn initialize_params(sampling: bool) -> mcp_types::InitializeRequestParams {
+    mcp_types::InitializeRequestParams {
+        capabilities: ClientCapabilities {
+            experimental: None,
+            roots: None,
+            sampling: sampling.then(|| json!({})),
+            // https://modelcontextprotocol.io/specification/2025-06-18/client/elicitation#capabilities
+            // indicates this should be an empty object.
+            elicitation: Some(json!({})),
+        },
+        client_info: Implementation {
+            name: "codex-mcp-client".to_owned(),
+            version: env!("CARGO_PKG_VERSION").to_owned(),
+            title: Some("Codex".into()),
+            // This field is used by Codex when it is an MCP server: it should
+            // not be used when Codex is an MCP client.
+            user_agent: None,
+        },
+        protocol_version: mcp_types::MCP_SCHEMA_VERSION.to_owned(),
+    }
+}
 
-    // Spawn one task per server so we can query them concurrently. This
-    // keeps the overall latency roughly at the slowest server instead of
-    // the cumulative latency.
-    for (server_name, managed_client) in clients {
-        let server_name_cloned = server_name.clone();
-        let client_clone = managed_client.client.clone();
-        let startup_timeout = managed_client.startup_timeout;
-        join_set.spawn(async move {
-            let res = client_clone.list_tools(None, Some(startup_timeout)).await;
-            (server_name_cloned, res)
-        });
+/// Spawn (or connect to) a server and run the MCP handshake.
+async fn start_client(
+    server_name: &str,
+    cfg: &McpServerConfig,
+    options: &StartOptions,
+) -> std::result::Result<ManagedClient, StartFailure> {
+    let startup_timeout = cfg.startup_timeout_sec.unwrap_or(DEFAULT_STARTUP_TIMEOUT);
+    let tool_timeout = cfg.tool_timeout_sec.unwrap_or(DEFAULT_TOOL_TIMEOUT);
+    let request_handler = options.server_requests.clone().map(|tx| {
+        Arc::new(ForwardingRequestHandler::new(
+            server_name.to_string(),
+            cfg.allow_sampling,
+            tx,
+        )) as Arc<dyn ServerRequestHandler>
+    });
+    let rmcp_client = options.use_rmcp_client
+        || matches!(
+            cfg.transport,
+            McpServerTransportConfig::StreamableHttp { .. }
+        );
+
+    let client = match &cfg.transport {
+        McpServerTransportConfig::Stdio { command, args, env } => {
+            let command_os: OsString = command.into();
+            let args_os: Vec<OsString> = args.iter().map(Into::into).collect();
+            McpClientAdapter::new_stdio_client(
+                options.use_rmcp_client,
+                command_os,
+                args_os,
+                env.clone(),
+                request_handler,
+            )
+            .await?
+        }
+        McpServerTransportConfig::StreamableHttp {
+            url,
+            bearer_token_env_var,
+        } => {
+            let bearer_token = resolve_bearer_token(server_name, bearer_token_env_var.as_deref())
+                .unwrap_or_default();
+            McpClientAdapter::new_streamable_http_client(
+                server_name,
+                url,
+                bearer_token,
+                options.store_mode,
+                request_handler,
+            )
+            .await?
+        }
+    };
+
+    match client
+        .initialize(
+            initialize_params(cfg.allow_sampling && rmcp_client),
+            startup_timeout,
+        )
+        .await
+    {
+        Ok(capabilities) => Ok(ManagedClient {
+            client,
+            capabilities,
+            startup_timeout,
+            tool_timeout: Some(tool_timeout),
+        }),
+        Err(error) => {
+            // A server that dies during the handshake usually explains why on
+            // stderr; give the reader a moment to pick that up.
+            tokio::time::sleep(STDERR_FLUSH_GRACE).await;
+            Err(StartFailure {
+                error,
+                stderr_tail: client.stderr_tail(),
+            })
+        }
+    }
+}
+
+/// Start `server_name`, whose entry is already marked as starting, and hand
+/// it to a supervisor once it runs.
+async fn start_server(
+    state: Arc<RwLock<ManagerState>>,
+    server_name: String,
+    cfg: McpServerConfig,
+    options: StartOptions,
+) -> Result<()> {
+    let managed = match start_client(&server_name, &cfg, &options).await {
+        Ok(managed) => managed,
+        Err(failure) => {
+            update_entry(&state, &server_name, &cfg, |entry| {
+                entry.fail(failure.status());
+            });
+            return Err(failure.error);
+        }
+    };
+
+    // Subscribe before listing so no change notification slips through.
+    let notifications = managed.client.subscribe_notifications();
+    let listings = list_server(&server_name, &managed).await;
+    if !update_entry(&state, &server_name, &cfg, |entry| {
+        entry.install(managed.clone(), listings);
+    }) {
+        return Ok(());
     }
 
-    let mut aggregated: Vec<ToolInfo> = Vec::with_capacity(join_set.len());
+    let supervisor = tokio::spawn(supervise_server(
+        Arc::clone(&state),
+        server_name.clone(),
+        cfg.clone(),
+        options,
+        managed,
+        notifications,
+    ));
+    if !update_entry(&state, &server_name, &cfg, |entry| {
+        entry.supervisor = Some(supervisor.abort_handle());
+    }) {
+        supervisor.abort();
+    }
+    Ok(())
+}
 
-    while let Some(join_res) = join_set.join_next().await {
-        let (server_name, list_result) = if let Ok(result) = join_res {
-            result
-        } else {
-            warn!("Task panic when listing tools for MCP server: {join_res:#?}");
-            continue;
+/// Watch a running server for as long as it is configured: refresh its
+/// listings when it reports changes and restart it with exponential backoff
+/// when it exits.
+async fn supervise_server(
+    state: Arc<RwLock<ManagerState>>,
+    server_name: String,
+    cfg: McpServerConfig,
+    options: StartOptions,
+    mut managed: ManagedClient,
+    mut notifications: broadcast::Receiver<ServerNotification>,
+) {
+    let mut attempt = 0;
+    loop {
+        let started_at = Instant::now();
+        watch_server(&state, &server_name, &cfg, &managed, &mut notifications).await;
+
+        warn!("MCP server '{server_name}' exited unexpectedly");
+        let status = McpServerStatus::Failed {
+            error: "server exited unexpectedly".to_string(),
+            stderr_tail: managed.client.stderr_tail(),
         };
+        if !update_entry(&state, &server_name, &cfg, |entry| entry.fail(status)) {
+            return;
+        }
+        if started_at.elapsed() >= RESTART_RESET_AFTER {
+            attempt = 0;
+        }
 
-        let list_result = if let Ok(result) = list_result {
-            result
-        } else {
-            warn!("Failed to list tools for MCP server '{server_name}': {list_result:#?}");
-            continue;
+        (managed, notifications) = loop {
+            if attempt >= MAX_RESTART_ATTEMPTS {
+                warn!("Giving up on MCP server '{server_name}' after {attempt} restart attempts");
+                return;
+            }
+            tokio::time::sleep(restart_backoff(attempt)).await;
+            attempt += 1;
+
+            if !update_entry(&state, &server_name, &cfg, |entry| {
+                entry.status = McpServerStatus::Starting;
+            }) {
+                return;
+            }
+            match start_client(&server_name, &cfg, &options).await {
+                Ok(restarted) => {
+                    let notifications = restarted.client.subscribe_notifications();
+                    let listings = list_server(&server_name, &restarted).await;
+                    if !update_entry(&state, &server_name, &cfg, |entry| {
+                        entry.install(restarted.clone(), listings);
+                    }) {
+                        return;
+                    }
+                    info!("Restarted MCP server '{server_name}'");
+                    break (restarted, notifications);
+                }
+                Err(failure) => {
+                    warn!(
+                        "Failed to restart MCP server '{server_name}': {:#}",
+                        failure.error
+                    );
+                    if !update_entry(&state, &server_name, &cfg, |entry| {
+                        entry.fail(failure.status());
+                    }) {
+                        return;
+                    }
+                }
+            }
         };
+    }
+}
 
-        for tool in list_result.tools {
-            let tool_info = ToolInfo {
-                server_name: server_name.clone(),
-                tool_name: tool.name.clone(),
-                tool,
-            };
-            aggregated.push(tool_info);
+/// Apply the changes `managed` reports until the server goes away.
+async fn watch_server(
+    state: &RwLock<ManagerState>,
+    server_name: &str,
+    cfg: &McpServerConfig,
+    managed: &ManagedClient,
+    notifications: &mut broadcast::Receiver<ServerNotification>,
+) {
+    let mut health_check = tokio::time::interval(HEALTH_CHECK_INTERVAL);
+    loop {
+        tokio::select! {
+            notification = notifications.recv() => {
+                let listings = match notification {
+                    Ok(ServerNotification::ToolListChangedNotification(_)) => ServerListings {
+                        tools: list_server_tools(server_name, managed).await,
+                        resources: None,
+                        prompts: None,
+                    },
+                    Ok(ServerNotification::ResourceListChangedNotification(_)) => ServerListings {
+                        tools: None,
+                        resources: list_server_resources(server_name, managed).await,
+                        prompts: None,
+                    },
+                    Ok(ServerNotification::PromptListChangedNotification(_)) => ServerListings {
+                        tools: None,
+                        resources: None,
+                        prompts: list_server_prompts(server_name, managed).await,
+                    },
+                    Ok(_) => continue,
+                    // We missed some notifications: list everything again.
+                    Err(broadcast::error::RecvError::Lagged(_)) => {
+                        list_server(server_name, managed).await
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return,
+                };
+                update_entry(state, server_name, cfg, |entry| entry.refresh(listings));
+            }
+            _ = health_check.tick() => {
+                if managed.client.is_closed().await {
+                    return;
+                }
+            }
         }
     }
+}
 
-    info!(
-        "aggregated {} tools from {} servers",
-        aggregated.len(),
-        clients.len()
+/// Delay before restart attempt number `attempt` (starting at zero).
+fn restart_backoff(attempt: u32) -> Duration {
+    RESTART_INITIAL_BACKOFF
+        .saturating_mul(2u32.saturating_pow(attempt))
+        .min(RESTART_MAX_BACKOFF)
+}
+
+/// Query a server for its tools, resources and prompts concurrently.
+async fn list_server(server_name: &str, managed: &ManagedClient) -> ServerListings {
+    let (tools, resources, prompts) = tokio::join!(
+        list_server_tools(server_name, managed),
+        list_server_resources(server_name, managed),
+        list_server_prompts(server_name, managed),
     );
+    ServerListings {
+        tools,
+        resources,
+        prompts,
+    }
+}
+
+async fn list_server_tools(server_name: &str, managed: &ManagedClient) -> Option<Vec<Tool>> {
+    match managed
+        .client
+        .list_tools(None, Some(managed.startup_timeout))
+        .await
+    {
+        Ok(result) => Some(result.tools),
+        Err(e) => {
+            warn!("Failed to list tools for MCP server '{server_name}': {e:#}");
+            None
+        }
+    }
+}
 
-    Ok(aggregated)
+async fn list_server_resources(
+    server_name: &str,
+    managed: &ManagedClient,
+) -> Option<Vec<Resource>> {
+    managed.capabilities.resources.as_ref()?;
+    match managed
+        .client
+        .list_resources(Some(managed.startup_timeout))
+        .await
+    {
+        Ok(resources) => Some(resources),
+        Err(e) => {
+            warn!("Failed to list resources for MCP server '{server_name}': {e:#}");
+            None
+        }
+    }
+}
+
+async fn list_server_prompts(server_name: &str, managed: &ManagedClient) -> Option<Vec<Prompt>> {
+    managed.capabilities.prompts.as_ref()?;
+    match managed
+        .client
+        .list_prompts(Some(managed.startup_timeout))
+        .await
+    {
+        Ok(prompts) => Some(prompts),
+        Err(e) => {
+            warn!("Failed to list prompts for MCP server '{server_name}': {e:#}");
+            None
+        }
+    }
 }
 
 fn is_valid_mcp_server_name(server_name: &str) -> bool {
@@ -462,6 +1060,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn restart_backoff_doubles_up_to_the_cap() {
+        assert_eq!(restart_backoff(0), Duration::from_secs(1));
+        assert_eq!(restart_backoff(1), Duration::from_secs(2));
+        assert_eq!(restart_backoff(4), Duration::from_secs(16));
+        assert_eq!(restart_backoff(5), RESTART_MAX_BACKOFF);
+        assert_eq!(restart_backoff(u32::MAX), RESTART_MAX_BACKOFF);
+    }
+
     #[test]
     fn test_qualify_tools_short_non_duplicated_names() {
         let tools = vec![
@@ -0,0 +1,439 @@
+//! Requests that MCP servers send back to Codex: sampling the model
+//! (`sampling/createMessage`) and asking the user for input
+//! (`elicitation/create`).
+//!
+//! Every server started through the RMCP client gets a
+//! [`ForwardingRequestHandler`] that queues its requests for the session. The
+//! submission loop answers them with [`handle_mcp_server_request`], so
+//! sampling uses the model of the current turn context.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use anyhow::anyhow;
+use anyhow::bail;
+use async_trait::async_trait;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_rmcp_client::ServerRequestHandler;
+use mcp_types::CreateMessageRequestParams;
+use mcp_types::CreateMessageResult;
+use mcp_types::CreateMessageResultContent;
+use mcp_types::ElicitRequestParams;
+use mcp_types::ElicitResult;
+use mcp_types::Role;
+use mcp_types::SamplingMessage;
+use mcp_types::SamplingMessageContent;
+use mcp_types::TextContent;
+use tokio::sync::mpsc;
+use tokio::sync::oneshot;
+
+use crate::agents::runtime::collect_response_with_usage;
+use crate::client_common::Prompt;
+use crate::codex::INITIAL_SUBMIT_ID;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::protocol::AskForApproval;
+use crate::protocol::ElicitationAction;
+use crate::protocol::ElicitationRequestEvent;
+use crate::protocol::McpSamplingRequestEvent;
+use crate::protocol::ReviewDecision;
+
+/// Sent ahead of the messages of every sampling request.
+const SAMPLING_PREAMBLE: &str =
+    "You are answering a request from a tool. Reply to the last message.";
+
+/// Most output tokens a sampling request may ask for, whatever the model
+/// allows.
+const MAX_SAMPLING_OUTPUT_TOKENS: u64 = 16_384;
+
+/// A request an MCP server sent back to Codex, waiting for the session to
+/// answer it.
+pub(crate) enum McpServerRequest {
+    CreateMessage {
+        server: String,
+        params: CreateMessageRequestParams,
+        respond_to: oneshot::Sender<Result<CreateMessageResult>>,
+    },
+    Elicit {
+        server: String,
+        params: ElicitRequestParams,
+        respond_to: oneshot::Sender<Result<ElicitResult>>,
+    },
+}
+
+pub(crate) type McpServerRequestSender = mpsc::UnboundedSender<McpServerRequest>;
+pub(crate) type McpServerRequestReceiver = mpsc::UnboundedReceiver<McpServerRequest>;
+
+/// Queues the requests of one server for the session.
+pub(crate) struct ForwardingRequestHandler {
+    server: String,
+    allow_sampling: bool,
+    tx: McpServerRequestSender,
+}
+
+impl ForwardingRequestHandler {
+    pub(crate) fn new(server: String, allow_sampling: bool, tx: McpServerRequestSender) -> Self {
+        Self {
+            server,
+            allow_sampling,
+            tx,
+        }
+    }
+
+    async fn forward<T>(
+        &self,
+        request: impl FnOnce(oneshot::Sender<Result<T>>) -> McpServerRequest,
+    ) -> Result<T> {
+        let (respond_to, response) = oneshot::channel();
+        self.tx
+            .send(request(respond_to))
+            .map_err(|_| anyhow!("the Codex session has ended"))?;
+        response
+            .await
+            .map_err(|_| anyhow!("the Codex session has ended"))?
+    }
+}
+
+#[async_trait]
+impl ServerRequestHandler for ForwardingRequestHandler {
+    async fn create_message(
+        &self,
+        params: CreateMessageRequestParams,
+    ) -> Result<CreateMessageResult> {
+        if !self.allow_sampling {
+            bail!(
+                "sampling is not enabled for MCP server '{}'; set `allow_sampling = true` in its config",
+                self.server
+            );
+        }
+        self.forward(|respond_to| McpServerRequest::CreateMessage {
+            server: self.server.clone(),
+            params,
+            respond_to,
+        })
+        .await
+    }
+
+    async fn elicit(&self, params: ElicitRequestParams) -> Result<ElicitResult> {
+        self.forward(|respond_to| McpServerRequest::Elicit {
+            server: self.server.clone(),
+            params,
+            respond_to,
+        })
+        .await
+    }
+}
+
+/// Answer `request` on behalf of the session. A server that gave up waiting
+/// is not an error.
+pub(crate) async fn handle_mcp_server_request(
+    sess: Arc<Session>,
+    turn_context: Arc<TurnContext>,
+    request: McpServerRequest,
+) {
+    match request {
+        McpServerRequest::CreateMessage {
+            server,
+            params,
+            respond_to,
+        } => {
+            let result = create_message(&sess, &turn_context, server, params).await;
+            let _ = respond_to.send(result);
+        }
+        McpServerRequest::Elicit {
+            server,
+            params,
+            respond_to,
+        } => {
+            let result = elicit(&sess, &turn_context, server, params).await;
+            let _ = respond_to.send(result);
+        }
+    }
+}
+
+async fn create_message(
+    sess: &Session,
+    turn_context: &TurnContext,
+    server: String,
+    params: CreateMessageRequestParams,
+) -> Result<CreateMessageResult> {
+    sess.check_model_request_budget()
+        .await
+        .map_err(|exceeded| anyhow!("sampling refused: {exceeded}"))?;
+
+    if !sess.is_mcp_sampling_approved_for_session(&server).await {
+        // With `never` there is nobody to ask, and `allow_sampling` alone
+        // must not let a server spend the user's model budget unseen.
+        if turn_context.approval_policy == AskForApproval::Never {
+            bail!("sampling needs approval, but the approval policy is `never`");
+        }
+        let decision = sess
+            .request_mcp_sampling_approval(McpSamplingRequestEvent {
+                server: server.clone(),
+                system_prompt: params.system_prompt.clone(),
+                messages: params.messages.clone(),
+            })
+            .await;
+        match decision {
+            ReviewDecision::Approved => {}
+            ReviewDecision::ApprovedForSession | ReviewDecision::ApprovedForProject => {
+                sess.approve_mcp_sampling_for_session(server.clone()).await;
+            }
+            ReviewDecision::Denied | ReviewDecision::Abort => {
+                bail!("the user declined the sampling request");
+            }
+        }
+    }
+
+    let mut input = vec![sampling_preamble(&server, params.system_prompt.as_deref())];
+    input.extend(sampling_input(&params.messages)?);
+    let prompt = Prompt {
+        input,
+        tools: Vec::new(),
+        parallel_tool_calls: false,
+        base_instructions_override: turn_context.base_instructions.clone(),
+        output_schema: None,
+        max_output_tokens: Some(sampling_max_output_tokens(
+            params.max_tokens,
+            turn_context.client.get_model_max_output_tokens(),
+        )),
+    };
+    let (items, token_usage) = collect_response_with_usage(&turn_context.client, &prompt).await?;
+    sess.update_token_usage_info(INITIAL_SUBMIT_ID, turn_context, token_usage.as_ref())
+        .await;
+    let text = items
+        .into_iter()
+        .filter_map(|item| match item {
+            ResponseItem::Message { role, content, .. } if role == "assistant" => Some(content),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|content| match content {
+            ContentItem::OutputText { text } => Some(text),
+            _ => None,
+        })
+        .collect::<String>();
+
+    Ok(CreateMessageResult {
+        content: CreateMessageResultContent::TextContent(TextContent {
+            annotations: None,
+            text,
+            r#type: "text".to_string(),
+        }),
+        model: turn_context.client.get_model(),
+        role: Role::Assistant,
+        stop_reason: Some("endTurn".to_string()),
+    })
+}
+
+async fn elicit(
+    sess: &Session,
+    turn_context: &TurnContext,
+    server: String,
+    params: ElicitRequestParams,
+) -> Result<ElicitResult> {
+    if turn_context.approval_policy == AskForApproval::Never {
+        return Ok(ElicitResult {
+            action: ElicitationAction::Decline.to_string(),
+            content: None,
+        });
+    }
+
+    let (action, content) = sess
+        .request_elicitation(ElicitationRequestEvent {
+            server,
+            message: params.message,
+            requested_schema: params.requested_schema,
+        })
+        .await;
+    Ok(ElicitResult {
+        action: action.to_string(),
+        content: content.filter(|_| action == ElicitationAction::Accept),
+    })
+}
+
+/// The first message of a sampling request. The server's system prompt goes
+/// here, quoted as the server's, so it cannot replace Codex's instructions.
+fn sampling_preamble(server: &str, system_prompt: Option<&str>) -> ResponseItem {
+    let text = match system_prompt {
+        Some(system_prompt) => format!(
+            "{SAMPLING_PREAMBLE}\n\nThe MCP server `{server}` gave these instructions for its request:\n{system_prompt}"
+        ),
+        None => SAMPLING_PREAMBLE.to_string(),
+    };
+    ResponseItem::Message {
+        id: None,
+        role: "user".to_string(),
+        content: vec![ContentItem::InputText { text }],
+    }
+}
+
+/// Clamp the `maxTokens` a server asked for to what the model and
+/// [`MAX_SAMPLING_OUTPUT_TOKENS`] allow.
+fn sampling_max_output_tokens(requested: i64, model_limit: Option<u64>) -> u64 {
+    let limit = model_limit
+        .map_or(MAX_SAMPLING_OUTPUT_TOKENS, |limit| {
+            limit.min(MAX_SAMPLING_OUTPUT_TOKENS)
+        })
+        .max(1);
+    u64::try_from(requested).unwrap_or(0).clamp(1, limit)
+}
+
+/// Convert the messages of a sampling request into model input.
+fn sampling_input(messages: &[SamplingMessage]) -> Result<Vec<ResponseItem>> {
+    messages
+        .iter()
+        .map(|message| {
+            let (role, content) = match (&message.role, &message.content) {
+                (Role::User, SamplingMessageContent::TextContent(text)) => (
+                    "user",
+                    ContentItem::InputText {
+                        text: text.text.clone(),
+                    },
+                ),
+                (Role::Assistant, SamplingMessageContent::TextContent(text)) => (
+                    "assistant",
+                    ContentItem::OutputText {
+                        text: text.text.clone(),
+                    },
+                ),
+                (Role::User, SamplingMessageContent::ImageContent(image)) => (
+                    "user",
+                    ContentItem::InputImage {
+                        image_url: format!("data:{};base64,{}", image.mime_type, image.data),
+                    },
+                ),
+                (Role::Assistant, SamplingMessageContent::ImageContent(_)) => {
+                    bail!("images are only supported in user messages")
+                }
+                (_, SamplingMessageContent::AudioContent(_)) => {
+                    bail!("audio content is not supported")
+                }
+            };
+            Ok(ResponseItem::Message {
+                id: None,
+                role: role.to_string(),
+                content: vec![content],
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcp_types::AudioContent;
+    use mcp_types::ImageContent;
+    use pretty_assertions::assert_eq;
+
+    fn text(role: Role, text: &str) -> SamplingMessage {
+        SamplingMessage {
+            content: SamplingMessageContent::TextContent(TextContent {
+                annotations: None,
+                text: text.to_string(),
+                r#type: "text".to_string(),
+            }),
+            role,
+        }
+    }
+
+    #[test]
+    fn sampling_input_maps_roles_and_content() {
+        let messages = vec![
+            text(Role::User, "What is 2 + 2?"),
+            text(Role::Assistant, "4"),
+            SamplingMessage {
+                content: SamplingMessageContent::ImageContent(ImageContent {
+                    annotations: None,
+                    data: "AAAA".to_string(),
+                    mime_type: "image/png".to_string(),
+                    r#type: "image".to_string(),
+                }),
+                role: Role::User,
+            },
+        ];
+
+        let input = sampling_input(&messages).expect("supported messages");
+
+        assert_eq!(
+            input,
+            vec![
+                ResponseItem::Message {
+                    id: None,
+                    role: "user".to_string(),
+                    content: vec![ContentItem::InputText {
+                        text: "What is 2 + 2?".to_string(),
+                    }],
+                },
+                ResponseItem::Message {
+                    id: None,
+                    role: "assistant".to_string(),
+                    content: vec![ContentItem::OutputText {
+                        text: "4".to_string(),
+                    }],
+                },
+                ResponseItem::Message {
+                    id: None,
+                    role: "user".to_string(),
+                    content: vec![ContentItem::InputImage {
+                        image_url: "data:image/png;base64,AAAA".to_string(),
+                    }],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn sampling_input_rejects_audio() {
+        let messages = vec![SamplingMessage {
+            content: SamplingMessageContent::AudioContent(AudioContent {
+                annotations: None,
+                data: "AAAA".to_string(),
+                mime_type: "audio/wav".to_string(),
+                r#type: "audio".to_string(),
+            }),
+            role: Role::User,
+        }];
+
+        assert!(sampling_input(&messages).is_err());
+    }
+
+    #[test]
+    fn sampling_preamble_quotes_the_system_prompt() {
+        assert_eq!(
+            sampling_preamble("docs", Some("Answer in one word.")),
+            ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: format!(
+                        "{SAMPLING_PREAMBLE}\n\nThe MCP server `docs` gave these instructions for its request:\nAnswer in one word."
+                    ),
+                }],
+            }
+        );
+        assert_eq!(
+            sampling_preamble("docs", None),
+            ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: SAMPLING_PREAMBLE.to_string(),
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn sampling_max_output_tokens_is_clamped() {
+        assert_eq!(sampling_max_output_tokens(100, Some(128_000)), 100);
+        assert_eq!(sampling_max_output_tokens(1_000_000, Some(4_096)), 4_096);
+        assert_eq!(
+            sampling_max_output_tokens(1_000_000, None),
+            MAX_SAMPLING_OUTPUT_TOKENS
+        );
+        assert_eq!(sampling_max_output_tokens(-5, Some(4_096)), 1);
+        assert_eq!(sampling_max_output_tokens(0, Some(0)), 1);
+    }
+}
@@ -0,0 +1,556 @@
+//! Filtering egress proxy for sandboxed commands.
+//!
+//! When a `workspace-write` sandbox denies direct network access but lists
+//! hosts in `network_allowlist`, Codex starts this proxy on a loopback port and
+//! points sandboxed commands at it through the usual `*_PROXY` environment
+//! variables. The sandbox only lets commands open TCP connections to the proxy
+//! port, and the proxy only forwards connections to allowlisted hosts.
+//!
+//! Both `CONNECT` tunnels (used for HTTPS) and plain `http://` requests are
+//! supported. Refused connections are answered with `403 Forbidden` and
+//! reported to subscribers as [`BlockedConnection`]s.
+
+use std::collections::HashMap;
+use std::io;
+use std::net::Ipv4Addr;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::sync::RwLock;
+
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpListener;
+use tokio::net::TcpStream;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::debug;
+use tracing::warn;
+
+use crate::spawn::CODEX_SANDBOX_NETWORK_PROXY_PORT_ENV_VAR;
+
+/// Upper bound on the size of a request line plus headers.
+const MAX_REQUEST_HEAD_BYTES: usize = 64 * 1024;
+
+/// Capacity of the channel reporting blocked connections. Slow subscribers
+/// miss older reports rather than stalling the proxy.
+const BLOCKED_CHANNEL_CAPACITY: usize = 64;
+
+/// Proxy variables set for sandboxed commands, in both the upper- and
+/// lower-case spellings different tools look for.
+const PROXY_ENV_VARS: [&str; 6] = [
+    "HTTP_PROXY",
+    "HTTPS_PROXY",
+    "ALL_PROXY",
+    "http_proxy",
+    "https_proxy",
+    "all_proxy",
+];
+
+/// Hosts that would bypass the proxy; removed so every request goes through it.
+const NO_PROXY_ENV_VARS: [&str; 2] = ["NO_PROXY", "no_proxy"];
+
+/// Parsed `network_allowlist` entries.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NetworkAllowlist {
+    rules: Vec<HostRule>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct HostRule {
+    host: HostPattern,
+    /// `None` allows every port.
+    port: Option<u16>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum HostPattern {
+    Exact(String),
+    /// `*.example.com`, stored as `.example.com`.
+    Subdomain(String),
+}
+
+impl NetworkAllowlist {
+    /// Parses entries of the form `host`, `host:port`, `*.domain`, or
+    /// `*.domain:port`. IPv6 addresses must be bracketed (`[::1]:8080`).
+    pub fn new(entries: &[String]) -> Result<Self, String> {
+        let rules = entries
+            .iter()
+            .map(|entry| {
+                parse_rule(entry)
+                    .ok_or_else(|| format!("invalid network_allowlist entry `{entry}`"))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { rules })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub fn allows(&self, host: &str, port: u16) -> bool {
+        let host = normalize_host(host);
+        self.rules.iter().any(|rule| {
+            let host_matches = match &rule.host {
+                HostPattern::Exact(exact) => *exact == host,
+                HostPattern::Subdomain(suffix) => host.ends_with(suffix.as_str()),
+            };
+            host_matches && rule.port.is_none_or(|allowed| allowed == port)
+        })
+    }
+}
+
+fn parse_rule(entry: &str) -> Option<HostRule> {
+    let entry = entry.trim();
+    let (host, port) = if let Some(rest) = entry.strip_prefix('[') {
+        let (host, after) = rest.split_once(']')?;
+        let port = match after {
+            "" => None,
+            after => Some(after.strip_prefix(':')?.parse().ok()?),
+        };
+        (host, port)
+    } else {
+        match entry.split_once(':') {
+            Some((host, port)) => (host, Some(port.parse().ok()?)),
+            None => (entry, None),
+        }
+    };
+
+    let host = normalize_host(host);
+    let host = match host.strip_prefix("*.") {
+        Some(domain) if !domain.is_empty() && !domain.contains('*') => {
+            HostPattern::Subdomain(format!(".{domain}"))
+        }
+        Some(_) => return None,
+        None if host.is_empty() || host.contains('*') => return None,
+        None => HostPattern::Exact(host),
+    };
+    Some(HostRule { host, port })
+}
+
+fn normalize_host(host: &str) -> String {
+    host.trim_start_matches('[')
+        .trim_end_matches(']')
+        .trim_end_matches('.')
+        .to_ascii_lowercase()
+}
+
+/// A connection the proxy refused because its destination is not allowlisted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockedConnection {
+    pub host: String,
+    pub port: u16,
+}
+
+/// A running egress proxy. The listener is shut down when this is dropped.
+#[derive(Debug)]
+pub struct NetworkProxy {
+    addr: SocketAddr,
+    allowlist: Arc<RwLock<NetworkAllowlist>>,
+    blocked_tx: broadcast::Sender<BlockedConnection>,
+    task: JoinHandle<()>,
+}
+
+impl NetworkProxy {
+    /// Starts a proxy on an ephemeral loopback port that forwards only to the
+    /// hosts in `allowlist`.
+    pub async fn start(allowlist: &[String]) -> io::Result<Self> {
+        let allowlist = NetworkAllowlist::new(allowlist)
+            .map_err(|message| io::Error::new(io::ErrorKind::InvalidInput, message))?;
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await?;
+        let addr = listener.local_addr()?;
+        let allowlist = Arc::new(RwLock::new(allowlist));
+        let (blocked_tx, _) = broadcast::channel(BLOCKED_CHANNEL_CAPACITY);
+
+        let task = tokio::spawn(accept_loop(
+            listener,
+            Arc::clone(&allowlist),
+            blocked_tx.clone(),
+        ));
+
+        Ok(Self {
+            addr,
+            allowlist,
+            blocked_tx,
+            task,
+        })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.addr.port()
+    }
+
+    /// Replaces the allowlist, e.g. after the sandbox policy changed.
+    pub fn set_allowlist(&self, allowlist: NetworkAllowlist) {
+        if let Ok(mut guard) = self.allowlist.write() {
+            *guard = allowlist;
+        }
+    }
+
+    /// Subscribes to connections refused from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<BlockedConnection> {
+        self.blocked_tx.subscribe()
+    }
+
+    /// Points a command's environment at this proxy.
+    pub fn apply_env(&self, env: &mut HashMap<String, String>) {
+        let url = format!("http://{}", self.addr);
+        for var in PROXY_ENV_VARS {
+            env.insert(var.to_string(), url.clone());
+        }
+        for var in NO_PROXY_ENV_VARS {
+            env.remove(var);
+        }
+        env.insert(
+            CODEX_SANDBOX_NETWORK_PROXY_PORT_ENV_VAR.to_string(),
+            self.port().to_string(),
+        );
+    }
+}
+
+impl Drop for NetworkProxy {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn accept_loop(
+    listener: TcpListener,
+    allowlist: Arc<RwLock<NetworkAllowlist>>,
+    blocked_tx: broadcast::Sender<BlockedConnection>,
+) {
+    loop {
+        let client = match listener.accept().await {
+            Ok((client, _)) => client,
+            Err(err) => {
+                warn!("network proxy failed to accept a connection: {err}");
+                continue;
+            }
+        };
+        let allowlist = Arc::clone(&allowlist);
+        let blocked_tx = blocked_tx.clone();
+        tokio::spawn(async move {
+            if let Err(err) = handle_client(client, &allowlist, &blocked_tx).await {
+                debug!("network proxy connection ended with an error: {err}");
+            }
+        });
+    }
+}
+
+async fn handle_client(
+    mut client: TcpStream,
+    allowlist: &RwLock<NetworkAllowlist>,
+    blocked_tx: &broadcast::Sender<BlockedConnection>,
+) -> io::Result<()> {
+    let (head, body_prefix) = read_request_head(&mut client).await?;
+    let Some(request) = ProxyRequest::parse(&head) else {
+        return respond(&mut client, "400 Bad Request", "malformed proxy request").await;
+    };
+
+    let allowed = allowlist
+        .read()
+        .map(|allowlist| allowlist.allows(&request.host, request.port))
+        .unwrap_or(false);
+    if !allowed {
+        // Nobody listening is fine; the client still gets the 403.
+        let _ = blocked_tx.send(BlockedConnection {
+            host: request.host.clone(),
+            port: request.port,
+        });
+        let message = format!(
+            "{}:{} is not in the Codex sandbox network allowlist",
+            request.host, request.port
+        );
+        return respond(&mut client, "403 Forbidden", &message).await;
+    }
+
+    let mut upstream = match TcpStream::connect((request.host.as_str(), request.port)).await {
+        Ok(upstream) => upstream,
+        Err(err) => {
+            let message = format!(
+                "failed to connect to {}:{}: {err}",
+                request.host, request.port
+            );
+            return respond(&mut client, "502 Bad Gateway", &message).await;
+        }
+    };
+
+    match &request.forward_head {
+        None => {
+            client
+                .write_all(b"HTTP/1.1 200 Connection Established\r\n\r\n")
+                .await?;
+        }
+        Some(head) => upstream.write_all(head.as_bytes()).await?,
+    }
+    upstream.write_all(&body_prefix).await?;
+    tokio::io::copy_bidirectional(&mut client, &mut upstream).await?;
+    Ok(())
+}
+
+/// Reads up to the blank line ending the request headers. Returns the head and
+/// any bytes read past it.
+async fn read_request_head(client: &mut TcpStream) -> io::Result<(String, Vec<u8>)> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    loop {
+        if let Some(end) = find_head_end(&buf) {
+            let rest = buf.split_off(end);
+            let head = String::from_utf8(buf)
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "non-UTF-8 request"))?;
+            return Ok((head, rest));
+        }
+        if buf.len() > MAX_REQUEST_HEAD_BYTES {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "request head too large",
+            ));
+        }
+        let read = client.read(&mut chunk).await?;
+        if read == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        buf.extend_from_slice(&chunk[..read]);
+    }
+}
+
+fn find_head_end(buf: &[u8]) -> Option<usize> {
+    buf.windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .map(|pos| pos + 4)
+}
+
+async fn respond(client: &mut TcpStream, status: &str, message: &str) -> io::Result<()> {
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{message}\n",
+        message.len() + 1
+    );
+    client.write_all(response.as_bytes()).await?;
+    client.shutdown().await
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct ProxyRequest {
+    host: String,
+    port: u16,
+    /// Request head to send upstream for plain HTTP requests; `None` for
+    /// `CONNECT` tunnels.
+    forward_head: Option<String>,
+}
+
+impl ProxyRequest {
+    fn parse(head: &str) -> Option<Self> {
+        let mut lines = head.split("\r\n");
+        let mut request_line = lines.next()?.split(' ');
+        let method = request_line.next()?;
+        let target = request_line.next()?;
+        let version = request_line.next()?;
+
+        if method.eq_ignore_ascii_case("CONNECT") {
+            let (host, port) = split_authority(target)?;
+            return Some(Self {
+                host,
+                port: port?,
+                forward_head: None,
+            });
+        }
+
+        // Plain HTTP requests use the absolute form: `GET http://host/path`.
+        let rest = target
+            .get(.."http://".len())
+            .filter(|scheme| scheme.eq_ignore_ascii_case("http://"))
+            .map(|scheme| &target[scheme.len()..])?;
+        let (authority, path) = match rest.find(['/', '?']) {
+            Some(idx) => (&rest[..idx], &rest[idx..]),
+            None => (rest, "/"),
+        };
+        let path = if path.starts_with('?') {
+            format!("/{path}")
+        } else {
+            path.to_string()
+        };
+        let (host, port) = split_authority(authority)?;
+
+        // One request per connection, so a kept-alive client connection
+        // cannot be reused to reach a different host.
+        let mut forward_head = format!("{method} {path} {version}\r\n");
+        for line in lines.filter(|line| !line.is_empty()) {
+            let name = line.split(':').next().unwrap_or_default().trim();
+            let hop_by_hop = [
+                "connection",
+                "keep-alive",
+                "proxy-connection",
+                "proxy-authorization",
+            ]
+            .iter()
+            .any(|header| name.eq_ignore_ascii_case(header));
+            if !hop_by_hop {
+                forward_head.push_str(line);
+                forward_head.push_str("\r\n");
+            }
+        }
+        forward_head.push_str("Connection: close\r\n\r\n");
+
+        Some(Self {
+            host,
+            port: port.unwrap_or(80),
+            forward_head: Some(forward_head),
+        })
+    }
+}
+
+/// Splits `host[:port]` (or `[v6][:port]`), ignoring any userinfo.
+fn split_authority(authority: &str) -> Option<(String, Option<u16>)> {
+    let authority = authority.rsplit('@').next()?;
+    let (host, port) = if let Some(rest) = authority.strip_prefix('[') {
+        let (host, after) = rest.split_once(']')?;
+        let port = match after {
+            "" => None,
+            after => Some(after.strip_prefix(':')?.parse().ok()?),
+        };
+        (host, port)
+    } else {
+        match authority.split_once(':') {
+            Some((host, port)) => (host, Some(port.parse().ok()?)),
+            None => (authority, None),
+        }
+    };
+    if host.is_empty() {
+        return None;
+    }
+    Some((host.to_string(), port))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn allowlist(entries: &[&str]) -> NetworkAllowlist {
+        let entries: Vec<String> = entries.iter().map(ToString::to_string).collect();
+        NetworkAllowlist::new(&entries).expect("valid allowlist")
+    }
+
+    #[test]
+    fn allowlist_matches_hosts_ports_and_subdomains() {
+        let allowlist = allowlist(&["pypi.internal:443", "*.mirror.corp", "[::1]:8080"]);
+
+        assert!(allowlist.allows("pypi.internal", 443));
+        assert!(allowlist.allows("PyPI.Internal.", 443));
+        assert!(!allowlist.allows("pypi.internal", 80));
+        assert!(allowlist.allows("a.mirror.corp", 80));
+        assert!(allowlist.allows("b.a.mirror.corp", 443));
+        assert!(!allowlist.allows("mirror.corp", 443));
+        assert!(!allowlist.allows("evilmirror.corp", 443));
+        assert!(allowlist.allows("::1", 8080));
+        assert!(!allowlist.allows("example.com", 443));
+    }
+
+    #[test]
+    fn allowlist_rejects_malformed_entries() {
+        for entry in ["", "host:port", "*", "*.", "a.*.com", "[::1", "[::1]8080"] {
+            assert!(
+                NetworkAllowlist::new(&[entry.to_string()]).is_err(),
+                "{entry:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn parses_connect_and_absolute_form_requests() {
+        let connect = ProxyRequest::parse(
+            "CONNECT pypi.internal:443 HTTP/1.1\r\nHost: pypi.internal:443\r\n\r\n",
+        );
+        assert_eq!(
+            connect,
+            Some(ProxyRequest {
+                host: "pypi.internal".to_string(),
+                port: 443,
+                forward_head: None,
+            })
+        );
+
+        let get = ProxyRequest::parse(
+            "GET http://mirror.corp/simple/?q=1 HTTP/1.1\r\nHost: mirror.corp\r\nProxy-Connection: keep-alive\r\nAccept: */*\r\n\r\n",
+        );
+        assert_eq!(
+            get,
+            Some(ProxyRequest {
+                host: "mirror.corp".to_string(),
+                port: 80,
+                forward_head: Some(
+                    "GET /simple/?q=1 HTTP/1.1\r\nHost: mirror.corp\r\nAccept: */*\r\nConnection: close\r\n\r\n"
+                        .to_string()
+                ),
+            })
+        );
+
+        assert_eq!(
+            ProxyRequest::parse("CONNECT pypi.internal HTTP/1.1\r\n\r\n"),
+            None
+        );
+        assert_eq!(ProxyRequest::parse("GET /relative HTTP/1.1\r\n\r\n"), None);
+    }
+
+    #[tokio::test]
+    async fn proxy_refuses_and_reports_hosts_outside_allowlist() {
+        let proxy = NetworkProxy::start(&["127.0.0.1:1".to_string()])
+            .await
+            .expect("start proxy");
+        let mut blocked = proxy.subscribe();
+
+        let mut client = TcpStream::connect(("127.0.0.1", proxy.port()))
+            .await
+            .expect("connect to proxy");
+        client
+            .write_all(b"CONNECT example.com:443 HTTP/1.1\r\nHost: example.com:443\r\n\r\n")
+            .await
+            .expect("send request");
+        let mut response = String::new();
+        client
+            .read_to_string(&mut response)
+            .await
+            .expect("read response");
+
+        assert!(
+            response.starts_with("HTTP/1.1 403 Forbidden\r\n"),
+            "{response}"
+        );
+        assert_eq!(
+            blocked.recv().await.expect("blocked connection"),
+            BlockedConnection {
+                host: "example.com".to_string(),
+                port: 443,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn proxy_tunnels_to_allowlisted_hosts() {
+        let upstream = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .await
+            .expect("bind upstream");
+        let upstream_port = upstream.local_addr().expect("upstream addr").port();
+        tokio::spawn(async move {
+            let (mut socket, _) = upstream.accept().await.expect("accept");
+            socket.write_all(b"hello").await.expect("write");
+        });
+
+        let proxy = NetworkProxy::start(&[format!("127.0.0.1:{upstream_port}")])
+            .await
+            .expect("start proxy");
+        let mut client = TcpStream::connect(("127.0.0.1", proxy.port()))
+            .await
+            .expect("connect to proxy");
+        client
+            .write_all(format!("CONNECT 127.0.0.1:{upstream_port} HTTP/1.1\r\n\r\n").as_bytes())
+            .await
+            .expect("send request");
+        let mut response = String::new();
+        client
+            .read_to_string(&mut response)
+            .await
+            .expect("read response");
+
+        assert_eq!(response, "HTTP/1.1 200 Connection Established\r\n\r\nhello");
+    }
+}
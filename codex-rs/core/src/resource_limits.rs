@@ -0,0 +1,578 @@
+//! Enforcement of the per-command ceilings configured in `[sandbox_limits]`.
+//!
+//! On Linux, memory and process limits are enforced with a dedicated cgroup
+//! v2 group per command when the cgroup Codex runs in is delegated to the
+//! user and holds nothing but Codex and its children, so an out-of-memory
+//! kill can be told apart from an ordinary failure. Otherwise they fall back
+//! to `RLIMIT_AS` and `RLIMIT_NPROC`, which are coarser: the address-space
+//! limit counts reserved rather than used memory, the process limit counts
+//! every process of the user, and hitting either only shows up as a failed
+//! allocation or `fork` that the command reports on stderr. CPU time is
+//! always limited per process with `RLIMIT_CPU`. Output size is enforced by
+//! the reader of the command's pipes on every platform.
+
+use std::process::ExitStatus;
+
+use tokio::process::Command;
+
+use crate::config_types::SandboxLimits;
+use crate::protocol::ExecResourceLimit;
+
+#[cfg(unix)]
+const EXIT_CODE_SIGNAL_BASE: i32 = 128;
+#[cfg(unix)]
+const MIB: u64 = 1024 * 1024;
+
+/// Applies [`SandboxLimits`] to a spawned command and attributes its
+/// termination to the limit it exceeded.
+#[derive(Debug, Default)]
+pub struct ResourceLimiter {
+    limits: SandboxLimits,
+    #[cfg(target_os = "linux")]
+    cgroup: Option<cgroup::Cgroup>,
+    /// CPU time of reaped children when the limiter was created, to tell a
+    /// command killed by `RLIMIT_CPU` from one that merely exited with 152.
+    #[cfg(unix)]
+    children_cpu_at_start: std::time::Duration,
+}
+
+impl ResourceLimiter {
+    pub fn new(limits: SandboxLimits) -> Self {
+        #[cfg(target_os = "linux")]
+        let cgroup = if limits.memory_mb.is_some() || limits.max_processes.is_some() {
+            match cgroup::Cgroup::create(&limits) {
+                Ok(cgroup) => Some(cgroup),
+                Err(err) => {
+                    tracing::debug!("cgroup v2 unavailable, falling back to rlimits: {err}");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        Self {
+            limits,
+            #[cfg(target_os = "linux")]
+            cgroup,
+            #[cfg(unix)]
+            children_cpu_at_start: children_cpu_time(),
+        }
+    }
+
+    /// A limiter that imposes no limits.
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    pub fn max_output_bytes(&self) -> Option<u64> {
+        self.limits.max_output_bytes
+    }
+
+    /// Arranges for the child spawned from `cmd` to start under the limits.
+    pub(crate) fn configure(&self, cmd: &mut Command) {
+        #[cfg(unix)]
+        {
+            let procs_fd = self.cgroup_procs_fd();
+            let mut rlimits = Vec::new();
+            if let Some(secs) = self.limits.cpu_time_secs {
+                rlimits.push(Rlimit::Cpu(secs));
+            }
+            if procs_fd.is_none() {
+                if let Some(mb) = self.limits.memory_mb {
+                    rlimits.push(Rlimit::AddressSpace(mb.saturating_mul(MIB)));
+                }
+                if let Some(max) = self.limits.max_processes {
+                    rlimits.push(Rlimit::Processes(max));
+                }
+            }
+            if rlimits.is_empty() && procs_fd.is_none() {
+                return;
+            }
+
+            unsafe {
+                cmd.pre_exec(move || {
+                    // Join the cgroup first so that every process the command
+                    // forks is accounted to it.
+                    if let Some(fd) = procs_fd
+                        && libc::write(fd, b"0".as_ptr().cast(), 1) == -1
+                    {
+                        return Err(std::io::Error::last_os_error());
+                    }
+                    for rlimit in &rlimits {
+                        rlimit.apply()?;
+                    }
+                    Ok(())
+                });
+            }
+        }
+        #[cfg(not(unix))]
+        let _ = cmd;
+    }
+
+    /// Kills every process still running under the limits, including ones
+    /// the command left behind.
+    pub(crate) fn kill_all(&self) {
+        #[cfg(target_os = "linux")]
+        if let Some(cgroup) = &self.cgroup {
+            cgroup.kill();
+        }
+    }
+
+    /// The limit that explains how a command that exited with `status`
+    /// after writing `stderr` ended, if any.
+    pub(crate) fn violation(&self, status: ExitStatus, stderr: &[u8]) -> Option<ExecResourceLimit> {
+        #[cfg(target_os = "linux")]
+        if let Some(cgroup) = &self.cgroup {
+            if cgroup.oom_killed() {
+                return Some(ExecResourceLimit::Memory);
+            }
+            if !status.success() && cgroup.hit_pids_max() {
+                return Some(ExecResourceLimit::Processes);
+            }
+        }
+
+        #[cfg(unix)]
+        if let Some(secs) = self.limits.cpu_time_secs {
+            use std::os::unix::process::ExitStatusExt;
+
+            if status.signal() == Some(libc::SIGXCPU) {
+                return Some(ExecResourceLimit::CpuTime);
+            }
+            // A shell wrapping the command reports the signal as its exit
+            // code, which a command can also exit with on its own.
+            let cpu_spent = children_cpu_time().saturating_sub(self.children_cpu_at_start);
+            if status.code() == Some(EXIT_CODE_SIGNAL_BASE + libc::SIGXCPU)
+                && cpu_spent >= std::time::Duration::from_secs(secs)
+            {
+                return Some(ExecResourceLimit::CpuTime);
+            }
+        }
+
+        if !status.success() && !self.limits_in_cgroup() {
+            let stderr = String::from_utf8_lossy(stderr).to_lowercase();
+            if self.limits.memory_mb.is_some()
+                && RLIMIT_AS_FAILURES.iter().any(|m| stderr.contains(m))
+            {
+                return Some(ExecResourceLimit::Memory);
+            }
+            if self.limits.max_processes.is_some()
+                && RLIMIT_NPROC_FAILURES.iter().any(|m| stderr.contains(m))
+            {
+                return Some(ExecResourceLimit::Processes);
+            }
+        }
+        None
+    }
+
+    /// Whether memory and process limits are enforced by a cgroup rather
+    /// than by rlimits.
+    fn limits_in_cgroup(&self) -> bool {
+        #[cfg(target_os = "linux")]
+        {
+            self.cgroup.is_some()
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            false
+        }
+    }
+
+    #[cfg(unix)]
+    fn cgroup_procs_fd(&self) -> Option<std::os::fd::RawFd> {
+        #[cfg(target_os = "linux")]
+        {
+            self.cgroup.as_ref().map(cgroup::Cgroup::procs_fd)
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            None
+        }
+    }
+}
+
+/// How common runtimes report an allocation refused with `ENOMEM`, in
+/// lowercase.
+const RLIMIT_AS_FAILURES: &[&str] = &[
+    "cannot allocate",
+    "out of memory",
+    "memoryerror",
+    "memory allocation of",
+];
+
+/// How common runtimes report a `fork` refused with `EAGAIN`, in lowercase.
+const RLIMIT_NPROC_FAILURES: &[&str] = &["resource temporarily unavailable", "cannot fork"];
+
+/// User plus system CPU time of every child this process has reaped.
+#[cfg(unix)]
+fn children_cpu_time() -> std::time::Duration {
+    // SAFETY: `rusage` is plain old data for which all zeroes is valid.
+    let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+    // SAFETY: `usage` is a valid, writable `rusage`.
+    if unsafe { libc::getrusage(libc::RUSAGE_CHILDREN, &mut usage) } == -1 {
+        return std::time::Duration::ZERO;
+    }
+    let to_duration = |time: libc::timeval| {
+        std::time::Duration::from_secs(time.tv_sec.max(0) as u64)
+            + std::time::Duration::from_micros(time.tv_usec.max(0) as u64)
+    };
+    to_duration(usage.ru_utime) + to_duration(usage.ru_stime)
+}
+
+#[cfg(unix)]
+#[derive(Debug, Clone, Copy)]
+enum Rlimit {
+    Cpu(u64),
+    AddressSpace(u64),
+    Processes(u64),
+}
+
+#[cfg(unix)]
+impl Rlimit {
+    /// Lowers the limit for the calling process. Only async-signal-safe
+    /// calls are made so this can run between `fork` and `exec`.
+    fn apply(self) -> std::io::Result<()> {
+        let lower = |resource, soft: u64, hard: u64| {
+            let mut current = libc::rlimit {
+                rlim_cur: 0,
+                rlim_max: 0,
+            };
+            // SAFETY: `current` is a valid, writable `rlimit`.
+            if unsafe { libc::getrlimit(resource, &mut current) } == -1 {
+                return Err(std::io::Error::last_os_error());
+            }
+            let hard = (hard as libc::rlim_t).min(current.rlim_max);
+            let limit = libc::rlimit {
+                rlim_cur: (soft as libc::rlim_t).min(hard),
+                rlim_max: hard,
+            };
+            // SAFETY: `limit` is a valid `rlimit`.
+            if unsafe { libc::setrlimit(resource, &limit) } == -1 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        };
+        match self {
+            // SIGXCPU at the soft limit lets the process report it; the hard
+            // limit a second later delivers SIGKILL.
+            Rlimit::Cpu(secs) => lower(libc::RLIMIT_CPU, secs, secs.saturating_add(1)),
+            Rlimit::AddressSpace(bytes) => lower(libc::RLIMIT_AS, bytes, bytes),
+            Rlimit::Processes(max) => lower(libc::RLIMIT_NPROC, max, max),
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod cgroup {
+    use std::fs::File;
+    use std::fs::OpenOptions;
+    use std::io;
+    use std::os::fd::AsRawFd;
+    use std::os::fd::RawFd;
+    use std::path::Path;
+    use std::path::PathBuf;
+    use std::sync::OnceLock;
+    use std::sync::atomic::AtomicU64;
+    use std::sync::atomic::Ordering;
+    use std::time::Duration;
+
+    use super::MIB;
+    use crate::config_types::SandboxLimits;
+
+    const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+    /// How long dropping a group waits for its killed processes to exit.
+    const KILL_WAIT_STEP: Duration = Duration::from_millis(10);
+    const KILL_WAIT_STEPS: u32 = 50;
+
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+    /// The group command groups are created in, prepared on first use.
+    static COMMAND_PARENT: OnceLock<Result<PathBuf, String>> = OnceLock::new();
+
+    /// A cgroup v2 group created for a single command and removed when
+    /// dropped.
+    #[derive(Debug)]
+    pub(super) struct Cgroup {
+        path: PathBuf,
+        procs: File,
+    }
+
+    impl Cgroup {
+        /// Creates a group next to the one Codex runs in, with the memory
+        /// and process limits of `limits`.
+        pub(super) fn create(limits: &SandboxLimits) -> io::Result<Self> {
+            let parent = COMMAND_PARENT
+                .get_or_init(|| command_parent().map_err(|err| err.to_string()))
+                .as_ref()
+                .map_err(|err| io::Error::other(err.clone()))?;
+            let mut controllers = Vec::new();
+            if limits.memory_mb.is_some() {
+                controllers.push("memory");
+            }
+            if limits.max_processes.is_some() {
+                controllers.push("pids");
+            }
+            enable_controllers(parent, &controllers)?;
+
+            let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+            let path = parent.join(format!("codex-exec-{}-{id}", std::process::id()));
+            std::fs::create_dir(&path)?;
+            let procs = match OpenOptions::new()
+                .write(true)
+                .open(path.join("cgroup.procs"))
+            {
+                Ok(procs) => procs,
+                Err(err) => {
+                    let _ = std::fs::remove_dir(&path);
+                    return Err(err);
+                }
+            };
+            // From here on, dropping `cgroup` removes the directory.
+            let cgroup = Self { path, procs };
+            if let Some(mb) = limits.memory_mb {
+                cgroup.write("memory.max", &mb.saturating_mul(MIB).to_string())?;
+                // Without this the kernel swaps rather than OOM-killing.
+                let _ = cgroup.write("memory.swap.max", "0");
+            }
+            if let Some(max) = limits.max_processes {
+                cgroup.write("pids.max", &max.to_string())?;
+            }
+            Ok(cgroup)
+        }
+
+        /// Descriptor of `cgroup.procs`, to which a process writes `0` to
+        /// join the group.
+        pub(super) fn procs_fd(&self) -> RawFd {
+            self.procs.as_raw_fd()
+        }
+
+        pub(super) fn oom_killed(&self) -> bool {
+            self.event_count("memory.events", "oom_kill") > 0
+        }
+
+        pub(super) fn hit_pids_max(&self) -> bool {
+            self.event_count("pids.events", "max") > 0
+        }
+
+        pub(super) fn kill(&self) {
+            let _ = self.write("cgroup.kill", "1");
+        }
+
+        fn populated(&self) -> bool {
+            self.event_count("cgroup.events", "populated") > 0
+        }
+
+        fn write(&self, file: &str, value: &str) -> io::Result<()> {
+            std::fs::write(self.path.join(file), value)
+        }
+
+        fn event_count(&self, file: &str, key: &str) -> u64 {
+            std::fs::read_to_string(self.path.join(file))
+                .ok()
+                .and_then(|contents| parse_event_count(&contents, key))
+                .unwrap_or(0)
+        }
+    }
+
+    impl Drop for Cgroup {
+        fn drop(&mut self) {
+            // A cgroup can only be removed once it is empty, and `cgroup.kill`
+            // returns before the processes it signals have exited.
+            if std::fs::remove_dir(&self.path).is_ok() {
+                return;
+            }
+            self.kill();
+            for _ in 0..KILL_WAIT_STEPS {
+                if !self.populated() {
+                    break;
+                }
+                std::thread::sleep(KILL_WAIT_STEP);
+            }
+            if let Err(err) = std::fs::remove_dir(&self.path) {
+                tracing::warn!("failed to remove cgroup {}: {err}", self.path.display());
+            }
+        }
+    }
+
+    /// Prepares the cgroup Codex runs in to hold command groups. A cgroup v2
+    /// group can only hand controllers to its children while it holds no
+    /// processes itself, so Codex and the children it has already spawned
+    /// move into a `codex-<pid>` leaf first and command groups become its
+    /// siblings. A group shared with unrelated processes is left alone.
+    fn command_parent() -> io::Result<PathBuf> {
+        let current = current_cgroup()?;
+        let pids: Vec<u32> = std::fs::read_to_string(current.join("cgroup.procs"))?
+            .lines()
+            .filter_map(|line| line.trim().parse().ok())
+            .collect();
+        let own = std::process::id();
+        if let Some(pid) = pids.iter().find(|pid| !is_self_or_descendant(**pid, own)) {
+            return Err(io::Error::other(format!(
+                "cgroup {} is shared with process {pid}",
+                current.display()
+            )));
+        }
+
+        // The leaf outlives Codex: a process cannot remove the group it is in.
+        let leaf = current.join(format!("codex-{own}"));
+        match std::fs::create_dir(&leaf) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {}
+            Err(err) => return Err(err),
+        }
+        for pid in pids {
+            if let Err(err) = std::fs::write(leaf.join("cgroup.procs"), pid.to_string())
+                && err.raw_os_error() != Some(libc::ESRCH)
+            {
+                return Err(err);
+            }
+        }
+        Ok(current)
+    }
+
+    fn is_self_or_descendant(mut pid: u32, own: u32) -> bool {
+        while pid > 1 {
+            if pid == own {
+                return true;
+            }
+            match parent_pid(pid) {
+                Some(parent) => pid = parent,
+                None => return false,
+            }
+        }
+        false
+    }
+
+    fn parent_pid(pid: u32) -> Option<u32> {
+        let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+        // The command name is parenthesized and may contain spaces; the state
+        // and the parent's pid follow it.
+        let (_, rest) = stat.rsplit_once(')')?;
+        rest.split_whitespace().nth(1)?.parse().ok()
+    }
+
+    fn current_cgroup() -> io::Result<PathBuf> {
+        let contents = std::fs::read_to_string("/proc/self/cgroup")?;
+        // On a cgroup v2 (unified) hierarchy the only entry is `0::<path>`.
+        let relative = contents
+            .lines()
+            .find_map(|line| line.strip_prefix("0::"))
+            .ok_or_else(|| io::Error::other("no cgroup v2 hierarchy"))?;
+        let path = Path::new(CGROUP_ROOT).join(relative.trim_start_matches('/'));
+        if !path.join("cgroup.controllers").exists() {
+            return Err(io::Error::other("cgroup v2 is not mounted"));
+        }
+        Ok(path)
+    }
+
+    /// Makes `controllers` available to children of `parent`. This fails
+    /// when `parent` is not delegated to the user or, because of cgroup v2's
+    /// "no internal processes" rule, when it still contains processes itself.
+    fn enable_controllers(parent: &Path, controllers: &[&str]) -> io::Result<()> {
+        let enabled = std::fs::read_to_string(parent.join("cgroup.subtree_control"))?;
+        let missing: Vec<String> = controllers
+            .iter()
+            .filter(|controller| !enabled.split_whitespace().any(|c| c == **controller))
+            .map(|controller| format!("+{controller}"))
+            .collect();
+        if missing.is_empty() {
+            return Ok(());
+        }
+        std::fs::write(parent.join("cgroup.subtree_control"), missing.join(" "))
+    }
+
+    pub(super) fn parse_event_count(contents: &str, key: &str) -> Option<u64> {
+        contents.lines().find_map(|line| {
+            let (name, count) = line.split_once(' ')?;
+            if name == key {
+                count.trim().parse().ok()
+            } else {
+                None
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn parses_cgroup_event_counts() {
+        let events = "low 0\nhigh 0\nmax 3\noom 1\noom_kill 1\noom_group_kill 0\n";
+        assert_eq!(cgroup::parse_event_count(events, "oom_kill"), Some(1));
+        assert_eq!(cgroup::parse_event_count(events, "max"), Some(3));
+        assert_eq!(cgroup::parse_event_count(events, "missing"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn attributes_sigxcpu_to_cpu_limit() {
+        use std::os::unix::process::ExitStatusExt;
+
+        let limiter = ResourceLimiter::new(SandboxLimits {
+            cpu_time_secs: Some(1),
+            ..Default::default()
+        });
+        assert_eq!(
+            limiter.violation(ExitStatus::from_raw(libc::SIGXCPU), b""),
+            Some(ExecResourceLimit::CpuTime)
+        );
+        assert_eq!(limiter.violation(ExitStatus::from_raw(1 << 8), b""), None);
+        assert_eq!(
+            ResourceLimiter::unlimited().violation(ExitStatus::from_raw(libc::SIGXCPU), b""),
+            None
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn exit_code_152_needs_cpu_time_to_count_as_cpu_limit() {
+        use std::os::unix::process::ExitStatusExt;
+
+        let limiter = ResourceLimiter::new(SandboxLimits {
+            cpu_time_secs: Some(3600),
+            ..Default::default()
+        });
+        let shell_exit = (EXIT_CODE_SIGNAL_BASE + libc::SIGXCPU) << 8;
+        assert_eq!(
+            limiter.violation(ExitStatus::from_raw(shell_exit), b""),
+            None
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn attributes_rlimit_failures_reported_on_stderr() {
+        use std::os::unix::process::ExitStatusExt;
+
+        // Built directly so that no cgroup is created for it.
+        let limiter = ResourceLimiter {
+            limits: SandboxLimits {
+                memory_mb: Some(64),
+                max_processes: Some(16),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let failed = ExitStatus::from_raw(2 << 8);
+        assert_eq!(
+            limiter.violation(failed, b"bash: xrealloc: cannot allocate 62996480 bytes\n"),
+            Some(ExecResourceLimit::Memory)
+        );
+        assert_eq!(
+            limiter.violation(
+                failed,
+                b"sh: fork: retry: Resource temporarily unavailable\n"
+            ),
+            Some(ExecResourceLimit::Processes)
+        );
+        assert_eq!(limiter.violation(failed, b"error: file not found\n"), None);
+        assert_eq!(
+            limiter.violation(ExitStatus::from_raw(0), b"out of memory\n"),
+            None
+        );
+    }
+}
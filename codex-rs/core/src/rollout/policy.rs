@@ -42,7 +42,11 @@ pub(crate) fn should_persist_event_msg(ev: &EventMsg) -> bool {
         | EventMsg::TokenCount(_)
         | EventMsg::EnteredReviewMode(_)
         | EventMsg::ExitedReviewMode(_)
-        | EventMsg::TurnAborted(_) => true,
+        | EventMsg::ExecApprovalRuleApplied(_)
+        | EventMsg::AgentRunBegin(_)
+        | EventMsg::AgentRunEnd(_)
+        | EventMsg::TurnAborted(_)
+        | EventMsg::TurnDiff(_) => true,
         EventMsg::Error(_)
         | EventMsg::TaskStarted(_)
         | EventMsg::TaskComplete(_)
@@ -58,16 +62,20 @@ pub(crate) fn should_persist_event_msg(ev: &EventMsg) -> bool {
         | EventMsg::ExecCommandBegin(_)
         | EventMsg::ExecCommandOutputDelta(_)
         | EventMsg::ExecCommandEnd(_)
+        | EventMsg::NetworkAccessBlocked(_)
         | EventMsg::ExecApprovalRequest(_)
         | EventMsg::ApplyPatchApprovalRequest(_)
+        | EventMsg::McpSamplingRequest(_)
+        | EventMsg::ElicitationRequest(_)
         | EventMsg::BackgroundEvent(_)
         | EventMsg::StreamError(_)
         | EventMsg::PatchApplyBegin(_)
         | EventMsg::PatchApplyEnd(_)
-        | EventMsg::TurnDiff(_)
         | EventMsg::GetHistoryEntryResponse(_)
         | EventMsg::McpListToolsResponse(_)
+        | EventMsg::McpListResourcesResponse(_)
         | EventMsg::ListCustomPromptsResponse(_)
+        | EventMsg::GetMcpPromptResponse(_)
         | EventMsg::PlanUpdate(_)
         | EventMsg::ShutdownComplete
         | EventMsg::ViewImageToolCall(_)
@@ -0,0 +1,861 @@
+//! Full-text search over recorded rollouts, used by `codex sessions search`,
+//! the resume picker and the app server.
+//!
+//! The searchable text of every rollout (user and agent messages, commands run
+//! and files patched) is kept in `CODEX_HOME/session_search_index.json`.
+//! [`SessionSearchIndex::load`] brings it up to date before searching: rollouts
+//! are append-only, so one that grew since it was indexed is read from where
+//! indexing stopped and only new or truncated rollouts are read in full.
+//! Long-lived callers keep the loaded index and bring it up to date the same
+//! way with [`SessionSearchIndex::refresh`].
+
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::ops::Bound;
+use std::ops::Range;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::UNIX_EPOCH;
+
+use codex_protocol::ConversationId;
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::models::ShellToolCallParams;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::InputMessageKind;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::RolloutLine;
+use codex_protocol::protocol::SessionSource;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::warn;
+
+use super::SESSIONS_SUBDIR;
+use crate::tools::spec::ApplyPatchToolArgs;
+
+pub const SEARCH_INDEX_FILE: &str = "session_search_index.json";
+
+/// Bump when the extracted fragments change so stale indexes are rebuilt.
+const INDEX_VERSION: u32 = 1;
+/// Longest excerpt of a fragment shown in a snippet, in bytes.
+const SNIPPET_MAX_BYTES: usize = 160;
+/// How much text before the first match a snippet starts with, in bytes.
+const SNIPPET_LEAD_BYTES: usize = 40;
+const MAX_SNIPPETS_PER_SESSION: usize = 3;
+
+/// Which part of a session a snippet was taken from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchHitKind {
+    UserMessage,
+    AgentMessage,
+    Command,
+    PatchedFile,
+}
+
+/// A recorded session that contains every term of a query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionSearchHit {
+    /// Absolute path to the rollout file.
+    pub path: PathBuf,
+    pub conversation_id: Option<ConversationId>,
+    /// RFC3339 timestamp of the session start, if recorded.
+    pub created_at: Option<String>,
+    /// RFC3339 timestamp of the last recorded item.
+    pub updated_at: Option<String>,
+    pub cwd: Option<PathBuf>,
+    pub source: Option<SessionSource>,
+    /// Best matching excerpts, the one matching the most terms first.
+    pub snippets: Vec<SearchSnippet>,
+}
+
+/// An excerpt of a message, command or patched path with the matches marked.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchSnippet {
+    pub kind: SearchHitKind,
+    /// The excerpt with whitespace collapsed. Text cut at either end is
+    /// replaced by `…`.
+    pub text: String,
+    /// Byte ranges of `text` matching a query term, ordered and disjoint.
+    pub highlights: Vec<Range<usize>>,
+}
+
+impl SearchSnippet {
+    /// Splits `text` into consecutive runs, each flagged with whether it is
+    /// highlighted.
+    pub fn segments(&self) -> Vec<(&str, bool)> {
+        let mut segments = Vec::new();
+        let mut pos = 0;
+        for range in &self.highlights {
+            if range.start > pos {
+                segments.push((&self.text[pos..range.start], false));
+            }
+            segments.push((&self.text[range.clone()], true));
+            pos = range.end;
+        }
+        if pos < self.text.len() {
+            segments.push((&self.text[pos..], false));
+        }
+        segments
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Fragment {
+    kind: SearchHitKind,
+    text: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct IndexedSession {
+    path: PathBuf,
+    /// Size and modification time (ms since the epoch) of the rollout when it
+    /// was last indexed.
+    len: u64,
+    modified_ms: u64,
+    /// Bytes of the rollout covered by `fragments`. Always ends on a line
+    /// boundary so a line still being written is indexed next time.
+    indexed_len: u64,
+    id: Option<ConversationId>,
+    created_at: Option<String>,
+    updated_at: Option<String>,
+    cwd: Option<PathBuf>,
+    source: Option<SessionSource>,
+    fragments: Vec<Fragment>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct IndexFile {
+    version: u32,
+    sessions: Vec<IndexedSession>,
+}
+
+/// Searchable text of every recorded session, with an in-memory map from
+/// each term to the sessions containing it.
+#[derive(Debug, Default)]
+pub struct SessionSearchIndex {
+    sessions: Vec<IndexedSession>,
+    terms: BTreeMap<String, Vec<usize>>,
+}
+
+impl SessionSearchIndex {
+    /// Loads the index saved under `codex_home`, indexes rollouts recorded or
+    /// extended since it was saved and saves it back.
+    pub async fn load(codex_home: &Path) -> io::Result<Self> {
+        let codex_home = codex_home.to_path_buf();
+        tokio::task::spawn_blocking(move || Self::load_blocking(&codex_home))
+            .await
+            .map_err(io::Error::other)?
+    }
+
+    /// Brings an index returned by [`Self::load`] up to date without reading
+    /// the saved index again, keeping the term map when no rollout changed.
+    pub async fn refresh(self, codex_home: &Path) -> io::Result<Self> {
+        let codex_home = codex_home.to_path_buf();
+        tokio::task::spawn_blocking(move || {
+            let Self { sessions, terms } = self;
+            let (sessions, changed) = update_sessions(&codex_home, sessions)?;
+            Ok(if changed {
+                Self::new(sessions)
+            } else {
+                Self { sessions, terms }
+            })
+        })
+        .await
+        .map_err(io::Error::other)?
+    }
+
+    fn load_blocking(codex_home: &Path) -> io::Result<Self> {
+        let previous = read_index_file(&codex_home.join(SEARCH_INDEX_FILE));
+        let (sessions, _) = update_sessions(codex_home, previous)?;
+        Ok(Self::new(sessions))
+    }
+
+    fn new(sessions: Vec<IndexedSession>) -> Self {
+        let mut terms: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        for (idx, session) in sessions.iter().enumerate() {
+            let mut seen = HashSet::new();
+            for fragment in &session.fragments {
+                for term in terms_of(&fragment.text) {
+                    if seen.insert(term.clone()) {
+                        terms.entry(term).or_default().push(idx);
+                    }
+                }
+            }
+        }
+        Self { sessions, terms }
+    }
+
+    /// Returns up to `limit` sessions that contain every term of `query`,
+    /// most recently updated first. A term matches the start of a word
+    /// regardless of ASCII case, so `conf` finds `Config.toml`. An empty
+    /// `allowed_sources` allows sessions from every source.
+    pub fn search(
+        &self,
+        query: &str,
+        limit: usize,
+        allowed_sources: &[SessionSource],
+    ) -> Vec<SessionSearchHit> {
+        let mut query_terms: Vec<String> = Vec::new();
+        for term in terms_of(query) {
+            if !query_terms.contains(&term) {
+                query_terms.push(term);
+            }
+        }
+        if query_terms.is_empty() {
+            return Vec::new();
+        }
+
+        let mut candidates: Option<HashSet<usize>> = None;
+        for term in &query_terms {
+            let containing: HashSet<usize> = self
+                .terms
+                .range::<str, _>((Bound::Included(term.as_str()), Bound::Unbounded))
+                .take_while(|(indexed, _)| indexed.starts_with(term.as_str()))
+                .flat_map(|(_, sessions)| sessions.iter().copied())
+                .collect();
+            candidates = Some(match candidates {
+                Some(previous) => previous.intersection(&containing).copied().collect(),
+                None => containing,
+            });
+        }
+
+        let mut matches: Vec<&IndexedSession> = candidates
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|idx| self.sessions.get(idx))
+            .filter(|session| {
+                allowed_sources.is_empty()
+                    || session
+                        .source
+                        .is_some_and(|source| allowed_sources.contains(&source))
+            })
+            .collect();
+        matches.sort_by(|a, b| {
+            b.updated_at
+                .cmp(&a.updated_at)
+                .then_with(|| b.path.cmp(&a.path))
+        });
+        matches
+            .into_iter()
+            .take(limit)
+            .map(|session| search_hit(session, &query_terms))
+            .collect()
+    }
+}
+
+/// Brings `previous` up to date with the rollouts under `codex_home`, saving
+/// the result when anything changed. Sessions come back in path order.
+fn update_sessions(
+    codex_home: &Path,
+    previous: Vec<IndexedSession>,
+) -> io::Result<(Vec<IndexedSession>, bool)> {
+    let mut previous: HashMap<PathBuf, IndexedSession> = previous
+        .into_iter()
+        .map(|session| (session.path.clone(), session))
+        .collect();
+
+    let mut rollouts = Vec::new();
+    collect_rollouts(&codex_home.join(SESSIONS_SUBDIR), &mut rollouts)?;
+    rollouts.sort();
+
+    let mut changed = false;
+    let mut sessions = Vec::with_capacity(rollouts.len());
+    for path in rollouts {
+        let Ok(metadata) = fs::metadata(&path) else {
+            continue;
+        };
+        let len = metadata.len();
+        let modified_ms = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map(|since_epoch| since_epoch.as_millis() as u64)
+            .unwrap_or_default();
+
+        let mut session = previous.remove(&path).unwrap_or_default();
+        if session.path == path && session.len == len && session.modified_ms == modified_ms {
+            sessions.push(session);
+            continue;
+        }
+        if session.path != path || len < session.indexed_len {
+            session = IndexedSession {
+                path: path.clone(),
+                ..Default::default()
+            };
+        }
+        changed = true;
+        match index_rollout(&mut session) {
+            Ok(()) => {
+                session.len = len;
+                session.modified_ms = modified_ms;
+            }
+            Err(err) => warn!("failed to index rollout {}: {err}", path.display()),
+        }
+        sessions.push(session);
+    }
+    changed |= !previous.is_empty();
+
+    if changed && let Err(err) = write_index_file(&codex_home.join(SEARCH_INDEX_FILE), &sessions) {
+        warn!("failed to save session search index: {err}");
+    }
+    Ok((sessions, changed))
+}
+
+fn read_index_file(path: &Path) -> Vec<IndexedSession> {
+    let contents = match fs::read(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Vec::new(),
+        Err(err) => {
+            warn!("failed to read session search index: {err}");
+            return Vec::new();
+        }
+    };
+    match serde_json::from_slice::<IndexFile>(&contents) {
+        Ok(index) if index.version == INDEX_VERSION => index.sessions,
+        Ok(_) => Vec::new(),
+        Err(err) => {
+            warn!("rebuilding unreadable session search index: {err}");
+            Vec::new()
+        }
+    }
+}
+
+fn write_index_file(path: &Path, sessions: &[IndexedSession]) -> io::Result<()> {
+    #[derive(Serialize)]
+    struct IndexFileRef<'a> {
+        version: u32,
+        sessions: &'a [IndexedSession],
+    }
+
+    let contents = serde_json::to_vec(&IndexFileRef {
+        version: INDEX_VERSION,
+        sessions,
+    })?;
+    // Write to a per-process temporary file and rename it into place so
+    // concurrent searches never read a partially written index.
+    let tmp_path = path.with_extension(format!("json.{}.tmp", std::process::id()));
+    fs::write(&tmp_path, contents)?;
+    fs::rename(&tmp_path, path)
+}
+
+/// Collects every `rollout-*.jsonl` file below `dir`.
+fn collect_rollouts(dir: &Path, rollouts: &mut Vec<PathBuf>) -> io::Result<()> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+    for entry in entries {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let path = entry.path();
+        if file_type.is_dir() {
+            collect_rollouts(&path, rollouts)?;
+        } else if file_type.is_file()
+            && let Some(name) = path.file_name().and_then(|name| name.to_str())
+            && name.starts_with("rollout-")
+            && name.ends_with(".jsonl")
+        {
+            rollouts.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Indexes the complete lines appended to `session.path` since
+/// `session.indexed_len`.
+fn index_rollout(session: &mut IndexedSession) -> io::Result<()> {
+    let mut file = fs::File::open(&session.path)?;
+    file.seek(SeekFrom::Start(session.indexed_len))?;
+    let mut appended = Vec::new();
+    file.read_to_end(&mut appended)?;
+    let Some(last_newline) = appended.iter().rposition(|b| *b == b'\n') else {
+        return Ok(());
+    };
+    let complete = &appended[..=last_newline];
+
+    for line in String::from_utf8_lossy(complete).lines() {
+        let Ok(RolloutLine { timestamp, item }) = serde_json::from_str::<RolloutLine>(line) else {
+            continue;
+        };
+        match item {
+            RolloutItem::SessionMeta(meta_line) if session.id.is_none() => {
+                let meta = meta_line.meta;
+                session.id = Some(meta.id);
+                session.created_at = Some(if meta.timestamp.is_empty() {
+                    timestamp.clone()
+                } else {
+                    meta.timestamp
+                });
+                session.cwd = Some(meta.cwd);
+                session.source = Some(meta.source);
+            }
+            RolloutItem::EventMsg(EventMsg::UserMessage(event))
+                if !matches!(
+                    event.kind,
+                    Some(InputMessageKind::UserInstructions | InputMessageKind::EnvironmentContext)
+                ) =>
+            {
+                push_fragment(session, SearchHitKind::UserMessage, event.message);
+            }
+            RolloutItem::EventMsg(EventMsg::AgentMessage(event)) => {
+                push_fragment(session, SearchHitKind::AgentMessage, event.message);
+            }
+            RolloutItem::ResponseItem(item) => push_tool_call(session, item),
+            _ => {}
+        }
+        session.updated_at = Some(timestamp);
+    }
+
+    session.indexed_len += complete.len() as u64;
+    Ok(())
+}
+
+fn push_tool_call(session: &mut IndexedSession, item: ResponseItem) {
+    match item {
+        ResponseItem::LocalShellCall {
+            action: LocalShellAction::Exec(exec),
+            ..
+        } => push_command(session, &exec.command),
+        ResponseItem::FunctionCall {
+            name, arguments, ..
+        } => match name.as_str() {
+            "shell" | "container.exec" => {
+                if let Ok(params) = serde_json::from_str::<ShellToolCallParams>(&arguments) {
+                    push_command(session, &params.command);
+                }
+            }
+            "apply_patch" => {
+                if let Ok(args) = serde_json::from_str::<ApplyPatchToolArgs>(&arguments) {
+                    push_patched_files(session, &args.input);
+                }
+            }
+            _ => {}
+        },
+        ResponseItem::CustomToolCall { name, input, .. } if name == "apply_patch" => {
+            push_patched_files(session, &input);
+        }
+        _ => {}
+    }
+}
+
+fn push_command(session: &mut IndexedSession, command: &[String]) {
+    let script = match command {
+        [program, patch] if program == "apply_patch" || program == "applypatch" => {
+            push_patched_files(session, patch);
+            return;
+        }
+        [shell, flag, script] if shell == "bash" && flag == "-lc" => script.clone(),
+        _ => shlex::try_join(command.iter().map(String::as_str))
+            .unwrap_or_else(|_| command.join(" ")),
+    };
+    // A heredoc patch is indexed by the files it touches rather than as a
+    // (very long) command.
+    if script.contains("*** Begin Patch") {
+        push_patched_files(session, &script);
+    } else {
+        push_fragment(session, SearchHitKind::Command, script);
+    }
+}
+
+fn push_patched_files(session: &mut IndexedSession, patch: &str) {
+    const FILE_MARKERS: [&str; 4] = [
+        "*** Add File: ",
+        "*** Update File: ",
+        "*** Delete File: ",
+        "*** Move to: ",
+    ];
+    for line in patch.lines() {
+        let Some(path) = FILE_MARKERS
+            .iter()
+            .find_map(|marker| line.trim_start().strip_prefix(marker))
+        else {
+            continue;
+        };
+        let path = path.trim();
+        let already_indexed = session
+            .fragments
+            .iter()
+            .any(|fragment| fragment.kind == SearchHitKind::PatchedFile && fragment.text == path);
+        if !already_indexed {
+            push_fragment(session, SearchHitKind::PatchedFile, path.to_string());
+        }
+    }
+}
+
+fn push_fragment(session: &mut IndexedSession, kind: SearchHitKind, text: String) {
+    if !text.trim().is_empty() {
+        session.fragments.push(Fragment { kind, text });
+    }
+}
+
+/// Splits `text` into ASCII-lowercased alphanumeric words.
+fn terms_of(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_ascii_lowercase)
+}
+
+fn search_hit(session: &IndexedSession, query_terms: &[String]) -> SessionSearchHit {
+    let mut scored: Vec<(usize, usize, SearchSnippet)> = session
+        .fragments
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, fragment)| {
+            let (snippet, matched_terms) = snippet(fragment, query_terms)?;
+            Some((matched_terms, idx, snippet))
+        })
+        .collect();
+    scored.sort_by_key(|(matched_terms, idx, _)| (Reverse(*matched_terms), *idx));
+
+    SessionSearchHit {
+        path: session.path.clone(),
+        conversation_id: session.id,
+        created_at: session.created_at.clone(),
+        updated_at: session.updated_at.clone(),
+        cwd: session.cwd.clone(),
+        source: session.source,
+        snippets: scored
+            .into_iter()
+            .take(MAX_SNIPPETS_PER_SESSION)
+            .map(|(_, _, snippet)| snippet)
+            .collect(),
+    }
+}
+
+/// Builds a snippet around the first match of any query term in `fragment`,
+/// along with how many distinct terms the fragment matches. Returns `None`
+/// when nothing matches.
+fn snippet(fragment: &Fragment, query_terms: &[String]) -> Option<(SearchSnippet, usize)> {
+    let text = fragment
+        .text
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    // ASCII lowercasing keeps byte offsets identical to `text`.
+    let lower = text.to_ascii_lowercase();
+
+    let mut matched_terms = 0;
+    let mut matches: Vec<Range<usize>> = Vec::new();
+    for term in query_terms {
+        let before = matches.len();
+        matches.extend(
+            lower
+                .match_indices(term.as_str())
+                .filter(|(idx, _)| {
+                    lower[..*idx]
+                        .chars()
+                        .next_back()
+                        .is_none_or(|c| !c.is_alphanumeric())
+                })
+                .map(|(idx, matched)| idx..idx + matched.len()),
+        );
+        if matches.len() > before {
+            matched_terms += 1;
+        }
+    }
+    matches.sort_by_key(|range| range.start);
+    let first = matches.first()?.start;
+
+    let mut start = first.saturating_sub(SNIPPET_LEAD_BYTES);
+    while !text.is_char_boundary(start) {
+        start -= 1;
+    }
+    // Avoid starting the excerpt in the middle of a word.
+    if start > 0
+        && let Some(space) = text[start..first].find(' ')
+    {
+        start += space + 1;
+    }
+    let mut end = (start + SNIPPET_MAX_BYTES).min(text.len());
+    while !text.is_char_boundary(end) {
+        end += 1;
+    }
+    if end < text.len()
+        && let Some(space) = text[first..end].rfind(' ')
+        && space > 0
+    {
+        end = first + space;
+    }
+
+    let prefix = if start > 0 { "…" } else { "" };
+    let suffix = if end < text.len() { "…" } else { "" };
+    let offset = prefix.len();
+    let mut highlights: Vec<Range<usize>> = Vec::new();
+    for range in matches {
+        if range.start >= end {
+            break;
+        }
+        let range = (range.start - start + offset)..(range.end.min(end) - start + offset);
+        match highlights.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => highlights.push(range),
+        }
+    }
+
+    Some((
+        SearchSnippet {
+            kind: fragment.kind,
+            text: format!("{prefix}{}{suffix}", &text[start..end]),
+            highlights,
+        },
+        matched_terms,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    fn line(timestamp: &str, item: serde_json::Value) -> String {
+        let mut value = item;
+        value["timestamp"] = json!(timestamp);
+        format!("{value}\n")
+    }
+
+    fn session_meta(id: &str) -> serde_json::Value {
+        json!({
+            "type": "session_meta",
+            "payload": {
+                "id": id,
+                "timestamp": "2025-05-01T10:00:00.000Z",
+                "cwd": "/repo",
+                "originator": "codex_cli_rs",
+                "cli_version": "0.0.0",
+                "instructions": null,
+                "source": "cli",
+            }
+        })
+    }
+
+    fn event(payload: serde_json::Value) -> serde_json::Value {
+        json!({ "type": "event_msg", "payload": payload })
+    }
+
+    fn write_rollout(codex_home: &Path, id: &str, lines: &[String]) -> PathBuf {
+        let dir = codex_home.join("sessions/2025/05/01");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(format!("rollout-2025-05-01T10-00-00-{id}.jsonl"));
+        fs::write(&path, lines.concat()).unwrap();
+        path
+    }
+
+    fn highlighted(snippet: &SearchSnippet) -> Vec<&str> {
+        snippet
+            .segments()
+            .into_iter()
+            .filter_map(|(text, highlight)| highlight.then_some(text))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn finds_messages_commands_and_patched_files() {
+        let codex_home = TempDir::new().unwrap();
+        let id = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+        let patch = "*** Begin Patch\n*** Update File: src/Config.rs\n@@\n-a\n+b\n*** End Patch";
+        write_rollout(
+            codex_home.path(),
+            id,
+            &[
+                line("2025-05-01T10:00:00.000Z", session_meta(id)),
+                line(
+                    "2025-05-01T10:00:01.000Z",
+                    event(
+                        json!({ "type": "user_message", "message": "Why does the parser panic?" }),
+                    ),
+                ),
+                line(
+                    "2025-05-01T10:00:02.000Z",
+                    json!({
+                        "type": "response_item",
+                        "payload": {
+                            "type": "function_call",
+                            "name": "shell",
+                            "arguments": json!({ "command": ["bash", "-lc", "cargo test -p parser"] }).to_string(),
+                            "call_id": "call-1",
+                        }
+                    }),
+                ),
+                line(
+                    "2025-05-01T10:00:03.000Z",
+                    json!({
+                        "type": "response_item",
+                        "payload": {
+                            "type": "custom_tool_call",
+                            "name": "apply_patch",
+                            "input": patch,
+                            "call_id": "call-2",
+                        }
+                    }),
+                ),
+                line(
+                    "2025-05-01T10:00:04.000Z",
+                    event(json!({ "type": "agent_message", "message": "Fixed the parser." })),
+                ),
+            ],
+        );
+
+        let index = SessionSearchIndex::load(codex_home.path()).await.unwrap();
+
+        let hits = index.search("PARSER", 10, &[]);
+        assert_eq!(hits.len(), 1);
+        let hit = &hits[0];
+        assert_eq!(
+            hit.conversation_id.map(|id| id.to_string()).as_deref(),
+            Some(id)
+        );
+        assert_eq!(hit.updated_at.as_deref(), Some("2025-05-01T10:00:04.000Z"));
+        assert_eq!(
+            hit.snippets
+                .iter()
+                .map(|snippet| snippet.kind)
+                .collect::<Vec<_>>(),
+            vec![
+                SearchHitKind::UserMessage,
+                SearchHitKind::Command,
+                SearchHitKind::AgentMessage,
+            ]
+        );
+        assert_eq!(highlighted(&hit.snippets[0]), vec!["parser"]);
+
+        let hits = index.search("conf", 10, &[]);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].snippets[0].kind, SearchHitKind::PatchedFile);
+        assert_eq!(hits[0].snippets[0].text, "src/Config.rs");
+        assert_eq!(highlighted(&hits[0].snippets[0]), vec!["Conf"]);
+
+        // Every term must appear somewhere in the session, and only at the
+        // start of a word.
+        assert_eq!(index.search("parser missing", 10, &[]), Vec::new());
+        assert_eq!(index.search("arser", 10, &[]), Vec::new());
+        assert_eq!(
+            index.search("parser", 10, &[SessionSource::Exec]),
+            Vec::new()
+        );
+    }
+
+    #[tokio::test]
+    async fn reindexes_only_appended_lines() {
+        let codex_home = TempDir::new().unwrap();
+        let id = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+        let path = write_rollout(
+            codex_home.path(),
+            id,
+            &[
+                line("2025-05-01T10:00:00.000Z", session_meta(id)),
+                line(
+                    "2025-05-01T10:00:01.000Z",
+                    event(json!({ "type": "user_message", "message": "first question" })),
+                ),
+            ],
+        );
+
+        let index = SessionSearchIndex::load(codex_home.path()).await.unwrap();
+        assert_eq!(index.search("first", 10, &[]).len(), 1);
+        assert!(codex_home.path().join(SEARCH_INDEX_FILE).exists());
+
+        let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(
+            line(
+                "2025-05-01T11:00:00.000Z",
+                event(json!({ "type": "user_message", "message": "second question" })),
+            )
+            .as_bytes(),
+        )
+        .unwrap();
+        // A line that is still being written is left for the next load.
+        file.write_all(b"{\"timestamp\":").unwrap();
+        drop(file);
+
+        let index = SessionSearchIndex::load(codex_home.path()).await.unwrap();
+        let hits = index.search("question", 10, &[]);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].snippets.len(), 2);
+        assert_eq!(
+            hits[0].updated_at.as_deref(),
+            Some("2025-05-01T11:00:00.000Z")
+        );
+        assert_eq!(
+            index.sessions[0].indexed_len,
+            fs::metadata(&path).unwrap().len() - 13
+        );
+
+        fs::remove_file(&path).unwrap();
+        let index = SessionSearchIndex::load(codex_home.path()).await.unwrap();
+        assert_eq!(index.search("question", 10, &[]), Vec::new());
+    }
+
+    #[tokio::test]
+    async fn refresh_picks_up_new_rollouts() {
+        let codex_home = TempDir::new().unwrap();
+        let first = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+        write_rollout(
+            codex_home.path(),
+            first,
+            &[
+                line("2025-05-01T10:00:00.000Z", session_meta(first)),
+                line(
+                    "2025-05-01T10:00:01.000Z",
+                    event(json!({ "type": "user_message", "message": "first question" })),
+                ),
+            ],
+        );
+        let index = SessionSearchIndex::load(codex_home.path()).await.unwrap();
+
+        let index = index.refresh(codex_home.path()).await.unwrap();
+        assert_eq!(index.search("question", 10, &[]).len(), 1);
+
+        let second = "9b2f0f4e-2a71-4c3f-8f8e-0b2f4a1c9d11";
+        write_rollout(
+            codex_home.path(),
+            second,
+            &[
+                line("2025-05-01T10:00:00.000Z", session_meta(second)),
+                line(
+                    "2025-05-01T12:00:00.000Z",
+                    event(json!({ "type": "user_message", "message": "second question" })),
+                ),
+            ],
+        );
+        let index = index.refresh(codex_home.path()).await.unwrap();
+        let hits = index.search("question", 10, &[]);
+        assert_eq!(
+            hits.iter()
+                .map(|hit| hit.conversation_id.map(|id| id.to_string()))
+                .collect::<Vec<_>>(),
+            vec![Some(second.to_string()), Some(first.to_string())]
+        );
+    }
+
+    #[test]
+    fn snippet_is_cut_around_the_first_match() {
+        let fragment = Fragment {
+            kind: SearchHitKind::AgentMessage,
+            text: format!(
+                "{}\n\nthe needle is here {}",
+                "lead ".repeat(20),
+                "tail ".repeat(40)
+            ),
+        };
+        let (snippet, matched_terms) =
+            snippet(&fragment, &["needle".to_string(), "absent".to_string()]).unwrap();
+
+        assert_eq!(matched_terms, 1);
+        assert!(snippet.text.starts_with("…lead "));
+        assert!(snippet.text.ends_with(" tail…"));
+        assert!(!snippet.text.contains('\n'));
+        assert_eq!(highlighted(&snippet), vec!["needle"]);
+    }
+}
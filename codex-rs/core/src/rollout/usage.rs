@@ -0,0 +1,518 @@
+//! Token usage aggregation over recorded rollouts, used by `codex usage`.
+//!
+//! Each rollout records a cumulative `token_count` event after every model
+//! response. Usage is attributed to the UTC day of the event and to the model
+//! and working directory of the most recent turn context.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::ConversationId;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::RolloutLine;
+use codex_protocol::protocol::SessionSource;
+use codex_protocol::protocol::TokenUsage;
+use serde::Serialize;
+use tracing::warn;
+
+use super::RolloutRecorder;
+use crate::budget::estimate_cost;
+use crate::budget::model_price;
+use crate::config_types::ModelPrice;
+
+const PAGE_SIZE: usize = 100;
+
+/// Tokens counted for one group of usage.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct UsageTotals {
+    pub input_tokens: u64,
+    pub cached_input_tokens: u64,
+    pub output_tokens: u64,
+    pub reasoning_output_tokens: u64,
+}
+
+impl UsageTotals {
+    fn add(&mut self, other: &UsageTotals) {
+        self.input_tokens += other.input_tokens;
+        self.cached_input_tokens += other.cached_input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.reasoning_output_tokens += other.reasoning_output_tokens;
+    }
+
+    fn is_zero(&self) -> bool {
+        *self == Self::default()
+    }
+
+    fn to_token_usage(self) -> TokenUsage {
+        TokenUsage {
+            input_tokens: self.input_tokens,
+            cached_input_tokens: self.cached_input_tokens,
+            output_tokens: self.output_tokens,
+            reasoning_output_tokens: self.reasoning_output_tokens,
+            total_tokens: self.input_tokens + self.output_tokens,
+        }
+    }
+}
+
+/// Usage from one session on one day with one model and working directory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageEntry {
+    pub session_id: Option<ConversationId>,
+    /// UTC day, `YYYY-MM-DD`.
+    pub day: String,
+    pub model: String,
+    pub cwd: PathBuf,
+    pub source: SessionSource,
+    pub usage: UsageTotals,
+}
+
+/// Every usage entry found under `~/.codex/sessions`.
+#[derive(Debug, Default)]
+pub struct UsageScan {
+    pub entries: Vec<UsageEntry>,
+    pub sessions_scanned: usize,
+    /// Whether the rollout listing stopped at its scan cap, leaving older
+    /// sessions out.
+    pub reached_scan_cap: bool,
+}
+
+/// Reads every recorded session and extracts its token usage.
+pub async fn scan_usage(codex_home: &Path) -> io::Result<UsageScan> {
+    let mut scan = UsageScan::default();
+    let mut cursor = None;
+    loop {
+        let page = RolloutRecorder::list_conversations(codex_home, PAGE_SIZE, cursor.as_ref(), &[])
+            .await?;
+        scan.reached_scan_cap |= page.reached_scan_cap;
+        for item in page.items {
+            match tokio::fs::read_to_string(&item.path).await {
+                Ok(contents) => {
+                    scan.sessions_scanned += 1;
+                    scan.entries.extend(parse_rollout_usage(&contents));
+                }
+                Err(err) => warn!("failed to read rollout {}: {err}", item.path.display()),
+            }
+        }
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+    Ok(scan)
+}
+
+/// Extracts the token usage recorded in the contents of one rollout file.
+pub fn parse_rollout_usage(contents: &str) -> Vec<UsageEntry> {
+    let mut session_id = None;
+    let mut source = SessionSource::default();
+    let mut model = String::from("unknown");
+    let mut cwd = PathBuf::new();
+    let mut previous = UsageTotals::default();
+    let mut grouped: BTreeMap<(String, String, PathBuf), UsageTotals> = BTreeMap::new();
+
+    for line in contents.lines() {
+        let Ok(RolloutLine { timestamp, item }) = serde_json::from_str::<RolloutLine>(line) else {
+            continue;
+        };
+        match item {
+            RolloutItem::SessionMeta(meta_line) => {
+                session_id = Some(meta_line.meta.id);
+                source = meta_line.meta.source;
+                cwd = meta_line.meta.cwd;
+            }
+            RolloutItem::TurnContext(turn_context) => {
+                model = turn_context.model;
+                cwd = turn_context.cwd;
+            }
+            RolloutItem::EventMsg(EventMsg::TokenCount(event)) => {
+                let Some(info) = event.info else {
+                    continue;
+                };
+                let total = &info.total_token_usage;
+                let current = UsageTotals {
+                    input_tokens: total.input_tokens,
+                    cached_input_tokens: total.cached_input_tokens,
+                    output_tokens: total.output_tokens,
+                    reasoning_output_tokens: total.reasoning_output_tokens,
+                };
+                let delta = usage_delta(&previous, &current);
+                previous = current;
+                if delta.is_zero() {
+                    continue;
+                }
+                let day = timestamp.get(..10).unwrap_or(&timestamp).to_string();
+                grouped
+                    .entry((day, model.clone(), cwd.clone()))
+                    .or_default()
+                    .add(&delta);
+            }
+            _ => {}
+        }
+    }
+
+    grouped
+        .into_iter()
+        .map(|((day, model, cwd), usage)| UsageEntry {
+            session_id,
+            day,
+            model,
+            cwd,
+            source,
+            usage,
+        })
+        .collect()
+}
+
+/// Usage added since `previous`. Totals restart from zero when a session is
+/// resumed and after the context window fills up, so a counter that went down
+/// is taken as new usage from zero.
+fn usage_delta(previous: &UsageTotals, current: &UsageTotals) -> UsageTotals {
+    let delta = |previous: u64, current: u64| {
+        if current >= previous {
+            current - previous
+        } else {
+            current
+        }
+    };
+    UsageTotals {
+        input_tokens: delta(previous.input_tokens, current.input_tokens),
+        cached_input_tokens: delta(previous.cached_input_tokens, current.cached_input_tokens),
+        output_tokens: delta(previous.output_tokens, current.output_tokens),
+        reasoning_output_tokens: delta(
+            previous.reasoning_output_tokens,
+            current.reasoning_output_tokens,
+        ),
+    }
+}
+
+/// Column usage can be grouped by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsageGroup {
+    Day,
+    Model,
+    Cwd,
+    Source,
+}
+
+/// Aggregated usage for one combination of the requested groups. Columns that
+/// were not grouped by are `None`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UsageRow {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub day: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<SessionSource>,
+    pub sessions: usize,
+    #[serde(flatten)]
+    pub usage: UsageTotals,
+    /// Estimated cost in USD, when pricing was requested. Usage of models
+    /// without a known price counts as zero.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_usd: Option<f64>,
+}
+
+/// Sums `entries` on or after `since` (a `YYYY-MM-DD` day) into one row per
+/// combination of `groups`, ordered by the grouped columns. Pass `prices` to
+/// estimate the cost of each row.
+pub fn aggregate_usage(
+    entries: &[UsageEntry],
+    groups: &[UsageGroup],
+    since: Option<&str>,
+    prices: Option<&HashMap<String, ModelPrice>>,
+) -> Vec<UsageRow> {
+    type Key = (
+        Option<String>,
+        Option<String>,
+        Option<PathBuf>,
+        Option<String>,
+    );
+    let mut rows: BTreeMap<Key, (UsageRow, HashSet<Option<ConversationId>>)> = BTreeMap::new();
+
+    for entry in entries {
+        if since.is_some_and(|since| entry.day.as_str() < since) {
+            continue;
+        }
+        let grouped = |group: UsageGroup| groups.contains(&group);
+        let day = grouped(UsageGroup::Day).then(|| entry.day.clone());
+        let model = grouped(UsageGroup::Model).then(|| entry.model.clone());
+        let cwd = grouped(UsageGroup::Cwd).then(|| entry.cwd.clone());
+        let source = grouped(UsageGroup::Source).then_some(entry.source);
+        let key = (
+            day.clone(),
+            model.clone(),
+            cwd.clone(),
+            source.map(|source| format!("{source:?}")),
+        );
+        let (row, sessions) = rows.entry(key).or_insert_with(|| {
+            (
+                UsageRow {
+                    day,
+                    model,
+                    cwd,
+                    source,
+                    sessions: 0,
+                    usage: UsageTotals::default(),
+                    cost_usd: prices.map(|_| 0.0),
+                },
+                HashSet::new(),
+            )
+        });
+        row.usage.add(&entry.usage);
+        if let (Some(cost), Some(prices)) = (row.cost_usd.as_mut(), prices)
+            && let Some(price) = model_price(prices, &entry.model)
+        {
+            *cost += estimate_cost(&price, &entry.usage.to_token_usage());
+        }
+        sessions.insert(entry.session_id);
+    }
+
+    rows.into_values()
+        .map(|(mut row, sessions)| {
+            row.sessions = sessions.len();
+            row
+        })
+        .collect()
+}
+
+/// Models in `entries` that have no known price.
+pub fn unpriced_models(
+    entries: &[UsageEntry],
+    prices: &HashMap<String, ModelPrice>,
+) -> BTreeSet<String> {
+    entries
+        .iter()
+        .filter(|entry| model_price(prices, &entry.model).is_none())
+        .map(|entry| entry.model.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn line(timestamp: &str, item: serde_json::Value) -> String {
+        let mut value = item;
+        value["timestamp"] = json!(timestamp);
+        value.to_string()
+    }
+
+    fn token_count(input: u64, cached: u64, output: u64) -> serde_json::Value {
+        json!({
+            "type": "event_msg",
+            "payload": {
+                "type": "token_count",
+                "info": {
+                    "total_token_usage": {
+                        "input_tokens": input,
+                        "cached_input_tokens": cached,
+                        "output_tokens": output,
+                        "reasoning_output_tokens": 0,
+                        "total_tokens": input + output,
+                    },
+                    "last_token_usage": {
+                        "input_tokens": 0,
+                        "cached_input_tokens": 0,
+                        "output_tokens": 0,
+                        "reasoning_output_tokens": 0,
+                        "total_tokens": 0,
+                    },
+                    "model_context_window": null,
+                },
+                "rate_limits": null,
+            }
+        })
+    }
+
+    fn turn_context(model: &str, cwd: &str) -> serde_json::Value {
+        json!({
+            "type": "turn_context",
+            "payload": {
+                "cwd": cwd,
+                "approval_policy": "never",
+                "sandbox_policy": { "mode": "read-only" },
+                "model": model,
+                "summary": "auto",
+            }
+        })
+    }
+
+    fn sample_rollout() -> String {
+        let id = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+        [
+            line(
+                "2025-05-01T10:00:00.000Z",
+                json!({
+                    "type": "session_meta",
+                    "payload": {
+                        "id": id,
+                        "timestamp": "2025-05-01T10:00:00.000Z",
+                        "cwd": "/repo",
+                        "originator": "codex_cli_rs",
+                        "cli_version": "0.0.0",
+                        "instructions": null,
+                        "source": "exec",
+                    }
+                }),
+            ),
+            line("2025-05-01T10:00:01.000Z", turn_context("gpt-5", "/repo")),
+            line("2025-05-01T10:00:02.000Z", token_count(1_000, 200, 100)),
+            // Rate limit updates repeat the same totals.
+            line("2025-05-01T10:00:02.500Z", token_count(1_000, 200, 100)),
+            line("2025-05-01T23:59:59.000Z", turn_context("o3", "/repo/api")),
+            line("2025-05-01T23:59:59.500Z", token_count(1_500, 200, 300)),
+            line("2025-05-02T00:00:01.000Z", token_count(2_000, 300, 400)),
+        ]
+        .join("\n")
+    }
+
+    #[test]
+    fn parse_attributes_token_deltas_to_day_model_and_cwd() {
+        let entries = parse_rollout_usage(&sample_rollout());
+        let summary: Vec<_> = entries
+            .iter()
+            .map(|entry| {
+                (
+                    entry.day.as_str(),
+                    entry.model.as_str(),
+                    entry.cwd.to_string_lossy().into_owned(),
+                    entry.source,
+                    entry.usage,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "2025-05-01",
+                    "gpt-5",
+                    "/repo".to_string(),
+                    SessionSource::Exec,
+                    UsageTotals {
+                        input_tokens: 1_000,
+                        cached_input_tokens: 200,
+                        output_tokens: 100,
+                        reasoning_output_tokens: 0,
+                    },
+                ),
+                (
+                    "2025-05-01",
+                    "o3",
+                    "/repo/api".to_string(),
+                    SessionSource::Exec,
+                    UsageTotals {
+                        input_tokens: 500,
+                        cached_input_tokens: 0,
+                        output_tokens: 200,
+                        reasoning_output_tokens: 0,
+                    },
+                ),
+                (
+                    "2025-05-02",
+                    "o3",
+                    "/repo/api".to_string(),
+                    SessionSource::Exec,
+                    UsageTotals {
+                        input_tokens: 500,
+                        cached_input_tokens: 100,
+                        output_tokens: 100,
+                        reasoning_output_tokens: 0,
+                    },
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn counters_that_restart_count_as_new_usage() {
+        let previous = UsageTotals {
+            input_tokens: 5_000,
+            cached_input_tokens: 1_000,
+            output_tokens: 800,
+            reasoning_output_tokens: 0,
+        };
+        let current = UsageTotals {
+            input_tokens: 300,
+            cached_input_tokens: 0,
+            output_tokens: 900,
+            reasoning_output_tokens: 0,
+        };
+        assert_eq!(
+            usage_delta(&previous, &current),
+            UsageTotals {
+                input_tokens: 300,
+                cached_input_tokens: 0,
+                output_tokens: 100,
+                reasoning_output_tokens: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn aggregate_groups_filters_and_prices_rows() {
+        let entries = parse_rollout_usage(&sample_rollout());
+        let prices = HashMap::from([(
+            "o3".to_string(),
+            ModelPrice {
+                input: 10.0,
+                cached_input: Some(1.0),
+                output: 100.0,
+            },
+        )]);
+
+        let mut rows = aggregate_usage(
+            &entries,
+            &[UsageGroup::Model],
+            Some("2025-05-02"),
+            Some(&prices),
+        );
+        // 400 uncached input at $10/M, 100 cached at $1/M, 100 output at $100/M.
+        let cost = rows[0].cost_usd.take().expect("priced");
+        assert!((cost - 0.0141).abs() < 1e-9, "{cost}");
+        assert_eq!(
+            rows,
+            vec![UsageRow {
+                day: None,
+                model: Some("o3".to_string()),
+                cwd: None,
+                source: None,
+                sessions: 1,
+                usage: UsageTotals {
+                    input_tokens: 500,
+                    cached_input_tokens: 100,
+                    output_tokens: 100,
+                    reasoning_output_tokens: 0,
+                },
+                cost_usd: None,
+            }]
+        );
+
+        let rows = aggregate_usage(&entries, &[UsageGroup::Day], None, None);
+        assert_eq!(
+            rows.iter()
+                .map(|row| (row.day.as_deref(), row.usage.input_tokens, row.cost_usd))
+                .collect::<Vec<_>>(),
+            vec![
+                (Some("2025-05-01"), 1_500, None),
+                (Some("2025-05-02"), 500, None)
+            ]
+        );
+        assert_eq!(
+            unpriced_models(&entries, &HashMap::new()),
+            BTreeSet::new(),
+            "gpt-5 and o3 have built-in prices"
+        );
+    }
+}
@@ -10,6 +10,8 @@ use crate::exec::SandboxType;
 
 use crate::command_safety::is_dangerous_command::command_might_be_dangerous;
 use crate::command_safety::is_safe_command::is_known_safe_command;
+use crate::exec_policy::ExecPolicy;
+use crate::exec_policy::ExecPolicyDecision;
 use crate::protocol::AskForApproval;
 use crate::protocol::SandboxPolicy;
 
@@ -87,15 +89,38 @@ pub fn assess_patch_safety(
 /// true:
 ///
 /// - the user has explicitly approved the command
-/// - the command is on the "known safe" list
+/// - the command is on the "known safe" list or allowed by `exec_policy`
 /// - `DangerFullAccess` was specified and `UnlessTrusted` was not
+///
+/// Commands that `exec_policy` forbids are always rejected, and commands it
+/// asks about need the user's approval regardless of the sandbox policy.
 pub fn assess_command_safety(
     command: &[String],
     approval_policy: AskForApproval,
     sandbox_policy: &SandboxPolicy,
+    exec_policy: &ExecPolicy,
     approved: &HashSet<Vec<String>>,
     with_escalated_permissions: bool,
 ) -> SafetyCheck {
+    let policy_decision = exec_policy.check(command);
+    match &policy_decision {
+        Some(ExecPolicyDecision::Forbid { reason }) => {
+            return SafetyCheck::Reject {
+                reason: format!("forbidden by exec policy: {reason}"),
+            };
+        }
+        Some(ExecPolicyDecision::Ask { .. }) if !approved.contains(command) => {
+            if approval_policy == AskForApproval::Never {
+                return SafetyCheck::Reject {
+                    reason: "exec policy requires approval; rejected by user approval settings"
+                        .to_string(),
+                };
+            }
+            return SafetyCheck::AskUser;
+        }
+        _ => {}
+    }
+
     // Some commands look dangerous. Even if they are run inside a sandbox,
     // unless the user has explicitly approved them, we should ask,
     // or reject if the approval_policy tells us not to ask.
@@ -124,7 +149,8 @@ pub fn assess_command_safety(
     // `approved.contains(command)` is `true`, the user may have approved it for
     // the session _because_ they know it needs to run outside a sandbox.
 
-    if is_known_safe_command(command) || approved.contains(command) {
+    let allowed_by_policy = policy_decision == Some(ExecPolicyDecision::Allow);
+    if is_known_safe_command(command) || allowed_by_policy || approved.contains(command) {
         let user_explicitly_approved = approved.contains(command);
         return SafetyCheck::AutoApprove {
             sandbox_type: SandboxType::None,
@@ -309,6 +335,7 @@ mod tests {
         let policy_workspace_only = SandboxPolicy::WorkspaceWrite {
             writable_roots: vec![],
             network_access: false,
+            network_allowlist: vec![],
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
         };
@@ -330,6 +357,7 @@ mod tests {
         let policy_with_parent = SandboxPolicy::WorkspaceWrite {
             writable_roots: vec![parent],
             network_access: false,
+            network_allowlist: vec![],
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
         };
@@ -353,6 +381,7 @@ mod tests {
             &command,
             approval_policy,
             &sandbox_policy,
+            &ExecPolicy::default(),
             &approved,
             request_escalated_privileges,
         );
@@ -373,6 +402,7 @@ mod tests {
             &command,
             approval_policy,
             &sandbox_policy,
+            &ExecPolicy::default(),
             &approved,
             request_escalated_privileges,
         );
@@ -398,6 +428,7 @@ mod tests {
             &command,
             approval_policy,
             &sandbox_policy,
+            &ExecPolicy::default(),
             &approved,
             request_escalated_privileges,
         );
@@ -423,6 +454,7 @@ mod tests {
             &command,
             approval_policy,
             &sandbox_policy,
+            &ExecPolicy::default(),
             &approved,
             request_escalated_privileges,
         );
@@ -436,4 +468,67 @@ mod tests {
         };
         assert_eq!(safety_check, expected);
     }
+
+    fn exec_policy(contents: &str) -> ExecPolicy {
+        let dir = TempDir::new().expect("policy dir");
+        let path = dir.path().join("test.policy");
+        std::fs::write(&path, contents).expect("write policy");
+        ExecPolicy::load_files(&[path]).expect("load policy")
+    }
+
+    #[test]
+    fn exec_policy_decisions_override_sandbox_policy() {
+        let policy = exec_policy(
+            r#"
+define_program(program="make", args=["check"])
+define_program(program="make", args=["release"], ask="publishes a release")
+define_program(program="make", args=["deploy"], forbidden="deploys go through CI")
+"#,
+        );
+        let check = |command: &[&str], approval_policy, approved: &HashSet<Vec<String>>| {
+            let command: Vec<String> = command.iter().map(ToString::to_string).collect();
+            assess_command_safety(
+                &command,
+                approval_policy,
+                &SandboxPolicy::DangerFullAccess,
+                &policy,
+                approved,
+                false,
+            )
+        };
+        let none = HashSet::new();
+
+        assert_eq!(
+            check(&["make", "check"], AskForApproval::UnlessTrusted, &none),
+            SafetyCheck::AutoApprove {
+                sandbox_type: SandboxType::None,
+                user_explicitly_approved: false,
+            }
+        );
+        assert_eq!(
+            check(&["make", "release"], AskForApproval::OnRequest, &none),
+            SafetyCheck::AskUser
+        );
+        assert_eq!(
+            check(&["make", "release"], AskForApproval::Never, &none),
+            SafetyCheck::Reject {
+                reason: "exec policy requires approval; rejected by user approval settings"
+                    .to_string(),
+            }
+        );
+        let approved = HashSet::from([vec!["make".to_string(), "release".to_string()]]);
+        assert_eq!(
+            check(&["make", "release"], AskForApproval::OnRequest, &approved),
+            SafetyCheck::AutoApprove {
+                sandbox_type: SandboxType::None,
+                user_explicitly_approved: true,
+            }
+        );
+        assert_eq!(
+            check(&["make", "deploy"], AskForApproval::OnRequest, &approved),
+            SafetyCheck::Reject {
+                reason: "forbidden by exec policy: deploys go through CI".to_string(),
+            }
+        );
+    }
 }
@@ -4,7 +4,9 @@ use std::path::PathBuf;
 use tokio::process::Child;
 
 use crate::protocol::SandboxPolicy;
+use crate::resource_limits::ResourceLimiter;
 use crate::spawn::CODEX_SANDBOX_ENV_VAR;
+use crate::spawn::CODEX_SANDBOX_NETWORK_PROXY_PORT_ENV_VAR;
 use crate::spawn::StdioPolicy;
 use crate::spawn::spawn_child_async;
 
@@ -23,8 +25,20 @@ pub async fn spawn_command_under_seatbelt(
     sandbox_policy_cwd: &Path,
     stdio_policy: StdioPolicy,
     mut env: HashMap<String, String>,
+    limiter: &ResourceLimiter,
 ) -> std::io::Result<Child> {
-    let args = create_seatbelt_command_args(command, sandbox_policy, sandbox_policy_cwd);
+    let network_proxy_port = if sandbox_policy.network_allowlist().is_empty() {
+        None
+    } else {
+        env.get(CODEX_SANDBOX_NETWORK_PROXY_PORT_ENV_VAR)
+            .and_then(|port| port.parse().ok())
+    };
+    let args = create_seatbelt_command_args(
+        command,
+        sandbox_policy,
+        sandbox_policy_cwd,
+        network_proxy_port,
+    );
     let arg0 = None;
     env.insert(CODEX_SANDBOX_ENV_VAR.to_string(), "seatbelt".to_string());
     spawn_child_async(
@@ -35,6 +49,7 @@ pub async fn spawn_command_under_seatbelt(
         sandbox_policy,
         stdio_policy,
         env,
+        limiter,
     )
     .await
 }
@@ -43,6 +58,7 @@ fn create_seatbelt_command_args(
     command: Vec<String>,
     sandbox_policy: &SandboxPolicy,
     sandbox_policy_cwd: &Path,
+    network_proxy_port: Option<u16>,
 ) -> Vec<String> {
     let (file_write_policy, extra_cli_args) = {
         if sandbox_policy.has_full_disk_write_access() {
@@ -105,9 +121,12 @@ fn create_seatbelt_command_args(
 
     // TODO(mbolin): apply_patch calls must also honor the SandboxPolicy.
     let network_policy = if sandbox_policy.has_full_network_access() {
-        "(allow network-outbound)\n(allow network-inbound)\n(allow system-socket)"
+        "(allow network-outbound)\n(allow network-inbound)\n(allow system-socket)".to_string()
+    } else if let Some(port) = network_proxy_port {
+        // Only Codex's egress proxy is reachable; it enforces the allowlist.
+        format!("(allow network-outbound (remote ip \"localhost:{port}\"))\n(allow system-socket)")
     } else {
-        ""
+        String::new()
     };
 
     let full_policy = format!(
@@ -156,6 +175,7 @@ mod tests {
         let policy = SandboxPolicy::WorkspaceWrite {
             writable_roots: vec![root_with_git, root_without_git],
             network_access: false,
+            network_allowlist: vec![],
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
         };
@@ -164,6 +184,7 @@ mod tests {
             vec!["/bin/echo".to_string(), "hello".to_string()],
             &policy,
             &cwd,
+            None,
         );
 
         // Build the expected policy text using a raw string for readability.
@@ -231,6 +252,7 @@ mod tests {
         let policy = SandboxPolicy::WorkspaceWrite {
             writable_roots: vec![],
             network_access: false,
+            network_allowlist: vec![],
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
         };
@@ -239,6 +261,7 @@ mod tests {
             vec!["/bin/echo".to_string(), "hello".to_string()],
             &policy,
             root_with_git.as_path(),
+            None,
         );
 
         let tmpdir_env_var = std::env::var("TMPDIR")
@@ -301,6 +324,31 @@ mod tests {
         assert_eq!(expected_args, args);
     }
 
+    #[test]
+    fn create_seatbelt_args_only_allows_network_proxy_port() {
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![],
+            network_access: false,
+            network_allowlist: vec!["pypi.internal:443".to_string()],
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+        };
+
+        let args = create_seatbelt_command_args(
+            vec!["/bin/echo".to_string(), "hello".to_string()],
+            &policy,
+            Path::new("/"),
+            Some(3128),
+        );
+
+        let full_policy = &args[1];
+        assert!(
+            full_policy.contains(r#"(allow network-outbound (remote ip "localhost:3128"))"#),
+            "{full_policy}"
+        );
+        assert!(!full_policy.contains("(allow network-outbound)\n"));
+    }
+
     struct PopulatedTmp {
         root_with_git: PathBuf,
         root_without_git: PathBuf,
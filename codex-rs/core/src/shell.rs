@@ -305,6 +305,7 @@ mod tests {
         for (input, expected_cmd, expected_output) in cases {
             use std::collections::HashMap;
 
+            use crate::config_types::SandboxLimits;
             use crate::exec::ExecParams;
             use crate::exec::SandboxType;
             use crate::exec::process_exec_tool_call;
@@ -350,6 +351,7 @@ mod tests {
                 },
                 SandboxType::None,
                 &SandboxPolicy::DangerFullAccess,
+                &SandboxLimits::default(),
                 temp_home.path(),
                 &None,
                 None,
@@ -412,6 +414,7 @@ mod macos_tests {
             use std::collections::HashMap;
             use std::path::PathBuf;
 
+            use crate::config_types::SandboxLimits;
             use crate::exec::ExecParams;
             use crate::exec::SandboxType;
             use crate::exec::process_exec_tool_call;
@@ -458,6 +461,7 @@ mod macos_tests {
                 },
                 SandboxType::None,
                 &SandboxPolicy::DangerFullAccess,
+                &SandboxLimits::default(),
                 temp_home.path(),
                 &None,
                 None,
@@ -6,6 +6,7 @@ use tokio::process::Command;
 use tracing::trace;
 
 use crate::protocol::SandboxPolicy;
+use crate::resource_limits::ResourceLimiter;
 
 /// Experimental environment variable that will be set to some non-empty value
 /// if both of the following are true:
@@ -17,6 +18,11 @@ use crate::protocol::SandboxPolicy;
 /// attributes, so this may change in the future.
 pub const CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR: &str = "CODEX_SANDBOX_NETWORK_DISABLED";
 
+/// Set to the loopback port of Codex's egress proxy when the sandbox policy
+/// limits network access to its `network_allowlist`. The sandbox only lets the
+/// command open TCP connections to this port.
+pub const CODEX_SANDBOX_NETWORK_PROXY_PORT_ENV_VAR: &str = "CODEX_SANDBOX_NETWORK_PROXY_PORT";
+
 /// Should be set when the process is spawned under a sandbox. Currently, the
 /// value is "seatbelt" for macOS, but it may change in the future to
 /// accommodate sandboxing configuration and other sandboxing mechanisms.
@@ -35,6 +41,9 @@ pub enum StdioPolicy {
 /// For now, we take `SandboxPolicy` as a parameter to spawn_child() because
 /// we need to determine whether to set the
 /// `CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR` environment variable.
+///
+/// The child starts under the resource limits of `limiter`.
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn spawn_child_async(
     program: PathBuf,
     args: Vec<String>,
@@ -43,6 +52,7 @@ pub(crate) async fn spawn_child_async(
     sandbox_policy: &SandboxPolicy,
     stdio_policy: StdioPolicy,
     env: HashMap<String, String>,
+    limiter: &ResourceLimiter,
 ) -> std::io::Result<Child> {
     trace!(
         "spawn_child_async: {program:?} {args:?} {arg0:?} {cwd:?} {sandbox_policy:?} {stdio_policy:?} {env:?}"
@@ -85,6 +95,8 @@ pub(crate) async fn spawn_child_async(
         });
     }
 
+    limiter.configure(&mut cmd);
+
     match stdio_policy {
         StdioPolicy::RedirectForShellTool => {
             // Do not create a file descriptor for stdin because otherwise some
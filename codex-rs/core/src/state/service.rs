@@ -1,4 +1,6 @@
 use crate::RolloutRecorder;
+use crate::agents::AgentRouter;
+use crate::agents::watcher::AgentWatcher;
 use crate::commands::CommandRegistry;
 use crate::commands::watcher::CommandWatcher;
 use crate::exec_command::ExecSessionManager;
@@ -21,4 +23,8 @@ pub(crate) struct SessionServices {
     pub(crate) command_registry: Option<Arc<CommandRegistry>>,
     #[allow(dead_code)]
     pub(crate) command_watcher: Option<CommandWatcher>,
+    #[allow(dead_code)]
+    pub(crate) agent_router: Option<Arc<AgentRouter>>,
+    #[allow(dead_code)]
+    pub(crate) agent_watcher: Option<AgentWatcher>,
 }
@@ -1,9 +1,17 @@
 //! Session-wide mutable state.
 
+use std::collections::HashMap;
+use std::collections::HashSet;
+
 use codex_protocol::models::ResponseItem;
+use serde_json::Value;
+use tokio::sync::oneshot;
 
+use crate::budget::BudgetTracker;
 use crate::conversation_history::ConversationHistory;
+use crate::protocol::ElicitationAction;
 use crate::protocol::RateLimitSnapshot;
+use crate::protocol::ReviewDecision;
 use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
 
@@ -13,6 +21,14 @@ pub(crate) struct SessionState {
     pub(crate) history: ConversationHistory,
     pub(crate) token_info: Option<TokenUsageInfo>,
     pub(crate) latest_rate_limits: Option<RateLimitSnapshot>,
+    pub(crate) budget: BudgetTracker,
+    /// MCP servers the user allowed to sample the model for the rest of the
+    /// session.
+    pub(crate) approved_sampling_servers: HashSet<String>,
+    /// MCP requests waiting for the user, keyed by the id of the event that
+    /// carried them. Unlike command approvals they may outlive a turn.
+    pending_mcp_sampling: HashMap<String, oneshot::Sender<ReviewDecision>>,
+    pending_elicitations: HashMap<String, oneshot::Sender<(ElicitationAction, Option<Value>)>>,
 }
 
 impl SessionState {
@@ -73,5 +89,36 @@ impl SessionState {
         }
     }
 
+    // Pending MCP server requests
+    pub(crate) fn insert_pending_mcp_sampling(
+        &mut self,
+        id: String,
+        tx: oneshot::Sender<ReviewDecision>,
+    ) {
+        self.pending_mcp_sampling.insert(id, tx);
+    }
+
+    pub(crate) fn remove_pending_mcp_sampling(
+        &mut self,
+        id: &str,
+    ) -> Option<oneshot::Sender<ReviewDecision>> {
+        self.pending_mcp_sampling.remove(id)
+    }
+
+    pub(crate) fn insert_pending_elicitation(
+        &mut self,
+        id: String,
+        tx: oneshot::Sender<(ElicitationAction, Option<Value>)>,
+    ) {
+        self.pending_elicitations.insert(id, tx);
+    }
+
+    pub(crate) fn remove_pending_elicitation(
+        &mut self,
+        id: &str,
+    ) -> Option<oneshot::Sender<(ElicitationAction, Option<Value>)>> {
+        self.pending_elicitations.remove(id)
+    }
+
     // Pending input/approval moved to TurnState.
 }
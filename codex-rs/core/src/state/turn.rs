@@ -32,6 +32,7 @@ pub(crate) enum TaskKind {
     Regular,
     Review,
     Compact,
+    AgentCommand,
 }
 
 #[derive(Clone)]
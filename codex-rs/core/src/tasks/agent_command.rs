@@ -0,0 +1,127 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use codex_protocol::agents::AgentRunResult;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseInputItem;
+use codex_protocol::models::ResponseItem;
+
+use crate::codex::TurnContext;
+use crate::commands::CommandExecutor;
+use crate::commands::CommandInvocation;
+use crate::commands::ExecutionContext;
+use crate::commands::agents::AgentResultFormatter;
+use crate::commands::agents::OutputFormat;
+use crate::protocol::AgentRunBeginEvent;
+use crate::protocol::AgentRunEndEvent;
+use crate::protocol::Event;
+use crate::protocol::EventMsg;
+use crate::protocol::InputItem;
+use crate::protocol::TaskStartedEvent;
+use crate::state::TaskKind;
+
+use super::SessionTask;
+use super::SessionTaskContext;
+
+/// Runs an agent-backed slash command in place of a model turn and reports
+/// its structured result through [`EventMsg::AgentRunEnd`].
+pub(crate) struct AgentCommandTask {
+    executor: CommandExecutor,
+    command_text: String,
+    invocation: CommandInvocation,
+    exec_context: ExecutionContext,
+}
+
+impl AgentCommandTask {
+    pub(crate) fn new(
+        executor: CommandExecutor,
+        command_text: String,
+        invocation: CommandInvocation,
+        exec_context: ExecutionContext,
+    ) -> Self {
+        Self {
+            executor,
+            command_text,
+            invocation,
+            exec_context,
+        }
+    }
+}
+
+#[async_trait]
+impl SessionTask for AgentCommandTask {
+    fn kind(&self) -> TaskKind {
+        TaskKind::AgentCommand
+    }
+
+    async fn run(
+        self: Arc<Self>,
+        session: Arc<SessionTaskContext>,
+        ctx: Arc<TurnContext>,
+        sub_id: String,
+        input: Vec<InputItem>,
+    ) -> Option<String> {
+        let sess = session.clone_session();
+        sess.send_event(Event {
+            id: sub_id.clone(),
+            msg: EventMsg::TaskStarted(TaskStartedEvent {
+                model_context_window: ctx.client.get_model_context_window(),
+            }),
+        })
+        .await;
+
+        // Keep the invocation in history so follow-up turns can refer to it.
+        sess.record_input_and_rollout_usermsg(&ResponseInputItem::from(input))
+            .await;
+
+        let command = self.invocation.command_name.clone();
+        sess.send_event(Event {
+            id: sub_id.clone(),
+            msg: EventMsg::AgentRunBegin(AgentRunBeginEvent {
+                command: command.clone(),
+                invocation: self.command_text.clone(),
+            }),
+        })
+        .await;
+
+        let started = Instant::now();
+        let outcome = self
+            .executor
+            .execute_agent(&self.invocation, &self.exec_context)
+            .await;
+        let duration = started.elapsed();
+
+        let (result, last_agent_message) = match outcome {
+            Ok(Some(result)) => (
+                Ok(AgentRunResult::from(&result)),
+                Some(AgentResultFormatter::format(
+                    &result,
+                    OutputFormat::Markdown,
+                )),
+            ),
+            Ok(None) => (Err(format!("/{command} is not an agent command")), None),
+            Err(err) => (Err(format!("{err:#}")), None),
+        };
+
+        sess.send_event(Event {
+            id: sub_id,
+            msg: EventMsg::AgentRunEnd(AgentRunEndEvent {
+                command,
+                duration,
+                result,
+            }),
+        })
+        .await;
+
+        if let Some(text) = &last_agent_message {
+            sess.record_conversation_items(&[ResponseItem::Message {
+                id: None,
+                role: "assistant".to_string(),
+                content: vec![ContentItem::OutputText { text: text.clone() }],
+            }])
+            .await;
+        }
+        last_agent_message
+    }
+}
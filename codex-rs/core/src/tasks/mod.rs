@@ -1,3 +1,4 @@
+mod agent_command;
 mod compact;
 mod regular;
 mod review;
@@ -7,8 +8,10 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use tracing::trace;
 
+use crate::budget::BudgetExceeded;
 use crate::codex::Session;
 use crate::codex::TurnContext;
+use crate::protocol::ErrorEvent;
 use crate::protocol::Event;
 use crate::protocol::EventMsg;
 use crate::protocol::InputItem;
@@ -19,6 +22,7 @@ use crate::state::ActiveTurn;
 use crate::state::RunningTask;
 use crate::state::TaskKind;
 
+pub(crate) use agent_command::AgentCommandTask;
 pub(crate) use compact::CompactTask;
 pub(crate) use regular::RegularTask;
 pub(crate) use review::ReviewTask;
@@ -69,18 +73,45 @@ impl Session {
         let task: Arc<dyn SessionTask> = Arc::new(task);
         let task_kind = task.kind();
 
+        // Compaction keeps the conversation going rather than starting a new
+        // turn, so it does not use up `session_turns`.
+        let deadline = match self.start_budget_turn(task_kind != TaskKind::Compact).await {
+            Ok(deadline) => deadline,
+            Err(exceeded) => {
+                self.send_budget_exceeded(sub_id, exceeded).await;
+                return;
+            }
+        };
+
         let handle = {
             let session_ctx = Arc::new(SessionTaskContext::new(Arc::clone(self)));
             let ctx = Arc::clone(&turn_context);
             let task_for_run = Arc::clone(&task);
             let sub_clone = sub_id.clone();
             tokio::spawn(async move {
-                let last_agent_message = task_for_run
-                    .run(Arc::clone(&session_ctx), ctx, sub_clone.clone(), input)
-                    .await;
-                // Emit completion uniformly from spawn site so all tasks share the same lifecycle.
+                let run = Arc::clone(&task_for_run).run(
+                    Arc::clone(&session_ctx),
+                    ctx,
+                    sub_clone.clone(),
+                    input,
+                );
                 let sess = session_ctx.clone_session();
-                sess.on_task_finished(sub_clone, last_agent_message).await;
+                let last_agent_message = match deadline {
+                    Some(deadline) => tokio::select! {
+                        last_agent_message = run => last_agent_message,
+                        () = tokio::time::sleep_until(deadline) => {
+                            task_for_run.abort(Arc::clone(&session_ctx), &sub_clone).await;
+                            sess.record_budget_deadline_reached().await;
+                            None
+                        }
+                    },
+                    None => run.await,
+                };
+                // Emit completion uniformly from spawn site so all tasks share the same lifecycle.
+                match sess.take_budget_exceeded().await {
+                    Some(exceeded) => sess.on_task_budget_exceeded(sub_clone, exceeded).await,
+                    None => sess.on_task_finished(sub_clone, last_agent_message).await,
+                }
             })
             .abort_handle()
         };
@@ -118,6 +149,39 @@ impl Session {
         self.send_event(event).await;
     }
 
+    /// Ends the task because a budget ran out, telling the client why.
+    pub(crate) async fn on_task_budget_exceeded(
+        self: &Arc<Self>,
+        sub_id: String,
+        exceeded: BudgetExceeded,
+    ) {
+        let mut active = self.active_turn.lock().await;
+        if let Some(at) = active.as_mut()
+            && at.remove_task(&sub_id)
+        {
+            *active = None;
+        }
+        drop(active);
+        self.send_budget_exceeded(sub_id, exceeded).await;
+    }
+
+    async fn send_budget_exceeded(&self, sub_id: String, exceeded: BudgetExceeded) {
+        self.send_event(Event {
+            id: sub_id.clone(),
+            msg: EventMsg::Error(ErrorEvent {
+                message: exceeded.to_string(),
+            }),
+        })
+        .await;
+        self.send_event(Event {
+            id: sub_id,
+            msg: EventMsg::TurnAborted(TurnAbortedEvent {
+                reason: TurnAbortReason::BudgetExceeded,
+            }),
+        })
+        .await;
+    }
+
     async fn register_new_active_task(&self, sub_id: String, task: RunningTask) {
         let mut active = self.active_turn.lock().await;
         let mut turn = ActiveTurn::default();
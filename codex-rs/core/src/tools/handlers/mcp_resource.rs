@@ -0,0 +1,107 @@
+use async_trait::async_trait;
+use mcp_types::ReadResourceResult;
+use mcp_types::ReadResourceResultContents;
+use serde::Deserialize;
+
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct McpResourceHandler;
+
+#[derive(Deserialize)]
+struct ReadMcpResourceArgs {
+    server: String,
+    uri: String,
+}
+
+#[async_trait]
+impl ToolHandler for McpResourceHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session, payload, ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "read_mcp_resource handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let args: ReadMcpResourceArgs = serde_json::from_str(&arguments).map_err(|e| {
+            FunctionCallError::RespondToModel(format!("failed to parse function arguments: {e:?}"))
+        })?;
+
+        let result = session
+            .read_mcp_resource(&args.server, &args.uri)
+            .await
+            .map_err(|e| FunctionCallError::RespondToModel(format!("{e:#}")))?;
+
+        Ok(ToolOutput::Function {
+            content: format_resource_contents(&result),
+            success: Some(true),
+        })
+    }
+}
+
+/// Concatenates the text contents of a resource. Binary contents are not sent
+/// to the model; they are replaced by a note naming their URI and MIME type.
+fn format_resource_contents(result: &ReadResourceResult) -> String {
+    if result.contents.is_empty() {
+        return "resource is empty".to_string();
+    }
+    result
+        .contents
+        .iter()
+        .map(|contents| match contents {
+            ReadResourceResultContents::TextResourceContents(text) => text.text.clone(),
+            ReadResourceResultContents::BlobResourceContents(blob) => format!(
+                "[binary content of {} ({}) omitted]",
+                blob.uri,
+                blob.mime_type.as_deref().unwrap_or("unknown type")
+            ),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcp_types::BlobResourceContents;
+    use mcp_types::TextResourceContents;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn formats_text_and_binary_contents() {
+        let result = ReadResourceResult {
+            contents: vec![
+                ReadResourceResultContents::TextResourceContents(TextResourceContents {
+                    mime_type: Some("text/markdown".to_string()),
+                    text: "# Deploying".to_string(),
+                    uri: "docs://deploy.md".to_string(),
+                }),
+                ReadResourceResultContents::BlobResourceContents(BlobResourceContents {
+                    blob: "aGVsbG8=".to_string(),
+                    mime_type: Some("image/png".to_string()),
+                    uri: "docs://diagram.png".to_string(),
+                }),
+            ],
+        };
+
+        assert_eq!(
+            format_resource_contents(&result),
+            "# Deploying\n[binary content of docs://diagram.png (image/png) omitted]"
+        );
+    }
+}
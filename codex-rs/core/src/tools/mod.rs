@@ -129,10 +129,13 @@ pub(crate) async fn handle_container_exec_with_params(
         None => ExecutionMode::Shell,
     };
 
-    sess.services.executor.update_environment(
-        turn_context.sandbox_policy.clone(),
-        turn_context.cwd.clone(),
-    );
+    sess.services
+        .executor
+        .update_environment(
+            turn_context.sandbox_policy.clone(),
+            turn_context.cwd.clone(),
+        )
+        .await;
 
     let prepared_exec = PreparedExec::new(
         exec_command_context,
@@ -167,9 +170,11 @@ pub(crate) async fn handle_container_exec_with_params(
             }
         }
         Err(ExecError::Function(err)) => Err(truncate_function_error(err)),
-        Err(ExecError::Codex(CodexErr::Sandbox(SandboxErr::Timeout { output }))) => Err(
-            FunctionCallError::RespondToModel(format_exec_output_apply_patch(&output)),
-        ),
+        Err(ExecError::Codex(CodexErr::Sandbox(
+            SandboxErr::Timeout { output } | SandboxErr::LimitExceeded { output, .. },
+        ))) => Err(FunctionCallError::RespondToModel(
+            format_exec_output_apply_patch(&output),
+        )),
         Err(ExecError::Codex(err)) => {
             let message = format!("execution error: {err:?}");
             Err(FunctionCallError::RespondToModel(format_exec_output(
@@ -230,6 +235,12 @@ pub fn format_exec_output_str(exec_output: &ExecToolCallOutput) -> String {
         return format_exec_output(&prefixed);
     }
 
+    if let Some(limit) = exec_output.limit_exceeded {
+        let prefixed =
+            format!("command was killed after exceeding its sandbox {limit} limit\n{content}");
+        return format_exec_output(&prefixed);
+    }
+
     format_exec_output(content)
 }
 
@@ -33,8 +33,9 @@ impl ToolRouter {
     pub fn from_config(
         config: &ToolsConfig,
         mcp_tools: Option<HashMap<String, mcp_types::Tool>>,
+        include_mcp_resource_tool: bool,
     ) -> Self {
-        let builder = build_specs(config, mcp_tools);
+        let builder = build_specs(config, mcp_tools, include_mcp_resource_tool);
         let (specs, registry) = builder.build();
 
         Self { registry, specs }
@@ -145,6 +146,14 @@ impl ToolRouter {
         let payload_outputs_custom = matches!(payload, ToolPayload::Custom { .. });
         let failure_call_id = call_id.clone();
 
+        if let Err(exceeded) = session.record_tool_call().await {
+            return Ok(Self::failure_response(
+                failure_call_id,
+                payload_outputs_custom,
+                FunctionCallError::RespondToModel(exceeded.to_string()),
+            ));
+        }
+
         let invocation = ToolInvocation {
             session,
             turn,
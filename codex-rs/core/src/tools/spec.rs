@@ -258,6 +258,35 @@ fn create_view_image_tool() -> ToolSpec {
     })
 }
 
+fn create_read_mcp_resource_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "server".to_string(),
+        JsonSchema::String {
+            description: Some("Name of the MCP server that offers the resource.".to_string()),
+        },
+    );
+    properties.insert(
+        "uri".to_string(),
+        JsonSchema::String {
+            description: Some("URI of the resource.".to_string()),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "read_mcp_resource".to_string(),
+        description: "Reads a resource offered by an MCP server. The user refers to resources \
+                      as `@server:uri`."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["server".to_string(), "uri".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
 fn create_test_sync_tool() -> ToolSpec {
     let mut properties = BTreeMap::new();
     properties.insert(
@@ -650,9 +679,13 @@ fn sanitize_json_schema(value: &mut JsonValue) {
 }
 
 /// Builds the tool registry builder while collecting tool specs for later serialization.
+///
+/// `include_mcp_resource_tool` adds `read_mcp_resource`, which should only be
+/// offered when an MCP server has resources.
 pub(crate) fn build_specs(
     config: &ToolsConfig,
     mcp_tools: Option<HashMap<String, mcp_types::Tool>>,
+    include_mcp_resource_tool: bool,
 ) -> ToolRegistryBuilder {
     use crate::exec_command::EXEC_COMMAND_TOOL_NAME;
     use crate::exec_command::WRITE_STDIN_TOOL_NAME;
@@ -663,6 +696,7 @@ pub(crate) fn build_specs(
     use crate::tools::handlers::GrepFilesHandler;
     use crate::tools::handlers::ListDirHandler;
     use crate::tools::handlers::McpHandler;
+    use crate::tools::handlers::McpResourceHandler;
     use crate::tools::handlers::PlanHandler;
     use crate::tools::handlers::ReadFileHandler;
     use crate::tools::handlers::ShellHandler;
@@ -773,6 +807,12 @@ pub(crate) fn build_specs(
         builder.register_handler("view_image", view_image_handler);
     }
 
+    if include_mcp_resource_tool {
+        let mcp_resource_handler = Arc::new(McpResourceHandler);
+        builder.push_spec_with_parallel_support(create_read_mcp_resource_tool(), true);
+        builder.register_handler("read_mcp_resource", mcp_resource_handler);
+    }
+
     if let Some(mcp_tools) = mcp_tools {
         let mut entries: Vec<(String, mcp_types::Tool)> = mcp_tools.into_iter().collect();
         entries.sort_by(|a, b| a.0.cmp(&b.0));
@@ -854,7 +894,7 @@ mod tests {
             include_view_image_tool: true,
             experimental_unified_exec_tool: true,
         });
-        let (tools, _) = build_specs(&config, Some(HashMap::new())).build();
+        let (tools, _) = build_specs(&config, Some(HashMap::new()), false).build();
 
         assert_eq_tool_names(
             &tools,
@@ -874,7 +914,7 @@ mod tests {
             include_view_image_tool: true,
             experimental_unified_exec_tool: true,
         });
-        let (tools, _) = build_specs(&config, Some(HashMap::new())).build();
+        let (tools, _) = build_specs(&config, Some(HashMap::new()), false).build();
 
         assert_eq_tool_names(
             &tools,
@@ -882,6 +922,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_build_specs_read_mcp_resource_tool() {
+        let model_family = find_family_for_model("codex-mini-latest")
+            .expect("codex-mini-latest should be a valid model family");
+        let config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            include_plan_tool: false,
+            include_apply_patch_tool: false,
+            include_web_search_request: false,
+            use_streamable_shell_tool: false,
+            include_view_image_tool: false,
+            experimental_unified_exec_tool: true,
+        });
+        let (tools, _) = build_specs(&config, Some(HashMap::new()), true).build();
+
+        assert_eq_tool_names(&tools, &["unified_exec", "read_mcp_resource"]);
+        assert!(find_tool(&tools, "read_mcp_resource").supports_parallel_tool_calls);
+    }
+
     #[test]
     #[ignore]
     fn test_parallel_support_flags() {
@@ -896,7 +955,7 @@ mod tests {
             include_view_image_tool: false,
             experimental_unified_exec_tool: true,
         });
-        let (tools, _) = build_specs(&config, None).build();
+        let (tools, _) = build_specs(&config, None, false).build();
 
         assert!(!find_tool(&tools, "unified_exec").supports_parallel_tool_calls);
         assert!(find_tool(&tools, "grep_files").supports_parallel_tool_calls);
@@ -917,7 +976,7 @@ mod tests {
             include_view_image_tool: false,
             experimental_unified_exec_tool: false,
         });
-        let (tools, _) = build_specs(&config, None).build();
+        let (tools, _) = build_specs(&config, None, false).build();
 
         assert!(
             tools
@@ -985,6 +1044,7 @@ mod tests {
                     description: Some("Do something cool".to_string()),
                 },
             )])),
+            false,
         )
         .build();
 
@@ -1104,7 +1164,7 @@ mod tests {
             ),
         ]);
 
-        let (tools, _) = build_specs(&config, Some(tools_map)).build();
+        let (tools, _) = build_specs(&config, Some(tools_map), false).build();
         // Expect unified_exec first, followed by MCP tools sorted by fully-qualified name.
         assert_eq_tool_names(
             &tools,
@@ -1153,6 +1213,7 @@ mod tests {
                     description: Some("Search docs".to_string()),
                 },
             )])),
+            false,
         )
         .build();
 
@@ -1220,6 +1281,7 @@ mod tests {
                     description: Some("Pagination".to_string()),
                 },
             )])),
+            false,
         )
         .build();
 
@@ -1284,6 +1346,7 @@ mod tests {
                     description: Some("Tags".to_string()),
                 },
             )])),
+            false,
         )
         .build();
 
@@ -1351,6 +1414,7 @@ mod tests {
                     description: Some("AnyOf Value".to_string()),
                 },
             )])),
+            false,
         )
         .build();
 
@@ -1455,6 +1519,7 @@ mod tests {
                     description: Some("Do something cool".to_string()),
                 },
             )])),
+            false,
         )
         .build();
 
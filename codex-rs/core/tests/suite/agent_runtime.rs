@@ -0,0 +1,276 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use codex_app_server_protocol::AuthMode;
+use codex_core::ModelClient;
+use codex_core::ModelProviderInfo;
+use codex_core::WireApi;
+use codex_core::agents::ActivationScore;
+use codex_core::agents::Agent;
+use codex_core::agents::AgentId;
+use codex_core::agents::AgentPermissions;
+use codex_core::agents::AgentResult;
+use codex_core::agents::AgentRuntime;
+use codex_core::agents::AgentToolkit;
+use codex_core::agents::Severity;
+use codex_core::agents::Task;
+use codex_core::agents::TaskContext;
+use codex_core::agents::builtin::ReviewAgent;
+use codex_core::agents::permissions::FileAccessPolicy;
+use codex_core::agents::runtime::run_with_fallback;
+use codex_otel::otel_event_manager::OtelEventManager;
+use codex_protocol::ConversationId;
+use core_test_support::load_default_config_for_test;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_function_call;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use tempfile::TempDir;
+use wiremock::MockServer;
+
+fn runtime_for(server: &MockServer, codex_home: &TempDir) -> AgentRuntime {
+    let provider = ModelProviderInfo {
+        name: "mock".into(),
+        base_url: Some(format!("{}/v1", server.uri())),
+        env_key: None,
+        env_key_instructions: None,
+        wire_api: WireApi::Responses,
+        query_params: None,
+        http_headers: None,
+        env_http_headers: None,
+        request_max_retries: Some(0),
+        stream_max_retries: Some(0),
+        stream_idle_timeout_ms: Some(5_000),
+        requires_openai_auth: false,
+    };
+
+    let mut config = load_default_config_for_test(codex_home);
+    config.model_provider_id = provider.name.clone();
+    config.model_provider = provider.clone();
+    let effort = config.model_reasoning_effort;
+    let summary = config.model_reasoning_summary;
+    let config = Arc::new(config);
+    let conversation_id = ConversationId::new();
+    let otel_event_manager = OtelEventManager::new(
+        conversation_id,
+        config.model.as_str(),
+        config.model_family.slug.as_str(),
+        None,
+        Some(AuthMode::ApiKey),
+        false,
+        "test".to_string(),
+    );
+
+    AgentRuntime::new(ModelClient::new(
+        config,
+        None,
+        otel_event_manager,
+        provider,
+        effort,
+        summary,
+        conversation_id,
+    ))
+}
+
+fn review_task(workspace: &TempDir) -> Task {
+    Task {
+        context: TaskContext {
+            file_paths: vec![workspace.path().join("lib.rs")],
+            user_intent: "Review lib.rs".to_string(),
+            ..Default::default()
+        },
+        additional_instructions: None,
+    }
+}
+
+fn toolkit_for(agent: &dyn Agent, workspace: &TempDir) -> AgentToolkit {
+    AgentToolkit::new(
+        agent.id(),
+        agent.permissions().clone(),
+        workspace.path().to_path_buf(),
+    )
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn agent_runtime_reads_files_and_parses_findings() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let codex_home = TempDir::new()?;
+    let workspace = TempDir::new()?;
+    std::fs::write(
+        workspace.path().join("lib.rs"),
+        "fn main() { x.unwrap(); }\n",
+    )?;
+
+    let findings = json!({
+        "findings": [{
+            "severity": "warning",
+            "category": "Error Handling",
+            "message": "Avoid unwrap",
+            "file": "lib.rs",
+            "line": 1
+        }]
+    })
+    .to_string();
+    let mock = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_function_call("call-1", "read_file", r#"{"file_path":"lib.rs"}"#),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_response_created("resp-2"),
+                ev_assistant_message("msg-1", &findings),
+                ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await;
+
+    let agent = ReviewAgent::new();
+    let runtime = runtime_for(&server, &codex_home);
+    let toolkit = toolkit_for(&agent, &workspace);
+    let result = runtime
+        .run(&agent, review_task(&workspace), &toolkit)
+        .await?;
+
+    let AgentResult::CodeReview { findings } = result else {
+        panic!("expected code review result");
+    };
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].severity, Severity::Warning);
+    assert_eq!(findings[0].message, "Avoid unwrap");
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 2);
+    let first = requests[0].body_json();
+    assert!(
+        first["instructions"]
+            .as_str()
+            .unwrap_or_default()
+            .starts_with(agent.system_prompt())
+    );
+    let tool_names: Vec<_> = first["tools"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|t| t["name"].as_str())
+        .collect();
+    assert_eq!(tool_names, vec!["read_file"]);
+    let output = requests[1].function_call_output("call-1");
+    assert_eq!(output["output"], "fn main() { x.unwrap(); }\n");
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn agent_runtime_stops_at_max_iterations() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let codex_home = TempDir::new()?;
+    let workspace = TempDir::new()?;
+    std::fs::write(workspace.path().join("lib.rs"), "fn main() {}\n")?;
+
+    let tool_call = || {
+        sse(vec![
+            ev_response_created("resp"),
+            ev_function_call("call", "read_file", r#"{"file_path":"lib.rs"}"#),
+            ev_completed("resp"),
+        ])
+    };
+    let mock = mount_sse_sequence(&server, vec![tool_call(), tool_call()]).await;
+
+    let agent = LimitedAgent {
+        inner: ReviewAgent::new(),
+        permissions: AgentPermissions {
+            file_access: FileAccessPolicy::read_only(),
+            max_iterations: 2,
+            ..Default::default()
+        },
+    };
+    let runtime = runtime_for(&server, &codex_home);
+    let toolkit = toolkit_for(&agent, &workspace);
+    let err = runtime
+        .run(&agent, review_task(&workspace), &toolkit)
+        .await
+        .expect_err("runtime should give up after max_iterations");
+
+    assert!(err.to_string().contains("within 2 iterations"));
+    assert_eq!(mock.requests().len(), 2);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn agent_runtime_falls_back_to_heuristics_on_model_failure() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let codex_home = TempDir::new()?;
+    let workspace = TempDir::new()?;
+    std::fs::write(
+        workspace.path().join("lib.rs"),
+        "fn main() {\n    let value = risky().unwrap();\n}\n",
+    )?;
+
+    // No responses are mounted, so every model request fails.
+    let agent = ReviewAgent::new();
+    let runtime = runtime_for(&server, &codex_home);
+    let toolkit = toolkit_for(&agent, &workspace);
+    let result =
+        run_with_fallback(Some(&runtime), &agent, review_task(&workspace), &toolkit).await?;
+
+    let AgentResult::CodeReview { findings } = result else {
+        panic!("expected code review result");
+    };
+    assert!(findings.iter().any(|f| f.message.contains("unwrap")));
+
+    Ok(())
+}
+
+/// Wraps the review agent with a custom permission set.
+struct LimitedAgent {
+    inner: ReviewAgent,
+    permissions: AgentPermissions,
+}
+
+#[async_trait]
+impl Agent for LimitedAgent {
+    fn id(&self) -> AgentId {
+        self.inner.id()
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn description(&self) -> &str {
+        self.inner.description()
+    }
+
+    fn can_handle(&self, context: &TaskContext) -> ActivationScore {
+        self.inner.can_handle(context)
+    }
+
+    async fn execute(&self, task: Task, toolkit: &AgentToolkit) -> anyhow::Result<AgentResult> {
+        self.inner.execute(task, toolkit).await
+    }
+
+    fn permissions(&self) -> &AgentPermissions {
+        &self.permissions
+    }
+
+    fn system_prompt(&self) -> &str {
+        self.inner.system_prompt()
+    }
+}
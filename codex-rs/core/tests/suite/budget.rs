@@ -0,0 +1,129 @@
+#![cfg(not(target_os = "windows"))]
+
+use codex_core::config_types::BudgetLimits;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::InputItem;
+use codex_core::protocol::Op;
+use codex_core::protocol::TurnAbortReason;
+use core_test_support::responses;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_local_shell_call;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+
+async fn submit_text(codex: &codex_core::CodexConversation, text: &str) -> anyhow::Result<()> {
+    codex
+        .submit(Op::UserInput {
+            items: vec![InputItem::Text { text: text.into() }],
+        })
+        .await?;
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn tool_call_over_turn_budget_aborts_turn() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let response_mock = responses::mount_sse_once(
+        &server,
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_local_shell_call("call-1", "completed", vec!["/bin/echo", "hi"]),
+            ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+
+    let codex = test_codex()
+        .with_config(|config| {
+            config.budget = BudgetLimits {
+                turn_tool_calls: Some(0),
+                ..Default::default()
+            };
+        })
+        .build(&server)
+        .await?
+        .codex;
+
+    submit_text(&codex, "run a command").await?;
+
+    let EventMsg::Error(error) =
+        wait_for_event(&codex, |ev| matches!(ev, EventMsg::Error(_))).await
+    else {
+        unreachable!()
+    };
+    assert_eq!(
+        error.message,
+        "Turn tool call budget exhausted (0 of 0 used)"
+    );
+    let EventMsg::TurnAborted(aborted) =
+        wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnAborted(_))).await
+    else {
+        unreachable!()
+    };
+    assert_eq!(aborted.reason, TurnAbortReason::BudgetExceeded);
+
+    // The refused call is not sent back to the model.
+    assert_eq!(response_mock.requests().len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn spent_session_turn_budget_refuses_next_turn() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let response_mock = responses::mount_sse(
+        &server,
+        sse(vec![
+            ev_assistant_message("msg-1", "done"),
+            ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+
+    let codex = test_codex()
+        .with_config(|config| {
+            config.budget = BudgetLimits {
+                session_turns: Some(1),
+                ..Default::default()
+            };
+        })
+        .build(&server)
+        .await?
+        .codex;
+
+    submit_text(&codex, "first").await?;
+    let EventMsg::TokenCount(token_count) =
+        wait_for_event(&codex, |ev| matches!(ev, EventMsg::TokenCount(_))).await
+    else {
+        unreachable!()
+    };
+    assert_eq!(token_count.budget.and_then(|budget| budget.turns), Some(0));
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await;
+
+    submit_text(&codex, "second").await?;
+    let EventMsg::Error(error) =
+        wait_for_event(&codex, |ev| matches!(ev, EventMsg::Error(_))).await
+    else {
+        unreachable!()
+    };
+    assert_eq!(error.message, "Session turn budget exhausted (1 of 1 used)");
+    let EventMsg::TurnAborted(aborted) =
+        wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnAborted(_))).await
+    else {
+        unreachable!()
+    };
+    assert_eq!(aborted.reason, TurnAbortReason::BudgetExceeded);
+    assert_eq!(response_mock.requests().len(), 1);
+
+    Ok(())
+}
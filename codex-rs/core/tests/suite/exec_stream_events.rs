@@ -5,6 +5,7 @@ use std::path::PathBuf;
 use std::time::Duration;
 
 use async_channel::Receiver;
+use codex_core::config_types::SandboxLimits;
 use codex_core::error::CodexErr;
 use codex_core::error::SandboxErr;
 use codex_core::exec::ExecParams;
@@ -15,6 +16,7 @@ use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::ExecCommandOutputDeltaEvent;
 use codex_core::protocol::ExecOutputStream;
+use codex_core::protocol::ExecResourceLimit;
 use codex_core::protocol::SandboxPolicy;
 
 fn collect_stdout_events(rx: Receiver<Event>) -> Vec<u8> {
@@ -65,6 +67,7 @@ async fn test_exec_stdout_stream_events_echo() {
         params,
         SandboxType::None,
         &policy,
+        &SandboxLimits::default(),
         cwd.as_path(),
         &None,
         Some(stdout_stream),
@@ -117,6 +120,7 @@ async fn test_exec_stderr_stream_events_echo() {
         params,
         SandboxType::None,
         &policy,
+        &SandboxLimits::default(),
         cwd.as_path(),
         &None,
         Some(stdout_stream),
@@ -172,6 +176,7 @@ async fn test_aggregated_output_interleaves_in_order() {
         params,
         SandboxType::None,
         &policy,
+        &SandboxLimits::default(),
         cwd.as_path(),
         &None,
         None,
@@ -210,6 +215,7 @@ async fn test_exec_timeout_returns_partial_output() {
         params,
         SandboxType::None,
         &policy,
+        &SandboxLimits::default(),
         cwd.as_path(),
         &None,
         None,
@@ -227,3 +233,137 @@ async fn test_exec_timeout_returns_partial_output() {
     assert!(output.duration >= Duration::from_millis(200));
     assert!(output.timed_out);
 }
+
+#[tokio::test]
+async fn test_exec_output_limit_kills_command() {
+    let cmd = vec![
+        "/bin/sh".to_string(),
+        "-c".to_string(),
+        "while :; do printf '0123456789'; done".to_string(),
+    ];
+
+    let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let params = ExecParams {
+        command: cmd,
+        cwd: cwd.clone(),
+        timeout_ms: Some(10_000),
+        env: HashMap::new(),
+        with_escalated_permissions: None,
+        justification: None,
+    };
+
+    let policy = SandboxPolicy::new_read_only_policy();
+    let limits = SandboxLimits {
+        max_output_bytes: Some(1024),
+        ..Default::default()
+    };
+
+    let result = process_exec_tool_call(
+        params,
+        SandboxType::None,
+        &policy,
+        &limits,
+        cwd.as_path(),
+        &None,
+        None,
+    )
+    .await;
+
+    let Err(CodexErr::Sandbox(SandboxErr::LimitExceeded { limit, output })) = result else {
+        panic!("expected limit exceeded error, got {result:?}");
+    };
+
+    assert_eq!(limit, ExecResourceLimit::OutputSize);
+    assert_eq!(output.limit_exceeded, Some(ExecResourceLimit::OutputSize));
+    assert_eq!(output.stdout.text.len(), 1024);
+    assert!(!output.timed_out);
+}
+
+#[tokio::test]
+async fn test_exec_cpu_limit_kills_command() {
+    let cmd = vec![
+        "/bin/sh".to_string(),
+        "-c".to_string(),
+        "while :; do :; done".to_string(),
+    ];
+
+    let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let params = ExecParams {
+        command: cmd,
+        cwd: cwd.clone(),
+        timeout_ms: Some(10_000),
+        env: HashMap::new(),
+        with_escalated_permissions: None,
+        justification: None,
+    };
+
+    let policy = SandboxPolicy::new_read_only_policy();
+    let limits = SandboxLimits {
+        cpu_time_secs: Some(1),
+        ..Default::default()
+    };
+
+    let result = process_exec_tool_call(
+        params,
+        SandboxType::None,
+        &policy,
+        &limits,
+        cwd.as_path(),
+        &None,
+        None,
+    )
+    .await;
+
+    let Err(CodexErr::Sandbox(SandboxErr::LimitExceeded { limit, output })) = result else {
+        panic!("expected limit exceeded error, got {result:?}");
+    };
+
+    assert_eq!(limit, ExecResourceLimit::CpuTime);
+    assert!(!output.timed_out);
+}
+
+#[tokio::test]
+async fn test_exec_memory_limit_kills_command() {
+    // bash reports a refused allocation on stderr rather than crashing, so
+    // the limit is recognized whether a cgroup or `RLIMIT_AS` enforces it.
+    let cmd = vec![
+        "/bin/bash".to_string(),
+        "-c".to_string(),
+        "x=$(head -c 200000000 /dev/zero | tr '\\0' a); echo ${#x}".to_string(),
+    ];
+
+    let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let params = ExecParams {
+        command: cmd,
+        cwd: cwd.clone(),
+        timeout_ms: Some(10_000),
+        env: HashMap::new(),
+        with_escalated_permissions: None,
+        justification: None,
+    };
+
+    let policy = SandboxPolicy::new_read_only_policy();
+    let limits = SandboxLimits {
+        memory_mb: Some(64),
+        ..Default::default()
+    };
+
+    let result = process_exec_tool_call(
+        params,
+        SandboxType::None,
+        &policy,
+        &limits,
+        cwd.as_path(),
+        &None,
+        None,
+    )
+    .await;
+
+    let Err(CodexErr::Sandbox(SandboxErr::LimitExceeded { limit, output })) = result else {
+        panic!("expected limit exceeded error, got {result:?}");
+    };
+
+    assert_eq!(limit, ExecResourceLimit::Memory);
+    assert_eq!(output.limit_exceeded, Some(ExecResourceLimit::Memory));
+    assert!(!output.timed_out);
+}
@@ -2,6 +2,8 @@
 
 #[cfg(not(target_os = "windows"))]
 mod abort_tasks;
+mod agent_runtime;
+mod budget;
 mod cli_stream;
 mod client;
 mod compact;
@@ -1,3 +1,6 @@
+use codex_core::approval_rules::ApprovalRule;
+use codex_core::approval_rules::ApprovalRules;
+use codex_core::approval_rules::project_for_cwd;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::InputItem;
@@ -939,6 +942,158 @@ async fn handle_container_exec_user_approved_for_session_records_tool_decision()
     ));
 }
 
+#[tokio::test]
+#[traced_test]
+async fn handle_container_exec_user_approved_for_project_saves_rule() {
+    let server = start_mock_server().await;
+
+    mount_sse(
+        &server,
+        sse(vec![
+            ev_local_shell_call(
+                "user_approved_project_call",
+                "completed",
+                vec!["/bin/echo", "remember"],
+            ),
+            ev_completed("done"),
+        ]),
+    )
+    .await;
+
+    let TestCodex {
+        codex, home, cwd, ..
+    } = test_codex()
+        .with_config(|config| {
+            config.approval_policy = AskForApproval::UnlessTrusted;
+            config.model_provider.request_max_retries = Some(0);
+            config.model_provider.stream_max_retries = Some(0);
+        })
+        .build(&server)
+        .await
+        .unwrap();
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![InputItem::Text {
+                text: "remember".into(),
+            }],
+        })
+        .await
+        .unwrap();
+
+    wait_for_event_with_timeout(
+        &codex,
+        |ev| matches!(ev, EventMsg::ExecApprovalRequest(_)),
+        Duration::from_secs(5),
+    )
+    .await;
+
+    codex
+        .submit(Op::ExecApproval {
+            id: "0".into(),
+            decision: ReviewDecision::ApprovedForProject,
+        })
+        .await
+        .unwrap();
+
+    wait_for_event_with_timeout(
+        &codex,
+        |ev| matches!(ev, EventMsg::TokenCount(_)),
+        Duration::from_secs(5),
+    )
+    .await;
+
+    logs_assert(tool_decision_assertion(
+        "user_approved_project_call",
+        "approvedforproject",
+        "user",
+    ));
+    let rules = ApprovalRules::load(home.path()).unwrap();
+    let command = vec!["/bin/echo".to_string(), "remember".to_string()];
+    let rule = rules
+        .find_match(&project_for_cwd(cwd.path()), &command, chrono::Utc::now())
+        .expect("approval rule saved for project");
+    assert_eq!(rule.pattern, "/bin/echo remember");
+}
+
+#[tokio::test]
+#[traced_test]
+async fn handle_container_exec_approval_rule_records_tool_decision() {
+    let server = start_mock_server().await;
+
+    mount_sse(
+        &server,
+        sse(vec![
+            ev_local_shell_call(
+                "rule_approved_call",
+                "completed",
+                vec!["/bin/echo", "ruled"],
+            ),
+            ev_completed("done"),
+        ]),
+    )
+    .await;
+
+    // Keep `home` alive: it holds the saved rule.
+    let TestCodex {
+        codex, home: _home, ..
+    } = test_codex()
+        .with_config(|config| {
+            config.approval_policy = AskForApproval::UnlessTrusted;
+            config.model_provider.request_max_retries = Some(0);
+            config.model_provider.stream_max_retries = Some(0);
+            let rules = ApprovalRules {
+                rules: vec![ApprovalRule::new(
+                    project_for_cwd(&config.cwd),
+                    "/bin/echo *",
+                )],
+            };
+            rules.save(&config.codex_home).unwrap();
+        })
+        .build(&server)
+        .await
+        .unwrap();
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![InputItem::Text {
+                text: "ruled".into(),
+            }],
+        })
+        .await
+        .unwrap();
+
+    let event = wait_for_event_with_timeout(
+        &codex,
+        |ev| {
+            matches!(
+                ev,
+                EventMsg::ExecApprovalRuleApplied(_) | EventMsg::ExecApprovalRequest(_)
+            )
+        },
+        Duration::from_secs(5),
+    )
+    .await;
+    let EventMsg::ExecApprovalRuleApplied(applied) = event else {
+        panic!("expected the approval rule to apply, got {event:?}");
+    };
+    assert_eq!(applied.call_id, "rule_approved_call");
+    assert_eq!(applied.pattern, "/bin/echo *");
+
+    wait_for_event_with_timeout(
+        &codex,
+        |ev| matches!(ev, EventMsg::TokenCount(_)),
+        Duration::from_secs(5),
+    )
+    .await;
+
+    logs_assert(tool_decision_assertion(
+        "rule_approved_call",
+        "approvedforproject",
+        "config",
+    ));
+}
+
 #[tokio::test]
 #[traced_test]
 async fn handle_sandbox_error_user_approves_retry_records_tool_decision() {
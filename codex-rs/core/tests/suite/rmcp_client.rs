@@ -10,19 +10,27 @@ use std::time::UNIX_EPOCH;
 use codex_core::config_types::McpServerConfig;
 use codex_core::config_types::McpServerTransportConfig;
 
+use codex_core::CodexConversation;
 use codex_core::protocol::AskForApproval;
+use codex_core::protocol::ElicitationAction;
+use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::InputItem;
+use codex_core::protocol::McpListToolsResponseEvent;
+use codex_core::protocol::McpServerStatus;
 use codex_core::protocol::Op;
+use codex_core::protocol::ReviewDecision;
 use codex_core::protocol::SandboxPolicy;
 use codex_protocol::config_types::ReasoningSummary;
 use core_test_support::responses;
 use core_test_support::responses::mount_sse_once_match;
+use core_test_support::responses::mount_sse_sequence;
 use core_test_support::skip_if_no_network;
 use core_test_support::test_codex::test_codex;
 use core_test_support::wait_for_event;
 use core_test_support::wait_for_event_with_timeout;
 use escargot::CargoBuild;
+use mcp_types::ContentBlock;
 use serde_json::Value;
 use serial_test::serial;
 use tempfile::tempdir;
@@ -88,6 +96,7 @@ async fn stdio_server_round_trip() -> anyhow::Result<()> {
                     },
                     startup_timeout_sec: Some(Duration::from_secs(10)),
                     tool_timeout_sec: None,
+                    allow_sampling: false,
                 },
             );
         })
@@ -167,6 +176,143 @@ async fn stdio_server_round_trip() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn stdio_server_resources_and_prompts() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = responses::start_mock_server().await;
+
+    let call_id = "call-resource";
+    let server_name = "rmcp";
+    let readme_uri = "memo://codex/readme";
+
+    let responses_mock = mount_sse_sequence(
+        &server,
+        vec![
+            responses::sse(vec![
+                responses::ev_response_created("resp-1"),
+                responses::ev_function_call(
+                    call_id,
+                    "read_mcp_resource",
+                    &serde_json::json!({ "server": server_name, "uri": readme_uri }).to_string(),
+                ),
+                responses::ev_completed("resp-1"),
+            ]),
+            responses::sse(vec![
+                responses::ev_assistant_message("msg-1", "The server echoes messages."),
+                responses::ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await;
+
+    let rmcp_test_server_bin = CargoBuild::new()
+        .package("codex-rmcp-client")
+        .bin("test_stdio_server")
+        .run()?
+        .path()
+        .to_string_lossy()
+        .into_owned();
+
+    let fixture = test_codex()
+        .with_config(move |config| {
+            config.use_experimental_use_rmcp_client = true;
+            config.mcp_servers.insert(
+                server_name.to_string(),
+                McpServerConfig {
+                    transport: McpServerTransportConfig::Stdio {
+                        command: rmcp_test_server_bin,
+                        args: Vec::new(),
+                        env: None,
+                    },
+                    startup_timeout_sec: Some(Duration::from_secs(10)),
+                    tool_timeout_sec: None,
+                    allow_sampling: false,
+                },
+            );
+        })
+        .build(&server)
+        .await?;
+
+    fixture.codex.submit(Op::ListMcpResources).await?;
+    let EventMsg::McpListResourcesResponse(listed) = wait_for_event(&fixture.codex, |ev| {
+        matches!(ev, EventMsg::McpListResourcesResponse(_))
+    })
+    .await
+    else {
+        unreachable!("event guard guarantees McpListResourcesResponse");
+    };
+    let resources = listed.resources.get(server_name).expect("rmcp resources");
+    assert_eq!(resources.len(), 1);
+    assert_eq!(resources[0].uri, readme_uri);
+
+    fixture.codex.submit(Op::ListCustomPrompts).await?;
+    let EventMsg::ListCustomPromptsResponse(listed) = wait_for_event(&fixture.codex, |ev| {
+        matches!(ev, EventMsg::ListCustomPromptsResponse(_))
+    })
+    .await
+    else {
+        unreachable!("event guard guarantees ListCustomPromptsResponse");
+    };
+    let prompts = listed.mcp_prompts.get(server_name).expect("rmcp prompts");
+    assert_eq!(prompts.len(), 1);
+    assert_eq!(prompts[0].name, "greeting");
+
+    fixture
+        .codex
+        .submit(Op::GetMcpPrompt {
+            server: server_name.to_string(),
+            name: "greeting".to_string(),
+            arguments: HashMap::from([("name".to_string(), "Codex".to_string())]),
+        })
+        .await?;
+    let EventMsg::GetMcpPromptResponse(rendered) = wait_for_event(&fixture.codex, |ev| {
+        matches!(ev, EventMsg::GetMcpPromptResponse(_))
+    })
+    .await
+    else {
+        unreachable!("event guard guarantees GetMcpPromptResponse");
+    };
+    let rendered = rendered.result.expect("prompt should render");
+    let ContentBlock::TextContent(text) = &rendered.messages[0].content else {
+        panic!("expected text content: {:?}", rendered.messages[0].content);
+    };
+    assert_eq!(text.text, "Say hello to Codex.");
+
+    let session_model = fixture.session_configured.model.clone();
+    fixture
+        .codex
+        .submit(Op::UserTurn {
+            items: vec![InputItem::Text {
+                text: format!("summarize @{server_name}:{readme_uri}"),
+            }],
+            final_output_json_schema: None,
+            cwd: fixture.cwd.path().to_path_buf(),
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::DangerFullAccess,
+            model: session_model,
+            effort: None,
+            summary: ReasoningSummary::Auto,
+        })
+        .await?;
+    wait_for_event(&fixture.codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await;
+
+    let requests = responses_mock.requests();
+    let tools = requests[0].body_json()["tools"].clone();
+    assert!(
+        tools
+            .as_array()
+            .expect("tools array")
+            .iter()
+            .any(|tool| tool["name"] == "read_mcp_resource"),
+        "read_mcp_resource should be offered: {tools:?}"
+    );
+    let output = requests[1].function_call_output(call_id);
+    assert_eq!(output["output"], "This server echoes messages back.");
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn streamable_http_tool_call_round_trip() -> anyhow::Result<()> {
     skip_if_no_network!(Ok(()));
@@ -236,6 +382,7 @@ async fn streamable_http_tool_call_round_trip() -> anyhow::Result<()> {
                     },
                     startup_timeout_sec: Some(Duration::from_secs(10)),
                     tool_timeout_sec: None,
+                    allow_sampling: false,
                 },
             );
         })
@@ -416,6 +563,7 @@ async fn streamable_http_with_oauth_round_trip() -> anyhow::Result<()> {
                     },
                     startup_timeout_sec: Some(Duration::from_secs(10)),
                     tool_timeout_sec: None,
+                    allow_sampling: false,
                 },
             );
         })
@@ -509,6 +657,500 @@ async fn streamable_http_with_oauth_round_trip() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn stdio_server_tool_list_changes_and_restart() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = responses::start_mock_server().await;
+    let server_name = "rmcp";
+
+    for (call_id, tool) in [("call-add", "add_tool"), ("call-crash", "crash")] {
+        mount_sse_once_match(
+            &server,
+            any(),
+            responses::sse(vec![
+                responses::ev_response_created("resp-1"),
+                responses::ev_function_call(call_id, &format!("{server_name}__{tool}"), "{}"),
+                responses::ev_completed("resp-1"),
+            ]),
+        )
+        .await;
+        mount_sse_once_match(
+            &server,
+            any(),
+            responses::sse(vec![
+                responses::ev_assistant_message("msg-1", "done"),
+                responses::ev_completed("resp-2"),
+            ]),
+        )
+        .await;
+    }
+
+    let rmcp_test_server_bin = CargoBuild::new()
+        .package("codex-rmcp-client")
+        .bin("test_stdio_server")
+        .run()?
+        .path()
+        .to_string_lossy()
+        .into_owned();
+
+    let fixture = test_codex()
+        .with_config(move |config| {
+            config.use_experimental_use_rmcp_client = true;
+            config.mcp_servers.insert(
+                server_name.to_string(),
+                McpServerConfig {
+                    transport: McpServerTransportConfig::Stdio {
+                        command: rmcp_test_server_bin,
+                        args: Vec::new(),
+                        env: Some(HashMap::from([(
+                            "MCP_TEST_SUPERVISION".to_string(),
+                            "1".to_string(),
+                        )])),
+                    },
+                    startup_timeout_sec: Some(Duration::from_secs(10)),
+                    tool_timeout_sec: Some(Duration::from_secs(5)),
+                    allow_sampling: false,
+                },
+            );
+        })
+        .build(&server)
+        .await?;
+    let session_model = fixture.session_configured.model.clone();
+
+    for text in ["add a tool", "crash the server"] {
+        fixture
+            .codex
+            .submit(Op::UserTurn {
+                items: vec![InputItem::Text { text: text.into() }],
+                final_output_json_schema: None,
+                cwd: fixture.cwd.path().to_path_buf(),
+                approval_policy: AskForApproval::Never,
+                sandbox_policy: SandboxPolicy::DangerFullAccess,
+                model: session_model.clone(),
+                effort: None,
+                summary: ReasoningSummary::Auto,
+            })
+            .await?;
+        wait_for_event(&fixture.codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await;
+
+        if text == "add a tool" {
+            // The server announces the new tool; the cached list follows.
+            wait_for_mcp_tools(&fixture.codex, |listed| {
+                listed.tools.contains_key("rmcp__added")
+            })
+            .await?;
+        }
+    }
+
+    let failed = wait_for_mcp_tools(&fixture.codex, |listed| {
+        matches!(
+            listed.server_statuses.get(server_name),
+            Some(McpServerStatus::Failed { .. })
+        )
+    })
+    .await?;
+    let Some(McpServerStatus::Failed { stderr_tail, .. }) = failed.server_statuses.get(server_name)
+    else {
+        unreachable!("guard guarantees a failed status");
+    };
+    assert!(
+        stderr_tail.iter().any(|line| line == "crashing on request"),
+        "stderr tail should explain the crash: {stderr_tail:?}"
+    );
+    assert!(!failed.tools.contains_key("rmcp__echo"));
+
+    // The server is restarted from scratch, so the added tool is gone.
+    let restarted = wait_for_mcp_tools(&fixture.codex, |listed| {
+        listed.server_statuses.get(server_name) == Some(&McpServerStatus::Ready)
+    })
+    .await?;
+    assert!(restarted.tools.contains_key("rmcp__echo"));
+    assert!(!restarted.tools.contains_key("rmcp__added"));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn reload_mcp_servers_applies_config_changes() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = responses::start_mock_server().await;
+
+    let rmcp_test_server_bin = CargoBuild::new()
+        .package("codex-rmcp-client")
+        .bin("test_stdio_server")
+        .run()?
+        .path()
+        .to_string_lossy()
+        .into_owned();
+
+    let initial_bin = rmcp_test_server_bin.clone();
+    let fixture = test_codex()
+        .with_config(move |config| {
+            config.use_experimental_use_rmcp_client = true;
+            config.mcp_servers.insert(
+                "rmcp".to_string(),
+                McpServerConfig {
+                    transport: McpServerTransportConfig::Stdio {
+                        command: initial_bin,
+                        args: Vec::new(),
+                        env: None,
+                    },
+                    startup_timeout_sec: Some(Duration::from_secs(10)),
+                    tool_timeout_sec: None,
+                    allow_sampling: false,
+                },
+            );
+        })
+        .build(&server)
+        .await?;
+
+    fs::write(
+        fixture.home.path().join("config.toml"),
+        format!(
+            "[mcp_servers.reloaded]\ncommand = {}\n",
+            toml::Value::String(rmcp_test_server_bin)
+        ),
+    )?;
+
+    fixture.codex.submit(Op::ReloadMcpServers).await?;
+    let EventMsg::McpListToolsResponse(listed) = wait_for_event(&fixture.codex, |ev| {
+        matches!(ev, EventMsg::McpListToolsResponse(_))
+    })
+    .await
+    else {
+        unreachable!("event guard guarantees McpListToolsResponse");
+    };
+
+    assert!(listed.tools.contains_key("reloaded__echo"));
+    assert!(!listed.tools.contains_key("rmcp__echo"));
+    assert_eq!(
+        listed.server_statuses,
+        HashMap::from([("reloaded".to_string(), McpServerStatus::Ready)])
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn reload_mcp_servers_keeps_cli_override_servers() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = responses::start_mock_server().await;
+
+    let rmcp_test_server_bin = CargoBuild::new()
+        .package("codex-rmcp-client")
+        .bin("test_stdio_server")
+        .run()?
+        .path()
+        .to_string_lossy()
+        .into_owned();
+
+    let fixture = test_codex()
+        .with_config(move |config| {
+            config.use_experimental_use_rmcp_client = true;
+            config.cli_overrides = vec![(
+                "mcp_servers.overridden.command".to_string(),
+                toml::Value::String(rmcp_test_server_bin),
+            )];
+        })
+        .build(&server)
+        .await?;
+
+    fixture.codex.submit(Op::ReloadMcpServers).await?;
+    let EventMsg::McpListToolsResponse(listed) = wait_for_event(&fixture.codex, |ev| {
+        matches!(ev, EventMsg::McpListToolsResponse(_))
+    })
+    .await
+    else {
+        unreachable!("event guard guarantees McpListToolsResponse");
+    };
+
+    assert!(listed.tools.contains_key("overridden__echo"));
+    assert_eq!(
+        listed.server_statuses,
+        HashMap::from([("overridden".to_string(), McpServerStatus::Ready)])
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn stdio_server_sampling_uses_the_model_after_approval() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = responses::start_mock_server().await;
+    let call_id = "call-sample";
+    let server_name = "rmcp";
+
+    let responses_mock = mount_sse_sequence(
+        &server,
+        vec![
+            responses::sse(vec![
+                responses::ev_response_created("resp-1"),
+                responses::ev_function_call(call_id, &format!("{server_name}__sample"), "{}"),
+                responses::ev_completed("resp-1"),
+            ]),
+            // Answer to the server's sampling request.
+            responses::sse(vec![
+                responses::ev_assistant_message("msg-1", "Paris"),
+                responses::ev_completed("resp-2"),
+            ]),
+            responses::sse(vec![
+                responses::ev_assistant_message("msg-2", "The server learned it is Paris."),
+                responses::ev_completed("resp-3"),
+            ]),
+        ],
+    )
+    .await;
+
+    let rmcp_test_server_bin = CargoBuild::new()
+        .package("codex-rmcp-client")
+        .bin("test_stdio_server")
+        .run()?
+        .path()
+        .to_string_lossy()
+        .into_owned();
+
+    let fixture = test_codex()
+        .with_config(move |config| {
+            config.use_experimental_use_rmcp_client = true;
+            config.approval_policy = AskForApproval::OnRequest;
+            config.mcp_servers.insert(
+                server_name.to_string(),
+                McpServerConfig {
+                    transport: McpServerTransportConfig::Stdio {
+                        command: rmcp_test_server_bin,
+                        args: Vec::new(),
+                        env: Some(HashMap::from([(
+                            "MCP_TEST_CALLBACKS".to_string(),
+                            "1".to_string(),
+                        )])),
+                    },
+                    startup_timeout_sec: Some(Duration::from_secs(10)),
+                    tool_timeout_sec: Some(Duration::from_secs(30)),
+                    allow_sampling: true,
+                },
+            );
+        })
+        .build(&server)
+        .await?;
+    let session_model = fixture.session_configured.model.clone();
+
+    fixture
+        .codex
+        .submit(Op::UserTurn {
+            items: vec![InputItem::Text {
+                text: "call the sample tool".into(),
+            }],
+            final_output_json_schema: None,
+            cwd: fixture.cwd.path().to_path_buf(),
+            approval_policy: AskForApproval::OnRequest,
+            sandbox_policy: SandboxPolicy::DangerFullAccess,
+            model: session_model,
+            effort: None,
+            summary: ReasoningSummary::Auto,
+        })
+        .await?;
+
+    let event = wait_for_request_event(&fixture.codex, |ev| {
+        matches!(ev, EventMsg::McpSamplingRequest(_))
+    })
+    .await?;
+    let EventMsg::McpSamplingRequest(request) = event.msg else {
+        unreachable!("event guard guarantees McpSamplingRequest");
+    };
+    assert_eq!(request.server, server_name);
+    assert_eq!(
+        request.system_prompt.as_deref(),
+        Some("Answer in one word.")
+    );
+    assert_eq!(request.messages.len(), 1);
+
+    fixture
+        .codex
+        .submit(Op::McpSamplingApproval {
+            id: event.id,
+            decision: ReviewDecision::Approved,
+        })
+        .await?;
+    wait_for_event(&fixture.codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await;
+
+    let requests = responses_mock.requests();
+    assert_eq!(requests.len(), 3);
+    let sampling = requests[1].body_json();
+    assert!(
+        sampling["tools"].as_array().is_none_or(Vec::is_empty),
+        "sampling requests should not offer tools: {sampling}"
+    );
+    assert_ne!(
+        sampling["instructions"], "Answer in one word.",
+        "the server's system prompt must not replace Codex's instructions"
+    );
+    assert!(
+        sampling["input"].to_string().contains("Answer in one word."),
+        "sampling input should quote the server's system prompt: {sampling}"
+    );
+    assert_eq!(sampling["max_output_tokens"], 100);
+    assert!(
+        sampling["input"]
+            .to_string()
+            .contains("What is the capital of France?"),
+        "sampling input should carry the server's message: {sampling}"
+    );
+    let output = requests[2].function_call_output(call_id);
+    assert!(
+        output["output"].to_string().contains("sampled: Paris"),
+        "tool output should contain the sampled answer: {output}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn stdio_server_elicitation_round_trip() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = responses::start_mock_server().await;
+    let call_id = "call-elicit";
+    let server_name = "rmcp";
+
+    let responses_mock = mount_sse_sequence(
+        &server,
+        vec![
+            responses::sse(vec![
+                responses::ev_response_created("resp-1"),
+                responses::ev_function_call(call_id, &format!("{server_name}__elicit"), "{}"),
+                responses::ev_completed("resp-1"),
+            ]),
+            responses::sse(vec![
+                responses::ev_assistant_message("msg-1", "Greeted Ada."),
+                responses::ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await;
+
+    let rmcp_test_server_bin = CargoBuild::new()
+        .package("codex-rmcp-client")
+        .bin("test_stdio_server")
+        .run()?
+        .path()
+        .to_string_lossy()
+        .into_owned();
+
+    let fixture = test_codex()
+        .with_config(move |config| {
+            config.use_experimental_use_rmcp_client = true;
+            config.approval_policy = AskForApproval::OnRequest;
+            config.mcp_servers.insert(
+                server_name.to_string(),
+                McpServerConfig {
+                    transport: McpServerTransportConfig::Stdio {
+                        command: rmcp_test_server_bin,
+                        args: Vec::new(),
+                        env: Some(HashMap::from([(
+                            "MCP_TEST_CALLBACKS".to_string(),
+                            "1".to_string(),
+                        )])),
+                    },
+                    startup_timeout_sec: Some(Duration::from_secs(10)),
+                    tool_timeout_sec: Some(Duration::from_secs(30)),
+                    allow_sampling: false,
+                },
+            );
+        })
+        .build(&server)
+        .await?;
+    let session_model = fixture.session_configured.model.clone();
+
+    fixture
+        .codex
+        .submit(Op::UserTurn {
+            items: vec![InputItem::Text {
+                text: "call the elicit tool".into(),
+            }],
+            final_output_json_schema: None,
+            cwd: fixture.cwd.path().to_path_buf(),
+            approval_policy: AskForApproval::OnRequest,
+            sandbox_policy: SandboxPolicy::DangerFullAccess,
+            model: session_model,
+            effort: None,
+            summary: ReasoningSummary::Auto,
+        })
+        .await?;
+
+    let event = wait_for_request_event(&fixture.codex, |ev| {
+        matches!(ev, EventMsg::ElicitationRequest(_))
+    })
+    .await?;
+    let EventMsg::ElicitationRequest(request) = event.msg else {
+        unreachable!("event guard guarantees ElicitationRequest");
+    };
+    assert_eq!(request.server, server_name);
+    assert_eq!(request.message, "What is your name?");
+    assert_eq!(
+        request.requested_schema.required,
+        Some(vec!["name".to_string()])
+    );
+
+    fixture
+        .codex
+        .submit(Op::ResolveElicitation {
+            id: event.id,
+            action: ElicitationAction::Accept,
+            content: Some(serde_json::json!({ "name": "Ada" })),
+        })
+        .await?;
+    wait_for_event(&fixture.codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await;
+
+    let output = responses_mock.requests()[1].function_call_output(call_id);
+    assert!(
+        output["output"].to_string().contains("Hello Ada"),
+        "tool output should greet the user: {output}"
+    );
+
+    Ok(())
+}
+
+/// Wait for the event an MCP server callback raised. Unlike `wait_for_event`
+/// this keeps the event id, which is how the request is answered.
+async fn wait_for_request_event(
+    codex: &CodexConversation,
+    matches: impl Fn(&EventMsg) -> bool,
+) -> anyhow::Result<Event> {
+    loop {
+        let event = tokio::time::timeout(Duration::from_secs(10), codex.next_event()).await??;
+        if matches(&event.msg) {
+            return Ok(event);
+        }
+    }
+}
+
+/// Poll `Op::ListMcpTools` until `done` accepts the response.
+async fn wait_for_mcp_tools(
+    codex: &CodexConversation,
+    done: impl Fn(&McpListToolsResponseEvent) -> bool,
+) -> anyhow::Result<McpListToolsResponseEvent> {
+    let deadline = Instant::now() + Duration::from_secs(20);
+    loop {
+        codex.submit(Op::ListMcpTools).await?;
+        let EventMsg::McpListToolsResponse(listed) =
+            wait_for_event(codex, |ev| matches!(ev, EventMsg::McpListToolsResponse(_))).await
+        else {
+            unreachable!("event guard guarantees McpListToolsResponse");
+        };
+        if done(&listed) {
+            return Ok(listed);
+        }
+        if Instant::now() >= deadline {
+            anyhow::bail!("timed out waiting for MCP tools: {listed:?}");
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+}
+
 async fn wait_for_streamable_http_server(
     server_child: &mut Child,
     address: &str,
@@ -99,6 +99,7 @@ impl MockAgentCommand {
             agent: true,
             agent_id: Some("mock-review".to_string()),
             activation_hints: vec!["review".to_string()],
+            pipeline: None,
         };
         Self { metadata }
     }
@@ -113,6 +114,7 @@ impl MockAgentCommand {
             agent: false,
             agent_id: None,
             activation_hints: Vec::new(),
+            pipeline: None,
         };
         Self { metadata }
     }
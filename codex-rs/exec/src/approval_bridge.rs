@@ -0,0 +1,218 @@
+//! Lets `codex exec` hand approval requests to an external decider, either a
+//! program run once per request or a process listening on a Unix socket.
+//!
+//! Each request is written as a single JSON line:
+//!
+//! ```json
+//! {"type":"exec","call_id":"call_1","command":["cargo","publish"],"cwd":"/repo"}
+//! ```
+//!
+//! and answered with `{"decision":"approved"}` or just the bare decision
+//! (`approved`, `approved_for_session`, `approved_for_project`, `denied` or
+//! `abort`). Anything else, including a failing program or one that does not
+//! answer within the timeout, denies the request.
+//!
+//! MCP sampling requests are sent with `"type":"mcp_sampling"`. Elicitation
+//! requests need a form and are always cancelled.
+
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+
+use anyhow::Context;
+use codex_core::CodexConversation;
+use codex_core::protocol::ApplyPatchApprovalRequestEvent;
+use codex_core::protocol::ElicitationAction;
+use codex_core::protocol::Event;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::ExecApprovalRequestEvent;
+use codex_core::protocol::McpSamplingRequestEvent;
+use codex_core::protocol::Op;
+use codex_core::protocol::ReviewDecision;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use tokio::io::AsyncWriteExt;
+use tracing::info;
+
+/// How long a request may wait for a decision unless `--approval-timeout`
+/// says otherwise.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ApprovalBridge {
+    decider: Decider,
+    /// Requests without a decision after this long are denied.
+    timeout: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Decider {
+    /// Program and arguments to run for every request.
+    Command(Vec<String>),
+    /// Socket another process is listening on.
+    Socket(PathBuf),
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ApprovalRequest<'a> {
+    Exec(&'a ExecApprovalRequestEvent),
+    ApplyPatch(&'a ApplyPatchApprovalRequestEvent),
+    McpSampling(&'a McpSamplingRequestEvent),
+}
+
+#[derive(Deserialize)]
+struct ApprovalResponse {
+    decision: ReviewDecision,
+}
+
+impl ApprovalBridge {
+    /// Builds the bridge selected by `--approval-command` or `--approval-socket`,
+    /// waiting `timeout_secs` for each decision.
+    pub(crate) fn from_cli(
+        command: Option<String>,
+        socket: Option<PathBuf>,
+        timeout_secs: Option<u64>,
+    ) -> anyhow::Result<Option<Self>> {
+        let decider = match (command, socket) {
+            (Some(command), _) => {
+                let argv = shlex::split(&command)
+                    .filter(|argv| !argv.is_empty())
+                    .with_context(|| format!("invalid --approval-command: {command}"))?;
+                Decider::Command(argv)
+            }
+            (None, Some(socket)) => {
+                if cfg!(not(unix)) {
+                    anyhow::bail!("--approval-socket is only supported on Unix");
+                }
+                Decider::Socket(socket)
+            }
+            (None, None) => return Ok(None),
+        };
+        Ok(Some(Self {
+            decider,
+            timeout: timeout_secs.map_or(DEFAULT_TIMEOUT, Duration::from_secs),
+        }))
+    }
+
+    /// Answers `event` on `conversation` if it is an approval request.
+    pub(crate) async fn handle_event(
+        &self,
+        conversation: &CodexConversation,
+        event: &Event,
+    ) -> anyhow::Result<()> {
+        let op = match &event.msg {
+            EventMsg::ExecApprovalRequest(request) => Op::ExecApproval {
+                id: event.id.clone(),
+                decision: self.decide(&ApprovalRequest::Exec(request)).await,
+            },
+            EventMsg::ApplyPatchApprovalRequest(request) => Op::PatchApproval {
+                id: event.id.clone(),
+                decision: self.decide(&ApprovalRequest::ApplyPatch(request)).await,
+            },
+            EventMsg::McpSamplingRequest(request) => Op::McpSamplingApproval {
+                id: event.id.clone(),
+                decision: self.decide(&ApprovalRequest::McpSampling(request)).await,
+            },
+            EventMsg::ElicitationRequest(_) => Op::ResolveElicitation {
+                id: event.id.clone(),
+                action: ElicitationAction::Cancel,
+                content: None,
+            },
+            _ => return Ok(()),
+        };
+        conversation.submit(op).await?;
+        Ok(())
+    }
+
+    async fn decide(&self, request: &ApprovalRequest<'_>) -> ReviewDecision {
+        match self.request_decision(request).await {
+            Ok(decision) => {
+                info!("Approval bridge decision: {decision}");
+                decision
+            }
+            Err(err) => {
+                eprintln!("Approval bridge failed, denying the request: {err:#}");
+                ReviewDecision::Denied
+            }
+        }
+    }
+
+    async fn request_decision(
+        &self,
+        request: &ApprovalRequest<'_>,
+    ) -> anyhow::Result<ReviewDecision> {
+        let mut payload = serde_json::to_string(request)?;
+        payload.push('\n');
+        let reply = async {
+            match &self.decider {
+                Decider::Command(argv) => run_command(argv, &payload).await,
+                Decider::Socket(path) => send_to_socket(path, &payload).await,
+            }
+        };
+        // Dropping the pending reply kills the approval command.
+        let reply = tokio::time::timeout(self.timeout, reply)
+            .await
+            .map_err(|_| {
+                anyhow::anyhow!("no decision within {} seconds", self.timeout.as_secs())
+            })??;
+        parse_decision(&reply)
+    }
+}
+
+async fn run_command(argv: &[String], payload: &str) -> anyhow::Result<String> {
+    let (program, args) = argv.split_first().context("empty approval command")?;
+    let mut child = tokio::process::Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("failed to run {program}"))?;
+    // Programs that decide without reading the request may close stdin early.
+    if let Some(mut stdin) = child.stdin.take()
+        && let Err(err) = stdin.write_all(payload.as_bytes()).await
+        && err.kind() != std::io::ErrorKind::BrokenPipe
+    {
+        return Err(err.into());
+    }
+    let output = child.wait_with_output().await?;
+    if !output.status.success() {
+        anyhow::bail!("{program} exited with {}", output.status);
+    }
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+#[cfg(unix)]
+async fn send_to_socket(path: &std::path::Path, payload: &str) -> anyhow::Result<String> {
+    use tokio::io::AsyncBufReadExt;
+    use tokio::io::BufReader;
+
+    let mut stream = tokio::net::UnixStream::connect(path)
+        .await
+        .with_context(|| format!("failed to connect to {}", path.display()))?;
+    stream.write_all(payload.as_bytes()).await?;
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply).await?;
+    Ok(reply)
+}
+
+#[cfg(not(unix))]
+async fn send_to_socket(path: &std::path::Path, _payload: &str) -> anyhow::Result<String> {
+    anyhow::bail!(
+        "cannot connect to {}: sockets are only supported on Unix",
+        path.display()
+    )
+}
+
+fn parse_decision(reply: &str) -> anyhow::Result<ReviewDecision> {
+    let reply = reply.trim();
+    let decision = if reply.starts_with('{') {
+        serde_json::from_str::<ApprovalResponse>(reply).map(|response| response.decision)
+    } else {
+        serde_json::from_value(Value::String(reply.to_string()))
+    };
+    decision.with_context(|| format!("invalid approval decision: {reply:?}"))
+}
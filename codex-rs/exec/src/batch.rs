@@ -0,0 +1,352 @@
+//! `codex exec --batch`: runs every task of a JSONL file in its own thread,
+//! a bounded number at a time, and prints one [`BatchTaskSummary`] per task.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::Context;
+use codex_common::CliConfigOverrides;
+use codex_core::CodexConversation;
+use codex_core::ConversationManager;
+use codex_core::NewConversation;
+use codex_core::config::Config;
+use codex_core::config::ConfigOverrides;
+use codex_core::git_info::get_git_repo_root;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::InputItem;
+use codex_core::protocol::Op;
+use codex_core::protocol::TaskCompleteEvent;
+use serde::Deserialize;
+use serde_json::Value;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tracing::error;
+
+use crate::approval_bridge::ApprovalBridge;
+use crate::event_processor_with_jsonl_output::EventProcessorWithJsonOutput;
+use crate::exec_events::BatchTaskSummary;
+use crate::exec_events::ThreadErrorEvent;
+use crate::exec_events::ThreadEvent;
+use crate::exec_events::TurnFailedEvent;
+use crate::exec_events::Usage;
+
+/// One line of a batch file, as written by the user.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BatchTaskLine {
+    id: Option<String>,
+    cwd: Option<PathBuf>,
+    prompt: String,
+    profile: Option<String>,
+    output_schema: Option<OutputSchemaSpec>,
+}
+
+/// `output_schema` is either a path to a JSON Schema file or the schema itself.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum OutputSchemaSpec {
+    Path(PathBuf),
+    Inline(Value),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct BatchTask {
+    pub id: String,
+    pub cwd: Option<PathBuf>,
+    pub prompt: String,
+    pub profile: Option<String>,
+    pub output_schema: Option<Value>,
+}
+
+/// Reads the tasks of a batch file. Blank lines are skipped and relative paths
+/// are resolved against the directory containing the file.
+pub(crate) fn load_batch_tasks(path: &Path) -> anyhow::Result<Vec<BatchTask>> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read batch file {}", path.display()))?;
+    let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let tasks = parse_batch_tasks(&contents, base_dir)
+        .with_context(|| format!("invalid batch file {}", path.display()))?;
+    if tasks.is_empty() {
+        anyhow::bail!("batch file {} contains no tasks", path.display());
+    }
+    Ok(tasks)
+}
+
+fn parse_batch_tasks(contents: &str, base_dir: &Path) -> anyhow::Result<Vec<BatchTask>> {
+    let mut tasks = Vec::new();
+    for (index, line) in contents.lines().enumerate() {
+        let line_number = index + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let task: BatchTaskLine =
+            serde_json::from_str(line).with_context(|| format!("line {line_number}"))?;
+        let output_schema = match task.output_schema {
+            Some(OutputSchemaSpec::Path(schema_path)) => {
+                let schema_path = base_dir.join(schema_path);
+                let schema = std::fs::read_to_string(&schema_path).with_context(|| {
+                    format!(
+                        "line {line_number}: failed to read output schema file {}",
+                        schema_path.display()
+                    )
+                })?;
+                let schema = serde_json::from_str(&schema).with_context(|| {
+                    format!(
+                        "line {line_number}: output schema file {} is not valid JSON",
+                        schema_path.display()
+                    )
+                })?;
+                Some(schema)
+            }
+            Some(OutputSchemaSpec::Inline(schema)) => Some(schema),
+            None => None,
+        };
+        tasks.push(BatchTask {
+            id: task.id.unwrap_or_else(|| line_number.to_string()),
+            cwd: task.cwd.map(|cwd| base_dir.join(cwd)),
+            prompt: task.prompt,
+            profile: task.profile,
+            output_schema,
+        });
+    }
+    Ok(tasks)
+}
+
+/// Settings shared by every task of a batch.
+pub(crate) struct BatchRunner {
+    pub conversation_manager: ConversationManager,
+    pub config_overrides: CliConfigOverrides,
+    pub overrides: ConfigOverrides,
+    /// Schema from `--output-schema`, used by tasks that do not set their own.
+    pub output_schema: Option<Value>,
+    pub approval_bridge: Option<ApprovalBridge>,
+    pub skip_git_repo_check: bool,
+}
+
+impl BatchRunner {
+    /// Runs `tasks` with at most `max_concurrency` of them in flight and prints
+    /// a summary line for each as it finishes. Returns whether every task
+    /// completed its turn.
+    pub(crate) async fn run(self, tasks: Vec<BatchTask>, max_concurrency: usize) -> bool {
+        let runner = Arc::new(self);
+        let semaphore = Arc::new(Semaphore::new(max_concurrency.max(1)));
+        let mut join_set = JoinSet::new();
+        for task in tasks {
+            let runner = Arc::clone(&runner);
+            let semaphore = Arc::clone(&semaphore);
+            join_set.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                runner.run_task(task).await
+            });
+        }
+
+        let mut all_completed = true;
+        while let Some(joined) = join_set.join_next().await {
+            match joined {
+                Ok(summary) => {
+                    all_completed &= matches!(summary.result, ThreadEvent::TurnCompleted(_));
+                    print_summary(&summary);
+                }
+                Err(err) => {
+                    error!("Batch task panicked: {err}");
+                    all_completed = false;
+                }
+            }
+        }
+        all_completed
+    }
+
+    async fn run_task(&self, task: BatchTask) -> BatchTaskSummary {
+        let id = task.id.clone();
+        eprintln!("[{id}] started");
+        let summary = match self.try_run_task(task).await {
+            Ok(summary) => summary,
+            Err(err) => BatchTaskSummary {
+                id,
+                thread_id: None,
+                rollout_path: None,
+                result: ThreadEvent::TurnFailed(TurnFailedEvent {
+                    error: ThreadErrorEvent {
+                        message: format!("{err:#}"),
+                    },
+                }),
+                usage: Usage::default(),
+                last_agent_message: None,
+            },
+        };
+        let status = match summary.result {
+            ThreadEvent::TurnCompleted(_) => "completed",
+            _ => "failed",
+        };
+        eprintln!("[{}] {status}", summary.id);
+        summary
+    }
+
+    async fn try_run_task(&self, task: BatchTask) -> anyhow::Result<BatchTaskSummary> {
+        let config = self.load_config(&task).await?;
+        if !self.skip_git_repo_check && get_git_repo_root(&config.cwd).is_none() {
+            anyhow::bail!(
+                "{} is not inside a trusted directory and --skip-git-repo-check was not specified",
+                config.cwd.display()
+            );
+        }
+
+        let NewConversation {
+            conversation_id,
+            conversation,
+            session_configured,
+        } = self
+            .conversation_manager
+            .new_conversation(config.clone())
+            .await?;
+        let output_schema = task.output_schema.or_else(|| self.output_schema.clone());
+        let outcome = run_turn(
+            &conversation,
+            &config,
+            task.prompt,
+            output_schema,
+            self.approval_bridge.as_ref(),
+        )
+        .await;
+
+        if let Err(err) = shutdown(&conversation).await {
+            error!("Failed to shut down batch task {}: {err}", task.id);
+        }
+        self.conversation_manager
+            .remove_conversation(&conversation_id)
+            .await;
+
+        let TurnOutcome {
+            result,
+            usage,
+            last_agent_message,
+        } = outcome?;
+        Ok(BatchTaskSummary {
+            id: task.id,
+            thread_id: Some(conversation_id.to_string()),
+            rollout_path: Some(session_configured.rollout_path.display().to_string()),
+            result,
+            usage,
+            last_agent_message,
+        })
+    }
+
+    async fn load_config(&self, task: &BatchTask) -> anyhow::Result<Config> {
+        let cli_kv_overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(|e| anyhow::anyhow!("Error parsing -c overrides: {e}"))?;
+        let mut overrides = self.overrides.clone();
+        if let Some(profile) = &task.profile {
+            overrides.config_profile = Some(profile.clone());
+        }
+        if let Some(cwd) = &task.cwd {
+            let cwd = cwd
+                .canonicalize()
+                .with_context(|| format!("invalid cwd {}", cwd.display()))?;
+            overrides.cwd = Some(cwd);
+        }
+        Ok(Config::load_with_cli_overrides(cli_kv_overrides, overrides).await?)
+    }
+}
+
+struct TurnOutcome {
+    result: ThreadEvent,
+    usage: Usage,
+    last_agent_message: Option<String>,
+}
+
+/// Submits `prompt` as a single turn and waits for it to finish.
+async fn run_turn(
+    conversation: &CodexConversation,
+    config: &Config,
+    prompt: String,
+    output_schema: Option<Value>,
+    approval_bridge: Option<&ApprovalBridge>,
+) -> anyhow::Result<TurnOutcome> {
+    conversation
+        .submit(Op::UserTurn {
+            items: vec![InputItem::Text { text: prompt }],
+            cwd: config.cwd.clone(),
+            approval_policy: config.approval_policy,
+            sandbox_policy: config.sandbox_policy.clone(),
+            model: config.model.clone(),
+            effort: config.model_reasoning_effort,
+            summary: config.model_reasoning_summary,
+            final_output_json_schema: output_schema,
+        })
+        .await?;
+
+    // Reuse the `--json` event mapping so batch results match what a single
+    // `codex exec --json` run would report for the same turn.
+    let mut processor = EventProcessorWithJsonOutput::new(None);
+    let mut result = None;
+    let mut usage = Usage::default();
+    loop {
+        let event = conversation.next_event().await?;
+        if let Some(bridge) = approval_bridge {
+            bridge.handle_event(conversation, &event).await?;
+        }
+        if let EventMsg::TokenCount(ev) = &event.msg
+            && let Some(info) = &ev.info
+        {
+            usage = Usage {
+                input_tokens: info.total_token_usage.input_tokens,
+                cached_input_tokens: info.total_token_usage.cached_input_tokens,
+                output_tokens: info.total_token_usage.output_tokens,
+            };
+        }
+        for thread_event in processor.collect_thread_events(&event) {
+            if matches!(
+                thread_event,
+                ThreadEvent::TurnCompleted(_) | ThreadEvent::TurnFailed(_)
+            ) {
+                result = Some(thread_event);
+            }
+        }
+        match event.msg {
+            EventMsg::TaskComplete(TaskCompleteEvent { last_agent_message }) => {
+                let result = result.context("turn finished without a result")?;
+                return Ok(TurnOutcome {
+                    result,
+                    usage,
+                    last_agent_message,
+                });
+            }
+            EventMsg::TurnAborted(_) => {
+                // A turn stopped by its budget still reports why it failed.
+                let result = result.context("turn was aborted")?;
+                return Ok(TurnOutcome {
+                    result,
+                    usage,
+                    last_agent_message: None,
+                });
+            }
+            EventMsg::ShutdownComplete => {
+                anyhow::bail!("session shut down before the turn finished")
+            }
+            _ => {}
+        }
+    }
+}
+
+async fn shutdown(conversation: &CodexConversation) -> anyhow::Result<()> {
+    conversation.submit(Op::Shutdown).await?;
+    loop {
+        if matches!(
+            conversation.next_event().await?.msg,
+            EventMsg::ShutdownComplete
+        ) {
+            return Ok(());
+        }
+    }
+}
+
+#[allow(clippy::print_stdout)]
+fn print_summary(summary: &BatchTaskSummary) {
+    match serde_json::to_string(summary) {
+        Ok(line) => println!("{line}"),
+        Err(err) => error!("Failed to serialize batch summary: {err}"),
+    }
+}
@@ -1,6 +1,9 @@
 use clap::Parser;
 use clap::ValueEnum;
+use codex_common::ApprovalModeCliArg;
 use codex_common::CliConfigOverrides;
+use codex_core::agents::Severity;
+use codex_core::commands::agents::OutputFormat;
 use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
@@ -40,10 +43,43 @@ pub struct Cli {
         long = "dangerously-bypass-approvals-and-sandbox",
         alias = "yolo",
         default_value_t = false,
-        conflicts_with = "full_auto"
+        conflicts_with_all = ["full_auto", "approval_bridge"]
     )]
     pub dangerously_bypass_approvals_and_sandbox: bool,
 
+    /// Program that decides approval requests. It receives each request as
+    /// JSON on stdin and prints its decision on stdout.
+    #[arg(
+        long = "approval-command",
+        value_name = "COMMAND",
+        group = "approval_bridge"
+    )]
+    pub approval_command: Option<String>,
+
+    /// Unix socket to forward approval requests to, one JSON line per request
+    /// and per decision.
+    #[arg(
+        long = "approval-socket",
+        value_name = "PATH",
+        group = "approval_bridge"
+    )]
+    pub approval_socket: Option<PathBuf>,
+
+    /// Seconds to wait for the approval bridge to decide a request before
+    /// denying it. Defaults to 300.
+    #[arg(
+        long = "approval-timeout",
+        value_name = "SECONDS",
+        requires = "approval_bridge"
+    )]
+    pub approval_timeout: Option<u64>,
+
+    /// Configure when the model requires approval before executing a command.
+    /// Only available with an approval bridge; defaults to `approval_policy`
+    /// from config.toml.
+    #[arg(long = "ask-for-approval", short = 'a', requires = "approval_bridge")]
+    pub approval_policy: Option<ApprovalModeCliArg>,
+
     /// Tell the agent to use the specified directory as its working root.
     #[clap(long = "cd", short = 'C', value_name = "DIR")]
     pub cwd: Option<PathBuf>,
@@ -71,10 +107,38 @@ pub struct Cli {
     #[arg(long = "include-plan-tool", default_value_t = false)]
     pub include_plan_tool: bool,
 
+    /// Output format for agent-backed slash commands such as `/review`.
+    #[arg(long = "report-format", value_enum, default_value_t = ReportFormat::Markdown)]
+    pub report_format: ReportFormat,
+
+    /// Exit with a non-zero status when an agent-backed slash command reports
+    /// a finding at or above this severity. Overrides `agents.fail_on`.
+    #[arg(long = "fail-on", value_enum, value_name = "SEVERITY")]
+    pub fail_on: Option<FailOnSeverity>,
+
     /// Specifies file where the last message from the agent should be written.
     #[arg(long = "output-last-message", short = 'o', value_name = "FILE")]
     pub last_message_file: Option<PathBuf>,
 
+    /// Run every task of a JSONL file, one task per line, and print a JSONL
+    /// summary of their results instead of a single session's output.
+    #[arg(
+        long = "batch",
+        value_name = "FILE",
+        conflicts_with_all = ["prompt", "images", "last_message_file", "json"]
+    )]
+    pub batch: Option<PathBuf>,
+
+    /// Maximum number of batch tasks to run at the same time.
+    #[arg(
+        long = "max-concurrency",
+        value_name = "N",
+        default_value_t = 4,
+        value_parser = clap::value_parser!(u16).range(1..),
+        requires = "batch"
+    )]
+    pub max_concurrency: u16,
+
     /// Initial instructions for the agent. If not provided as an argument (or
     /// if `-` is used), instructions are read from stdin.
     #[arg(value_name = "PROMPT", value_hint = clap::ValueHint::Other)]
@@ -111,3 +175,49 @@ pub enum Color {
     #[default]
     Auto,
 }
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum ReportFormat {
+    #[default]
+    Markdown,
+    Json,
+    Text,
+    /// SARIF 2.1.0 log of the findings.
+    Sarif,
+    /// GitHub Actions annotations for the findings.
+    Github,
+    /// GitLab Code Quality report of the findings.
+    Gitlab,
+}
+
+impl From<ReportFormat> for OutputFormat {
+    fn from(format: ReportFormat) -> Self {
+        match format {
+            ReportFormat::Markdown => OutputFormat::Markdown,
+            ReportFormat::Json => OutputFormat::Json,
+            ReportFormat::Text => OutputFormat::PlainText,
+            ReportFormat::Sarif => OutputFormat::Sarif,
+            ReportFormat::Github => OutputFormat::GitHub,
+            ReportFormat::Gitlab => OutputFormat::GitLab,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum FailOnSeverity {
+    Error,
+    Warning,
+    Info,
+}
+
+impl From<FailOnSeverity> for Severity {
+    fn from(severity: FailOnSeverity) -> Self {
+        match severity {
+            FailOnSeverity::Error => Severity::Error,
+            FailOnSeverity::Warning => Severity::Warning,
+            FailOnSeverity::Info => Severity::Info,
+        }
+    }
+}
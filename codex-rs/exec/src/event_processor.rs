@@ -1,5 +1,7 @@
 use std::path::Path;
 
+use codex_core::commands::AgentCommandOutput;
+use codex_core::commands::agents::OutputFormat;
 use codex_core::config::Config;
 use codex_core::protocol::Event;
 use codex_core::protocol::SessionConfiguredEvent;
@@ -22,6 +24,10 @@ pub(crate) trait EventProcessor {
     /// Handle a single event emitted by the agent.
     fn process_event(&mut self, event: Event) -> CodexStatus;
 
+    /// Handle the result of an agent-backed slash command, which runs
+    /// without a model turn.
+    fn process_agent_command(&mut self, output: &AgentCommandOutput, format: OutputFormat);
+
     fn print_final_output(&mut self) {}
 }
 
@@ -1,18 +1,27 @@
 use codex_common::elapsed::format_duration;
 use codex_common::elapsed::format_elapsed;
+use codex_core::commands::AgentCommandOutput;
+use codex_core::commands::agents::AgentResultFormatter;
+use codex_core::commands::agents::OutputFormat;
 use codex_core::config::Config;
 use codex_core::protocol::AgentMessageEvent;
 use codex_core::protocol::AgentReasoningRawContentEvent;
+use codex_core::protocol::AgentRunBeginEvent;
+use codex_core::protocol::AgentRunEndEvent;
 use codex_core::protocol::BackgroundEventEvent;
+use codex_core::protocol::ElicitationRequestEvent;
 use codex_core::protocol::ErrorEvent;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
+use codex_core::protocol::ExecApprovalRuleAppliedEvent;
 use codex_core::protocol::ExecCommandBeginEvent;
 use codex_core::protocol::ExecCommandEndEvent;
 use codex_core::protocol::FileChange;
 use codex_core::protocol::McpInvocation;
+use codex_core::protocol::McpSamplingRequestEvent;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
+use codex_core::protocol::NetworkAccessBlockedEvent;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
 use codex_core::protocol::SessionConfiguredEvent;
@@ -221,6 +230,7 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                 aggregated_output,
                 duration,
                 exit_code,
+                limit_exceeded,
                 ..
             }) => {
                 let duration = format!(" in {}", format_duration(duration));
@@ -230,12 +240,16 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     .take(MAX_OUTPUT_LINES_FOR_EXEC_TOOL_CALL)
                     .collect::<Vec<_>>()
                     .join("\n");
-                match exit_code {
-                    0 => {
+                match (exit_code, limit_exceeded) {
+                    (_, Some(limit)) => {
+                        let title = format!(" exceeded {limit} limit{duration}:");
+                        ts_msg!(self, "{}", title.style(self.red));
+                    }
+                    (0, None) => {
                         let title = format!(" succeeded{duration}:");
                         ts_msg!(self, "{}", title.style(self.green));
                     }
-                    _ => {
+                    (_, None) => {
                         let title = format!(" exited {exit_code}{duration}:");
                         ts_msg!(self, "{}", title.style(self.red));
                     }
@@ -417,6 +431,78 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             EventMsg::ApplyPatchApprovalRequest(_) => {
                 // Should we exit?
             }
+            EventMsg::McpSamplingRequest(McpSamplingRequestEvent { server, .. }) => {
+                ts_msg!(
+                    self,
+                    "{} {}",
+                    "mcp sampling requested by".style(self.magenta),
+                    server.style(self.bold),
+                );
+            }
+            EventMsg::ElicitationRequest(ElicitationRequestEvent {
+                server, message, ..
+            }) => {
+                ts_msg!(
+                    self,
+                    "{} {}: {}",
+                    "input requested by".style(self.magenta),
+                    server.style(self.bold),
+                    message,
+                );
+            }
+            EventMsg::ExecApprovalRuleApplied(ExecApprovalRuleAppliedEvent {
+                command,
+                pattern,
+                ..
+            }) => {
+                ts_msg!(
+                    self,
+                    "{} {} by rule {}",
+                    "auto-approved".style(self.green),
+                    escape_command(&command).style(self.bold),
+                    pattern.style(self.dimmed),
+                );
+            }
+            EventMsg::NetworkAccessBlocked(NetworkAccessBlockedEvent { host, port, .. }) => {
+                ts_msg!(
+                    self,
+                    "{} {host}:{port} (not in the sandbox network allowlist)",
+                    "network access blocked".style(self.red),
+                );
+            }
+            EventMsg::AgentRunBegin(AgentRunBeginEvent { invocation, .. }) => {
+                ts_msg!(
+                    self,
+                    "{} {}",
+                    "command".style(self.magenta),
+                    invocation.style(self.bold),
+                );
+            }
+            EventMsg::AgentRunEnd(AgentRunEndEvent {
+                command,
+                duration,
+                result,
+            }) => match result {
+                Ok(result) => {
+                    ts_msg!(
+                        self,
+                        "{} /{} reported {} finding(s) in {}",
+                        "command".style(self.magenta),
+                        command,
+                        result.findings().len(),
+                        format_duration(duration),
+                    );
+                }
+                Err(message) => {
+                    ts_msg!(
+                        self,
+                        "{} /{} failed: {}",
+                        "command".style(self.magenta),
+                        command,
+                        message.style(self.red),
+                    );
+                }
+            },
             EventMsg::AgentReasoning(agent_reasoning_event) => {
                 if self.show_agent_reasoning {
                     ts_msg!(
@@ -487,9 +573,15 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             EventMsg::McpListToolsResponse(_) => {
                 // Currently ignored in exec output.
             }
+            EventMsg::McpListResourcesResponse(_) => {
+                // Currently ignored in exec output.
+            }
             EventMsg::ListCustomPromptsResponse(_) => {
                 // Currently ignored in exec output.
             }
+            EventMsg::GetMcpPromptResponse(_) => {
+                // Currently ignored in exec output.
+            }
             EventMsg::ViewImageToolCall(view) => {
                 ts_msg!(
                     self,
@@ -508,6 +600,10 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                 TurnAbortReason::ReviewEnded => {
                     ts_msg!(self, "task aborted: review ended");
                 }
+                TurnAbortReason::BudgetExceeded => {
+                    ts_msg!(self, "task aborted: budget exceeded");
+                    return CodexStatus::InitiateShutdown;
+                }
             },
             EventMsg::ShutdownComplete => return CodexStatus::Shutdown,
             EventMsg::ConversationPath(_) => {}
@@ -521,6 +617,22 @@ impl EventProcessor for EventProcessorWithHumanOutput {
         CodexStatus::Running
     }
 
+    fn process_agent_command(&mut self, output: &AgentCommandOutput, format: OutputFormat) {
+        ts_msg!(
+            self,
+            "{} /{} reported {} finding(s)",
+            "command".style(self.magenta),
+            output.command_name,
+            output.result.findings().len()
+        );
+
+        let message = AgentResultFormatter::format(&output.result, format);
+        if let Some(output_file) = self.last_message_path.as_deref() {
+            handle_last_message(Some(&message), output_file);
+        }
+        self.final_message = Some(message);
+    }
+
     fn print_final_output(&mut self) {
         if let Some(usage_info) = &self.last_total_token_usage {
             eprintln!(
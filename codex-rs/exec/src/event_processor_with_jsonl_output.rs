@@ -5,11 +5,15 @@ use std::sync::atomic::AtomicU64;
 use crate::event_processor::CodexStatus;
 use crate::event_processor::EventProcessor;
 use crate::event_processor::handle_last_message;
+use crate::exec_events::AgentCommandResultItem;
+use crate::exec_events::AgentFinding;
 use crate::exec_events::AgentMessageItem;
 use crate::exec_events::CommandExecutionItem;
 use crate::exec_events::CommandExecutionStatus;
+use crate::exec_events::ErrorItem;
 use crate::exec_events::FileChangeItem;
 use crate::exec_events::FileUpdateChange;
+use crate::exec_events::FindingSeverity;
 use crate::exec_events::ItemCompletedEvent;
 use crate::exec_events::ItemStartedEvent;
 use crate::exec_events::ItemUpdatedEvent;
@@ -30,9 +34,15 @@ use crate::exec_events::TurnFailedEvent;
 use crate::exec_events::TurnStartedEvent;
 use crate::exec_events::Usage;
 use crate::exec_events::WebSearchItem;
+use codex_core::agents::AgentResult;
+use codex_core::agents::Severity;
+use codex_core::commands::AgentCommandOutput;
+use codex_core::commands::agents::AgentResultFormatter;
+use codex_core::commands::agents::OutputFormat;
 use codex_core::config::Config;
 use codex_core::protocol::AgentMessageEvent;
 use codex_core::protocol::AgentReasoningEvent;
+use codex_core::protocol::AgentRunEndEvent;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::ExecCommandBeginEvent;
@@ -45,6 +55,8 @@ use codex_core::protocol::PatchApplyEndEvent;
 use codex_core::protocol::SessionConfiguredEvent;
 use codex_core::protocol::TaskCompleteEvent;
 use codex_core::protocol::TaskStartedEvent;
+use codex_core::protocol::TurnAbortReason;
+use codex_core::protocol::TurnAbortedEvent;
 use codex_core::protocol::WebSearchEndEvent;
 use codex_protocol::plan_tool::StepStatus;
 use codex_protocol::plan_tool::UpdatePlanArgs;
@@ -118,6 +130,9 @@ impl EventProcessorWithJsonOutput {
             }
             EventMsg::TaskStarted(ev) => self.handle_task_started(ev),
             EventMsg::TaskComplete(_) => self.handle_task_complete(),
+            EventMsg::TurnAborted(ev) if ev.reason == TurnAbortReason::BudgetExceeded => {
+                self.handle_budget_exceeded()
+            }
             EventMsg::Error(ev) => {
                 let error = ThreadErrorEvent {
                     message: ev.message.clone(),
@@ -129,10 +144,94 @@ impl EventProcessorWithJsonOutput {
                 message: ev.message.clone(),
             })],
             EventMsg::PlanUpdate(ev) => self.handle_plan_update(ev),
+            EventMsg::AgentRunEnd(ev) => self.handle_agent_run_end(ev),
             _ => Vec::new(),
         }
     }
 
+    /// Converts the result of an agent-backed slash command into a complete
+    /// turn holding a single `agent_command_result` item.
+    pub fn collect_agent_command_events(
+        &mut self,
+        output: &AgentCommandOutput,
+        format: OutputFormat,
+    ) -> Vec<ThreadEvent> {
+        let item = self.agent_command_result_item(&output.command_name, &output.result, format);
+
+        vec![
+            ThreadEvent::TurnStarted(TurnStartedEvent {}),
+            ThreadEvent::ItemCompleted(ItemCompletedEvent { item }),
+            ThreadEvent::TurnCompleted(TurnCompletedEvent {
+                usage: Usage::default(),
+            }),
+        ]
+    }
+
+    fn agent_command_result_item(
+        &self,
+        command: &str,
+        result: &AgentResult,
+        format: OutputFormat,
+    ) -> ThreadItem {
+        let findings = result
+            .findings()
+            .into_iter()
+            .map(|finding| AgentFinding {
+                severity: match finding.severity {
+                    Severity::Error => FindingSeverity::Error,
+                    Severity::Warning => FindingSeverity::Warning,
+                    Severity::Info => FindingSeverity::Info,
+                },
+                category: finding.category.clone(),
+                message: finding.message.clone(),
+                path: finding
+                    .location
+                    .as_ref()
+                    .map(|path| path.to_string_lossy().into_owned()),
+                line: finding.line_number,
+            })
+            .collect();
+        ThreadItem {
+            id: self.get_next_item_id(),
+            details: ThreadItemDetails::AgentCommandResult(AgentCommandResultItem {
+                command: command.to_string(),
+                findings,
+                output: AgentResultFormatter::format(result, format),
+            }),
+        }
+    }
+
+    fn handle_agent_run_end(&self, ev: &AgentRunEndEvent) -> Vec<ThreadEvent> {
+        let item = match &ev.result {
+            Ok(result) => self.agent_command_result_item(
+                &ev.command,
+                &AgentResult::from(result.clone()),
+                OutputFormat::Markdown,
+            ),
+            Err(message) => ThreadItem {
+                id: self.get_next_item_id(),
+                details: ThreadItemDetails::Error(ErrorItem {
+                    message: format!("/{} failed: {message}", ev.command),
+                }),
+            },
+        };
+        vec![ThreadEvent::ItemCompleted(ItemCompletedEvent { item })]
+    }
+
+    #[allow(clippy::print_stdout)]
+    fn print_thread_events(events: Vec<ThreadEvent>) {
+        for conv_event in events {
+            match serde_json::to_string(&conv_event) {
+                Ok(line) => {
+                    println!("{line}");
+                }
+                Err(e) => {
+                    error!("Failed to serialize event: {e:?}");
+                }
+            }
+        }
+    }
+
     fn get_next_item_id(&self) -> String {
         format!(
             "item_{}",
@@ -418,6 +517,17 @@ impl EventProcessorWithJsonOutput {
 
         items
     }
+
+    fn handle_budget_exceeded(&mut self) -> Vec<ThreadEvent> {
+        // Core reports which budget ran out in the `Error` event just before.
+        let error = self
+            .last_critical_error
+            .take()
+            .unwrap_or_else(|| ThreadErrorEvent {
+                message: "budget exceeded".to_string(),
+            });
+        vec![ThreadEvent::TurnFailed(TurnFailedEvent { error })]
+    }
 }
 
 impl EventProcessor for EventProcessorWithJsonOutput {
@@ -428,19 +538,9 @@ impl EventProcessor for EventProcessorWithJsonOutput {
         });
     }
 
-    #[allow(clippy::print_stdout)]
     fn process_event(&mut self, event: Event) -> CodexStatus {
         let aggregated = self.collect_thread_events(&event);
-        for conv_event in aggregated {
-            match serde_json::to_string(&conv_event) {
-                Ok(line) => {
-                    println!("{line}");
-                }
-                Err(e) => {
-                    error!("Failed to serialize event: {e:?}");
-                }
-            }
-        }
+        Self::print_thread_events(aggregated);
 
         let Event { msg, .. } = event;
 
@@ -449,8 +549,23 @@ impl EventProcessor for EventProcessorWithJsonOutput {
                 handle_last_message(last_agent_message.as_deref(), output_file);
             }
             CodexStatus::InitiateShutdown
+        } else if let EventMsg::TurnAborted(TurnAbortedEvent {
+            reason: TurnAbortReason::BudgetExceeded,
+        }) = msg
+        {
+            CodexStatus::InitiateShutdown
         } else {
             CodexStatus::Running
         }
     }
+
+    fn process_agent_command(&mut self, output: &AgentCommandOutput, format: OutputFormat) {
+        let events = self.collect_agent_command_events(output, format);
+        Self::print_thread_events(events);
+
+        if let Some(output_file) = self.last_message_path.as_deref() {
+            let message = AgentResultFormatter::format(&output.result, format);
+            handle_last_message(Some(&message), output_file);
+        }
+    }
 }
@@ -85,6 +85,27 @@ pub struct ThreadErrorEvent {
     pub message: String,
 }
 
+/// One line of the summary printed by `codex exec --batch`, written once the
+/// task's turn has finished.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS)]
+pub struct BatchTaskSummary {
+    /// The task's `id`, or its 1-based line number in the batch file.
+    pub id: String,
+    /// Thread the task ran in. Absent if the task failed before it started.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread_id: Option<String>,
+    /// Rollout file recording the task's thread.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rollout_path: Option<String>,
+    /// Either `turn.completed` or `turn.failed`.
+    pub result: ThreadEvent,
+    /// Token usage of the task's turn.
+    pub usage: Usage,
+    /// The last message from the agent, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_agent_message: Option<String>,
+}
+
 /// Canonical representation of a thread item and its domain-specific payload.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS)]
 pub struct ThreadItem {
@@ -119,6 +140,9 @@ pub enum ThreadItemDetails {
     TodoList(TodoListItem),
     /// Describes a non-fatal error surfaced as an item.
     Error(ErrorItem),
+    /// Result of an agent-backed slash command such as `/review`, run
+    /// without a model turn.
+    AgentCommandResult(AgentCommandResultItem),
 }
 
 /// Response from the agent.
@@ -215,6 +239,38 @@ pub struct ErrorItem {
     pub message: String,
 }
 
+/// Result of an agent-backed slash command.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS)]
+pub struct AgentCommandResultItem {
+    /// Name of the command, without the leading slash.
+    pub command: String,
+    /// Code review findings reported by the command's agents.
+    pub findings: Vec<AgentFinding>,
+    /// The full result rendered in the requested report format.
+    pub output: String,
+}
+
+/// A code review finding reported by an agent command.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS)]
+pub struct AgentFinding {
+    pub severity: FindingSeverity,
+    pub category: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+}
+
+/// The severity of an agent finding.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingSeverity {
+    Error,
+    Warning,
+    Info,
+}
+
 /// An item in agent's to-do list.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS)]
 pub struct TodoItem {
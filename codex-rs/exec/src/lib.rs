@@ -4,17 +4,21 @@
 // For both modes, any other output must be written to stderr.
 #![deny(clippy::print_stdout)]
 
+mod approval_bridge;
+mod batch;
 mod cli;
 mod event_processor;
 mod event_processor_with_human_output;
 pub mod event_processor_with_jsonl_output;
 pub mod exec_events;
 
+use approval_bridge::ApprovalBridge;
 pub use cli::Cli;
 use codex_core::AuthManager;
 use codex_core::BUILT_IN_OSS_MODEL_PROVIDER_ID;
 use codex_core::ConversationManager;
 use codex_core::NewConversation;
+use codex_core::commands::HeadlessCommandRunner;
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
 use codex_core::git_info::get_git_repo_root;
@@ -60,12 +64,20 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         config_profile,
         full_auto,
         dangerously_bypass_approvals_and_sandbox,
+        approval_command,
+        approval_socket,
+        approval_timeout,
+        approval_policy: approval_policy_cli_arg,
         cwd,
         skip_git_repo_check,
         color,
         last_message_file,
+        report_format,
+        fail_on,
         json: json_mode,
         sandbox_mode: sandbox_mode_cli_arg,
+        batch,
+        max_concurrency,
         prompt,
         output_schema: output_schema_path,
         include_plan_tool,
@@ -80,41 +92,14 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         None => prompt,
     };
 
-    let prompt = match prompt_arg {
-        Some(p) if p != "-" => p,
-        // Either `-` was passed or no positional arg.
-        maybe_dash => {
-            // When no arg (None) **and** stdin is a TTY, bail out early – unless the
-            // user explicitly forced reading via `-`.
-            let force_stdin = matches!(maybe_dash.as_deref(), Some("-"));
-
-            if std::io::stdin().is_terminal() && !force_stdin {
-                eprintln!(
-                    "No prompt provided. Either specify one as an argument or pipe the prompt into stdin."
-                );
-                std::process::exit(1);
-            }
-
-            // Ensure the user knows we are waiting on stdin, as they may
-            // have gotten into this state by mistake. If so, and they are not
-            // writing to stdin, Codex will hang indefinitely, so this should
-            // help them debug in that case.
-            if !force_stdin {
-                eprintln!("Reading prompt from stdin...");
-            }
-            let mut buffer = String::new();
-            if let Err(e) = std::io::stdin().read_to_string(&mut buffer) {
-                eprintln!("Failed to read prompt from stdin: {e}");
-                std::process::exit(1);
-            } else if buffer.trim().is_empty() {
-                eprintln!("No prompt provided via stdin.");
-                std::process::exit(1);
-            }
-            buffer
-        }
-    };
+    if batch.is_some() && command.is_some() {
+        eprintln!("--batch cannot be combined with a subcommand.");
+        std::process::exit(1);
+    }
 
     let output_schema = load_output_schema(output_schema_path);
+    let approval_bridge =
+        ApprovalBridge::from_cli(approval_command, approval_socket, approval_timeout)?;
 
     let (stdout_with_ansi, stderr_with_ansi) = match color {
         cli::Color::Always => (true, true),
@@ -169,8 +154,13 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         review_model: None,
         config_profile,
         // This CLI is intended to be headless and has no affordances for asking
-        // the user for approval.
-        approval_policy: Some(AskForApproval::Never),
+        // the user for approval, so requests are only raised when an approval
+        // bridge can answer them.
+        approval_policy: if approval_bridge.is_some() {
+            approval_policy_cli_arg.map(Into::into)
+        } else {
+            Some(AskForApproval::Never)
+        },
         sandbox_mode,
         cwd: cwd.map(|p| p.canonicalize().unwrap_or(p)),
         model_provider,
@@ -191,7 +181,7 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         }
     };
 
-    let config = Config::load_with_cli_overrides(cli_kv_overrides, overrides).await?;
+    let config = Config::load_with_cli_overrides(cli_kv_overrides, overrides.clone()).await?;
 
     let otel = codex_core::otel_init::build_provider(&config, env!("CARGO_PKG_VERSION"));
 
@@ -217,6 +207,31 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         let _ = tracing_subscriber::registry().with(fmt_layer).try_init();
     }
 
+    if oss {
+        codex_ollama::ensure_oss_ready(&config)
+            .await
+            .map_err(|e| anyhow::anyhow!("OSS setup failed: {e}"))?;
+    }
+
+    if let Some(batch_path) = batch {
+        let tasks = batch::load_batch_tasks(&batch_path)?;
+        let auth_manager = AuthManager::shared(config.codex_home.clone(), true);
+        let runner = batch::BatchRunner {
+            conversation_manager: ConversationManager::new(auth_manager, SessionSource::Exec),
+            config_overrides,
+            overrides,
+            output_schema,
+            approval_bridge,
+            skip_git_repo_check,
+        };
+        if !runner.run(tasks, usize::from(max_concurrency)).await {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    let prompt = read_prompt(prompt_arg);
+
     let mut event_processor: Box<dyn EventProcessor> = match json_mode {
         true => Box::new(EventProcessorWithJsonOutput::new(last_message_file.clone())),
         _ => Box::new(EventProcessorWithHumanOutput::create_with_ansi(
@@ -226,12 +241,6 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         )),
     };
 
-    if oss {
-        codex_ollama::ensure_oss_ready(&config)
-            .await
-            .map_err(|e| anyhow::anyhow!("OSS setup failed: {e}"))?;
-    }
-
     let default_cwd = config.cwd.to_path_buf();
     let default_approval_policy = config.approval_policy;
     let default_sandbox_policy = config.sandbox_policy.clone();
@@ -245,6 +254,23 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     }
 
     let auth_manager = AuthManager::shared(config.codex_home.clone(), true);
+
+    // Agent-backed slash commands such as `/review src/` run directly through
+    // their agents; everything else goes to the model below.
+    if command.is_none() && prompt.trim_start().starts_with('/') {
+        let runner = HeadlessCommandRunner::from_config(&config, auth_manager.clone()).await?;
+        if let Some(output) = runner.run(&prompt).await? {
+            event_processor.process_agent_command(&output, report_format.into());
+            event_processor.print_final_output();
+
+            let fail_on = fail_on.map(Into::into).or(config.agents.fail_on);
+            if fail_on.is_some_and(|severity| output.result.has_findings_at_least(severity)) {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+    }
+
     let conversation_manager = ConversationManager::new(auth_manager.clone(), SessionSource::Exec);
 
     // Handle resume subcommand by resolving a rollout path and using explicit resume API.
@@ -357,6 +383,9 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     // exit with a non-zero status for automation-friendly signaling.
     let mut error_seen = false;
     while let Some(event) = rx.recv().await {
+        if let Some(bridge) = &approval_bridge {
+            bridge.handle_event(&conversation, &event).await?;
+        }
         if matches!(event.msg, EventMsg::Error(_)) {
             error_seen = true;
         }
@@ -426,3 +455,40 @@ fn load_output_schema(path: Option<PathBuf>) -> Option<Value> {
         }
     }
 }
+
+/// Returns the prompt argument, reading it from stdin when it is missing or `-`.
+fn read_prompt(prompt_arg: Option<String>) -> String {
+    match prompt_arg {
+        Some(p) if p != "-" => p,
+        // Either `-` was passed or no positional arg.
+        maybe_dash => {
+            // When no arg (None) **and** stdin is a TTY, bail out early – unless the
+            // user explicitly forced reading via `-`.
+            let force_stdin = matches!(maybe_dash.as_deref(), Some("-"));
+
+            if std::io::stdin().is_terminal() && !force_stdin {
+                eprintln!(
+                    "No prompt provided. Either specify one as an argument or pipe the prompt into stdin."
+                );
+                std::process::exit(1);
+            }
+
+            // Ensure the user knows we are waiting on stdin, as they may
+            // have gotten into this state by mistake. If so, and they are not
+            // writing to stdin, Codex will hang indefinitely, so this should
+            // help them debug in that case.
+            if !force_stdin {
+                eprintln!("Reading prompt from stdin...");
+            }
+            let mut buffer = String::new();
+            if let Err(e) = std::io::stdin().read_to_string(&mut buffer) {
+                eprintln!("Failed to read prompt from stdin: {e}");
+                std::process::exit(1);
+            } else if buffer.trim().is_empty() {
+                eprintln!("No prompt provided via stdin.");
+                std::process::exit(1);
+            }
+            buffer
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use codex_core::protocol::AgentMessageEvent;
 use codex_core::protocol::AgentReasoningEvent;
+use codex_core::protocol::AgentRunBeginEvent;
+use codex_core::protocol::AgentRunEndEvent;
 use codex_core::protocol::ErrorEvent;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
@@ -12,11 +14,16 @@ use codex_core::protocol::McpToolCallEndEvent;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
 use codex_core::protocol::SessionConfiguredEvent;
+use codex_core::protocol::TurnAbortReason;
+use codex_core::protocol::TurnAbortedEvent;
 use codex_core::protocol::WebSearchEndEvent;
 use codex_exec::event_processor_with_jsonl_output::EventProcessorWithJsonOutput;
+use codex_exec::exec_events::AgentFinding as ExecAgentFinding;
 use codex_exec::exec_events::AgentMessageItem;
 use codex_exec::exec_events::CommandExecutionItem;
 use codex_exec::exec_events::CommandExecutionStatus;
+use codex_exec::exec_events::ErrorItem;
+use codex_exec::exec_events::FindingSeverity;
 use codex_exec::exec_events::ItemCompletedEvent;
 use codex_exec::exec_events::ItemStartedEvent;
 use codex_exec::exec_events::ItemUpdatedEvent;
@@ -37,6 +44,9 @@ use codex_exec::exec_events::TurnFailedEvent;
 use codex_exec::exec_events::TurnStartedEvent;
 use codex_exec::exec_events::Usage;
 use codex_exec::exec_events::WebSearchItem;
+use codex_protocol::agents::AgentFinding;
+use codex_protocol::agents::AgentFindingSeverity;
+use codex_protocol::agents::AgentRunResult;
 use codex_protocol::plan_tool::PlanItemArg;
 use codex_protocol::plan_tool::StepStatus;
 use codex_protocol::plan_tool::UpdatePlanArgs;
@@ -494,6 +504,31 @@ fn error_followed_by_task_complete_produces_turn_failed() {
     );
 }
 
+#[test]
+fn budget_exceeded_abort_produces_turn_failed() {
+    let mut ep = EventProcessorWithJsonOutput::new(None);
+    let message = "Turn token budget exhausted (1200 of 1000 used)".to_string();
+
+    ep.collect_thread_events(&event(
+        "e1",
+        EventMsg::Error(ErrorEvent {
+            message: message.clone(),
+        }),
+    ));
+    let aborted = event(
+        "e2",
+        EventMsg::TurnAborted(TurnAbortedEvent {
+            reason: TurnAbortReason::BudgetExceeded,
+        }),
+    );
+    assert_eq!(
+        ep.collect_thread_events(&aborted),
+        vec![ThreadEvent::TurnFailed(TurnFailedEvent {
+            error: ThreadErrorEvent { message },
+        })]
+    );
+}
+
 #[test]
 fn exec_command_end_success_produces_completed_command_item() {
     let mut ep = EventProcessorWithJsonOutput::new(None);
@@ -535,6 +570,7 @@ fn exec_command_end_success_produces_completed_command_item() {
             exit_code: 0,
             duration: Duration::from_millis(5),
             formatted_output: String::new(),
+            limit_exceeded: None,
         }),
     );
     let out_ok = ep.collect_thread_events(&end_ok);
@@ -594,6 +630,7 @@ fn exec_command_end_failure_produces_failed_command_item() {
             exit_code: 1,
             duration: Duration::from_millis(2),
             formatted_output: String::new(),
+            limit_exceeded: None,
         }),
     );
     let out_fail = ep.collect_thread_events(&end_fail);
@@ -628,6 +665,7 @@ fn exec_command_end_without_begin_is_ignored() {
             exit_code: 0,
             duration: Duration::from_millis(1),
             formatted_output: String::new(),
+            limit_exceeded: None,
         }),
     );
     let out = ep.collect_thread_events(&end_only);
@@ -792,6 +830,7 @@ fn task_complete_produces_turn_completed_with_usage() {
         EventMsg::TokenCount(codex_core::protocol::TokenCountEvent {
             info: Some(info),
             rate_limits: None,
+            budget: None,
         }),
     );
     assert!(ep.collect_thread_events(&token_count_event).is_empty());
@@ -815,3 +854,74 @@ fn task_complete_produces_turn_completed_with_usage() {
         })]
     );
 }
+
+#[test]
+fn agent_run_end_produces_agent_command_result_item() {
+    let mut ep = EventProcessorWithJsonOutput::new(None);
+    let result = AgentRunResult::CodeReview {
+        findings: vec![AgentFinding {
+            severity: AgentFindingSeverity::Warning,
+            category: "Style".to_string(),
+            message: "Prefer let-else".to_string(),
+            path: Some(PathBuf::from("src/lib.rs")),
+            line: Some(3),
+        }],
+    };
+    let begin = event(
+        "a1",
+        EventMsg::AgentRunBegin(AgentRunBeginEvent {
+            command: "review".to_string(),
+            invocation: "/review src".to_string(),
+        }),
+    );
+    assert!(ep.collect_thread_events(&begin).is_empty());
+
+    let end = event(
+        "a2",
+        EventMsg::AgentRunEnd(AgentRunEndEvent {
+            command: "review".to_string(),
+            duration: Duration::from_millis(250),
+            result: Ok(result),
+        }),
+    );
+    let out = ep.collect_thread_events(&end);
+    assert_eq!(out.len(), 1);
+    let ThreadEvent::ItemCompleted(ItemCompletedEvent { item }) = &out[0] else {
+        panic!("unexpected event: {:?}", out[0]);
+    };
+    let ThreadItemDetails::AgentCommandResult(details) = &item.details else {
+        panic!("unexpected details: {:?}", item.details);
+    };
+    assert_eq!(details.command, "review");
+    assert_eq!(
+        details.findings,
+        vec![ExecAgentFinding {
+            severity: FindingSeverity::Warning,
+            category: "Style".to_string(),
+            message: "Prefer let-else".to_string(),
+            path: Some("src/lib.rs".to_string()),
+            line: Some(3),
+        }]
+    );
+    assert!(details.output.contains("Prefer let-else"));
+
+    let failed = event(
+        "a3",
+        EventMsg::AgentRunEnd(AgentRunEndEvent {
+            command: "review".to_string(),
+            duration: Duration::from_millis(10),
+            result: Err("no agent available".to_string()),
+        }),
+    );
+    assert_eq!(
+        ep.collect_thread_events(&failed),
+        vec![ThreadEvent::ItemCompleted(ItemCompletedEvent {
+            item: ThreadItem {
+                id: "item_1".to_string(),
+                details: ThreadItemDetails::Error(ErrorItem {
+                    message: "/review failed: no agent available".to_string(),
+                }),
+            },
+        })]
+    );
+}
@@ -0,0 +1,118 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use core_test_support::responses;
+use core_test_support::test_codex_exec::TestCodexExecBuilder;
+use core_test_support::test_codex_exec::test_codex_exec;
+use serde_json::Value;
+use wiremock::MockServer;
+use wiremock::matchers::any;
+
+const SCAN_COMMAND: &str = r#"---
+name: scan
+description: Scan files for problems
+category: agents
+agent: true
+agent_id: security
+args:
+  - name: files
+    type: string
+    required: true
+    description: Files to scan
+---
+Scan {{files}}
+"#;
+
+/// Sets up a `scan` agent command and a model reply with one warning.
+async fn setup() -> (TestCodexExecBuilder, MockServer) {
+    let test = test_codex_exec();
+    let commands_dir = test.home_path().join("commands");
+    std::fs::create_dir_all(&commands_dir).unwrap();
+    std::fs::write(commands_dir.join("scan.md"), SCAN_COMMAND).unwrap();
+    std::fs::write(test.cwd_path().join("auth.rs"), "fn main() {}\n").unwrap();
+
+    let findings = serde_json::json!({
+        "findings": [{
+            "severity": "warning",
+            "category": "Weak Cryptography",
+            "message": "MD5 is broken",
+            "file": "auth.rs",
+            "line": 1
+        }]
+    })
+    .to_string();
+    let server = responses::start_mock_server().await;
+    let body = responses::sse(vec![
+        responses::ev_response_created("resp1"),
+        responses::ev_assistant_message("m1", &findings),
+        responses::ev_completed("resp1"),
+    ]);
+    responses::mount_sse_once_match(&server, any(), body).await;
+
+    (test, server)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn agent_command_emits_jsonl_result() -> anyhow::Result<()> {
+    let (test, server) = setup().await;
+
+    let output = test
+        .cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("--json")
+        .arg("--report-format")
+        .arg("sarif")
+        .arg("/scan auth.rs")
+        .output()?;
+    assert!(output.status.success());
+
+    let events: Vec<Value> = String::from_utf8(output.stdout)?
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?;
+    let types: Vec<&str> = events.iter().map(|e| e["type"].as_str().unwrap()).collect();
+    assert_eq!(types, ["turn.started", "item.completed", "turn.completed"]);
+
+    let item = &events[1]["item"];
+    assert_eq!(item["type"], "agent_command_result");
+    assert_eq!(item["command"], "scan");
+    assert_eq!(item["findings"][0]["severity"], "warning");
+    assert_eq!(item["findings"][0]["path"], "auth.rs");
+    let sarif: Value = serde_json::from_str(item["output"].as_str().unwrap())?;
+    assert_eq!(sarif["version"], "2.1.0");
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn agent_command_fails_on_severity_threshold() -> anyhow::Result<()> {
+    let (test, server) = setup().await;
+
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("--report-format")
+        .arg("github")
+        .arg("--fail-on")
+        .arg("warning")
+        .arg("/scan auth.rs")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("::warning file=auth.rs,line=1"));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn agent_command_passes_below_severity_threshold() -> anyhow::Result<()> {
+    let (test, server) = setup().await;
+
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("--fail-on")
+        .arg("error")
+        .arg("/scan auth.rs")
+        .assert()
+        .success();
+
+    Ok(())
+}
@@ -0,0 +1,151 @@
+#![cfg(unix)]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use std::os::unix::fs::PermissionsExt;
+
+use core_test_support::responses;
+use core_test_support::test_codex_exec::test_codex_exec;
+use serde_json::Value;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+
+fn shell_call_then_message(call_id: &str) -> Vec<String> {
+    vec![
+        responses::sse(vec![
+            responses::ev_response_created("resp1"),
+            responses::ev_local_shell_call(call_id, "completed", vec!["/bin/echo", "bridged"]),
+            responses::ev_completed("resp1"),
+        ]),
+        responses::sse(vec![
+            responses::ev_response_created("resp2"),
+            responses::ev_assistant_message("m1", "done"),
+            responses::ev_completed("resp2"),
+        ]),
+    ]
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn exec_approval_command_receives_request_and_approves() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    let server = responses::start_mock_server().await;
+    let response_mock =
+        responses::mount_sse_sequence(&server, shell_call_then_message("call_approve")).await;
+
+    let request_path = test.cwd_path().join("request.json");
+    let script_path = test.cwd_path().join("approve.sh");
+    std::fs::write(
+        &script_path,
+        format!(
+            "#!/bin/sh\ncat > '{}'\necho '{{\"decision\":\"approved\"}}'\n",
+            request_path.display()
+        ),
+    )?;
+    std::fs::set_permissions(&script_path, std::fs::Permissions::from_mode(0o755))?;
+
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("--approval-command")
+        .arg(&script_path)
+        .arg("-a")
+        .arg("untrusted")
+        .arg("run echo")
+        .assert()
+        .success();
+
+    let request: Value = serde_json::from_str(&std::fs::read_to_string(&request_path)?)?;
+    assert_eq!(request["type"], "exec");
+    assert_eq!(request["call_id"], "call_approve");
+    assert_eq!(
+        request["command"],
+        serde_json::json!(["/bin/echo", "bridged"])
+    );
+
+    let requests = response_mock.requests();
+    assert_eq!(requests.len(), 2);
+    let output = requests[1].function_call_output("call_approve");
+    assert!(output.to_string().contains("bridged"), "{output}");
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn exec_approval_socket_forwards_denial() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    let server = responses::start_mock_server().await;
+    let response_mock =
+        responses::mount_sse_sequence(&server, shell_call_then_message("call_deny")).await;
+
+    let socket_path = test.cwd_path().join("approvals.sock");
+    let listener = tokio::net::UnixListener::bind(&socket_path)?;
+    let decider = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.expect("accept");
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        reader.read_line(&mut line).await.expect("read request");
+        reader
+            .get_mut()
+            .write_all(b"denied\n")
+            .await
+            .expect("write decision");
+        serde_json::from_str::<Value>(&line).expect("request is JSON")
+    });
+
+    let socket_arg = socket_path.clone();
+    tokio::task::spawn_blocking(move || {
+        test.cmd_with_server(&server)
+            .arg("--skip-git-repo-check")
+            .arg("--approval-socket")
+            .arg(&socket_arg)
+            .arg("-a")
+            .arg("untrusted")
+            .arg("run echo")
+            .assert()
+            .success();
+    })
+    .await?;
+
+    let request = decider.await?;
+    assert_eq!(request["type"], "exec");
+    assert_eq!(request["call_id"], "call_deny");
+
+    let requests = response_mock.requests();
+    assert_eq!(requests.len(), 2);
+    let output = requests[1].function_call_output("call_deny");
+    assert!(!output.to_string().contains("bridged"), "{output}");
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn exec_approval_command_times_out_and_denies() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    let server = responses::start_mock_server().await;
+    let response_mock =
+        responses::mount_sse_sequence(&server, shell_call_then_message("call_slow")).await;
+
+    let script_path = test.cwd_path().join("slow.sh");
+    std::fs::write(&script_path, "#!/bin/sh\nexec sleep 30\n")?;
+    std::fs::set_permissions(&script_path, std::fs::Permissions::from_mode(0o755))?;
+
+    let started = std::time::Instant::now();
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("--approval-command")
+        .arg(&script_path)
+        .arg("--approval-timeout")
+        .arg("1")
+        .arg("-a")
+        .arg("untrusted")
+        .arg("run echo")
+        .assert()
+        .success();
+    assert!(started.elapsed() < std::time::Duration::from_secs(20));
+
+    let requests = response_mock.requests();
+    assert_eq!(requests.len(), 2);
+    let output = requests[1].function_call_output("call_slow");
+    assert!(!output.to_string().contains("bridged"), "{output}");
+
+    Ok(())
+}
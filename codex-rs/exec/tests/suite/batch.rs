@@ -0,0 +1,127 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use core_test_support::responses;
+use core_test_support::test_codex_exec::test_codex_exec;
+use serde_json::Value;
+use serde_json::json;
+
+fn summary_lines(stdout: &[u8]) -> Vec<Value> {
+    let mut lines: Vec<Value> = String::from_utf8_lossy(stdout)
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("summary line is JSON"))
+        .collect();
+    lines.sort_by_key(|line| line["id"].as_str().unwrap_or_default().to_string());
+    lines
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn exec_batch_runs_each_task_in_its_own_thread() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+
+    let server = responses::start_mock_server().await;
+    let body = responses::sse(vec![
+        responses::ev_response_created("resp1"),
+        responses::ev_assistant_message("m1", "batch done"),
+        responses::ev_completed_with_tokens("resp1", 42),
+    ]);
+    let response_mock = responses::mount_sse(&server, body).await;
+
+    let schema = json!({
+        "type": "object",
+        "properties": { "answer": { "type": "string" } },
+        "required": ["answer"],
+        "additionalProperties": false
+    });
+    let tasks = [
+        json!({ "id": "a", "prompt": "first task" }),
+        json!({ "id": "b", "prompt": "second task", "cwd": ".", "output_schema": schema }),
+    ]
+    .map(|task| task.to_string())
+    .join("\n");
+    let batch_path = test.cwd_path().join("tasks.jsonl");
+    std::fs::write(&batch_path, tasks)?;
+
+    let output = test
+        .cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("--batch")
+        .arg(&batch_path)
+        .arg("--max-concurrency")
+        .arg("2")
+        .output()?;
+    assert!(output.status.success(), "{output:?}");
+
+    let summaries = summary_lines(&output.stdout);
+    assert_eq!(summaries.len(), 2, "{summaries:?}");
+    for (summary, id) in summaries.iter().zip(["a", "b"]) {
+        assert_eq!(summary["id"], id);
+        assert_eq!(summary["result"]["type"], "turn.completed");
+        assert_eq!(summary["usage"]["input_tokens"], 42);
+        assert_eq!(summary["last_agent_message"], "batch done");
+        let rollout_path = summary["rollout_path"].as_str().expect("rollout_path");
+        assert!(std::path::Path::new(rollout_path).exists());
+    }
+    assert_ne!(summaries[0]["thread_id"], summaries[1]["thread_id"]);
+    assert_ne!(summaries[0]["rollout_path"], summaries[1]["rollout_path"]);
+
+    let requests = response_mock.requests();
+    assert_eq!(requests.len(), 2);
+    let formats: Vec<Value> = requests
+        .iter()
+        .map(|request| request.body_json()["text"]["format"].clone())
+        .collect();
+    assert!(
+        formats
+            .iter()
+            .any(|format| format["schema"] == schema && format["strict"] == true),
+        "{formats:?}"
+    );
+    assert!(formats.iter().any(Value::is_null), "{formats:?}");
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn exec_batch_reports_failed_tasks_and_exits_non_zero() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+
+    let server = responses::start_mock_server().await;
+    let body = responses::sse(vec![
+        responses::ev_response_created("resp1"),
+        responses::ev_assistant_message("m1", "batch done"),
+        responses::ev_completed("resp1"),
+    ]);
+    responses::mount_sse(&server, body).await;
+
+    let tasks = [
+        json!({ "prompt": "works" }),
+        json!({ "prompt": "missing cwd", "cwd": "does-not-exist" }),
+    ]
+    .map(|task| task.to_string())
+    .join("\n");
+    let batch_path = test.cwd_path().join("tasks.jsonl");
+    std::fs::write(&batch_path, tasks)?;
+
+    let output = test
+        .cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("--batch")
+        .arg(&batch_path)
+        .output()?;
+    assert!(!output.status.success(), "{output:?}");
+
+    let summaries = summary_lines(&output.stdout);
+    assert_eq!(summaries.len(), 2, "{summaries:?}");
+    assert_eq!(summaries[0]["id"], "1");
+    assert_eq!(summaries[0]["result"]["type"], "turn.completed");
+    assert_eq!(summaries[1]["id"], "2");
+    assert_eq!(summaries[1]["result"]["type"], "turn.failed");
+    let message = summaries[1]["result"]["error"]["message"]
+        .as_str()
+        .expect("error message");
+    assert!(message.contains("does-not-exist"), "{message}");
+    assert!(summaries[1].get("thread_id").is_none());
+
+    Ok(())
+}
@@ -1,6 +1,9 @@
 // Aggregates all former standalone integration tests as modules.
+mod agent_command;
 mod apply_patch;
+mod approval_bridge;
 mod auth_env;
+mod batch;
 mod originator;
 mod output_schema;
 mod resume;
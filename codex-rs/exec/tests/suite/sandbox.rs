@@ -1,5 +1,6 @@
 #![cfg(unix)]
 use codex_core::protocol::SandboxPolicy;
+use codex_core::resource_limits::ResourceLimiter;
 use codex_core::spawn::StdioPolicy;
 use std::collections::HashMap;
 use std::future::Future;
@@ -27,6 +28,7 @@ async fn spawn_command_under_sandbox(
         sandbox_cwd,
         stdio_policy,
         env,
+        &ResourceLimiter::unlimited(),
     )
     .await
 }
@@ -50,6 +52,7 @@ async fn spawn_command_under_sandbox(
         sandbox_cwd,
         stdio_policy,
         env,
+        &ResourceLimiter::unlimited(),
     )
     .await
 }
@@ -70,6 +73,7 @@ async fn python_multiprocessing_lock_works_under_sandbox() {
     let policy = SandboxPolicy::WorkspaceWrite {
         writable_roots,
         network_access: false,
+        network_allowlist: vec![],
         exclude_tmpdir_env_var: false,
         exclude_slash_tmp: false,
     };
@@ -130,6 +134,7 @@ async fn sandbox_distinguishes_command_and_policy_cwds() {
     let policy = SandboxPolicy::WorkspaceWrite {
         writable_roots: vec![],
         network_access: false,
+        network_allowlist: vec![],
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
     };
@@ -17,6 +17,7 @@ use std::str::FromStr;
 const MATCHED_BUT_WRITES_FILES_EXIT_CODE: i32 = 12;
 const MIGHT_BE_SAFE_EXIT_CODE: i32 = 13;
 const FORBIDDEN_EXIT_CODE: i32 = 14;
+const ASK_EXIT_CODE: i32 = 15;
 
 #[derive(Parser, Deserialize, Debug)]
 #[command(version, about, long_about = None)]
@@ -113,6 +114,16 @@ fn check_command(
                 (Output::Safe { r#match: exec }, 0)
             }
         }
+        Ok(MatchedExec::Ask { exec, reason }) => {
+            let exit_code = if check { ASK_EXIT_CODE } else { 0 };
+            (
+                Output::Ask {
+                    reason,
+                    r#match: exec,
+                },
+                exit_code,
+            )
+        }
         Ok(MatchedExec::Forbidden { reason, cause }) => {
             let exit_code = if check { FORBIDDEN_EXIT_CODE } else { 0 };
             (Output::Forbidden { reason, cause }, exit_code)
@@ -136,6 +147,10 @@ pub enum Output {
     #[serde(rename = "match")]
     Match { r#match: ValidExec },
 
+    /// The command matched a rule that requires the user to approve it.
+    #[serde(rename = "ask")]
+    Ask { reason: String, r#match: ValidExec },
+
     /// The user is forbidden from running the command.
     #[serde(rename = "forbidden")]
     Forbidden {
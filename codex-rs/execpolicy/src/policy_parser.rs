@@ -128,6 +128,7 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
         options: Option<UnpackList<Opt>>,
         args: Option<UnpackList<ArgMatcher>>,
         forbidden: Option<String>,
+        ask: Option<String>,
         should_match: Option<UnpackList<UnpackList<String>>>,
         should_not_match: Option<UnpackList<UnpackList<String>>>,
         eval: &mut Evaluator,
@@ -138,6 +139,12 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
         let options = options.map_or_else(Vec::new, |v| v.items.to_vec());
         let args = args.map_or_else(Vec::new, |v| v.items.to_vec());
 
+        if forbidden.is_some() && ask.is_some() {
+            return Err(anyhow::format_err!(
+                "program {program} cannot be both `forbidden` and `ask`"
+            ));
+        }
+
         let mut allowed_options = HashMap::<String, Opt>::new();
         for opt in options {
             let name = opt.name().to_string();
@@ -157,6 +164,7 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
             allowed_options,
             args,
             forbidden,
+            ask,
             should_match
                 .map_or_else(Vec::new, |v| v.items.to_vec())
                 .into_iter()
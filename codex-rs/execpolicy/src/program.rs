@@ -24,6 +24,7 @@ pub struct ProgramSpec {
     pub allowed_options: HashMap<String, Opt>,
     pub arg_patterns: Vec<ArgMatcher>,
     forbidden: Option<String>,
+    ask: Option<String>,
     required_options: HashSet<String>,
     should_match: Vec<Vec<String>>,
     should_not_match: Vec<Vec<String>>,
@@ -38,6 +39,7 @@ impl ProgramSpec {
         allowed_options: HashMap<String, Opt>,
         arg_patterns: Vec<ArgMatcher>,
         forbidden: Option<String>,
+        ask: Option<String>,
         should_match: Vec<Vec<String>>,
         should_not_match: Vec<Vec<String>>,
     ) -> Self {
@@ -59,6 +61,7 @@ impl ProgramSpec {
             allowed_options,
             arg_patterns,
             forbidden,
+            ask,
             required_options,
             should_match,
             should_not_match,
@@ -68,8 +71,18 @@ impl ProgramSpec {
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 pub enum MatchedExec {
-    Match { exec: ValidExec },
-    Forbidden { cause: Forbidden, reason: String },
+    Match {
+        exec: ValidExec,
+    },
+    /// The command matched a rule that requires a human to approve it.
+    Ask {
+        exec: ValidExec,
+        reason: String,
+    },
+    Forbidden {
+        cause: Forbidden,
+        reason: String,
+    },
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize)]
@@ -185,12 +198,16 @@ impl ProgramSpec {
             args: matched_args,
             system_path: self.system_path.clone(),
         };
-        match &self.forbidden {
-            Some(reason) => Ok(MatchedExec::Forbidden {
+        match (&self.forbidden, &self.ask) {
+            (Some(reason), _) => Ok(MatchedExec::Forbidden {
                 cause: Forbidden::Exec { exec },
                 reason: reason.clone(),
             }),
-            None => Ok(MatchedExec::Match { exec }),
+            (None, Some(reason)) => Ok(MatchedExec::Ask {
+                exec,
+                reason: reason.clone(),
+            }),
+            (None, None) => Ok(MatchedExec::Match { exec }),
         }
     }
 
@@ -0,0 +1,64 @@
+use codex_execpolicy::ArgType;
+use codex_execpolicy::ExecCall;
+use codex_execpolicy::MatchedArg;
+use codex_execpolicy::MatchedExec;
+use codex_execpolicy::PolicyParser;
+use codex_execpolicy::Result;
+use codex_execpolicy::ValidExec;
+
+extern crate codex_execpolicy;
+
+#[test]
+fn test_ask_rule_requires_approval() -> Result<()> {
+    let unparsed_policy = r#"
+define_program(
+    program="git",
+    args=["push"],
+    ask="pushing publishes commits",
+    should_match=[["push"]],
+    should_not_match=[["status"]],
+)
+"#;
+    let parser = PolicyParser::new("test_ask_rule_requires_approval", unparsed_policy);
+    let policy = parser.parse().expect("failed to parse policy");
+    assert_eq!(
+        Ok(MatchedExec::Ask {
+            exec: ValidExec::new(
+                "git",
+                vec![MatchedArg::new(
+                    0,
+                    ArgType::Literal("push".to_string()),
+                    "push"
+                )?],
+                &[]
+            ),
+            reason: "pushing publishes commits".to_string(),
+        }),
+        policy.check(&ExecCall::new("git", &["push"]))
+    );
+    assert!(policy.check_each_good_list_individually().is_empty());
+    assert!(policy.check_each_bad_list_individually().is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_ask_and_forbidden_are_exclusive() {
+    let unparsed_policy = r#"
+define_program(
+    program="git",
+    args=["push"],
+    ask="pushing publishes commits",
+    forbidden="no pushing",
+)
+"#;
+    let parser = PolicyParser::new("test_ask_and_forbidden_are_exclusive", unparsed_policy);
+    let err = parser
+        .parse()
+        .err()
+        .expect("policy with both `ask` and `forbidden` should not parse");
+    assert!(
+        err.to_string()
+            .contains("cannot be both `forbidden` and `ask`"),
+        "unexpected error: {err}"
+    );
+}
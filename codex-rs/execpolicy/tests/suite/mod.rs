@@ -1,4 +1,5 @@
 // Aggregates all former standalone integration tests as modules.
+mod ask;
 mod bad;
 mod cp;
 mod good;
@@ -6,12 +6,17 @@ use codex_core::error::CodexErr;
 use codex_core::error::Result;
 use codex_core::error::SandboxErr;
 use codex_core::protocol::SandboxPolicy;
+use codex_core::spawn::CODEX_SANDBOX_NETWORK_PROXY_PORT_ENV_VAR;
+
+use crate::network_namespace::enter_proxy_network_namespace;
 
 use landlock::ABI;
 use landlock::Access;
 use landlock::AccessFs;
+use landlock::AccessNet;
 use landlock::CompatLevel;
 use landlock::Compatible;
+use landlock::NetPort;
 use landlock::Ruleset;
 use landlock::RulesetAttr;
 use landlock::RulesetCreatedAttr;
@@ -32,7 +37,14 @@ pub(crate) fn apply_sandbox_policy_to_current_thread(
     cwd: &Path,
 ) -> Result<()> {
     if !sandbox_policy.has_full_network_access() {
-        install_network_seccomp_filter_on_current_thread()?;
+        let proxy_port = network_proxy_port(sandbox_policy);
+        if let Some(port) = proxy_port {
+            enter_proxy_network_namespace(port)?;
+        }
+        install_network_seccomp_filter_on_current_thread(proxy_port.is_some())?;
+        if let Some(port) = proxy_port {
+            install_network_landlock_rules_on_current_thread(port)?;
+        }
     }
 
     if !sandbox_policy.has_full_disk_write_access() {
@@ -52,6 +64,40 @@ pub(crate) fn apply_sandbox_policy_to_current_thread(
     Ok(())
 }
 
+/// Port of Codex's egress proxy, when the policy limits network access to an
+/// allowlist and Codex started the proxy for this command.
+fn network_proxy_port(sandbox_policy: &SandboxPolicy) -> Option<u16> {
+    if sandbox_policy.network_allowlist().is_empty() {
+        return None;
+    }
+    std::env::var(CODEX_SANDBOX_NETWORK_PROXY_PORT_ENV_VAR)
+        .ok()?
+        .parse()
+        .ok()
+}
+
+/// Installs Landlock network rules on the current thread that only allow TCP
+/// connections to `proxy_port`. Unlike the file-system rules, these are a hard
+/// requirement (Landlock ABI v4): without them the seccomp filter installed in
+/// proxy mode would leave TCP unrestricted. The rules cannot tell hosts apart,
+/// which is why the command also runs in a loopback-only network namespace.
+fn install_network_landlock_rules_on_current_thread(proxy_port: u16) -> Result<()> {
+    let abi = ABI::V4;
+    let status = Ruleset::default()
+        .set_compatibility(CompatLevel::HardRequirement)
+        .handle_access(AccessNet::from_all(abi))?
+        .create()?
+        .add_rule(NetPort::new(proxy_port, AccessNet::ConnectTcp))?
+        .set_no_new_privs(true)
+        .restrict_self()?;
+
+    if status.ruleset != landlock::RulesetStatus::FullyEnforced {
+        return Err(CodexErr::Sandbox(SandboxErr::LandlockRestrict));
+    }
+
+    Ok(())
+}
+
 /// Installs Landlock file-system rules on the current thread allowing read
 /// access to the entire file-system while restricting write access to
 /// `/dev/null` and the provided list of `writable_roots`.
@@ -86,7 +132,12 @@ fn install_filesystem_landlock_rules_on_current_thread(writable_roots: Vec<PathB
 
 /// Installs a seccomp filter that blocks outbound network access except for
 /// AF_UNIX domain sockets.
-fn install_network_seccomp_filter_on_current_thread() -> std::result::Result<(), SandboxErr> {
+///
+/// With `allow_tcp_streams`, TCP sockets may also be created and used; the
+/// Landlock network rules then decide which ports they may connect to.
+fn install_network_seccomp_filter_on_current_thread(
+    allow_tcp_streams: bool,
+) -> std::result::Result<(), SandboxErr> {
     // Build rule map.
     let mut rules: BTreeMap<i64, Vec<SeccompRule>> = BTreeMap::new();
 
@@ -95,25 +146,27 @@ fn install_network_seccomp_filter_on_current_thread() -> std::result::Result<(),
         rules.insert(nr, vec![]); // empty rule vec = unconditional match
     };
 
-    deny_syscall(libc::SYS_connect);
     deny_syscall(libc::SYS_accept);
     deny_syscall(libc::SYS_accept4);
     deny_syscall(libc::SYS_bind);
     deny_syscall(libc::SYS_listen);
-    deny_syscall(libc::SYS_getpeername);
-    deny_syscall(libc::SYS_getsockname);
-    deny_syscall(libc::SYS_shutdown);
-    deny_syscall(libc::SYS_sendto);
-    deny_syscall(libc::SYS_sendmsg);
-    deny_syscall(libc::SYS_sendmmsg);
-    // NOTE: allowing recvfrom allows some tools like: `cargo clippy` to run
-    // with their socketpair + child processes for sub-proc management
-    // deny_syscall(libc::SYS_recvfrom);
-    deny_syscall(libc::SYS_recvmsg);
-    deny_syscall(libc::SYS_recvmmsg);
-    deny_syscall(libc::SYS_getsockopt);
-    deny_syscall(libc::SYS_setsockopt);
     deny_syscall(libc::SYS_ptrace);
+    if !allow_tcp_streams {
+        deny_syscall(libc::SYS_connect);
+        deny_syscall(libc::SYS_getpeername);
+        deny_syscall(libc::SYS_getsockname);
+        deny_syscall(libc::SYS_shutdown);
+        deny_syscall(libc::SYS_sendto);
+        deny_syscall(libc::SYS_sendmsg);
+        deny_syscall(libc::SYS_sendmmsg);
+        // NOTE: allowing recvfrom allows some tools like: `cargo clippy` to run
+        // with their socketpair + child processes for sub-proc management
+        // deny_syscall(libc::SYS_recvfrom);
+        deny_syscall(libc::SYS_recvmsg);
+        deny_syscall(libc::SYS_recvmmsg);
+        deny_syscall(libc::SYS_getsockopt);
+        deny_syscall(libc::SYS_setsockopt);
+    }
 
     // For `socket` we allow AF_UNIX (arg0 == AF_UNIX) and deny everything else.
     let unix_only_rule = SeccompRule::new(vec![SeccompCondition::new(
@@ -123,7 +176,12 @@ fn install_network_seccomp_filter_on_current_thread() -> std::result::Result<(),
         libc::AF_UNIX as u64,
     )?])?;
 
-    rules.insert(libc::SYS_socket, vec![unix_only_rule.clone()]);
+    let socket_rules = if allow_tcp_streams {
+        tcp_stream_only_rules()?
+    } else {
+        vec![unix_only_rule.clone()]
+    };
+    rules.insert(libc::SYS_socket, socket_rules);
     rules.insert(libc::SYS_socketpair, vec![unix_only_rule]); // always deny (Unix can use socketpair but fine, keep open?)
 
     let filter = SeccompFilter::new(
@@ -145,3 +203,38 @@ fn install_network_seccomp_filter_on_current_thread() -> std::result::Result<(),
 
     Ok(())
 }
+
+/// `socket` rules that deny every socket except AF_UNIX and TCP (stream)
+/// sockets over IPv4/IPv6.
+fn tcp_stream_only_rules() -> std::result::Result<Vec<SeccompRule>, SandboxErr> {
+    let domain_is = |domain: libc::c_int| {
+        SeccompCondition::new(0, SeccompCmpArgLen::Dword, SeccompCmpOp::Eq, domain as u64)
+    };
+    let domain_is_not = |domain: libc::c_int| {
+        SeccompCondition::new(0, SeccompCmpArgLen::Dword, SeccompCmpOp::Ne, domain as u64)
+    };
+
+    let mut rules = vec![SeccompRule::new(vec![
+        domain_is_not(libc::AF_UNIX)?,
+        domain_is_not(libc::AF_INET)?,
+        domain_is_not(libc::AF_INET6)?,
+    ])?];
+
+    // The low nibble of the `type` argument is the socket type (the rest are
+    // SOCK_NONBLOCK/SOCK_CLOEXEC flags). SOCK_STREAM is 1; deny all others.
+    for domain in [libc::AF_INET, libc::AF_INET6] {
+        for socket_type in 2..=0xf {
+            rules.push(SeccompRule::new(vec![
+                domain_is(domain)?,
+                SeccompCondition::new(
+                    1, // second argument (type)
+                    SeccompCmpArgLen::Dword,
+                    SeccompCmpOp::MaskedEq(0xf),
+                    socket_type,
+                )?,
+            ])?);
+        }
+    }
+
+    Ok(rules)
+}
@@ -0,0 +1,204 @@
+//! Loopback-only network namespace for commands whose network access goes
+//! through Codex's egress proxy.
+//!
+//! Landlock can only restrict the *port* a TCP socket connects to, so on its
+//! own it would also let a command reach the proxy's port on any other host.
+//! Running the command in a fresh network namespace, whose only interface is
+//! loopback, leaves nothing to connect to except a listener on the proxy's
+//! port that relays every connection to the real proxy.
+
+use std::io;
+use std::net::Ipv4Addr;
+use std::net::Shutdown;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::os::fd::AsRawFd;
+use std::os::fd::FromRawFd;
+use std::os::fd::OwnedFd;
+use std::os::fd::RawFd;
+use std::os::unix::net::UnixStream;
+
+/// Room for a single file descriptor in an `SCM_RIGHTS` control message.
+const FD_CONTROL_LEN: usize = unsafe { libc::CMSG_SPACE(size_of::<RawFd>() as u32) } as usize;
+
+#[repr(C, align(8))]
+struct FdControlBuffer([u8; FD_CONTROL_LEN]);
+
+/// Moves the rest of the sandbox setup, and the command it runs, into a new
+/// user and network namespace where `127.0.0.1:proxy_port` is relayed to the
+/// proxy listening on the same port outside the namespace.
+///
+/// The current process forks. The child enters the namespace and returns so
+/// the caller can finish sandboxing it and exec the command. The parent stays
+/// outside to relay connections and exits with the child's status; it never
+/// returns.
+pub(crate) fn enter_proxy_network_namespace(proxy_port: u16) -> io::Result<()> {
+    let (parent_socket, child_socket) = UnixStream::pair()?;
+    let parent_pid = unsafe { libc::getpid() };
+
+    match unsafe { libc::fork() } {
+        -1 => Err(io::Error::last_os_error()),
+        0 => {
+            drop(parent_socket);
+            // Don't outlive the relay, e.g. when Codex kills it on timeout.
+            if unsafe { libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGKILL) } == -1 {
+                return Err(io::Error::last_os_error());
+            }
+            if unsafe { libc::getppid() } != parent_pid {
+                std::process::exit(1);
+            }
+
+            unshare_network()?;
+            let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, proxy_port))?;
+            send_fd(&child_socket, listener.as_raw_fd())
+        }
+        child_pid => {
+            drop(child_socket);
+            // The child reports its own error when it fails to set up the
+            // namespace, so only its exit status matters then.
+            if let Ok(listener) = recv_fd(&parent_socket) {
+                let listener = TcpListener::from(listener);
+                std::thread::spawn(move || relay_connections(listener, proxy_port));
+            }
+            exit_with_status_of(child_pid)
+        }
+    }
+}
+
+/// Enters a new user namespace, keeping the caller's uid and gid, and a new
+/// network namespace with loopback brought up.
+fn unshare_network() -> io::Result<()> {
+    let uid = unsafe { libc::geteuid() };
+    let gid = unsafe { libc::getegid() };
+    if unsafe { libc::unshare(libc::CLONE_NEWUSER | libc::CLONE_NEWNET) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    std::fs::write("/proc/self/setgroups", "deny")?;
+    std::fs::write("/proc/self/uid_map", format!("{uid} {uid} 1"))?;
+    std::fs::write("/proc/self/gid_map", format!("{gid} {gid} 1"))?;
+
+    let socket = unsafe { libc::socket(libc::AF_INET, libc::SOCK_DGRAM | libc::SOCK_CLOEXEC, 0) };
+    if socket == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    let socket = unsafe { OwnedFd::from_raw_fd(socket) };
+
+    let mut ifreq: libc::ifreq = unsafe { std::mem::zeroed() };
+    for (dst, src) in ifreq.ifr_name.iter_mut().zip(b"lo") {
+        *dst = *src as libc::c_char;
+    }
+    if unsafe { libc::ioctl(socket.as_raw_fd(), libc::SIOCGIFFLAGS as _, &mut ifreq) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    unsafe {
+        ifreq.ifr_ifru.ifru_flags |= (libc::IFF_UP | libc::IFF_RUNNING) as libc::c_short;
+    }
+    if unsafe { libc::ioctl(socket.as_raw_fd(), libc::SIOCSIFFLAGS as _, &ifreq) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+fn relay_connections(listener: TcpListener, proxy_port: u16) {
+    for client in listener.incoming().flatten() {
+        std::thread::spawn(move || {
+            if let Ok(upstream) = TcpStream::connect((Ipv4Addr::LOCALHOST, proxy_port)) {
+                relay(client, upstream);
+            }
+        });
+    }
+}
+
+/// Copies bytes both ways until each side has closed its half.
+fn relay(mut client: TcpStream, mut upstream: TcpStream) {
+    let (Ok(mut client_reader), Ok(mut upstream_writer)) =
+        (client.try_clone(), upstream.try_clone())
+    else {
+        return;
+    };
+    let outbound = std::thread::spawn(move || {
+        let _ = io::copy(&mut client_reader, &mut upstream_writer);
+        let _ = upstream_writer.shutdown(Shutdown::Write);
+    });
+    let _ = io::copy(&mut upstream, &mut client);
+    let _ = client.shutdown(Shutdown::Write);
+    let _ = outbound.join();
+}
+
+/// Waits for `pid` and exits the same way it did.
+fn exit_with_status_of(pid: libc::pid_t) -> ! {
+    let mut status = 0;
+    while unsafe { libc::waitpid(pid, &mut status, 0) } == -1 {
+        if io::Error::last_os_error().kind() != io::ErrorKind::Interrupted {
+            std::process::exit(1);
+        }
+    }
+
+    if libc::WIFSIGNALED(status) {
+        let signal = libc::WTERMSIG(status);
+        unsafe {
+            libc::signal(signal, libc::SIG_DFL);
+            libc::raise(signal);
+        }
+        std::process::exit(128 + signal);
+    }
+    std::process::exit(libc::WEXITSTATUS(status))
+}
+
+fn send_fd(socket: &UnixStream, fd: RawFd) -> io::Result<()> {
+    let mut byte = [0u8];
+    let mut iov = libc::iovec {
+        iov_base: byte.as_mut_ptr().cast(),
+        iov_len: byte.len(),
+    };
+    let mut control = FdControlBuffer([0; FD_CONTROL_LEN]);
+    let mut msg: libc::msghdr = unsafe { std::mem::zeroed() };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.0.as_mut_ptr().cast();
+    msg.msg_controllen = FD_CONTROL_LEN as _;
+
+    unsafe {
+        let cmsg = libc::CMSG_FIRSTHDR(&msg);
+        (*cmsg).cmsg_level = libc::SOL_SOCKET;
+        (*cmsg).cmsg_type = libc::SCM_RIGHTS;
+        (*cmsg).cmsg_len = libc::CMSG_LEN(size_of::<RawFd>() as u32) as _;
+        std::ptr::write_unaligned(libc::CMSG_DATA(cmsg).cast::<RawFd>(), fd);
+    }
+
+    if unsafe { libc::sendmsg(socket.as_raw_fd(), &msg, 0) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+fn recv_fd(socket: &UnixStream) -> io::Result<OwnedFd> {
+    let mut byte = [0u8];
+    let mut iov = libc::iovec {
+        iov_base: byte.as_mut_ptr().cast(),
+        iov_len: byte.len(),
+    };
+    let mut control = FdControlBuffer([0; FD_CONTROL_LEN]);
+    let mut msg: libc::msghdr = unsafe { std::mem::zeroed() };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.0.as_mut_ptr().cast();
+    msg.msg_controllen = FD_CONTROL_LEN as _;
+
+    if unsafe { libc::recvmsg(socket.as_raw_fd(), &mut msg, libc::MSG_CMSG_CLOEXEC) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    let cmsg = unsafe { libc::CMSG_FIRSTHDR(&msg) };
+    if cmsg.is_null()
+        || unsafe { (*cmsg).cmsg_level } != libc::SOL_SOCKET
+        || unsafe { (*cmsg).cmsg_type } != libc::SCM_RIGHTS
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "no file descriptor received",
+        ));
+    }
+    let fd = unsafe { std::ptr::read_unaligned(libc::CMSG_DATA(cmsg).cast::<RawFd>()) };
+    Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+}
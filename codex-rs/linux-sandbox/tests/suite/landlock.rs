@@ -1,4 +1,5 @@
 #![cfg(target_os = "linux")]
+use codex_core::config_types::SandboxLimits;
 use codex_core::config_types::ShellEnvironmentPolicy;
 use codex_core::error::CodexErr;
 use codex_core::error::SandboxErr;
@@ -6,8 +7,17 @@ use codex_core::exec::ExecParams;
 use codex_core::exec::SandboxType;
 use codex_core::exec::process_exec_tool_call;
 use codex_core::exec_env::create_env;
+use codex_core::network_proxy::NetworkProxy;
 use codex_core::protocol::SandboxPolicy;
+use landlock::ABI;
+use landlock::Access;
+use landlock::AccessNet;
+use landlock::CompatLevel;
+use landlock::Compatible;
+use landlock::Ruleset;
+use landlock::RulesetAttr;
 use std::collections::HashMap;
+use std::io::Write;
 use std::path::PathBuf;
 use tempfile::NamedTempFile;
 
@@ -49,6 +59,7 @@ async fn run_cmd(cmd: &[&str], writable_roots: &[PathBuf], timeout_ms: u64) {
     let sandbox_policy = SandboxPolicy::WorkspaceWrite {
         writable_roots: writable_roots.to_vec(),
         network_access: false,
+        network_allowlist: vec![],
         // Exclude tmp-related folders from writable roots because we need a
         // folder that is writable by tests but that we intentionally disallow
         // writing to in the sandbox.
@@ -61,6 +72,7 @@ async fn run_cmd(cmd: &[&str], writable_roots: &[PathBuf], timeout_ms: u64) {
         params,
         SandboxType::LinuxSeccomp,
         &sandbox_policy,
+        &SandboxLimits::default(),
         sandbox_cwd.as_path(),
         &codex_linux_sandbox_exe,
         None,
@@ -155,6 +167,7 @@ async fn assert_network_blocked(cmd: &[&str]) {
         params,
         SandboxType::LinuxSeccomp,
         &sandbox_policy,
+        &SandboxLimits::default(),
         sandbox_cwd.as_path(),
         &codex_linux_sandbox_exe,
         None,
@@ -234,3 +247,138 @@ async fn sandbox_blocks_dev_tcp_redirection() {
     // all images ship bash, so we guard against 127 as well.
     assert_network_blocked(&["bash", "-c", "echo hi > /dev/tcp/127.0.0.1/80"]).await;
 }
+
+/// Runs `cmd` under a workspace-write policy that routes network access
+/// through `proxy` and returns its exit code.
+#[expect(clippy::expect_used)]
+async fn run_cmd_with_network_proxy(cmd: &[&str], proxy: &NetworkProxy) -> i32 {
+    let cwd = std::env::current_dir().expect("cwd should exist");
+    let sandbox_cwd = cwd.clone();
+    let mut env = create_env_from_core_vars();
+    proxy.apply_env(&mut env);
+    let params = ExecParams {
+        command: cmd.iter().copied().map(str::to_owned).collect(),
+        cwd,
+        timeout_ms: Some(NETWORK_TIMEOUT_MS),
+        env,
+        with_escalated_permissions: None,
+        justification: None,
+    };
+
+    let sandbox_policy = SandboxPolicy::WorkspaceWrite {
+        writable_roots: vec![],
+        network_access: false,
+        network_allowlist: vec!["127.0.0.1".to_string()],
+        exclude_tmpdir_env_var: true,
+        exclude_slash_tmp: true,
+    };
+    let sandbox_program = env!("CARGO_BIN_EXE_codex-linux-sandbox");
+    let codex_linux_sandbox_exe = Some(PathBuf::from(sandbox_program));
+    let result = process_exec_tool_call(
+        params,
+        SandboxType::LinuxSeccomp,
+        &sandbox_policy,
+        &SandboxLimits::default(),
+        sandbox_cwd.as_path(),
+        &codex_linux_sandbox_exe,
+        None,
+    )
+    .await;
+
+    match result {
+        Ok(output) => output.exit_code,
+        Err(CodexErr::Sandbox(SandboxErr::Denied { output })) => output.exit_code,
+        _ => panic!("unexpected result: {result:?}"),
+    }
+}
+
+/// Landlock TCP rules need ABI v4 (Linux 6.7+).
+fn landlock_supports_network_rules() -> bool {
+    Ruleset::default()
+        .set_compatibility(CompatLevel::HardRequirement)
+        .handle_access(AccessNet::from_all(ABI::V4))
+        .and_then(Ruleset::create)
+        .is_ok()
+}
+
+#[tokio::test]
+#[allow(clippy::unwrap_used)]
+async fn sandbox_only_allows_connections_through_network_proxy() {
+    if !landlock_supports_network_rules() {
+        return;
+    }
+
+    let upstream = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let upstream_port = upstream.local_addr().unwrap().port();
+    std::thread::spawn(move || {
+        for mut stream in upstream.incoming().flatten() {
+            let _ = stream.write_all(b"hello");
+        }
+    });
+    let proxy = NetworkProxy::start(&[format!("127.0.0.1:{upstream_port}")])
+        .await
+        .unwrap();
+    let proxy_port = proxy.port();
+
+    // Connecting directly, bypassing the proxy, is refused by the sandbox.
+    let direct = format!("exec 3<>/dev/tcp/127.0.0.1/{upstream_port}");
+    assert_ne!(
+        run_cmd_with_network_proxy(&["bash", "-c", &direct], &proxy).await,
+        0
+    );
+
+    // Tunnelling through the proxy reaches the allowlisted host.
+    let tunnel = format!(
+        "exec 3<>/dev/tcp/127.0.0.1/{proxy_port} && \
+         printf 'CONNECT 127.0.0.1:{upstream_port} HTTP/1.1\\r\\n\\r\\n' >&3 && \
+         grep -q hello <&3"
+    );
+    assert_eq!(
+        run_cmd_with_network_proxy(&["bash", "-c", &tunnel], &proxy).await,
+        0
+    );
+
+    // UDP stays blocked even in proxy mode.
+    assert_ne!(
+        run_cmd_with_network_proxy(&["bash", "-c", "echo hi > /dev/udp/127.0.0.1/53"], &proxy)
+            .await,
+        0
+    );
+}
+
+#[tokio::test]
+#[allow(clippy::unwrap_used)]
+async fn sandbox_blocks_proxy_port_on_other_hosts() {
+    if !landlock_supports_network_rules() {
+        return;
+    }
+    // Pick the address this host would use for outbound traffic; connecting a
+    // UDP socket sends nothing.
+    let Some(host_ip) = std::net::UdpSocket::bind("0.0.0.0:0")
+        .and_then(|socket| {
+            socket.connect("192.0.2.1:9")?;
+            socket.local_addr()
+        })
+        .ok()
+        .map(|addr| addr.ip())
+        .filter(|ip| !ip.is_loopback())
+    else {
+        return;
+    };
+
+    let proxy = NetworkProxy::start(&["example.com".to_string()])
+        .await
+        .unwrap();
+    let proxy_port = proxy.port();
+    let listener = std::net::TcpListener::bind((host_ip, proxy_port)).unwrap();
+    std::thread::spawn(move || for _ in listener.incoming() {});
+    let connect = format!("exec 3<>/dev/tcp/{host_ip}/{proxy_port}");
+
+    // Reachable outside the sandbox...
+    assert!(std::net::TcpStream::connect((host_ip, proxy_port)).is_ok());
+    // ...but not from inside, even though it is on the proxy's port.
+    assert_ne!(
+        run_cmd_with_network_proxy(&["bash", "-c", &connect], &proxy).await,
+        0
+    );
+}
@@ -5,15 +5,21 @@
 //!      communicates over stdio.
 //!   2. Sending MCP requests and pairing them with their corresponding
 //!      responses.
-//!   3. Offering a convenience helper for the common `tools/list` request.
+//!   3. Offering convenience helpers for the common `tools/*`, `resources/*`
+//!      and `prompts/*` requests.
+//!   4. Forwarding server notifications to subscribers and keeping the tail
+//!      of the server's stderr for diagnostics.
 //!
 //! The crate hides all JSON‐RPC framing details behind a typed API. Users
 //! interact with the [`ModelContextProtocolRequest`] trait from `mcp-types` to
 //! issue requests and receive strongly-typed results.
 
 use std::collections::HashMap;
+use std::collections::VecDeque;
 use std::ffi::OsString;
 use std::sync::Arc;
+use std::sync::PoisonError;
+use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicI64;
 use std::sync::atomic::Ordering;
 use std::time::Duration;
@@ -23,6 +29,9 @@ use anyhow::Result;
 use anyhow::anyhow;
 use mcp_types::CallToolRequest;
 use mcp_types::CallToolRequestParams;
+use mcp_types::GetPromptRequest;
+use mcp_types::GetPromptRequestParams;
+use mcp_types::GetPromptResult;
 use mcp_types::InitializeRequest;
 use mcp_types::InitializeRequestParams;
 use mcp_types::InitializedNotification;
@@ -31,12 +40,22 @@ use mcp_types::JSONRPCMessage;
 use mcp_types::JSONRPCNotification;
 use mcp_types::JSONRPCRequest;
 use mcp_types::JSONRPCResponse;
+use mcp_types::ListPromptsRequest;
+use mcp_types::ListPromptsRequestParams;
+use mcp_types::ListPromptsResult;
+use mcp_types::ListResourcesRequest;
+use mcp_types::ListResourcesRequestParams;
+use mcp_types::ListResourcesResult;
 use mcp_types::ListToolsRequest;
 use mcp_types::ListToolsRequestParams;
 use mcp_types::ListToolsResult;
 use mcp_types::ModelContextProtocolNotification;
 use mcp_types::ModelContextProtocolRequest;
+use mcp_types::ReadResourceRequest;
+use mcp_types::ReadResourceRequestParams;
+use mcp_types::ReadResourceResult;
 use mcp_types::RequestId;
+use mcp_types::ServerNotification;
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 use tokio::io::AsyncBufReadExt;
@@ -44,6 +63,7 @@ use tokio::io::AsyncWriteExt;
 use tokio::io::BufReader;
 use tokio::process::Command;
 use tokio::sync::Mutex;
+use tokio::sync::broadcast;
 use tokio::sync::mpsc;
 use tokio::sync::oneshot;
 use tokio::time;
@@ -56,6 +76,9 @@ use tracing::warn;
 /// client API and the IO tasks.
 const CHANNEL_CAPACITY: usize = 128;
 
+/// Number of trailing stderr lines kept for diagnostics.
+const STDERR_TAIL_LINES: usize = 20;
+
 /// Internal representation of a pending request sender.
 type PendingSender = oneshot::Sender<JSONRPCMessage>;
 
@@ -76,6 +99,15 @@ pub struct McpClient {
 
     /// Monotonically increasing counter used to generate request IDs.
     id_counter: AtomicI64,
+
+    /// Server-initiated notifications, fanned out to every subscriber.
+    notifications_tx: broadcast::Sender<ServerNotification>,
+
+    /// Set once the server closed its STDOUT, i.e. the process exited.
+    closed: Arc<AtomicBool>,
+
+    /// Last [`STDERR_TAIL_LINES`] lines the server wrote to STDERR.
+    stderr_tail: Arc<std::sync::Mutex<VecDeque<String>>>,
 }
 
 impl McpClient {
@@ -93,7 +125,7 @@ impl McpClient {
             .envs(create_env_for_mcp_server(env))
             .stdin(std::process::Stdio::piped())
             .stdout(std::process::Stdio::piped())
-            .stderr(std::process::Stdio::null())
+            .stderr(std::process::Stdio::piped())
             // As noted in the `kill_on_drop` documentation, the Tokio runtime makes
             // a "best effort" to reap-after-exit to avoid zombie processes, but it
             // is not a guarantee.
@@ -108,9 +140,13 @@ impl McpClient {
             .stdout
             .take()
             .ok_or_else(|| std::io::Error::other("failed to capture child stdout"))?;
+        let stderr = child.stderr.take();
 
         let (outgoing_tx, mut outgoing_rx) = mpsc::channel::<JSONRPCMessage>(CHANNEL_CAPACITY);
         let pending: Arc<Mutex<HashMap<i64, PendingSender>>> = Arc::new(Mutex::new(HashMap::new()));
+        let (notifications_tx, _) = broadcast::channel(CHANNEL_CAPACITY);
+        let closed = Arc::new(AtomicBool::new(false));
+        let stderr_tail = Arc::new(std::sync::Mutex::new(VecDeque::new()));
 
         // Spawn writer task. It listens on the `outgoing_rx` channel and
         // writes messages to the child's STDIN.
@@ -141,6 +177,8 @@ impl McpClient {
         // STDOUT and dispatches responses to the pending map.
         let reader_handle = {
             let pending = pending.clone();
+            let notifications_tx = notifications_tx.clone();
+            let closed = closed.clone();
             let mut lines = BufReader::new(stdout).lines();
 
             tokio::spawn(async move {
@@ -153,9 +191,15 @@ impl McpClient {
                         Ok(JSONRPCMessage::Error(err)) => {
                             Self::dispatch_error(err, &pending).await;
                         }
-                        Ok(JSONRPCMessage::Notification(JSONRPCNotification { .. })) => {
-                            // For now we only log server-initiated notifications.
+                        Ok(JSONRPCMessage::Notification(notification)) => {
                             info!("<- notification: {}", line);
+                            match ServerNotification::try_from(notification) {
+                                Ok(notification) => {
+                                    // No subscribers is not an error.
+                                    let _ = notifications_tx.send(notification);
+                                }
+                                Err(e) => warn!("failed to parse server notification: {e}"),
+                            }
                         }
                         Ok(other) => {
                             // Batch responses and requests are currently not
@@ -167,23 +211,65 @@ impl McpClient {
                         }
                     }
                 }
+                closed.store(true, Ordering::SeqCst);
             })
         };
 
+        // Spawn stderr task. It keeps the last lines around so failures can
+        // be explained to the user.
+        let stderr_handle = stderr.map(|stderr| {
+            let stderr_tail = stderr_tail.clone();
+            let mut lines = BufReader::new(stderr).lines();
+            tokio::spawn(async move {
+                while let Ok(Some(line)) = lines.next_line().await {
+                    debug!("MCP server stderr: {line}");
+                    let mut tail = stderr_tail.lock().unwrap_or_else(PoisonError::into_inner);
+                    if tail.len() == STDERR_TAIL_LINES {
+                        tail.pop_front();
+                    }
+                    tail.push_back(line);
+                }
+            })
+        });
+
         // We intentionally *detach* the tasks. They will keep running in the
         // background as long as their respective resources (channels/stdin/
         // stdout) are alive. Dropping `McpClient` cancels the tasks due to
         // dropped resources.
-        let _ = (writer_handle, reader_handle);
+        let _ = (writer_handle, reader_handle, stderr_handle);
 
         Ok(Self {
             child,
             outgoing_tx,
             pending,
             id_counter: AtomicI64::new(1),
+            notifications_tx,
+            closed,
+            stderr_tail,
         })
     }
 
+    /// Subscribe to notifications sent by the server from now on.
+    pub fn subscribe_notifications(&self) -> broadcast::Receiver<ServerNotification> {
+        self.notifications_tx.subscribe()
+    }
+
+    /// Whether the server process has closed its STDOUT, which means it exited
+    /// and no further requests can succeed.
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+
+    /// The last lines the server wrote to STDERR, oldest first.
+    pub fn stderr_tail(&self) -> Vec<String> {
+        self.stderr_tail
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .cloned()
+            .collect()
+    }
+
     /// Send an arbitrary MCP request and await the typed result.
     ///
     /// If `timeout` is `None` the call waits indefinitely. If `Some(duration)`
@@ -345,6 +431,45 @@ impl McpClient {
         self.send_request::<CallToolRequest>(params, timeout).await
     }
 
+    /// Convenience wrapper around `resources/list`.
+    pub async fn list_resources(
+        &self,
+        params: Option<ListResourcesRequestParams>,
+        timeout: Option<Duration>,
+    ) -> Result<ListResourcesResult> {
+        self.send_request::<ListResourcesRequest>(params, timeout)
+            .await
+    }
+
+    /// Convenience wrapper around `resources/read`.
+    pub async fn read_resource(
+        &self,
+        params: ReadResourceRequestParams,
+        timeout: Option<Duration>,
+    ) -> Result<ReadResourceResult> {
+        self.send_request::<ReadResourceRequest>(params, timeout)
+            .await
+    }
+
+    /// Convenience wrapper around `prompts/list`.
+    pub async fn list_prompts(
+        &self,
+        params: Option<ListPromptsRequestParams>,
+        timeout: Option<Duration>,
+    ) -> Result<ListPromptsResult> {
+        self.send_request::<ListPromptsRequest>(params, timeout)
+            .await
+    }
+
+    /// Convenience wrapper around `prompts/get`.
+    pub async fn get_prompt(
+        &self,
+        params: GetPromptRequestParams,
+        timeout: Option<Duration>,
+    ) -> Result<GetPromptResult> {
+        self.send_request::<GetPromptRequest>(params, timeout).await
+    }
+
     /// Internal helper: route a JSON-RPC *response* object to the pending map.
     async fn dispatch_response(
         resp: JSONRPCResponse,
@@ -9,19 +9,23 @@ use crate::exec_approval::handle_exec_approval_request;
 use crate::outgoing_message::OutgoingMessageSender;
 use crate::outgoing_message::OutgoingNotificationMeta;
 use crate::patch_approval::handle_patch_approval_request;
+use crate::resources::SessionResources;
 use codex_core::CodexConversation;
 use codex_core::ConversationManager;
 use codex_core::NewConversation;
 use codex_core::config::Config as CodexConfig;
 use codex_core::protocol::AgentMessageEvent;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
+use codex_core::protocol::ElicitationAction;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::ExecApprovalRequestEvent;
 use codex_core::protocol::InputItem;
 use codex_core::protocol::Op;
+use codex_core::protocol::ReviewDecision;
 use codex_core::protocol::Submission;
 use codex_core::protocol::TaskCompleteEvent;
+use codex_core::protocol::TurnDiffEvent;
 use codex_protocol::ConversationId;
 use mcp_types::CallToolResult;
 use mcp_types::ContentBlock;
@@ -43,6 +47,7 @@ pub async fn run_codex_tool_session(
     outgoing: Arc<OutgoingMessageSender>,
     conversation_manager: Arc<ConversationManager>,
     running_requests_id_to_codex_uuid: Arc<Mutex<HashMap<RequestId, ConversationId>>>,
+    session_resources: Arc<SessionResources>,
 ) {
     let NewConversation {
         conversation_id,
@@ -106,9 +111,11 @@ pub async fn run_codex_tool_session(
 
     run_codex_tool_session_inner(
         conversation,
+        conversation_id,
         outgoing,
         id,
         running_requests_id_to_codex_uuid,
+        session_resources,
     )
     .await;
 }
@@ -120,6 +127,7 @@ pub async fn run_codex_tool_session_reply(
     prompt: String,
     running_requests_id_to_codex_uuid: Arc<Mutex<HashMap<RequestId, ConversationId>>>,
     conversation_id: ConversationId,
+    session_resources: Arc<SessionResources>,
 ) {
     running_requests_id_to_codex_uuid
         .lock()
@@ -142,18 +150,22 @@ pub async fn run_codex_tool_session_reply(
 
     run_codex_tool_session_inner(
         conversation,
+        conversation_id,
         outgoing,
         request_id,
         running_requests_id_to_codex_uuid,
+        session_resources,
     )
     .await;
 }
 
 async fn run_codex_tool_session_inner(
     codex: Arc<CodexConversation>,
+    conversation_id: ConversationId,
     outgoing: Arc<OutgoingMessageSender>,
     request_id: RequestId,
     running_requests_id_to_codex_uuid: Arc<Mutex<HashMap<RequestId, ConversationId>>>,
+    session_resources: Arc<SessionResources>,
 ) {
     let request_id_str = match &request_id {
         RequestId::String(s) => s.clone(),
@@ -220,6 +232,38 @@ async fn run_codex_tool_session_inner(
                         .await;
                         continue;
                     }
+                    EventMsg::McpSamplingRequest(_) => {
+                        // There is nobody to ask: refuse rather than leave the
+                        // requesting server waiting.
+                        if let Err(err) = codex
+                            .submit(Op::McpSamplingApproval {
+                                id: event.id.clone(),
+                                decision: ReviewDecision::Denied,
+                            })
+                            .await
+                        {
+                            tracing::error!("failed to deny MCP sampling request: {err}");
+                        }
+                        continue;
+                    }
+                    EventMsg::ElicitationRequest(_) => {
+                        if let Err(err) = codex
+                            .submit(Op::ResolveElicitation {
+                                id: event.id.clone(),
+                                action: ElicitationAction::Cancel,
+                                content: None,
+                            })
+                            .await
+                        {
+                            tracing::error!("failed to cancel MCP elicitation request: {err}");
+                        }
+                        continue;
+                    }
+                    EventMsg::TurnDiff(TurnDiffEvent { unified_diff }) => {
+                        session_resources
+                            .record_turn_diff(conversation_id, unified_diff, &outgoing)
+                            .await;
+                    }
                     EventMsg::TaskComplete(TaskCompleteEvent { last_agent_message }) => {
                         let text = match last_agent_message {
                             Some(msg) => msg,
@@ -263,15 +307,17 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::McpToolCallBegin(_)
                     | EventMsg::McpToolCallEnd(_)
                     | EventMsg::McpListToolsResponse(_)
+                    | EventMsg::McpListResourcesResponse(_)
                     | EventMsg::ListCustomPromptsResponse(_)
+                    | EventMsg::GetMcpPromptResponse(_)
                     | EventMsg::ExecCommandBegin(_)
                     | EventMsg::ExecCommandOutputDelta(_)
                     | EventMsg::ExecCommandEnd(_)
+                    | EventMsg::NetworkAccessBlocked(_)
                     | EventMsg::BackgroundEvent(_)
                     | EventMsg::StreamError(_)
                     | EventMsg::PatchApplyBegin(_)
                     | EventMsg::PatchApplyEnd(_)
-                    | EventMsg::TurnDiff(_)
                     | EventMsg::WebSearchBegin(_)
                     | EventMsg::WebSearchEnd(_)
                     | EventMsg::GetHistoryEntryResponse(_)
@@ -281,6 +327,9 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::UserMessage(_)
                     | EventMsg::ShutdownComplete
                     | EventMsg::ViewImageToolCall(_)
+                    | EventMsg::ExecApprovalRuleApplied(_)
+                    | EventMsg::AgentRunBegin(_)
+                    | EventMsg::AgentRunEnd(_)
                     | EventMsg::EnteredReviewMode(_)
                     | EventMsg::ExitedReviewMode(_) => {
                         // For now, we do not do anything extra for these
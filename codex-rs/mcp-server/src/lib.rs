@@ -27,6 +27,8 @@ mod exec_approval;
 pub(crate) mod message_processor;
 mod outgoing_message;
 mod patch_approval;
+mod prompts;
+mod resources;
 
 use crate::message_processor::MessageProcessor;
 use crate::outgoing_message::OutgoingMessage;
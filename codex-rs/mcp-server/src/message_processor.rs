@@ -7,6 +7,8 @@ use crate::codex_tool_config::create_tool_for_codex_tool_call_param;
 use crate::codex_tool_config::create_tool_for_codex_tool_call_reply_param;
 use crate::error_code::INVALID_REQUEST_ERROR_CODE;
 use crate::outgoing_message::OutgoingMessageSender;
+use crate::prompts::PromptCatalog;
+use crate::resources::SessionResources;
 use codex_protocol::ConversationId;
 use codex_protocol::protocol::SessionSource;
 
@@ -28,6 +30,8 @@ use mcp_types::JSONRPCResponse;
 use mcp_types::ListToolsResult;
 use mcp_types::ModelContextProtocolRequest;
 use mcp_types::RequestId;
+use mcp_types::ServerCapabilitiesPrompts;
+use mcp_types::ServerCapabilitiesResources;
 use mcp_types::ServerCapabilitiesTools;
 use mcp_types::ServerNotification;
 use mcp_types::TextContent;
@@ -42,6 +46,8 @@ pub(crate) struct MessageProcessor {
     codex_linux_sandbox_exe: Option<PathBuf>,
     conversation_manager: Arc<ConversationManager>,
     running_requests_id_to_codex_uuid: Arc<Mutex<HashMap<RequestId, ConversationId>>>,
+    session_resources: Arc<SessionResources>,
+    prompts: PromptCatalog,
 }
 
 impl MessageProcessor {
@@ -62,6 +68,8 @@ impl MessageProcessor {
             codex_linux_sandbox_exe,
             conversation_manager,
             running_requests_id_to_codex_uuid: Arc::new(Mutex::new(HashMap::new())),
+            session_resources: Arc::new(SessionResources::new(config.codex_home.clone())),
+            prompts: PromptCatalog::new(&config),
         }
     }
 
@@ -86,25 +94,26 @@ impl MessageProcessor {
                 self.handle_ping(request_id, params).await;
             }
             McpClientRequest::ListResourcesRequest(params) => {
-                self.handle_list_resources(params);
+                self.handle_list_resources(request_id, params).await;
             }
             McpClientRequest::ListResourceTemplatesRequest(params) => {
-                self.handle_list_resource_templates(params);
+                self.handle_list_resource_templates(request_id, params)
+                    .await;
             }
             McpClientRequest::ReadResourceRequest(params) => {
-                self.handle_read_resource(params);
+                self.handle_read_resource(request_id, params).await;
             }
             McpClientRequest::SubscribeRequest(params) => {
-                self.handle_subscribe(params);
+                self.handle_subscribe(request_id, params).await;
             }
             McpClientRequest::UnsubscribeRequest(params) => {
-                self.handle_unsubscribe(params);
+                self.handle_unsubscribe(request_id, params).await;
             }
             McpClientRequest::ListPromptsRequest(params) => {
-                self.handle_list_prompts(params);
+                self.handle_list_prompts(request_id, params).await;
             }
             McpClientRequest::GetPromptRequest(params) => {
-                self.handle_get_prompt(params);
+                self.handle_get_prompt(request_id, params).await;
             }
             McpClientRequest::ListToolsRequest(params) => {
                 self.handle_list_tools(request_id, params).await;
@@ -204,8 +213,11 @@ impl MessageProcessor {
                 completions: None,
                 experimental: None,
                 logging: None,
-                prompts: None,
-                resources: None,
+                prompts: Some(ServerCapabilitiesPrompts { list_changed: None }),
+                resources: Some(ServerCapabilitiesResources {
+                    list_changed: Some(true),
+                    subscribe: Some(true),
+                }),
                 tools: Some(ServerCapabilitiesTools {
                     list_changed: Some(true),
                 }),
@@ -242,54 +254,99 @@ impl MessageProcessor {
             .await;
     }
 
-    fn handle_list_resources(
+    async fn handle_list_resources(
         &self,
+        id: RequestId,
         params: <mcp_types::ListResourcesRequest as mcp_types::ModelContextProtocolRequest>::Params,
     ) {
         tracing::info!("resources/list -> params: {:?}", params);
+        let cursor = params.and_then(|p| p.cursor);
+        match self.session_resources.list(cursor).await {
+            Ok(result) => {
+                self.send_response::<mcp_types::ListResourcesRequest>(id, result)
+                    .await;
+            }
+            Err(error) => self.outgoing.send_error(id, error).await,
+        }
     }
 
-    fn handle_list_resource_templates(
+    async fn handle_list_resource_templates(
         &self,
+        id: RequestId,
         params:
             <mcp_types::ListResourceTemplatesRequest as mcp_types::ModelContextProtocolRequest>::Params,
     ) {
         tracing::info!("resources/templates/list -> params: {:?}", params);
+        let result = self.session_resources.templates();
+        self.send_response::<mcp_types::ListResourceTemplatesRequest>(id, result)
+            .await;
     }
 
-    fn handle_read_resource(
+    async fn handle_read_resource(
         &self,
+        id: RequestId,
         params: <mcp_types::ReadResourceRequest as mcp_types::ModelContextProtocolRequest>::Params,
     ) {
         tracing::info!("resources/read -> params: {:?}", params);
+        match self.session_resources.read(params.uri).await {
+            Ok(result) => {
+                self.send_response::<mcp_types::ReadResourceRequest>(id, result)
+                    .await;
+            }
+            Err(error) => self.outgoing.send_error(id, error).await,
+        }
     }
 
-    fn handle_subscribe(
+    async fn handle_subscribe(
         &self,
+        id: RequestId,
         params: <mcp_types::SubscribeRequest as mcp_types::ModelContextProtocolRequest>::Params,
     ) {
         tracing::info!("resources/subscribe -> params: {:?}", params);
+        match self.session_resources.subscribe(params.uri).await {
+            Ok(()) => {
+                self.send_response::<mcp_types::SubscribeRequest>(id, json!({}))
+                    .await;
+            }
+            Err(error) => self.outgoing.send_error(id, error).await,
+        }
     }
 
-    fn handle_unsubscribe(
+    async fn handle_unsubscribe(
         &self,
+        id: RequestId,
         params: <mcp_types::UnsubscribeRequest as mcp_types::ModelContextProtocolRequest>::Params,
     ) {
         tracing::info!("resources/unsubscribe -> params: {:?}", params);
+        self.session_resources.unsubscribe(&params.uri).await;
+        self.send_response::<mcp_types::UnsubscribeRequest>(id, json!({}))
+            .await;
     }
 
-    fn handle_list_prompts(
+    async fn handle_list_prompts(
         &self,
+        id: RequestId,
         params: <mcp_types::ListPromptsRequest as mcp_types::ModelContextProtocolRequest>::Params,
     ) {
         tracing::info!("prompts/list -> params: {:?}", params);
+        let result = self.prompts.list().await;
+        self.send_response::<mcp_types::ListPromptsRequest>(id, result)
+            .await;
     }
 
-    fn handle_get_prompt(
+    async fn handle_get_prompt(
         &self,
+        id: RequestId,
         params: <mcp_types::GetPromptRequest as mcp_types::ModelContextProtocolRequest>::Params,
     ) {
         tracing::info!("prompts/get -> params: {:?}", params);
+        match self.prompts.get(&params.name, params.arguments).await {
+            Ok(result) => {
+                self.send_response::<mcp_types::GetPromptRequest>(id, result)
+                    .await;
+            }
+            Err(error) => self.outgoing.send_error(id, error).await,
+        }
     }
 
     async fn handle_list_tools(
@@ -401,6 +458,7 @@ impl MessageProcessor {
         let outgoing = self.outgoing.clone();
         let conversation_manager = self.conversation_manager.clone();
         let running_requests_id_to_codex_uuid = self.running_requests_id_to_codex_uuid.clone();
+        let session_resources = self.session_resources.clone();
 
         // Spawn an async task to handle the Codex session so that we do not
         // block the synchronous message-processing loop.
@@ -413,6 +471,7 @@ impl MessageProcessor {
                 outgoing,
                 conversation_manager,
                 running_requests_id_to_codex_uuid,
+                session_resources,
             )
             .await;
         });
@@ -488,6 +547,7 @@ impl MessageProcessor {
         // Clone outgoing to move into async task.
         let outgoing = self.outgoing.clone();
         let running_requests_id_to_codex_uuid = self.running_requests_id_to_codex_uuid.clone();
+        let session_resources = self.session_resources.clone();
 
         let codex = match self
             .conversation_manager
@@ -525,6 +585,7 @@ impl MessageProcessor {
                     prompt,
                     running_requests_id_to_codex_uuid,
                     conversation_id,
+                    session_resources,
                 )
                 .await;
             }
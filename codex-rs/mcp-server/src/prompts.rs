@@ -0,0 +1,489 @@
+//! User commands and saved custom prompts exposed as MCP prompts.
+//!
+//! Commands from `$CODEX_HOME/commands` (when the experimental command system
+//! is enabled) keep their names and declare the arguments from their
+//! frontmatter. Custom prompts from `$CODEX_HOME/prompts` are listed as
+//! `prompts:{name}`, matching how they are invoked in the TUI.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_core::commands::CommandExecutor;
+use codex_core::commands::CommandInvocation;
+use codex_core::commands::CommandRegistry;
+use codex_core::commands::ExecutionContext;
+use codex_core::commands::parser::ArgDefinition;
+use codex_core::commands::parser::ArgType;
+use codex_core::commands::user::loader::UserCommand;
+use codex_core::config::Config;
+use codex_core::custom_prompts::discover_prompts_in;
+use codex_protocol::custom_prompts::CustomPrompt;
+use codex_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
+use mcp_types::ContentBlock;
+use mcp_types::GetPromptResult;
+use mcp_types::JSONRPCErrorError;
+use mcp_types::ListPromptsResult;
+use mcp_types::Prompt;
+use mcp_types::PromptArgument;
+use mcp_types::PromptMessage;
+use mcp_types::Role;
+use mcp_types::TextContent;
+
+use crate::codex_tool_runner::INVALID_PARAMS_ERROR_CODE;
+
+/// Argument that fills `$1..$9` and `$ARGUMENTS` in a custom prompt.
+const POSITIONAL_ARGUMENT: &str = "ARGUMENTS";
+
+fn invalid_params(message: String) -> JSONRPCErrorError {
+    JSONRPCErrorError {
+        code: INVALID_PARAMS_ERROR_CODE,
+        message,
+        data: None,
+    }
+}
+
+pub(crate) struct PromptCatalog {
+    codex_home: PathBuf,
+    cwd: PathBuf,
+    commands_enabled: bool,
+}
+
+impl PromptCatalog {
+    pub(crate) fn new(config: &Config) -> Self {
+        Self {
+            codex_home: config.codex_home.clone(),
+            cwd: config.cwd.clone(),
+            commands_enabled: config.experimental_command_system_enabled,
+        }
+    }
+
+    pub(crate) async fn list(&self) -> ListPromptsResult {
+        let mut prompts = Vec::new();
+        if let Some(registry) = self.command_registry().await {
+            let mut commands = registry.list().await;
+            commands.sort_by(|a, b| a.name.cmp(&b.name));
+            for info in commands {
+                let Some(command) = registry.get(&info.name).await else {
+                    continue;
+                };
+                let Some(user_cmd) = command.as_any().downcast_ref::<UserCommand>() else {
+                    continue;
+                };
+                // Agent commands run a sub-agent instead of producing a prompt.
+                if user_cmd.metadata.agent {
+                    continue;
+                }
+                prompts.push(Prompt {
+                    arguments: Some(
+                        user_cmd
+                            .metadata
+                            .args
+                            .iter()
+                            .map(command_argument)
+                            .collect(),
+                    ),
+                    description: Some(info.description),
+                    name: info.name,
+                    title: None,
+                });
+            }
+        }
+
+        for prompt in self.custom_prompts().await {
+            prompts.push(Prompt {
+                arguments: Some(custom_prompt_arguments(&prompt)),
+                description: prompt.description,
+                name: format!("{PROMPTS_CMD_PREFIX}:{}", prompt.name),
+                title: None,
+            });
+        }
+
+        ListPromptsResult {
+            next_cursor: None,
+            prompts,
+        }
+    }
+
+    pub(crate) async fn get(
+        &self,
+        name: &str,
+        arguments: Option<serde_json::Value>,
+    ) -> Result<GetPromptResult, JSONRPCErrorError> {
+        let arguments = string_arguments(arguments)?;
+        let (description, text) = match name.strip_prefix(&format!("{PROMPTS_CMD_PREFIX}:")) {
+            Some(prompt_name) => {
+                let prompt = self
+                    .custom_prompts()
+                    .await
+                    .into_iter()
+                    .find(|prompt| prompt.name == prompt_name)
+                    .ok_or_else(|| invalid_params(format!("unknown prompt: {name}")))?;
+                let text = expand_custom_prompt(&prompt, &arguments)
+                    .map_err(|e| invalid_params(format!("{name}: {e}")))?;
+                (prompt.description, text)
+            }
+            None => self.expand_command(name, arguments).await?,
+        };
+
+        Ok(GetPromptResult {
+            description,
+            messages: vec![PromptMessage {
+                content: ContentBlock::TextContent(TextContent {
+                    r#type: "text".to_string(),
+                    text,
+                    annotations: None,
+                }),
+                role: Role::User,
+            }],
+        })
+    }
+
+    async fn expand_command(
+        &self,
+        name: &str,
+        arguments: HashMap<String, String>,
+    ) -> Result<(Option<String>, String), JSONRPCErrorError> {
+        let unknown = || invalid_params(format!("unknown prompt: {name}"));
+        let registry = self.command_registry().await.ok_or_else(unknown)?;
+        let command = registry.get(name).await.ok_or_else(unknown)?;
+        let user_cmd = command
+            .as_any()
+            .downcast_ref::<UserCommand>()
+            .filter(|user_cmd| !user_cmd.metadata.agent)
+            .ok_or_else(unknown)?;
+
+        for arg in &user_cmd.metadata.args {
+            if let Some(value) = arguments.get(&arg.name) {
+                check_argument_type(arg, value)
+                    .map_err(|e| invalid_params(format!("{name}: {e}")))?;
+            }
+        }
+
+        let description = Some(user_cmd.metadata.description.clone());
+        let invocation = CommandInvocation {
+            command_name: name.to_string(),
+            args: arguments,
+            raw_args: Vec::new(),
+        };
+        let text = CommandExecutor::new(Arc::new(registry))
+            .execute(invocation, &ExecutionContext::new(self.cwd.clone()))
+            .await
+            .map_err(|e| invalid_params(format!("{e:#}")))?;
+        Ok((description, text))
+    }
+
+    async fn command_registry(&self) -> Option<CommandRegistry> {
+        if !self.commands_enabled {
+            return None;
+        }
+        // Load on every request so edits to the commands directory show up
+        // without restarting the server.
+        match CommandRegistry::new(self.codex_home.join("commands")).await {
+            Ok(registry) => Some(registry),
+            Err(e) => {
+                tracing::warn!("Failed to load commands: {e:#}");
+                None
+            }
+        }
+    }
+
+    async fn custom_prompts(&self) -> Vec<CustomPrompt> {
+        discover_prompts_in(&self.codex_home.join("prompts")).await
+    }
+}
+
+/// MCP prompt arguments are strings; accept other JSON scalars by rendering
+/// them, since hosts commonly send numbers and booleans for typed arguments.
+fn string_arguments(
+    arguments: Option<serde_json::Value>,
+) -> Result<HashMap<String, String>, JSONRPCErrorError> {
+    match arguments {
+        None | Some(serde_json::Value::Null) => Ok(HashMap::new()),
+        Some(serde_json::Value::Object(map)) => Ok(map
+            .into_iter()
+            .map(|(key, value)| match value {
+                serde_json::Value::String(s) => (key, s),
+                other => (key, other.to_string()),
+            })
+            .collect()),
+        Some(other) => Err(invalid_params(format!(
+            "prompt arguments must be an object, got {other}"
+        ))),
+    }
+}
+
+fn arg_type_name(arg_type: &ArgType) -> &'static str {
+    match arg_type {
+        ArgType::String => "string",
+        ArgType::Number => "number",
+        ArgType::Boolean => "boolean",
+        ArgType::File => "file",
+    }
+}
+
+/// MCP has no argument types, so the declared type is carried in the
+/// description.
+fn command_argument(arg: &ArgDefinition) -> PromptArgument {
+    let type_name = arg_type_name(&arg.arg_type);
+    let description = if arg.description.is_empty() {
+        format!("({type_name})")
+    } else {
+        format!("{} ({type_name})", arg.description)
+    };
+    PromptArgument {
+        description: Some(description),
+        name: arg.name.clone(),
+        required: Some(arg.required),
+        title: None,
+    }
+}
+
+fn check_argument_type(arg: &ArgDefinition, value: &str) -> Result<(), String> {
+    let valid = match arg.arg_type {
+        ArgType::Number => value.parse::<f64>().is_ok(),
+        ArgType::Boolean => matches!(value, "true" | "false"),
+        ArgType::String | ArgType::File => true,
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "argument '{}' must be a {}, got '{value}'",
+            arg.name,
+            arg_type_name(&arg.arg_type)
+        ))
+    }
+}
+
+/// A `$` placeholder recognised in custom prompt content.
+#[derive(Debug, PartialEq)]
+enum Placeholder<'a> {
+    /// `$$`, kept verbatim.
+    Escaped,
+    /// `$1..$9`, zero-based.
+    Positional(usize),
+    /// `$ARGUMENTS`, all positional values joined by spaces.
+    All,
+    /// `$NAME`.
+    Named(&'a str),
+}
+
+/// Parses the placeholder starting right after a `$`, returning it with the
+/// number of bytes it spans.
+fn parse_placeholder(after: &str) -> Option<(Placeholder<'_>, usize)> {
+    let bytes = after.as_bytes();
+    match bytes.first()? {
+        b'$' => Some((Placeholder::Escaped, 1)),
+        digit @ b'1'..=b'9' => Some((Placeholder::Positional(usize::from(digit - b'1')), 1)),
+        b'A'..=b'Z' => {
+            let len = bytes
+                .iter()
+                .take_while(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || **b == b'_')
+                .count();
+            let name = &after[..len];
+            if name == POSITIONAL_ARGUMENT {
+                Some((Placeholder::All, len))
+            } else {
+                Some((Placeholder::Named(name), len))
+            }
+        }
+        _ => None,
+    }
+}
+
+fn placeholders(content: &str) -> Vec<Placeholder<'_>> {
+    let mut found = Vec::new();
+    let mut rest = content;
+    while let Some(idx) = rest.find('$') {
+        let after = &rest[idx + 1..];
+        match parse_placeholder(after) {
+            Some((placeholder, len)) => {
+                found.push(placeholder);
+                rest = &after[len..];
+            }
+            None => rest = after,
+        }
+    }
+    found
+}
+
+/// Named placeholders become required arguments; positional ones are filled
+/// from a single optional `ARGUMENTS` value split with shell quoting rules.
+fn custom_prompt_arguments(prompt: &CustomPrompt) -> Vec<PromptArgument> {
+    let mut arguments: Vec<PromptArgument> = Vec::new();
+    let mut positional = false;
+    for placeholder in placeholders(&prompt.content) {
+        match placeholder {
+            Placeholder::Named(name) => {
+                if arguments.iter().all(|arg| arg.name != name) {
+                    arguments.push(PromptArgument {
+                        description: None,
+                        name: name.to_string(),
+                        required: Some(true),
+                        title: None,
+                    });
+                }
+            }
+            Placeholder::Positional(_) | Placeholder::All => positional = true,
+            Placeholder::Escaped => {}
+        }
+    }
+    if positional {
+        arguments.push(PromptArgument {
+            description: Some(
+                prompt
+                    .argument_hint
+                    .clone()
+                    .unwrap_or_else(|| "Space-separated values for $1..$9".to_string()),
+            ),
+            name: POSITIONAL_ARGUMENT.to_string(),
+            required: Some(false),
+            title: None,
+        });
+    }
+    arguments
+}
+
+fn expand_custom_prompt(
+    prompt: &CustomPrompt,
+    arguments: &HashMap<String, String>,
+) -> Result<String, String> {
+    let mut missing: Vec<&str> = Vec::new();
+    for placeholder in placeholders(&prompt.content) {
+        if let Placeholder::Named(name) = placeholder
+            && !arguments.contains_key(name)
+            && !missing.contains(&name)
+        {
+            missing.push(name);
+        }
+    }
+    if !missing.is_empty() {
+        return Err(format!(
+            "missing required arguments: {}",
+            missing.join(", ")
+        ));
+    }
+
+    let positional: Vec<String> = arguments
+        .get(POSITIONAL_ARGUMENT)
+        .map(|values| shlex::Shlex::new(values).collect())
+        .unwrap_or_default();
+
+    let content = prompt.content.as_str();
+    let mut out = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(idx) = rest.find('$') {
+        out.push_str(&rest[..idx]);
+        let after = &rest[idx + 1..];
+        let Some((placeholder, len)) = parse_placeholder(after) else {
+            out.push('$');
+            rest = after;
+            continue;
+        };
+        match placeholder {
+            Placeholder::Escaped => out.push_str("$$"),
+            Placeholder::Positional(i) => {
+                if let Some(value) = positional.get(i) {
+                    out.push_str(value);
+                }
+            }
+            Placeholder::All => out.push_str(&positional.join(" ")),
+            Placeholder::Named(name) => {
+                if let Some(value) = arguments.get(name) {
+                    out.push_str(value);
+                }
+            }
+        }
+        rest = &after[len..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn prompt(content: &str) -> CustomPrompt {
+        CustomPrompt {
+            name: "review".to_string(),
+            path: PathBuf::from("review.md"),
+            content: content.to_string(),
+            description: None,
+            argument_hint: None,
+        }
+    }
+
+    fn args(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn custom_prompt_declares_named_and_positional_arguments() {
+        let arguments = custom_prompt_arguments(&prompt("Review $FILE for $USER: $1 ($FILE) $$5"));
+        let names: Vec<(&str, Option<bool>)> = arguments
+            .iter()
+            .map(|arg| (arg.name.as_str(), arg.required))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("FILE", Some(true)),
+                ("USER", Some(true)),
+                ("ARGUMENTS", Some(false)),
+            ]
+        );
+    }
+
+    #[test]
+    fn custom_prompt_expands_named_and_positional_values() {
+        let expanded = expand_custom_prompt(
+            &prompt("Review $FILE: $1 then [$ARGUMENTS], costs $$ and $lower"),
+            &args(&[
+                ("FILE", "src/main.rs"),
+                ("ARGUMENTS", "first \"second arg\""),
+            ]),
+        );
+        assert_eq!(
+            expanded,
+            Ok(
+                "Review src/main.rs: first then [first second arg], costs $$ and $lower"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn custom_prompt_reports_missing_named_arguments() {
+        let expanded = expand_custom_prompt(&prompt("$FILE and $USER and $FILE"), &args(&[]));
+        assert_eq!(
+            expanded,
+            Err("missing required arguments: FILE, USER".to_string())
+        );
+    }
+
+    #[test]
+    fn typed_command_arguments_are_checked() {
+        let arg = ArgDefinition {
+            name: "line".to_string(),
+            arg_type: ArgType::Number,
+            required: true,
+            description: "Line to start at".to_string(),
+            default: None,
+        };
+        assert_eq!(
+            command_argument(&arg).description.as_deref(),
+            Some("Line to start at (number)")
+        );
+        assert_eq!(check_argument_type(&arg, "42"), Ok(()));
+        assert_eq!(
+            check_argument_type(&arg, "forty-two"),
+            Err("argument 'line' must be a number, got 'forty-two'".to_string())
+        );
+    }
+}
@@ -0,0 +1,297 @@
+//! Recorded sessions and live turn diffs exposed as MCP resources.
+//!
+//! Every rollout under `$CODEX_HOME/sessions` is listed as
+//! `codex://sessions/{id}`. Sessions driven through the `codex` tool also
+//! expose `codex://sessions/{id}/diff`, the aggregated diff of their latest
+//! turn; clients subscribed to that URI are sent
+//! `notifications/resources/updated` whenever it changes.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use codex_core::Cursor as RolloutCursor;
+use codex_core::RolloutRecorder;
+use codex_core::SessionMeta;
+use codex_core::find_conversation_path_by_id_str;
+use codex_protocol::ConversationId;
+use mcp_types::JSONRPCErrorError;
+use mcp_types::ListResourceTemplatesResult;
+use mcp_types::ListResourcesResult;
+use mcp_types::ModelContextProtocolNotification;
+use mcp_types::ReadResourceResult;
+use mcp_types::ReadResourceResultContents;
+use mcp_types::Resource;
+use mcp_types::ResourceListChangedNotification;
+use mcp_types::ResourceTemplate;
+use mcp_types::ResourceUpdatedNotification;
+use mcp_types::ResourceUpdatedNotificationParams;
+use mcp_types::TextResourceContents;
+use tokio::sync::Mutex;
+
+use crate::codex_tool_runner::INVALID_PARAMS_ERROR_CODE;
+use crate::error_code::INTERNAL_ERROR_CODE;
+use crate::outgoing_message::OutgoingMessageSender;
+use crate::outgoing_message::OutgoingNotification;
+
+const SESSION_URI_PREFIX: &str = "codex://sessions/";
+const DIFF_URI_SUFFIX: &str = "/diff";
+const SESSIONS_PAGE_SIZE: usize = 50;
+const ROLLOUT_MIME_TYPE: &str = "application/jsonl";
+const DIFF_MIME_TYPE: &str = "text/x-diff";
+
+/// A URI this server knows how to serve.
+#[derive(Debug, PartialEq)]
+enum SessionUri {
+    Rollout(ConversationId),
+    Diff(ConversationId),
+}
+
+impl SessionUri {
+    fn parse(uri: &str) -> Option<Self> {
+        let rest = uri.strip_prefix(SESSION_URI_PREFIX)?;
+        match rest.strip_suffix(DIFF_URI_SUFFIX) {
+            Some(id) => ConversationId::from_string(id).ok().map(Self::Diff),
+            None => ConversationId::from_string(rest).ok().map(Self::Rollout),
+        }
+    }
+}
+
+fn rollout_uri(id: ConversationId) -> String {
+    format!("{SESSION_URI_PREFIX}{id}")
+}
+
+fn diff_uri(id: ConversationId) -> String {
+    format!("{SESSION_URI_PREFIX}{id}{DIFF_URI_SUFFIX}")
+}
+
+fn invalid_params(message: String) -> JSONRPCErrorError {
+    JSONRPCErrorError {
+        code: INVALID_PARAMS_ERROR_CODE,
+        message,
+        data: None,
+    }
+}
+
+fn internal_error(message: String) -> JSONRPCErrorError {
+    JSONRPCErrorError {
+        code: INTERNAL_ERROR_CODE,
+        message,
+        data: None,
+    }
+}
+
+pub(crate) struct SessionResources {
+    codex_home: PathBuf,
+    /// Latest aggregated diff per running session, keyed by conversation.
+    turn_diffs: Mutex<HashMap<ConversationId, String>>,
+    /// URIs the client asked to be notified about.
+    subscriptions: Mutex<HashSet<String>>,
+}
+
+impl SessionResources {
+    pub(crate) fn new(codex_home: PathBuf) -> Self {
+        Self {
+            codex_home,
+            turn_diffs: Mutex::new(HashMap::new()),
+            subscriptions: Mutex::new(HashSet::new()),
+        }
+    }
+
+    pub(crate) async fn list(
+        &self,
+        cursor: Option<String>,
+    ) -> Result<ListResourcesResult, JSONRPCErrorError> {
+        // Diffs of running sessions are few; list them once, ahead of the
+        // first page of recorded sessions.
+        let mut resources = Vec::new();
+        if cursor.is_none() {
+            let mut ids: Vec<ConversationId> =
+                self.turn_diffs.lock().await.keys().copied().collect();
+            ids.sort_by_key(ToString::to_string);
+            resources.extend(ids.into_iter().map(diff_resource));
+        }
+
+        let cursor = cursor.and_then(|s| {
+            serde_json::from_value::<RolloutCursor>(serde_json::Value::String(s)).ok()
+        });
+        let page = RolloutRecorder::list_conversations(
+            &self.codex_home,
+            SESSIONS_PAGE_SIZE,
+            cursor.as_ref(),
+            &[],
+        )
+        .await
+        .map_err(|e| internal_error(format!("failed to list recorded sessions: {e}")))?;
+        resources.extend(
+            page.items
+                .iter()
+                .filter_map(|item| rollout_resource(item.head.first()?)),
+        );
+
+        let next_cursor = match page.next_cursor.map(serde_json::to_value) {
+            Some(Ok(serde_json::Value::String(s))) => Some(s),
+            _ => None,
+        };
+        Ok(ListResourcesResult {
+            next_cursor,
+            resources,
+        })
+    }
+
+    pub(crate) fn templates(&self) -> ListResourceTemplatesResult {
+        ListResourceTemplatesResult {
+            next_cursor: None,
+            resource_templates: vec![
+                ResourceTemplate {
+                    annotations: None,
+                    description: Some(
+                        "Recorded Codex session, one JSON event per line.".to_string(),
+                    ),
+                    mime_type: Some(ROLLOUT_MIME_TYPE.to_string()),
+                    name: "session".to_string(),
+                    title: Some("Codex session".to_string()),
+                    uri_template: format!("{SESSION_URI_PREFIX}{{id}}"),
+                },
+                ResourceTemplate {
+                    annotations: None,
+                    description: Some(
+                        "Changes made during the latest turn of a running session.".to_string(),
+                    ),
+                    mime_type: Some(DIFF_MIME_TYPE.to_string()),
+                    name: "session-diff".to_string(),
+                    title: Some("Codex session diff".to_string()),
+                    uri_template: format!("{SESSION_URI_PREFIX}{{id}}{DIFF_URI_SUFFIX}"),
+                },
+            ],
+        }
+    }
+
+    pub(crate) async fn read(&self, uri: String) -> Result<ReadResourceResult, JSONRPCErrorError> {
+        let (text, mime_type) = match SessionUri::parse(&uri) {
+            Some(SessionUri::Rollout(id)) => {
+                let path = find_conversation_path_by_id_str(&self.codex_home, &id.to_string())
+                    .await
+                    .map_err(|e| internal_error(format!("failed to locate session {id}: {e}")))?
+                    .ok_or_else(|| invalid_params(format!("no recorded session {id}")))?;
+                let text = tokio::fs::read_to_string(&path).await.map_err(|e| {
+                    internal_error(format!("failed to read {}: {e}", path.display()))
+                })?;
+                (text, ROLLOUT_MIME_TYPE)
+            }
+            Some(SessionUri::Diff(id)) => {
+                let text = self
+                    .turn_diffs
+                    .lock()
+                    .await
+                    .get(&id)
+                    .cloned()
+                    .ok_or_else(|| invalid_params(format!("no diff recorded for session {id}")))?;
+                (text, DIFF_MIME_TYPE)
+            }
+            None => return Err(invalid_params(format!("unknown resource: {uri}"))),
+        };
+        Ok(ReadResourceResult {
+            contents: vec![ReadResourceResultContents::TextResourceContents(
+                TextResourceContents {
+                    mime_type: Some(mime_type.to_string()),
+                    text,
+                    uri,
+                },
+            )],
+        })
+    }
+
+    /// Subscribing to a diff is allowed before the session has produced one,
+    /// so a client can subscribe right after the session is configured.
+    pub(crate) async fn subscribe(&self, uri: String) -> Result<(), JSONRPCErrorError> {
+        if SessionUri::parse(&uri).is_none() {
+            return Err(invalid_params(format!("unknown resource: {uri}")));
+        }
+        self.subscriptions.lock().await.insert(uri);
+        Ok(())
+    }
+
+    pub(crate) async fn unsubscribe(&self, uri: &str) {
+        self.subscriptions.lock().await.remove(uri);
+    }
+
+    /// Records the latest diff of a running session and notifies subscribers.
+    pub(crate) async fn record_turn_diff(
+        &self,
+        conversation_id: ConversationId,
+        unified_diff: String,
+        outgoing: &OutgoingMessageSender,
+    ) {
+        let is_new = self
+            .turn_diffs
+            .lock()
+            .await
+            .insert(conversation_id, unified_diff)
+            .is_none();
+        if is_new {
+            outgoing
+                .send_notification(OutgoingNotification {
+                    method: ResourceListChangedNotification::METHOD.to_string(),
+                    params: None,
+                })
+                .await;
+        }
+
+        let uri = diff_uri(conversation_id);
+        if !self.subscriptions.lock().await.contains(&uri) {
+            return;
+        }
+        let params = serde_json::to_value(ResourceUpdatedNotificationParams { uri }).ok();
+        outgoing
+            .send_notification(OutgoingNotification {
+                method: ResourceUpdatedNotification::METHOD.to_string(),
+                params,
+            })
+            .await;
+    }
+}
+
+fn rollout_resource(meta_line: &serde_json::Value) -> Option<Resource> {
+    let meta = serde_json::from_value::<SessionMeta>(meta_line.clone()).ok()?;
+    Some(Resource {
+        annotations: None,
+        description: Some(format!("Recorded in {}", meta.cwd.display())),
+        mime_type: Some(ROLLOUT_MIME_TYPE.to_string()),
+        name: meta.id.to_string(),
+        size: None,
+        title: Some(format!("Codex session {}", meta.timestamp)),
+        uri: rollout_uri(meta.id),
+    })
+}
+
+fn diff_resource(id: ConversationId) -> Resource {
+    Resource {
+        annotations: None,
+        description: Some("Changes made during the latest turn.".to_string()),
+        mime_type: Some(DIFF_MIME_TYPE.to_string()),
+        name: format!("{id}-diff"),
+        size: None,
+        title: Some(format!("Diff of session {id}")),
+        uri: diff_uri(id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_session_uris() {
+        let id = ConversationId::from_string("3f941c35-29b3-493b-b0a4-e25800d9aeb0")
+            .unwrap_or_else(|e| panic!("valid id: {e}"));
+        assert_eq!(
+            SessionUri::parse(&rollout_uri(id)),
+            Some(SessionUri::Rollout(id))
+        );
+        assert_eq!(SessionUri::parse(&diff_uri(id)), Some(SessionUri::Diff(id)));
+        assert_eq!(SessionUri::parse("codex://sessions/not-a-uuid"), None);
+        assert_eq!(SessionUri::parse("file:///tmp/rollout.jsonl"), None);
+    }
+}
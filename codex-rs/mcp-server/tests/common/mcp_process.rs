@@ -15,16 +15,20 @@ use codex_mcp_server::CodexToolCallParam;
 
 use mcp_types::CallToolRequestParams;
 use mcp_types::ClientCapabilities;
+use mcp_types::GetPromptRequestParams;
 use mcp_types::Implementation;
 use mcp_types::InitializeRequestParams;
 use mcp_types::JSONRPC_VERSION;
+use mcp_types::JSONRPCError;
 use mcp_types::JSONRPCMessage;
 use mcp_types::JSONRPCNotification;
 use mcp_types::JSONRPCRequest;
 use mcp_types::JSONRPCResponse;
 use mcp_types::ModelContextProtocolNotification;
 use mcp_types::ModelContextProtocolRequest;
+use mcp_types::ReadResourceRequestParams;
 use mcp_types::RequestId;
+use mcp_types::SubscribeRequestParams;
 use pretty_assertions::assert_eq;
 use serde_json::json;
 use std::process::Command as StdCommand;
@@ -156,6 +160,11 @@ impl McpProcess {
                 id: RequestId::Integer(request_id),
                 result: json!({
                     "capabilities": {
+                        "prompts": {},
+                        "resources": {
+                            "listChanged": true,
+                            "subscribe": true
+                        },
                         "tools": {
                             "listChanged": true
                         },
@@ -200,6 +209,59 @@ impl McpProcess {
         .await
     }
 
+    /// Send a `resources/list` JSON-RPC request.
+    pub async fn send_list_resources_request(&mut self) -> anyhow::Result<i64> {
+        self.send_request(mcp_types::ListResourcesRequest::METHOD, None)
+            .await
+    }
+
+    /// Send a `resources/read` JSON-RPC request.
+    pub async fn send_read_resource_request(&mut self, uri: &str) -> anyhow::Result<i64> {
+        let params = ReadResourceRequestParams {
+            uri: uri.to_string(),
+        };
+        self.send_request(
+            mcp_types::ReadResourceRequest::METHOD,
+            Some(serde_json::to_value(params)?),
+        )
+        .await
+    }
+
+    /// Send a `resources/subscribe` JSON-RPC request.
+    pub async fn send_subscribe_request(&mut self, uri: &str) -> anyhow::Result<i64> {
+        let params = SubscribeRequestParams {
+            uri: uri.to_string(),
+        };
+        self.send_request(
+            mcp_types::SubscribeRequest::METHOD,
+            Some(serde_json::to_value(params)?),
+        )
+        .await
+    }
+
+    /// Send a `prompts/list` JSON-RPC request.
+    pub async fn send_list_prompts_request(&mut self) -> anyhow::Result<i64> {
+        self.send_request(mcp_types::ListPromptsRequest::METHOD, None)
+            .await
+    }
+
+    /// Send a `prompts/get` JSON-RPC request.
+    pub async fn send_get_prompt_request(
+        &mut self,
+        name: &str,
+        arguments: serde_json::Value,
+    ) -> anyhow::Result<i64> {
+        let params = GetPromptRequestParams {
+            arguments: Some(arguments),
+            name: name.to_string(),
+        };
+        self.send_request(
+            mcp_types::GetPromptRequest::METHOD,
+            Some(serde_json::to_value(params)?),
+        )
+        .await
+    }
+
     async fn send_request(
         &mut self,
         method: &str,
@@ -239,7 +301,7 @@ impl McpProcess {
         Ok(())
     }
 
-    async fn read_jsonrpc_message(&mut self) -> anyhow::Result<JSONRPCMessage> {
+    pub async fn read_jsonrpc_message(&mut self) -> anyhow::Result<JSONRPCMessage> {
         let mut line = String::new();
         self.stdout.read_line(&mut line).await?;
         let message = serde_json::from_str::<JSONRPCMessage>(&line)?;
@@ -270,6 +332,60 @@ impl McpProcess {
         }
     }
 
+    pub async fn read_stream_until_error_message(
+        &mut self,
+        request_id: RequestId,
+    ) -> anyhow::Result<JSONRPCError> {
+        eprintln!("in read_stream_until_error_message({request_id:?})");
+
+        loop {
+            let message = self.read_jsonrpc_message().await?;
+            match message {
+                JSONRPCMessage::Notification(_) => {
+                    eprintln!("notification: {message:?}");
+                }
+                JSONRPCMessage::Request(_) => {
+                    anyhow::bail!("unexpected JSONRPCMessage::Request: {message:?}");
+                }
+                JSONRPCMessage::Response(_) => {
+                    anyhow::bail!("unexpected JSONRPCMessage::Response: {message:?}");
+                }
+                JSONRPCMessage::Error(err) => {
+                    if err.id == request_id {
+                        return Ok(err);
+                    }
+                }
+            }
+        }
+    }
+
+    pub async fn read_stream_until_notification_message(
+        &mut self,
+        method: &str,
+    ) -> anyhow::Result<JSONRPCNotification> {
+        eprintln!("in read_stream_until_notification_message({method})");
+
+        loop {
+            let message = self.read_jsonrpc_message().await?;
+            match message {
+                JSONRPCMessage::Notification(notification) => {
+                    if notification.method == method {
+                        return Ok(notification);
+                    }
+                }
+                JSONRPCMessage::Request(_) => {
+                    anyhow::bail!("unexpected JSONRPCMessage::Request: {message:?}");
+                }
+                JSONRPCMessage::Error(_) => {
+                    anyhow::bail!("unexpected JSONRPCMessage::Error: {message:?}");
+                }
+                JSONRPCMessage::Response(_) => {
+                    anyhow::bail!("unexpected JSONRPCMessage::Response: {message:?}");
+                }
+            }
+        }
+    }
+
     pub async fn read_stream_until_response_message(
         &mut self,
         request_id: RequestId,
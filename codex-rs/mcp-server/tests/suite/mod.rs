@@ -1 +1,3 @@
 mod codex_tool;
+mod prompts;
+mod resources;
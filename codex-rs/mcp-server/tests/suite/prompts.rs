@@ -0,0 +1,147 @@
+use std::path::Path;
+
+use mcp_test_support::McpProcess;
+use mcp_test_support::to_response;
+use mcp_types::ContentBlock;
+use mcp_types::GetPromptResult;
+use mcp_types::ListPromptsResult;
+use mcp_types::RequestId;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(20);
+
+const COMMAND: &str = r#"---
+name: explain
+description: Explain a region of code
+category: analysis
+args:
+  - name: file
+    type: file
+    required: true
+    description: File to explain
+  - name: line
+    type: number
+    description: Line to start at
+---
+Explain {{args.file}} from line {{args.line}}."#;
+
+#[tokio::test]
+async fn commands_and_custom_prompts_are_listed() -> anyhow::Result<()> {
+    let codex_home = prepare_codex_home()?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let list_id = mcp.send_list_prompts_request().await?;
+    let response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(list_id)),
+    )
+    .await??;
+    let ListPromptsResult { prompts, .. } = to_response(response)?;
+    assert_eq!(
+        serde_json::to_value(prompts)?,
+        json!([
+            {
+                "name": "explain",
+                "description": "Explain a region of code",
+                "arguments": [
+                    {"name": "file", "description": "File to explain (file)", "required": true},
+                    {"name": "line", "description": "Line to start at (number)", "required": false}
+                ]
+            },
+            {
+                "name": "prompts:greet",
+                "arguments": [
+                    {"name": "NAME", "required": true},
+                    {"name": "ARGUMENTS", "description": "Space-separated values for $1..$9", "required": false}
+                ]
+            }
+        ])
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn prompts_expand_with_arguments() -> anyhow::Result<()> {
+    let codex_home = prepare_codex_home()?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let command_id = mcp
+        .send_get_prompt_request("explain", json!({"file": "src/main.rs", "line": 42}))
+        .await?;
+    let response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(command_id)),
+    )
+    .await??;
+    let result: GetPromptResult = to_response(response)?;
+    assert_eq!(prompt_text(&result), "Explain src/main.rs from line 42.");
+
+    let custom_id = mcp
+        .send_get_prompt_request(
+            "prompts:greet",
+            json!({"NAME": "Ada", "ARGUMENTS": "\"the parser\""}),
+        )
+        .await?;
+    let response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(custom_id)),
+    )
+    .await??;
+    let result: GetPromptResult = to_response(response)?;
+    assert_eq!(prompt_text(&result), "Say hi to Ada about the parser.\n");
+
+    let mistyped_id = mcp
+        .send_get_prompt_request("explain", json!({"file": "src/main.rs", "line": "top"}))
+        .await?;
+    let error = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(mistyped_id)),
+    )
+    .await??;
+    assert_eq!(
+        error.error.message,
+        "explain: argument 'line' must be a number, got 'top'"
+    );
+
+    Ok(())
+}
+
+fn prompt_text(result: &GetPromptResult) -> &str {
+    match result.messages.as_slice() {
+        [message] => match &message.content {
+            ContentBlock::TextContent(text) => &text.text,
+            other => panic!("expected text content, got {other:?}"),
+        },
+        other => panic!("expected a single message, got {other:?}"),
+    }
+}
+
+fn prepare_codex_home() -> anyhow::Result<TempDir> {
+    let codex_home = TempDir::new()?;
+    write_file(
+        codex_home.path(),
+        "config.toml",
+        "experimental_command_system_enabled = true\n",
+    )?;
+    write_file(codex_home.path(), "commands/explain.md", COMMAND)?;
+    write_file(
+        codex_home.path(),
+        "prompts/greet.md",
+        "Say hi to $NAME about $1.\n",
+    )?;
+    Ok(codex_home)
+}
+
+fn write_file(root: &Path, relative: &str, contents: &str) -> std::io::Result<()> {
+    let path = root.join(relative);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, contents)
+}
@@ -0,0 +1,246 @@
+use std::path::Path;
+
+use codex_core::protocol::ReviewDecision;
+use codex_mcp_server::CodexToolCallParam;
+use codex_mcp_server::PatchApprovalResponse;
+use core_test_support::skip_if_no_network;
+use mcp_test_support::McpProcess;
+use mcp_test_support::create_apply_patch_sse_response;
+use mcp_test_support::create_final_assistant_message_sse_response;
+use mcp_test_support::create_mock_chat_completions_server;
+use mcp_test_support::to_response;
+use mcp_types::JSONRPCMessage;
+use mcp_types::ListResourcesResult;
+use mcp_types::ReadResourceResult;
+use mcp_types::ReadResourceResultContents;
+use mcp_types::RequestId;
+use mcp_types::TextResourceContents;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(20);
+
+const SESSION_ID: &str = "3f941c35-29b3-493b-b0a4-e25800d9aeb0";
+
+#[tokio::test]
+async fn recorded_sessions_are_listed_and_readable() -> anyhow::Result<()> {
+    let codex_home = TempDir::new()?;
+    let rollout = write_rollout(codex_home.path())?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let list_id = mcp.send_list_resources_request().await?;
+    let response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(list_id)),
+    )
+    .await??;
+    let ListResourcesResult { resources, .. } = to_response(response)?;
+    let uri = format!("codex://sessions/{SESSION_ID}");
+    assert_eq!(
+        resources.iter().map(|r| r.uri.as_str()).collect::<Vec<_>>(),
+        vec![uri.as_str()]
+    );
+    assert_eq!(
+        resources[0].description.as_deref(),
+        Some("Recorded in /repo")
+    );
+
+    let read_id = mcp.send_read_resource_request(&uri).await?;
+    let response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(read_id)),
+    )
+    .await??;
+    let ReadResourceResult { contents } = to_response(response)?;
+    assert_eq!(
+        contents,
+        vec![ReadResourceResultContents::TextResourceContents(
+            TextResourceContents {
+                mime_type: Some("application/jsonl".to_string()),
+                text: std::fs::read_to_string(rollout)?,
+                uri,
+            }
+        )]
+    );
+
+    let missing_id = mcp
+        .send_read_resource_request("codex://sessions/00000000-0000-0000-0000-000000000000")
+        .await?;
+    let error = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(missing_id)),
+    )
+    .await??;
+    assert_eq!(error.error.code, -32602);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn turn_diff_notifies_subscribers() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let cwd = TempDir::new()?;
+    let test_file = cwd.path().join("destination_file.txt");
+    std::fs::write(&test_file, "original content\n")?;
+    let patch_content = format!(
+        "*** Begin Patch\n*** Update File: {}\n-original content\n+modified content\n*** End Patch",
+        test_file.as_path().to_string_lossy()
+    );
+    let server = create_mock_chat_completions_server(vec![
+        create_apply_patch_sse_response(&patch_content, "call1234")?,
+        create_final_assistant_message_sse_response("Patch has been applied successfully!")?,
+    ])
+    .await;
+    let codex_home = TempDir::new()?;
+    write_config(codex_home.path(), &server.uri())?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let codex_request_id = mcp
+        .send_codex_tool_call(CodexToolCallParam {
+            cwd: Some(cwd.path().to_string_lossy().to_string()),
+            prompt: "please modify the test file".to_string(),
+            ..Default::default()
+        })
+        .await?;
+
+    // The first event of a session carries its conversation id.
+    let session_configured = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_notification_message("codex/event"),
+    )
+    .await??;
+    let session_id = session_configured
+        .params
+        .as_ref()
+        .and_then(|params| params["msg"]["session_id"].as_str())
+        .ok_or_else(|| anyhow::anyhow!("session_configured must carry a session id"))?
+        .to_string();
+    let diff_uri = format!("codex://sessions/{session_id}/diff");
+    let subscribe_id = mcp.send_subscribe_request(&diff_uri).await?;
+
+    // The patch waits for approval, so the subscription lands before any
+    // diff; its response may interleave with the approval request.
+    let mut subscribed = false;
+    let mut approval_request = None;
+    while !subscribed || approval_request.is_none() {
+        match timeout(DEFAULT_READ_TIMEOUT, mcp.read_jsonrpc_message()).await?? {
+            JSONRPCMessage::Response(response)
+                if response.id == RequestId::Integer(subscribe_id) =>
+            {
+                subscribed = true;
+            }
+            JSONRPCMessage::Request(request) => approval_request = Some(request),
+            _ => {}
+        }
+    }
+    let approval_request =
+        approval_request.ok_or_else(|| anyhow::anyhow!("approval request expected"))?;
+    mcp.send_response(
+        approval_request.id,
+        serde_json::to_value(PatchApprovalResponse {
+            decision: ReviewDecision::Approved,
+        })?,
+    )
+    .await?;
+
+    let updated = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_notification_message("notifications/resources/updated"),
+    )
+    .await??;
+    assert_eq!(updated.params, Some(json!({ "uri": diff_uri })));
+
+    timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(codex_request_id)),
+    )
+    .await??;
+
+    let read_id = mcp.send_read_resource_request(&diff_uri).await?;
+    let response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(read_id)),
+    )
+    .await??;
+    let ReadResourceResult { contents } = to_response(response)?;
+    let [ReadResourceResultContents::TextResourceContents(diff)] = contents.as_slice() else {
+        anyhow::bail!("expected a single text diff, got {contents:?}");
+    };
+    assert_eq!(diff.mime_type.as_deref(), Some("text/x-diff"));
+    assert!(
+        diff.text.contains("-original content\n+modified content"),
+        "unexpected diff: {}",
+        diff.text
+    );
+
+    Ok(())
+}
+
+fn write_rollout(codex_home: &Path) -> std::io::Result<std::path::PathBuf> {
+    let dir = codex_home.join("sessions/2025/01/02");
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("rollout-2025-01-02T12-00-00-{SESSION_ID}.jsonl"));
+    let timestamp = "2025-01-02T12:00:00Z";
+    let lines = [
+        json!({
+            "timestamp": timestamp,
+            "type": "session_meta",
+            "payload": {
+                "id": SESSION_ID,
+                "timestamp": timestamp,
+                "cwd": "/repo",
+                "originator": "codex",
+                "cli_version": "0.0.0",
+                "instructions": null
+            }
+        }),
+        json!({
+            "timestamp": timestamp,
+            "type": "response_item",
+            "payload": {
+                "type": "message",
+                "role": "user",
+                "content": [{"type": "input_text", "text": "hello"}]
+            }
+        }),
+        json!({
+            "timestamp": timestamp,
+            "type": "event_msg",
+            "payload": {
+                "type": "user_message",
+                "message": "hello",
+                "kind": "plain"
+            }
+        }),
+    ];
+    let contents: String = lines.iter().map(|line| format!("{line}\n")).collect();
+    std::fs::write(&path, contents)?;
+    Ok(path)
+}
+
+fn write_config(codex_home: &Path, server_uri: &str) -> std::io::Result<()> {
+    std::fs::write(
+        codex_home.join("config.toml"),
+        format!(
+            r#"
+model = "mock-model"
+approval_policy = "untrusted"
+sandbox_policy = "read-only"
+
+model_provider = "mock_provider"
+
+[model_providers.mock_provider]
+name = "Mock provider for test"
+base_url = "{server_uri}/v1"
+wire_api = "chat"
+request_max_retries = 0
+stream_max_retries = 0
+"#
+        ),
+    )
+}
@@ -0,0 +1,129 @@
+//! Structured results of agent runs.
+//!
+//! These mirror the results produced by Codex's built-in and user-defined
+//! agents so clients can render findings, analyses, and suggestions without
+//! parsing formatted text.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use serde::Serialize;
+use ts_rs::TS;
+
+/// Result of an agent run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AgentRunResult {
+    /// Code review findings.
+    CodeReview { findings: Vec<AgentFinding> },
+    /// Analysis results.
+    Analysis {
+        summary: String,
+        details: BTreeMap<String, String>,
+    },
+    /// Suggestions for improvements.
+    Suggestions { items: Vec<AgentSuggestion> },
+    /// Results from several agents, in the order they ran.
+    Combined { results: Vec<AgentRunResultEntry> },
+}
+
+impl AgentRunResult {
+    /// All findings in the result, including those of combined results.
+    pub fn findings(&self) -> Vec<&AgentFinding> {
+        match self {
+            AgentRunResult::CodeReview { findings } => findings.iter().collect(),
+            AgentRunResult::Combined { results } => results
+                .iter()
+                .flat_map(|entry| entry.result.findings())
+                .collect(),
+            AgentRunResult::Analysis { .. } | AgentRunResult::Suggestions { .. } => Vec::new(),
+        }
+    }
+}
+
+/// The result of one agent within a combined result.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+pub struct AgentRunResultEntry {
+    pub agent_id: String,
+    pub result: AgentRunResult,
+}
+
+/// A code review finding reported by an agent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+pub struct AgentFinding {
+    pub severity: AgentFindingSeverity,
+    pub category: String,
+    pub message: String,
+    /// File the finding refers to, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    /// 1-based line number within `path`, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+}
+
+/// Severity of an agent finding, from most to least severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum AgentFindingSeverity {
+    Error,
+    Warning,
+    Info,
+}
+
+/// A suggested improvement reported by an agent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+pub struct AgentSuggestion {
+    pub title: String,
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_change: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_tagged_results() {
+        let result = AgentRunResult::Combined {
+            results: vec![AgentRunResultEntry {
+                agent_id: "security".to_string(),
+                result: AgentRunResult::CodeReview {
+                    findings: vec![AgentFinding {
+                        severity: AgentFindingSeverity::Warning,
+                        category: "Injection".to_string(),
+                        message: "Unescaped input".to_string(),
+                        path: Some(PathBuf::from("src/db.rs")),
+                        line: Some(7),
+                    }],
+                },
+            }],
+        };
+
+        let value = serde_json::to_value(&result).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "type": "combined",
+                "results": [{
+                    "agent_id": "security",
+                    "result": {
+                        "type": "code_review",
+                        "findings": [{
+                            "severity": "warning",
+                            "category": "Injection",
+                            "message": "Unescaped input",
+                            "path": "src/db.rs",
+                            "line": 7,
+                        }],
+                    },
+                }],
+            })
+        );
+        let round_trip: AgentRunResult = serde_json::from_value(value).unwrap();
+        assert_eq!(round_trip, result);
+        assert_eq!(round_trip.findings().len(), 1);
+    }
+}
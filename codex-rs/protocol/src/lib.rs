@@ -1,5 +1,6 @@
 mod conversation_id;
 pub use conversation_id::ConversationId;
+pub mod agents;
 pub mod config_types;
 pub mod custom_prompts;
 pub mod message_history;
@@ -11,6 +11,7 @@ use std::str::FromStr;
 use std::time::Duration;
 
 use crate::ConversationId;
+use crate::agents::AgentRunResult;
 use crate::config_types::ReasoningEffort as ReasoningEffortConfig;
 use crate::config_types::ReasoningSummary as ReasoningSummaryConfig;
 use crate::custom_prompts::CustomPrompt;
@@ -21,6 +22,11 @@ use crate::num_format::format_with_separators;
 use crate::parse_command::ParsedCommand;
 use crate::plan_tool::UpdatePlanArgs;
 use mcp_types::CallToolResult;
+use mcp_types::ElicitRequestParamsRequestedSchema;
+use mcp_types::GetPromptResult;
+use mcp_types::Prompt as McpPrompt;
+use mcp_types::Resource as McpResource;
+use mcp_types::SamplingMessage;
 use mcp_types::Tool as McpTool;
 use serde::Deserialize;
 use serde::Serialize;
@@ -135,6 +141,10 @@ pub enum Op {
         decision: ReviewDecision,
     },
 
+    /// Re-read the project approval rules from `CODEX_HOME`, e.g. after a
+    /// rule was revoked. No reply is sent.
+    ReloadApprovalRules,
+
     /// Approve a code patch
     PatchApproval {
         /// The id of the submission we are approving
@@ -143,6 +153,26 @@ pub enum Op {
         decision: ReviewDecision,
     },
 
+    /// Answer an `EventMsg::McpSamplingRequest`.
+    McpSamplingApproval {
+        /// The id of the event that carried the request.
+        id: String,
+        /// The user's decision in response to the request.
+        decision: ReviewDecision,
+    },
+
+    /// Answer an `EventMsg::ElicitationRequest`.
+    ResolveElicitation {
+        /// The id of the event that carried the request.
+        id: String,
+        /// Whether the user submitted, declined or dismissed the form.
+        action: ElicitationAction,
+        /// The submitted values, keyed by field name, when `action` is
+        /// `Accept`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        content: Option<serde_json::Value>,
+    },
+
     /// Append an entry to the persistent cross-session message history.
     ///
     /// Note the entry is not guaranteed to be logged if the user has
@@ -163,9 +193,30 @@ pub enum Op {
     /// Reply is delivered via `EventMsg::McpListToolsResponse`.
     ListMcpTools,
 
-    /// Request the list of available custom prompts.
+    /// Re-read `mcp_servers` from `config.toml`: start servers that were
+    /// added or changed and stop the ones that were removed.
+    /// Reply is delivered via `EventMsg::McpListToolsResponse`.
+    ReloadMcpServers,
+
+    /// Request the list of resources offered by the configured MCP servers.
+    /// Reply is delivered via `EventMsg::McpListResourcesResponse`.
+    ListMcpResources,
+
+    /// Request the list of available custom prompts, including the prompts
+    /// offered by MCP servers.
     ListCustomPrompts,
 
+    /// Render a prompt offered by an MCP server with the given arguments.
+    /// Reply is delivered via `EventMsg::GetMcpPromptResponse`.
+    GetMcpPrompt {
+        /// Name of the MCP server as defined in the config.
+        server: String,
+        /// Name of the prompt as given by the MCP server.
+        name: String,
+        /// Argument name -> value.
+        arguments: HashMap<String, String>,
+    },
+
     /// Request the agent to summarize the current conversation context.
     /// The agent will use its existing context (either conversation history or previous response id)
     /// to generate a summary which will be returned as an AgentMessage event.
@@ -233,6 +284,12 @@ pub enum SandboxPolicy {
         #[serde(default)]
         network_access: bool,
 
+        /// Hosts (`host`, `host:port`, or `*.domain[:port]`) that commands may
+        /// reach through Codex's filtering egress proxy when `network_access`
+        /// is `false`. All other connections are refused.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        network_allowlist: Vec<String>,
+
         /// When set to `true`, will NOT include the per-user `TMPDIR`
         /// environment variable among the default writable roots. Defaults to
         /// `false`.
@@ -298,6 +355,7 @@ impl SandboxPolicy {
         SandboxPolicy::WorkspaceWrite {
             writable_roots: vec![],
             network_access: false,
+            network_allowlist: vec![],
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
         }
@@ -324,6 +382,19 @@ impl SandboxPolicy {
         }
     }
 
+    /// Hosts reachable through the egress proxy. Only meaningful when the
+    /// policy does not already grant full network access.
+    pub fn network_allowlist(&self) -> &[String] {
+        match self {
+            SandboxPolicy::WorkspaceWrite {
+                network_access: false,
+                network_allowlist,
+                ..
+            } => network_allowlist,
+            _ => &[],
+        }
+    }
+
     /// Returns the list of writable roots (tailored to the current working
     /// directory) together with subpaths that should remain read‑only under
     /// each writable root.
@@ -336,6 +407,7 @@ impl SandboxPolicy {
                 exclude_tmpdir_env_var,
                 exclude_slash_tmp,
                 network_access: _,
+                network_allowlist: _,
             } => {
                 // Start from explicitly configured writable roots.
                 let mut roots: Vec<PathBuf> = writable_roots.clone();
@@ -477,13 +549,29 @@ pub enum EventMsg {
 
     ExecCommandEnd(ExecCommandEndEvent),
 
+    /// Notification that the sandbox egress proxy refused a connection made
+    /// by a running command.
+    NetworkAccessBlocked(NetworkAccessBlockedEvent),
+
     /// Notification that the agent attached a local image via the view_image tool.
     ViewImageToolCall(ViewImageToolCallEvent),
 
     ExecApprovalRequest(ExecApprovalRequestEvent),
 
+    /// Notification that a command was approved by a stored approval rule
+    /// instead of asking the user.
+    ExecApprovalRuleApplied(ExecApprovalRuleAppliedEvent),
+
     ApplyPatchApprovalRequest(ApplyPatchApprovalRequestEvent),
 
+    /// An MCP server asks to sample the model. Answer with
+    /// `Op::McpSamplingApproval`.
+    McpSamplingRequest(McpSamplingRequestEvent),
+
+    /// An MCP server asks the user to fill in a form. Answer with
+    /// `Op::ResolveElicitation`.
+    ElicitationRequest(ElicitationRequestEvent),
+
     BackgroundEvent(BackgroundEventEvent),
 
     /// Notification that a model stream experienced an error or disconnect
@@ -505,9 +593,15 @@ pub enum EventMsg {
     /// List of MCP tools available to the agent.
     McpListToolsResponse(McpListToolsResponseEvent),
 
+    /// List of MCP resources available to the agent.
+    McpListResourcesResponse(McpListResourcesResponseEvent),
+
     /// List of custom prompts available to the agent.
     ListCustomPromptsResponse(ListCustomPromptsResponseEvent),
 
+    /// A prompt rendered by an MCP server in response to `Op::GetMcpPrompt`.
+    GetMcpPromptResponse(GetMcpPromptResponseEvent),
+
     PlanUpdate(UpdatePlanArgs),
 
     TurnAborted(TurnAbortedEvent),
@@ -522,6 +616,31 @@ pub enum EventMsg {
 
     /// Exited review mode with an optional final result to apply.
     ExitedReviewMode(ExitedReviewModeEvent),
+
+    /// Notification that an agent-backed slash command started running.
+    AgentRunBegin(AgentRunBeginEvent),
+
+    /// Notification that an agent-backed slash command finished, with its
+    /// structured result.
+    AgentRunEnd(AgentRunEndEvent),
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, TS)]
+pub struct AgentRunBeginEvent {
+    /// Name of the command, without the leading slash.
+    pub command: String,
+    /// The command line as entered, e.g. `/review src/`.
+    pub invocation: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, TS)]
+pub struct AgentRunEndEvent {
+    /// Name of the command, without the leading slash.
+    pub command: String,
+    #[ts(type = "string")]
+    pub duration: Duration,
+    /// Result of the run, or a description of why it failed.
+    pub result: Result<AgentRunResult, String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
@@ -627,6 +746,25 @@ impl TokenUsageInfo {
 pub struct TokenCountEvent {
     pub info: Option<TokenUsageInfo>,
     pub rate_limits: Option<RateLimitSnapshot>,
+    /// What is left of the session budget, when one is configured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget: Option<BudgetRemaining>,
+}
+
+/// Remaining session budget. `None` fields are unlimited.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, TS)]
+pub struct BudgetRemaining {
+    #[ts(type = "number | null")]
+    pub tokens: Option<u64>,
+    #[ts(type = "number | null")]
+    pub turns: Option<u64>,
+    #[ts(type = "number | null")]
+    pub tool_calls: Option<u64>,
+    /// Seconds left when the event was sent.
+    #[ts(type = "number | null")]
+    pub wall_clock_secs: Option<u64>,
+    /// Estimated cost left, in USD.
+    pub cost_usd: Option<f64>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
@@ -1137,6 +1275,46 @@ pub struct ExecCommandEndEvent {
     pub duration: Duration,
     /// Formatted output from the command, as seen by the model.
     pub formatted_output: String,
+    /// Set when the command was stopped for exceeding a sandbox resource
+    /// limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit_exceeded: Option<ExecResourceLimit>,
+}
+
+/// A per-command resource ceiling from the `[sandbox_limits]` config table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecResourceLimit {
+    /// CPU time.
+    CpuTime,
+    /// Memory usage; the command was OOM-killed.
+    Memory,
+    /// Number of processes and threads.
+    Processes,
+    /// Combined size of stdout and stderr.
+    OutputSize,
+}
+
+impl fmt::Display for ExecResourceLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ExecResourceLimit::CpuTime => "CPU time",
+            ExecResourceLimit::Memory => "memory",
+            ExecResourceLimit::Processes => "process count",
+            ExecResourceLimit::OutputSize => "output size",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, TS)]
+pub struct NetworkAccessBlockedEvent {
+    /// Identifier of the command that was running when the connection was
+    /// refused.
+    pub call_id: String,
+    /// Host the command tried to reach.
+    pub host: String,
+    pub port: u16,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
@@ -1180,6 +1358,18 @@ pub struct ExecApprovalRequestEvent {
     pub reason: Option<String>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, TS)]
+pub struct ExecApprovalRuleAppliedEvent {
+    /// Identifier for the associated exec call.
+    pub call_id: String,
+    /// The command that was approved.
+    pub command: Vec<String>,
+    /// Project root the rule is scoped to.
+    pub project: PathBuf,
+    /// Pattern of the rule that approved the command.
+    pub pattern: String,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
 pub struct ApplyPatchApprovalRequestEvent {
     /// Responses API call id for the associated patch apply call, if available.
@@ -1193,6 +1383,40 @@ pub struct ApplyPatchApprovalRequestEvent {
     pub grant_root: Option<PathBuf>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, TS)]
+pub struct McpSamplingRequestEvent {
+    /// Name of the MCP server as defined in the config.
+    pub server: String,
+    /// System prompt the server asked for, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_prompt: Option<String>,
+    /// Messages the model is asked to respond to.
+    pub messages: Vec<SamplingMessage>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, TS)]
+pub struct ElicitationRequestEvent {
+    /// Name of the MCP server as defined in the config.
+    pub server: String,
+    /// What the server wants to know.
+    pub message: String,
+    /// Flat JSON schema describing the fields to fill in.
+    pub requested_schema: ElicitRequestParamsRequestedSchema,
+}
+
+/// User's answer to an elicitation request.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Display, TS)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ElicitationAction {
+    /// The user submitted the form.
+    Accept,
+    /// The user explicitly refused to provide the information.
+    Decline,
+    /// The user dismissed the form without answering.
+    Cancel,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
 pub struct BackgroundEventEvent {
     pub message: String,
@@ -1243,12 +1467,50 @@ pub struct GetHistoryEntryResponseEvent {
 pub struct McpListToolsResponseEvent {
     /// Fully qualified tool name -> tool definition.
     pub tools: std::collections::HashMap<String, McpTool>,
+    /// Server name -> health of that server.
+    #[serde(default)]
+    pub server_statuses: HashMap<String, McpServerStatus>,
+}
+
+/// Health of a configured MCP server.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, TS)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum McpServerStatus {
+    /// The server is being started or restarted.
+    Starting,
+    /// The server is connected and its tools are available.
+    Ready,
+    /// The server failed to start or exited. `stderr_tail` holds the last
+    /// lines it wrote to stderr, if any.
+    Failed {
+        error: String,
+        stderr_tail: Vec<String>,
+    },
+}
+
+/// Response payload for `Op::ListMcpResources`.
+#[derive(Debug, Clone, Deserialize, Serialize, TS)]
+pub struct McpListResourcesResponseEvent {
+    /// Server name -> resources offered by that server.
+    pub resources: HashMap<String, Vec<McpResource>>,
 }
 
 /// Response payload for `Op::ListCustomPrompts`.
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
 pub struct ListCustomPromptsResponseEvent {
     pub custom_prompts: Vec<CustomPrompt>,
+    /// Server name -> prompts offered by that MCP server.
+    #[serde(default)]
+    pub mcp_prompts: HashMap<String, Vec<McpPrompt>>,
+}
+
+/// Response payload for `Op::GetMcpPrompt`.
+#[derive(Debug, Clone, Deserialize, Serialize, TS)]
+pub struct GetMcpPromptResponseEvent {
+    pub server: String,
+    pub name: String,
+    /// The rendered prompt. Note this could be an error.
+    pub result: Result<GetPromptResult, String>,
 }
 
 #[derive(Debug, Default, Clone, Deserialize, Serialize, TS)]
@@ -1289,6 +1551,11 @@ pub enum ReviewDecision {
     /// remainder of the session.
     ApprovedForSession,
 
+    /// User has approved this command and wants to automatically approve any
+    /// future identical instances in the same project, across sessions. The
+    /// approval is stored as an approval rule.
+    ApprovedForProject,
+
     /// User has denied this command and the agent should not execute it, but
     /// it should continue the session and try something else.
     #[default]
@@ -1333,6 +1600,8 @@ pub enum TurnAbortReason {
     Interrupted,
     Replaced,
     ReviewEnded,
+    /// A session or turn budget ran out.
+    BudgetExceeded,
 }
 
 #[cfg(test)]
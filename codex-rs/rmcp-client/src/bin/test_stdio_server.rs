@@ -1,15 +1,36 @@
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::PoisonError;
+use std::sync::RwLock;
 
 use rmcp::ErrorData as McpError;
 use rmcp::ServiceExt;
 use rmcp::handler::server::ServerHandler;
+use rmcp::model::AnnotateAble;
 use rmcp::model::CallToolRequestParam;
 use rmcp::model::CallToolResult;
+use rmcp::model::Content;
+use rmcp::model::CreateElicitationRequestParam;
+use rmcp::model::CreateMessageRequestParam;
+use rmcp::model::ElicitationAction;
+use rmcp::model::GetPromptRequestParam;
+use rmcp::model::GetPromptResult;
 use rmcp::model::JsonObject;
+use rmcp::model::ListPromptsResult;
+use rmcp::model::ListResourcesResult;
 use rmcp::model::ListToolsResult;
 use rmcp::model::PaginatedRequestParam;
+use rmcp::model::Prompt;
+use rmcp::model::PromptArgument;
+use rmcp::model::PromptMessage;
+use rmcp::model::PromptMessageRole;
+use rmcp::model::RawResource;
+use rmcp::model::ReadResourceRequestParam;
+use rmcp::model::ReadResourceResult;
+use rmcp::model::ResourceContents;
+use rmcp::model::Role;
+use rmcp::model::SamplingMessage;
 use rmcp::model::ServerCapabilities;
 use rmcp::model::ServerInfo;
 use rmcp::model::Tool;
@@ -19,19 +40,54 @@ use tokio::task;
 
 #[derive(Clone)]
 struct TestToolServer {
-    tools: Arc<Vec<Tool>>,
+    tools: Arc<RwLock<Vec<Tool>>>,
 }
 pub fn stdio() -> (tokio::io::Stdin, tokio::io::Stdout) {
     (tokio::io::stdin(), tokio::io::stdout())
 }
 impl TestToolServer {
     fn new() -> Self {
-        let tools = vec![Self::echo_tool()];
+        let mut tools = vec![Self::echo_tool()];
+        // Tools that let tests exercise tool list changes and crashes.
+        if std::env::var_os("MCP_TEST_SUPERVISION").is_some() {
+            tools.push(Self::no_args_tool(
+                "add_tool",
+                "Offer an additional tool and announce the change.",
+            ));
+            tools.push(Self::no_args_tool("crash", "Exit the server immediately."));
+        }
+        // Tools that call back into the client.
+        if std::env::var_os("MCP_TEST_CALLBACKS").is_some() {
+            tools.push(Self::no_args_tool(
+                "sample",
+                "Ask the client's model a question and return the answer.",
+            ));
+            tools.push(Self::no_args_tool(
+                "elicit",
+                "Ask the user for their name and greet them.",
+            ));
+        }
         Self {
-            tools: Arc::new(tools),
+            tools: Arc::new(RwLock::new(tools)),
         }
     }
 
+    fn no_args_tool(name: &'static str, description: &'static str) -> Tool {
+        #[expect(clippy::expect_used)]
+        let schema: JsonObject = serde_json::from_value(json!({
+            "type": "object",
+            "properties": {},
+            "additionalProperties": false
+        }))
+        .expect("tool schema should deserialize");
+
+        Tool::new(
+            Cow::Borrowed(name),
+            Cow::Borrowed(description),
+            Arc::new(schema),
+        )
+    }
+
     fn echo_tool() -> Tool {
         #[expect(clippy::expect_used)]
         let schema: JsonObject = serde_json::from_value(json!({
@@ -53,6 +109,9 @@ impl TestToolServer {
     }
 }
 
+const README_URI: &str = "memo://codex/readme";
+const README_TEXT: &str = "This server echoes messages back.";
+
 #[derive(Deserialize)]
 struct EchoArgs {
     message: String,
@@ -66,6 +125,8 @@ impl ServerHandler for TestToolServer {
             capabilities: ServerCapabilities::builder()
                 .enable_tools()
                 .enable_tool_list_changed()
+                .enable_resources()
+                .enable_prompts()
                 .build(),
             ..ServerInfo::default()
         }
@@ -76,19 +137,96 @@ impl ServerHandler for TestToolServer {
         _request: Option<PaginatedRequestParam>,
         _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
     ) -> impl std::future::Future<Output = Result<ListToolsResult, McpError>> + Send + '_ {
-        let tools = self.tools.clone();
+        let tools = self
+            .tools
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
         async move {
             Ok(ListToolsResult {
-                tools: (*tools).clone(),
+                tools,
                 next_cursor: None,
             })
         }
     }
 
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<ListResourcesResult, McpError> {
+        Ok(ListResourcesResult {
+            resources: vec![RawResource::new(README_URI, "readme").no_annotation()],
+            next_cursor: None,
+        })
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParam,
+        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<ReadResourceResult, McpError> {
+        if request.uri != README_URI {
+            return Err(McpError::resource_not_found(
+                format!("unknown resource: {}", request.uri),
+                None,
+            ));
+        }
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::text(README_TEXT, README_URI)],
+        })
+    }
+
+    async fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<ListPromptsResult, McpError> {
+        Ok(ListPromptsResult {
+            prompts: vec![Prompt::new(
+                "greeting",
+                Some("Greet someone by name."),
+                Some(vec![PromptArgument {
+                    name: "name".to_string(),
+                    title: None,
+                    description: Some("Who to greet.".to_string()),
+                    required: Some(true),
+                }]),
+            )],
+            next_cursor: None,
+        })
+    }
+
+    async fn get_prompt(
+        &self,
+        request: GetPromptRequestParam,
+        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<GetPromptResult, McpError> {
+        if request.name != "greeting" {
+            return Err(McpError::invalid_params(
+                format!("unknown prompt: {}", request.name),
+                None,
+            ));
+        }
+        let name = request
+            .arguments
+            .as_ref()
+            .and_then(|arguments| arguments.get("name"))
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| McpError::invalid_params("missing argument: name", None))?;
+        Ok(GetPromptResult {
+            description: None,
+            messages: vec![PromptMessage::new_text(
+                PromptMessageRole::User,
+                format!("Say hello to {name}."),
+            )],
+        })
+    }
+
     async fn call_tool(
         &self,
         request: CallToolRequestParam,
-        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
     ) -> Result<CallToolResult, McpError> {
         match request.name.as_ref() {
             "echo" => {
@@ -118,6 +256,80 @@ impl ServerHandler for TestToolServer {
                     meta: None,
                 })
             }
+            "add_tool" => {
+                self.tools
+                    .write()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .push(Self::no_args_tool("added", "Added by add_tool."));
+                context
+                    .peer
+                    .notify_tool_list_changed()
+                    .await
+                    .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+                Ok(CallToolResult::success(Vec::new()))
+            }
+            "sample" => {
+                let result = context
+                    .peer
+                    .create_message(CreateMessageRequestParam {
+                        messages: vec![SamplingMessage {
+                            role: Role::User,
+                            content: Content::text("What is the capital of France?"),
+                        }],
+                        model_preferences: None,
+                        system_prompt: Some("Answer in one word.".to_string()),
+                        include_context: None,
+                        temperature: None,
+                        max_tokens: 100,
+                        stop_sequences: None,
+                        metadata: None,
+                    })
+                    .await
+                    .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+                let answer = result
+                    .message
+                    .content
+                    .as_text()
+                    .map(|text| text.text.clone())
+                    .unwrap_or_default();
+                Ok(CallToolResult::success(vec![Content::text(format!(
+                    "sampled: {answer}"
+                ))]))
+            }
+            "elicit" => {
+                #[expect(clippy::expect_used)]
+                let requested_schema: JsonObject = serde_json::from_value(json!({
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string", "title": "Name" }
+                    },
+                    "required": ["name"]
+                }))
+                .expect("elicitation schema should deserialize");
+                let result = context
+                    .peer
+                    .create_elicitation(CreateElicitationRequestParam {
+                        message: "What is your name?".to_string(),
+                        requested_schema,
+                    })
+                    .await
+                    .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+                let text = match (result.action, result.content) {
+                    (ElicitationAction::Accept, Some(content)) => {
+                        let name = content
+                            .get("name")
+                            .and_then(serde_json::Value::as_str)
+                            .unwrap_or_default();
+                        format!("Hello {name}")
+                    }
+                    (action, _) => format!("no name: {action:?}"),
+                };
+                Ok(CallToolResult::success(vec![Content::text(text)]))
+            }
+            "crash" => {
+                eprintln!("crashing on request");
+                std::process::exit(1);
+            }
             other => Err(McpError::invalid_params(
                 format!("unknown tool: {other}"),
                 None,
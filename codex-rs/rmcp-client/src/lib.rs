@@ -3,6 +3,7 @@ mod logging_client_handler;
 mod oauth;
 mod perform_oauth_login;
 mod rmcp_client;
+mod server_request_handler;
 mod utils;
 
 pub use oauth::OAuthCredentialsStoreMode;
@@ -13,3 +14,4 @@ pub(crate) use oauth::load_oauth_tokens;
 pub use oauth::save_oauth_tokens;
 pub use perform_oauth_login::perform_oauth_login;
 pub use rmcp_client::RmcpClient;
+pub use server_request_handler::ServerRequestHandler;
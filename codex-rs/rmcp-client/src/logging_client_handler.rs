@@ -1,9 +1,17 @@
+use std::sync::Arc;
+
+use mcp_types::ResourceUpdatedNotificationParams;
+use mcp_types::ServerNotification;
 use rmcp::ClientHandler;
+use rmcp::ErrorData;
 use rmcp::RoleClient;
 use rmcp::model::CancelledNotificationParam;
 use rmcp::model::ClientInfo;
 use rmcp::model::CreateElicitationRequestParam;
 use rmcp::model::CreateElicitationResult;
+use rmcp::model::CreateMessageRequestMethod;
+use rmcp::model::CreateMessageRequestParam;
+use rmcp::model::CreateMessageResult;
 use rmcp::model::ElicitationAction;
 use rmcp::model::LoggingLevel;
 use rmcp::model::LoggingMessageNotificationParam;
@@ -11,37 +19,84 @@ use rmcp::model::ProgressNotificationParam;
 use rmcp::model::ResourceUpdatedNotificationParam;
 use rmcp::service::NotificationContext;
 use rmcp::service::RequestContext;
+use tokio::sync::broadcast;
 use tracing::debug;
 use tracing::error;
 use tracing::info;
 use tracing::warn;
 
-#[derive(Debug, Clone)]
+use crate::ServerRequestHandler;
+use crate::utils::convert_to_mcp;
+use crate::utils::convert_to_rmcp;
+
+#[derive(Clone)]
 pub(crate) struct LoggingClientHandler {
     client_info: ClientInfo,
+    notifications_tx: broadcast::Sender<ServerNotification>,
+    request_handler: Option<Arc<dyn ServerRequestHandler>>,
 }
 
 impl LoggingClientHandler {
-    pub(crate) fn new(client_info: ClientInfo) -> Self {
-        Self { client_info }
+    pub(crate) fn new(
+        client_info: ClientInfo,
+        notifications_tx: broadcast::Sender<ServerNotification>,
+        request_handler: Option<Arc<dyn ServerRequestHandler>>,
+    ) -> Self {
+        Self {
+            client_info,
+            notifications_tx,
+            request_handler,
+        }
+    }
+
+    fn forward(&self, notification: ServerNotification) {
+        // Nobody listening is not an error.
+        let _ = self.notifications_tx.send(notification);
     }
 }
 
 impl ClientHandler for LoggingClientHandler {
-    // TODO (CODEX-3571): support elicitations.
+    async fn create_message(
+        &self,
+        params: CreateMessageRequestParam,
+        _context: RequestContext<RoleClient>,
+    ) -> Result<CreateMessageResult, ErrorData> {
+        let Some(handler) = &self.request_handler else {
+            return Err(ErrorData::method_not_found::<CreateMessageRequestMethod>());
+        };
+        info!("MCP server requested sampling");
+        let params = convert_to_mcp(params)
+            .map_err(|err| ErrorData::invalid_params(err.to_string(), None))?;
+        let result = handler
+            .create_message(params)
+            .await
+            .map_err(|err| ErrorData::internal_error(format!("{err:#}"), None))?;
+        convert_to_rmcp(result).map_err(|err| ErrorData::internal_error(err.to_string(), None))
+    }
+
     async fn create_elicitation(
         &self,
         request: CreateElicitationRequestParam,
         _context: RequestContext<RoleClient>,
-    ) -> Result<CreateElicitationResult, rmcp::ErrorData> {
-        info!(
-            "MCP server requested elicitation ({}). Elicitations are not supported yet. Declining.",
-            request.message
-        );
-        Ok(CreateElicitationResult {
-            action: ElicitationAction::Decline,
-            content: None,
-        })
+    ) -> Result<CreateElicitationResult, ErrorData> {
+        let Some(handler) = &self.request_handler else {
+            info!(
+                "MCP server requested elicitation ({}) but no handler is installed. Declining.",
+                request.message
+            );
+            return Ok(CreateElicitationResult {
+                action: ElicitationAction::Decline,
+                content: None,
+            });
+        };
+        info!("MCP server requested elicitation ({})", request.message);
+        let params = convert_to_mcp(request)
+            .map_err(|err| ErrorData::invalid_params(err.to_string(), None))?;
+        let result = handler
+            .elicit(params)
+            .await
+            .map_err(|err| ErrorData::internal_error(format!("{err:#}"), None))?;
+        convert_to_rmcp(result).map_err(|err| ErrorData::internal_error(err.to_string(), None))
     }
 
     async fn on_cancelled(
@@ -72,18 +127,24 @@ impl ClientHandler for LoggingClientHandler {
         _context: NotificationContext<RoleClient>,
     ) {
         info!("MCP server resource updated (uri: {})", params.uri);
+        self.forward(ServerNotification::ResourceUpdatedNotification(
+            ResourceUpdatedNotificationParams { uri: params.uri },
+        ));
     }
 
     async fn on_resource_list_changed(&self, _context: NotificationContext<RoleClient>) {
         info!("MCP server resource list changed");
+        self.forward(ServerNotification::ResourceListChangedNotification(None));
     }
 
     async fn on_tool_list_changed(&self, _context: NotificationContext<RoleClient>) {
         info!("MCP server tool list changed");
+        self.forward(ServerNotification::ToolListChangedNotification(None));
     }
 
     async fn on_prompt_list_changed(&self, _context: NotificationContext<RoleClient>) {
         info!("MCP server prompt list changed");
+        self.forward(ServerNotification::PromptListChangedNotification(None));
     }
 
     fn get_info(&self) -> ClientInfo {
@@ -1,8 +1,10 @@
 use std::collections::HashMap;
+use std::collections::VecDeque;
 use std::ffi::OsString;
 use std::io;
 use std::process::Stdio;
 use std::sync::Arc;
+use std::sync::PoisonError;
 use std::time::Duration;
 
 use anyhow::Result;
@@ -10,13 +12,24 @@ use anyhow::anyhow;
 use futures::FutureExt;
 use mcp_types::CallToolRequestParams;
 use mcp_types::CallToolResult;
+use mcp_types::GetPromptRequestParams;
+use mcp_types::GetPromptResult;
 use mcp_types::InitializeRequestParams;
 use mcp_types::InitializeResult;
+use mcp_types::ListPromptsRequestParams;
+use mcp_types::ListPromptsResult;
+use mcp_types::ListResourcesRequestParams;
+use mcp_types::ListResourcesResult;
 use mcp_types::ListToolsRequestParams;
 use mcp_types::ListToolsResult;
+use mcp_types::ReadResourceRequestParams;
+use mcp_types::ReadResourceResult;
+use mcp_types::ServerNotification;
 use rmcp::model::CallToolRequestParam;
+use rmcp::model::GetPromptRequestParam;
 use rmcp::model::InitializeRequestParam;
 use rmcp::model::PaginatedRequestParam;
+use rmcp::model::ReadResourceRequestParam;
 use rmcp::service::RoleClient;
 use rmcp::service::RunningService;
 use rmcp::service::{self};
@@ -29,6 +42,7 @@ use tokio::io::AsyncBufReadExt;
 use tokio::io::BufReader;
 use tokio::process::Command;
 use tokio::sync::Mutex;
+use tokio::sync::broadcast;
 use tokio::time;
 use tracing::info;
 use tracing::warn;
@@ -38,12 +52,19 @@ use crate::logging_client_handler::LoggingClientHandler;
 use crate::oauth::OAuthCredentialsStoreMode;
 use crate::oauth::OAuthPersistor;
 use crate::oauth::StoredOAuthTokens;
+use crate::server_request_handler::ServerRequestHandler;
 use crate::utils::convert_call_tool_result;
 use crate::utils::convert_to_mcp;
 use crate::utils::convert_to_rmcp;
 use crate::utils::create_env_for_mcp_server;
 use crate::utils::run_with_timeout;
 
+/// Capacity of the channel that fans out server notifications.
+const NOTIFICATION_CHANNEL_CAPACITY: usize = 128;
+
+/// Number of trailing stderr lines kept for diagnostics.
+const STDERR_TAIL_LINES: usize = 20;
+
 enum PendingTransport {
     ChildProcess(TokioChildProcess),
     StreamableHttp {
@@ -69,6 +90,10 @@ enum ClientState {
 /// https://github.com/modelcontextprotocol/rust-sdk
 pub struct RmcpClient {
     state: Mutex<ClientState>,
+    notifications_tx: broadcast::Sender<ServerNotification>,
+    /// Last [`STDERR_TAIL_LINES`] lines a STDIO server wrote to STDERR.
+    stderr_tail: Arc<std::sync::Mutex<VecDeque<String>>>,
+    request_handler: Option<Arc<dyn ServerRequestHandler>>,
 }
 
 impl RmcpClient {
@@ -91,13 +116,21 @@ impl RmcpClient {
             .stderr(Stdio::piped())
             .spawn()?;
 
+        let stderr_tail = Arc::new(std::sync::Mutex::new(VecDeque::new()));
         if let Some(stderr) = stderr {
+            let stderr_tail = Arc::clone(&stderr_tail);
             tokio::spawn(async move {
                 let mut reader = BufReader::new(stderr).lines();
                 loop {
                     match reader.next_line().await {
                         Ok(Some(line)) => {
                             info!("MCP server stderr ({program_name}): {line}");
+                            let mut tail =
+                                stderr_tail.lock().unwrap_or_else(PoisonError::into_inner);
+                            if tail.len() == STDERR_TAIL_LINES {
+                                tail.pop_front();
+                            }
+                            tail.push_back(line);
                         }
                         Ok(None) => break,
                         Err(error) => {
@@ -113,6 +146,9 @@ impl RmcpClient {
             state: Mutex::new(ClientState::Connecting {
                 transport: Some(PendingTransport::ChildProcess(transport)),
             }),
+            notifications_tx: broadcast::channel(NOTIFICATION_CHANNEL_CAPACITY).0,
+            stderr_tail,
+            request_handler: None,
         })
     }
 
@@ -153,9 +189,20 @@ impl RmcpClient {
             state: Mutex::new(ClientState::Connecting {
                 transport: Some(transport),
             }),
+            notifications_tx: broadcast::channel(NOTIFICATION_CHANNEL_CAPACITY).0,
+            stderr_tail: Arc::default(),
+            request_handler: None,
         })
     }
 
+    /// Answer sampling and elicitation requests from the server with
+    /// `handler`. Without one, sampling is refused and elicitation declined.
+    /// Takes effect at [`RmcpClient::initialize`].
+    pub fn with_request_handler(mut self, handler: Arc<dyn ServerRequestHandler>) -> Self {
+        self.request_handler = Some(handler);
+        self
+    }
+
     /// Perform the initialization handshake with the MCP server.
     /// https://modelcontextprotocol.io/specification/2025-06-18/basic/lifecycle#initialization
     pub async fn initialize(
@@ -164,7 +211,11 @@ impl RmcpClient {
         timeout: Option<Duration>,
     ) -> Result<InitializeResult> {
         let rmcp_params: InitializeRequestParam = convert_to_rmcp(params.clone())?;
-        let client_handler = LoggingClientHandler::new(rmcp_params);
+        let client_handler = LoggingClientHandler::new(
+            rmcp_params,
+            self.notifications_tx.clone(),
+            self.request_handler.clone(),
+        );
 
         let (transport, oauth_persistor) = {
             let mut guard = self.state.lock().await;
@@ -257,6 +308,94 @@ impl RmcpClient {
         Ok(converted)
     }
 
+    pub async fn list_resources(
+        &self,
+        params: Option<ListResourcesRequestParams>,
+        timeout: Option<Duration>,
+    ) -> Result<ListResourcesResult> {
+        let service = self.service().await?;
+        let rmcp_params = params
+            .map(convert_to_rmcp::<_, PaginatedRequestParam>)
+            .transpose()?;
+
+        let fut = service.list_resources(rmcp_params);
+        let result = run_with_timeout(fut, timeout, "resources/list").await?;
+        let converted = convert_to_mcp(result)?;
+        self.persist_oauth_tokens().await;
+        Ok(converted)
+    }
+
+    pub async fn read_resource(
+        &self,
+        params: ReadResourceRequestParams,
+        timeout: Option<Duration>,
+    ) -> Result<ReadResourceResult> {
+        let service = self.service().await?;
+        let rmcp_params: ReadResourceRequestParam = convert_to_rmcp(params)?;
+        let fut = service.read_resource(rmcp_params);
+        let result = run_with_timeout(fut, timeout, "resources/read").await?;
+        let converted = convert_to_mcp(result)?;
+        self.persist_oauth_tokens().await;
+        Ok(converted)
+    }
+
+    pub async fn list_prompts(
+        &self,
+        params: Option<ListPromptsRequestParams>,
+        timeout: Option<Duration>,
+    ) -> Result<ListPromptsResult> {
+        let service = self.service().await?;
+        let rmcp_params = params
+            .map(convert_to_rmcp::<_, PaginatedRequestParam>)
+            .transpose()?;
+
+        let fut = service.list_prompts(rmcp_params);
+        let result = run_with_timeout(fut, timeout, "prompts/list").await?;
+        let converted = convert_to_mcp(result)?;
+        self.persist_oauth_tokens().await;
+        Ok(converted)
+    }
+
+    pub async fn get_prompt(
+        &self,
+        params: GetPromptRequestParams,
+        timeout: Option<Duration>,
+    ) -> Result<GetPromptResult> {
+        let service = self.service().await?;
+        let rmcp_params: GetPromptRequestParam = convert_to_rmcp(params)?;
+        let fut = service.get_prompt(rmcp_params);
+        let result = run_with_timeout(fut, timeout, "prompts/get").await?;
+        let converted = convert_to_mcp(result)?;
+        self.persist_oauth_tokens().await;
+        Ok(converted)
+    }
+
+    /// Subscribe to notifications sent by the server from now on.
+    pub fn subscribe_notifications(&self) -> broadcast::Receiver<ServerNotification> {
+        self.notifications_tx.subscribe()
+    }
+
+    /// Whether the connection to the server is gone, e.g. because a STDIO
+    /// server exited. A client that has not been initialized is not closed.
+    pub async fn is_closed(&self) -> bool {
+        let guard = self.state.lock().await;
+        match &*guard {
+            ClientState::Ready { service, .. } => service.is_transport_closed(),
+            ClientState::Connecting { .. } => false,
+        }
+    }
+
+    /// The last lines a STDIO server wrote to STDERR, oldest first. Always
+    /// empty for streamable HTTP servers.
+    pub fn stderr_tail(&self) -> Vec<String> {
+        self.stderr_tail
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .cloned()
+            .collect()
+    }
+
     async fn service(&self) -> Result<Arc<RunningService<RoleClient, LoggingClientHandler>>> {
         let guard = self.state.lock().await;
         match &*guard {
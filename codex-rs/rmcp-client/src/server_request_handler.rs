@@ -0,0 +1,20 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use mcp_types::CreateMessageRequestParams;
+use mcp_types::CreateMessageResult;
+use mcp_types::ElicitRequestParams;
+use mcp_types::ElicitResult;
+
+/// Answers the requests an MCP server sends back to the client while it is
+/// connected. Install one with [`crate::RmcpClient::with_request_handler`].
+#[async_trait]
+pub trait ServerRequestHandler: Send + Sync {
+    /// Answer a `sampling/createMessage` request by asking a model.
+    async fn create_message(
+        &self,
+        params: CreateMessageRequestParams,
+    ) -> Result<CreateMessageResult>;
+
+    /// Answer an `elicitation/create` request by asking the user.
+    async fn elicit(&self, params: ElicitRequestParams) -> Result<ElicitResult>;
+}
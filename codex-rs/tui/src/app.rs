@@ -2,6 +2,7 @@ use crate::app_backtrack::BacktrackState;
 use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
 use crate::bottom_pane::ApprovalRequest;
+use crate::bottom_pane::sampling_lines;
 use crate::chatwidget::ChatWidget;
 use crate::command_palette::{CommandPalette, PaletteAction};
 use crate::diff_render::DiffSummary;
@@ -16,9 +17,11 @@ use crate::tui::TuiEvent;
 use codex_ansi_escape::ansi_escape_line;
 use codex_core::AuthManager;
 use codex_core::ConversationManager;
+use codex_core::approval_rules::revoke_rule;
 use codex_core::config::Config;
 use codex_core::config::persist_model_selection;
 use codex_core::model_family::find_family_for_model;
+use codex_core::protocol::Op;
 use codex_core::protocol::SessionSource;
 use codex_core::protocol::TokenUsage;
 use codex_core::protocol_config_types::ReasoningEffort as ReasoningEffortConfig;
@@ -334,6 +337,27 @@ impl App {
             AppEvent::OpenReasoningPopup { model, presets } => {
                 self.chat_widget.open_reasoning_popup(model, presets);
             }
+            AppEvent::OpenApprovalRulesPopup => {
+                self.chat_widget.open_approval_rules_popup();
+            }
+            AppEvent::RevokeApprovalRule { pattern } => {
+                match revoke_rule(&self.config.codex_home, &self.config.cwd, &pattern) {
+                    Ok(true) => {
+                        self.chat_widget.submit_op(Op::ReloadApprovalRules);
+                        self.chat_widget
+                            .add_info_message(format!("Revoked approval rule `{pattern}`"), None);
+                    }
+                    Ok(false) => self
+                        .chat_widget
+                        .add_error_message(format!("No approval rule `{pattern}` found")),
+                    Err(err) => {
+                        tracing::error!(error = %err, "failed to revoke approval rule");
+                        self.chat_widget.add_error_message(format!(
+                            "Failed to revoke approval rule `{pattern}`: {err}"
+                        ));
+                    }
+                }
+            }
             AppEvent::PersistModelSelection { model, effort } => {
                 let profile = self.active_profile.as_deref();
                 match persist_model_selection(&self.config.codex_home, profile, &model, effort)
@@ -406,6 +430,17 @@ impl App {
                         "E X E C".to_string(),
                     ));
                 }
+                ApprovalRequest::McpSampling {
+                    system_prompt,
+                    messages,
+                    ..
+                } => {
+                    let _ = tui.enter_alt_screen();
+                    self.overlay = Some(Overlay::new_static_with_lines(
+                        sampling_lines(system_prompt.as_deref(), &messages),
+                        "S A M P L I N G".to_string(),
+                    ));
+                }
             },
         }
         Ok(true)
@@ -67,6 +67,14 @@ pub(crate) enum AppEvent {
         presets: Vec<ModelPreset>,
     },
 
+    /// Open the list of approval rules saved for the current project.
+    OpenApprovalRulesPopup,
+
+    /// Remove the approval rule with `pattern` from the current project.
+    RevokeApprovalRule {
+        pattern: String,
+    },
+
     /// Update the current approval policy in the running app and widget.
     UpdateAskForApprovalPolicy(AskForApproval),
 
@@ -24,6 +24,9 @@ use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
 use crossterm::event::KeyModifiers;
+use mcp_types::Role;
+use mcp_types::SamplingMessage;
+use mcp_types::SamplingMessageContent;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::style::Stylize;
@@ -46,6 +49,12 @@ pub(crate) enum ApprovalRequest {
         cwd: PathBuf,
         changes: HashMap<PathBuf, FileChange>,
     },
+    McpSampling {
+        id: String,
+        server: String,
+        system_prompt: Option<String>,
+        messages: Vec<SamplingMessage>,
+    },
 }
 
 /// Modal overlay asking the user to approve or deny one or more requests.
@@ -103,6 +112,10 @@ impl ApprovalOverlay {
                 patch_options(),
                 "Would you like to make the following edits?".to_string(),
             ),
+            ApprovalVariant::McpSampling { server, .. } => (
+                sampling_options(),
+                format!("Allow MCP server {server} to send this request to the model?"),
+            ),
         };
 
         let header = Box::new(ColumnRenderable::new([
@@ -152,6 +165,9 @@ impl ApprovalOverlay {
                 (ApprovalVariant::ApplyPatch { id, .. }, decision) => {
                     self.handle_patch_decision(id, decision);
                 }
+                (ApprovalVariant::McpSampling { id, .. }, decision) => {
+                    self.handle_sampling_decision(id, decision);
+                }
             }
         }
 
@@ -178,6 +194,14 @@ impl ApprovalOverlay {
         }));
     }
 
+    fn handle_sampling_decision(&self, id: &str, decision: ReviewDecision) {
+        self.app_event_tx
+            .send(AppEvent::CodexOp(Op::McpSamplingApproval {
+                id: id.to_string(),
+                decision,
+            }));
+    }
+
     fn advance_queue(&mut self) {
         if let Some(next) = self.queue.pop() {
             self.set_current(next);
@@ -243,6 +267,9 @@ impl BottomPaneView for ApprovalOverlay {
                 ApprovalVariant::ApplyPatch { id, .. } => {
                     self.handle_patch_decision(id, ReviewDecision::Abort);
                 }
+                ApprovalVariant::McpSampling { id, .. } => {
+                    self.handle_sampling_decision(id, ReviewDecision::Denied);
+                }
             }
         }
         self.queue.clear();
@@ -330,6 +357,18 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                     header: Box::new(ColumnRenderable::new(header)),
                 }
             }
+            ApprovalRequest::McpSampling {
+                id,
+                server,
+                system_prompt,
+                messages,
+            } => Self {
+                variant: ApprovalVariant::McpSampling { id, server },
+                header: Box::new(
+                    Paragraph::new(sampling_lines(system_prompt.as_deref(), &messages))
+                        .wrap(Wrap { trim: false }),
+                ),
+            },
         }
     }
 }
@@ -338,6 +377,7 @@ impl From<ApprovalRequest> for ApprovalRequestState {
 enum ApprovalVariant {
     Exec { id: String, command: Vec<String> },
     ApplyPatch { id: String },
+    McpSampling { id: String, server: String },
 }
 
 #[derive(Clone)]
@@ -370,6 +410,12 @@ fn exec_options() -> Vec<ApprovalOption> {
             display_shortcut: None,
             additional_shortcuts: vec![key_hint::plain(KeyCode::Char('a'))],
         },
+        ApprovalOption {
+            label: "Yes, and always allow this command in this project".to_string(),
+            decision: ReviewDecision::ApprovedForProject,
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('p'))],
+        },
         ApprovalOption {
             label: "No, and tell Codex what to do differently".to_string(),
             decision: ReviewDecision::Abort,
@@ -396,6 +442,58 @@ fn patch_options() -> Vec<ApprovalOption> {
     ]
 }
 
+fn sampling_options() -> Vec<ApprovalOption> {
+    vec![
+        ApprovalOption {
+            label: "Yes, send it".to_string(),
+            decision: ReviewDecision::Approved,
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
+        },
+        ApprovalOption {
+            label: "Yes, and don't ask again for this server".to_string(),
+            decision: ReviewDecision::ApprovedForSession,
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('a'))],
+        },
+        ApprovalOption {
+            label: "No, refuse the request".to_string(),
+            decision: ReviewDecision::Denied,
+            display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
+        },
+    ]
+}
+
+/// Lines showing what an MCP server wants to send to the model.
+pub(crate) fn sampling_lines(
+    system_prompt: Option<&str>,
+    messages: &[SamplingMessage],
+) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    if let Some(system_prompt) = system_prompt
+        && !system_prompt.is_empty()
+    {
+        lines.push(Line::from(vec![
+            "System: ".dim(),
+            system_prompt.to_string().italic(),
+        ]));
+    }
+    for message in messages {
+        let role = match message.role {
+            Role::User => "User: ",
+            Role::Assistant => "Assistant: ",
+        };
+        let content = match &message.content {
+            SamplingMessageContent::TextContent(text) => text.text.clone(),
+            SamplingMessageContent::ImageContent(image) => format!("<{} image>", image.mime_type),
+            SamplingMessageContent::AudioContent(audio) => format!("<{} audio>", audio.mime_type),
+        };
+        lines.push(Line::from(vec![role.dim(), content.into()]));
+    }
+    lines
+}
+
 fn build_exec_history_lines(
     command: Vec<String>,
     decision: ReviewDecision,
@@ -429,6 +527,19 @@ fn build_exec_history_lines(
                 ],
             )
         }
+        ApprovedForProject => {
+            let snippet = Span::from(exec_snippet(&command)).dim();
+            (
+                "✔ ".green(),
+                vec![
+                    "You ".into(),
+                    "approved".bold(),
+                    " codex to run ".into(),
+                    snippet,
+                    " every time in this project".bold(),
+                ],
+            )
+        }
         Denied => {
             let snippet = Span::from(exec_snippet(&command)).dim();
             (
@@ -572,4 +683,46 @@ mod tests {
         }
         assert_eq!(decision, Some(ReviewDecision::ApprovedForSession));
     }
+
+    #[test]
+    fn sampling_request_is_denied_on_esc() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx_raw);
+        let request = ApprovalRequest::McpSampling {
+            id: "sampling".to_string(),
+            server: "docs".to_string(),
+            system_prompt: None,
+            messages: Vec::new(),
+        };
+        let mut view = ApprovalOverlay::new(request, tx);
+        view.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+
+        let mut decision = None;
+        while let Ok(ev) = rx.try_recv() {
+            if let AppEvent::CodexOp(Op::McpSamplingApproval { id, decision: d }) = ev {
+                assert_eq!(id, "sampling");
+                decision = Some(d);
+                break;
+            }
+        }
+        assert_eq!(decision, Some(ReviewDecision::Denied));
+        assert!(view.is_complete());
+    }
+
+    #[test]
+    fn shortcut_approves_for_project() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx_raw);
+        let mut view = ApprovalOverlay::new(make_exec_request(), tx);
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::NONE));
+
+        let mut decision = None;
+        while let Ok(ev) = rx.try_recv() {
+            if let AppEvent::CodexOp(Op::ExecApproval { decision: d, .. }) = ev {
+                decision = Some(d);
+                break;
+            }
+        }
+        assert_eq!(decision, Some(ReviewDecision::ApprovedForProject));
+    }
 }
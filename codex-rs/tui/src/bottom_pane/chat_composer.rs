@@ -19,6 +19,7 @@ use super::chat_composer_history::ChatComposerHistory;
 use super::command_popup::CommandItem;
 use super::command_popup::CommandPopup;
 use super::file_search_popup::FileSearchPopup;
+use super::file_search_popup::match_resources;
 use super::footer::FooterMode;
 use super::footer::FooterProps;
 use super::footer::esc_hint_mode;
@@ -29,8 +30,11 @@ use super::footer::toggle_shortcut_mode;
 use super::paste_burst::CharDecision;
 use super::paste_burst::PasteBurst;
 use crate::bottom_pane::paste_burst::FlushResult;
+use crate::bottom_pane::prompt_args::McpPromptInvocation;
 use crate::bottom_pane::prompt_args::expand_custom_prompt;
 use crate::bottom_pane::prompt_args::expand_if_numeric_with_positional_args;
+use crate::bottom_pane::prompt_args::mcp_prompt_command_with_arg_placeholders;
+use crate::bottom_pane::prompt_args::parse_mcp_prompt_command;
 use crate::bottom_pane::prompt_args::parse_slash_name;
 use crate::bottom_pane::prompt_args::prompt_argument_names;
 use crate::bottom_pane::prompt_args::prompt_command_with_arg_placeholders;
@@ -38,8 +42,12 @@ use crate::bottom_pane::prompt_args::prompt_has_numeric_placeholders;
 use crate::slash_command::SlashCommand;
 use crate::slash_command::built_in_slash_commands;
 use crate::style::user_message_style;
+use codex_core::protocol::Op;
 use codex_protocol::custom_prompts::CustomPrompt;
+use codex_protocol::custom_prompts::MCP_PROMPTS_CMD_PREFIX;
 use codex_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
+use mcp_types::Prompt as McpPrompt;
+use mcp_types::Resource as McpResource;
 
 use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
@@ -106,6 +114,9 @@ pub(crate) struct ChatComposer {
     // When true, disables paste-burst logic and inserts characters immediately.
     disable_paste_burst: bool,
     custom_prompts: Vec<CustomPrompt>,
+    // Prompts and resources offered by MCP servers, keyed by server name.
+    mcp_prompts: HashMap<String, Vec<McpPrompt>>,
+    mcp_resources: HashMap<String, Vec<McpResource>>,
     footer_mode: FooterMode,
     footer_hint_override: Option<Vec<(String, String)>>,
     context_window_percent: Option<u8>,
@@ -149,6 +160,8 @@ impl ChatComposer {
             paste_burst: PasteBurst::default(),
             disable_paste_burst: false,
             custom_prompts: Vec::new(),
+            mcp_prompts: HashMap::new(),
+            mcp_resources: HashMap::new(),
             footer_mode: FooterMode::ShortcutPrompt,
             footer_hint_override: None,
             context_window_percent: None,
@@ -478,6 +491,13 @@ impl ChatComposer {
                                 }
                             }
                         }
+                        CommandItem::McpPrompt(idx) => {
+                            if let Some((server, prompt)) = popup.mcp_prompt(idx) {
+                                let (text, cursor) = mcp_prompt_completion(server, prompt);
+                                self.textarea.set_text(&text);
+                                cursor_target = Some(cursor);
+                            }
+                        }
                     }
                     if let Some(pos) = cursor_target {
                         self.textarea.set_cursor(pos);
@@ -531,6 +551,30 @@ impl ChatComposer {
                             }
                             return (InputResult::None, true);
                         }
+                        CommandItem::McpPrompt(idx) => {
+                            let Some((server, prompt)) = popup.mcp_prompt(idx) else {
+                                return (InputResult::None, true);
+                            };
+                            let command =
+                                format!("/{MCP_PROMPTS_CMD_PREFIX}:{server}:{} ", prompt.name);
+                            let has_args = prompt.arguments.as_ref().is_some_and(|a| !a.is_empty());
+                            // Prompts that take arguments are completed with
+                            // placeholders first unless the line already names
+                            // this prompt.
+                            if has_args && !first_line.starts_with(&command) {
+                                let (text, cursor) = mcp_prompt_completion(server, prompt);
+                                self.textarea.set_text(&text);
+                                self.textarea.set_cursor(cursor);
+                                return (InputResult::None, true);
+                            }
+                            let text = if first_line.starts_with(&command) {
+                                first_line.to_string()
+                            } else {
+                                command
+                            };
+                            self.active_popup = ActivePopup::None;
+                            return self.submit_mcp_prompt_command(text);
+                        }
                     }
                 }
                 // Fallback to default newline handling if no command selected.
@@ -616,6 +660,12 @@ impl ChatComposer {
                 modifiers: KeyModifiers::NONE,
                 ..
             } => {
+                if let Some(mention) = popup.selected_resource() {
+                    let mention = format!("@{mention}");
+                    self.insert_selected_path(&mention);
+                    self.active_popup = ActivePopup::None;
+                    return (InputResult::None, true);
+                }
                 let Some(sel) = popup.selected_match() else {
                     self.active_popup = ActivePopup::None;
                     return (InputResult::None, true);
@@ -971,6 +1021,11 @@ impl ChatComposer {
                 // If there is neither text nor attachments, suppress submission entirely.
                 let has_attachments = !self.attached_images.is_empty();
                 text = text.trim().to_string();
+                if parse_slash_name(&text).is_some_and(|(name, _)| {
+                    name.starts_with(&format!("{MCP_PROMPTS_CMD_PREFIX}:"))
+                }) {
+                    return self.submit_mcp_prompt_command(text);
+                }
                 let expanded_prompt = match expand_custom_prompt(&text, &self.custom_prompts) {
                     Ok(expanded) => expanded,
                     Err(err) => {
@@ -1399,6 +1454,7 @@ impl ChatComposer {
             _ => {
                 if is_editing_slash_command_name {
                     let mut command_popup = CommandPopup::new(self.custom_prompts.clone());
+                    command_popup.set_mcp_prompts(&self.mcp_prompts);
                     command_popup.on_composer_text_change(first_line.to_string());
                     self.active_popup = ActivePopup::Command(command_popup);
                 }
@@ -1413,6 +1469,54 @@ impl ChatComposer {
         }
     }
 
+    pub(crate) fn set_mcp_prompts(&mut self, prompts: HashMap<String, Vec<McpPrompt>>) {
+        if let ActivePopup::Command(popup) = &mut self.active_popup {
+            popup.set_mcp_prompts(&prompts);
+        }
+        self.mcp_prompts = prompts;
+    }
+
+    pub(crate) fn set_mcp_resources(&mut self, resources: HashMap<String, Vec<McpResource>>) {
+        self.mcp_resources = resources;
+    }
+
+    /// Parse `text` as a `/mcp:server:name` command and ask codex to render the
+    /// prompt. The rendered prompt is submitted once the server replies.
+    fn submit_mcp_prompt_command(&mut self, text: String) -> (InputResult, bool) {
+        let command = text.trim();
+        match parse_mcp_prompt_command(command, &self.mcp_prompts) {
+            Ok(Some(McpPromptInvocation {
+                server,
+                name,
+                arguments,
+            })) => {
+                self.history.record_local_submission(command);
+                self.textarea.set_text("");
+                self.app_event_tx.send(AppEvent::CodexOp(Op::GetMcpPrompt {
+                    server,
+                    name,
+                    arguments,
+                }));
+            }
+            Ok(None) => {
+                let command = parse_slash_name(command).map_or(command, |(name, _)| name);
+                self.app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                    history_cell::new_error_event(format!("Unknown MCP prompt: {command}")),
+                )));
+                self.textarea.set_text(&text);
+                self.textarea.set_cursor(text.len());
+            }
+            Err(err) => {
+                self.app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                    history_cell::new_error_event(err.user_message()),
+                )));
+                self.textarea.set_text(&text);
+                self.textarea.set_cursor(text.len());
+            }
+        }
+        (InputResult::None, true)
+    }
+
     /// Synchronize `self.file_search_popup` with the current text in the textarea.
     /// Note this is only called when self.active_popup is NOT Command.
     fn sync_file_search_popup(&mut self) {
@@ -1436,6 +1540,7 @@ impl ChatComposer {
                 .send(AppEvent::StartFileSearch(query.clone()));
         }
 
+        let resources = match_resources(&self.mcp_resources, &query);
         match &mut self.active_popup {
             ActivePopup::File(popup) => {
                 if query.is_empty() {
@@ -1443,6 +1548,7 @@ impl ChatComposer {
                 } else {
                     popup.set_query(&query);
                 }
+                popup.set_resources(resources);
             }
             _ => {
                 let mut popup = FileSearchPopup::new();
@@ -1451,6 +1557,7 @@ impl ChatComposer {
                 } else {
                     popup.set_query(&query);
                 }
+                popup.set_resources(resources);
                 self.active_popup = ActivePopup::File(popup);
             }
         }
@@ -1553,6 +1660,23 @@ impl WidgetRef for ChatComposer {
     }
 }
 
+/// Text for the composer after completing an MCP prompt, with `name=""`
+/// placeholders for its arguments, and the cursor position to use.
+fn mcp_prompt_completion(server: &str, prompt: &McpPrompt) -> (String, usize) {
+    let args: Vec<String> = prompt
+        .arguments
+        .iter()
+        .flatten()
+        .map(|arg| arg.name.clone())
+        .collect();
+    if args.is_empty() {
+        let text = format!("/{MCP_PROMPTS_CMD_PREFIX}:{server}:{} ", prompt.name);
+        let cursor = text.len();
+        return (text, cursor);
+    }
+    mcp_prompt_command_with_arg_placeholders(server, &prompt.name, &args)
+}
+
 fn prompt_selection_action(
     prompt: &CustomPrompt,
     first_line: &str,
@@ -3230,4 +3354,94 @@ mod tests {
         assert_eq!(composer.textarea.text(), "z".repeat(count));
         assert!(composer.pending_pastes.is_empty());
     }
+
+    #[test]
+    fn mcp_prompt_command_requests_prompt_from_server() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+
+        composer.set_mcp_prompts(HashMap::from([(
+            "docs".to_string(),
+            vec![McpPrompt {
+                name: "greeting".to_string(),
+                title: None,
+                description: None,
+                arguments: Some(vec![mcp_types::PromptArgument {
+                    name: "name".to_string(),
+                    title: None,
+                    description: None,
+                    required: Some(true),
+                }]),
+            }],
+        )]));
+
+        composer.textarea.set_text("/mcp:docs:greeting name=Ada");
+
+        let (result, _needs_redraw) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert_eq!(InputResult::None, result);
+        assert!(composer.textarea.is_empty());
+        let mut requested = None;
+        while let Ok(event) = rx.try_recv() {
+            if let AppEvent::CodexOp(op) = event {
+                requested = Some(op);
+            }
+        }
+        match requested {
+            Some(Op::GetMcpPrompt {
+                server,
+                name,
+                arguments,
+            }) => {
+                assert_eq!(server, "docs");
+                assert_eq!(name, "greeting");
+                assert_eq!(
+                    arguments,
+                    HashMap::from([("name".to_string(), "Ada".to_string())])
+                );
+            }
+            other => panic!("expected Op::GetMcpPrompt, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn selecting_mcp_resource_inserts_mention() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+
+        composer.set_mcp_resources(HashMap::from([(
+            "docs".to_string(),
+            vec![McpResource {
+                annotations: None,
+                description: None,
+                mime_type: None,
+                name: "readme".to_string(),
+                size: None,
+                title: None,
+                uri: "memo://readme".to_string(),
+            }],
+        )]));
+
+        type_chars_humanlike(&mut composer, &['@', 'r', 'e', 'a', 'd']);
+        let (result, _needs_redraw) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
+
+        assert_eq!(InputResult::None, result);
+        assert_eq!(composer.textarea.text(), "@docs:memo://readme ");
+    }
 }
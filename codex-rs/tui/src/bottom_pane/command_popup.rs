@@ -12,21 +12,29 @@ use crate::slash_command::SlashCommand;
 use crate::slash_command::built_in_slash_commands;
 use codex_common::fuzzy_match::fuzzy_match;
 use codex_protocol::custom_prompts::CustomPrompt;
+use codex_protocol::custom_prompts::MCP_PROMPTS_CMD_PREFIX;
 use codex_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
+use mcp_types::Prompt as McpPrompt;
+use std::collections::HashMap;
 use std::collections::HashSet;
 
-/// A selectable item in the popup: either a built-in command or a user prompt.
+/// A selectable item in the popup: a built-in command, a user prompt, or a
+/// prompt offered by an MCP server.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(crate) enum CommandItem {
     Builtin(SlashCommand),
     // Index into `prompts`
     UserPrompt(usize),
+    // Index into `mcp_prompts`
+    McpPrompt(usize),
 }
 
 pub(crate) struct CommandPopup {
     command_filter: String,
     builtins: Vec<(&'static str, SlashCommand)>,
     prompts: Vec<CustomPrompt>,
+    // (server, prompt) pairs sorted by server, then prompt name.
+    mcp_prompts: Vec<(String, McpPrompt)>,
     state: ScrollState,
 }
 
@@ -41,6 +49,7 @@ impl CommandPopup {
             command_filter: String::new(),
             builtins,
             prompts,
+            mcp_prompts: Vec::new(),
             state: ScrollState::new(),
         }
     }
@@ -60,6 +69,30 @@ impl CommandPopup {
         self.prompts.get(idx)
     }
 
+    pub(crate) fn set_mcp_prompts(&mut self, mcp_prompts: &HashMap<String, Vec<McpPrompt>>) {
+        let mut flattened: Vec<(String, McpPrompt)> = mcp_prompts
+            .iter()
+            .flat_map(|(server, prompts)| {
+                prompts
+                    .iter()
+                    .map(move |prompt| (server.clone(), prompt.clone()))
+            })
+            .collect();
+        flattened.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.name.cmp(&b.1.name)));
+        self.mcp_prompts = flattened;
+    }
+
+    pub(crate) fn mcp_prompt(&self, idx: usize) -> Option<(&str, &McpPrompt)> {
+        self.mcp_prompts
+            .get(idx)
+            .map(|(server, prompt)| (server.as_str(), prompt))
+    }
+
+    fn mcp_prompt_command(&self, idx: usize) -> String {
+        let (server, prompt) = &self.mcp_prompts[idx];
+        format!("{MCP_PROMPTS_CMD_PREFIX}:{server}:{}", prompt.name)
+    }
+
     /// Update the filter string based on the current composer text. The text
     /// passed in is expected to start with a leading '/'. Everything after the
     /// *first* '/" on the *first* line becomes the active filter that is used
@@ -115,6 +148,10 @@ impl CommandPopup {
             for idx in 0..self.prompts.len() {
                 out.push((CommandItem::UserPrompt(idx), None, 0));
             }
+            // Then MCP prompts, grouped by server.
+            for idx in 0..self.mcp_prompts.len() {
+                out.push((CommandItem::McpPrompt(idx), None, 0));
+            }
             return out;
         }
 
@@ -132,16 +169,23 @@ impl CommandPopup {
                 out.push((CommandItem::UserPrompt(idx), Some(indices), score));
             }
         }
+        for idx in 0..self.mcp_prompts.len() {
+            if let Some((indices, score)) = fuzzy_match(&self.mcp_prompt_command(idx), filter) {
+                out.push((CommandItem::McpPrompt(idx), Some(indices), score));
+            }
+        }
         // When filtering, sort by ascending score and then by name for stability.
         out.sort_by(|a, b| {
             a.2.cmp(&b.2).then_with(|| {
                 let an = match a.0 {
                     CommandItem::Builtin(c) => c.command(),
                     CommandItem::UserPrompt(i) => &self.prompts[i].name,
+                    CommandItem::McpPrompt(i) => &self.mcp_prompts[i].1.name,
                 };
                 let bn = match b.0 {
                     CommandItem::Builtin(c) => c.command(),
                     CommandItem::UserPrompt(i) => &self.prompts[i].name,
+                    CommandItem::McpPrompt(i) => &self.mcp_prompts[i].1.name,
                 };
                 an.cmp(bn)
             })
@@ -168,6 +212,14 @@ impl CommandPopup {
                         format!("/{PROMPTS_CMD_PREFIX}:{}", self.prompts[i].name),
                         "send saved prompt".to_string(),
                     ),
+                    CommandItem::McpPrompt(i) => (
+                        format!("/{}", self.mcp_prompt_command(i)),
+                        self.mcp_prompts[i]
+                            .1
+                            .description
+                            .clone()
+                            .unwrap_or_else(|| "send MCP prompt".to_string()),
+                    ),
                 };
                 GenericDisplayRow {
                     name,
@@ -234,7 +286,7 @@ mod tests {
         let matches = popup.filtered_items();
         let has_init = matches.iter().any(|item| match item {
             CommandItem::Builtin(cmd) => cmd.command() == "init",
-            CommandItem::UserPrompt(_) => false,
+            CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_) => false,
         });
         assert!(
             has_init,
@@ -252,7 +304,9 @@ mod tests {
         let selected = popup.selected_item();
         match selected {
             Some(CommandItem::Builtin(cmd)) => assert_eq!(cmd.command(), "init"),
-            Some(CommandItem::UserPrompt(_)) => panic!("unexpected prompt selected for '/init'"),
+            Some(CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_)) => {
+                panic!("unexpected prompt selected for '/init'")
+            }
             None => panic!("expected a selected command for exact match"),
         }
     }
@@ -264,7 +318,7 @@ mod tests {
         let matches = popup.filtered_items();
         match matches.first() {
             Some(CommandItem::Builtin(cmd)) => assert_eq!(cmd.command(), "model"),
-            Some(CommandItem::UserPrompt(_)) => {
+            Some(CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_)) => {
                 panic!("unexpected prompt ranked before '/model' for '/mo'")
             }
             None => panic!("expected at least one match for '/mo'"),
@@ -322,4 +376,27 @@ mod tests {
             "prompt with builtin name should be ignored"
         );
     }
+
+    #[test]
+    fn mcp_prompts_are_listed_under_server_namespace() {
+        let mut popup = CommandPopup::new(Vec::new());
+        popup.set_mcp_prompts(&HashMap::from([(
+            "docs".to_string(),
+            vec![McpPrompt {
+                name: "greeting".to_string(),
+                title: None,
+                description: Some("Greet someone".to_string()),
+                arguments: None,
+            }],
+        )]));
+        popup.on_composer_text_change("/mcp:docs:gr".to_string());
+
+        match popup.selected_item() {
+            Some(CommandItem::McpPrompt(idx)) => {
+                let (server, prompt) = popup.mcp_prompt(idx).expect("mcp prompt");
+                assert_eq!((server, prompt.name.as_str()), ("docs", "greeting"));
+            }
+            other => panic!("expected the MCP prompt to be selected, got {other:?}"),
+        }
+    }
 }
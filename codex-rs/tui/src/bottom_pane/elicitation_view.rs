@@ -0,0 +1,472 @@
+use codex_core::protocol::ElicitationAction;
+use codex_core::protocol::Op;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyModifiers;
+use mcp_types::ElicitRequestParamsRequestedSchema;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Widget;
+use ratatui::widgets::Wrap;
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::key_hint;
+use crate::render::renderable::Renderable;
+
+use super::CancellationEvent;
+use super::bottom_pane_view::BottomPaneView;
+
+/// Form asking the user for the input an MCP server requested
+/// (`elicitation/create`). Each property of the requested schema becomes one
+/// field.
+pub(crate) struct ElicitationView {
+    id: String,
+    server: String,
+    message: String,
+    fields: Vec<ElicitationField>,
+    selected: usize,
+    error: Option<String>,
+    app_event_tx: AppEventSender,
+    complete: bool,
+}
+
+struct ElicitationField {
+    name: String,
+    label: String,
+    description: Option<String>,
+    kind: FieldKind,
+    required: bool,
+    value: String,
+}
+
+enum FieldKind {
+    String,
+    Number,
+    Integer,
+    /// One of the listed values; `value` is always one of them (or empty when
+    /// the field is optional).
+    Choice(Vec<String>),
+    Boolean,
+}
+
+impl ElicitationView {
+    pub(crate) fn new(
+        id: String,
+        server: String,
+        message: String,
+        schema: ElicitRequestParamsRequestedSchema,
+        app_event_tx: AppEventSender,
+    ) -> Self {
+        Self {
+            id,
+            server,
+            message,
+            fields: fields_from_schema(&schema),
+            selected: 0,
+            error: None,
+            app_event_tx,
+            complete: false,
+        }
+    }
+
+    fn resolve(&mut self, action: ElicitationAction, content: Option<Value>) {
+        self.app_event_tx
+            .send(AppEvent::CodexOp(Op::ResolveElicitation {
+                id: self.id.clone(),
+                action,
+                content,
+            }));
+        self.complete = true;
+    }
+
+    fn submit(&mut self) {
+        match self.content() {
+            Ok(content) => self.resolve(ElicitationAction::Accept, Some(content)),
+            Err(error) => self.error = Some(error),
+        }
+    }
+
+    /// The form values as the JSON object the server asked for.
+    fn content(&self) -> Result<Value, String> {
+        let mut content = Map::new();
+        for field in &self.fields {
+            let raw = field.value.trim();
+            if raw.is_empty() {
+                if field.required {
+                    return Err(format!("{} is required", field.label));
+                }
+                continue;
+            }
+            let value = match &field.kind {
+                FieldKind::String | FieldKind::Choice(_) => Value::String(raw.to_string()),
+                FieldKind::Boolean => Value::Bool(raw == "true"),
+                FieldKind::Integer => raw
+                    .parse::<i64>()
+                    .map(Value::from)
+                    .map_err(|_| format!("{} must be a whole number", field.label))?,
+                FieldKind::Number => raw
+                    .parse::<f64>()
+                    .ok()
+                    .and_then(|number| serde_json::Number::from_f64(number).map(Value::Number))
+                    .ok_or_else(|| format!("{} must be a number", field.label))?,
+            };
+            content.insert(field.name.clone(), value);
+        }
+        Ok(Value::Object(content))
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        if self.fields.is_empty() {
+            return;
+        }
+        let len = self.fields.len() as isize;
+        self.selected = (self.selected as isize + delta).rem_euclid(len) as usize;
+    }
+
+    /// Step through the values of a boolean or choice field.
+    fn cycle_value(&mut self, delta: isize) {
+        let Some(field) = self.fields.get_mut(self.selected) else {
+            return;
+        };
+        let options = field.options();
+        if options.is_empty() {
+            return;
+        }
+        let len = options.len() as isize;
+        let current = options
+            .iter()
+            .position(|option| *option == field.value)
+            .map_or(if delta > 0 { -1 } else { 0 }, |idx| idx as isize);
+        field.value = options[(current + delta).rem_euclid(len) as usize].clone();
+    }
+
+    fn edit_text(&mut self, key_event: KeyEvent) {
+        let Some(field) = self.fields.get_mut(self.selected) else {
+            return;
+        };
+        if !field.options().is_empty() {
+            return;
+        }
+        match key_event.code {
+            KeyCode::Char(c) => field.value.push(c),
+            KeyCode::Backspace => {
+                field.value.pop();
+            }
+            _ => {}
+        }
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let mut lines = vec![
+            Line::from(vec![
+                gutter(),
+                "MCP server ".bold(),
+                self.server.clone().bold().cyan(),
+                " is asking for input".bold(),
+            ]),
+            Line::from(vec![gutter(), self.message.clone().into()]),
+            Line::from(gutter()),
+        ];
+        for (idx, field) in self.fields.iter().enumerate() {
+            let selected = idx == self.selected;
+            let marker = if selected { "› ".cyan() } else { "  ".into() };
+            let mut label = field.label.clone();
+            if field.required {
+                label.push('*');
+            }
+            let value: Span<'static> = if field.options().is_empty() {
+                field.value.clone().into()
+            } else if field.value.is_empty() {
+                "‹ - ›".dim()
+            } else {
+                format!("‹ {} ›", field.value).into()
+            };
+            let label = if selected { label.bold() } else { label.into() };
+            lines.push(Line::from(vec![
+                gutter(),
+                marker,
+                label,
+                ": ".into(),
+                value,
+            ]));
+            if selected && let Some(description) = &field.description {
+                lines.push(Line::from(vec![
+                    gutter(),
+                    "    ".into(),
+                    description.clone().dim(),
+                ]));
+            }
+        }
+        if let Some(error) = &self.error {
+            lines.push(Line::from(vec![gutter(), error.clone().red()]));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            "Press ".into(),
+            key_hint::plain(KeyCode::Enter).into(),
+            " to submit, ".into(),
+            key_hint::ctrl(KeyCode::Char('d')).into(),
+            " to decline or ".into(),
+            key_hint::plain(KeyCode::Esc).into(),
+            " to cancel".into(),
+        ]));
+        lines
+    }
+
+    fn paragraph(&self) -> Paragraph<'static> {
+        Paragraph::new(self.lines()).wrap(Wrap { trim: false })
+    }
+}
+
+impl ElicitationField {
+    fn options(&self) -> Vec<String> {
+        match &self.kind {
+            FieldKind::Choice(options) => options.clone(),
+            FieldKind::Boolean => vec!["true".to_string(), "false".to_string()],
+            FieldKind::String | FieldKind::Number | FieldKind::Integer => Vec::new(),
+        }
+    }
+}
+
+impl BottomPaneView for ElicitationView {
+    fn handle_key_event(&mut self, key_event: KeyEvent) {
+        match key_event {
+            KeyEvent {
+                code: KeyCode::Esc, ..
+            } => {
+                self.on_ctrl_c();
+            }
+            KeyEvent {
+                code: KeyCode::Char('d'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => self.resolve(ElicitationAction::Decline, None),
+            KeyEvent {
+                code: KeyCode::Enter,
+                ..
+            } => self.submit(),
+            KeyEvent {
+                code: KeyCode::Up, ..
+            }
+            | KeyEvent {
+                code: KeyCode::BackTab,
+                ..
+            } => self.move_selection(-1),
+            KeyEvent {
+                code: KeyCode::Down,
+                ..
+            }
+            | KeyEvent {
+                code: KeyCode::Tab, ..
+            } => self.move_selection(1),
+            KeyEvent {
+                code: KeyCode::Left,
+                ..
+            } => self.cycle_value(-1),
+            KeyEvent {
+                code: KeyCode::Right,
+                ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char(' '),
+                ..
+            } if self
+                .fields
+                .get(self.selected)
+                .is_some_and(|field| !field.options().is_empty()) =>
+            {
+                self.cycle_value(1)
+            }
+            KeyEvent {
+                modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+                ..
+            } => self.edit_text(key_event),
+            _ => {}
+        }
+    }
+
+    fn on_ctrl_c(&mut self) -> CancellationEvent {
+        if !self.complete {
+            self.resolve(ElicitationAction::Cancel, None);
+        }
+        CancellationEvent::Handled
+    }
+
+    fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    fn handle_paste(&mut self, pasted: String) -> bool {
+        let Some(field) = self.fields.get_mut(self.selected) else {
+            return false;
+        };
+        if !field.options().is_empty() || pasted.is_empty() {
+            return false;
+        }
+        field.value.push_str(pasted.trim_end_matches('\n'));
+        true
+    }
+}
+
+impl Renderable for ElicitationView {
+    fn desired_height(&self, width: u16) -> u16 {
+        self.paragraph().line_count(width) as u16
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        self.paragraph().render(area, buf);
+    }
+}
+
+/// One field per schema property, in the order the server listed them.
+fn fields_from_schema(schema: &ElicitRequestParamsRequestedSchema) -> Vec<ElicitationField> {
+    let required = schema.required.clone().unwrap_or_default();
+    let Some(properties) = schema.properties.as_object() else {
+        return Vec::new();
+    };
+    properties
+        .iter()
+        .map(|(name, property)| {
+            let text = |key: &str| {
+                property
+                    .get(key)
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+            };
+            let kind = match (
+                property.get("type").and_then(Value::as_str),
+                property.get("enum").and_then(Value::as_array),
+            ) {
+                (_, Some(options)) => FieldKind::Choice(
+                    options
+                        .iter()
+                        .filter_map(Value::as_str)
+                        .map(str::to_string)
+                        .collect(),
+                ),
+                (Some("boolean"), None) => FieldKind::Boolean,
+                (Some("integer"), None) => FieldKind::Integer,
+                (Some("number"), None) => FieldKind::Number,
+                _ => FieldKind::String,
+            };
+            let value = match (&kind, property.get("default")) {
+                (_, Some(Value::String(default))) => default.clone(),
+                (_, Some(Value::Bool(default))) => default.to_string(),
+                (_, Some(Value::Number(default))) => default.to_string(),
+                (FieldKind::Boolean, None) => "false".to_string(),
+                _ => String::new(),
+            };
+            ElicitationField {
+                name: name.clone(),
+                label: text("title").unwrap_or_else(|| name.clone()),
+                description: text("description"),
+                kind,
+                required: required.contains(name),
+                value,
+            }
+        })
+        .collect()
+}
+
+fn gutter() -> Span<'static> {
+    "▌ ".cyan()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    type AppEventReceiver = tokio::sync::mpsc::UnboundedReceiver<AppEvent>;
+
+    fn make_view(properties: Value, required: &[&str]) -> (ElicitationView, AppEventReceiver) {
+        let (tx, rx) = unbounded_channel::<AppEvent>();
+        let view = ElicitationView::new(
+            "elicit".to_string(),
+            "docs".to_string(),
+            "Who are you?".to_string(),
+            ElicitRequestParamsRequestedSchema {
+                properties,
+                required: Some(required.iter().map(ToString::to_string).collect()),
+                r#type: "object".to_string(),
+            },
+            AppEventSender::new(tx),
+        );
+        (view, rx)
+    }
+
+    fn resolved(rx: &mut AppEventReceiver) -> Option<(ElicitationAction, Option<Value>)> {
+        while let Ok(ev) = rx.try_recv() {
+            if let AppEvent::CodexOp(Op::ResolveElicitation {
+                action, content, ..
+            }) = ev
+            {
+                return Some((action, content));
+            }
+        }
+        None
+    }
+
+    fn type_text(view: &mut ElicitationView, text: &str) {
+        for c in text.chars() {
+            view.handle_key_event(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+    }
+
+    #[test]
+    fn enter_submits_typed_values() {
+        let (mut view, mut rx) = make_view(
+            json!({
+                "name": { "type": "string" },
+                "age": { "type": "integer" },
+                "subscribe": { "type": "boolean" },
+            }),
+            &["name"],
+        );
+        type_text(&mut view, "Ada");
+        view.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        type_text(&mut view, "36");
+        view.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        view.handle_key_event(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE));
+        view.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert_eq!(
+            resolved(&mut rx),
+            Some((
+                ElicitationAction::Accept,
+                Some(json!({ "name": "Ada", "age": 36, "subscribe": true })),
+            ))
+        );
+        assert!(view.is_complete());
+    }
+
+    #[test]
+    fn missing_required_field_keeps_the_form_open() {
+        let (mut view, mut rx) = make_view(json!({ "name": { "type": "string" } }), &["name"]);
+        view.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert_eq!(resolved(&mut rx), None);
+        assert!(!view.is_complete());
+        assert_eq!(view.error.as_deref(), Some("name is required"));
+    }
+
+    #[test]
+    fn esc_cancels_and_ctrl_d_declines() {
+        let (mut view, mut rx) = make_view(json!({}), &[]);
+        view.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert_eq!(resolved(&mut rx), Some((ElicitationAction::Cancel, None)));
+
+        let (mut view, mut rx) = make_view(json!({}), &[]);
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL));
+        assert_eq!(resolved(&mut rx), Some((ElicitationAction::Decline, None)));
+    }
+}
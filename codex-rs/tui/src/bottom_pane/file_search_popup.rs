@@ -1,4 +1,6 @@
+use codex_common::fuzzy_match::fuzzy_match;
 use codex_file_search::FileMatch;
+use mcp_types::Resource as McpResource;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::widgets::WidgetRef;
@@ -10,6 +12,49 @@ use super::popup_consts::MAX_POPUP_ROWS;
 use super::scroll_state::ScrollState;
 use super::selection_popup_common::GenericDisplayRow;
 use super::selection_popup_common::render_rows;
+use std::collections::HashMap;
+
+/// An MCP resource offered in the popup above the file matches.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ResourceMatch {
+    /// Mention text without the leading `@`, in the form `server:uri`.
+    pub mention: String,
+    /// Human-readable name reported by the server.
+    pub name: String,
+    pub indices: Option<Vec<usize>>,
+}
+
+/// Returns the resources whose `server:uri` mention fuzzy-matches `query`,
+/// best matches first. An empty query matches every resource.
+pub(crate) fn match_resources(
+    resources: &HashMap<String, Vec<McpResource>>,
+    query: &str,
+) -> Vec<ResourceMatch> {
+    let mut scored: Vec<(ResourceMatch, i32)> = resources
+        .iter()
+        .flat_map(|(server, resources)| {
+            resources.iter().filter_map(move |resource| {
+                let mention = format!("{server}:{}", resource.uri);
+                let (indices, score) = if query.is_empty() {
+                    (None, 0)
+                } else {
+                    let (indices, score) = fuzzy_match(&mention, query)?;
+                    (Some(indices), score)
+                };
+                Some((
+                    ResourceMatch {
+                        mention,
+                        name: resource.name.clone(),
+                        indices,
+                    },
+                    score,
+                ))
+            })
+        })
+        .collect();
+    scored.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.mention.cmp(&b.0.mention)));
+    scored.into_iter().map(|(m, _)| m).collect()
+}
 
 /// Visual state for the file-search popup.
 pub(crate) struct FileSearchPopup {
@@ -22,6 +67,8 @@ pub(crate) struct FileSearchPopup {
     waiting: bool,
     /// Cached matches; paths relative to the search dir.
     matches: Vec<FileMatch>,
+    /// MCP resources matching the current query, listed before `matches`.
+    resources: Vec<ResourceMatch>,
     /// Shared selection/scroll state.
     state: ScrollState,
 }
@@ -33,6 +80,7 @@ impl FileSearchPopup {
             pending_query: String::new(),
             waiting: true,
             matches: Vec::new(),
+            resources: Vec::new(),
             state: ScrollState::new(),
         }
     }
@@ -78,21 +126,33 @@ impl FileSearchPopup {
         self.display_query = query.to_string();
         self.matches = matches;
         self.waiting = false;
-        let len = self.matches.len();
+        let len = self.len();
+        self.state.clamp_selection(len);
+        self.state.ensure_visible(len, len.min(MAX_POPUP_ROWS));
+    }
+
+    /// Replace the MCP resources shown above the file matches.
+    pub(crate) fn set_resources(&mut self, resources: Vec<ResourceMatch>) {
+        self.resources = resources;
+        let len = self.len();
         self.state.clamp_selection(len);
         self.state.ensure_visible(len, len.min(MAX_POPUP_ROWS));
     }
 
+    fn len(&self) -> usize {
+        self.resources.len() + self.matches.len()
+    }
+
     /// Move selection cursor up.
     pub(crate) fn move_up(&mut self) {
-        let len = self.matches.len();
+        let len = self.len();
         self.state.move_up_wrap(len);
         self.state.ensure_visible(len, len.min(MAX_POPUP_ROWS));
     }
 
     /// Move selection cursor down.
     pub(crate) fn move_down(&mut self) {
-        let len = self.matches.len();
+        let len = self.len();
         self.state.move_down_wrap(len);
         self.state.ensure_visible(len, len.min(MAX_POPUP_ROWS));
     }
@@ -100,10 +160,20 @@ impl FileSearchPopup {
     pub(crate) fn selected_match(&self) -> Option<&str> {
         self.state
             .selected_idx
+            .and_then(|idx| idx.checked_sub(self.resources.len()))
             .and_then(|idx| self.matches.get(idx))
             .map(|file_match| file_match.path.as_str())
     }
 
+    /// The `server:uri` mention of the selected MCP resource, if a resource
+    /// rather than a file is selected.
+    pub(crate) fn selected_resource(&self) -> Option<&str> {
+        self.state
+            .selected_idx
+            .and_then(|idx| self.resources.get(idx))
+            .map(|resource| resource.mention.as_str())
+    }
+
     pub(crate) fn calculate_required_height(&self) -> u16 {
         // Row count depends on whether we already have matches. If no matches
         // yet (e.g. initial search or query with no results) reserve a single
@@ -111,19 +181,25 @@ impl FileSearchPopup {
         // up to MAX_RESULTS regardless of the waiting flag so the list
         // remains stable while a newer search is in-flight.
 
-        self.matches.len().clamp(1, MAX_POPUP_ROWS) as u16
+        self.len().clamp(1, MAX_POPUP_ROWS) as u16
     }
 }
 
 impl WidgetRef for &FileSearchPopup {
     fn render_ref(&self, area: Rect, buf: &mut Buffer) {
         // Convert matches to GenericDisplayRow, translating indices to usize at the UI boundary.
-        let rows_all: Vec<GenericDisplayRow> = if self.matches.is_empty() {
-            Vec::new()
-        } else {
-            self.matches
-                .iter()
-                .map(|m| GenericDisplayRow {
+        let rows_all: Vec<GenericDisplayRow> = self
+            .resources
+            .iter()
+            .map(|r| GenericDisplayRow {
+                name: r.mention.clone(),
+                match_indices: r.indices.clone(),
+                is_current: false,
+                display_shortcut: None,
+                description: Some(r.name.clone()),
+            })
+            .chain(self.matches.iter().map(|m| {
+                GenericDisplayRow {
                     name: m.path.clone(),
                     match_indices: m
                         .indices
@@ -132,9 +208,9 @@ impl WidgetRef for &FileSearchPopup {
                     is_current: false,
                     display_shortcut: None,
                     description: None,
-                })
-                .collect()
-        };
+                }
+            }))
+            .collect();
 
         let empty_message = if self.waiting {
             "loading..."
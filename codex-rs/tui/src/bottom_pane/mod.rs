@@ -1,4 +1,5 @@
 //! Bottom pane: shows the ChatComposer or a BottomPaneView, if one is active.
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use crate::app_event_sender::AppEventSender;
@@ -17,11 +18,14 @@ use std::time::Duration;
 mod approval_overlay;
 pub(crate) use approval_overlay::ApprovalOverlay;
 pub(crate) use approval_overlay::ApprovalRequest;
+pub(crate) use approval_overlay::sampling_lines;
 mod bottom_pane_view;
 mod chat_composer;
 mod chat_composer_history;
 mod command_popup;
 pub mod custom_prompt_view;
+mod elicitation_view;
+pub(crate) use elicitation_view::ElicitationView;
 mod file_search_popup;
 mod footer;
 mod list_selection_view;
@@ -42,6 +46,8 @@ pub(crate) enum CancellationEvent {
 pub(crate) use chat_composer::ChatComposer;
 pub(crate) use chat_composer::InputResult;
 use codex_protocol::custom_prompts::CustomPrompt;
+use mcp_types::Prompt as McpPrompt;
+use mcp_types::Resource as McpResource;
 
 use crate::status_indicator_widget::StatusIndicatorWidget;
 pub(crate) use list_selection_view::SelectionAction;
@@ -381,6 +387,15 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    pub(crate) fn set_mcp_prompts(&mut self, prompts: HashMap<String, Vec<McpPrompt>>) {
+        self.composer.set_mcp_prompts(prompts);
+        self.request_redraw();
+    }
+
+    pub(crate) fn set_mcp_resources(&mut self, resources: HashMap<String, Vec<McpResource>>) {
+        self.composer.set_mcp_resources(resources);
+    }
+
     pub(crate) fn composer_is_empty(&self) -> bool {
         self.composer.is_empty()
     }
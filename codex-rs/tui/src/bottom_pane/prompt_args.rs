@@ -1,6 +1,8 @@
 use codex_protocol::custom_prompts::CustomPrompt;
+use codex_protocol::custom_prompts::MCP_PROMPTS_CMD_PREFIX;
 use codex_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
 use lazy_static::lazy_static;
+use mcp_types::Prompt as McpPrompt;
 use regex_lite::Regex;
 use shlex::Shlex;
 use std::collections::HashMap;
@@ -192,6 +194,66 @@ pub fn expand_custom_prompt(
     Ok(Some(expanded))
 }
 
+/// A `/mcp:server:name` command whose arguments have been parsed and checked
+/// against the prompt's declared arguments.
+#[derive(Debug, PartialEq)]
+pub struct McpPromptInvocation {
+    pub server: String,
+    pub name: String,
+    pub arguments: HashMap<String, String>,
+}
+
+/// Parses a message of the form `/mcp:server:name [key=value] …` against the
+/// prompts advertised by MCP servers.
+///
+/// Returns `Ok(None)` when the text is not an MCP prompt command or names a
+/// prompt that no server offers. Every argument the prompt marks as required
+/// must be supplied.
+pub fn parse_mcp_prompt_command(
+    text: &str,
+    mcp_prompts: &HashMap<String, Vec<McpPrompt>>,
+) -> Result<Option<McpPromptInvocation>, PromptExpansionError> {
+    let Some((command, rest)) = parse_slash_name(text) else {
+        return Ok(None);
+    };
+    let Some((server, name)) = command
+        .strip_prefix(&format!("{MCP_PROMPTS_CMD_PREFIX}:"))
+        .and_then(|qualified| qualified.split_once(':'))
+    else {
+        return Ok(None);
+    };
+    let Some(prompt) = mcp_prompts
+        .get(server)
+        .and_then(|prompts| prompts.iter().find(|p| p.name == name))
+    else {
+        return Ok(None);
+    };
+
+    let arguments = parse_prompt_inputs(rest).map_err(|error| PromptExpansionError::Args {
+        command: format!("/{command}"),
+        error,
+    })?;
+    let missing: Vec<String> = prompt
+        .arguments
+        .iter()
+        .flatten()
+        .filter(|arg| arg.required == Some(true) && !arguments.contains_key(&arg.name))
+        .map(|arg| arg.name.clone())
+        .collect();
+    if !missing.is_empty() {
+        return Err(PromptExpansionError::MissingArgs {
+            command: format!("/{command}"),
+            missing,
+        });
+    }
+
+    Ok(Some(McpPromptInvocation {
+        server: server.to_string(),
+        name: name.to_string(),
+        arguments,
+    }))
+}
+
 /// Detect whether `content` contains numeric placeholders ($1..$9) or `$ARGUMENTS`.
 pub fn prompt_has_numeric_placeholders(content: &str) -> bool {
     if content.contains("$ARGUMENTS") {
@@ -299,7 +361,20 @@ pub fn expand_numeric_placeholders(content: &str, args: &[String]) -> String {
 /// Constructs a command text for a custom prompt with arguments.
 /// Returns the text and the cursor position (inside the first double quote).
 pub fn prompt_command_with_arg_placeholders(name: &str, args: &[String]) -> (String, usize) {
-    let mut text = format!("/{PROMPTS_CMD_PREFIX}:{name}");
+    command_with_arg_placeholders(format!("/{PROMPTS_CMD_PREFIX}:{name}"), args)
+}
+
+/// Constructs a command text for an MCP prompt with arguments.
+/// Returns the text and the cursor position (inside the first double quote).
+pub fn mcp_prompt_command_with_arg_placeholders(
+    server: &str,
+    name: &str,
+    args: &[String],
+) -> (String, usize) {
+    command_with_arg_placeholders(format!("/{MCP_PROMPTS_CMD_PREFIX}:{server}:{name}"), args)
+}
+
+fn command_with_arg_placeholders(mut text: String, args: &[String]) -> (String, usize) {
     let mut cursor: usize = text.len();
     for (i, arg) in args.iter().enumerate() {
         text.push_str(format!(" {arg}=\"\"").as_str());
@@ -403,4 +478,57 @@ mod tests {
         let out = expand_custom_prompt("/prompts:my-prompt", &prompts).unwrap();
         assert_eq!(out, Some("literal $$USER".to_string()));
     }
+
+    fn greeting_prompts() -> HashMap<String, Vec<McpPrompt>> {
+        HashMap::from([(
+            "docs".to_string(),
+            vec![McpPrompt {
+                name: "greeting".to_string(),
+                title: None,
+                description: None,
+                arguments: Some(vec![
+                    mcp_types::PromptArgument {
+                        name: "name".to_string(),
+                        title: None,
+                        description: None,
+                        required: Some(true),
+                    },
+                    mcp_types::PromptArgument {
+                        name: "tone".to_string(),
+                        title: None,
+                        description: None,
+                        required: None,
+                    },
+                ]),
+            }],
+        )])
+    }
+
+    #[test]
+    fn parses_mcp_prompt_command() {
+        let out =
+            parse_mcp_prompt_command("/mcp:docs:greeting name=\"Ada L\"", &greeting_prompts())
+                .unwrap();
+        assert_eq!(
+            out,
+            Some(McpPromptInvocation {
+                server: "docs".to_string(),
+                name: "greeting".to_string(),
+                arguments: HashMap::from([("name".to_string(), "Ada L".to_string())]),
+            })
+        );
+        assert_eq!(
+            parse_mcp_prompt_command("/mcp:docs:unknown", &greeting_prompts()).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn mcp_prompt_missing_required_args_reports_error() {
+        let err = parse_mcp_prompt_command("/mcp:docs:greeting tone=warm", &greeting_prompts())
+            .unwrap_err()
+            .user_message();
+        assert!(err.contains("/mcp:docs:greeting"));
+        assert!(err.contains("name"));
+    }
 }
@@ -3,6 +3,8 @@ use std::collections::VecDeque;
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use codex_core::approval_rules::ApprovalRules;
+use codex_core::approval_rules::project_for_cwd;
 use codex_core::config::Config;
 use codex_core::config_types::Notifications;
 use codex_core::git_info::current_branch_name;
@@ -13,21 +15,30 @@ use codex_core::protocol::AgentReasoningDeltaEvent;
 use codex_core::protocol::AgentReasoningEvent;
 use codex_core::protocol::AgentReasoningRawContentDeltaEvent;
 use codex_core::protocol::AgentReasoningRawContentEvent;
+use codex_core::protocol::AgentRunBeginEvent;
+use codex_core::protocol::AgentRunEndEvent;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
 use codex_core::protocol::BackgroundEventEvent;
+use codex_core::protocol::BudgetRemaining;
+use codex_core::protocol::ElicitationRequestEvent;
 use codex_core::protocol::ErrorEvent;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::ExecApprovalRequestEvent;
+use codex_core::protocol::ExecApprovalRuleAppliedEvent;
 use codex_core::protocol::ExecCommandBeginEvent;
 use codex_core::protocol::ExecCommandEndEvent;
 use codex_core::protocol::ExitedReviewModeEvent;
+use codex_core::protocol::GetMcpPromptResponseEvent;
 use codex_core::protocol::InputItem;
 use codex_core::protocol::InputMessageKind;
 use codex_core::protocol::ListCustomPromptsResponseEvent;
+use codex_core::protocol::McpListResourcesResponseEvent;
 use codex_core::protocol::McpListToolsResponseEvent;
+use codex_core::protocol::McpSamplingRequestEvent;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
+use codex_core::protocol::NetworkAccessBlockedEvent;
 use codex_core::protocol::Op;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::RateLimitSnapshot;
@@ -48,6 +59,7 @@ use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
 use crossterm::event::KeyModifiers;
+use mcp_types::ContentBlock;
 use rand::Rng;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Constraint;
@@ -64,6 +76,7 @@ use crate::bottom_pane::ApprovalRequest;
 use crate::bottom_pane::BottomPane;
 use crate::bottom_pane::BottomPaneParams;
 use crate::bottom_pane::CancellationEvent;
+use crate::bottom_pane::ElicitationView;
 use crate::bottom_pane::InputResult;
 use crate::bottom_pane::SelectionAction;
 use crate::bottom_pane::SelectionItem;
@@ -229,6 +242,7 @@ pub(crate) struct ChatWidget {
     initial_user_message: Option<UserMessage>,
     token_info: Option<TokenUsageInfo>,
     rate_limit_snapshot: Option<RateLimitSnapshotDisplay>,
+    budget_remaining: Option<BudgetRemaining>,
     rate_limit_warnings: RateLimitWarningState,
     // Stream lifecycle controller
     stream_controller: Option<StreamController>,
@@ -321,6 +335,9 @@ impl ChatWidget {
         }
         // Ask codex-core to enumerate custom prompts for this session.
         self.submit_op(Op::ListCustomPrompts);
+        if !self.config.mcp_servers.is_empty() {
+            self.submit_op(Op::ListMcpResources);
+        }
         if let Some(user_message) = self.initial_user_message.take() {
             self.submit_user_message(user_message);
         }
@@ -531,6 +548,32 @@ impl ChatWidget {
         );
     }
 
+    /// MCP servers call back while one of their tools runs, so there is no
+    /// stream to defer behind.
+    fn on_mcp_sampling_request(&mut self, id: String, ev: McpSamplingRequestEvent) {
+        self.flush_answer_stream_with_separator();
+        self.bottom_pane
+            .push_approval_request(ApprovalRequest::McpSampling {
+                id,
+                server: ev.server,
+                system_prompt: ev.system_prompt,
+                messages: ev.messages,
+            });
+        self.request_redraw();
+    }
+
+    fn on_elicitation_request(&mut self, id: String, ev: ElicitationRequestEvent) {
+        self.flush_answer_stream_with_separator();
+        self.bottom_pane.show_view(Box::new(ElicitationView::new(
+            id,
+            ev.server,
+            ev.message,
+            ev.requested_schema,
+            self.app_event_tx.clone(),
+        )));
+        self.request_redraw();
+    }
+
     fn on_exec_command_begin(&mut self, ev: ExecCommandBeginEvent) {
         self.flush_answer_stream_with_separator();
         let ev2 = ev.clone();
@@ -616,6 +659,44 @@ impl ChatWidget {
         debug!("TurnDiffEvent: {unified_diff}");
     }
 
+    fn on_exec_approval_rule_applied(&mut self, ev: ExecApprovalRuleAppliedEvent) {
+        self.flush_answer_stream_with_separator();
+        self.add_to_history(history_cell::new_approval_rule_applied(
+            &ev.command,
+            ev.pattern,
+        ));
+        self.request_redraw();
+    }
+
+    fn on_network_access_blocked(&mut self, ev: NetworkAccessBlockedEvent) {
+        self.flush_answer_stream_with_separator();
+        self.add_to_history(history_cell::new_network_access_blocked(&ev.host, ev.port));
+        self.request_redraw();
+    }
+
+    fn on_agent_run_begin(&mut self, ev: AgentRunBeginEvent) {
+        self.bottom_pane
+            .update_status_header(format!("Running /{}", ev.command));
+        self.request_redraw();
+    }
+
+    fn on_agent_run_end(&mut self, ev: AgentRunEndEvent) {
+        self.flush_answer_stream_with_separator();
+        match ev.result {
+            Ok(result) => self.add_to_history(history_cell::new_agent_run_result(
+                ev.command,
+                ev.duration,
+                result,
+                &self.config,
+            )),
+            Err(message) => self.add_to_history(history_cell::new_error_event(format!(
+                "/{} failed: {message}",
+                ev.command
+            ))),
+        }
+        self.request_redraw();
+    }
+
     fn on_background_event(&mut self, message: String) {
         debug!("BackgroundEvent: {message}");
     }
@@ -725,12 +806,19 @@ impl ChatWidget {
             .as_mut()
             .and_then(|c| c.as_any_mut().downcast_mut::<ExecCell>())
         {
+            let mut stderr = ev.stderr.clone();
+            if let Some(limit) = ev.limit_exceeded {
+                if !stderr.is_empty() && !stderr.ends_with('\n') {
+                    stderr.push('\n');
+                }
+                stderr.push_str(&format!("killed after exceeding the sandbox {limit} limit"));
+            }
             cell.complete_call(
                 &ev.call_id,
                 CommandOutput {
                     exit_code: ev.exit_code,
                     stdout: ev.stdout.clone(),
-                    stderr: ev.stderr.clone(),
+                    stderr,
                     formatted_output: ev.formatted_output.clone(),
                 },
                 ev.duration,
@@ -921,6 +1009,7 @@ impl ChatWidget {
             ),
             token_info: None,
             rate_limit_snapshot: None,
+            budget_remaining: None,
             rate_limit_warnings: RateLimitWarningState::default(),
             stream_controller: None,
             running_commands: HashMap::new(),
@@ -984,6 +1073,7 @@ impl ChatWidget {
             ),
             token_info: None,
             rate_limit_snapshot: None,
+            budget_remaining: None,
             rate_limit_warnings: RateLimitWarningState::default(),
             stream_controller: None,
             running_commands: HashMap::new(),
@@ -1162,6 +1252,9 @@ impl ChatWidget {
             SlashCommand::Mcp => {
                 self.add_mcp_output();
             }
+            SlashCommand::McpReload => {
+                self.submit_op(Op::ReloadMcpServers);
+            }
             #[cfg(debug_assertions)]
             SlashCommand::TestApproval => {
                 use codex_core::protocol::EventMsg;
@@ -1394,6 +1487,7 @@ impl ChatWidget {
             EventMsg::TokenCount(ev) => {
                 self.set_token_info(ev.info);
                 self.on_rate_limit_snapshot(ev.rate_limits);
+                self.budget_remaining = ev.budget;
             }
             EventMsg::Error(ErrorEvent { message }) => self.on_error(message),
             EventMsg::TurnAborted(ev) => match ev.reason {
@@ -1406,6 +1500,8 @@ impl ChatWidget {
                 TurnAbortReason::ReviewEnded => {
                     self.on_interrupted_turn(ev.reason);
                 }
+                // The `Error` event sent just before says which budget ran out.
+                TurnAbortReason::BudgetExceeded => self.finalize_turn(),
             },
             EventMsg::PlanUpdate(update) => self.on_plan_update(update),
             EventMsg::ExecApprovalRequest(ev) => {
@@ -1415,11 +1511,21 @@ impl ChatWidget {
             EventMsg::ApplyPatchApprovalRequest(ev) => {
                 self.on_apply_patch_approval_request(id.unwrap_or_default(), ev)
             }
+            EventMsg::McpSamplingRequest(ev) => {
+                self.on_mcp_sampling_request(id.unwrap_or_default(), ev)
+            }
+            EventMsg::ElicitationRequest(ev) => {
+                self.on_elicitation_request(id.unwrap_or_default(), ev)
+            }
+            EventMsg::ExecApprovalRuleApplied(ev) => self.on_exec_approval_rule_applied(ev),
+            EventMsg::AgentRunBegin(ev) => self.on_agent_run_begin(ev),
+            EventMsg::AgentRunEnd(ev) => self.on_agent_run_end(ev),
             EventMsg::ExecCommandBegin(ev) => self.on_exec_command_begin(ev),
             EventMsg::ExecCommandOutputDelta(delta) => self.on_exec_command_output_delta(delta),
             EventMsg::PatchApplyBegin(ev) => self.on_patch_apply_begin(ev),
             EventMsg::PatchApplyEnd(ev) => self.on_patch_apply_end(ev),
             EventMsg::ExecCommandEnd(ev) => self.on_exec_command_end(ev),
+            EventMsg::NetworkAccessBlocked(ev) => self.on_network_access_blocked(ev),
             EventMsg::ViewImageToolCall(ev) => self.on_view_image_tool_call(ev),
             EventMsg::McpToolCallBegin(ev) => self.on_mcp_tool_call_begin(ev),
             EventMsg::McpToolCallEnd(ev) => self.on_mcp_tool_call_end(ev),
@@ -1428,6 +1534,8 @@ impl ChatWidget {
             EventMsg::GetHistoryEntryResponse(ev) => self.on_get_history_entry_response(ev),
             EventMsg::McpListToolsResponse(ev) => self.on_list_mcp_tools(ev),
             EventMsg::ListCustomPromptsResponse(ev) => self.on_list_custom_prompts(ev),
+            EventMsg::McpListResourcesResponse(ev) => self.on_list_mcp_resources(ev),
+            EventMsg::GetMcpPromptResponse(ev) => self.on_get_mcp_prompt(ev),
             EventMsg::ShutdownComplete => self.on_shutdown_complete(),
             EventMsg::TurnDiff(TurnDiffEvent { unified_diff }) => self.on_turn_diff(unified_diff),
             EventMsg::BackgroundEvent(BackgroundEventEvent { message }) => {
@@ -1588,6 +1696,7 @@ impl ChatWidget {
             context_usage,
             &self.conversation_id,
             self.rate_limit_snapshot.as_ref(),
+            self.budget_remaining.as_ref(),
         ));
     }
 
@@ -1802,6 +1911,16 @@ impl ChatWidget {
             });
         }
 
+        items.push(SelectionItem {
+            name: "Saved approval rules".to_string(),
+            description: Some(
+                "Review or revoke commands always allowed in this project".to_string(),
+            ),
+            actions: vec![Box::new(|tx| tx.send(AppEvent::OpenApprovalRulesPopup))],
+            dismiss_on_select: true,
+            ..Default::default()
+        });
+
         self.bottom_pane.show_selection_view(SelectionViewParams {
             title: Some("Select Approval Mode".to_string()),
             footer_hint: Some(standard_popup_hint_line()),
@@ -1810,6 +1929,73 @@ impl ChatWidget {
         });
     }
 
+    /// Open a popup listing the approval rules saved for the current
+    /// project. Selecting a rule revokes it.
+    pub(crate) fn open_approval_rules_popup(&mut self) {
+        let rules = match ApprovalRules::load(&self.config.codex_home) {
+            Ok(rules) => rules,
+            Err(err) => {
+                self.add_error_message(format!("Failed to load approval rules: {err}"));
+                return;
+            }
+        };
+        let project = project_for_cwd(&self.config.cwd);
+        let now = chrono::Utc::now();
+        let items: Vec<SelectionItem> = rules
+            .for_project(&project)
+            .map(|rule| {
+                let description = match rule.expires_at {
+                    Some(expires_at) if expires_at <= now => "expired".to_string(),
+                    Some(expires_at) => format!(
+                        "expires {}",
+                        expires_at
+                            .with_timezone(&chrono::Local)
+                            .format("%Y-%m-%d %H:%M")
+                    ),
+                    None => "never expires".to_string(),
+                };
+                let pattern = rule.pattern.clone();
+                let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                    tx.send(AppEvent::RevokeApprovalRule {
+                        pattern: pattern.clone(),
+                    });
+                })];
+                SelectionItem {
+                    name: rule.pattern.clone(),
+                    description: Some(description),
+                    actions,
+                    dismiss_on_select: true,
+                    search_value: Some(rule.pattern.clone()),
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        if items.is_empty() {
+            self.add_info_message(
+                "No approval rules saved for this project".to_string(),
+                Some(
+                    "Choose \"always allow this command in this project\" when approving a command"
+                        .to_string(),
+                ),
+            );
+            return;
+        }
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Approval Rules".to_string()),
+            subtitle: Some(format!(
+                "Commands always allowed in {}. Select a rule to revoke it.",
+                project.display()
+            )),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            is_searchable: true,
+            search_placeholder: Some("Type to search rules".to_string()),
+            ..Default::default()
+        });
+    }
+
     /// Set the approval policy in the widget's config copy.
     pub(crate) fn set_approval_policy(&mut self, policy: AskForApproval) {
         self.config.approval_policy = policy;
@@ -1842,11 +2028,9 @@ impl ChatWidget {
     }
 
     pub(crate) fn add_mcp_output(&mut self) {
-        if self.config.mcp_servers.is_empty() {
-            self.add_to_history(history_cell::empty_mcp_output());
-        } else {
-            self.submit_op(Op::ListMcpTools);
-        }
+        // Ask even without configured servers: `/mcp-reload` may have started
+        // some since this session's config was loaded.
+        self.submit_op(Op::ListMcpTools);
     }
 
     /// Forward file-search results to the bottom pane.
@@ -1906,7 +2090,15 @@ impl ChatWidget {
     }
 
     fn on_list_mcp_tools(&mut self, ev: McpListToolsResponseEvent) {
-        self.add_to_history(history_cell::new_mcp_tools_output(&self.config, ev.tools));
+        if ev.tools.is_empty() && ev.server_statuses.is_empty() {
+            self.add_to_history(history_cell::empty_mcp_output());
+        } else {
+            self.add_to_history(history_cell::new_mcp_tools_output(
+                &self.config,
+                ev.tools,
+                ev.server_statuses,
+            ));
+        }
     }
 
     fn on_list_custom_prompts(&mut self, ev: ListCustomPromptsResponseEvent) {
@@ -1914,6 +2106,49 @@ impl ChatWidget {
         debug!("received {len} custom prompts");
         // Forward to bottom pane so the slash popup can show them now.
         self.bottom_pane.set_custom_prompts(ev.custom_prompts);
+        self.bottom_pane.set_mcp_prompts(ev.mcp_prompts);
+    }
+
+    fn on_list_mcp_resources(&mut self, ev: McpListResourcesResponseEvent) {
+        // Offered as `@server:uri` mentions in the file-search popup.
+        self.bottom_pane.set_mcp_resources(ev.resources);
+    }
+
+    fn on_get_mcp_prompt(&mut self, ev: GetMcpPromptResponseEvent) {
+        let GetMcpPromptResponseEvent {
+            server,
+            name,
+            result,
+        } = ev;
+        let text = match result {
+            Ok(result) => result
+                .messages
+                .into_iter()
+                .filter_map(|message| match message.content {
+                    ContentBlock::TextContent(text) => Some(text.text),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join("\n\n"),
+            Err(err) => {
+                self.add_error_message(format!("Failed to get MCP prompt {server}:{name}: {err}"));
+                return;
+            }
+        };
+        if text.is_empty() {
+            self.add_error_message(format!("MCP prompt {server}:{name} returned no text"));
+            return;
+        }
+        let user_message = UserMessage {
+            text,
+            image_paths: Vec::new(),
+        };
+        if self.bottom_pane.is_task_running() {
+            self.queued_user_messages.push_back(user_message);
+            self.refresh_queued_user_messages();
+        } else {
+            self.submit_user_message(user_message);
+        }
     }
 
     pub(crate) fn open_review_popup(&mut self) {
@@ -269,6 +269,7 @@ fn make_chatwidget_manual() -> (
         initial_user_message: None,
         token_info: None,
         rate_limit_snapshot: None,
+        budget_remaining: None,
         rate_limit_warnings: RateLimitWarningState::default(),
         stream_controller: None,
         running_commands: HashMap::new(),
@@ -534,6 +535,7 @@ fn end_exec(chat: &mut ChatWidget, call_id: &str, stdout: &str, stderr: &str, ex
             exit_code,
             duration: std::time::Duration::from_millis(5),
             formatted_output: aggregated,
+            limit_exceeded: None,
         }),
     });
 }
@@ -674,6 +676,40 @@ fn streaming_final_answer_keeps_task_running_state() {
     assert!(chat.bottom_pane.ctrl_c_quit_hint_visible());
 }
 
+#[test]
+fn mcp_prompt_response_is_submitted_as_user_message() {
+    let (mut chat, _rx, mut op_rx) = make_chatwidget_manual();
+
+    chat.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::GetMcpPromptResponse(GetMcpPromptResponseEvent {
+            server: "docs".to_string(),
+            name: "greeting".to_string(),
+            result: Ok(mcp_types::GetPromptResult {
+                description: None,
+                messages: vec![mcp_types::PromptMessage {
+                    content: ContentBlock::TextContent(mcp_types::TextContent {
+                        annotations: None,
+                        text: "Say hello to Ada.".to_string(),
+                        r#type: "text".to_string(),
+                    }),
+                    role: mcp_types::Role::User,
+                }],
+            }),
+        }),
+    });
+
+    match op_rx.try_recv() {
+        Ok(Op::UserInput { items }) => assert_eq!(
+            items,
+            vec![InputItem::Text {
+                text: "Say hello to Ada.".to_string()
+            }]
+        ),
+        other => panic!("expected Op::UserInput, got {other:?}"),
+    }
+}
+
 #[test]
 fn exec_history_cell_shows_working_then_completed() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual();
@@ -2246,6 +2282,7 @@ fn chatwidget_exec_and_status_layout_vt100_snapshot() {
             exit_code: 0,
             duration: std::time::Duration::from_millis(16000),
             formatted_output: String::new(),
+            limit_exceeded: None,
         }),
     });
     chat.handle_codex_event(Event {
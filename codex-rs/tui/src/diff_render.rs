@@ -14,6 +14,8 @@ use std::path::PathBuf;
 
 use crate::exec_command::relativize_to_home;
 use crate::render::Insets;
+use crate::render::highlight::highlight_code_to_lines;
+use crate::render::language::Language;
 use crate::render::line_utils::prefix_lines;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::InsetRenderable;
@@ -42,13 +44,33 @@ impl DiffSummary {
 impl Renderable for FileChange {
     fn render(&self, area: Rect, buf: &mut Buffer) {
         let mut lines = vec![];
-        render_change(self, &mut lines, area.width as usize);
+        render_change(self, None, &mut lines, area.width as usize);
         Paragraph::new(lines).render(area, buf);
     }
 
     fn desired_height(&self, width: u16) -> u16 {
         let mut lines = vec![];
-        render_change(self, &mut lines, width as usize);
+        render_change(self, None, &mut lines, width as usize);
+        lines.len() as u16
+    }
+}
+
+/// A file change rendered with syntax highlighting for its file's language.
+struct HighlightedChange {
+    change: FileChange,
+    language: Option<Language>,
+}
+
+impl Renderable for HighlightedChange {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let mut lines = vec![];
+        render_change(&self.change, self.language, &mut lines, area.width as usize);
+        Paragraph::new(lines).render(area, buf);
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        let mut lines = vec![];
+        render_change(&self.change, self.language, &mut lines, width as usize);
         lines.len() as u16
     }
 }
@@ -66,8 +88,12 @@ impl From<DiffSummary> for Box<dyn Renderable> {
             path.extend(render_line_count_summary(row.added, row.removed));
             rows.push(Box::new(path));
             rows.push(Box::new(RtLine::from("")));
+            let language = row.language();
             rows.push(Box::new(InsetRenderable::new(
-                row.change,
+                HighlightedChange {
+                    change: row.change,
+                    language,
+                },
                 Insets::tlbr(0, 2, 0, 0),
             )));
         }
@@ -96,6 +122,14 @@ struct Row {
     change: FileChange,
 }
 
+impl Row {
+    /// Language used to highlight the row's lines, taken from the destination
+    /// path when the file is moved.
+    fn language(&self) -> Option<Language> {
+        Language::from_path(self.move_path.as_deref().unwrap_or(&self.path))
+    }
+}
+
 fn collect_rows(changes: &HashMap<PathBuf, FileChange>) -> Vec<Row> {
     let mut rows: Vec<Row> = Vec::new();
     for (path, change) in changes.iter() {
@@ -186,14 +220,19 @@ fn render_changes_block(rows: Vec<Row>, wrap_cols: usize, cwd: &Path) -> Vec<RtL
         }
 
         let mut lines = vec![];
-        render_change(&r.change, &mut lines, wrap_cols - 4);
+        render_change(&r.change, r.language(), &mut lines, wrap_cols - 4);
         out.extend(prefix_lines(lines, "    ".into(), "    ".into()));
     }
 
     out
 }
 
-fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usize) {
+fn render_change(
+    change: &FileChange,
+    language: Option<Language>,
+    out: &mut Vec<RtLine<'static>>,
+    width: usize,
+) {
     match change {
         FileChange::Add { content } => {
             let line_number_width = line_number_width(content.lines().count());
@@ -202,6 +241,7 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                     i + 1,
                     DiffLineType::Insert,
                     raw,
+                    language,
                     width,
                     line_number_width,
                 ));
@@ -214,6 +254,7 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                     i + 1,
                     DiffLineType::Delete,
                     raw,
+                    language,
                     width,
                     line_number_width,
                 ));
@@ -263,6 +304,7 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                                     new_ln,
                                     DiffLineType::Insert,
                                     s,
+                                    language,
                                     width,
                                     line_number_width,
                                 ));
@@ -274,6 +316,7 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                                     old_ln,
                                     DiffLineType::Delete,
                                     s,
+                                    language,
                                     width,
                                     line_number_width,
                                 ));
@@ -285,6 +328,7 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                                     new_ln,
                                     DiffLineType::Context,
                                     s,
+                                    language,
                                     width,
                                     line_number_width,
                                 ));
@@ -335,11 +379,11 @@ fn push_wrapped_diff_line(
     line_number: usize,
     kind: DiffLineType,
     text: &str,
+    language: Option<Language>,
     width: usize,
     line_number_width: usize,
 ) -> Vec<RtLine<'static>> {
     let ln_str = line_number.to_string();
-    let mut remaining_text: &str = text;
 
     // Reserve a fixed number of spaces (equal to the widest line number plus a
     // trailing spacer) so the sign column stays aligned across the diff block.
@@ -353,45 +397,84 @@ fn push_wrapped_diff_line(
         DiffLineType::Context => (' ', style_context()),
     };
     let mut lines: Vec<RtLine<'static>> = Vec::new();
+    // Context lines take the token colors. Added and removed lines keep their
+    // green/red and only pick up the token modifiers (e.g. italic comments),
+    // so the highlighting never hides which side of the diff a line is on.
+    let token_style = |style: Style| match kind {
+        DiffLineType::Context => line_style.patch(style),
+        DiffLineType::Insert | DiffLineType::Delete => line_style.add_modifier(style.add_modifier),
+    };
+    let mut remaining: Vec<RtSpan<'static>> = match language {
+        Some(language) => highlight_code_to_lines(text, language)
+            .into_iter()
+            .next()
+            .map(|line| line.spans)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|span| RtSpan::styled(span.content, token_style(span.style)))
+            .collect(),
+        None => vec![RtSpan::styled(text.to_string(), line_style)],
+    };
 
     loop {
         // Fit the content for the current terminal row:
         // compute how many columns are available after the prefix, then split
-        // at a UTF-8 character boundary so this row's chunk fits exactly.
+        // at a character boundary so this row's chunk fits exactly.
         let available_content_cols = width.saturating_sub(prefix_cols + 1).max(1);
-        let split_at_byte_index = remaining_text
-            .char_indices()
-            .nth(available_content_cols)
-            .map(|(i, _)| i)
-            .unwrap_or_else(|| remaining_text.len());
-        let (chunk, rest) = remaining_text.split_at(split_at_byte_index);
-        remaining_text = rest;
+        let rest = split_spans_at(&mut remaining, available_content_cols);
+        let chunk = std::mem::replace(&mut remaining, rest);
 
+        let mut spans = Vec::with_capacity(chunk.len() + 2);
         if first {
             // Build gutter (right-aligned line number plus spacer) as a dimmed span
             let gutter = format!("{ln_str:>gutter_width$} ");
-            // Content with a sign ('+'/'-'/' ') styled per diff kind
-            let content = format!("{sign_char}{chunk}");
-            lines.push(RtLine::from(vec![
-                RtSpan::styled(gutter, style_gutter()),
-                RtSpan::styled(content, line_style),
-            ]));
+            spans.push(RtSpan::styled(gutter, style_gutter()));
+            // Sign ('+'/'-'/' ') styled per diff kind
+            spans.push(RtSpan::styled(sign_char.to_string(), line_style));
             first = false;
         } else {
             // Continuation lines keep a space for the sign column so content aligns
             let gutter = format!("{:gutter_width$}  ", "");
-            lines.push(RtLine::from(vec![
-                RtSpan::styled(gutter, style_gutter()),
-                RtSpan::styled(chunk.to_string(), line_style),
-            ]));
+            spans.push(RtSpan::styled(gutter, style_gutter()));
         }
-        if remaining_text.is_empty() {
+        spans.extend(chunk);
+        lines.push(RtLine::from(spans));
+        if remaining.is_empty() {
             break;
         }
     }
     lines
 }
 
+/// Truncates `spans` to their first `max_chars` characters and returns the
+/// remainder, splitting a span in two when the boundary falls inside it.
+fn split_spans_at(spans: &mut Vec<RtSpan<'static>>, max_chars: usize) -> Vec<RtSpan<'static>> {
+    let mut taken = 0;
+    let Some(idx) = spans.iter().position(|span| {
+        let len = span.content.chars().count();
+        if taken + len > max_chars {
+            return true;
+        }
+        taken += len;
+        false
+    }) else {
+        return Vec::new();
+    };
+    let split_at = spans[idx]
+        .content
+        .char_indices()
+        .nth(max_chars - taken)
+        .map(|(i, _)| i)
+        .unwrap_or_else(|| spans[idx].content.len());
+    let mut rest = spans.split_off(idx);
+    let head = rest[0].content[..split_at].to_string();
+    if !head.is_empty() {
+        spans.push(RtSpan::styled(head, rest[0].style));
+    }
+    rest[0].content = rest[0].content[split_at..].to_string().into();
+    rest
+}
+
 fn line_number_width(max_line_number: usize) -> usize {
     if max_line_number == 0 {
         1
@@ -419,6 +502,7 @@ fn style_del() -> Style {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::render::highlight::HighlightTheme;
     use insta::assert_snapshot;
     use ratatui::Terminal;
     use ratatui::backend::TestBackend;
@@ -465,13 +549,75 @@ mod tests {
         let long_line = "this is a very long line that should wrap across multiple terminal columns and continue";
 
         // Call the wrapping function directly so we can precisely control the width
-        let lines =
-            push_wrapped_diff_line(1, DiffLineType::Insert, long_line, 80, line_number_width(1));
+        let lines = push_wrapped_diff_line(
+            1,
+            DiffLineType::Insert,
+            long_line,
+            None,
+            80,
+            line_number_width(1),
+        );
 
         // Render into a small terminal to capture the visual layout
         snapshot_lines("wrap_behavior_insert", lines, 90, 8);
     }
 
+    #[test]
+    fn highlighted_diff_line_wraps_styled_spans() {
+        let lines = push_wrapped_diff_line(
+            1,
+            DiffLineType::Insert,
+            "fn main() { let value = 1; }",
+            Some(Language::Rust),
+            16,
+            line_number_width(1),
+        );
+
+        let text: Vec<String> = lines
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert_eq!(text, vec!["1 +fn main() { l", "   et value = 1;", "    }"]);
+        let keyword = lines[0]
+            .spans
+            .iter()
+            .find(|span| span.content == "fn")
+            .expect("keyword span");
+        assert_eq!(
+            keyword.style,
+            style_add().add_modifier(HighlightTheme::current().keyword.add_modifier)
+        );
+    }
+
+    #[test]
+    fn highlighted_diff_lines_keep_add_and_delete_colors() {
+        let line = "let s = \"text\"; // note";
+        let fg_colors = |kind| {
+            push_wrapped_diff_line(1, kind, line, Some(Language::Rust), 80, 1)
+                .into_iter()
+                .flat_map(|line| line.spans)
+                .skip(1)
+                .map(|span| span.style.fg)
+                .collect::<Vec<_>>()
+        };
+
+        assert!(
+            fg_colors(DiffLineType::Insert)
+                .iter()
+                .all(|fg| *fg == Some(Color::Green))
+        );
+        assert!(
+            fg_colors(DiffLineType::Delete)
+                .iter()
+                .all(|fg| *fg == Some(Color::Red))
+        );
+        assert!(
+            fg_colors(DiffLineType::Context)
+                .iter()
+                .any(|fg| fg.is_some())
+        );
+    }
+
     #[test]
     fn ui_snapshot_apply_update_block() {
         let mut changes: HashMap<PathBuf, FileChange> = HashMap::new();
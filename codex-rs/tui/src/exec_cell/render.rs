@@ -69,14 +69,16 @@ pub(crate) fn output_lines(
     };
 
     let src = if *exit_code == 0 { stdout } else { stderr };
-    let lines: Vec<&str> = src.lines().collect();
-    let total = lines.len();
-    let limit = TOOL_CALL_MAX_LINES;
 
     let mut out = Vec::new();
-
-    let head_end = total.min(limit);
-    for (i, raw) in lines[..head_end].iter().enumerate() {
+    for (i, output_line) in truncate_output_lines(src).into_iter().enumerate() {
+        let raw = match output_line {
+            OutputLine::Text(raw) => raw,
+            OutputLine::Omitted(omitted) => {
+                out.push(format!("… +{omitted} lines").into());
+                continue;
+            }
+        };
         let mut line = ansi_escape_line(raw);
         let prefix = if !include_prefix {
             ""
@@ -92,28 +94,34 @@ pub(crate) fn output_lines(
         out.push(line);
     }
 
-    let show_ellipsis = total > 2 * limit;
-    if show_ellipsis {
-        let omitted = total - 2 * limit;
-        out.push(format!("… +{omitted} lines").into());
-    }
+    out
+}
 
-    let tail_start = if show_ellipsis {
-        total - limit
-    } else {
-        head_end
-    };
-    for raw in lines[tail_start..].iter() {
-        let mut line = ansi_escape_line(raw);
-        if include_prefix {
-            line.spans.insert(0, "    ".into());
-        }
-        line.spans.iter_mut().for_each(|span| {
-            span.style = span.style.add_modifier(Modifier::DIM);
-        });
-        out.push(line);
+/// A line of command output kept by [`truncate_output_lines`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OutputLine<'a> {
+    Text(&'a str),
+    /// Stands in for this many lines dropped from the middle of the output.
+    Omitted(usize),
+}
+
+/// Keeps the first and last [`TOOL_CALL_MAX_LINES`] lines of `src`, replacing
+/// everything in between with a single [`OutputLine::Omitted`] marker.
+pub(crate) fn truncate_output_lines(src: &str) -> Vec<OutputLine<'_>> {
+    let lines: Vec<&str> = src.lines().collect();
+    let total = lines.len();
+    let limit = TOOL_CALL_MAX_LINES;
+    if total <= 2 * limit {
+        return lines.into_iter().map(OutputLine::Text).collect();
     }
 
+    let mut out: Vec<OutputLine<'_>> = lines[..limit]
+        .iter()
+        .copied()
+        .map(OutputLine::Text)
+        .collect();
+    out.push(OutputLine::Omitted(total - 2 * limit));
+    out.extend(lines[total - limit..].iter().copied().map(OutputLine::Text));
     out
 }
 
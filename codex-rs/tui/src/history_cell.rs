@@ -6,24 +6,31 @@ use crate::exec_cell::TOOL_CALL_MAX_LINES;
 use crate::exec_cell::output_lines;
 use crate::exec_cell::spinner;
 use crate::exec_command::relativize_to_home;
+use crate::exec_command::strip_bash_lc_and_escape;
 use crate::markdown::MarkdownCitationContext;
 use crate::markdown::append_markdown;
 use crate::render::line_utils::line_to_static;
 use crate::render::line_utils::prefix_lines;
 use crate::style::user_message_style;
 use crate::text_formatting::format_and_truncate_tool_result;
+use crate::text_formatting::truncate_text;
 use crate::ui_consts::LIVE_PREFIX_COLS;
 use crate::wrapping::RtOptions;
 use crate::wrapping::word_wrap_line;
 use crate::wrapping::word_wrap_lines;
 use base64::Engine;
+use codex_common::elapsed::format_duration;
 use codex_core::config::Config;
 use codex_core::config_types::McpServerTransportConfig;
 use codex_core::config_types::ReasoningSummaryFormat;
 use codex_core::protocol::FileChange;
 use codex_core::protocol::McpInvocation;
+use codex_core::protocol::McpServerStatus;
 use codex_core::protocol::SessionConfiguredEvent;
 use codex_core::protocol_config_types::ReasoningEffort as ReasoningEffortConfig;
+use codex_protocol::agents::AgentFinding;
+use codex_protocol::agents::AgentFindingSeverity;
+use codex_protocol::agents::AgentRunResult;
 use codex_protocol::plan_tool::PlanItemArg;
 use codex_protocol::plan_tool::StepStatus;
 use codex_protocol::plan_tool::UpdatePlanArgs;
@@ -846,10 +853,15 @@ pub(crate) fn empty_mcp_output() -> PlainHistoryCell {
     PlainHistoryCell { lines }
 }
 
-/// Render MCP tools grouped by connection using the fully-qualified tool names.
+/// Number of stderr lines shown for a failed MCP server.
+const MCP_STDERR_TAIL_LINES: usize = 5;
+
+/// Render MCP tools grouped by connection using the fully-qualified tool names,
+/// along with the health of every server.
 pub(crate) fn new_mcp_tools_output(
     config: &Config,
-    tools: std::collections::HashMap<String, mcp_types::Tool>,
+    tools: HashMap<String, mcp_types::Tool>,
+    statuses: HashMap<String, McpServerStatus>,
 ) -> PlainHistoryCell {
     let mut lines: Vec<Line<'static>> = vec![
         "/mcp".magenta().into(),
@@ -858,13 +870,18 @@ pub(crate) fn new_mcp_tools_output(
         "".into(),
     ];
 
-    if tools.is_empty() {
+    if tools.is_empty() && statuses.is_empty() {
         lines.push("  • No MCP tools available.".italic().into());
         lines.push("".into());
         return PlainHistoryCell { lines };
     }
 
-    for (server, cfg) in config.mcp_servers.iter() {
+    // Servers started by `/mcp-reload` are not in this session's config.
+    let mut servers: Vec<&String> = config.mcp_servers.keys().chain(statuses.keys()).collect();
+    servers.sort();
+    servers.dedup();
+
+    for server in servers {
         let prefix = format!("{server}__");
         let mut names: Vec<String> = tools
             .keys()
@@ -875,8 +892,8 @@ pub(crate) fn new_mcp_tools_output(
 
         lines.push(vec!["  • Server: ".into(), server.clone().into()].into());
 
-        match &cfg.transport {
-            McpServerTransportConfig::Stdio { command, args, env } => {
+        match config.mcp_servers.get(server).map(|cfg| &cfg.transport) {
+            Some(McpServerTransportConfig::Stdio { command, args, env }) => {
                 let args_suffix = if args.is_empty() {
                     String::new()
                 } else {
@@ -894,9 +911,35 @@ pub(crate) fn new_mcp_tools_output(
                     lines.push(vec!["    • Env: ".into(), env_pairs.join(" ").into()].into());
                 }
             }
-            McpServerTransportConfig::StreamableHttp { url, .. } => {
+            Some(McpServerTransportConfig::StreamableHttp { url, .. }) => {
                 lines.push(vec!["    • URL: ".into(), url.clone().into()].into());
             }
+            None => {}
+        }
+
+        match statuses.get(server) {
+            Some(McpServerStatus::Starting) => {
+                lines.push(vec!["    • Status: ".into(), "starting".cyan()].into());
+            }
+            Some(McpServerStatus::Ready) => {
+                lines.push(vec!["    • Status: ".into(), "ready".green()].into());
+            }
+            Some(McpServerStatus::Failed { error, stderr_tail }) => {
+                lines.push(
+                    vec![
+                        "    • Status: ".into(),
+                        "failed".red(),
+                        " ".into(),
+                        error.clone().dim(),
+                    ]
+                    .into(),
+                );
+                let skip = stderr_tail.len().saturating_sub(MCP_STDERR_TAIL_LINES);
+                for line in &stderr_tail[skip..] {
+                    lines.push(vec!["      │ ".dim(), line.clone().dim()].into());
+                }
+            }
+            None => {}
         }
 
         if names.is_empty() {
@@ -920,6 +963,174 @@ pub(crate) fn new_info_event(message: String, hint: Option<String>) -> PlainHist
     PlainHistoryCell { lines }
 }
 
+/// Records that a stored approval rule approved a command without asking.
+pub(crate) fn new_approval_rule_applied(command: &[String], pattern: String) -> PlainHistoryCell {
+    let snippet = truncate_text(&strip_bash_lc_and_escape(command), 80);
+    let lines: Vec<Line<'static>> = vec![
+        vec![
+            "✔ ".green(),
+            "Auto-approved".bold(),
+            " ".into(),
+            snippet.dim(),
+            " by rule ".into(),
+            pattern.cyan(),
+        ]
+        .into(),
+    ];
+    PlainHistoryCell { lines }
+}
+
+pub(crate) fn new_network_access_blocked(host: &str, port: u16) -> PlainHistoryCell {
+    let lines: Vec<Line<'static>> = vec![
+        vec![
+            "✗ ".red(),
+            "Blocked network access".bold(),
+            " to ".into(),
+            format!("{host}:{port}").cyan(),
+            " (not in the sandbox network allowlist)".dim(),
+        ]
+        .into(),
+    ];
+    PlainHistoryCell { lines }
+}
+
+/// Result of an agent-backed slash command. Findings are grouped by
+/// severity, most severe first, and link to their location when a file
+/// opener is configured.
+#[derive(Debug)]
+pub(crate) struct AgentRunCell {
+    command: String,
+    duration: Duration,
+    result: AgentRunResult,
+    citation_context: MarkdownCitationContext,
+    cwd: PathBuf,
+}
+
+pub(crate) fn new_agent_run_result(
+    command: String,
+    duration: Duration,
+    result: AgentRunResult,
+    config: &Config,
+) -> AgentRunCell {
+    AgentRunCell {
+        command,
+        duration,
+        result,
+        citation_context: config.into(),
+        cwd: config.cwd.clone(),
+    }
+}
+
+impl AgentRunCell {
+    fn finding_lines(&self, finding: &AgentFinding) -> Vec<Line<'static>> {
+        let path = finding
+            .path
+            .as_ref()
+            .map(|path| path_clean::clean(self.cwd.join(path)));
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        if let Some(path) = &path {
+            let location = match finding.line {
+                Some(line) => format!("{}:{line}", display_path_for(path, &self.cwd)),
+                None => display_path_for(path, &self.cwd),
+            };
+            spans.push(location.bold());
+            spans.push(" ".into());
+        }
+        spans.push(format!("[{}] ", finding.category).dim());
+        spans.push(finding.message.clone().into());
+        if let (Some(scheme), Some(path)) = (self.citation_context.scheme(), &path) {
+            // Same link format as rewritten markdown citations.
+            let url = format!(
+                "{scheme}://file{}:{}",
+                path.to_string_lossy().replace('\\', "/"),
+                finding.line.unwrap_or(1)
+            );
+            spans.push(" (".into());
+            spans.push(url.cyan().underlined());
+            spans.push(")".into());
+        }
+        vec![Line::from(spans)]
+    }
+
+    fn summary_lines(result: &AgentRunResult, lines: &mut Vec<Line<'static>>) {
+        match result {
+            AgentRunResult::CodeReview { .. } => {}
+            AgentRunResult::Analysis { summary, details } => {
+                lines.push(Line::from(summary.clone()));
+                for (key, value) in details {
+                    lines.push(vec![format!("{key}: ").dim(), value.clone().into()].into());
+                }
+            }
+            AgentRunResult::Suggestions { items } => {
+                for item in items {
+                    lines.push(vec!["• ".dim(), item.title.clone().bold()].into());
+                    lines.push(Line::from(format!("  {}", item.description)));
+                }
+            }
+            AgentRunResult::Combined { results } => {
+                for entry in results {
+                    Self::summary_lines(&entry.result, lines);
+                }
+            }
+        }
+    }
+}
+
+impl HistoryCell for AgentRunCell {
+    fn display_lines(&self, _width: u16) -> Vec<Line<'static>> {
+        let findings = self.result.findings();
+        let mut lines: Vec<Line<'static>> = vec![
+            vec![
+                "• ".dim(),
+                format!("/{}", self.command).bold(),
+                format!(
+                    " · {} finding(s) in {}",
+                    findings.len(),
+                    format_duration(self.duration)
+                )
+                .dim(),
+            ]
+            .into(),
+        ];
+
+        let mut body: Vec<Line<'static>> = Vec::new();
+        for severity in [
+            AgentFindingSeverity::Error,
+            AgentFindingSeverity::Warning,
+            AgentFindingSeverity::Info,
+        ] {
+            let group: Vec<&AgentFinding> = findings
+                .iter()
+                .copied()
+                .filter(|finding| finding.severity == severity)
+                .collect();
+            if group.is_empty() {
+                continue;
+            }
+            let heading = match severity {
+                AgentFindingSeverity::Error => format!("Errors ({})", group.len()).red(),
+                AgentFindingSeverity::Warning => format!("Warnings ({})", group.len()).into(),
+                AgentFindingSeverity::Info => format!("Info ({})", group.len()).dim(),
+            };
+            body.push(Line::from(heading.bold()));
+            for finding in group {
+                body.extend(prefix_lines(
+                    self.finding_lines(finding),
+                    "  ".into(),
+                    "  ".into(),
+                ));
+            }
+        }
+        Self::summary_lines(&self.result, &mut body);
+        if body.is_empty() {
+            body.push(Line::from("(no findings)".dim().italic()));
+        }
+
+        lines.extend(prefix_lines(body, "  └ ".dim(), "    ".into()));
+        lines
+    }
+}
+
 pub(crate) fn new_error_event(message: String) -> PlainHistoryCell {
     // Use a hair space (U+200A) to create a subtle, near-invisible separation
     // before the text. VS16 is intentionally omitted to keep spacing tighter
@@ -1181,6 +1392,45 @@ mod tests {
         render_lines(&cell.transcript_lines(u16::MAX))
     }
 
+    #[test]
+    fn mcp_tools_output_shows_server_health() {
+        let config = test_config();
+        let statuses = HashMap::from([
+            ("docs".to_string(), McpServerStatus::Ready),
+            (
+                "search".to_string(),
+                McpServerStatus::Failed {
+                    error: "server exited unexpectedly".to_string(),
+                    stderr_tail: (1..=7).map(|i| format!("line {i}")).collect(),
+                },
+            ),
+        ]);
+        let cell = new_mcp_tools_output(&config, HashMap::new(), statuses);
+
+        assert_eq!(
+            render_transcript(&cell),
+            vec![
+                "/mcp",
+                "",
+                "🔌  MCP Tools",
+                "",
+                "  • Server: docs",
+                "    • Status: ready",
+                "    • Tools: (none)",
+                "",
+                "  • Server: search",
+                "    • Status: failed server exited unexpectedly",
+                "      │ line 3",
+                "      │ line 4",
+                "      │ line 5",
+                "      │ line 6",
+                "      │ line 7",
+                "    • Tools: (none)",
+                "",
+            ]
+        );
+    }
+
     #[test]
     fn empty_agent_message_cell_transcript() {
         let cell = AgentMessageCell::new(vec![Line::default()], false);
@@ -1188,6 +1438,48 @@ mod tests {
         assert_eq!(cell.desired_transcript_height(80), 1);
     }
 
+    #[test]
+    fn agent_run_cell_groups_findings_by_severity() {
+        let mut config = test_config();
+        config.cwd = PathBuf::from("/workspace");
+        config.file_opener = codex_core::config_types::UriBasedFileOpener::VsCode;
+        let finding = |severity, message: &str, line| AgentFinding {
+            severity,
+            category: "Security".to_string(),
+            message: message.to_string(),
+            path: Some(PathBuf::from("src/db.rs")),
+            line,
+        };
+        let result = AgentRunResult::CodeReview {
+            findings: vec![
+                finding(
+                    AgentFindingSeverity::Info,
+                    "Consider a prepared statement",
+                    None,
+                ),
+                finding(AgentFindingSeverity::Error, "SQL injection", Some(12)),
+            ],
+        };
+
+        let cell = new_agent_run_result(
+            "review".to_string(),
+            Duration::from_millis(1500),
+            result,
+            &config,
+        );
+
+        assert_eq!(
+            render_lines(&cell.display_lines(80)),
+            vec![
+                "• /review · 2 finding(s) in 1.50s",
+                "  └ Errors (1)",
+                "      /workspace/src/db.rs:12 [Security] SQL injection (vscode://file/workspace/src/db.rs:12)",
+                "    Info (1)",
+                "      /workspace/src/db.rs [Security] Consider a prepared statement (vscode://file/workspace/src/db.rs:1)",
+            ]
+        );
+    }
+
     #[test]
     fn active_mcp_tool_call_snapshot() {
         let invocation = McpInvocation {
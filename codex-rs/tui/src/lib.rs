@@ -60,6 +60,7 @@ mod pager_overlay;
 pub mod public_widgets;
 mod render;
 mod resume_picker;
+mod session_export;
 mod session_log;
 mod shimmer;
 mod slash_command;
@@ -89,6 +90,9 @@ pub use cli::Cli;
 pub use markdown_render::render_markdown_text;
 pub use public_widgets::composer_input::ComposerAction;
 pub use public_widgets::composer_input::ComposerInput;
+pub use session_export::TranscriptFormat;
+pub use session_export::TranscriptOptions;
+pub use session_export::export_transcript;
 use std::io::Write as _;
 
 // (tests access modules directly within the crate)
@@ -14,6 +14,11 @@ impl MarkdownCitationContext {
     pub(crate) fn new(file_opener: UriBasedFileOpener, cwd: PathBuf) -> Self {
         Self { file_opener, cwd }
     }
+
+    /// URI scheme used to open files, if a file opener is configured.
+    pub(crate) fn scheme(&self) -> Option<&str> {
+        self.file_opener.get_scheme()
+    }
 }
 
 impl From<&Config> for MarkdownCitationContext {
@@ -1,4 +1,6 @@
 use crate::citation_regex::CITATION_REGEX;
+use crate::render::highlight::highlight_code_to_lines;
+use crate::render::language::Language;
 use crate::render::line_utils::line_to_static;
 use crate::wrapping::RtOptions;
 use crate::wrapping::word_wrap_line;
@@ -79,6 +81,10 @@ where
     scheme: Option<String>,
     cwd: Option<std::path::PathBuf>,
     in_code_block: bool,
+    /// Language of the current fenced code block, when it can be highlighted.
+    code_block_language: Option<Language>,
+    /// Text of a highlighted code block, rendered once the block ends.
+    code_block_buffer: String,
     wrap_width: Option<usize>,
     current_line_content: Option<Line<'static>>,
     current_initial_indent: Vec<Span<'static>>,
@@ -110,6 +116,8 @@ where
             scheme,
             cwd,
             in_code_block: false,
+            code_block_language: None,
+            code_block_buffer: String::new(),
             wrap_width,
             current_line_content: None,
             current_initial_indent: Vec::new(),
@@ -260,6 +268,10 @@ where
     }
 
     fn text(&mut self, text: CowStr<'a>) {
+        if self.in_code_block && self.code_block_language.is_some() {
+            self.code_block_buffer.push_str(&text);
+            return;
+        }
         if self.pending_marker_line {
             self.push_line(Line::default());
         }
@@ -382,12 +394,14 @@ where
         self.needs_newline = false;
     }
 
-    fn start_codeblock(&mut self, _lang: Option<String>, indent: Option<Span<'static>>) {
+    fn start_codeblock(&mut self, lang: Option<String>, indent: Option<Span<'static>>) {
         self.flush_current_line();
         if !self.text.lines.is_empty() {
             self.push_blank_line();
         }
         self.in_code_block = true;
+        self.code_block_language = lang.as_deref().and_then(Language::from_info_string);
+        self.code_block_buffer.clear();
         self.indent_stack.push(IndentContext::new(
             vec![indent.unwrap_or_default()],
             None,
@@ -397,11 +411,43 @@ where
     }
 
     fn end_codeblock(&mut self) {
+        if let Some(language) = self.code_block_language.take() {
+            let code = std::mem::take(&mut self.code_block_buffer);
+            self.push_highlighted_code(&code, language);
+        }
         self.needs_newline = true;
         self.in_code_block = false;
         self.indent_stack.pop();
     }
 
+    /// Pushes a whole code block, one highlighted line at a time, following
+    /// the same line breaking as [`Self::text`].
+    fn push_highlighted_code(&mut self, code: &str, language: Language) {
+        if code.is_empty() {
+            return;
+        }
+        if self.pending_marker_line {
+            self.push_line(Line::default());
+        }
+        self.pending_marker_line = false;
+        for (i, line) in highlight_code_to_lines(code, language)
+            .into_iter()
+            .enumerate()
+        {
+            if self.needs_newline {
+                self.push_line(Line::default());
+                self.needs_newline = false;
+            }
+            if i > 0 {
+                self.push_line(Line::default());
+            }
+            for span in line.spans {
+                self.push_span(span);
+            }
+        }
+        self.needs_newline = false;
+    }
+
     fn push_inline_style(&mut self, style: Style) {
         let current = self.inline_styles.last().copied().unwrap_or_default();
         let merged = current.patch(style);
@@ -5,6 +5,7 @@ use ratatui::text::Span;
 use ratatui::text::Text;
 
 use crate::markdown_render::render_markdown_text;
+use crate::render::highlight::HighlightTheme;
 use insta::assert_snapshot;
 
 #[test]
@@ -652,9 +653,24 @@ fn link() {
 }
 
 #[test]
-fn code_block_unhighlighted() {
+fn code_block_highlighted() {
     let text = render_markdown_text("```rust\nfn main() {}\n```\n");
-    let expected = Text::from_iter([Line::from_iter(["", "fn main() {}"])]);
+    assert_eq!(text.lines.len(), 1);
+    let line = &text.lines[0];
+    let content: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+    assert_eq!(content, "fn main() {}");
+    let keyword = line
+        .spans
+        .iter()
+        .find(|s| s.content == "fn")
+        .expect("keyword span");
+    assert_eq!(keyword.style, HighlightTheme::current().keyword);
+}
+
+#[test]
+fn code_block_unknown_language_unhighlighted() {
+    let text = render_markdown_text("```brainfuck\n+[-]\n```\n");
+    let expected = Text::from_iter([Line::from_iter(["", "+[-]"])]);
     assert_eq!(text, expected);
 }
 
@@ -1,3 +1,8 @@
+use crate::color::is_light;
+use crate::render::language::Language;
+use crate::render::language::LanguageSpec;
+use crate::terminal_palette::default_bg;
+use ratatui::style::Color;
 use ratatui::style::Style;
 use ratatui::style::Stylize;
 use ratatui::text::Line;
@@ -143,6 +148,258 @@ pub(crate) fn highlight_bash_to_lines(script: &str) -> Vec<Line<'static>> {
     }
 }
 
+/// Styles for the token classes of the lexical highlighter. Only ANSI
+/// colors are used; dark backgrounds get the bright variants.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct HighlightTheme {
+    pub(crate) keyword: Style,
+    pub(crate) string: Style,
+    pub(crate) comment: Style,
+    pub(crate) literal: Style,
+    pub(crate) type_name: Style,
+}
+
+impl HighlightTheme {
+    /// Theme for the terminal's detected background color.
+    pub(crate) fn current() -> Self {
+        Self::for_background(default_bg())
+    }
+
+    /// Light backgrounds get the base ANSI colors; dark or unknown
+    /// backgrounds get the bright variants.
+    pub(crate) fn for_background(bg: Option<(u8, u8, u8)>) -> Self {
+        let light = bg.is_some_and(is_light);
+        let pick =
+            |base: Color, bright: Color| Style::default().fg(if light { base } else { bright });
+        Self {
+            keyword: pick(Color::Magenta, Color::LightMagenta),
+            string: pick(Color::Green, Color::LightGreen),
+            comment: Style::default().dim().italic(),
+            literal: pick(Color::Red, Color::LightRed),
+            type_name: pick(Color::Cyan, Color::LightCyan),
+        }
+    }
+}
+
+/// Highlights `code` written in `language` into one `Line` per source line.
+/// A single trailing newline is ignored, matching how code blocks and diff
+/// hunks carry their text.
+pub(crate) fn highlight_code_to_lines(code: &str, language: Language) -> Vec<Line<'static>> {
+    let code = code.strip_suffix('\n').unwrap_or(code);
+    match language.spec() {
+        None => highlight_bash_to_lines(code),
+        Some(spec) => highlight_with_spec(code, spec, &HighlightTheme::current()),
+    }
+}
+
+/// Construct that continues onto the next line.
+enum Carry {
+    BlockComment(&'static str),
+    String(&'static str),
+}
+
+fn highlight_with_spec(
+    code: &str,
+    spec: &LanguageSpec,
+    theme: &HighlightTheme,
+) -> Vec<Line<'static>> {
+    let mut carry = None;
+    code.split('\n')
+        .map(|line| {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            Line::from(highlight_line(line, spec, theme, &mut carry))
+        })
+        .collect()
+}
+
+fn highlight_line(
+    line: &str,
+    spec: &LanguageSpec,
+    theme: &HighlightTheme,
+    carry: &mut Option<Carry>,
+) -> Vec<Span<'static>> {
+    let mut spans = SpanBuilder::default();
+    let mut rest = line;
+
+    match carry.take() {
+        Some(Carry::BlockComment(end)) => match rest.find(end) {
+            Some(idx) => {
+                let (comment, tail) = rest.split_at(idx + end.len());
+                spans.push(comment, theme.comment);
+                rest = tail;
+            }
+            None => {
+                spans.push(rest, theme.comment);
+                *carry = Some(Carry::BlockComment(end));
+                return spans.finish();
+            }
+        },
+        Some(Carry::String(delimiter)) => match find_string_end(rest, delimiter) {
+            Some(idx) => {
+                let (string, tail) = rest.split_at(idx);
+                spans.push(string, theme.string);
+                rest = tail;
+            }
+            None => {
+                spans.push(rest, theme.string);
+                *carry = Some(Carry::String(delimiter));
+                return spans.finish();
+            }
+        },
+        None => {}
+    }
+
+    while let Some(c) = rest.chars().next() {
+        if spec
+            .line_comments
+            .iter()
+            .any(|prefix| rest.starts_with(prefix))
+            && !is_directive(rest, spec)
+        {
+            spans.push(rest, theme.comment);
+            break;
+        }
+        if let Some((start, end)) = spec.block_comment
+            && rest.starts_with(start)
+        {
+            match rest[start.len()..].find(end) {
+                Some(idx) => {
+                    let (comment, tail) = rest.split_at(start.len() + idx + end.len());
+                    spans.push(comment, theme.comment);
+                    rest = tail;
+                    continue;
+                }
+                None => {
+                    spans.push(rest, theme.comment);
+                    *carry = Some(Carry::BlockComment(end));
+                    break;
+                }
+            }
+        }
+        if let Some(delimiter) = spec.strings.iter().find(|d| rest.starts_with(**d)) {
+            let body = &rest[delimiter.len()..];
+            match find_string_end(body, delimiter) {
+                Some(idx) => {
+                    let (string, tail) = rest.split_at(delimiter.len() + idx);
+                    spans.push(string, theme.string);
+                    rest = tail;
+                    continue;
+                }
+                None => {
+                    spans.push(rest, theme.string);
+                    if spec.multiline_strings.contains(delimiter) {
+                        *carry = Some(Carry::String(delimiter));
+                    }
+                    break;
+                }
+            }
+        }
+        if c.is_ascii_digit() {
+            let len = token_len(rest, |c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+            let (number, tail) = rest.split_at(len);
+            spans.push(number, theme.literal);
+            rest = tail;
+            continue;
+        }
+        if c == '#' && is_directive(rest, spec) {
+            let len = 1 + token_len(&rest[1..], is_ident_char);
+            let (directive, tail) = rest.split_at(len);
+            spans.push(directive, theme.keyword);
+            rest = tail;
+            continue;
+        }
+        if c.is_alphabetic() || c == '_' {
+            let len = token_len(rest, is_ident_char);
+            let (word, tail) = rest.split_at(len);
+            let style = classify_word(word, spec, theme);
+            spans.push(word, style);
+            rest = tail;
+            continue;
+        }
+        let (plain, tail) = rest.split_at(c.len_utf8());
+        spans.push(plain, Style::default());
+        rest = tail;
+    }
+
+    spans.finish()
+}
+
+fn classify_word(word: &str, spec: &LanguageSpec, theme: &HighlightTheme) -> Style {
+    if spec.keywords.contains(&word) {
+        theme.keyword
+    } else if spec.constants.contains(&word) {
+        theme.literal
+    } else if spec.types.contains(&word)
+        || (spec.capitalized_types && word.starts_with(|c: char| c.is_uppercase()))
+    {
+        theme.type_name
+    } else {
+        Style::default()
+    }
+}
+
+/// Whether `text` starts with a preprocessor directive keyword such as
+/// `#include`.
+fn is_directive(text: &str, spec: &LanguageSpec) -> bool {
+    if !text.starts_with('#') {
+        return false;
+    }
+    let len = 1 + token_len(&text[1..], is_ident_char);
+    spec.keywords.contains(&&text[..len])
+}
+
+/// Byte offset just past the closing `delimiter` in `text`, skipping
+/// backslash escapes.
+fn find_string_end(text: &str, delimiter: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (idx, c) in text.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if text[idx..].starts_with(delimiter) {
+            return Some(idx + delimiter.len());
+        }
+    }
+    None
+}
+
+fn token_len(text: &str, is_token_char: impl Fn(char) -> bool) -> usize {
+    text.char_indices()
+        .find(|(_, c)| !is_token_char(*c))
+        .map(|(idx, _)| idx)
+        .unwrap_or(text.len())
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Accumulates spans, merging neighbors that share a style.
+#[derive(Default)]
+struct SpanBuilder {
+    spans: Vec<Span<'static>>,
+}
+
+impl SpanBuilder {
+    fn push(&mut self, text: &str, style: Style) {
+        if text.is_empty() {
+            return;
+        }
+        if let Some(last) = self.spans.last_mut()
+            && last.style == style
+        {
+            last.content.to_mut().push_str(text);
+            return;
+        }
+        self.spans.push(Span::styled(text.to_string(), style));
+    }
+
+    fn finish(self) -> Vec<Span<'static>> {
+        self.spans
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -233,4 +490,56 @@ mod tests {
         let body_style = body_style.expect("missing heredoc span");
         assert!(body_style.add_modifier.contains(Modifier::DIM));
     }
+
+    fn style_of(lines: &[Line<'static>], token: &str) -> Style {
+        lines
+            .iter()
+            .flat_map(|l| l.spans.iter())
+            .find(|sp| sp.content.as_ref() == token)
+            .map(|sp| sp.style)
+            .unwrap_or_else(|| panic!("missing span {token:?}"))
+    }
+
+    #[test]
+    fn highlights_rust_tokens() {
+        let theme = HighlightTheme::for_background(None);
+        let code = "fn main() {\n    let n: u32 = 42; // answer\n    println!(\"{n}\");\n}\n";
+        let lines = highlight_code_to_lines(code, Language::Rust);
+        assert_eq!(reconstructed(&lines), code.trim_end_matches('\n'));
+        assert_eq!(lines.len(), 4);
+
+        assert_eq!(style_of(&lines, "fn"), theme.keyword);
+        assert_eq!(style_of(&lines, "u32"), theme.type_name);
+        assert_eq!(style_of(&lines, "42"), theme.literal);
+        assert_eq!(style_of(&lines, "// answer"), theme.comment);
+        assert_eq!(style_of(&lines, "\"{n}\""), theme.string);
+    }
+
+    #[test]
+    fn carries_multiline_strings_and_comments() {
+        let theme = HighlightTheme::for_background(None);
+        let lines = highlight_code_to_lines("x = \"\"\"doc\nstill doc\"\"\" + 1", Language::Python);
+        assert_eq!(style_of(&lines, "still doc\"\"\""), theme.string);
+        assert_eq!(style_of(&lines, "1"), theme.literal);
+
+        let lines = highlight_code_to_lines("/* start\n end */ int x;", Language::C);
+        assert_eq!(style_of(&lines, " end */"), theme.comment);
+        assert_eq!(style_of(&lines, "int"), theme.type_name);
+    }
+
+    #[test]
+    fn highlights_preprocessor_directives() {
+        let theme = HighlightTheme::for_background(None);
+        let lines = highlight_code_to_lines("#include <stdio.h>", Language::C);
+        assert_eq!(style_of(&lines, "#include"), theme.keyword);
+    }
+
+    #[test]
+    fn theme_follows_background_brightness() {
+        let light = HighlightTheme::for_background(Some((250, 250, 250)));
+        let dark = HighlightTheme::for_background(Some((20, 20, 20)));
+        assert_eq!(light.keyword.fg, Some(Color::Magenta));
+        assert_eq!(dark.keyword.fg, Some(Color::LightMagenta));
+        assert_eq!(HighlightTheme::for_background(None), dark);
+    }
 }
@@ -0,0 +1,667 @@
+//! Languages the TUI knows how to syntax highlight.
+//!
+//! Code is matched to a [`Language`] either by the info string of a fenced
+//! markdown code block (`rust`, `py`, `ts`, ...) or by the file name of a
+//! diff. Bash is highlighted with tree-sitter; every other language is
+//! described by a [`LanguageSpec`] that drives the lexical highlighter in
+//! [`super::highlight`].
+
+use std::path::Path;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Language {
+    Bash,
+    C,
+    Cpp,
+    CSharp,
+    Go,
+    Java,
+    JavaScript,
+    Json,
+    Python,
+    Ruby,
+    Rust,
+    Toml,
+    TypeScript,
+    Yaml,
+}
+
+/// Lexical description of a language: the words and delimiters the
+/// highlighter styles. Keywords starting with `#` are preprocessor
+/// directives.
+#[derive(Debug)]
+pub(crate) struct LanguageSpec {
+    pub(crate) keywords: &'static [&'static str],
+    /// Literal words such as `true` and `null`, styled like numbers.
+    pub(crate) constants: &'static [&'static str],
+    /// Primitive type names. Capitalized identifiers are also treated as
+    /// types when `capitalized_types` is set.
+    pub(crate) types: &'static [&'static str],
+    pub(crate) capitalized_types: bool,
+    pub(crate) line_comments: &'static [&'static str],
+    pub(crate) block_comment: Option<(&'static str, &'static str)>,
+    /// String delimiters, longest first so `"""` wins over `"`.
+    pub(crate) strings: &'static [&'static str],
+    /// Delimiters whose strings may span several lines.
+    pub(crate) multiline_strings: &'static [&'static str],
+}
+
+/// Fence names and file extensions for each language.
+const REGISTRY: &[(Language, &[&str], &[&str])] = &[
+    (
+        Language::Bash,
+        &["bash", "sh", "shell", "zsh", "console"],
+        &["sh", "bash", "zsh"],
+    ),
+    (Language::C, &["c", "h"], &["c", "h"]),
+    (
+        Language::Cpp,
+        &["cpp", "c++", "cxx", "cc", "hpp"],
+        &["cpp", "cc", "cxx", "hpp", "hh", "hxx"],
+    ),
+    (Language::CSharp, &["csharp", "cs", "c#"], &["cs"]),
+    (Language::Go, &["go", "golang"], &["go"]),
+    (Language::Java, &["java"], &["java"]),
+    (
+        Language::JavaScript,
+        &["javascript", "js", "jsx", "mjs", "cjs", "node"],
+        &["js", "jsx", "mjs", "cjs"],
+    ),
+    (
+        Language::Json,
+        &["json", "jsonc", "json5"],
+        &["json", "jsonc"],
+    ),
+    (
+        Language::Python,
+        &["python", "py", "python3", "py3"],
+        &["py", "pyi"],
+    ),
+    (Language::Ruby, &["ruby", "rb"], &["rb", "rake", "gemspec"]),
+    (Language::Rust, &["rust", "rs"], &["rs"]),
+    (Language::Toml, &["toml"], &["toml"]),
+    (
+        Language::TypeScript,
+        &["typescript", "ts", "tsx", "mts", "cts"],
+        &["ts", "tsx", "mts", "cts"],
+    ),
+    (Language::Yaml, &["yaml", "yml"], &["yaml", "yml"]),
+];
+
+impl Language {
+    /// Resolves the info string of a fenced code block, e.g. `rust`,
+    /// `rust,ignore` or `python {.numberLines}`.
+    pub(crate) fn from_info_string(info: &str) -> Option<Self> {
+        let name = info
+            .split(|c: char| c.is_whitespace() || c == ',' || c == '{')
+            .next()?
+            .to_ascii_lowercase();
+        REGISTRY
+            .iter()
+            .find(|(_, names, _)| names.contains(&name.as_str()))
+            .map(|(language, _, _)| *language)
+    }
+
+    /// Resolves a file name by its extension, or by its full name for
+    /// well-known extensionless files.
+    pub(crate) fn from_path(path: &Path) -> Option<Self> {
+        let file_name = path.file_name()?.to_str()?;
+        match file_name {
+            "Cargo.lock" | "Pipfile" | "poetry.lock" => return Some(Language::Toml),
+            "Gemfile" | "Rakefile" => return Some(Language::Ruby),
+            ".bashrc" | ".bash_profile" | ".zshrc" | ".profile" => return Some(Language::Bash),
+            _ => {}
+        }
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        REGISTRY
+            .iter()
+            .find(|(_, _, extensions)| extensions.contains(&extension.as_str()))
+            .map(|(language, _, _)| *language)
+    }
+
+    /// The lexical spec for this language, or `None` for Bash, which is
+    /// highlighted with tree-sitter instead.
+    pub(crate) fn spec(self) -> Option<&'static LanguageSpec> {
+        let spec = match self {
+            Language::Bash => return None,
+            Language::C => &C,
+            Language::Cpp => &CPP,
+            Language::CSharp => &CSHARP,
+            Language::Go => &GO,
+            Language::Java => &JAVA,
+            Language::JavaScript => &JAVASCRIPT,
+            Language::Json => &JSON,
+            Language::Python => &PYTHON,
+            Language::Ruby => &RUBY,
+            Language::Rust => &RUST,
+            Language::Toml => &TOML,
+            Language::TypeScript => &TYPESCRIPT,
+            Language::Yaml => &YAML,
+        };
+        Some(spec)
+    }
+}
+
+const C_KEYWORDS: &[&str] = &[
+    "auto", "break", "case", "const", "continue", "default", "do", "else", "enum", "extern", "for",
+    "goto", "if", "inline", "register", "restrict", "return", "sizeof", "static", "struct",
+    "switch", "typedef", "union", "volatile", "while", "#include", "#define", "#if", "#ifdef",
+    "#ifndef", "#else", "#elif", "#endif", "#pragma",
+];
+
+const C_TYPES: &[&str] = &[
+    "bool", "char", "double", "float", "int", "long", "short", "signed", "unsigned", "void",
+    "size_t", "ssize_t", "int8_t", "int16_t", "int32_t", "int64_t", "uint8_t", "uint16_t",
+    "uint32_t", "uint64_t",
+];
+
+const JS_KEYWORDS: &[&str] = &[
+    "async",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "export",
+    "extends",
+    "finally",
+    "for",
+    "from",
+    "function",
+    "if",
+    "import",
+    "in",
+    "instanceof",
+    "let",
+    "new",
+    "of",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+];
+
+const JS_CONSTANTS: &[&str] = &["true", "false", "null", "undefined", "NaN", "Infinity"];
+
+const C: LanguageSpec = LanguageSpec {
+    keywords: C_KEYWORDS,
+    constants: &["true", "false", "NULL"],
+    types: C_TYPES,
+    capitalized_types: false,
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    strings: &["\"", "'"],
+    multiline_strings: &[],
+};
+
+const CPP: LanguageSpec = LanguageSpec {
+    keywords: &[
+        "auto",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "const",
+        "constexpr",
+        "continue",
+        "decltype",
+        "default",
+        "delete",
+        "do",
+        "else",
+        "enum",
+        "explicit",
+        "extern",
+        "for",
+        "friend",
+        "if",
+        "inline",
+        "mutable",
+        "namespace",
+        "new",
+        "noexcept",
+        "operator",
+        "override",
+        "private",
+        "protected",
+        "public",
+        "return",
+        "sizeof",
+        "static",
+        "struct",
+        "switch",
+        "template",
+        "this",
+        "throw",
+        "try",
+        "typedef",
+        "typename",
+        "union",
+        "using",
+        "virtual",
+        "volatile",
+        "while",
+        "#include",
+        "#define",
+        "#if",
+        "#ifdef",
+        "#ifndef",
+        "#else",
+        "#endif",
+        "#pragma",
+    ],
+    constants: &["true", "false", "nullptr", "NULL"],
+    types: C_TYPES,
+    capitalized_types: true,
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    strings: &["\"", "'"],
+    multiline_strings: &[],
+};
+
+const CSHARP: LanguageSpec = LanguageSpec {
+    keywords: &[
+        "abstract",
+        "as",
+        "async",
+        "await",
+        "base",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "const",
+        "continue",
+        "default",
+        "delegate",
+        "do",
+        "else",
+        "enum",
+        "event",
+        "explicit",
+        "extern",
+        "finally",
+        "fixed",
+        "for",
+        "foreach",
+        "get",
+        "if",
+        "implicit",
+        "in",
+        "interface",
+        "internal",
+        "is",
+        "lock",
+        "namespace",
+        "new",
+        "operator",
+        "out",
+        "override",
+        "params",
+        "private",
+        "protected",
+        "public",
+        "readonly",
+        "record",
+        "ref",
+        "return",
+        "sealed",
+        "set",
+        "static",
+        "struct",
+        "switch",
+        "this",
+        "throw",
+        "try",
+        "typeof",
+        "using",
+        "var",
+        "virtual",
+        "void",
+        "while",
+        "yield",
+    ],
+    constants: &["true", "false", "null"],
+    types: &[
+        "bool", "byte", "char", "decimal", "double", "float", "int", "long", "object", "sbyte",
+        "short", "string", "uint", "ulong", "ushort",
+    ],
+    capitalized_types: true,
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    strings: &["\"", "'"],
+    multiline_strings: &[],
+};
+
+const GO: LanguageSpec = LanguageSpec {
+    keywords: &[
+        "break",
+        "case",
+        "chan",
+        "const",
+        "continue",
+        "default",
+        "defer",
+        "else",
+        "fallthrough",
+        "for",
+        "func",
+        "go",
+        "goto",
+        "if",
+        "import",
+        "interface",
+        "map",
+        "package",
+        "range",
+        "return",
+        "select",
+        "struct",
+        "switch",
+        "type",
+        "var",
+    ],
+    constants: &["true", "false", "nil", "iota"],
+    types: &[
+        "any",
+        "bool",
+        "byte",
+        "complex64",
+        "complex128",
+        "error",
+        "float32",
+        "float64",
+        "int",
+        "int8",
+        "int16",
+        "int32",
+        "int64",
+        "rune",
+        "string",
+        "uint",
+        "uint8",
+        "uint16",
+        "uint32",
+        "uint64",
+        "uintptr",
+    ],
+    capitalized_types: false,
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    strings: &["\"", "`", "'"],
+    multiline_strings: &["`"],
+};
+
+const JAVA: LanguageSpec = LanguageSpec {
+    keywords: &[
+        "abstract",
+        "assert",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "continue",
+        "default",
+        "do",
+        "else",
+        "enum",
+        "extends",
+        "final",
+        "finally",
+        "for",
+        "if",
+        "implements",
+        "import",
+        "instanceof",
+        "interface",
+        "native",
+        "new",
+        "package",
+        "private",
+        "protected",
+        "public",
+        "record",
+        "return",
+        "static",
+        "super",
+        "switch",
+        "synchronized",
+        "this",
+        "throw",
+        "throws",
+        "transient",
+        "try",
+        "var",
+        "volatile",
+        "while",
+        "yield",
+    ],
+    constants: &["true", "false", "null"],
+    types: &[
+        "boolean", "byte", "char", "double", "float", "int", "long", "short", "void",
+    ],
+    capitalized_types: true,
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    strings: &["\"\"\"", "\"", "'"],
+    multiline_strings: &["\"\"\""],
+};
+
+const JAVASCRIPT: LanguageSpec = LanguageSpec {
+    keywords: JS_KEYWORDS,
+    constants: JS_CONSTANTS,
+    types: &[],
+    capitalized_types: true,
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    strings: &["`", "\"", "'"],
+    multiline_strings: &["`"],
+};
+
+const JSON: LanguageSpec = LanguageSpec {
+    keywords: &[],
+    constants: &["true", "false", "null"],
+    types: &[],
+    capitalized_types: false,
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    strings: &["\""],
+    multiline_strings: &[],
+};
+
+const PYTHON: LanguageSpec = LanguageSpec {
+    keywords: &[
+        "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del",
+        "elif", "else", "except", "finally", "for", "from", "global", "if", "import", "in", "is",
+        "lambda", "match", "case", "nonlocal", "not", "or", "pass", "raise", "return", "try",
+        "while", "with", "yield",
+    ],
+    constants: &["True", "False", "None"],
+    types: &[
+        "bool", "bytes", "dict", "float", "int", "list", "object", "set", "str", "tuple",
+    ],
+    capitalized_types: true,
+    line_comments: &["#"],
+    block_comment: None,
+    strings: &["\"\"\"", "'''", "\"", "'"],
+    multiline_strings: &["\"\"\"", "'''"],
+};
+
+const RUBY: LanguageSpec = LanguageSpec {
+    keywords: &[
+        "alias", "and", "begin", "break", "case", "class", "def", "do", "else", "elsif", "end",
+        "ensure", "for", "if", "in", "module", "next", "not", "or", "redo", "require", "rescue",
+        "retry", "return", "self", "super", "then", "unless", "until", "when", "while", "yield",
+    ],
+    constants: &["true", "false", "nil"],
+    types: &[],
+    capitalized_types: true,
+    line_comments: &["#"],
+    block_comment: None,
+    strings: &["\"", "'"],
+    multiline_strings: &[],
+};
+
+const RUST: LanguageSpec = LanguageSpec {
+    keywords: &[
+        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+        "extern", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+        "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "type",
+        "unsafe", "use", "where", "while",
+    ],
+    constants: &["true", "false", "None", "Some", "Ok", "Err"],
+    types: &[
+        "bool", "char", "f32", "f64", "i8", "i16", "i32", "i64", "i128", "isize", "str", "u8",
+        "u16", "u32", "u64", "u128", "usize",
+    ],
+    capitalized_types: true,
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    // `'` also starts lifetimes, so char literals are left unstyled.
+    strings: &["\""],
+    multiline_strings: &["\""],
+};
+
+const TOML: LanguageSpec = LanguageSpec {
+    keywords: &[],
+    constants: &["true", "false"],
+    types: &[],
+    capitalized_types: false,
+    line_comments: &["#"],
+    block_comment: None,
+    strings: &["\"\"\"", "'''", "\"", "'"],
+    multiline_strings: &["\"\"\"", "'''"],
+};
+
+const TYPESCRIPT: LanguageSpec = LanguageSpec {
+    keywords: &[
+        "abstract",
+        "as",
+        "async",
+        "await",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "const",
+        "continue",
+        "declare",
+        "default",
+        "delete",
+        "do",
+        "else",
+        "enum",
+        "export",
+        "extends",
+        "finally",
+        "for",
+        "from",
+        "function",
+        "if",
+        "implements",
+        "import",
+        "in",
+        "instanceof",
+        "interface",
+        "keyof",
+        "let",
+        "namespace",
+        "new",
+        "of",
+        "private",
+        "protected",
+        "public",
+        "readonly",
+        "return",
+        "satisfies",
+        "static",
+        "super",
+        "switch",
+        "this",
+        "throw",
+        "try",
+        "type",
+        "typeof",
+        "var",
+        "void",
+        "while",
+        "yield",
+    ],
+    constants: JS_CONSTANTS,
+    types: &[
+        "any", "bigint", "boolean", "never", "number", "object", "string", "symbol", "unknown",
+    ],
+    capitalized_types: true,
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    strings: &["`", "\"", "'"],
+    multiline_strings: &["`"],
+};
+
+const YAML: LanguageSpec = LanguageSpec {
+    keywords: &[],
+    constants: &["true", "false", "null", "yes", "no", "on", "off"],
+    types: &[],
+    capitalized_types: false,
+    line_comments: &["#"],
+    block_comment: None,
+    strings: &["\"", "'"],
+    multiline_strings: &[],
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn resolves_fence_info_strings() {
+        assert_eq!(Language::from_info_string("rust"), Some(Language::Rust));
+        assert_eq!(
+            Language::from_info_string("rust,ignore"),
+            Some(Language::Rust)
+        );
+        assert_eq!(Language::from_info_string("Python"), Some(Language::Python));
+        assert_eq!(
+            Language::from_info_string("ts {.numberLines}"),
+            Some(Language::TypeScript)
+        );
+        assert_eq!(Language::from_info_string("sh"), Some(Language::Bash));
+        assert_eq!(Language::from_info_string("text"), None);
+        assert_eq!(Language::from_info_string(""), None);
+    }
+
+    #[test]
+    fn resolves_paths() {
+        assert_eq!(
+            Language::from_path(Path::new("src/main.rs")),
+            Some(Language::Rust)
+        );
+        assert_eq!(
+            Language::from_path(Path::new("web/App.TSX")),
+            Some(Language::TypeScript)
+        );
+        assert_eq!(
+            Language::from_path(Path::new("Cargo.lock")),
+            Some(Language::Toml)
+        );
+        assert_eq!(Language::from_path(Path::new("README.md")), None);
+        assert_eq!(Language::from_path(Path::new("Makefile")), None);
+    }
+}
@@ -1,6 +1,7 @@
 use ratatui::layout::Rect;
 
 pub mod highlight;
+pub mod language;
 pub mod line_utils;
 pub mod renderable;
 
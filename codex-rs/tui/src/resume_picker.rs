@@ -10,6 +10,9 @@ use codex_core::ConversationsPage;
 use codex_core::Cursor;
 use codex_core::INTERACTIVE_SESSION_SOURCES;
 use codex_core::RolloutRecorder;
+use codex_core::SearchSnippet;
+use codex_core::SessionSearchHit;
+use codex_core::SessionSearchIndex;
 use color_eyre::eyre::Result;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
@@ -20,12 +23,16 @@ use ratatui::layout::Rect;
 use ratatui::style::Stylize as _;
 use ratatui::text::Line;
 use ratatui::text::Span;
+use tokio::sync::OnceCell;
 use tokio::sync::mpsc;
 use tokio_stream::StreamExt;
 use tokio_stream::wrappers::UnboundedReceiverStream;
+use unicode_width::UnicodeWidthChar;
 use unicode_width::UnicodeWidthStr;
 
 use crate::key_hint;
+use crate::render::highlight::highlight_code_to_lines;
+use crate::render::language::Language;
 use crate::text_formatting::truncate_text;
 use crate::tui::FrameRequester;
 use crate::tui::Tui;
@@ -37,6 +44,7 @@ use codex_protocol::protocol::USER_MESSAGE_BEGIN;
 
 const PAGE_SIZE: usize = 25;
 const LOAD_NEAR_THRESHOLD: usize = 5;
+const FULL_TEXT_SEARCH_LIMIT: usize = 100;
 
 #[derive(Debug, Clone)]
 pub enum ResumeSelection {
@@ -55,17 +63,31 @@ struct PageLoadRequest {
 
 type PageLoader = Arc<dyn Fn(PageLoadRequest) + Send + Sync>;
 
+#[derive(Clone)]
+struct FullTextSearchRequest {
+    codex_home: PathBuf,
+    query: String,
+    token: usize,
+}
+
+type FullTextSearcher = Arc<dyn Fn(FullTextSearchRequest) + Send + Sync>;
+
 enum BackgroundEvent {
     PageLoaded {
         request_token: usize,
         search_token: Option<usize>,
         page: std::io::Result<ConversationsPage>,
     },
+    FullTextResults {
+        token: usize,
+        hits: std::io::Result<Vec<SessionSearchHit>>,
+    },
 }
 
 /// Interactive session picker that lists recorded rollout files with simple
 /// search and pagination. Shows the first user input as the preview, relative
-/// time (e.g., "5 seconds ago"), and the absolute path.
+/// time (e.g., "5 seconds ago"), and the absolute path. Typing a query also
+/// searches the full text of every session, showing where each match was found.
 pub async fn run_resume_picker(tui: &mut Tui, codex_home: &Path) -> Result<ResumeSelection> {
     let alt = AltScreenGuard::enter(tui);
     let (bg_tx, bg_rx) = mpsc::unbounded_channel();
@@ -89,10 +111,36 @@ pub async fn run_resume_picker(tui: &mut Tui, codex_home: &Path) -> Result<Resum
         });
     });
 
+    // The search index is loaded on the first query and reused for the
+    // following keystrokes.
+    let search_index: Arc<OnceCell<SessionSearchIndex>> = Arc::new(OnceCell::new());
+    let search_tx = bg_tx.clone();
+    let full_text_searcher: FullTextSearcher = Arc::new(move |request: FullTextSearchRequest| {
+        let tx = search_tx.clone();
+        let search_index = search_index.clone();
+        tokio::spawn(async move {
+            let hits = search_index
+                .get_or_try_init(|| SessionSearchIndex::load(&request.codex_home))
+                .await
+                .map(|index| {
+                    index.search(
+                        &request.query,
+                        FULL_TEXT_SEARCH_LIMIT,
+                        INTERACTIVE_SESSION_SOURCES,
+                    )
+                });
+            let _ = tx.send(BackgroundEvent::FullTextResults {
+                token: request.token,
+                hits,
+            });
+        });
+    });
+
     let mut state = PickerState::new(
         codex_home.to_path_buf(),
         alt.tui.frame_requester(),
         page_loader,
+        full_text_searcher,
     );
     state.load_initial_page().await?;
     state.request_frame();
@@ -166,6 +214,11 @@ struct PickerState {
     next_request_token: usize,
     next_search_token: usize,
     page_loader: PageLoader,
+    full_text_searcher: FullTextSearcher,
+    /// Token of the full-text search for the current query, while it runs.
+    pending_full_text: Option<usize>,
+    /// Full-text matches for the current query.
+    full_text_rows: Vec<Row>,
     view_rows: Option<usize>,
 }
 
@@ -224,10 +277,17 @@ struct Row {
     preview: String,
     created_at: Option<DateTime<Utc>>,
     updated_at: Option<DateTime<Utc>>,
+    /// Where a full-text search matched, shown instead of the preview.
+    snippet: Option<SearchSnippet>,
 }
 
 impl PickerState {
-    fn new(codex_home: PathBuf, requester: FrameRequester, page_loader: PageLoader) -> Self {
+    fn new(
+        codex_home: PathBuf,
+        requester: FrameRequester,
+        page_loader: PageLoader,
+        full_text_searcher: FullTextSearcher,
+    ) -> Self {
         Self {
             codex_home,
             requester,
@@ -247,6 +307,9 @@ impl PickerState {
             next_request_token: 0,
             next_search_token: 0,
             page_loader,
+            full_text_searcher,
+            pending_full_text: None,
+            full_text_rows: Vec::new(),
             view_rows: None,
         }
     }
@@ -363,6 +426,20 @@ impl PickerState {
                 let completed_token = pending.search_token.or(search_token);
                 self.continue_search_if_token_matches(completed_token);
             }
+            BackgroundEvent::FullTextResults { token, hits } => {
+                if self.pending_full_text != Some(token) {
+                    return Ok(());
+                }
+                self.pending_full_text = None;
+                match hits {
+                    Ok(hits) => {
+                        self.full_text_rows = hits.into_iter().map(row_from_hit).collect();
+                    }
+                    // The preview filter still works without the index.
+                    Err(err) => tracing::warn!("full-text session search failed: {err}"),
+                }
+                self.apply_filter();
+            }
         }
         Ok(())
     }
@@ -403,12 +480,19 @@ impl PickerState {
             self.filtered_rows = self.all_rows.clone();
         } else {
             let q = self.query.to_lowercase();
-            self.filtered_rows = self
-                .all_rows
-                .iter()
-                .filter(|r| r.preview.to_lowercase().contains(&q))
-                .cloned()
-                .collect();
+            let full_text_paths: HashSet<&PathBuf> =
+                self.full_text_rows.iter().map(|row| &row.path).collect();
+            let mut rows: Vec<Row> = self.full_text_rows.clone();
+            rows.extend(
+                self.all_rows
+                    .iter()
+                    .filter(|r| {
+                        !full_text_paths.contains(&r.path) && r.preview.to_lowercase().contains(&q)
+                    })
+                    .cloned(),
+            );
+            rows.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+            self.filtered_rows = rows;
         }
         if self.selected >= self.filtered_rows.len() {
             self.selected = self.filtered_rows.len().saturating_sub(1);
@@ -426,6 +510,8 @@ impl PickerState {
         }
         self.query = new_query;
         self.selected = 0;
+        self.full_text_rows.clear();
+        self.start_full_text_search();
         self.apply_filter();
         if self.query.is_empty() {
             self.search_state = SearchState::Idle;
@@ -444,6 +530,20 @@ impl PickerState {
         self.load_more_if_needed(LoadTrigger::Search { token });
     }
 
+    fn start_full_text_search(&mut self) {
+        if self.query.trim().is_empty() {
+            self.pending_full_text = None;
+            return;
+        }
+        let token = self.allocate_request_token();
+        self.pending_full_text = Some(token);
+        (self.full_text_searcher)(FullTextSearchRequest {
+            codex_home: self.codex_home.clone(),
+            query: self.query.clone(),
+            token,
+        });
+    }
+
     fn continue_search_if_needed(&mut self) {
         let Some(token) = self.search_state.active_token() else {
             return;
@@ -596,6 +696,27 @@ fn head_to_row(item: &ConversationItem) -> Row {
         preview,
         created_at,
         updated_at,
+        snippet: None,
+    }
+}
+
+fn row_from_hit(hit: SessionSearchHit) -> Row {
+    let created_at = hit.created_at.as_deref().and_then(parse_timestamp_str);
+    let updated_at = hit
+        .updated_at
+        .as_deref()
+        .and_then(parse_timestamp_str)
+        .or(created_at);
+    let snippet = hit.snippets.into_iter().next();
+    Row {
+        path: hit.path,
+        preview: snippet
+            .as_ref()
+            .map(|snippet| snippet.text.clone())
+            .unwrap_or_default(),
+        created_at,
+        updated_at,
+        snippet,
     }
 }
 
@@ -651,6 +772,98 @@ fn preview_from_head(head: &[serde_json::Value]) -> Option<String> {
         })
 }
 
+/// Styles a preview the way the transcript renders markdown: inline code is
+/// dimmed and fenced code is highlighted for the fence's language.
+fn preview_spans(preview: &str) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    for (i, segment) in preview.split("```").enumerate() {
+        if i > 0 {
+            spans.push("```".dim());
+        }
+        if i % 2 == 0 {
+            push_inline_code_spans(segment, &mut spans);
+            continue;
+        }
+        let info_end = segment.find(char::is_whitespace).unwrap_or(segment.len());
+        let (info, code) = segment.split_at(info_end);
+        if !info.is_empty() {
+            spans.push(info.to_string().dim());
+        }
+        match Language::from_info_string(info) {
+            Some(language) if !code.is_empty() => {
+                for (idx, line) in highlight_code_to_lines(code, language)
+                    .into_iter()
+                    .enumerate()
+                {
+                    if idx > 0 {
+                        spans.push("\n".into());
+                    }
+                    spans.extend(line.spans);
+                }
+                if code.ends_with('\n') {
+                    spans.push("\n".into());
+                }
+            }
+            _ if !code.is_empty() => spans.push(code.to_string().into()),
+            _ => {}
+        }
+    }
+    spans
+}
+
+fn push_inline_code_spans(text: &str, spans: &mut Vec<Span<'static>>) {
+    let parts: Vec<&str> = text.split('`').collect();
+    // An unmatched backtick leaves its trailing text unstyled.
+    let paired = parts.len() - (parts.len() + 1) % 2;
+    for (i, part) in parts.iter().enumerate() {
+        if i >= paired {
+            spans.push(format!("`{part}").into());
+        } else if i % 2 == 1 {
+            spans.push(part.to_string().dim());
+        } else if !part.is_empty() {
+            spans.push(part.to_string().into());
+        }
+    }
+}
+
+/// Renders a full-text search snippet with its matches highlighted, cut to
+/// `max_width` columns.
+fn snippet_spans(snippet: &SearchSnippet, max_width: usize) -> Vec<Span<'static>> {
+    let fits = UnicodeWidthStr::width(snippet.text.as_str()) <= max_width;
+    let mut remaining = if fits {
+        max_width
+    } else {
+        max_width.saturating_sub(1)
+    };
+    let mut spans = Vec::new();
+    for (text, highlighted) in snippet.segments() {
+        let mut visible = String::new();
+        for ch in text.chars() {
+            let width = UnicodeWidthChar::width(ch).unwrap_or(0);
+            if width > remaining {
+                break;
+            }
+            remaining -= width;
+            visible.push(ch);
+        }
+        let cut = visible.len() < text.len();
+        if !visible.is_empty() {
+            spans.push(if highlighted {
+                visible.bold().cyan()
+            } else {
+                visible.into()
+            });
+        }
+        if cut {
+            break;
+        }
+    }
+    if !fits && max_width > 0 {
+        spans.push("…".dim());
+    }
+    spans
+}
+
 fn draw_picker(tui: &mut Tui, state: &PickerState) -> std::io::Result<()> {
     // Render full-screen overlay
     let height = tui.terminal.size()?.height;
@@ -762,7 +975,6 @@ fn render_list(
         if add_leading_gap {
             preview_width = preview_width.saturating_sub(2);
         }
-        let preview = truncate_text(&row.preview, preview_width);
         let mut spans: Vec<Span> = vec![marker];
         if let Some(created) = created_span {
             spans.push(created);
@@ -775,7 +987,10 @@ fn render_list(
         if add_leading_gap {
             spans.push("  ".into());
         }
-        spans.push(preview.into());
+        match &row.snippet {
+            Some(snippet) => spans.extend(snippet_spans(snippet, preview_width)),
+            None => spans.extend(preview_spans(&truncate_text(&row.preview, preview_width))),
+        }
 
         let line: Line = spans.into();
         let rect = Rect::new(area.x, y, area.width, 1);
@@ -793,6 +1008,7 @@ fn render_list(
 fn render_empty_state_line(state: &PickerState) -> Line<'static> {
     if !state.query.is_empty() {
         if state.search_state.is_active()
+            || state.pending_full_text.is_some()
             || (state.pagination.loading.is_pending() && state.pagination.next_cursor.is_some())
         {
             return vec!["Searching…".italic().dim()].into();
@@ -928,11 +1144,14 @@ fn calculate_column_metrics(rows: &[Row]) -> ColumnMetrics {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::render::highlight::HighlightTheme;
     use chrono::Duration;
+    use codex_core::SearchHitKind;
     use crossterm::event::KeyCode;
     use crossterm::event::KeyEvent;
     use crossterm::event::KeyModifiers;
     use insta::assert_snapshot;
+    use ratatui::style::Modifier;
     use serde_json::json;
     use std::future::Future;
     use std::path::PathBuf;
@@ -990,6 +1209,30 @@ mod tests {
             .block_on(future)
     }
 
+    #[test]
+    fn preview_spans_style_inline_and_fenced_code() {
+        let spans = preview_spans("run `cargo test` on ```rust fn main() {}");
+        let text: String = spans.iter().map(|span| span.content.as_ref()).collect();
+        assert_eq!(text, "run cargo test on ```rust fn main() {}");
+
+        let inline = spans
+            .iter()
+            .find(|span| span.content == "cargo test")
+            .expect("inline code span");
+        assert!(inline.style.add_modifier.contains(Modifier::DIM));
+        let keyword = spans
+            .iter()
+            .find(|span| span.content == "fn")
+            .expect("keyword span");
+        assert_eq!(keyword.style, HighlightTheme::current().keyword);
+
+        let unmatched: String = preview_spans("a `b")
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert_eq!(unmatched, "a `b");
+    }
+
     #[test]
     fn preview_uses_first_message_input_text() {
         let head = vec![
@@ -1079,8 +1322,12 @@ mod tests {
         use ratatui::layout::Layout;
 
         let loader: PageLoader = Arc::new(|_| {});
-        let mut state =
-            PickerState::new(PathBuf::from("/tmp"), FrameRequester::test_dummy(), loader);
+        let mut state = PickerState::new(
+            PathBuf::from("/tmp"),
+            FrameRequester::test_dummy(),
+            loader,
+            Arc::new(|_| {}),
+        );
 
         let now = Utc::now();
         let rows = vec![
@@ -1089,18 +1336,21 @@ mod tests {
                 preview: String::from("Fix resume picker timestamps"),
                 created_at: Some(now - Duration::minutes(16)),
                 updated_at: Some(now - Duration::seconds(42)),
+                snippet: None,
             },
             Row {
                 path: PathBuf::from("/tmp/b.jsonl"),
                 preview: String::from("Investigate lazy pagination cap"),
                 created_at: Some(now - Duration::hours(1)),
                 updated_at: Some(now - Duration::minutes(35)),
+                snippet: None,
             },
             Row {
                 path: PathBuf::from("/tmp/c.jsonl"),
                 preview: String::from("Explain the codebase"),
                 created_at: Some(now - Duration::hours(2)),
                 updated_at: Some(now - Duration::hours(2)),
+                snippet: None,
             },
         ];
         state.all_rows = rows.clone();
@@ -1135,8 +1385,12 @@ mod tests {
     #[test]
     fn pageless_scrolling_deduplicates_and_keeps_order() {
         let loader: PageLoader = Arc::new(|_| {});
-        let mut state =
-            PickerState::new(PathBuf::from("/tmp"), FrameRequester::test_dummy(), loader);
+        let mut state = PickerState::new(
+            PathBuf::from("/tmp"),
+            FrameRequester::test_dummy(),
+            loader,
+            Arc::new(|_| {}),
+        );
 
         state.reset_pagination();
         state.ingest_page(page(
@@ -1197,8 +1451,12 @@ mod tests {
             request_sink.lock().unwrap().push(req);
         });
 
-        let mut state =
-            PickerState::new(PathBuf::from("/tmp"), FrameRequester::test_dummy(), loader);
+        let mut state = PickerState::new(
+            PathBuf::from("/tmp"),
+            FrameRequester::test_dummy(),
+            loader,
+            Arc::new(|_| {}),
+        );
         state.reset_pagination();
         state.ingest_page(page(
             vec![
@@ -1222,8 +1480,12 @@ mod tests {
     #[test]
     fn page_navigation_uses_view_rows() {
         let loader: PageLoader = Arc::new(|_| {});
-        let mut state =
-            PickerState::new(PathBuf::from("/tmp"), FrameRequester::test_dummy(), loader);
+        let mut state = PickerState::new(
+            PathBuf::from("/tmp"),
+            FrameRequester::test_dummy(),
+            loader,
+            Arc::new(|_| {}),
+        );
 
         let mut items = Vec::new();
         for idx in 0..20 {
@@ -1266,8 +1528,12 @@ mod tests {
     #[test]
     fn up_at_bottom_does_not_scroll_when_visible() {
         let loader: PageLoader = Arc::new(|_| {});
-        let mut state =
-            PickerState::new(PathBuf::from("/tmp"), FrameRequester::test_dummy(), loader);
+        let mut state = PickerState::new(
+            PathBuf::from("/tmp"),
+            FrameRequester::test_dummy(),
+            loader,
+            Arc::new(|_| {}),
+        );
 
         let mut items = Vec::new();
         for idx in 0..10 {
@@ -1306,8 +1572,12 @@ mod tests {
             request_sink.lock().unwrap().push(req);
         });
 
-        let mut state =
-            PickerState::new(PathBuf::from("/tmp"), FrameRequester::test_dummy(), loader);
+        let mut state = PickerState::new(
+            PathBuf::from("/tmp"),
+            FrameRequester::test_dummy(),
+            loader,
+            Arc::new(|_| {}),
+        );
         state.reset_pagination();
         state.ingest_page(page(
             vec![make_item(
@@ -1404,4 +1674,109 @@ mod tests {
         assert!(!state.search_state.is_active());
         assert!(state.pagination.reached_scan_cap);
     }
+
+    fn search_hit(path: &str, updated_at: &str) -> SessionSearchHit {
+        SessionSearchHit {
+            path: PathBuf::from(path),
+            conversation_id: None,
+            created_at: Some(updated_at.to_string()),
+            updated_at: Some(updated_at.to_string()),
+            cwd: None,
+            source: None,
+            snippets: vec![SearchSnippet {
+                kind: SearchHitKind::Command,
+                text: "cargo test -p parser".to_string(),
+                highlights: vec![14..20],
+            }],
+        }
+    }
+
+    #[test]
+    fn full_text_results_merge_with_preview_matches() {
+        let searches: Arc<Mutex<Vec<FullTextSearchRequest>>> = Arc::new(Mutex::new(Vec::new()));
+        let search_sink = searches.clone();
+        let mut state = PickerState::new(
+            PathBuf::from("/tmp"),
+            FrameRequester::test_dummy(),
+            Arc::new(|_| {}),
+            Arc::new(move |req: FullTextSearchRequest| {
+                search_sink.lock().unwrap().push(req);
+            }),
+        );
+        state.reset_pagination();
+        state.ingest_page(page(
+            vec![
+                make_item("/tmp/new.jsonl", "2025-01-03T00:00:00Z", "parser crash"),
+                make_item("/tmp/old.jsonl", "2025-01-01T00:00:00Z", "unrelated"),
+            ],
+            None,
+            2,
+            false,
+        ));
+
+        state.set_query("pars".to_string());
+        state.set_query("parser".to_string());
+        let requests = searches.lock().unwrap().clone();
+        assert_eq!(
+            requests
+                .iter()
+                .map(|req| req.query.as_str())
+                .collect::<Vec<_>>(),
+            vec!["pars", "parser"]
+        );
+        // Preview matches show while the full-text search runs.
+        assert_eq!(state.filtered_rows.len(), 1);
+
+        // Results for an outdated query are ignored.
+        state
+            .handle_background_event(BackgroundEvent::FullTextResults {
+                token: requests[0].token,
+                hits: Ok(vec![search_hit("/tmp/old.jsonl", "2025-01-01T00:00:00Z")]),
+            })
+            .unwrap();
+        assert_eq!(state.filtered_rows.len(), 1);
+
+        state
+            .handle_background_event(BackgroundEvent::FullTextResults {
+                token: requests[1].token,
+                hits: Ok(vec![search_hit("/tmp/old.jsonl", "2025-01-01T00:00:00Z")]),
+            })
+            .unwrap();
+        let paths: Vec<_> = state
+            .filtered_rows
+            .iter()
+            .map(|row| row.path.clone())
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("/tmp/new.jsonl"),
+                PathBuf::from("/tmp/old.jsonl")
+            ]
+        );
+        assert!(state.filtered_rows[1].snippet.is_some());
+        assert!(state.pending_full_text.is_none());
+    }
+
+    #[test]
+    fn snippet_spans_highlight_matches_and_truncate() {
+        let snippet = search_hit("/tmp/a.jsonl", "2025-01-01T00:00:00Z")
+            .snippets
+            .remove(0);
+
+        let spans = snippet_spans(&snippet, 40);
+        let text: String = spans.iter().map(|span| span.content.as_ref()).collect();
+        assert_eq!(text, "cargo test -p parser");
+        let matched = spans
+            .iter()
+            .find(|span| span.content == "parser")
+            .expect("highlighted span");
+        assert!(matched.style.add_modifier.contains(Modifier::BOLD));
+
+        let text: String = snippet_spans(&snippet, 17)
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert_eq!(text, "cargo test -p pa…");
+    }
 }
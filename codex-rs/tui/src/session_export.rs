@@ -0,0 +1,556 @@
+//! Exports a recorded session as a Markdown, HTML or JSON transcript.
+//!
+//! Transcripts follow the chat view's rendering rules: `bash -lc` wrappers
+//! are stripped from commands, command output keeps only its first and last
+//! lines, and file citations become links for the configured file opener.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use chrono::DateTime;
+use codex_protocol::ConversationId;
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::models::ShellToolCallParams;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::InputMessageKind;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::RolloutLine;
+use pulldown_cmark::Event;
+use pulldown_cmark::Options;
+use pulldown_cmark::Parser;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::exec_cell::OutputLine;
+use crate::exec_cell::truncate_output_lines;
+use crate::exec_command::strip_bash_lc_and_escape;
+use crate::markdown_render::rewrite_file_citations_with_scheme;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscriptFormat {
+    Markdown,
+    Html,
+    Json,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TranscriptOptions {
+    /// Include the model's reasoning summaries.
+    pub include_reasoning: bool,
+    /// URI scheme used to link file citations (e.g. `vscode`). Citations are
+    /// left as-is when unset.
+    pub file_opener_scheme: Option<String>,
+}
+
+/// Renders the contents of a rollout file as a transcript in `format`.
+pub fn export_transcript(
+    rollout: &str,
+    format: TranscriptFormat,
+    options: &TranscriptOptions,
+) -> anyhow::Result<String> {
+    let transcript = Transcript::from_rollout(rollout, options.include_reasoning);
+    Ok(match format {
+        TranscriptFormat::Markdown => transcript.to_markdown(options),
+        TranscriptFormat::Html => render_html(&transcript.to_markdown(options)),
+        TranscriptFormat::Json => serde_json::to_string_pretty(&transcript)? + "\n",
+    })
+}
+
+#[derive(Debug, Default, Serialize)]
+struct Transcript {
+    conversation_id: Option<ConversationId>,
+    started_at: Option<String>,
+    cwd: Option<PathBuf>,
+    model: Option<String>,
+    entries: Vec<TranscriptEntry>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum TranscriptEntry {
+    UserMessage {
+        timestamp: String,
+        message: String,
+    },
+    AgentMessage {
+        timestamp: String,
+        message: String,
+    },
+    Reasoning {
+        timestamp: String,
+        text: String,
+    },
+    Command {
+        timestamp: String,
+        command: String,
+        /// `None` when the session ended before the command finished, or when
+        /// the command never ran (e.g. it was rejected).
+        exit_code: Option<i32>,
+        output: Option<String>,
+    },
+    TurnDiff {
+        timestamp: String,
+        unified_diff: String,
+    },
+}
+
+/// Shell tool output as recorded by core: `{"output": ..., "metadata": {...}}`.
+#[derive(Deserialize)]
+struct ExecOutput {
+    output: String,
+    metadata: ExecMetadata,
+}
+
+#[derive(Deserialize)]
+struct ExecMetadata {
+    exit_code: i32,
+}
+
+impl Transcript {
+    fn from_rollout(rollout: &str, include_reasoning: bool) -> Self {
+        let mut transcript = Transcript::default();
+        // Index into `entries` of each command still waiting for its output.
+        let mut pending_commands: HashMap<String, usize> = HashMap::new();
+
+        for line in rollout.lines() {
+            let Ok(RolloutLine { timestamp, item }) = serde_json::from_str::<RolloutLine>(line)
+            else {
+                continue;
+            };
+            let entry = match item {
+                RolloutItem::SessionMeta(meta_line) if transcript.conversation_id.is_none() => {
+                    let meta = meta_line.meta;
+                    transcript.conversation_id = Some(meta.id);
+                    transcript.started_at = Some(if meta.timestamp.is_empty() {
+                        timestamp
+                    } else {
+                        meta.timestamp
+                    });
+                    transcript.cwd = Some(meta.cwd);
+                    continue;
+                }
+                RolloutItem::TurnContext(context) => {
+                    transcript.model.get_or_insert(context.model);
+                    continue;
+                }
+                RolloutItem::EventMsg(EventMsg::UserMessage(event))
+                    if !matches!(
+                        event.kind,
+                        Some(
+                            InputMessageKind::UserInstructions
+                                | InputMessageKind::EnvironmentContext
+                        )
+                    ) =>
+                {
+                    TranscriptEntry::UserMessage {
+                        timestamp,
+                        message: event.message,
+                    }
+                }
+                RolloutItem::EventMsg(EventMsg::AgentMessage(event)) => {
+                    TranscriptEntry::AgentMessage {
+                        timestamp,
+                        message: event.message,
+                    }
+                }
+                RolloutItem::EventMsg(EventMsg::AgentReasoning(event)) if include_reasoning => {
+                    TranscriptEntry::Reasoning {
+                        timestamp,
+                        text: event.text,
+                    }
+                }
+                RolloutItem::EventMsg(EventMsg::TurnDiff(event)) => TranscriptEntry::TurnDiff {
+                    timestamp,
+                    unified_diff: event.unified_diff,
+                },
+                RolloutItem::ResponseItem(ResponseItem::FunctionCallOutput { call_id, output }) => {
+                    if let Some(idx) = pending_commands.remove(&call_id)
+                        && let Some(TranscriptEntry::Command {
+                            exit_code,
+                            output: command_output,
+                            ..
+                        }) = transcript.entries.get_mut(idx)
+                    {
+                        let (code, text) = match serde_json::from_str::<ExecOutput>(&output.content)
+                        {
+                            Ok(exec) => (Some(exec.metadata.exit_code), exec.output),
+                            // Rejected or failed calls report a plain message.
+                            Err(_) => (None, output.content),
+                        };
+                        *exit_code = code;
+                        *command_output = Some(truncate_output(&text));
+                    }
+                    continue;
+                }
+                RolloutItem::ResponseItem(item) => {
+                    let Some((call_id, command)) = shell_call(item) else {
+                        continue;
+                    };
+                    pending_commands.insert(call_id, transcript.entries.len());
+                    TranscriptEntry::Command {
+                        timestamp,
+                        command: strip_bash_lc_and_escape(&command),
+                        exit_code: None,
+                        output: None,
+                    }
+                }
+                _ => continue,
+            };
+            transcript.entries.push(entry);
+        }
+
+        transcript
+    }
+
+    fn to_markdown(&self, options: &TranscriptOptions) -> String {
+        let scheme = options.file_opener_scheme.as_deref();
+        let cwd = self.cwd.as_deref().unwrap_or_else(|| Path::new("/"));
+
+        let mut md = String::from("# Codex session\n\n");
+        if let Some(id) = &self.conversation_id {
+            md.push_str(&format!("- **Session:** `{id}`\n"));
+        }
+        if let Some(started_at) = &self.started_at {
+            md.push_str(&format!(
+                "- **Started:** {}\n",
+                format_timestamp(started_at)
+            ));
+        }
+        if let Some(cwd) = &self.cwd {
+            md.push_str(&format!("- **Directory:** `{}`\n", cwd.display()));
+        }
+        if let Some(model) = &self.model {
+            md.push_str(&format!("- **Model:** {model}\n"));
+        }
+        md.push('\n');
+
+        let mut turn = 0;
+        for entry in &self.entries {
+            match entry {
+                TranscriptEntry::UserMessage { message, .. } => {
+                    turn += 1;
+                    md.push_str(&format!("## Turn {turn}\n\n**User**\n\n"));
+                    push_block(&mut md, message);
+                }
+                TranscriptEntry::AgentMessage { message, .. } => {
+                    md.push_str("**Codex**\n\n");
+                    push_block(
+                        &mut md,
+                        &rewrite_file_citations_with_scheme(message, scheme, cwd),
+                    );
+                }
+                TranscriptEntry::Reasoning { text, .. } => {
+                    md.push_str("**Reasoning**\n\n");
+                    let quoted: Vec<String> = text
+                        .trim_end()
+                        .lines()
+                        .map(|line| format!("> {line}").trim_end().to_string())
+                        .collect();
+                    push_block(&mut md, &quoted.join("\n"));
+                }
+                TranscriptEntry::Command {
+                    command,
+                    exit_code,
+                    output,
+                    ..
+                } => {
+                    match exit_code {
+                        Some(code) => md.push_str(&format!("**Command** (exit code {code})\n\n")),
+                        None => md.push_str("**Command**\n\n"),
+                    }
+                    push_fenced(&mut md, "bash", command);
+                    if let Some(output) = output.as_deref().filter(|o| !o.trim().is_empty()) {
+                        push_fenced(&mut md, "text", output);
+                    }
+                }
+                TranscriptEntry::TurnDiff { unified_diff, .. } => {
+                    md.push_str("**Changes**\n\n");
+                    push_fenced(&mut md, "diff", unified_diff);
+                }
+            }
+        }
+
+        md
+    }
+}
+
+/// Returns the call id and argv of a shell tool call.
+fn shell_call(item: ResponseItem) -> Option<(String, Vec<String>)> {
+    match item {
+        ResponseItem::LocalShellCall {
+            id,
+            call_id,
+            action: LocalShellAction::Exec(exec),
+            ..
+        } => Some((call_id.or(id)?, exec.command)),
+        ResponseItem::FunctionCall {
+            name,
+            arguments,
+            call_id,
+            ..
+        } if name == "shell" || name == "container.exec" => {
+            let params = serde_json::from_str::<ShellToolCallParams>(&arguments).ok()?;
+            Some((call_id, params.command))
+        }
+        _ => None,
+    }
+}
+
+fn truncate_output(output: &str) -> String {
+    truncate_output_lines(output)
+        .into_iter()
+        .map(|line| match line {
+            OutputLine::Text(text) => text.to_string(),
+            OutputLine::Omitted(omitted) => format!("… +{omitted} lines"),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Shows `2025-05-01T10:00:02.000Z` as `2025-05-01 10:00:02 UTC`.
+fn format_timestamp(timestamp: &str) -> String {
+    DateTime::parse_from_rfc3339(timestamp)
+        .map(|ts| ts.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .unwrap_or_else(|_| timestamp.to_string())
+}
+
+fn push_block(md: &mut String, text: &str) {
+    md.push_str(text.trim_end());
+    md.push_str("\n\n");
+}
+
+/// Wraps `body` in a code fence longer than any run of backticks inside it.
+fn push_fenced(md: &mut String, info: &str, body: &str) {
+    let longest_run = body.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    md.push_str(&format!(
+        "{fence}{info}\n{}\n{fence}\n\n",
+        body.trim_end_matches('\n')
+    ));
+}
+
+const HTML_STYLE: &str = "body { max-width: 56rem; margin: 2rem auto; padding: 0 1rem; \
+font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Helvetica, Arial, sans-serif; \
+line-height: 1.5; } \
+pre { background: #f6f8fa; padding: 0.75rem; overflow-x: auto; } \
+blockquote { color: #57606a; border-left: 0.25rem solid #d0d7de; margin: 0; padding: 0 1rem; } \
+h2 { border-bottom: 1px solid #d0d7de; }";
+
+fn render_html(markdown: &str) -> String {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    let parser = Parser::new_ext(markdown, options).map(|event| match event {
+        // Show raw HTML from messages as text instead of embedding it in the page.
+        Event::Html(html) | Event::InlineHtml(html) => Event::Text(html),
+        event => event,
+    });
+    let mut body = String::new();
+    pulldown_cmark::html::push_html(&mut body, parser);
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+<title>Codex session</title>\n<style>{HTML_STYLE}</style>\n</head>\n<body>\n{body}</body>\n</html>\n"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn rollout(items: &[serde_json::Value]) -> String {
+        items
+            .iter()
+            .map(|payload| {
+                let mut line = payload.clone();
+                line["timestamp"] = json!("2025-05-01T10:00:00.000Z");
+                line.to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn sample_rollout() -> String {
+        let long_output: String = (1..=12).map(|i| format!("line {i}\n")).collect();
+        rollout(&[
+            json!({
+                "type": "session_meta",
+                "payload": {
+                    "id": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+                    "timestamp": "2025-05-01T10:00:00.000Z",
+                    "cwd": "/repo",
+                    "originator": "codex_cli_rs",
+                    "cli_version": "0.0.0",
+                    "instructions": null,
+                }
+            }),
+            json!({
+                "type": "event_msg",
+                "payload": { "type": "user_message", "message": "<environment_context/>", "kind": "environment_context" }
+            }),
+            json!({
+                "type": "event_msg",
+                "payload": { "type": "user_message", "message": "run the tests", "kind": "plain" }
+            }),
+            json!({
+                "type": "turn_context",
+                "payload": {
+                    "cwd": "/repo",
+                    "approval_policy": "never",
+                    "sandbox_policy": { "mode": "read-only" },
+                    "model": "gpt-5",
+                    "summary": "auto",
+                }
+            }),
+            json!({
+                "type": "event_msg",
+                "payload": { "type": "agent_reasoning", "text": "Running the suite." }
+            }),
+            json!({
+                "type": "response_item",
+                "payload": {
+                    "type": "function_call",
+                    "name": "shell",
+                    "arguments": json!({ "command": ["bash", "-lc", "cargo test"] }).to_string(),
+                    "call_id": "call-1",
+                }
+            }),
+            json!({
+                "type": "response_item",
+                "payload": {
+                    "type": "function_call_output",
+                    "call_id": "call-1",
+                    "output": json!({
+                        "output": long_output,
+                        "metadata": { "exit_code": 101, "duration_seconds": 1.5 },
+                    }).to_string(),
+                }
+            }),
+            json!({
+                "type": "event_msg",
+                "payload": { "type": "turn_diff", "unified_diff": "--- a/lib.rs\n+++ b/lib.rs\n@@ -1 +1 @@\n-a\n+b\n" }
+            }),
+            json!({
+                "type": "event_msg",
+                "payload": { "type": "agent_message", "message": "Fixed 【F:src/lib.rs†L1】 <b>done</b>" }
+            }),
+        ])
+    }
+
+    #[test]
+    fn markdown_transcript_follows_chat_rendering_rules() {
+        let options = TranscriptOptions {
+            include_reasoning: false,
+            file_opener_scheme: Some("vscode".to_string()),
+        };
+        let md = export_transcript(&sample_rollout(), TranscriptFormat::Markdown, &options)
+            .expect("export markdown");
+
+        let expected = "\
+# Codex session
+
+- **Session:** `67e55044-10b1-426f-9247-bb680e5fe0c8`
+- **Started:** 2025-05-01 10:00:00 UTC
+- **Directory:** `/repo`
+- **Model:** gpt-5
+
+## Turn 1
+
+**User**
+
+run the tests
+
+**Command** (exit code 101)
+
+```bash
+cargo test
+```
+
+```text
+line 1
+line 2
+line 3
+line 4
+line 5
+… +2 lines
+line 8
+line 9
+line 10
+line 11
+line 12
+```
+
+**Changes**
+
+```diff
+--- a/lib.rs
++++ b/lib.rs
+@@ -1 +1 @@
+-a
++b
+```
+
+**Codex**
+
+Fixed [src/lib.rs:1](vscode://file/repo/src/lib.rs:1)  <b>done</b>
+
+";
+        assert_eq!(md, expected);
+    }
+
+    #[test]
+    fn reasoning_is_included_on_request() {
+        let options = TranscriptOptions {
+            include_reasoning: true,
+            file_opener_scheme: None,
+        };
+        let md = export_transcript(&sample_rollout(), TranscriptFormat::Markdown, &options)
+            .expect("export markdown");
+
+        assert!(md.contains("**Reasoning**\n\n> Running the suite.\n\n"));
+        assert!(md.contains("Fixed 【F:src/lib.rs†L1】"));
+    }
+
+    #[test]
+    fn html_transcript_escapes_raw_html() {
+        let html = export_transcript(
+            &sample_rollout(),
+            TranscriptFormat::Html,
+            &TranscriptOptions::default(),
+        )
+        .expect("export html");
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<h2>Turn 1</h2>"));
+        assert!(html.contains("<code class=\"language-diff\">"));
+        assert!(html.contains("&lt;b&gt;done&lt;/b&gt;"));
+        assert!(!html.contains("<b>done</b>"));
+    }
+
+    #[test]
+    fn json_transcript_lists_entries() {
+        let json = export_transcript(
+            &sample_rollout(),
+            TranscriptFormat::Json,
+            &TranscriptOptions::default(),
+        )
+        .expect("export json");
+        let value: serde_json::Value = serde_json::from_str(&json).expect("valid json");
+
+        assert_eq!(value["model"], json!("gpt-5"));
+        let types: Vec<&str> = value["entries"]
+            .as_array()
+            .expect("entries")
+            .iter()
+            .filter_map(|entry| entry["type"].as_str())
+            .collect();
+        assert_eq!(
+            types,
+            vec!["user_message", "command", "turn_diff", "agent_message"]
+        );
+        assert_eq!(value["entries"][1]["command"], json!("cargo test"));
+        assert_eq!(value["entries"][1]["exit_code"], json!(101));
+    }
+}
@@ -23,6 +23,7 @@ pub enum SlashCommand {
     Mention,
     Status,
     Mcp,
+    McpReload,
     Logout,
     Quit,
     #[cfg(debug_assertions)]
@@ -45,6 +46,7 @@ impl SlashCommand {
             SlashCommand::Model => "choose what model and reasoning effort to use",
             SlashCommand::Approvals => "choose what Codex can do without approval",
             SlashCommand::Mcp => "list configured MCP tools",
+            SlashCommand::McpReload => "reload MCP servers from config.toml",
             SlashCommand::Logout => "log out of Codex",
             #[cfg(debug_assertions)]
             SlashCommand::TestApproval => "test approval request",
@@ -67,6 +69,7 @@ impl SlashCommand {
             | SlashCommand::Model
             | SlashCommand::Approvals
             | SlashCommand::Review
+            | SlashCommand::McpReload
             | SlashCommand::Logout => false,
             SlashCommand::Diff
             | SlashCommand::Mention
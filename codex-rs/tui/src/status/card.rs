@@ -5,6 +5,7 @@ use crate::history_cell::with_border_with_inner_width;
 use crate::version::CODEX_CLI_VERSION;
 use codex_common::create_config_summary_entries;
 use codex_core::config::Config;
+use codex_core::protocol::BudgetRemaining;
 use codex_core::protocol::SandboxPolicy;
 use codex_core::protocol::TokenUsage;
 use codex_protocol::ConversationId;
@@ -20,9 +21,12 @@ use super::format::push_label;
 use super::format::truncate_line_to_width;
 use super::helpers::compose_account_display;
 use super::helpers::compose_agents_summary;
+use super::helpers::compose_budget_summary;
 use super::helpers::compose_model_display;
+use super::helpers::compose_turn_budget_summary;
 use super::helpers::format_directory_display;
 use super::helpers::format_tokens_compact;
+use super::helpers::session_budget_from_limits;
 use super::rate_limits::RateLimitSnapshotDisplay;
 use super::rate_limits::StatusRateLimitData;
 use super::rate_limits::compose_rate_limit_data;
@@ -56,6 +60,8 @@ struct StatusHistoryCell {
     session_id: Option<String>,
     token_usage: StatusTokenUsageData,
     rate_limits: StatusRateLimitData,
+    budget_left: Option<String>,
+    turn_budget: Option<String>,
 }
 
 pub(crate) fn new_status_output(
@@ -64,9 +70,17 @@ pub(crate) fn new_status_output(
     context_usage: Option<&TokenUsage>,
     session_id: &Option<ConversationId>,
     rate_limits: Option<&RateLimitSnapshotDisplay>,
+    budget: Option<&BudgetRemaining>,
 ) -> CompositeHistoryCell {
     let command = PlainHistoryCell::new(vec!["/status".magenta().into()]);
-    let card = StatusHistoryCell::new(config, total_usage, context_usage, session_id, rate_limits);
+    let card = StatusHistoryCell::new(
+        config,
+        total_usage,
+        context_usage,
+        session_id,
+        rate_limits,
+        budget,
+    );
 
     CompositeHistoryCell::new(vec![Box::new(command), Box::new(card)])
 }
@@ -78,6 +92,7 @@ impl StatusHistoryCell {
         context_usage: Option<&TokenUsage>,
         session_id: &Option<ConversationId>,
         rate_limits: Option<&RateLimitSnapshotDisplay>,
+        budget: Option<&BudgetRemaining>,
     ) -> Self {
         let config_entries = create_config_summary_entries(config);
         let (model_name, model_details) = compose_model_display(config, &config_entries);
@@ -109,6 +124,12 @@ impl StatusHistoryCell {
             context_window,
         };
         let rate_limits = compose_rate_limit_data(rate_limits);
+        // Before the first token count arrives nothing has been spent yet.
+        let budget_left = match budget {
+            Some(budget) => compose_budget_summary(budget),
+            None => compose_budget_summary(&session_budget_from_limits(&config.budget)),
+        };
+        let turn_budget = compose_turn_budget_summary(&config.budget);
 
         Self {
             model_name,
@@ -121,6 +142,8 @@ impl StatusHistoryCell {
             session_id,
             token_usage,
             rate_limits,
+            budget_left,
+            turn_budget,
         }
     }
 
@@ -273,6 +296,12 @@ impl HistoryCell for StatusHistoryCell {
         if self.token_usage.context_window.is_some() {
             push_label(&mut labels, &mut seen, "Context window");
         }
+        if self.budget_left.is_some() {
+            push_label(&mut labels, &mut seen, "Budget left");
+        }
+        if self.turn_budget.is_some() {
+            push_label(&mut labels, &mut seen, "Turn budget");
+        }
         self.collect_rate_limit_labels(&mut seen, &mut labels);
 
         let formatter = FieldFormatter::from_labels(labels.iter().map(String::as_str));
@@ -311,6 +340,13 @@ impl HistoryCell for StatusHistoryCell {
             lines.push(formatter.line("Context window", spans));
         }
 
+        if let Some(budget_left) = self.budget_left.as_ref() {
+            lines.push(formatter.line("Budget left", vec![Span::from(budget_left.clone())]));
+        }
+        if let Some(turn_budget) = self.turn_budget.as_ref() {
+            lines.push(formatter.line("Turn budget", vec![Span::from(turn_budget.clone()).dim()]));
+        }
+
         lines.extend(self.rate_limit_lines(available_inner_width, &formatter));
 
         let content_width = lines.iter().map(line_display_width).max().unwrap_or(0);
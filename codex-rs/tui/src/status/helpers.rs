@@ -5,7 +5,9 @@ use chrono::Local;
 use codex_core::auth::get_auth_file;
 use codex_core::auth::try_read_auth_json;
 use codex_core::config::Config;
+use codex_core::config_types::BudgetLimits;
 use codex_core::project_doc::discover_project_doc_paths;
+use codex_core::protocol::BudgetRemaining;
 use std::path::Path;
 use unicode_width::UnicodeWidthStr;
 
@@ -142,6 +144,77 @@ pub(crate) fn format_tokens_compact(value: u64) -> String {
     format!("{formatted}{suffix}")
 }
 
+/// Summarizes what is left of the session budget, e.g.
+/// `40K tokens, 3 turns, $1.25`. Returns `None` when nothing is limited.
+pub(crate) fn compose_budget_summary(remaining: &BudgetRemaining) -> Option<String> {
+    format_budget_parts(
+        remaining.tokens,
+        remaining.turns,
+        remaining.tool_calls,
+        remaining.wall_clock_secs,
+        remaining.cost_usd,
+    )
+}
+
+/// Summarizes the per-turn limits from `[budget]`.
+pub(crate) fn compose_turn_budget_summary(limits: &BudgetLimits) -> Option<String> {
+    format_budget_parts(
+        limits.turn_tokens,
+        None,
+        limits.turn_tool_calls,
+        limits.turn_wall_clock_secs,
+        limits.turn_cost_usd,
+    )
+}
+
+/// The session budget before anything has been spent.
+pub(crate) fn session_budget_from_limits(limits: &BudgetLimits) -> BudgetRemaining {
+    BudgetRemaining {
+        tokens: limits.session_tokens,
+        turns: limits.session_turns,
+        tool_calls: limits.session_tool_calls,
+        wall_clock_secs: limits.session_wall_clock_secs,
+        cost_usd: limits.session_cost_usd,
+    }
+}
+
+fn format_budget_parts(
+    tokens: Option<u64>,
+    turns: Option<u64>,
+    tool_calls: Option<u64>,
+    wall_clock_secs: Option<u64>,
+    cost_usd: Option<f64>,
+) -> Option<String> {
+    let mut parts = Vec::new();
+    if let Some(tokens) = tokens {
+        parts.push(format!("{} tokens", format_tokens_compact(tokens)));
+    }
+    if let Some(turns) = turns {
+        parts.push(format!("{turns} turns"));
+    }
+    if let Some(tool_calls) = tool_calls {
+        parts.push(format!("{tool_calls} tool calls"));
+    }
+    if let Some(secs) = wall_clock_secs {
+        parts.push(format_duration_compact(secs));
+    }
+    if let Some(cost) = cost_usd {
+        parts.push(format!("${cost:.2}"));
+    }
+    (!parts.is_empty()).then(|| parts.join(", "))
+}
+
+fn format_duration_compact(secs: u64) -> String {
+    let (hours, minutes, seconds) = (secs / 3600, secs % 3600 / 60, secs % 60);
+    if hours > 0 {
+        format!("{hours}h {minutes:02}m")
+    } else if minutes > 0 {
+        format!("{minutes}m {seconds:02}s")
+    } else {
+        format!("{seconds}s")
+    }
+}
+
 pub(crate) fn format_directory_display(directory: &Path, max_width: Option<usize>) -> String {
     let formatted = if let Some(rel) = relativize_to_home(directory) {
         if rel.as_os_str().is_empty() {
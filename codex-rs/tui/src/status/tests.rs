@@ -5,6 +5,8 @@ use chrono::TimeZone;
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
 use codex_core::config::ConfigToml;
+use codex_core::config_types::BudgetLimits;
+use codex_core::protocol::BudgetRemaining;
 use codex_core::protocol::RateLimitSnapshot;
 use codex_core::protocol::RateLimitWindow;
 use codex_core::protocol::SandboxPolicy;
@@ -71,6 +73,7 @@ fn status_snapshot_includes_reasoning_details() {
     config.sandbox_policy = SandboxPolicy::WorkspaceWrite {
         writable_roots: Vec::new(),
         network_access: false,
+        network_allowlist: vec![],
         exclude_tmpdir_env_var: false,
         exclude_slash_tmp: false,
     };
@@ -103,7 +106,14 @@ fn status_snapshot_includes_reasoning_details() {
         .expect("timestamp");
     let rate_display = rate_limit_snapshot_display(&snapshot, captured_at);
 
-    let composite = new_status_output(&config, &usage, Some(&usage), &None, Some(&rate_display));
+    let composite = new_status_output(
+        &config,
+        &usage,
+        Some(&usage),
+        &None,
+        Some(&rate_display),
+        None,
+    );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
         for line in &mut rendered_lines {
@@ -144,7 +154,14 @@ fn status_snapshot_includes_monthly_limit() {
         .expect("timestamp");
     let rate_display = rate_limit_snapshot_display(&snapshot, captured_at);
 
-    let composite = new_status_output(&config, &usage, Some(&usage), &None, Some(&rate_display));
+    let composite = new_status_output(
+        &config,
+        &usage,
+        Some(&usage),
+        &None,
+        Some(&rate_display),
+        None,
+    );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
         for line in &mut rendered_lines {
@@ -170,7 +187,7 @@ fn status_card_token_usage_excludes_cached_tokens() {
         total_tokens: 2_100,
     };
 
-    let composite = new_status_output(&config, &usage, Some(&usage), &None, None);
+    let composite = new_status_output(&config, &usage, Some(&usage), &None, None, None);
     let rendered = render_lines(&composite.display_lines(120));
 
     assert!(
@@ -211,7 +228,14 @@ fn status_snapshot_truncates_in_narrow_terminal() {
         .expect("timestamp");
     let rate_display = rate_limit_snapshot_display(&snapshot, captured_at);
 
-    let composite = new_status_output(&config, &usage, Some(&usage), &None, Some(&rate_display));
+    let composite = new_status_output(
+        &config,
+        &usage,
+        Some(&usage),
+        &None,
+        Some(&rate_display),
+        None,
+    );
     let mut rendered_lines = render_lines(&composite.display_lines(46));
     if cfg!(windows) {
         for line in &mut rendered_lines {
@@ -238,7 +262,7 @@ fn status_snapshot_shows_missing_limits_message() {
         total_tokens: 750,
     };
 
-    let composite = new_status_output(&config, &usage, Some(&usage), &None, None);
+    let composite = new_status_output(&config, &usage, Some(&usage), &None, None, None);
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
         for line in &mut rendered_lines {
@@ -274,7 +298,14 @@ fn status_snapshot_shows_empty_limits_message() {
         .expect("timestamp");
     let rate_display = rate_limit_snapshot_display(&snapshot, captured_at);
 
-    let composite = new_status_output(&config, &usage, Some(&usage), &None, Some(&rate_display));
+    let composite = new_status_output(
+        &config,
+        &usage,
+        Some(&usage),
+        &None,
+        Some(&rate_display),
+        None,
+    );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
         for line in &mut rendered_lines {
@@ -306,7 +337,7 @@ fn status_context_window_uses_last_usage() {
         total_tokens: 13_679,
     };
 
-    let composite = new_status_output(&config, &total_usage, Some(&last_usage), &None, None);
+    let composite = new_status_output(&config, &total_usage, Some(&last_usage), &None, None, None);
     let rendered_lines = render_lines(&composite.display_lines(80));
     let context_line = rendered_lines
         .into_iter()
@@ -322,3 +353,42 @@ fn status_context_window_uses_last_usage() {
         "context line should not use total aggregated tokens, got: {context_line}"
     );
 }
+
+#[test]
+fn status_card_shows_remaining_budget() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home);
+    config.budget = BudgetLimits {
+        session_tokens: Some(100_000),
+        session_cost_usd: Some(2.0),
+        turn_tool_calls: Some(20),
+        turn_wall_clock_secs: Some(600),
+        ..Default::default()
+    };
+    let usage = TokenUsage::default();
+
+    let render = |budget: Option<&BudgetRemaining>| {
+        let composite = new_status_output(&config, &usage, Some(&usage), &None, None, budget);
+        render_lines(&composite.display_lines(120))
+    };
+    let find = |lines: &[String], label: &str| {
+        lines
+            .iter()
+            .find(|line| line.contains(label))
+            .cloned()
+            .unwrap_or_else(|| panic!("expected {label} line, got: {lines:?}"))
+    };
+
+    // Before the first token count, the whole session budget is left.
+    let rendered = render(None);
+    assert!(find(&rendered, "Budget left").contains("100K tokens, $2.00"));
+    assert!(find(&rendered, "Turn budget").contains("20 tool calls, 10m 00s"));
+
+    let remaining = BudgetRemaining {
+        tokens: Some(42_500),
+        cost_usd: Some(0.75),
+        ..Default::default()
+    };
+    let rendered = render(Some(&remaining));
+    assert!(find(&rendered, "Budget left").contains("42.5K tokens, $0.75"));
+}
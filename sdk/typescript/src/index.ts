@@ -21,6 +21,9 @@ export type {
   WebSearchItem,
   TodoListItem,
   ErrorItem,
+  AgentCommandResultItem,
+  AgentFinding,
+  FindingSeverity,
 } from "./items";
 
 export { Thread } from "./thread";
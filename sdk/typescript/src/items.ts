@@ -102,6 +102,30 @@ export type TodoListItem = {
   items: TodoItem[];
 };
 
+/** The severity of an agent finding. */
+export type FindingSeverity = "error" | "warning" | "info";
+
+/** A code review finding reported by an agent command. */
+export type AgentFinding = {
+  severity: FindingSeverity;
+  category: string;
+  message: string;
+  path?: string;
+  line?: number;
+};
+
+/**
+ * Result of an agent-backed slash command such as `/review`, run without a model turn.
+ * `output` holds the full result rendered in the requested report format.
+ */
+export type AgentCommandResultItem = {
+  id: string;
+  type: "agent_command_result";
+  command: string;
+  findings: AgentFinding[];
+  output: string;
+};
+
 export type SessionItem = {
   id: string;
   type: "session";
@@ -117,4 +141,5 @@ export type ThreadItem =
   | McpToolCallItem
   | WebSearchItem
   | TodoListItem
-  | ErrorItem;
+  | ErrorItem
+  | AgentCommandResultItem;